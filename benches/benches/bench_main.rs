@@ -53,6 +53,7 @@ mod benchmarks {
     pub mod brainfuck;
     pub mod external_functions;
     pub mod fib;
+    pub mod optimize;
 }
 
 criterion::criterion_main! {
@@ -63,4 +64,5 @@ criterion::criterion_main! {
     benchmarks::brainfuck::benches,
     benchmarks::fib::benches,
     benchmarks::external_functions::benches,
+    benchmarks::optimize::benches,
 }
@@ -0,0 +1,96 @@
+//! Compare the same programs compiled with and without optimizations.
+
+use std::sync::Arc;
+
+use criterion::Criterion;
+use rune::{Context, Hash, Options, Vm};
+
+criterion::criterion_group!(benches, match_integers, constant_arithmetic);
+
+fn vm(source: &str, level: u8) -> Vm {
+    let context = Context::with_default_modules().expect("Failed to build context");
+    let mut sources = crate::sources(source);
+
+    let mut options = Options::default();
+    options.opt_level(level);
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()
+        .expect("Program to compile successfully");
+
+    let context = context.runtime().expect("Failed to build runtime context");
+    Vm::new(Arc::new(context), Arc::new(unit))
+}
+
+fn match_integers(b: &mut Criterion) {
+    let source = stringify! {
+        fn classify(n) {
+            match n {
+                0 => 1,
+                1 => 2,
+                2 => 3,
+                3 => 5,
+                _ => 0,
+            }
+        }
+
+        pub fn main(n) {
+            let total = 0;
+            let i = 0;
+
+            while i < n {
+                total += classify(i % 5);
+                i += 1;
+            }
+
+            total
+        }
+    };
+
+    let mut group = b.benchmark_group("optimize_match_integers");
+    let entry = Hash::type_hash(["main"]);
+
+    for level in [0, 2] {
+        let mut vm = vm(source, level);
+
+        group.bench_function(format!("opt_level_{level}"), |b| {
+            b.iter(|| vm.call(entry, (1000,)).expect("failed call"));
+        });
+    }
+
+    group.finish();
+}
+
+fn constant_arithmetic(b: &mut Criterion) {
+    let source = stringify! {
+        pub fn main(n) {
+            let total = 0;
+
+            for i in 0..n {
+                let scale = 60 * 60 * 24;
+                let offset = (1 << 10) - 24;
+
+                if scale > 0 && true {
+                    total += i * scale + offset;
+                }
+            }
+
+            total
+        }
+    };
+
+    let mut group = b.benchmark_group("optimize_constant_arithmetic");
+    let entry = Hash::type_hash(["main"]);
+
+    for level in [0, 2] {
+        let mut vm = vm(source, level);
+
+        group.bench_function(format!("opt_level_{level}"), |b| {
+            b.iter(|| vm.call(entry, (1000,)).expect("failed call"));
+        });
+    }
+
+    group.finish();
+}
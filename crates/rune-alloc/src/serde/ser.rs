@@ -2,6 +2,7 @@ use serde::ser::{Serialize, Serializer};
 
 use crate::borrow::{Cow, TryToOwned};
 use crate::boxed::Box;
+use crate::btree::map::BTreeMap;
 use crate::btree::set::BTreeSet;
 use crate::vec::Vec;
 
@@ -45,3 +46,17 @@ macro_rules! seq_impl {
 
 seq_impl!(BTreeSet<T: Ord>);
 seq_impl!(Vec<T>);
+
+impl<K, V> Serialize for BTreeMap<K, V>
+where
+    K: Serialize,
+    V: Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(self)
+    }
+}
@@ -409,6 +409,36 @@ define! {
         ],
     };
 
+    /// The function to implement for the negation operation.
+    pub const [NEG, NEG_HASH]: Protocol = Protocol {
+        name: "neg",
+        hash: 0x9ffb490461f68150u64,
+        repr: Some("let output = -$value"),
+        doc: [
+            "Allows the `-` operator to apply to values of this type."
+        ],
+    };
+
+    /// The function to implement for the not operation.
+    pub const [NOT, NOT_HASH]: Protocol = Protocol {
+        name: "not",
+        hash: 0xea93fbfca4da3b6bu64,
+        repr: Some("let output = !$value"),
+        doc: [
+            "Allows the `!` operator to apply to values of this type."
+        ],
+    };
+
+    /// The function to implement for calling a value like a function.
+    pub const [CALL, CALL_HASH]: Protocol = Protocol {
+        name: "call",
+        hash: 0x3c5fd7b2e1a60934u64,
+        repr: Some("let output = $value(a, b)"),
+        doc: [
+            "Allows values of this type to be called like a function, where the current type is passed as the first argument."
+        ],
+    };
+
     /// Protocol function used by template strings.
     pub const [STRING_DISPLAY, STRING_DISPLAY_HASH]: Protocol = Protocol {
         name: "string_display",
@@ -26,6 +26,8 @@ pub(crate) struct FunctionAttrs {
     self_type: Option<syn::PathSegment>,
     /// Defines a fallible function which can make use of the `?` operator.
     vm_result: bool,
+    /// The function can be called in constant contexts.
+    is_const: bool,
 }
 
 impl FunctionAttrs {
@@ -34,6 +36,16 @@ impl FunctionAttrs {
         let mut out = Self::default();
 
         while !input.is_empty() {
+            if input.parse::<Option<Token![const]>>()?.is_some() {
+                out.is_const = true;
+
+                if input.parse::<Option<Token![,]>>()?.is_none() {
+                    break;
+                }
+
+                continue;
+            }
+
             let ident = input.parse::<syn::Ident>()?;
 
             if ident == "instance" {
@@ -352,6 +364,7 @@ impl Function {
 
         let arguments = &self.arguments;
         let docs = &self.docs;
+        let is_const = attrs.is_const;
 
         let build_with = if instance {
             None
@@ -375,6 +388,7 @@ impl Function {
                     deprecated: None,
                     docs: &#docs[..],
                     arguments: &#arguments[..],
+                    is_const: #is_const,
                 })
             }
         });
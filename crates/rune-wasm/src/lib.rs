@@ -35,7 +35,7 @@ use anyhow::{Context as _, Result};
 use gloo_utils::format::JsValueSerdeExt;
 use rune::ast::Spanned;
 use rune::compile::LinkerError;
-use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use rune::diagnostics::{Diagnostic, FatalDiagnosticKind, LintLevel};
 use rune::modules::capture_io::CaptureIo;
 use rune::runtime::{budget, Value, VmResult};
 use rune::{Context, ContextError, Options};
@@ -251,8 +251,13 @@ async fn inner_compile(
                         WasmPosition::from(source.pos_to_utf8_linecol(span.start.into_usize()));
                     let end = WasmPosition::from(source.pos_to_utf8_linecol(span.end.into_usize()));
 
+                    let kind = match warning.level() {
+                        LintLevel::Deny => WasmDiagnosticKind::Error,
+                        _ => WasmDiagnosticKind::Warning,
+                    };
+
                     diagnostics.push(WasmDiagnostic {
-                        kind: WasmDiagnosticKind::Warning,
+                        kind,
                         start,
                         end,
                         message: warning.to_string(),
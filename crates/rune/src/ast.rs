@@ -139,6 +139,7 @@ mod item_fn;
 mod item_impl;
 mod item_mod;
 mod item_struct;
+mod item_trait;
 mod item_use;
 mod label;
 mod lit;
@@ -158,6 +159,7 @@ mod span;
 pub(crate) mod spanned;
 mod stmt;
 mod token;
+mod ty;
 pub(super) mod unescape;
 mod utils;
 mod vis;
@@ -196,7 +198,7 @@ pub use self::expr_while::ExprWhile;
 pub use self::expr_yield::ExprYield;
 pub use self::fields::Fields;
 pub use self::file::{File, Shebang};
-pub use self::fn_arg::FnArg;
+pub use self::fn_arg::{FnArg, PatType};
 pub use self::grouped::{AngleBracketed, Braced, Bracketed, Parenthesized};
 pub use self::ident::Ident;
 pub use self::item::Item;
@@ -206,6 +208,7 @@ pub use self::item_fn::ItemFn;
 pub use self::item_impl::ItemImpl;
 pub use self::item_mod::{ItemInlineBody, ItemMod, ItemModBody};
 pub use self::item_struct::{Field, ItemStruct};
+pub use self::item_trait::{ItemTrait, TraitFn};
 pub use self::item_use::{ItemUse, ItemUsePath, ItemUseSegment};
 pub use self::label::Label;
 pub use self::lit::Lit;
@@ -230,6 +233,7 @@ pub use self::token::{
     BuiltIn, CopySource, Delimiter, LitSource, Number, NumberBase, NumberSource, NumberSuffix,
     NumberText, NumberValue, StrSource, StrText, Token,
 };
+pub use self::ty::Type;
 pub use self::vis::Visibility;
 
 macro_rules! decl_tokens {
@@ -302,3 +306,27 @@ pub struct IsNot {
     /// The `not` token.
     pub not: Not,
 }
+
+/// The composite `is impl` operation.
+#[derive(Debug, TryClone, Clone, Copy, PartialEq, Eq, Hash, ToTokens, Spanned)]
+#[try_clone(copy)]
+#[non_exhaustive]
+pub struct IsImpl {
+    /// The `is` token.
+    pub is: Is,
+    /// The `impl` token.
+    pub impl_: Impl,
+}
+
+/// The composite `is not impl` operation.
+#[derive(Debug, TryClone, Clone, Copy, PartialEq, Eq, Hash, ToTokens, Spanned)]
+#[try_clone(copy)]
+#[non_exhaustive]
+pub struct IsNotImpl {
+    /// The `is` token.
+    pub is: Is,
+    /// The `not` token.
+    pub not: Not,
+    /// The `impl` token.
+    pub impl_: Impl,
+}
@@ -8,6 +8,12 @@ fn ast_parse() {
 
     rt::<ast::ExprBinary>("42 + b");
     rt::<ast::ExprBinary>("b << 10");
+
+    let expr = rt::<ast::ExprBinary>("a is impl Foo");
+    assert!(matches!(expr.op, ast::BinOp::IsImpl(..)));
+
+    let expr = rt::<ast::ExprBinary>("a is not impl Foo");
+    assert!(matches!(expr.op, ast::BinOp::IsNotImpl(..)));
 }
 
 /// A binary expression.
@@ -62,6 +68,10 @@ pub enum BinOp {
     Is(T![is]),
     /// Negated instance of test `a is not b`.
     IsNot(T![is not]),
+    /// Trait implementation test `a is impl b`.
+    IsImpl(T![is impl]),
+    /// Negated trait implementation test `a is not impl b`.
+    IsNotImpl(T![is not impl]),
     /// Lazy and operator `&&`.
     And(T![&&]),
     /// Lazy or operator `||`.
@@ -133,7 +143,7 @@ impl BinOp {
     pub(super) fn precedence(&self) -> usize {
         // NB: Rules from: https://doc.rust-lang.org/reference/expressions.html#expression-precedence
         match self {
-            Self::Is(..) | Self::IsNot(..) => 13,
+            Self::Is(..) | Self::IsNot(..) | Self::IsImpl(..) | Self::IsNotImpl(..) => 13,
             Self::As(..) => 13,
             Self::Mul(..) | Self::Div(..) | Self::Rem(..) => 11,
             Self::Add(..) | Self::Sub(..) => 10,
@@ -196,9 +206,22 @@ impl BinOp {
                 let ast::Token { kind, span } = p.tok_at(1);
 
                 match kind {
-                    K![not] => Self::IsNot(ast::IsNot {
+                    K![not] => {
+                        let not = ast::Not { span };
+                        let ast::Token { kind, span } = p.tok_at(2);
+
+                        match kind {
+                            K![impl] => Self::IsNotImpl(ast::IsNotImpl {
+                                is,
+                                not,
+                                impl_: ast::Impl { span },
+                            }),
+                            _ => Self::IsNot(ast::IsNot { is, not }),
+                        }
+                    }
+                    K![impl] => Self::IsImpl(ast::IsImpl {
                         is,
-                        not: ast::Not { span },
+                        impl_: ast::Impl { span },
                     }),
                     _ => Self::Is(is),
                 }
@@ -231,7 +254,12 @@ impl BinOp {
     /// Get how many tokens to advance for this operator.
     pub(crate) fn advance(&self, p: &mut Parser<'_>) -> Result<()> {
         match self {
-            Self::IsNot(..) => {
+            Self::IsNot(..) | Self::IsImpl(..) => {
+                p.next()?;
+                p.next()?;
+            }
+            Self::IsNotImpl(..) => {
+                p.next()?;
                 p.next()?;
                 p.next()?;
             }
@@ -261,6 +289,8 @@ impl fmt::Display for BinOp {
             Self::As(..) => write!(f, "as"),
             Self::Is(..) => write!(f, "is"),
             Self::IsNot(..) => write!(f, "is not"),
+            Self::IsImpl(..) => write!(f, "is impl"),
+            Self::IsNotImpl(..) => write!(f, "is not impl"),
             Self::And(..) => write!(f, "&&"),
             Self::Or(..) => write!(f, "||"),
             Self::Shl(..) => write!(f, "<<"),
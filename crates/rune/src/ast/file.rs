@@ -50,6 +50,17 @@ fn ast_parse() {
     );
 
     assert!(file.shebang.is_some());
+
+    let file = crate::testing::rt_with::<ast::File>(
+        r#"#![allow(unused_functions)]
+
+        fn main() {}
+        "#,
+        true,
+    );
+
+    assert!(file.shebang.is_none());
+    assert_eq!(file.attributes.len(), 1);
 }
 
 /// A rune file.
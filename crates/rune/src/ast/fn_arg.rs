@@ -7,6 +7,12 @@ fn ast_parse() {
     rt::<ast::FnArg>("self");
     rt::<ast::FnArg>("_");
     rt::<ast::FnArg>("abc");
+
+    let arg = rt::<ast::FnArg>("abc: i64");
+    assert!(matches!(arg, ast::FnArg::Typed(..)));
+
+    let arg = rt::<ast::FnArg>("(a, b): (i64, String)");
+    assert!(matches!(arg, ast::FnArg::Typed(..)));
 }
 
 /// A single argument in a closure.
@@ -17,13 +23,51 @@ pub enum FnArg {
     SelfValue(T![self]),
     /// Function argument is a pattern binding.
     Pat(ast::Pat),
+    /// Function argument is a pattern binding with a type annotation.
+    Typed(ast::PatType),
+}
+
+impl FnArg {
+    /// Get the type annotation of the argument, if it has one.
+    #[cfg(feature = "doc")]
+    pub(crate) fn ty(&self) -> Option<&ast::Type> {
+        match self {
+            Self::Typed(typed) => Some(&typed.ty),
+            _ => None,
+        }
+    }
 }
 
 impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self> {
-        Ok(match p.nth(0)? {
-            K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
-        })
+        if let K![self] = p.nth(0)? {
+            return Ok(Self::SelfValue(p.parse()?));
+        }
+
+        let pat = ast::Pat::parse_without_binding(p)?;
+
+        if !p.peek::<T![:]>()? {
+            return Ok(Self::Pat(pat));
+        }
+
+        Ok(Self::Typed(ast::PatType {
+            pat,
+            colon: p.parse()?,
+            ty: p.parse()?,
+        }))
     }
 }
+
+/// A pattern with a type annotation.
+///
+/// * `<pat>: <type>`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct PatType {
+    /// The pattern being annotated.
+    pub pat: ast::Pat,
+    /// The colon separating the pattern from its type.
+    pub colon: T![:],
+    /// The type of the pattern.
+    pub ty: ast::Type,
+}
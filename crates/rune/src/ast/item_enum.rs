@@ -51,6 +51,6 @@ pub struct ItemVariant {
     /// The name of the variant.
     pub name: ast::Ident,
     /// The body of the variant.
-    #[rune(iter)]
+    #[rune(option)]
     pub body: ast::Fields,
 }
@@ -45,7 +45,7 @@ pub struct ItemStruct {
     /// The identifier of the struct declaration.
     pub ident: ast::Ident,
     /// The body of the struct.
-    #[rune(iter)]
+    #[rune(option)]
     pub body: ast::Fields,
 }
 
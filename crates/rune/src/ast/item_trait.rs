@@ -0,0 +1,174 @@
+use crate::ast::prelude::*;
+
+#[test]
+fn ast_parse() {
+    use crate::testing::rt;
+
+    rt::<ast::ItemTrait>("trait Foo {}");
+    rt::<ast::ItemTrait>("pub trait Foo { fn test(self); }");
+    rt::<ast::ItemTrait>("trait Foo { fn test(self) { } fn other(self, a, b); }");
+    rt::<ast::ItemTrait>("#[xyz] trait Foo { #[doc = \"Hello\"] async fn test(self) { } }");
+    rt::<ast::ItemTrait>("trait Foo { fn test(self, a: i64) -> String; }");
+
+    let item = rt::<ast::TraitFn>("fn test(self, a);");
+    assert!(item.body.is_none());
+    assert!(item.semi.is_some());
+
+    let item = rt::<ast::TraitFn>("fn test(self, a) { a }");
+    assert!(item.body.is_some());
+    assert!(item.semi.is_none());
+}
+
+/// A trait item.
+///
+/// * `trait <name> { <fn>* }`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ItemTrait {
+    /// The attributes of the `trait` item.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the `trait` item.
+    #[rune(option)]
+    pub visibility: ast::Visibility,
+    /// The `trait` keyword.
+    pub trait_token: T![trait],
+    /// The name of the trait.
+    pub name: ast::Ident,
+    /// The open brace.
+    pub open: T!['{'],
+    /// The functions declared by the trait.
+    #[rune(iter)]
+    pub functions: Vec<ast::TraitFn>,
+    /// The close brace.
+    pub close: T!['}'],
+}
+
+impl ItemTrait {
+    /// Parse a `trait` item with the given meta.
+    pub(crate) fn parse_with_meta(
+        parser: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        visibility: ast::Visibility,
+    ) -> Result<Self> {
+        let trait_token = parser.parse()?;
+        let name = parser.parse()?;
+        let open = parser.parse()?;
+
+        let mut functions = Vec::new();
+
+        while !parser.peek::<ast::CloseBrace>()? {
+            functions.try_push(parser.parse()?)?;
+        }
+
+        let close = parser.parse()?;
+
+        Ok(Self {
+            attributes,
+            visibility,
+            trait_token,
+            name,
+            open,
+            functions,
+            close,
+        })
+    }
+}
+
+item_parse!(Trait, ItemTrait, "trait item");
+
+/// A function declared in a trait.
+///
+/// Functions which have a body provide a default implementation, functions
+/// without one are terminated by a semi-colon and must be provided by every
+/// implementation of the trait.
+///
+/// * `fn <name>(<args>);`.
+/// * `fn <name>(<args>) <body>`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct TraitFn {
+    /// The attributes for the function.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The optional `async` keyword.
+    #[rune(iter)]
+    pub async_token: Option<T![async]>,
+    /// The `fn` token.
+    pub fn_token: T![fn],
+    /// The name of the function.
+    pub name: ast::Ident,
+    /// The arguments of the function.
+    pub args: ast::Parenthesized<ast::FnArg, T![,]>,
+    /// The optional return type of the function.
+    #[rune(iter)]
+    pub output: Option<(T![->], ast::Type)>,
+    /// The default implementation of the function.
+    #[rune(iter)]
+    pub body: Option<ast::Block>,
+    /// The semi-colon terminating a function without a default
+    /// implementation.
+    #[rune(iter)]
+    pub semi: Option<T![;]>,
+}
+
+impl TraitFn {
+    /// Get the descriptive span of this function, e.g. `fn foo()` instead of
+    /// the span for the whole function declaration, body included.
+    pub(crate) fn descriptive_span(&self) -> Span {
+        if let Some(async_token) = &self.async_token {
+            async_token.span().join(self.args.span())
+        } else {
+            self.fn_token.span().join(self.args.span())
+        }
+    }
+
+    /// Convert the default implementation of the function into a function
+    /// item, if it has one.
+    pub(crate) fn to_item_fn(&self) -> alloc::Result<Option<ast::ItemFn>> {
+        let Some(body) = &self.body else {
+            return Ok(None);
+        };
+
+        Ok(Some(ast::ItemFn {
+            id: Default::default(),
+            attributes: self.attributes.try_clone()?,
+            visibility: ast::Visibility::Inherited,
+            const_token: None,
+            async_token: self.async_token,
+            fn_token: self.fn_token,
+            name: self.name,
+            args: self.args.try_clone()?,
+            output: self.output.try_clone()?,
+            body: body.try_clone()?,
+        }))
+    }
+}
+
+impl Parse for TraitFn {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        let attributes = p.parse()?;
+        let async_token = p.parse()?;
+        let fn_token = p.parse()?;
+        let name = p.parse()?;
+        let args = p.parse()?;
+        let output = p.parse()?;
+
+        let (body, semi) = if p.peek::<T![;]>()? {
+            (None, Some(p.parse()?))
+        } else {
+            (Some(p.parse()?), None)
+        };
+
+        Ok(Self {
+            attributes,
+            async_token,
+            fn_token,
+            name,
+            args,
+            output,
+            body,
+            semi,
+        })
+    }
+}
@@ -82,6 +82,9 @@ impl<'a> Resolve<'a> for LitNumber {
             "i64" => Some(ast::NumberSuffix::Int(text.suffix)),
             "f64" => Some(ast::NumberSuffix::Float(text.suffix)),
             "u8" => Some(ast::NumberSuffix::Byte(text.suffix)),
+            "u64" => Some(ast::NumberSuffix::U64(text.suffix)),
+            "i128" => Some(ast::NumberSuffix::I128(text.suffix)),
+            "u128" => Some(ast::NumberSuffix::U128(text.suffix)),
             "" => None,
             _ => {
                 return Err(compile::Error::new(
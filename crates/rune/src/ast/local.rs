@@ -7,11 +7,18 @@ fn ast_parse() {
     rt::<ast::Local>("let x = 1;");
     rt::<ast::Local>("#[attr] let a = f();");
     rt::<ast::Local>("let a = b{}().foo[0].await;");
+
+    let local = rt::<ast::Local>("let a: i64 = 42;");
+    assert!(local.ty.is_some());
+
+    let local = rt::<ast::Local>("let (a, b): (i64, String) = f();");
+    assert!(local.ty.is_some());
 }
 
 /// A local variable declaration.
 ///
 /// * `let <pattern> = <expr>;`
+/// * `let <pattern>: <type> = <expr>;`
 #[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 #[non_exhaustive]
 pub struct Local {
@@ -24,7 +31,11 @@ pub struct Local {
     #[rune(iter)]
     pub mut_token: Option<T![mut]>,
     /// The name of the binding.
+    #[rune(parse_with = "parse_pat")]
     pub pat: ast::Pat,
+    /// The optional type annotation of the binding.
+    #[rune(iter)]
+    pub ty: Option<(T![:], ast::Type)>,
     /// The equality keyword.
     pub eq: T![=],
     /// The expression the binding is assigned to.
@@ -34,6 +45,10 @@ pub struct Local {
     pub semi: T![;],
 }
 
+fn parse_pat(p: &mut Parser<'_>) -> Result<ast::Pat> {
+    ast::Pat::parse_without_binding(p)
+}
+
 fn parse_expr(p: &mut Parser<'_>) -> Result<ast::Expr> {
     ast::Expr::parse_with(
         p,
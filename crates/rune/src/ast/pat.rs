@@ -40,8 +40,14 @@ pub enum Pat {
     Rest(PatRest),
 }
 
-impl Parse for Pat {
-    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+impl Pat {
+    /// Parse a pattern which is not a binding, so that a trailing `:` can be
+    /// used to annotate its type like in `let a: i64 = 42;`.
+    pub(crate) fn parse_without_binding(p: &mut Parser<'_>) -> Result<Self> {
+        Self::parse_with(p, false)
+    }
+
+    fn parse_with(p: &mut Parser<'_>, binding: bool) -> Result<Self> {
         let attributes = p.parse::<Vec<ast::Attribute>>()?;
 
         match p.nth(0)? {
@@ -71,7 +77,7 @@ impl Parse for Pat {
             }
             K![str] => {
                 return Ok(match p.nth(1)? {
-                    K![:] => Self::Binding(PatBinding {
+                    K![:] if binding => Self::Binding(PatBinding {
                         attributes,
                         key: ast::ObjectKey::LitStr(p.parse()?),
                         colon: p.parse()?,
@@ -149,7 +155,7 @@ impl Parse for Pat {
                         ident: ast::ObjectIdent::Named(path),
                         items: p.parse()?,
                     }),
-                    K![:] => Self::Binding(PatBinding {
+                    K![:] if binding => Self::Binding(PatBinding {
                         attributes,
                         key: ast::ObjectKey::Path(path),
                         colon: p.parse()?,
@@ -165,6 +171,13 @@ impl Parse for Pat {
     }
 }
 
+impl Parse for Pat {
+    #[inline]
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        Self::parse_with(p, true)
+    }
+}
+
 impl Peek for Pat {
     fn peek(p: &mut Peeker<'_>) -> bool {
         match p.nth(0) {
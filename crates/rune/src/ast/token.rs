@@ -206,6 +206,12 @@ pub enum NumberSuffix {
     Float(Span),
     /// The `u8` suffix.
     Byte(Span),
+    /// The `u64` suffix.
+    U64(Span),
+    /// The `i128` suffix.
+    I128(Span),
+    /// The `u128` suffix.
+    U128(Span),
 }
 
 /// A resolved number literal.
@@ -0,0 +1,43 @@
+use crate::ast::prelude::*;
+
+#[test]
+fn ast_parse() {
+    use crate::testing::rt;
+
+    rt::<ast::Type>("i64");
+    rt::<ast::Type>("std::string::String");
+    rt::<ast::Type>("()");
+    rt::<ast::Type>("(i64, String)");
+}
+
+/// A type annotation.
+///
+/// * `<path>`.
+/// * `(<type>,*)`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum Type {
+    /// A type referenced by path, like `i64` or `std::string::String`.
+    Path(ast::Path),
+    /// A tuple type, like `(i64, String)` or the unit type `()`.
+    Tuple(ast::Parenthesized<ast::Type, T![,]>),
+}
+
+impl Parse for Type {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        Ok(match p.nth(0)? {
+            K!['('] => Self::Tuple(p.parse()?),
+            _ if ast::Path::peek(p.peeker()) => Self::Path(p.parse()?),
+            _ => return Err(compile::Error::expected(p.tok_at(0)?, "type")),
+        })
+    }
+}
+
+impl Peek for Type {
+    fn peek(p: &mut Peeker<'_>) -> bool {
+        match p.nth(0) {
+            K!['('] => true,
+            _ => ast::Path::peek(p),
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::compile;
 use crate::compile::FileSourceLoader as DefaultSourceLoader;
 #[cfg(not(feature = "std"))]
 use crate::compile::NoopSourceLoader as DefaultSourceLoader;
-use crate::compile::{CompileVisitor, Located, MetaError, Options, Pool, SourceLoader};
+use crate::compile::{CompileVisitor, Located, MetaError, Options, ParseCache, Pool, SourceLoader};
 use crate::runtime::unit::{DefaultStorage, UnitEncoder};
 use crate::runtime::Unit;
 use crate::{Context, Diagnostics, SourceId, Sources};
@@ -120,6 +120,7 @@ where
         options: None,
         visitors: Vec::new(),
         source_loader: None,
+        parse_cache: None,
         _unit_storage: PhantomData,
     }
 }
@@ -132,6 +133,7 @@ pub struct Build<'a, S> {
     options: Option<&'a Options>,
     visitors: Vec<&'a mut dyn compile::CompileVisitor>,
     source_loader: Option<&'a mut dyn SourceLoader>,
+    parse_cache: Option<&'a mut ParseCache>,
     _unit_storage: PhantomData<S>,
 }
 
@@ -174,6 +176,19 @@ impl<'a> compile::CompileVisitor for CompileVisitorGroup<'a> {
         Ok(())
     }
 
+    fn visit_field_use(
+        &mut self,
+        location: &dyn Located,
+        hash: crate::Hash,
+        field: &str,
+    ) -> Result<(), MetaError> {
+        for v in self.visitors.iter_mut() {
+            v.visit_field_use(location, hash, field)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_mod(&mut self, location: &dyn Located) -> Result<(), MetaError> {
         for v in self.visitors.iter_mut() {
             v.visit_mod(location)?;
@@ -260,6 +275,17 @@ impl<'a, S> Build<'a, S> {
         self
     }
 
+    /// Modify the current [Build] to reuse files parsed by earlier builds
+    /// through the given [ParseCache].
+    ///
+    /// Only sources whose content has changed since they were last built with
+    /// the same cache are parsed again.
+    #[inline]
+    pub fn with_parse_cache(mut self, parse_cache: &'a mut ParseCache) -> Self {
+        self.parse_cache = Some(parse_cache);
+        self
+    }
+
     /// Build a [`Unit`] with the current configuration.
     pub fn build(mut self) -> Result<Unit<S>, BuildError>
     where
@@ -341,6 +367,7 @@ impl<'a, S> Build<'a, S> {
             visitors,
             diagnostics,
             source_loader,
+            self.parse_cache.take(),
             options,
             &mut unit_storage,
         )?;
@@ -8,11 +8,15 @@
 
 mod benches;
 mod check;
+mod coverage;
+mod debug;
 mod doc;
 mod format;
 mod languageserver;
 mod loader;
 mod naming;
+mod profile;
+mod repl;
 mod run;
 mod tests;
 mod visitor;
@@ -31,7 +35,8 @@ use anyhow::{bail, Context as _, Error, Result};
 use clap::{Parser, Subcommand};
 use tracing_subscriber::filter::EnvFilter;
 
-use crate::compile::{ItemBuf, ParseOptionError};
+use crate::compile::ItemBuf;
+use crate::diagnostics::{Lint, LintLevel};
 use crate::modules::capture_io::CaptureIo;
 use crate::termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use crate::{Context, ContextError, Hash, Options};
@@ -252,6 +257,25 @@ impl EntryPoint<'_> {
             EntryPoint::Package(p) => &p.found.path,
         }
     }
+
+    /// Construct compiler options for the entrypoint.
+    ///
+    /// Lints configured in the manifest of the package are applied on top of
+    /// the base options, but lints specified on the command line always take
+    /// precedence.
+    fn options(&self, base: &Options, shared: &SharedFlags) -> Result<Options> {
+        let mut options = base.clone();
+
+        if let EntryPoint::Package(p) = self {
+            for &(lint, level) in &p.package.lints {
+                options.lint(lint, level);
+            }
+
+            shared.lints(&mut options)?;
+        }
+
+        Ok(options)
+    }
 }
 
 struct Io<'a> {
@@ -275,7 +299,7 @@ where
     T: CommandBase + clap::Args,
 {
     /// Construct compiler options from arguments.
-    fn options(&self) -> Result<Options, ParseOptionError> {
+    fn options(&self) -> Result<Options> {
         let mut options = Options::default();
 
         // Command-specific override defaults.
@@ -285,10 +309,15 @@ where
             options.bytecode(false);
         }
 
+        if self.command.is_runtime_type_checks() {
+            options.runtime_type_checks(true);
+        }
+
         for option in &self.shared.compiler_options {
             options.parse_option(option)?;
         }
 
+        self.shared.lints(&mut options)?;
         Ok(options)
     }
 }
@@ -372,6 +401,12 @@ trait CommandBase {
         false
     }
 
+    /// Test if the command should check type annotations at runtime.
+    #[inline]
+    fn is_runtime_type_checks(&self) -> bool {
+        false
+    }
+
     /// Test if the command should acquire workspace assets for the given asset kind.
     #[inline]
     fn is_workspace(&self, _: AssetKind) -> bool {
@@ -403,6 +438,10 @@ enum Command {
     Run(CommandShared<run::Flags>),
     /// Format the provided file
     Fmt(CommandShared<format::Flags>),
+    /// Start an interactive session
+    Repl(SharedFlags),
+    /// Run a debug adapter for the Debug Adapter Protocol over stdio
+    Debug(SharedFlags),
     /// Run a language server.
     LanguageServer(SharedFlags),
     /// Helper command to generate type hashes.
@@ -410,13 +449,15 @@ enum Command {
 }
 
 impl Command {
-    const ALL: [&'static str; 8] = [
+    const ALL: [&'static str; 10] = [
         "check",
         "doc",
         "test",
         "bench",
         "run",
         "fmt",
+        "repl",
+        "debug",
         "languageserver",
         "hash",
     ];
@@ -429,6 +470,8 @@ impl Command {
             Command::Bench(shared) => (&mut shared.shared, &mut shared.command),
             Command::Run(shared) => (&mut shared.shared, &mut shared.command),
             Command::Fmt(shared) => (&mut shared.shared, &mut shared.command),
+            Command::Repl(..) => return None,
+            Command::Debug(..) => return None,
             Command::LanguageServer(..) => return None,
             Command::Hash(..) => return None,
         };
@@ -444,6 +487,8 @@ impl Command {
             Command::Bench(shared) => (&shared.shared, &shared.command),
             Command::Run(shared) => (&shared.shared, &shared.command),
             Command::Fmt(shared) => (&shared.shared, &shared.command),
+            Command::Repl(..) => return None,
+            Command::Debug(..) => return None,
             Command::LanguageServer(..) => return None,
             Command::Hash(..) => return None,
         };
@@ -515,6 +560,27 @@ impl Config {
 }
 
 impl SharedFlags {
+    /// Apply lint levels specified on the command line.
+    fn lints(&self, options: &mut Options) -> Result<()> {
+        let levels = [
+            (&self.allow, LintLevel::Allow),
+            (&self.warn, LintLevel::Warn),
+            (&self.deny, LintLevel::Deny),
+        ];
+
+        for (names, level) in levels {
+            for name in names {
+                let Some(lint) = Lint::from_name(name) else {
+                    bail!("Unknown lint `{name}`");
+                };
+
+                options.lint(lint, level);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Setup build context.
     fn context(
         &self,
@@ -603,9 +669,24 @@ struct SharedFlags {
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    ///
+    /// runtime-type-checks[=<true/false>] - Check type annotations while the program runs.
     #[arg(name = "option", short = 'O', number_of_values = 1)]
     compiler_options: Vec<String>,
 
+    /// Allow the given lint, so that it is not reported.
+    #[arg(long, short = 'A', value_name = "LINT", number_of_values = 1)]
+    allow: Vec<String>,
+
+    /// Report the given lint as a warning. Warnings are only displayed if
+    /// `--warnings` is specified.
+    #[arg(long, short = 'W', value_name = "LINT", number_of_values = 1)]
+    warn: Vec<String>,
+
+    /// Report the given lint as an error, causing the build to fail.
+    #[arg(long, short = 'D', value_name = "LINT", number_of_values = 1)]
+    deny: Vec<String>,
+
     /// Run with the following binary from a loaded manifest. This requires a
     /// `Rune.toml` manifest.
     #[arg(long = "bin")]
@@ -815,6 +896,8 @@ where
             let options = f.options()?;
 
             for e in entries {
+                let options = e.options(&options, &f.shared)?;
+
                 match check::run(io, entry, c, &f.command, &f.shared, &options, e.path())? {
                     ExitCode::Success => (),
                     other => return Ok(other),
@@ -841,6 +924,7 @@ where
             let options = f.options()?;
 
             for e in entries {
+                let options = e.options(&options, &f.shared)?;
                 let capture_io = crate::modules::capture_io::CaptureIo::new();
                 let context = f.shared.context(entry, c, Some(&capture_io))?;
 
@@ -874,6 +958,8 @@ where
             let context = f.shared.context(entry, c, None)?;
 
             for e in entries {
+                let options = e.options(&options, &f.shared)?;
+
                 let load = loader::load(
                     io,
                     &context,
@@ -889,6 +975,15 @@ where
                 }
             }
         }
+        Command::Repl(shared) => {
+            let context = shared.context(entry, c, None)?;
+            return repl::run(io, &context, shared).await;
+        }
+        Command::Debug(shared) => {
+            let capture_io = CaptureIo::new();
+            let context = shared.context(entry, c, Some(&capture_io))?;
+            return debug::run(io, &context, &capture_io, shared).await;
+        }
         Command::LanguageServer(shared) => {
             let context = shared.context(entry, c, None)?;
             languageserver::run(context).await?;
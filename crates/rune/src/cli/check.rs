@@ -2,7 +2,7 @@ use std::io::Write;
 use std::path::Path;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
 use crate::cli::{visitor, AssetKind, CommandBase, Config, Entry, ExitCode, Io, SharedFlags};
 use crate::compile::FileSourceLoader;
@@ -13,6 +13,21 @@ pub(super) struct Flags {
     /// Exit with a non-zero exit-code even for warnings
     #[arg(long)]
     warnings_are_errors: bool,
+    /// Statically check type annotations and function calls
+    #[arg(long)]
+    types: bool,
+    /// The format in which diagnostics are emitted
+    #[arg(long, value_enum, default_value = "human")]
+    message_format: MessageFormat,
+}
+
+/// The format in which diagnostics are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MessageFormat {
+    /// Human-readable diagnostics.
+    Human,
+    /// One JSON object per line for each diagnostic.
+    Json,
 }
 
 impl CommandBase for Flags {
@@ -41,7 +56,10 @@ pub(super) fn run(
     options: &Options,
     path: &Path,
 ) -> Result<ExitCode> {
-    writeln!(io.stdout, "Checking: {}", path.display())?;
+    match flags.message_format {
+        MessageFormat::Human => writeln!(io.stdout, "Checking: {}", path.display())?,
+        MessageFormat::Json => writeln!(io.stderr, "Checking: {}", path.display())?,
+    }
 
     let context = shared.context(entry, c, None)?;
 
@@ -58,18 +76,27 @@ pub(super) fn run(
         Diagnostics::without_warnings()
     };
 
+    let mut options = options.clone();
+
+    if flags.types {
+        options.type_check(true);
+    }
+
     let mut test_finder = visitor::FunctionVisitor::new(visitor::Attribute::None);
     let mut source_loader = FileSourceLoader::new();
 
     let _ = crate::prepare(&mut sources)
         .with_context(&context)
         .with_diagnostics(&mut diagnostics)
-        .with_options(options)
+        .with_options(&options)
         .with_visitor(&mut test_finder)?
         .with_source_loader(&mut source_loader)
         .build();
 
-    diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+    match flags.message_format {
+        MessageFormat::Human => diagnostics.emit(&mut io.stdout.lock(), &sources)?,
+        MessageFormat::Json => diagnostics.emit_json(&mut io.stdout.lock(), &sources)?,
+    }
 
     if diagnostics.has_error() || flags.warnings_are_errors && diagnostics.has_warning() {
         Ok(ExitCode::Failure)
@@ -0,0 +1,189 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::alloc::prelude::*;
+use crate::alloc::{BTreeMap, String, Vec};
+use crate::ast::Spanned;
+use crate::runtime::Coverage;
+use crate::termcolor::{Color, ColorSpec, WriteColor};
+use crate::Sources;
+
+/// Coverage of a single source file.
+#[derive(Default)]
+struct File {
+    /// Hits by one-based line.
+    lines: BTreeMap<usize, usize>,
+    /// Functions by name, with their one-based line and number of calls.
+    functions: BTreeMap<String, (usize, usize)>,
+}
+
+impl File {
+    fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|&&hits| hits > 0).count()
+    }
+
+    fn functions_hit(&self) -> usize {
+        self.functions.values().filter(|&&(_, hits)| hits > 0).count()
+    }
+}
+
+/// Coverage collected from tests, aggregated by source file.
+#[derive(Default)]
+pub(super) struct Report {
+    files: BTreeMap<PathBuf, File>,
+}
+
+impl Report {
+    /// Add the coverage recorded for a unit.
+    ///
+    /// Instructions in sources which do not correspond to a file, like
+    /// documentation tests, are ignored.
+    pub(super) fn add(&mut self, coverage: &Coverage, sources: &Sources) -> Result<()> {
+        let mut instructions = Vec::new();
+
+        for (ip, inst, hits) in coverage.instructions() {
+            let Some(source) = sources.get(inst.source_id) else {
+                continue;
+            };
+
+            let Some(path) = source.path() else {
+                continue;
+            };
+
+            let (line, _) = source.pos_to_utf8_linecol(inst.span.span().start.into_usize());
+            instructions.try_push((ip, path, line + 1))?;
+
+            // An instruction which starts on a line is executed whenever the
+            // line is, so the line is hit as many times as the most executed
+            // instruction on it.
+            let file = self.file(path)?;
+            let count = file.lines.entry(line + 1).or_try_insert(0)?;
+            *count = (*count).max(hits);
+        }
+
+        instructions.sort_by_key(|&(ip, ..)| ip);
+
+        for (entry, signature, hits) in coverage.functions() {
+            // The entry of a function doesn't have a location, so it's
+            // attributed to the line of the first instruction which does.
+            let index = instructions.partition_point(|&(ip, ..)| ip < entry);
+
+            let Some(&(_, path, line)) = instructions.get(index) else {
+                continue;
+            };
+
+            let name = signature.path.try_to_string()?;
+            let file = self.file(path)?;
+            let function = file.functions.entry(name).or_try_insert((line, 0))?;
+            function.1 += hits;
+        }
+
+        Ok(())
+    }
+
+    fn file(&mut self, path: &Path) -> Result<&mut File> {
+        if !self.files.contains_key(path) {
+            self.files.try_insert(path.to_path_buf(), File::default())?;
+        }
+
+        self.files.get_mut(path).context("missing file")
+    }
+
+    /// Write the report in the LCOV tracefile format.
+    pub(super) fn write_lcov(&self, path: &Path) -> Result<()> {
+        let file = match fs::File::create(path) {
+            Ok(file) => file,
+            Err(error) => return Err(error).context(path.display().try_to_string()?),
+        };
+
+        let mut out = io::BufWriter::new(file);
+
+        for (path, file) in &self.files {
+            writeln!(out, "TN:")?;
+            writeln!(out, "SF:{}", path.display())?;
+
+            for (name, (line, _)) in &file.functions {
+                writeln!(out, "FN:{line},{name}")?;
+            }
+
+            for (name, (_, hits)) in &file.functions {
+                writeln!(out, "FNDA:{hits},{name}")?;
+            }
+
+            writeln!(out, "FNF:{}", file.functions.len())?;
+            writeln!(out, "FNH:{}", file.functions_hit())?;
+
+            for (line, hits) in &file.lines {
+                writeln!(out, "DA:{line},{hits}")?;
+            }
+
+            writeln!(out, "LF:{}", file.lines.len())?;
+            writeln!(out, "LH:{}", file.lines_hit())?;
+            writeln!(out, "end_of_record")?;
+        }
+
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Emit a summary of the report per source file.
+    pub(super) fn emit(&self, o: &mut dyn WriteColor) -> Result<()> {
+        let mut total_lines = 0;
+        let mut total_lines_hit = 0;
+        let mut total_functions = 0;
+        let mut total_functions_hit = 0;
+
+        writeln!(o, "Coverage:")?;
+
+        for (path, file) in &self.files {
+            let lines_hit = file.lines_hit();
+            let functions_hit = file.functions_hit();
+
+            write!(o, "  {}: ", path.display())?;
+            emit_ratio(o, lines_hit, file.lines.len())?;
+            writeln!(
+                o,
+                " of lines, {functions_hit}/{} functions",
+                file.functions.len()
+            )?;
+
+            total_lines += file.lines.len();
+            total_lines_hit += lines_hit;
+            total_functions += file.functions.len();
+            total_functions_hit += functions_hit;
+        }
+
+        write!(o, "  Total: ")?;
+        emit_ratio(o, total_lines_hit, total_lines)?;
+        writeln!(
+            o,
+            " of lines, {total_functions_hit}/{total_functions} functions"
+        )?;
+        Ok(())
+    }
+}
+
+fn emit_ratio(o: &mut dyn WriteColor, hit: usize, total: usize) -> Result<()> {
+    let percent = if total == 0 {
+        100.0
+    } else {
+        hit as f64 * 100.0 / total as f64
+    };
+
+    let color = if percent >= 80.0 {
+        Color::Green
+    } else if percent >= 50.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    o.set_color(ColorSpec::new().set_fg(Some(color)))?;
+    write!(o, "{percent:.1}%")?;
+    o.reset()?;
+    write!(o, " ({hit}/{total})")?;
+    Ok(())
+}
@@ -0,0 +1,581 @@
+//! A debug adapter implementing the [Debug Adapter Protocol] over stdio, which
+//! allows editors like VS Code to debug scripts.
+//!
+//! The adapter runs a single script on a single thread. Requests are only
+//! processed while the script is stopped, so pausing a running script is not
+//! supported.
+//!
+//! [Debug Adapter Protocol]: https://microsoft.github.io/debug-adapter-protocol/
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ::rust_alloc::borrow::ToOwned;
+use ::rust_alloc::format;
+use ::rust_alloc::string::{String, ToString};
+use ::rust_alloc::vec;
+use ::rust_alloc::vec::Vec;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde_json::{json, Value as Json};
+
+use crate::cli::{ExitCode, Io, SharedFlags};
+use crate::modules::capture_io::CaptureIo;
+use crate::runtime::{
+    DebugFrame, DebugLocation, DebugStep, DebugStop, Debugger, Formatter, VmExecution,
+};
+use crate::termcolor::NoColor;
+use crate::{Context, Diagnostics, Options, Source, SourceId, Sources, Unit, Vm};
+
+/// The identifier of the only thread being debugged.
+const THREAD_ID: u64 = 1;
+
+/// A script which has been compiled in response to a `launch` request.
+struct Program {
+    sources: Sources,
+    unit: Unit,
+    /// Stop before running any code.
+    stop_on_entry: bool,
+}
+
+/// A request received from the client.
+struct Request {
+    seq: u64,
+    command: String,
+    arguments: Json,
+}
+
+/// The connection to the client.
+struct Adapter<'a> {
+    stdin: io::StdinLock<'static>,
+    stdout: &'a mut dyn Write,
+    /// Sequence number of the next message sent.
+    seq: u64,
+    /// If lines sent by the client start at 1.
+    lines_start_at_1: bool,
+    /// If columns sent by the client start at 1.
+    columns_start_at_1: bool,
+}
+
+impl Adapter<'_> {
+    /// Receive the next request, or `None` if the client disconnected.
+    fn receive(&mut self) -> Result<Option<Request>> {
+        let mut length = None;
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+
+            if self.stdin.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+
+            let line = line.trim();
+
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some(value) = line.strip_prefix("Content-Length:") {
+                length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+
+        let length = length.context("Missing Content-Length header")?;
+        let mut body = vec![0; length];
+        self.stdin.read_exact(&mut body)?;
+
+        let mut message: Json = serde_json::from_slice(&body)?;
+
+        let seq = message["seq"].as_u64().unwrap_or_default();
+
+        let command = message["command"]
+            .as_str()
+            .context("Missing command in request")?
+            .to_owned();
+
+        Ok(Some(Request {
+            seq,
+            command,
+            arguments: message["arguments"].take(),
+        }))
+    }
+
+    fn send(&mut self, mut message: Json) -> Result<()> {
+        message["seq"] = json!(self.seq);
+        self.seq += 1;
+
+        let body = serde_json::to_vec(&message)?;
+        write!(self.stdout, "Content-Length: {}\r\n\r\n", body.len())?;
+        self.stdout.write_all(&body)?;
+        self.stdout.flush()?;
+        Ok(())
+    }
+
+    fn respond(&mut self, request: &Request, body: Json) -> Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": true,
+            "command": request.command,
+            "body": body,
+        }))
+    }
+
+    fn respond_error(&mut self, request: &Request, message: &str) -> Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": false,
+            "command": request.command,
+            "message": message,
+        }))
+    }
+
+    fn event(&mut self, event: &str, body: Json) -> Result<()> {
+        self.send(json!({
+            "type": "event",
+            "event": event,
+            "body": body,
+        }))
+    }
+
+    fn output(&mut self, category: &str, output: &str) -> Result<()> {
+        if output.is_empty() {
+            return Ok(());
+        }
+
+        self.event("output", json!({ "category": category, "output": output }))
+    }
+
+    /// Convert a zero-based line to one understood by the client.
+    fn to_client_line(&self, line: usize) -> usize {
+        line + usize::from(self.lines_start_at_1)
+    }
+
+    /// Convert a line from the client to a zero-based line.
+    fn client_to_line(&self, line: usize) -> usize {
+        line.saturating_sub(usize::from(self.lines_start_at_1))
+    }
+
+    /// Convert a zero-based column to one understood by the client.
+    fn to_client_column(&self, column: usize) -> usize {
+        column + usize::from(self.columns_start_at_1)
+    }
+}
+
+pub(super) async fn run(
+    io: &mut Io<'_>,
+    context: &Context,
+    capture: &CaptureIo,
+    shared: &SharedFlags,
+) -> Result<ExitCode> {
+    let mut options = Options::default();
+
+    for option in &shared.compiler_options {
+        options.parse_option(option)?;
+    }
+
+    shared.lints(&mut options)?;
+
+    let mut adapter = Adapter {
+        stdin: io::stdin().lock(),
+        stdout: io.stdout,
+        seq: 1,
+        lines_start_at_1: true,
+        columns_start_at_1: true,
+    };
+
+    // Configuration is only accepted once a program has been launched, which
+    // is signalled to the client through the `initialized` event.
+    let program = loop {
+        let Some(request) = adapter.receive()? else {
+            return Ok(ExitCode::Success);
+        };
+
+        match request.command.as_str() {
+            "initialize" => {
+                let arguments = &request.arguments;
+                adapter.lines_start_at_1 = arguments["linesStartAt1"].as_bool().unwrap_or(true);
+                adapter.columns_start_at_1 =
+                    arguments["columnsStartAt1"].as_bool().unwrap_or(true);
+
+                adapter.respond(
+                    &request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsSteppingGranularity": true,
+                    }),
+                )?;
+            }
+            "launch" => match launch(context, &options, &request.arguments) {
+                Ok((program, diagnostics)) => {
+                    adapter.output("stderr", &diagnostics)?;
+                    adapter.respond(&request, json!({}))?;
+                    adapter.event("initialized", json!({}))?;
+                    break program;
+                }
+                Err(error) => {
+                    adapter.respond_error(&request, &error.to_string())?;
+                }
+            },
+            "disconnect" => {
+                adapter.respond(&request, json!({}))?;
+                return Ok(ExitCode::Success);
+            }
+            command => {
+                adapter.respond_error(&request, &format!("Unsupported request `{command}`"))?;
+            }
+        }
+    };
+
+    let Program {
+        sources,
+        unit,
+        stop_on_entry,
+    } = program;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    let mut execution = vm.execute(["main"], ())?;
+    let mut debugger = Debugger::new();
+    let mut terminated = false;
+
+    while let Some(request) = adapter.receive()? {
+        let step = match request.command.as_str() {
+            "setBreakpoints" => {
+                let body = set_breakpoints(&adapter, &mut debugger, &execution, &sources, &request)?;
+                adapter.respond(&request, body)?;
+                continue;
+            }
+            "configurationDone" => {
+                adapter.respond(&request, json!({}))?;
+
+                // Stopping on entry runs until the first instruction which
+                // has a source location.
+                if stop_on_entry {
+                    DebugStep::Into
+                } else {
+                    DebugStep::Continue
+                }
+            }
+            "threads" => {
+                adapter.respond(
+                    &request,
+                    json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+                )?;
+                continue;
+            }
+            "stackTrace" => {
+                let body = stack_trace(&adapter, &execution, &sources)?;
+                adapter.respond(&request, body)?;
+                continue;
+            }
+            "scopes" => {
+                let frame = request.arguments["frameId"].as_u64().unwrap_or_default();
+
+                adapter.respond(
+                    &request,
+                    json!({
+                        "scopes": [{
+                            "name": "Locals",
+                            "variablesReference": frame + 1,
+                            "expensive": false,
+                        }]
+                    }),
+                )?;
+                continue;
+            }
+            "variables" => {
+                let reference = request.arguments["variablesReference"]
+                    .as_u64()
+                    .unwrap_or_default();
+
+                let mut variables = Vec::new();
+
+                if let Some(frame) = frame(&execution, reference.checked_sub(1))? {
+                    for (name, value) in execution.vm().debug_locals(&frame)? {
+                        variables.push(json!({
+                            "name": name,
+                            "value": format_value(execution.vm(), &value),
+                            "variablesReference": 0,
+                        }));
+                    }
+                }
+
+                adapter.respond(&request, json!({ "variables": variables }))?;
+                continue;
+            }
+            "evaluate" => {
+                let name = request.arguments["expression"].as_str().unwrap_or_default();
+                let name = name.trim();
+
+                let value = match frame(&execution, request.arguments["frameId"].as_u64())? {
+                    Some(frame) => execution.vm().debug_local(&frame, name)?,
+                    None => None,
+                };
+
+                match value {
+                    Some(value) => {
+                        let result = format_value(execution.vm(), &value);
+
+                        adapter.respond(
+                            &request,
+                            json!({ "result": result, "variablesReference": 0 }),
+                        )?;
+                    }
+                    None => {
+                        let message = format!("`{name}` is not a local variable");
+                        adapter.respond_error(&request, &message)?;
+                    }
+                }
+
+                continue;
+            }
+            "continue" => DebugStep::Continue,
+            "next" | "stepIn" | "stepOut"
+                if request.arguments["granularity"].as_str() == Some("instruction") =>
+            {
+                DebugStep::Instruction
+            }
+            "next" => DebugStep::Over,
+            "stepIn" => DebugStep::Into,
+            "stepOut" => DebugStep::Out,
+            "disconnect" => {
+                adapter.respond(&request, json!({}))?;
+                break;
+            }
+            command => {
+                adapter.respond_error(&request, &format!("Unsupported request `{command}`"))?;
+                continue;
+            }
+        };
+
+        if request.command != "configurationDone" {
+            if terminated {
+                adapter.respond_error(&request, "The program has terminated")?;
+                continue;
+            }
+
+            adapter.respond(&request, json!({ "allThreadsContinued": true }))?;
+        }
+
+        let result = execution
+            .async_debug(&mut debugger, &sources, step)
+            .await
+            .into_result();
+
+        adapter.output("stdout", &capture.drain_utf8()?)?;
+
+        let reason = match result {
+            Ok(DebugStop::Breakpoint) => "breakpoint",
+            Ok(DebugStop::Step) if request.command == "configurationDone" => "entry",
+            Ok(DebugStop::Step) => "step",
+            Ok(DebugStop::Exited(..)) => {
+                terminate(&mut adapter, 0)?;
+                terminated = true;
+                continue;
+            }
+            Err(error) => {
+                let mut out = NoColor::new(Vec::new());
+                error.emit(&mut out, &sources)?;
+                adapter.output("stderr", &String::from_utf8_lossy(&out.into_inner()))?;
+                terminate(&mut adapter, 1)?;
+                terminated = true;
+                continue;
+            }
+        };
+
+        adapter.event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        )?;
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Compile the program described by the arguments of a `launch` request,
+/// returning it together with any diagnostics.
+fn launch(context: &Context, options: &Options, arguments: &Json) -> Result<(Program, String)> {
+    let path = arguments["program"]
+        .as_str()
+        .context("Missing `program` in launch arguments")?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::from_path(path).with_context(|| anyhow!("{path}"))?)?;
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = crate::prepare(&mut sources)
+        .with_context(context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(options)
+        .build();
+
+    let mut out = NoColor::new(Vec::new());
+    diagnostics.emit(&mut out, &sources)?;
+    let diagnostics = String::from_utf8_lossy(&out.into_inner()).into_owned();
+
+    let Ok(unit) = result else {
+        bail!("Failed to build `{path}`:\n{diagnostics}");
+    };
+
+    let program = Program {
+        sources,
+        unit,
+        stop_on_entry: arguments["stopOnEntry"].as_bool().unwrap_or_default(),
+    };
+
+    Ok((program, diagnostics))
+}
+
+fn terminate(adapter: &mut Adapter<'_>, exit_code: u64) -> Result<()> {
+    adapter.event("exited", json!({ "exitCode": exit_code }))?;
+    adapter.event("terminated", json!({}))?;
+    Ok(())
+}
+
+/// Handle a `setBreakpoints` request, replacing all breakpoints in a source.
+fn set_breakpoints(
+    adapter: &Adapter<'_>,
+    debugger: &mut Debugger,
+    execution: &VmExecution<&mut Vm>,
+    sources: &Sources,
+    request: &Request,
+) -> Result<Json> {
+    let path = request.arguments["source"]["path"].as_str().unwrap_or_default();
+    let source_id = find_source(sources, Path::new(path));
+
+    if let Some(source_id) = source_id {
+        debugger.clear_breakpoints(source_id);
+    }
+
+    let lines = executable_lines(execution.vm(), sources);
+    let mut breakpoints = Vec::new();
+
+    let requested = request.arguments["breakpoints"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    for breakpoint in requested {
+        let line = breakpoint["line"].as_u64().unwrap_or_default() as usize;
+        let line = adapter.client_to_line(line);
+
+        let verified = match source_id {
+            Some(source_id) if lines.get(&source_id).is_some_and(|l| l.contains(&line)) => {
+                debugger.set_breakpoint(source_id, line)?;
+                true
+            }
+            _ => false,
+        };
+
+        breakpoints.push(json!({
+            "verified": verified,
+            "line": adapter.to_client_line(line),
+        }));
+    }
+
+    Ok(json!({ "breakpoints": breakpoints }))
+}
+
+/// Handle a `stackTrace` request.
+fn stack_trace(
+    adapter: &Adapter<'_>,
+    execution: &VmExecution<&mut Vm>,
+    sources: &Sources,
+) -> Result<Json> {
+    let vm = execution.vm();
+    let debug = vm.unit().debug_info();
+    let mut frames = Vec::new();
+
+    for (id, frame) in vm.debug_frames()?.into_iter().enumerate() {
+        let name = debug
+            .and_then(|debug| {
+                let entry = debug.function_entry_at(frame.ip)?;
+                let (_, signature) = debug.function_at(entry)?;
+                Some(signature.path.to_string())
+            })
+            .unwrap_or_else(|| String::from("<unknown>"));
+
+        let mut stack_frame = json!({
+            "id": id,
+            "name": name,
+            "line": 0,
+            "column": 0,
+        });
+
+        if let Some(location) = DebugLocation::at(vm, sources, frame.ip) {
+            if let Some(source) = sources.get(location.source_id) {
+                let mut json_source = json!({ "name": source.name() });
+
+                if let Some(path) = source.path() {
+                    json_source["path"] = json!(path);
+                }
+
+                stack_frame["source"] = json_source;
+            }
+
+            stack_frame["line"] = json!(adapter.to_client_line(location.line));
+            stack_frame["column"] = json!(adapter.to_client_column(location.column));
+        }
+
+        frames.push(stack_frame);
+    }
+
+    Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+}
+
+/// Get the frame with the given index, where frames are numbered from the
+/// innermost one.
+fn frame(execution: &VmExecution<&mut Vm>, index: Option<u64>) -> Result<Option<DebugFrame>> {
+    let frames = execution.vm().debug_frames()?;
+    let index = index.unwrap_or_default() as usize;
+    Ok(frames.get(index).copied())
+}
+
+/// Find the source corresponding to the given path.
+fn find_source(sources: &Sources, path: &Path) -> Option<SourceId> {
+    let path = canonicalize(path);
+
+    sources.source_ids().find(|&id| {
+        sources
+            .path(id)
+            .is_some_and(|source| canonicalize(source) == path)
+    })
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_owned())
+}
+
+/// Collect the zero-based lines which have instructions, by source.
+fn executable_lines(vm: &Vm, sources: &Sources) -> HashMap<SourceId, Vec<usize>> {
+    let mut lines = HashMap::<_, Vec<_>>::new();
+
+    let Some(debug) = vm.unit().debug_info() else {
+        return lines;
+    };
+
+    for &ip in debug.instructions.keys() {
+        if let Some(location) = DebugLocation::at(vm, sources, ip) {
+            lines
+                .entry(location.source_id)
+                .or_default()
+                .push(location.line);
+        }
+    }
+
+    lines
+}
+
+fn format_value(vm: &Vm, value: &crate::Value) -> String {
+    let mut f = Formatter::new();
+
+    match vm.with(|| value.string_debug(&mut f)).into_result() {
+        Ok(()) => f.as_str().to_owned(),
+        Err(error) => format!("<{error}>"),
+    }
+}
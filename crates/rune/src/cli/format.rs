@@ -1,6 +1,7 @@
 use core::fmt;
 
 use std::io::Write;
+use std::path::Path;
 
 use clap::Parser;
 use similar::{ChangeTag, TextDiff};
@@ -8,6 +9,7 @@ use similar::{ChangeTag, TextDiff};
 use crate::alloc::prelude::*;
 use crate::alloc::BTreeSet;
 use crate::cli::{AssetKind, CommandBase, Config, Entry, EntryPoint, ExitCode, Io, SharedFlags};
+use crate::fmt::FormatOptions;
 use crate::support::{Context, Result};
 use crate::termcolor::{Color, ColorSpec, WriteColor};
 use crate::{Diagnostics, Options, Source, Sources};
@@ -35,6 +37,21 @@ impl CommandBase for Flags {
     }
 }
 
+/// Load formatting options, where a format file in the root of the project
+/// takes precedence over the `[format]` section of the manifest.
+fn format_options(c: &Config) -> Result<FormatOptions> {
+    let root = match &c.manifest_root {
+        Some(root) => root.as_path(),
+        None => Path::new("."),
+    };
+
+    if let Some(options) = crate::fmt::load_format_file(root)? {
+        return Ok(options);
+    }
+
+    Ok(c.manifest.format.clone().unwrap_or_default())
+}
+
 pub(super) fn run<'m, I>(
     io: &mut Io<'_>,
     entry: &mut Entry<'_>,
@@ -56,6 +73,8 @@ where
 
     let context = shared.context(entry, c, None)?;
 
+    let format_options = format_options(c)?;
+
     let mut paths = BTreeSet::new();
 
     for e in entrys {
@@ -97,7 +116,7 @@ where
             Err(error) => return Err(error).context(path.display().try_to_string()?),
         };
 
-        let val = match crate::fmt::layout_source(source.as_str()) {
+        let val = match crate::fmt::layout_source(source.as_str(), &format_options) {
             Ok(val) => val,
             Err(err) => {
                 failed += 1;
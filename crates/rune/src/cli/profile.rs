@@ -0,0 +1,192 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{HashMap, HashSet, String, Vec};
+use crate::cli::Io;
+use crate::compile::context::ContextMeta;
+use crate::compile::meta;
+use crate::runtime::{ProfileFrame, ProfileSample, Profiler};
+use crate::Context;
+
+/// What was recorded for a single function, across all call stacks.
+#[derive(Default)]
+struct Function {
+    calls: u64,
+    instructions: u64,
+    self_time: Duration,
+    total_time: Duration,
+}
+
+/// Write the folded call stacks recorded by a profiler to the given path, and
+/// emit the functions which most time was spent in.
+pub(super) fn emit(
+    io: &mut Io<'_>,
+    context: &Context,
+    profiler: &Profiler,
+    path: &Path,
+    top: usize,
+) -> Result<()> {
+    let mut samples = Vec::new();
+
+    for sample in profiler.samples()? {
+        let mut names = Vec::new();
+
+        for frame in &sample.stack {
+            names.try_push(name(context, profiler, frame)?)?;
+        }
+
+        samples.try_push((names, sample))?;
+    }
+
+    write_folded(path, &samples)?;
+
+    let mut functions = HashMap::<&str, Function>::new();
+
+    for (names, sample) in &samples {
+        let Some(name) = names.last() else {
+            continue;
+        };
+
+        let function = functions.entry(name.as_str()).or_try_default()?;
+        function.calls += sample.calls;
+        function.instructions += sample.instructions;
+        function.self_time += sample.time;
+
+        // Recursive functions appear multiple times in a stack, but the time
+        // should only count once towards their total.
+        let mut seen = HashSet::new();
+
+        for name in names {
+            if seen.try_insert(name.as_str())? {
+                functions.entry(name.as_str()).or_try_default()?.total_time += sample.time;
+            }
+        }
+    }
+
+    let mut functions = functions.into_iter().try_collect::<Vec<_>>()?;
+    functions.sort_by(|(a_name, a), (b_name, b)| {
+        b.self_time
+            .cmp(&a.self_time)
+            .then_with(|| b.instructions.cmp(&a.instructions))
+            .then_with(|| a_name.cmp(b_name))
+    });
+
+    writeln!(
+        io.stderr,
+        "{:>12} {:>12} {:>14} {:>10}  function",
+        "self time", "total time", "instructions", "calls"
+    )?;
+
+    for (name, function) in functions.iter().take(top) {
+        writeln!(
+            io.stderr,
+            "{:>12} {:>12} {:>14} {:>10}  {name}",
+            format_duration(function.self_time)?,
+            format_duration(function.total_time)?,
+            function.instructions,
+            function.calls,
+        )?;
+    }
+
+    writeln!(io.stderr, "Wrote {}", path.display())?;
+    Ok(())
+}
+
+/// Write samples in the folded stack format used by flamegraph tools, where
+/// the value of each stack is the number of nanoseconds spent in it.
+fn write_folded(path: &Path, samples: &[(Vec<String>, ProfileSample)]) -> Result<()> {
+    let file = match fs::File::create(path) {
+        Ok(file) => file,
+        Err(error) => return Err(error).context(path.display().try_to_string()?),
+    };
+
+    let mut out = io::BufWriter::new(file);
+
+    for (names, sample) in samples {
+        let nanos = sample.time.as_nanos();
+
+        if nanos == 0 {
+            continue;
+        }
+
+        let stack: String = names.iter().map(String::as_str).try_join(";")?;
+        writeln!(out, "{stack} {nanos}")?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// Get the name of a frame.
+fn name(context: &Context, profiler: &Profiler, frame: &ProfileFrame) -> Result<String> {
+    let mut name = String::new();
+
+    match *frame {
+        ProfileFrame::Function(entry) => {
+            let signature = profiler
+                .unit()
+                .debug_info()
+                .and_then(|debug| debug.function_at(entry));
+
+            match signature {
+                Some((_, signature)) => write!(name, "{}", signature.path)?,
+                None => write!(name, "{entry:04}")?,
+            }
+        }
+        ProfileFrame::Native(hash) => match context.lookup_meta_by_hash(hash).next() {
+            Some(ContextMeta {
+                item: Some(item), ..
+            }) => write!(name, "{item}")?,
+            // Protocol functions don't have items, so they are named after the
+            // type they are associated with.
+            Some(ContextMeta {
+                kind:
+                    meta::Kind::Function {
+                        associated: Some(associated),
+                        container,
+                        ..
+                    },
+                ..
+            }) => {
+                let container = container.and_then(|hash| {
+                    context
+                        .lookup_meta_by_hash(hash)
+                        .find_map(|meta| meta.item.as_ref())
+                });
+
+                match container {
+                    Some(item) => write!(name, "{item}::{associated}")?,
+                    None => write!(name, "{associated}")?,
+                }
+            }
+            _ => write!(name, "{hash}")?,
+        },
+    }
+
+    // Semicolons separate frames in the folded stack format.
+    if name.contains(';') {
+        name = name.replace(';', ":").as_str().try_to_owned()?;
+    }
+
+    Ok(name)
+}
+
+fn format_duration(duration: Duration) -> Result<String> {
+    let mut string = String::new();
+
+    if duration.as_secs() > 0 {
+        write!(string, "{:.3}s", duration.as_secs_f64())?;
+    } else if duration.as_millis() > 0 {
+        write!(string, "{:.3}ms", duration.as_secs_f64() * 1e3)?;
+    } else {
+        write!(string, "{:.3}µs", duration.as_secs_f64() * 1e6)?;
+    }
+
+    Ok(string)
+}
@@ -0,0 +1,552 @@
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{self, String, Vec};
+use crate::ast::{self, Spanned};
+use crate::cli::{ExitCode, Io, SharedFlags};
+use crate::compile;
+use crate::diagnostics::{Diagnostic, LintLevel};
+use crate::runtime::{Formatter, Value};
+use crate::termcolor::WriteColor;
+use crate::{Context, Diagnostics, Options, Source, SourceId, Sources, Unit, Vm};
+
+/// The name of the function each input is compiled into.
+const ENTRY: &str = "__repl";
+
+/// The name of the source each input is compiled from.
+const SOURCE: &str = "<repl>";
+
+/// An item declared by an input, like a function or a `use` declaration.
+struct Item {
+    /// The name of the item, if it declares one. Declaring another item with
+    /// the same name replaces it.
+    name: Option<String>,
+    /// The source of the item.
+    source: String,
+}
+
+/// Items and bindings which have been declared by earlier inputs.
+#[derive(Default)]
+struct Session {
+    /// Every item declared so far.
+    items: Vec<Item>,
+    /// The bindings declared so far, in the order they were declared.
+    bindings: Vec<(String, Value)>,
+}
+
+impl Session {
+    /// Declare an item, replacing any earlier item with the same name.
+    fn declare(&mut self, item: Item) -> alloc::Result<()> {
+        if item.name.is_some() {
+            if let Some(existing) = self.items.iter_mut().find(|i| i.name == item.name) {
+                *existing = item;
+                return Ok(());
+            }
+        }
+
+        self.items.try_push(item)
+    }
+}
+
+/// An input which has been split up into items and statements.
+struct Input {
+    /// The items declared by the input.
+    items: Vec<Item>,
+    /// The source of statements evaluated by the input, excluding the final
+    /// expression.
+    body: String,
+    /// The source of the final expression of the input, if any.
+    value: Option<String>,
+    /// Bindings declared by `let` statements in the input.
+    names: Vec<String>,
+}
+
+/// The result of parsing an input.
+enum Parsed {
+    /// The input is incomplete, like an unclosed block.
+    Incomplete,
+    /// The input failed to parse.
+    Error(compile::Error, String),
+    /// The input was parsed.
+    Complete(Input),
+}
+
+pub(super) async fn run(io: &mut Io<'_>, context: &Context, shared: &SharedFlags) -> Result<ExitCode> {
+    let mut options = Options::default();
+
+    for option in &shared.compiler_options {
+        options.parse_option(option)?;
+    }
+
+    shared.lints(&mut options)?;
+
+    let runtime = Arc::new(context.runtime()?);
+    let mut vm = Vm::new(runtime, Arc::new(Unit::default()));
+    let mut session = Session::default();
+
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    let mut input = String::new();
+    let mut line = std::string::String::new();
+
+    loop {
+        let prompt = if input.is_empty() { "> " } else { ". " };
+        write!(io.stdout, "{prompt}")?;
+        io.stdout.flush()?;
+
+        line.clear();
+
+        if stdin.read_line(&mut line)? == 0 {
+            writeln!(io.stdout)?;
+            break;
+        }
+
+        let continued = !input.is_empty();
+        input.try_push_str(&line)?;
+
+        if input.trim().is_empty() {
+            input.clear();
+            continue;
+        }
+
+        let parsed = parse(&input)?;
+
+        // An empty line forces an incomplete input to be evaluated, so that
+        // its errors can be reported.
+        let parsed = match parsed {
+            Parsed::Incomplete if !continued || !line.trim().is_empty() => continue,
+            Parsed::Incomplete => parse_complete(&input)?,
+            parsed => parsed,
+        };
+
+        input.clear();
+
+        match parsed {
+            Parsed::Incomplete => {}
+            Parsed::Error(error, source) => {
+                let mut sources = Sources::new();
+                let source_id = sources.insert(Source::new(SOURCE, source)?)?;
+
+                let mut diagnostics = Diagnostics::new();
+                diagnostics.error(source_id, error)?;
+                diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+            }
+            Parsed::Complete(input) => {
+                evaluate(
+                    io.stdout,
+                    context,
+                    &options,
+                    shared.warnings,
+                    &mut vm,
+                    &mut session,
+                    input,
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Compile and run a single input.
+async fn evaluate<O>(
+    out: &mut O,
+    context: &Context,
+    options: &Options,
+    warnings: bool,
+    vm: &mut Vm,
+    session: &mut Session,
+    input: Input,
+) -> Result<()>
+where
+    O: WriteColor,
+{
+    let mut names = Vec::new();
+
+    for (name, _) in &session.bindings {
+        names.try_push(name.try_clone()?)?;
+    }
+
+    for name in &input.names {
+        if !names.contains(name) {
+            names.try_push(name.try_clone()?)?;
+        }
+    }
+
+    let mut source = String::new();
+
+    for item in &session.items {
+        if item.name.is_some() && input.items.iter().any(|i| i.name == item.name) {
+            continue;
+        }
+
+        writeln!(source, "{}", item.source)?;
+    }
+
+    // The ranges of the source which are compiled from the new input, as
+    // opposed to earlier inputs or the code generated around it.
+    let mut ranges = Vec::new();
+    let start = source.len();
+
+    for item in &input.items {
+        writeln!(source, "{}", item.source)?;
+    }
+
+    ranges.try_push(start..source.len())?;
+
+    writeln!(source, "pub async fn {ENTRY}(__bindings) {{")?;
+
+    if !session.bindings.is_empty() {
+        let bound: String = session
+            .bindings
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .try_join(", ")?;
+
+        writeln!(source, "let [{bound}] = __bindings;")?;
+    }
+
+    let start = source.len();
+    source.try_push_str(&input.body)?;
+    ranges.try_push(start..source.len())?;
+
+    match &input.value {
+        Some(value) => {
+            write!(source, "let __value = ")?;
+            let start = source.len();
+            source.try_push_str(value)?;
+            ranges.try_push(start..source.len())?;
+            writeln!(source, ";")?;
+        }
+        None => writeln!(source, "let __value = ();")?,
+    }
+
+    let returned: String = names.iter().map(|name| name.as_str()).try_join(", ")?;
+    writeln!(source, "(__value, [{returned}])")?;
+    writeln!(source, "}}")?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(SOURCE, source)?)?;
+
+    let mut diagnostics = if warnings {
+        Diagnostics::new()
+    } else {
+        Diagnostics::without_warnings()
+    };
+
+    let result = crate::prepare(&mut sources)
+        .with_context(context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(options)
+        .build();
+
+    // Diagnostics for earlier inputs have already been reported, unless the
+    // new input makes them fail to build, like by redefining an item they
+    // use.
+    let all = result.is_err()
+        && !diagnostics
+            .diagnostics()
+            .iter()
+            .any(|d| is_error(d) && is_in_input(d, &ranges));
+
+    for diagnostic in diagnostics.diagnostics() {
+        if !all && !is_in_input(diagnostic, &ranges) {
+            continue;
+        }
+
+        match diagnostic {
+            Diagnostic::Fatal(d) => d.emit(out, &sources)?,
+            Diagnostic::Warning(d) => d.emit(out, &sources)?,
+            Diagnostic::RuntimeWarning(d) => d.emit(out, &sources, None, None)?,
+        }
+    }
+
+    let Ok(unit) = result else {
+        return Ok(());
+    };
+
+    for item in input.items {
+        session.declare(item)?;
+    }
+
+    *vm.unit_mut() = Arc::new(unit);
+
+    let mut bindings = Vec::new();
+
+    for (_, value) in &session.bindings {
+        bindings.try_push(value.clone())?;
+    }
+
+    let result = match vm.async_call([ENTRY], (bindings,)).await {
+        Ok(result) => result,
+        Err(error) => {
+            error.emit(out, &sources)?;
+            return Ok(());
+        }
+    };
+
+    let (value, values) = crate::from_value::<(Value, Vec<Value>)>(result)?;
+
+    session.bindings.clear();
+
+    for (name, value) in names.into_iter().zip(values) {
+        session.bindings.try_push((name, value))?;
+    }
+
+    if value.into_unit().is_ok() {
+        return Ok(());
+    }
+
+    let mut f = Formatter::new();
+
+    if let Err(error) = vm.with(|| value.string_debug(&mut f)).into_result() {
+        error.emit(out, &sources)?;
+        return Ok(());
+    }
+
+    writeln!(out, "{}", f.as_str())?;
+    Ok(())
+}
+
+/// Test if a diagnostic causes the build to fail.
+fn is_error(diagnostic: &Diagnostic) -> bool {
+    match diagnostic {
+        Diagnostic::Fatal(..) => true,
+        Diagnostic::Warning(d) => d.level() == LintLevel::Deny,
+        Diagnostic::RuntimeWarning(..) => false,
+    }
+}
+
+/// Test if a diagnostic refers to any of the given ranges of the source, which
+/// were compiled from the new input.
+fn is_in_input(diagnostic: &Diagnostic, ranges: &[Range<usize>]) -> bool {
+    let span = match diagnostic {
+        Diagnostic::Fatal(d) => d.span(),
+        Diagnostic::Warning(d) => Some(d.span()),
+        Diagnostic::RuntimeWarning(..) => None,
+    };
+
+    span.map_or(true, |span| {
+        ranges
+            .iter()
+            .any(|range| range.contains(&span.start.into_usize()))
+    })
+}
+
+/// Parse an input, treating it as incomplete if it ends before the parser
+/// expects it to.
+fn parse(input: &str) -> Result<Parsed> {
+    parse_with(input, true)
+}
+
+/// Parse an input, reporting all errors.
+fn parse_complete(input: &str) -> Result<Parsed> {
+    parse_with(input, false)
+}
+
+fn parse_with(input: &str, detect_incomplete: bool) -> Result<Parsed> {
+    // The input is parsed as the statements of a block, which allows it to
+    // contain both items and expressions.
+    let mut source = String::new();
+    writeln!(source, "{{")?;
+    source.try_push_str(input)?;
+    writeln!(source, "}}")?;
+
+    let end = source.len() - 2;
+
+    let block = match crate::parse::parse_all::<ast::Block>(&source, SourceId::empty(), false) {
+        Ok(block) => block,
+        Err(error) if detect_incomplete && error.span().start.into_usize() >= end => {
+            return Ok(Parsed::Incomplete);
+        }
+        Err(error) => return Ok(Parsed::Error(error, source)),
+    };
+
+    let mut input = Input {
+        items: Vec::new(),
+        body: String::new(),
+        value: None,
+        names: Vec::new(),
+    };
+
+    let mut statements = block.statements.iter().peekable();
+
+    while let Some(stmt) = statements.next() {
+        let text = &source[stmt.span().range()];
+
+        match stmt {
+            ast::Stmt::Item(item, _) => {
+                input.items.try_push(Item {
+                    name: item_name(&source, item)?,
+                    source: text.try_to_owned()?,
+                })?;
+            }
+            ast::Stmt::Expr(..) if statements.peek().is_none() => {
+                input.value = Some(text.try_to_owned()?);
+            }
+            ast::Stmt::Local(local) => {
+                bindings(&source, &local.pat, &mut input.names)?;
+                writeln!(input.body, "{text}")?;
+            }
+            _ => {
+                writeln!(input.body, "{text}")?;
+            }
+        }
+    }
+
+    Ok(Parsed::Complete(input))
+}
+
+/// Get the name declared by an item, if any.
+fn item_name(source: &str, item: &ast::Item) -> Result<Option<String>> {
+    let ident = match item {
+        ast::Item::Fn(item) => &item.name,
+        ast::Item::Enum(item) => &item.name,
+        ast::Item::Struct(item) => &item.ident,
+        ast::Item::Trait(item) => &item.name,
+        ast::Item::Mod(item) => &item.name,
+        ast::Item::Const(item) => &item.name,
+        ast::Item::Use(..) | ast::Item::Impl(..) | ast::Item::MacroCall(..) => return Ok(None),
+    };
+
+    Ok(Some(source[ident.span().range()].try_to_owned()?))
+}
+
+/// Collect the names bound by a pattern.
+fn bindings(source: &str, pat: &ast::Pat, names: &mut Vec<String>) -> Result<()> {
+    match pat {
+        ast::Pat::Path(pat) => {
+            if let Some(ident) = pat.path.try_as_ident() {
+                let name = source[ident.span().range()].try_to_owned()?;
+
+                if !names.contains(&name) {
+                    names.try_push(name)?;
+                }
+            }
+        }
+        ast::Pat::Vec(pat) => {
+            for (pat, _) in &pat.items {
+                bindings(source, pat, names)?;
+            }
+        }
+        ast::Pat::Tuple(pat) => {
+            for (pat, _) in &pat.items {
+                bindings(source, pat, names)?;
+            }
+        }
+        ast::Pat::Object(pat) => {
+            for (pat, _) in &pat.items {
+                bindings(source, pat, names)?;
+            }
+        }
+        ast::Pat::Binding(pat) => {
+            bindings(source, &pat.pat, names)?;
+        }
+        ast::Pat::Ignore(..) | ast::Pat::Lit(..) | ast::Pat::Rest(..) => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+    use std::sync::Arc;
+
+    use crate::termcolor::NoColor;
+    use crate::{Context, Options, Unit, Vm};
+
+    use super::{evaluate, parse, Parsed, Session};
+
+    struct Repl {
+        warnings: bool,
+        context: Context,
+        vm: Vm,
+        session: Session,
+    }
+
+    impl Repl {
+        fn new() -> Self {
+            let context = Context::with_default_modules().unwrap();
+            let runtime = Arc::new(context.runtime().unwrap());
+            let vm = Vm::new(runtime, Arc::new(Unit::default()));
+
+            Self {
+                warnings: false,
+                context,
+                vm,
+                session: Session::default(),
+            }
+        }
+
+        /// Evaluate a complete input and return its output.
+        fn eval(&mut self, input: &str) -> String {
+            let Parsed::Complete(input) = parse(input).unwrap() else {
+                panic!("expected complete input: {input:?}");
+            };
+
+            let mut out = NoColor::new(std::vec::Vec::new());
+            let options = Options::default();
+
+            let future = evaluate(
+                &mut out,
+                &self.context,
+                &options,
+                self.warnings,
+                &mut self.vm,
+                &mut self.session,
+                input,
+            );
+
+            futures_executor::block_on(future).unwrap();
+            String::from_utf8(out.into_inner()).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_incomplete() {
+        assert!(matches!(parse("fn foo() {\n").unwrap(), Parsed::Incomplete));
+        assert!(matches!(parse("if true {\n").unwrap(), Parsed::Incomplete));
+        assert!(matches!(parse("1 +\n").unwrap(), Parsed::Incomplete));
+        assert!(matches!(parse("1 + 2\n").unwrap(), Parsed::Complete(..)));
+        assert!(matches!(parse("1 + )\n").unwrap(), Parsed::Error(..)));
+    }
+
+    #[test]
+    fn test_let_persists() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.eval("let a = 40;\n"), "");
+        assert_eq!(repl.eval("let b = a + 1;\n"), "");
+        assert_eq!(repl.eval("a + b - 39\n"), "42\n");
+    }
+
+    #[test]
+    fn test_redefine_fn() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.eval("fn foo() { 1 }\n"), "");
+        assert_eq!(repl.eval("fn bar() { foo() + 1 }\n"), "");
+        assert_eq!(repl.eval("foo()\n"), "1\n");
+        assert_eq!(repl.eval("fn foo() { 2 }\n"), "");
+        assert_eq!(repl.eval("(foo(), bar())\n"), "(2, 3)\n");
+    }
+
+    #[test]
+    fn test_diagnostics_for_new_input() {
+        let mut repl = Repl::new();
+        repl.warnings = true;
+
+        let out = repl.eval("pub fn foo() { let a = `Hello`; a }\n");
+        assert!(out.contains("template string without expansions"), "{out}");
+
+        // The warning in `foo` isn't reported again, and neither are warnings
+        // for the code which passes bindings between inputs.
+        assert_eq!(repl.eval("let a = foo();\n"), "");
+        assert_eq!(repl.eval("a\n"), "\"Hello\"\n");
+    }
+}
@@ -1,12 +1,13 @@
 use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
 
-use crate::cli::{AssetKind, CommandBase, Config, ExitCode, Io, SharedFlags};
-use crate::runtime::{UnitStorage, VmError, VmExecution, VmResult};
+use crate::cli::{profile, AssetKind, CommandBase, Config, ExitCode, Io, SharedFlags};
+use crate::runtime::{Profiler, UnitStorage, VmError, VmExecution, VmResult};
 use crate::{Context, Sources, Unit, Value, Vm};
 
 #[derive(Parser, Debug)]
@@ -21,6 +22,19 @@ pub(super) struct Flags {
     /// Time how long the script took to execute.
     #[arg(long)]
     time: bool,
+    /// Check type annotations while the script runs.
+    #[arg(long)]
+    runtime_type_checks: bool,
+    /// Attribute time and instructions to the functions called by the script,
+    /// and write them as folded stacks which can be rendered as a flamegraph.
+    #[arg(long)]
+    profile: bool,
+    /// The path to write folded stacks to when profiling.
+    #[arg(long, default_value = "profile.folded")]
+    profile_path: PathBuf,
+    /// The number of functions to list after profiling.
+    #[arg(long, default_value = "20")]
+    profile_top: usize,
     /// Perform a default dump.
     #[arg(short, long)]
     dump: bool,
@@ -63,6 +77,11 @@ pub(super) struct Flags {
 }
 
 impl CommandBase for Flags {
+    #[inline]
+    fn is_runtime_type_checks(&self) -> bool {
+        self.runtime_type_checks
+    }
+
     #[inline]
     fn is_workspace(&self, kind: AssetKind) -> bool {
         matches!(kind, AssetKind::Bin)
@@ -207,7 +226,15 @@ pub(super) async fn run(
 
     let last = Instant::now();
 
+    let profiler = if args.profile {
+        Some(Profiler::new(unit.clone())?)
+    } else {
+        None
+    };
+
     let mut vm = Vm::new(runtime, unit);
+    vm.set_profiler(profiler.clone());
+
     let mut execution: VmExecution<_> = vm.execute(["main"], ())?;
 
     let result = if args.trace {
@@ -249,6 +276,10 @@ pub(super) async fn run(
         }
     };
 
+    if let Some(profiler) = &profiler {
+        profile::emit(io, context, profiler, &args.profile_path, args.profile_top)?;
+    }
+
     let exit = if let Some(error) = errored {
         error.emit(io.stdout, sources)?;
         ExitCode::VmError
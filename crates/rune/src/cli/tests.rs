@@ -12,12 +12,15 @@ use crate::cli::{
 };
 use crate::compile::{FileSourceLoader, ItemBuf};
 use crate::doc::TestParams;
+use crate::cli::coverage;
 use crate::modules::capture_io::CaptureIo;
-use crate::runtime::{UnitFn, Value, Vm, VmError, VmResult, ValueKind};
+use crate::runtime::{Coverage, UnitFn, Value, Vm, VmError, VmResult, ValueKind};
 use crate::termcolor::{Color, ColorSpec, WriteColor};
 use crate::{Diagnostics, Hash, Source, Sources, Unit};
 
 mod cli {
+    use std::path::PathBuf;
+
     use ::rust_alloc::string::String;
     use ::rust_alloc::vec::Vec;
     use clap::Parser;
@@ -36,6 +39,16 @@ mod cli {
         /// Break on the first test failed.
         #[arg(long)]
         pub fail_fast: bool,
+        /// Check type annotations while tests run.
+        #[arg(long)]
+        pub runtime_type_checks: bool,
+        /// Record which lines and functions are executed by tests, and write
+        /// the result to an LCOV file.
+        #[arg(long)]
+        pub coverage: bool,
+        /// The path to write the LCOV file to when recording coverage.
+        #[arg(long, default_value = "lcov.info")]
+        pub coverage_path: PathBuf,
     }
 }
 
@@ -47,6 +60,11 @@ impl CommandBase for Flags {
         true
     }
 
+    #[inline]
+    fn is_runtime_type_checks(&self) -> bool {
+        self.runtime_type_checks
+    }
+
     #[inline]
     fn is_workspace(&self, kind: AssetKind) -> bool {
         matches!(kind, AssetKind::Test)
@@ -88,6 +106,7 @@ where
 
     let mut doc_visitors = Vec::new();
     let mut cases = Vec::new();
+    let mut coverages = Vec::new();
     let mut naming = Naming::default();
 
     let mut include_std = false;
@@ -104,6 +123,7 @@ where
     }
 
     for e in entries {
+        let options = &e.options(options, shared)?;
         let name = naming.name(&e)?;
         let item = ItemBuf::with_crate(&name)?;
 
@@ -147,14 +167,25 @@ where
 
         doc_visitors.try_push(doc_visitor)?;
 
+        let coverage = if flags.coverage {
+            let coverage = Coverage::new(unit.clone())?;
+            coverages.try_push((coverage.clone(), sources.clone()))?;
+            Some(coverage)
+        } else {
+            None
+        };
+
         for (hash, item) in functions.into_functions() {
-            cases.try_push(TestCase::new(
+            let mut case = TestCase::new(
                 hash,
                 item,
                 unit.clone(),
                 sources.clone(),
                 TestParams::default(),
-            ))?;
+            );
+
+            case.coverage = coverage.clone();
+            cases.try_push(case)?;
         }
     }
 
@@ -232,6 +263,7 @@ where
         executed = executed.wrapping_add(1);
 
         let mut vm = Vm::new(runtime.clone(), case.unit.clone());
+        vm.set_coverage(case.coverage.clone());
         case.execute(&mut vm, &capture).await?;
 
         if case.outcome.is_ok() {
@@ -277,6 +309,18 @@ where
         elapsed.as_secs_f64()
     )?;
 
+    if flags.coverage {
+        let mut report = coverage::Report::default();
+
+        for (coverage, sources) in &coverages {
+            report.add(coverage, sources)?;
+        }
+
+        report.write_lcov(&flags.coverage_path)?;
+        report.emit(io.stdout)?;
+        writeln!(io.stdout, "Wrote {}", flags.coverage_path.display())?;
+    }
+
     if build_errors == 0 && failures == 0 {
         Ok(ExitCode::Success)
     } else {
@@ -305,6 +349,7 @@ struct TestCase {
     unit: Arc<Unit>,
     sources: Arc<Sources>,
     params: TestParams,
+    coverage: Option<Coverage>,
     outcome: Outcome,
     output: Vec<u8>,
 }
@@ -323,6 +368,7 @@ impl TestCase {
             unit,
             sources,
             params,
+            coverage: None,
             outcome: Outcome::Ok,
             output: Vec::new(),
         }
@@ -50,6 +50,9 @@ pub(crate) mod v1;
 mod options;
 pub use self::options::{Options, ParseOptionError};
 
+mod parse_cache;
+pub use self::parse_cache::ParseCache;
+
 mod location;
 pub(crate) use self::location::DynLocation;
 pub use self::location::{Located, Location};
@@ -29,6 +29,11 @@ pub(crate) enum AssemblyInst {
         label: Label,
         out: Output,
     },
+    JumpIfNotEqInteger {
+        addr: InstAddress,
+        value: i64,
+        label: Label,
+    },
     Raw {
         raw: Inst,
     },
@@ -49,6 +54,8 @@ pub(crate) struct Assembly {
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
     pub(crate) required_functions: HashMap<Hash, Vec<(Span, SourceId)>>,
+    /// Named variables which are available starting at the given instruction.
+    pub(crate) variables: HashMap<usize, Vec<(String, InstAddress)>>,
 }
 
 impl Assembly {
@@ -61,6 +68,7 @@ impl Assembly {
             comments: Default::default(),
             label_count,
             required_functions: Default::default(),
+            variables: Default::default(),
         }
     }
 
@@ -158,6 +166,19 @@ impl Assembly {
         Ok(())
     }
 
+    /// Record a named variable stored at the given address, which is available
+    /// starting at the next instruction.
+    pub(crate) fn variable(&mut self, name: &str, addr: InstAddress) -> compile::Result<()> {
+        let pos = self.instructions.len();
+
+        self.variables
+            .entry(pos)
+            .or_try_default()?
+            .try_push((name.try_to_owned()?, addr))?;
+
+        Ok(())
+    }
+
     /// Push a raw instruction.
     pub(crate) fn push(&mut self, raw: Inst, span: &dyn Spanned) -> compile::Result<()> {
         self.inner_push(AssemblyInst::Raw { raw }, span)?;
@@ -7,6 +7,7 @@ use crate::ast;
 use crate::ast::{LitStr, Spanned};
 use crate::compile::{self, ErrorKind};
 use crate::parse::{self, Parse, Resolve, ResolveContext};
+use crate::runtime;
 
 /// Helper for parsing internal attributes.
 pub(crate) struct Parser {
@@ -229,3 +230,70 @@ impl Attribute for Doc {
     /// Must match the specified name.
     const PATH: &'static str = "doc";
 }
+
+/// The `#[allow(..)]` attribute, which allows the given lints.
+#[derive(Parse)]
+pub(crate) struct Allow {
+    /// The lints to allow.
+    pub lints: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Attribute for Allow {
+    /// Must match the specified name.
+    const PATH: &'static str = "allow";
+}
+
+/// The `#[warn(..)]` attribute, which reports the given lints as warnings.
+#[derive(Parse)]
+pub(crate) struct Warn {
+    /// The lints to warn about.
+    pub lints: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Attribute for Warn {
+    /// Must match the specified name.
+    const PATH: &'static str = "warn";
+}
+
+/// The `#[deny(..)]` attribute, which reports the given lints as errors.
+#[derive(Parse)]
+pub(crate) struct Deny {
+    /// The lints to deny.
+    pub lints: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Attribute for Deny {
+    /// Must match the specified name.
+    const PATH: &'static str = "deny";
+}
+
+/// The `#[protocol(..)]` attribute, which makes an instance function implement
+/// the given protocol for its type.
+#[derive(Parse)]
+pub(crate) struct Protocol {
+    /// The opening parenthesis.
+    #[allow(dead_code)]
+    pub open: T!['('],
+    /// The name of the protocol.
+    pub name: ast::Ident,
+    /// The closing parenthesis.
+    #[allow(dead_code)]
+    pub close: T![')'],
+}
+
+impl Protocol {
+    /// Resolve the protocol being implemented.
+    pub(crate) fn protocol(&self, cx: ResolveContext<'_>) -> compile::Result<runtime::Protocol> {
+        match self.name.resolve(cx)? {
+            "NEG" => Ok(runtime::Protocol::NEG),
+            "NOT" => Ok(runtime::Protocol::NOT),
+            "CALL" => Ok(runtime::Protocol::CALL),
+            _ => Err(compile::Error::msg(self.name, "unsupported protocol")),
+        }
+    }
+}
+
+impl Attribute for Protocol {
+    /// Must match the specified name.
+    const PATH: &'static str = "protocol";
+}
@@ -4,8 +4,8 @@ use crate::ast;
 use crate::ast::{Span, Spanned};
 use crate::compile::v1;
 use crate::compile::{
-    self, Assembly, CompileVisitor, Context, ErrorKind, Location, Options, Pool, Prelude,
-    SourceLoader, UnitBuilder,
+    self, Assembly, CompileVisitor, Context, ErrorKind, Location, Options, ParseCache, Pool,
+    Prelude, SourceLoader, UnitBuilder,
 };
 use crate::hir;
 use crate::indexing::FunctionAst;
@@ -27,9 +27,12 @@ pub(crate) fn compile(
     visitor: &mut dyn CompileVisitor,
     diagnostics: &mut Diagnostics,
     source_loader: &mut dyn SourceLoader,
+    parse_cache: Option<&mut ParseCache>,
     options: &Options,
     unit_storage: &mut dyn UnitEncoder,
 ) -> alloc::Result<()> {
+    diagnostics.configure_lints(options.lints);
+
     // Shared id generator.
     let gen = Gen::new();
     let const_arena = hir::Arena::new();
@@ -55,7 +58,7 @@ pub(crate) fn compile(
     );
 
     // The worker queue.
-    let mut worker = Worker::new(q);
+    let mut worker = Worker::new(q, parse_cache);
 
     // Queue up the initial sources to be loaded.
     for source_id in worker.q.sources.source_ids() {
@@ -146,6 +149,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
             options: self.options,
             select_branches: Vec::new(),
             drop: Vec::new(),
+            return_type: None,
         })
     }
 
@@ -247,6 +251,13 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     FunctionAst::Empty(ast, span) => hir::lowering::empty_fn(&mut cx, ast, &span)?,
                 };
 
+                hir::check::item_fn(
+                    self.q.borrow(),
+                    location.source_id,
+                    &hir,
+                    self.options.type_check,
+                )?;
+
                 let count = hir.args.len();
 
                 let mut scopes = self::v1::Scopes::new(location.source_id)?;
@@ -257,7 +268,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                 if !self.q.is_used(&item_meta) {
                     self.q
                         .diagnostics
-                        .not_used(location.source_id, span, None)?;
+                        .unused_function(location.source_id, span)?;
                 } else {
                     let instance = match (type_hash, &f.ast) {
                         (Some(type_hash), FunctionAst::Item(ast)) => {
@@ -267,10 +278,14 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                         _ => None,
                     };
 
+                    self::v1::optimize::assembly(&mut asm, size, self.options.opt_level)?;
+
                     self.q.unit.new_function(
                         location,
                         self.q.pool.item(item_meta.item),
                         instance,
+                        f.impl_trait,
+                        f.protocol,
                         count,
                         None,
                         asm,
@@ -310,7 +325,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
 
                 if !c.q.is_used(&item_meta) {
                     c.q.diagnostics
-                        .not_used(location.source_id, &location.span, None)?;
+                        .not_used(location.source_id, &location.span, None, None)?;
                 } else {
                     let captures =
                         c.q.get_captures(captures)
@@ -323,10 +338,14 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                         .len()
                         .saturating_add(usize::from(captures.is_some()));
 
+                    self::v1::optimize::assembly(&mut asm, size, self.options.opt_level)?;
+
                     self.q.unit.new_function(
                         location,
                         self.q.pool.item(item_meta.item),
                         None,
+                        None,
+                        None,
                         args,
                         captures,
                         asm,
@@ -359,14 +378,18 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                 if !self.q.is_used(&item_meta) {
                     self.q
                         .diagnostics
-                        .not_used(location.source_id, &location.span, None)?;
+                        .not_used(location.source_id, &location.span, None, None)?;
                 } else {
                     let args = hir.captures.len();
 
+                    self::v1::optimize::assembly(&mut asm, size, self.options.opt_level)?;
+
                     self.q.unit.new_function(
                         location,
                         self.q.pool.item(item_meta.item),
                         None,
+                        None,
+                        None,
                         args,
                         None,
                         asm,
@@ -383,7 +406,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                 if !item_meta.visibility.is_public() {
                     self.q
                         .diagnostics
-                        .not_used(location.source_id, &location.span, None)?;
+                        .not_used(location.source_id, &location.span, None, None)?;
                 }
             }
             Build::Import(import) => {
@@ -403,7 +426,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                 if !self.q.is_used(&item_meta) {
                     self.q
                         .diagnostics
-                        .not_used(location.source_id, &location.span, None)?;
+                        .unused_import(location.source_id, &location.span)?;
                 }
 
                 let missing = match result {
@@ -481,7 +504,7 @@ where
             ast::FnArg::SelfValue(..) => {
                 args.try_push(Box::try_from("self")?)?;
             }
-            ast::FnArg::Pat(pat) => {
+            ast::FnArg::Pat(pat) | ast::FnArg::Typed(ast::PatType { pat, .. }) => {
                 let span = pat.span();
 
                 if let Some(s) = sources.source(location.source_id, span) {
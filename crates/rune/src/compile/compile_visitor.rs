@@ -25,6 +25,17 @@ pub trait CompileVisitor {
         Ok(())
     }
 
+    /// Visit a use of the named field of the struct or variant identified by
+    /// `hash`, such as in an object literal or an object pattern.
+    fn visit_field_use(
+        &mut self,
+        _location: &dyn Located,
+        _hash: Hash,
+        _field: &str,
+    ) -> Result<(), MetaError> {
+        Ok(())
+    }
+
     /// Visit something that is a module.
     fn visit_mod(&mut self, _location: &dyn Located) -> Result<(), MetaError> {
         Ok(())
@@ -97,6 +97,8 @@ pub struct Context {
     functions: hash::Map<Arc<FunctionHandler>>,
     /// Registered deprecation mesages for native functions.
     deprecations: hash::Map<String>,
+    /// Native functions which can be called in constant contexts.
+    const_functions: HashSet<Hash>,
     /// Information on associated types.
     #[cfg(feature = "doc")]
     associated: HashMap<Hash, Vec<Hash>>,
@@ -154,6 +156,9 @@ impl Context {
         this.install(crate::modules::fmt::module()?)?;
         this.install(crate::modules::future::module()?)?;
         this.install(crate::modules::i64::module()?)?;
+        this.install(crate::modules::u64::module()?)?;
+        this.install(crate::modules::i128::module()?)?;
+        this.install(crate::modules::u128::module()?)?;
         this.install(crate::modules::io::module(stdio)?)?;
         this.install(crate::modules::iter::module()?)?;
         this.install(crate::modules::macros::module()?)?;
@@ -247,6 +252,11 @@ impl Context {
         })
     }
 
+    /// Iterate over all metadata in the [Context].
+    pub(crate) fn iter_meta(&self) -> impl Iterator<Item = &ContextMeta> {
+        self.meta.iter()
+    }
+
     /// Iterate over all available types in the [Context].
     #[cfg(feature = "cli")]
     pub(crate) fn iter_types(&self) -> impl Iterator<Item = (Hash, &Item)> {
@@ -321,6 +331,16 @@ impl Context {
         self.functions.get(&hash)
     }
 
+    /// Lookup the given native function handler in the context, if the
+    /// function can be called in constant contexts.
+    pub(crate) fn lookup_const_function(&self, hash: Hash) -> Option<&Arc<FunctionHandler>> {
+        if !self.const_functions.contains(&hash) {
+            return None;
+        }
+
+        self.functions.get(&hash)
+    }
+
     /// Get all associated types for the given hash.
     #[cfg(feature = "doc")]
     pub(crate) fn associated(&self, hash: Hash) -> impl Iterator<Item = Hash> + '_ {
@@ -460,7 +480,7 @@ impl Context {
                                 argument_types: Box::default(),
                             };
 
-                            self.insert_native_fn(hash, c, None)?;
+                            self.insert_native_fn(hash, c, None, false)?;
                             Some(signature)
                         }
                         None => None,
@@ -525,7 +545,7 @@ impl Context {
                                 argument_types: Box::default(),
                             };
 
-                            self.insert_native_fn(hash, c, variant.deprecated.as_deref())?;
+                            self.insert_native_fn(hash, c, variant.deprecated.as_deref(), false)?;
                             Some(signature)
                         } else {
                             None
@@ -650,7 +670,12 @@ impl Context {
                         .try_collect()?,
                 };
 
-                self.insert_native_fn(hash, &f.handler, module_item.common.deprecated.as_deref())?;
+                self.insert_native_fn(
+                    hash,
+                    &f.handler,
+                    module_item.common.deprecated.as_deref(),
+                    f.is_const,
+                )?;
 
                 meta::Kind::Function {
                     associated: None,
@@ -672,6 +697,18 @@ impl Context {
                 self.attribute_macros.try_insert(hash, m.handler.clone())?;
                 meta::Kind::AttributeMacro
             }
+            rune::module::ModuleItemKind::Trait(trait_) => {
+                let mut functions = Vec::new();
+
+                for name in &trait_.functions {
+                    functions.try_push(meta::TraitFn {
+                        name: name.try_clone()?,
+                        has_default: false,
+                    })?;
+                }
+
+                meta::Kind::Trait { functions }
+            }
             rune::module::ModuleItemKind::InternalEnum(internal_enum) => {
                 if !self.internal_enums.try_insert(internal_enum.static_type)? {
                     return Err(ContextError::InternalAlreadyPresent {
@@ -718,6 +755,7 @@ impl Context {
                             variant_hash,
                             constructor,
                             variant.deprecated.as_deref(),
+                            false,
                         )?;
 
                         Some(meta::Signature {
@@ -853,10 +891,20 @@ impl Context {
                         ConstValue::String(item.try_to_string()?),
                     )?;
 
-                    self.insert_native_fn(*hash, &f.handler, assoc.common.deprecated.as_deref())?;
+                    self.insert_native_fn(
+                        *hash,
+                        &f.handler,
+                        assoc.common.deprecated.as_deref(),
+                        f.is_const,
+                    )?;
                 }
 
-                self.insert_native_fn(hash, &f.handler, assoc.common.deprecated.as_deref())?;
+                self.insert_native_fn(
+                    hash,
+                    &f.handler,
+                    assoc.common.deprecated.as_deref(),
+                    f.is_const,
+                )?;
 
                 meta::Kind::Function {
                     associated: Some(assoc.name.kind.try_clone()?),
@@ -892,6 +940,7 @@ impl Context {
         hash: Hash,
         handler: &Arc<FunctionHandler>,
         deprecation: Option<&str>,
+        is_const: bool,
     ) -> Result<(), ContextError> {
         if self.functions.contains_key(&hash) {
             return Err(ContextError::ConflictingFunction { hash });
@@ -901,6 +950,11 @@ impl Context {
         if let Some(msg) = deprecation {
             self.deprecations.try_insert(hash, msg.try_to_owned()?)?;
         }
+
+        if is_const {
+            self.const_functions.try_insert(hash)?;
+        }
+
         Ok(())
     }
 
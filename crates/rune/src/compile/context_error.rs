@@ -32,6 +32,13 @@ pub enum ContextError {
         item: ItemBuf,
         hash: Hash,
     },
+    ConflictingTrait {
+        item: ItemBuf,
+        hash: Hash,
+    },
+    ConflictingTraitFunction {
+        name: Box<str>,
+    },
     ConflictingInstanceFunction {
         type_info: TypeInfo,
         name: Box<str>,
@@ -159,6 +166,12 @@ impl fmt::Display for ContextError {
             ContextError::ConflictingConstantName { item, hash } => {
                 write!(f, "Constant `{item}` already exists with hash `{hash}`")?;
             }
+            ContextError::ConflictingTrait { item, hash } => {
+                write!(f, "Trait `{item}` already exists with hash `{hash}`")?;
+            }
+            ContextError::ConflictingTraitFunction { name } => {
+                write!(f, "Trait function `{name}` is declared multiple times")?;
+            }
             ContextError::ConflictingInstanceFunction { type_info, name } => {
                 write!(
                     f,
@@ -11,6 +11,7 @@ use crate::ast::unescape;
 use crate::ast::{Span, Spanned};
 use crate::compile::ir;
 use crate::compile::{HasSpan, ItemBuf, Location, MetaInfo, Visibility};
+use crate::diagnostics::Suggestion;
 use crate::indexing::items::{GuardMismatch, MissingLastId};
 use crate::macros::{SyntheticId, SyntheticKind};
 use crate::parse::{Expectation, IntoExpectation, LexerMode};
@@ -31,6 +32,8 @@ pub struct Error {
     // Errors are exempt from fallible allocations since they're not commonly
     // constructed.
     kind: rust_alloc::boxed::Box<ErrorKind>,
+    // Suggested changes which would address the error.
+    suggestions: rust_alloc::vec::Vec<Suggestion>,
 }
 
 impl Error {
@@ -43,6 +46,7 @@ impl Error {
         Self {
             span: span.span(),
             kind: rust_alloc::boxed::Box::new(ErrorKind::from(kind)),
+            suggestions: rust_alloc::vec::Vec::new(),
         }
     }
 
@@ -55,9 +59,21 @@ impl Error {
         Self {
             span: span.span(),
             kind: rust_alloc::boxed::Box::new(ErrorKind::msg(message)),
+            suggestions: rust_alloc::vec::Vec::new(),
         }
     }
 
+    /// Add a suggested change which would address the error.
+    pub(crate) fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// Suggested changes which would address the error.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
     /// Get the kind of the error.
     #[cfg(feature = "emit")]
     pub(crate) fn kind(&self) -> &ErrorKind {
@@ -296,6 +312,14 @@ pub(crate) enum ErrorKind {
         field: Box<str>,
         item: ItemBuf,
     },
+    UnknownField {
+        field: Box<str>,
+        item: ItemBuf,
+    },
+    TypeMismatch {
+        expected: Box<str>,
+        actual: Box<str>,
+    },
     UnsupportedAssignExpr,
     UnsupportedBinaryExpr,
     UnsupportedRef,
@@ -312,6 +336,18 @@ pub(crate) enum ErrorKind {
         object: Span,
     },
     InstanceFunctionOutsideImpl,
+    MissingTraitFunction {
+        trait_item: ItemBuf,
+        name: Box<str>,
+    },
+    UnknownTraitFunction {
+        trait_item: ItemBuf,
+        name: Box<str>,
+    },
+    ConflictingTraitImpl {
+        item: ItemBuf,
+        trait_item: ItemBuf,
+    },
     UnsupportedTupleIndex {
         number: ast::Number,
     },
@@ -513,6 +549,9 @@ pub(crate) enum ErrorKind {
     UnsupportedPatternRest,
     UnsupportedMut,
     UnsupportedSuffix,
+    UnknownLint {
+        name: Box<str>,
+    },
 }
 
 impl ErrorKind {
@@ -524,6 +563,143 @@ impl ErrorKind {
             error: anyhow::Error::msg(message),
         }
     }
+
+    /// A stable, machine-readable code identifying the kind of error.
+    #[cfg(feature = "emit-json")]
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Custom { .. } => "custom",
+            ErrorKind::Expected { .. } => "expected",
+            ErrorKind::Unsupported { .. } => "unsupported",
+            ErrorKind::AllocError { .. } => "alloc_error",
+            ErrorKind::CapacityError { .. } => "capacity_error",
+            ErrorKind::IrError(..) => "ir_error",
+            ErrorKind::MetaError(..) => "meta_error",
+            ErrorKind::AccessError(..) => "access_error",
+            ErrorKind::VmError(..) => "vm_error",
+            ErrorKind::EncodeError(..) => "encode_error",
+            ErrorKind::MissingLastId(..) => "missing_last_id",
+            ErrorKind::GuardMismatch(..) => "guard_mismatch",
+            ErrorKind::MissingScope(..) => "missing_scope",
+            ErrorKind::PopError(..) => "pop_error",
+            ErrorKind::MissingId(..) => "missing_id",
+            ErrorKind::UnescapeError(..) => "unescape_error",
+            ErrorKind::SourceError { .. } => "source_error",
+            ErrorKind::ModNotFound { .. } => "mod_not_found",
+            ErrorKind::ModAlreadyLoaded { .. } => "mod_already_loaded",
+            ErrorKind::MissingMacro { .. } => "missing_macro",
+            ErrorKind::MissingSelf => "missing_self",
+            ErrorKind::MissingLocal { .. } => "missing_local",
+            ErrorKind::MissingItem { .. } => "missing_item",
+            ErrorKind::MissingItemHash { .. } => "missing_item_hash",
+            ErrorKind::MissingItemParameters { .. } => "missing_item_parameters",
+            ErrorKind::UnsupportedGlobal => "unsupported_global",
+            ErrorKind::UnsupportedModuleSource => "unsupported_module_source",
+            ErrorKind::UnsupportedModuleRoot { .. } => "unsupported_module_root",
+            ErrorKind::UnsupportedModuleItem { .. } => "unsupported_module_item",
+            ErrorKind::UnsupportedSelf => "unsupported_self",
+            ErrorKind::UnsupportedUnaryOp { .. } => "unsupported_unary_op",
+            ErrorKind::UnsupportedBinaryOp { .. } => "unsupported_binary_op",
+            ErrorKind::UnsupportedLitObject { .. } => "unsupported_lit_object",
+            ErrorKind::LitObjectMissingField { .. } => "lit_object_missing_field",
+            ErrorKind::LitObjectNotField { .. } => "lit_object_not_field",
+            ErrorKind::UnknownField { .. } => "unknown_field",
+            ErrorKind::TypeMismatch { .. } => "type_mismatch",
+            ErrorKind::UnsupportedAssignExpr => "unsupported_assign_expr",
+            ErrorKind::UnsupportedBinaryExpr => "unsupported_binary_expr",
+            ErrorKind::UnsupportedRef => "unsupported_ref",
+            ErrorKind::UnsupportedArgumentCount { .. } => "unsupported_argument_count",
+            ErrorKind::UnsupportedPatternExpr => "unsupported_pattern_expr",
+            ErrorKind::UnsupportedBinding => "unsupported_binding",
+            ErrorKind::DuplicateObjectKey { .. } => "duplicate_object_key",
+            ErrorKind::InstanceFunctionOutsideImpl => "instance_function_outside_impl",
+            ErrorKind::MissingTraitFunction { .. } => "missing_trait_function",
+            ErrorKind::UnknownTraitFunction { .. } => "unknown_trait_function",
+            ErrorKind::ConflictingTraitImpl { .. } => "conflicting_trait_impl",
+            ErrorKind::UnsupportedTupleIndex { .. } => "unsupported_tuple_index",
+            ErrorKind::BreakUnsupported => "break_unsupported",
+            ErrorKind::BreakUnsupportedValue => "break_unsupported_value",
+            ErrorKind::ContinueUnsupported => "continue_unsupported",
+            ErrorKind::ContinueUnsupportedBlock => "continue_unsupported_block",
+            ErrorKind::SelectMultipleDefaults => "select_multiple_defaults",
+            ErrorKind::ExpectedBlockSemiColon { .. } => "expected_block_semi_colon",
+            ErrorKind::FnConstAsyncConflict => "fn_const_async_conflict",
+            ErrorKind::BlockConstAsyncConflict => "block_const_async_conflict",
+            ErrorKind::ClosureKind => "closure_kind",
+            ErrorKind::UnsupportedSelfType => "unsupported_self_type",
+            ErrorKind::UnsupportedSuper => "unsupported_super",
+            ErrorKind::UnsupportedSuperInSelfType => "unsupported_super_in_self_type",
+            ErrorKind::UnsupportedAfterGeneric => "unsupported_after_generic",
+            ErrorKind::IllegalUseSegment => "illegal_use_segment",
+            ErrorKind::UseAliasNotSupported => "use_alias_not_supported",
+            ErrorKind::FunctionConflict { .. } => "function_conflict",
+            ErrorKind::FunctionReExportConflict { .. } => "function_re_export_conflict",
+            ErrorKind::ConstantConflict { .. } => "constant_conflict",
+            ErrorKind::StaticStringMissing { .. } => "static_string_missing",
+            ErrorKind::StaticBytesMissing { .. } => "static_bytes_missing",
+            ErrorKind::StaticStringHashConflict { .. } => "static_string_hash_conflict",
+            ErrorKind::StaticBytesHashConflict { .. } => "static_bytes_hash_conflict",
+            ErrorKind::StaticObjectKeysMissing { .. } => "static_object_keys_missing",
+            ErrorKind::StaticObjectKeysHashConflict { .. } => "static_object_keys_hash_conflict",
+            ErrorKind::MissingLabel { .. } => "missing_label",
+            ErrorKind::ExpectedLeadingPathSegment => "expected_leading_path_segment",
+            ErrorKind::UnsupportedVisibility => "unsupported_visibility",
+            ErrorKind::ExpectedMeta { .. } => "expected_meta",
+            ErrorKind::NoSuchBuiltInMacro { .. } => "no_such_built_in_macro",
+            ErrorKind::VariableMoved { .. } => "variable_moved",
+            ErrorKind::UnsupportedGenerics => "unsupported_generics",
+            ErrorKind::NestedTest { .. } => "nested_test",
+            ErrorKind::NestedBench { .. } => "nested_bench",
+            ErrorKind::MissingFunctionHash { .. } => "missing_function_hash",
+            ErrorKind::FunctionConflictHash { .. } => "function_conflict_hash",
+            ErrorKind::PatternMissingFields { .. } => "pattern_missing_fields",
+            ErrorKind::MissingLabelLocation { .. } => "missing_label_location",
+            ErrorKind::MaxMacroRecursion { .. } => "max_macro_recursion",
+            ErrorKind::YieldInConst => "yield_in_const",
+            ErrorKind::AwaitInConst => "await_in_const",
+            ErrorKind::AwaitOutsideAsync => "await_outside_async",
+            ErrorKind::ExpectedEof { .. } => "expected_eof",
+            ErrorKind::UnexpectedEof => "unexpected_eof",
+            ErrorKind::BadLexerMode { .. } => "bad_lexer_mode",
+            ErrorKind::ExpectedEscape => "expected_escape",
+            ErrorKind::UnterminatedStrLit => "unterminated_str_lit",
+            ErrorKind::UnterminatedByteStrLit => "unterminated_byte_str_lit",
+            ErrorKind::UnterminatedCharLit => "unterminated_char_lit",
+            ErrorKind::UnterminatedByteLit => "unterminated_byte_lit",
+            ErrorKind::ExpectedCharClose => "expected_char_close",
+            ErrorKind::ExpectedCharOrLabel => "expected_char_or_label",
+            ErrorKind::ExpectedByteClose => "expected_byte_close",
+            ErrorKind::UnexpectedChar { .. } => "unexpected_char",
+            ErrorKind::PrecedenceGroupRequired => "precedence_group_required",
+            ErrorKind::BadNumberOutOfBounds => "bad_number_out_of_bounds",
+            ErrorKind::BadFieldAccess => "bad_field_access",
+            ErrorKind::ExpectedMacroCloseDelimiter { .. } => "expected_macro_close_delimiter",
+            ErrorKind::MultipleMatchingAttributes { .. } => "multiple_matching_attributes",
+            ErrorKind::MissingSourceId { .. } => "missing_source_id",
+            ErrorKind::ExpectedMultilineCommentTerm => "expected_multiline_comment_term",
+            ErrorKind::BadSlice => "bad_slice",
+            ErrorKind::BadSyntheticId { .. } => "bad_synthetic_id",
+            ErrorKind::BadCharLiteral => "bad_char_literal",
+            ErrorKind::BadByteLiteral => "bad_byte_literal",
+            ErrorKind::BadNumberLiteral => "bad_number_literal",
+            ErrorKind::AmbiguousItem { .. } => "ambiguous_item",
+            ErrorKind::AmbiguousContextItem { .. } => "ambiguous_context_item",
+            ErrorKind::NotVisible { .. } => "not_visible",
+            ErrorKind::NotVisibleMod { .. } => "not_visible_mod",
+            ErrorKind::MissingMod { .. } => "missing_mod",
+            ErrorKind::ImportCycle { .. } => "import_cycle",
+            ErrorKind::ImportRecursionLimit { .. } => "import_recursion_limit",
+            ErrorKind::LastUseComponent => "last_use_component",
+            ErrorKind::VariantRttiConflict { .. } => "variant_rtti_conflict",
+            ErrorKind::TypeRttiConflict { .. } => "type_rtti_conflict",
+            ErrorKind::ArenaWriteSliceOutOfBounds { .. } => "arena_write_slice_out_of_bounds",
+            ErrorKind::ArenaAllocError { .. } => "arena_alloc_error",
+            ErrorKind::UnsupportedPatternRest => "unsupported_pattern_rest",
+            ErrorKind::UnsupportedMut => "unsupported_mut",
+            ErrorKind::UnsupportedSuffix => "unsupported_suffix",
+            ErrorKind::UnknownLint { .. } => "unknown_lint",
+        }
+    }
 }
 
 cfg_std! {
@@ -676,6 +852,15 @@ impl fmt::Display for ErrorKind {
             ErrorKind::LitObjectNotField { field, item } => {
                 write!(f, "Field `{field}` is not a field in `{item}`",)?;
             }
+            ErrorKind::UnknownField { field, item } => {
+                write!(f, "No field `{field}` on `{item}`",)?;
+            }
+            ErrorKind::TypeMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Mismatched types, expected `{expected}` but found `{actual}`",
+                )?;
+            }
             ErrorKind::UnsupportedAssignExpr => {
                 write!(f, "Cannot assign to expression")?;
             }
@@ -703,6 +888,24 @@ impl fmt::Display for ErrorKind {
             ErrorKind::InstanceFunctionOutsideImpl => {
                 write!(f, "Instance function declared outside of `impl` block")?;
             }
+            ErrorKind::MissingTraitFunction { trait_item, name } => {
+                write!(
+                    f,
+                    "Missing function `{name}` which is required by trait `{trait_item}`"
+                )?;
+            }
+            ErrorKind::UnknownTraitFunction { trait_item, name } => {
+                write!(
+                    f,
+                    "Function `{name}` is not a member of trait `{trait_item}`"
+                )?;
+            }
+            ErrorKind::ConflictingTraitImpl { item, trait_item } => {
+                write!(
+                    f,
+                    "Conflicting implementations of trait `{trait_item}` for `{item}`"
+                )?;
+            }
             ErrorKind::UnsupportedTupleIndex { number } => {
                 write!(f, "Unsupported tuple index `{number}`")?;
             }
@@ -1041,6 +1244,9 @@ impl fmt::Display for ErrorKind {
                     "Unsupported suffix, expected one of `u8`, `i64`, or `f64`"
                 )?;
             }
+            ErrorKind::UnknownLint { name } => {
+                write!(f, "Unknown lint `{name}`")?;
+            }
         }
 
         Ok(())
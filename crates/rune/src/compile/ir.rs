@@ -10,6 +10,8 @@ pub(crate) mod scopes;
 
 use core::ops::{AddAssign, MulAssign, ShlAssign, ShrAssign, SubAssign};
 
+use ::rust_alloc::sync::Arc;
+
 use crate as rune;
 use crate::alloc::prelude::*;
 use crate::alloc::{Box, Vec};
@@ -21,7 +23,8 @@ use crate::indexing::index;
 use crate::macros::MacroContext;
 use crate::parse::NonZeroId;
 use crate::query::Used;
-use crate::runtime::{Value, ValueKind};
+use crate::runtime::{Rtti, Value, ValueKind, VariantRtti};
+use crate::Hash;
 
 pub(crate) use self::compiler::Ctxt;
 pub(crate) use self::eval::{eval_ir, EvalOutcome};
@@ -454,10 +457,25 @@ pub(crate) struct IrObject {
     /// Span of the object.
     #[rune(span)]
     pub(crate) span: Span,
+    /// The kind of object being constructed.
+    pub(crate) kind: IrObjectKind,
     /// Field initializations.
     pub(crate) assignments: Box<[(Box<str>, Ir)]>,
 }
 
+/// The kind of an object expression.
+#[derive(Debug, TryClone)]
+pub(crate) enum IrObjectKind {
+    /// An anonymous object.
+    Anonymous,
+    /// An empty struct.
+    EmptyStruct(Arc<Rtti>),
+    /// A struct with named fields.
+    Struct(Arc<Rtti>),
+    /// An enum variant with named fields.
+    StructVariant(Arc<VariantRtti>),
+}
+
 /// Call expressions.
 #[derive(Debug, TryClone, Spanned)]
 pub(crate) struct IrCall {
@@ -465,11 +483,28 @@ pub(crate) struct IrCall {
     #[rune(span)]
     pub(crate) span: Span,
     /// The target of the call.
-    pub(crate) id: NonZeroId,
+    pub(crate) kind: IrCallKind,
     /// Arguments to the call.
     pub(crate) args: Vec<Ir>,
 }
 
+/// The target of a call expression.
+#[derive(Debug, TryClone)]
+pub(crate) enum IrCallKind {
+    /// A constant function defined in a script.
+    ConstFn(NonZeroId),
+    /// A native function which can be called in constant contexts.
+    Native(Hash),
+    /// The constructor of an empty struct.
+    EmptyStruct(Arc<Rtti>),
+    /// The constructor of a tuple struct.
+    TupleStruct(Arc<Rtti>),
+    /// The constructor of an enum variant without fields.
+    UnitVariant(Arc<VariantRtti>),
+    /// The constructor of a tuple variant.
+    TupleVariant(Arc<VariantRtti>),
+}
+
 /// Vector expression.
 #[derive(Debug, TryClone, Spanned)]
 pub(crate) struct IrVec {
@@ -526,6 +561,29 @@ pub(crate) enum IrAssignOp {
     Shr,
 }
 
+/// Perform an assign operation on a fixed-width integer, where the operation
+/// is checked for overflow.
+macro_rules! assign_checked {
+    ($op:expr, $target:expr, $operand:expr) => {{
+        let (target, operand) = ($target, $operand);
+
+        let value = match $op {
+            IrAssignOp::Add => target.checked_add(operand),
+            IrAssignOp::Sub => target.checked_sub(operand),
+            IrAssignOp::Mul => target.checked_mul(operand),
+            IrAssignOp::Div => target.checked_div(operand),
+            IrAssignOp::Shl => u32::try_from(operand)
+                .ok()
+                .and_then(|n| target.checked_shl(n)),
+            IrAssignOp::Shr => u32::try_from(operand)
+                .ok()
+                .and_then(|n| target.checked_shr(n)),
+        };
+
+        value.ok_or("bad operand")
+    }};
+}
+
 impl IrAssignOp {
     /// Perform the given assign operation.
     pub(crate) fn assign<S>(
@@ -543,6 +601,25 @@ impl IrAssignOp {
             }
         }
 
+        match (
+            &mut *target.borrow_kind_mut().with_span(spanned)?,
+            &*operand.borrow_kind_ref().with_span(spanned)?,
+        ) {
+            (ValueKind::U64(target), ValueKind::U64(operand)) => {
+                *target = assign_checked!(self, *target, *operand).with_span(spanned)?;
+                return Ok(());
+            }
+            (ValueKind::I128(target), ValueKind::I128(operand)) => {
+                *target = assign_checked!(self, *target, *operand).with_span(spanned)?;
+                return Ok(());
+            }
+            (ValueKind::U128(target), ValueKind::U128(operand)) => {
+                *target = assign_checked!(self, *target, *operand).with_span(spanned)?;
+                return Ok(());
+            }
+            _ => {}
+        }
+
         Err(compile::Error::msg(spanned, "unsupported operands"))
     }
 
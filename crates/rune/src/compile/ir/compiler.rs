@@ -1,14 +1,16 @@
 use core::mem::take;
 
+use ::rust_alloc::sync::Arc;
+
 use crate::alloc::prelude::*;
 use crate::alloc::{try_format, Box, Vec};
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
-use crate::compile::{self, ErrorKind, WithSpan};
+use crate::compile::{self, meta, ErrorKind, WithSpan};
 use crate::hir;
 use crate::query::Query;
-use crate::runtime::{Bytes, Value};
-use crate::SourceId;
+use crate::runtime::{Bytes, Rtti, Value, VariantRtti};
+use crate::{Hash, SourceId};
 
 use rune_macros::instrument;
 
@@ -131,14 +133,66 @@ fn expr_call(
         args.try_push(expr(e, c)?)?;
     }
 
-    if let hir::Call::ConstFn { id, .. } = hir.call {
-        return Ok(ir::IrCall { span, id, args });
-    }
+    let kind = match hir.call {
+        hir::Call::ConstFn { id, .. } => ir::IrCallKind::ConstFn(id),
+        hir::Call::Meta { hash } => match c.q.meta_by_hash(hash).map(|meta| &meta.kind) {
+            Some(meta::Kind::Struct {
+                fields: meta::Fields::Empty,
+                ..
+            }) => ir::IrCallKind::EmptyStruct(rtti(c, span, hash)?),
+            Some(meta::Kind::Struct {
+                fields: meta::Fields::Unnamed(..),
+                ..
+            }) => ir::IrCallKind::TupleStruct(rtti(c, span, hash)?),
+            Some(meta::Kind::Variant {
+                fields: meta::Fields::Empty,
+                ..
+            }) => ir::IrCallKind::UnitVariant(variant_rtti(c, span, hash)?),
+            Some(meta::Kind::Variant {
+                fields: meta::Fields::Unnamed(..),
+                ..
+            }) => ir::IrCallKind::TupleVariant(variant_rtti(c, span, hash)?),
+            _ if c.q.context.lookup_const_function(hash).is_some() => ir::IrCallKind::Native(hash),
+            _ => {
+                return Err(compile::Error::msg(
+                    span,
+                    "Call not supported in constant contexts",
+                ))
+            }
+        },
+        _ => {
+            return Err(compile::Error::msg(
+                span,
+                "Call not supported in constant contexts",
+            ))
+        }
+    };
 
-    Err(compile::Error::msg(
-        span,
-        "Call not supported in constant contexts",
-    ))
+    Ok(ir::IrCall { span, kind, args })
+}
+
+/// Get the runtime type information of a struct declared in a script.
+fn rtti(c: &Ctxt<'_, '_>, span: Span, hash: Hash) -> compile::Result<Arc<Rtti>> {
+    let Some(rtti) = c.q.unit.lookup_rtti(hash) else {
+        return Err(compile::Error::msg(
+            span,
+            try_format!("Missing type information for hash {hash}"),
+        ));
+    };
+
+    Ok(rtti.clone())
+}
+
+/// Get the runtime type information of a variant declared in a script.
+fn variant_rtti(c: &Ctxt<'_, '_>, span: Span, hash: Hash) -> compile::Result<Arc<VariantRtti>> {
+    let Some(rtti) = c.q.unit.lookup_variant_rtti(hash) else {
+        return Err(compile::Error::msg(
+            span,
+            try_format!("Missing type information for hash {hash}"),
+        ));
+    };
+
+    Ok(rtti.clone())
 }
 
 #[instrument]
@@ -216,6 +270,18 @@ fn lit(c: &mut Ctxt<'_, '_>, span: Span, hir: hir::Lit<'_>) -> compile::Result<i
             let value = Value::try_from(n).with_span(span)?;
             ir::Ir::new(span, value)
         }
+        hir::Lit::U64(n) => {
+            let value = Value::try_from(n).with_span(span)?;
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::I128(n) => {
+            let value = Value::try_from(n).with_span(span)?;
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::U128(n) => {
+            let value = Value::try_from(n).with_span(span)?;
+            ir::Ir::new(span, value)
+        }
         hir::Lit::Float(n) => {
             let value = Value::try_from(n).with_span(span)?;
             ir::Ir::new(span, value)
@@ -282,6 +348,23 @@ fn expr_object(
     c: &mut Ctxt<'_, '_>,
     hir: &hir::ExprObject<'_>,
 ) -> compile::Result<ir::IrObject> {
+    let kind = match hir.kind {
+        hir::ExprObjectKind::Anonymous => ir::IrObjectKind::Anonymous,
+        hir::ExprObjectKind::EmptyStruct { hash } => {
+            ir::IrObjectKind::EmptyStruct(rtti(c, span, hash)?)
+        }
+        hir::ExprObjectKind::Struct { hash } => ir::IrObjectKind::Struct(rtti(c, span, hash)?),
+        hir::ExprObjectKind::StructVariant { hash } => {
+            ir::IrObjectKind::StructVariant(variant_rtti(c, span, hash)?)
+        }
+        hir::ExprObjectKind::ExternalType { .. } => {
+            return Err(compile::Error::msg(
+                span,
+                "External types are not supported in constant contexts",
+            ))
+        }
+    };
+
     let mut assignments = Vec::new();
 
     for assign in hir.assignments {
@@ -292,6 +375,7 @@ fn expr_object(
 
     Ok(ir::IrObject {
         span,
+        kind,
         assignments: assignments.try_into_boxed_slice()?,
     })
 }
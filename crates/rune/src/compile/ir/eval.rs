@@ -7,7 +7,7 @@ use crate::ast::{Span, Spanned};
 use crate::compile::ir::{self};
 use crate::compile::{self, WithSpan};
 use crate::query::Used;
-use crate::runtime::{Object, OwnedTuple, Value, ValueKind};
+use crate::runtime::{EmptyStruct, Object, OwnedTuple, Struct, Value, ValueKind, Variant};
 
 /// The outcome of a constant evaluation.
 pub enum EvalOutcome {
@@ -53,6 +53,58 @@ fn eval_ir_assign(
     Ok(Value::empty().with_span(ir)?)
 }
 
+/// Evaluate a binary operation over fixed-width integers, where arithmetic is
+/// checked for overflow.
+macro_rules! checked_binary {
+    ($ir:expr, $span:expr, $variant:ident, $a:expr, $b:expr) => {{
+        let (a, b) = ($a, $b);
+
+        let n = match $ir.op {
+            ir::IrBinaryOp::Add => a.checked_add(b),
+            ir::IrBinaryOp::Sub => a.checked_sub(b),
+            ir::IrBinaryOp::Mul => a.checked_mul(b),
+            ir::IrBinaryOp::Div => a.checked_div(b),
+            ir::IrBinaryOp::Lt => return Ok(Value::try_from(a < b).with_span($ir)?),
+            ir::IrBinaryOp::Lte => return Ok(Value::try_from(a <= b).with_span($ir)?),
+            ir::IrBinaryOp::Eq => return Ok(Value::try_from(a == b).with_span($ir)?),
+            ir::IrBinaryOp::Gt => return Ok(Value::try_from(a > b).with_span($ir)?),
+            ir::IrBinaryOp::Gte => return Ok(Value::try_from(a >= b).with_span($ir)?),
+            _ => return Err(EvalOutcome::not_const($ir)),
+        };
+
+        let Some(n) = n else {
+            return Err(EvalOutcome::from(compile::Error::msg(
+                $span,
+                "integer arithmetic overflowed or divided by zero",
+            )));
+        };
+
+        ValueKind::$variant(n)
+    }};
+}
+
+/// Evaluate a shift of a fixed-width integer, which is checked for overflow.
+macro_rules! checked_shift {
+    ($ir:expr, $variant:ident, $a:expr, $b:expr) => {{
+        let Some(n) = u32::try_from($b).ok().and_then(|b| match $ir.op {
+            ir::IrBinaryOp::Shl => $a.checked_shl(b),
+            ir::IrBinaryOp::Shr => $a.checked_shr(b),
+            _ => None,
+        }) else {
+            if !matches!($ir.op, ir::IrBinaryOp::Shl | ir::IrBinaryOp::Shr) {
+                return Err(EvalOutcome::not_const($ir));
+            }
+
+            return Err(EvalOutcome::from(compile::Error::msg(
+                &$ir.rhs,
+                "cannot be converted to shift operand",
+            )));
+        };
+
+        ValueKind::$variant(n)
+    }};
+}
+
 fn eval_ir_binary(
     ir: &ir::IrBinary,
     interp: &mut ir::Interpreter<'_, '_>,
@@ -114,6 +166,24 @@ fn eval_ir_binary(
                 ir::IrBinaryOp::Gt => break 'out ValueKind::Bool(a > b),
                 ir::IrBinaryOp::Gte => break 'out ValueKind::Bool(a >= b),
             },
+            (ValueKind::U64(a), ValueKind::Integer(b)) => {
+                break 'out checked_shift!(ir, U64, *a, *b);
+            }
+            (ValueKind::U64(a), ValueKind::U64(b)) => {
+                break 'out checked_binary!(ir, span, U64, *a, *b);
+            }
+            (ValueKind::I128(a), ValueKind::Integer(b)) => {
+                break 'out checked_shift!(ir, I128, *a, *b);
+            }
+            (ValueKind::I128(a), ValueKind::I128(b)) => {
+                break 'out checked_binary!(ir, span, I128, *a, *b);
+            }
+            (ValueKind::U128(a), ValueKind::Integer(b)) => {
+                break 'out checked_shift!(ir, U128, *a, *b);
+            }
+            (ValueKind::U128(a), ValueKind::U128(b)) => {
+                break 'out checked_binary!(ir, span, U128, *a, *b);
+            }
             (ValueKind::Float(a), ValueKind::Float(b)) => {
                 #[allow(clippy::float_cmp)]
                 match ir.op {
@@ -184,7 +254,24 @@ fn eval_ir_call(
         args.try_push(eval_ir(arg, interp, used)?)?;
     }
 
-    Ok(interp.call_const_fn(ir, ir.id, args, used)?)
+    let value = match &ir.kind {
+        ir::IrCallKind::ConstFn(id) => interp.call_const_fn(ir, *id, args, used)?,
+        ir::IrCallKind::Native(hash) => interp.call_native_fn(ir, *hash, args)?,
+        ir::IrCallKind::EmptyStruct(rtti) => Value::empty_struct(rtti.clone())
+            .into_result()
+            .with_span(ir)?,
+        ir::IrCallKind::TupleStruct(rtti) => Value::tuple_struct(rtti.clone(), args)
+            .into_result()
+            .with_span(ir)?,
+        ir::IrCallKind::UnitVariant(rtti) => Value::unit_variant(rtti.clone())
+            .into_result()
+            .with_span(ir)?,
+        ir::IrCallKind::TupleVariant(rtti) => Value::tuple_variant(rtti.clone(), args)
+            .into_result()
+            .with_span(ir)?,
+    };
+
+    Ok(value)
 }
 
 fn eval_ir_condition(
@@ -281,7 +368,19 @@ fn eval_ir_object(
         object.insert(key, eval_ir(value, interp, used)?)?;
     }
 
-    Ok(Value::try_from(object).with_span(ir)?)
+    let value = match &ir.kind {
+        ir::IrObjectKind::Anonymous => Value::try_from(object),
+        ir::IrObjectKind::EmptyStruct(rtti) => Value::try_from(EmptyStruct { rtti: rtti.clone() }),
+        ir::IrObjectKind::Struct(rtti) => Value::try_from(Struct {
+            rtti: rtti.clone(),
+            data: object,
+        }),
+        ir::IrObjectKind::StructVariant(rtti) => {
+            Value::try_from(Variant::struct_(rtti.clone(), object))
+        }
+    };
+
+    Ok(value.with_span(ir)?)
 }
 
 fn eval_ir_scope(
@@ -339,6 +438,15 @@ fn eval_ir_template(
                     ValueKind::Integer(integer) => {
                         write!(buf, "{integer}")?;
                     }
+                    ValueKind::U64(unsigned) => {
+                        write!(buf, "{unsigned}")?;
+                    }
+                    ValueKind::I128(integer) => {
+                        write!(buf, "{integer}")?;
+                    }
+                    ValueKind::U128(unsigned) => {
+                        write!(buf, "{unsigned}")?;
+                    }
                     ValueKind::Float(float) => {
                         let mut buffer = ryu::Buffer::new();
                         buf.try_push_str(buffer.format(*float))?;
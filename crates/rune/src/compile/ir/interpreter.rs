@@ -8,7 +8,10 @@ use crate::compile::{self, IrErrorKind, ItemId, ModId, WithSpan};
 use crate::hir;
 use crate::parse::NonZeroId;
 use crate::query::{Query, Used};
-use crate::runtime::{ConstValue, Object, OwnedTuple, Value, ValueKind};
+use crate::runtime::{
+    ConstValue, FunctionHandler, InstAddress, Object, OwnedTuple, Stack, Value, ValueKind, VmResult,
+};
+use crate::Hash;
 
 /// The interpreter that executed [Ir][crate::ir::Ir].
 pub struct Interpreter<'a, 'arena> {
@@ -187,6 +190,52 @@ impl Interpreter<'_, '_> {
         self.scopes.pop(guard).with_span(span)?;
         Ok(value)
     }
+
+    /// Call a native function which has been marked as callable in constant
+    /// contexts.
+    pub(crate) fn call_native_fn<S>(
+        &mut self,
+        spanned: S,
+        hash: Hash,
+        args: Vec<Value>,
+    ) -> compile::Result<Value>
+    where
+        S: Copy + Spanned,
+    {
+        let span = Spanned::span(&spanned);
+
+        let Some(handler) = self.q.context.lookup_const_function(hash) else {
+            return Err(compile::Error::msg(
+                span,
+                try_format!("Missing constant function for hash {hash}"),
+            ));
+        };
+
+        Ok(call_handler(&**handler, args)
+            .into_result()
+            .with_span(span)?)
+    }
+}
+
+/// Call the given function handler on a fresh stack.
+fn call_handler(handler: &FunctionHandler, args: Vec<Value>) -> VmResult<Value> {
+    let count = args.len();
+    // Ensure we have space for the return value.
+    let size = count.max(1);
+    let mut stack = vm_try!(Stack::with_capacity(size));
+
+    for arg in args {
+        vm_try!(stack.push(arg));
+    }
+
+    vm_try!(stack.resize(size));
+    vm_try!(handler(
+        &mut stack,
+        InstAddress::ZERO,
+        count,
+        InstAddress::ZERO.output()
+    ));
+    VmResult::Ok(vm_try!(stack.at(InstAddress::ZERO)).clone())
 }
 
 impl ir::Scopes {
@@ -203,6 +252,11 @@ impl ir::Scopes {
                             return Ok(value);
                         }
                     }
+                    ValueKind::Struct(st) => {
+                        if let Some(value) = st.get(field.as_ref()).try_cloned()? {
+                            return Ok(value);
+                        }
+                    }
                     actual => {
                         return Err(compile::Error::expected_type::<_, OwnedTuple>(
                             ir_target, actual,
@@ -231,6 +285,11 @@ impl ir::Scopes {
                             return Ok(value);
                         }
                     }
+                    ValueKind::TupleStruct(tuple) => {
+                        if let Some(value) = tuple.get(*index).try_cloned()? {
+                            return Ok(value);
+                        }
+                    }
                     actual => {
                         return Err(compile::Error::expected_type::<_, OwnedTuple>(
                             ir_target, actual,
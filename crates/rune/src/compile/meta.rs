@@ -126,6 +126,7 @@ impl Meta {
             Kind::Import { .. } => None,
             Kind::Macro => None,
             Kind::AttributeMacro => None,
+            Kind::Trait { .. } => None,
             Kind::Module => None,
         }
     }
@@ -224,13 +225,17 @@ pub enum Kind {
     },
     /// Purely an import.
     Import(Import),
+    /// A trait.
+    Trait {
+        /// The functions declared by the trait.
+        functions: Vec<TraitFn>,
+    },
     /// A module.
     Module,
 }
 
 impl Kind {
     /// Access the underlying signature of the kind, if available.
-    #[cfg(feature = "doc")]
     pub(crate) fn as_signature(&self) -> Option<&Signature> {
         match self {
             Kind::Struct { constructor, .. } => constructor.as_ref(),
@@ -262,6 +267,16 @@ impl Kind {
     }
 }
 
+/// A function declared by a trait.
+#[derive(Debug, TryClone)]
+#[non_exhaustive]
+pub struct TraitFn {
+    /// The name of the function.
+    pub(crate) name: Box<str>,
+    /// If the trait provides a default implementation of the function.
+    pub(crate) has_default: bool,
+}
+
 /// An imported entry.
 #[derive(Debug, TryClone, Clone, Copy)]
 #[try_clone(copy)]
@@ -331,6 +346,69 @@ pub struct Signature {
     pub(crate) argument_types: Box<[Option<Hash>]>,
 }
 
+impl Signature {
+    /// Test if the function is asynchronous.
+    ///
+    /// This information is only available with the `doc` feature.
+    pub(crate) fn is_async(&self) -> bool {
+        #[cfg(feature = "doc")]
+        {
+            self.is_async
+        }
+
+        #[cfg(not(feature = "doc"))]
+        {
+            false
+        }
+    }
+
+    /// The number of arguments the function takes, if known.
+    ///
+    /// This information is only available with the `doc` feature.
+    pub(crate) fn args(&self) -> Option<usize> {
+        #[cfg(feature = "doc")]
+        {
+            self.args
+        }
+
+        #[cfg(not(feature = "doc"))]
+        {
+            None
+        }
+    }
+
+    /// The return type of the function, if known.
+    ///
+    /// This information is only available with the `doc` feature.
+    pub(crate) fn return_type(&self) -> Option<Hash> {
+        #[cfg(feature = "doc")]
+        {
+            self.return_type
+        }
+
+        #[cfg(not(feature = "doc"))]
+        {
+            None
+        }
+    }
+
+    /// The type of the argument at the given index, if known.
+    ///
+    /// This information is only available with the `doc` feature.
+    pub(crate) fn argument_type(&self, index: usize) -> Option<Hash> {
+        #[cfg(feature = "doc")]
+        {
+            self.argument_types.get(index).copied().flatten()
+        }
+
+        #[cfg(not(feature = "doc"))]
+        {
+            let _ = index;
+            None
+        }
+    }
+}
+
 /// The kind of an associated function.
 #[derive(Debug, TryClone, PartialEq, Eq, Hash)]
 #[non_exhaustive]
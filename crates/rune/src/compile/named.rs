@@ -31,6 +31,24 @@ impl Named for i64 {
 
 impl InstallWith for i64 {}
 
+impl Named for u64 {
+    const BASE_NAME: RawStr = RawStr::from_str("u64");
+}
+
+impl InstallWith for u64 {}
+
+impl Named for i128 {
+    const BASE_NAME: RawStr = RawStr::from_str("i128");
+}
+
+impl InstallWith for i128 {}
+
+impl Named for u128 {
+    const BASE_NAME: RawStr = RawStr::from_str("u128");
+}
+
+impl InstallWith for u128 {}
+
 impl Named for f64 {
     const BASE_NAME: RawStr = RawStr::from_str("f64");
 }
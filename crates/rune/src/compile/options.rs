@@ -2,6 +2,8 @@ use core::fmt;
 
 use ::rust_alloc::boxed::Box;
 
+use crate::diagnostics::{Lint, LintLevel, Lints};
+
 /// Error raised when trying to parse an invalid option.
 #[derive(Debug, Clone)]
 pub struct ParseOptionError {
@@ -39,6 +41,14 @@ pub struct Options {
     pub(crate) v2: bool,
     /// Build sources as function bodies.
     pub(crate) function_body: bool,
+    /// Statically check type annotations and function calls.
+    pub(crate) type_check: bool,
+    /// Check type annotations at runtime.
+    pub(crate) runtime_type_checks: bool,
+    /// The level of optimizations to perform over compiled functions.
+    pub(crate) opt_level: u8,
+    /// The configured level of every lint.
+    pub(crate) lints: Lints,
 }
 
 impl Options {
@@ -76,6 +86,21 @@ impl Options {
             Some("function-body") => {
                 self.function_body = it.next() == Some("true");
             }
+            Some("type-check") => {
+                self.type_check = it.next() == Some("true");
+            }
+            Some("runtime-type-checks") => {
+                self.runtime_type_checks = it.next() == Some("true");
+            }
+            Some("opt-level") => {
+                let Some(level) = it.next().and_then(|level| level.parse().ok()) else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.opt_level = level;
+            }
             _ => {
                 return Err(ParseOptionError {
                     option: option.into(),
@@ -113,6 +138,59 @@ impl Options {
         self.bytecode = enabled;
     }
 
+    /// Set if the static type checker is enabled or not. Defaults to `false`.
+    ///
+    /// The type checker uses type annotations and the signatures of native
+    /// functions to report obvious type mismatches, calls with the wrong number
+    /// of arguments and accesses to unknown fields as compile errors.
+    pub fn type_check(&mut self, enabled: bool) {
+        self.type_check = enabled;
+    }
+
+    /// Set if type annotations are checked at runtime or not. Defaults to
+    /// `false`.
+    ///
+    /// This checks the arguments and return values of annotated functions as
+    /// well as annotated `let` bindings while the program runs, raising an
+    /// error if a value doesn't match its annotation. Each check costs an extra
+    /// instruction.
+    ///
+    /// The `rune run` and `rune test` commands enable this with the
+    /// `--runtime-type-checks` flag.
+    pub fn runtime_type_checks(&mut self, enabled: bool) {
+        self.runtime_type_checks = enabled;
+    }
+
+    /// Set the level of optimizations to perform. Defaults to `0`.
+    ///
+    /// * `0` disables all optimizations.
+    /// * `1` folds constant expressions and threads jumps.
+    /// * `2` and above additionally eliminates dead stores and fuses common
+    ///   instruction sequences, which relies on analyzing which stack slots
+    ///   are live.
+    pub fn opt_level(&mut self, level: u8) {
+        self.opt_level = level;
+    }
+
+    /// Set the level of the given lint. Defaults to
+    /// [Lint::default_level].
+    ///
+    /// This can still be overriden for individual items using attributes like
+    /// `#[allow(unused_imports)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Options;
+    /// use rune::diagnostics::{Lint, LintLevel};
+    ///
+    /// let mut options = Options::default();
+    /// options.lint(Lint::UnusedImports, LintLevel::Deny);
+    /// ```
+    pub fn lint(&mut self, lint: Lint, level: LintLevel) {
+        self.lints.set(lint, level);
+    }
+
     /// Memoize the instance function in a loop. Defaults to `false`.
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
@@ -130,6 +208,10 @@ impl Default for Options {
             cfg_test: false,
             v2: false,
             function_body: false,
+            type_check: false,
+            runtime_type_checks: false,
+            opt_level: 0,
+            lints: Lints::default(),
         }
     }
 }
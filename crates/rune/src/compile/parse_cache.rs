@@ -0,0 +1,91 @@
+use core::mem::take;
+
+use crate::alloc::prelude::*;
+use crate::alloc::HashMap;
+use crate::ast;
+use crate::compile;
+use crate::{Hash, Source, SourceId};
+
+/// A cache of parsed sources which can be reused across builds.
+///
+/// Files are keyed by the id of the source they were parsed from and a hash of
+/// its content, so building with the same cache again only parses the sources
+/// which have changed. Files which weren't used by the last build are dropped
+/// once it's done.
+///
+/// Only parsing is cached. Items are still indexed and compiled from scratch by
+/// every build.
+///
+/// # Examples
+///
+/// ```
+/// use rune::compile::ParseCache;
+/// use rune::{Source, Sources};
+///
+/// let mut cache = ParseCache::new();
+///
+/// let mut sources = Sources::new();
+/// sources.insert(Source::memory("pub fn main() { 42 }")?)?;
+///
+/// let _ = rune::prepare(&mut sources).with_parse_cache(&mut cache).build()?;
+/// assert_eq!(cache.len(), 1);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default)]
+pub struct ParseCache {
+    files: HashMap<(SourceId, Hash), Entry>,
+}
+
+struct Entry {
+    file: ast::File,
+    used: bool,
+}
+
+impl ParseCache {
+    /// Construct a new empty parse cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of files in the cache.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Test if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Parse the given source, reusing the file parsed from the same content
+    /// by an earlier build if there is one.
+    pub(crate) fn parse(
+        &mut self,
+        source_id: SourceId,
+        source: &Source,
+    ) -> compile::Result<ast::File> {
+        let key = (source_id, Hash::static_bytes(source.as_str().as_bytes()));
+
+        if let Some(entry) = self.files.get_mut(&key) {
+            entry.used = true;
+            return Ok(entry.file.try_clone()?);
+        }
+
+        let file = crate::parse::parse_all::<ast::File>(source.as_str(), source_id, true)?;
+
+        self.files.try_insert(
+            key,
+            Entry {
+                file: file.try_clone()?,
+                used: true,
+            },
+        )?;
+
+        Ok(file)
+    }
+
+    /// Drop every file which hasn't been used since this was last called.
+    pub(crate) fn evict_unused(&mut self) {
+        self.files.retain(|_, entry| take(&mut entry.used));
+    }
+}
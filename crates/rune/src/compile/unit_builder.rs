@@ -9,13 +9,13 @@ use ::rust_alloc::sync::Arc;
 
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
-use crate::alloc::{self, try_format, Box, HashMap, String, Vec};
+use crate::alloc::{self, try_format, BTreeMap, Box, HashMap, String, Vec};
 use crate::ast::{Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{self, Assembly, AssemblyInst, ErrorKind, Item, Location, Pool, WithSpan};
 use crate::hash;
 use crate::query::QueryInner;
-use crate::runtime::debug::{DebugArgs, DebugSignature};
+use crate::runtime::debug::{DebugArgs, DebugSignature, DebugVariable};
 use crate::runtime::unit::UnitEncoder;
 use crate::runtime::{
     Call, ConstValue, DebugInfo, DebugInst, Inst, Label, Protocol, Rtti, StaticString, Unit,
@@ -56,7 +56,7 @@ pub(crate) struct UnitBuilder {
     /// Where functions are located in the collection of instructions.
     functions: hash::Map<UnitFn>,
     /// Function by address.
-    functions_rev: HashMap<usize, Hash>,
+    functions_rev: BTreeMap<usize, Hash>,
     /// A static string.
     static_strings: Vec<Arc<StaticString>>,
     /// Reverse lookup for static strings.
@@ -86,6 +86,11 @@ pub(crate) struct UnitBuilder {
     debug: Option<Box<DebugInfo>>,
     /// Constant values
     constants: hash::Map<ConstValue>,
+    /// The traits implemented by each type, by type hash.
+    traits: hash::Map<Vec<Hash>>,
+    /// Functions implementing traits, by the hash they would have if they
+    /// were declared directly on the type.
+    trait_functions: hash::Map<Vec<UnitFn>>,
     /// Hash to identifiers.
     hash_to_ident: HashMap<Hash, Box<str>>,
 }
@@ -107,6 +112,17 @@ impl UnitBuilder {
             debug.hash_to_ident = self.hash_to_ident;
         }
 
+        // Trait functions are only reachable without naming the trait if
+        // they are unambiguous and not shadowed by a function declared
+        // directly on the type.
+        for (hash, functions) in self.trait_functions {
+            if let [info] = functions[..] {
+                if !self.functions.contains_key(&hash) {
+                    self.functions.try_insert(hash, info).with_span(span)?;
+                }
+            }
+        }
+
         for (from, to) in self.reexports {
             if let Some(info) = self.functions.get(&to) {
                 let info = *info;
@@ -158,6 +174,7 @@ impl UnitBuilder {
             self.variant_rtti,
             self.debug,
             self.constants,
+            self.traits,
         ))
     }
 
@@ -246,6 +263,16 @@ impl UnitBuilder {
         Ok(new_slot)
     }
 
+    /// Lookup the runtime type information of a struct declared in the unit.
+    pub(crate) fn lookup_rtti(&self, hash: Hash) -> Option<&Arc<Rtti>> {
+        self.rtti.get(&hash)
+    }
+
+    /// Lookup the runtime type information of a variant declared in the unit.
+    pub(crate) fn lookup_variant_rtti(&self, hash: Hash) -> Option<&Arc<VariantRtti>> {
+        self.variant_rtti.get(&hash)
+    }
+
     /// Insert a new collection of static object keys, or return one already
     /// existing.
     pub(crate) fn new_static_object_keys_iter<I>(
@@ -625,12 +652,33 @@ impl UnitBuilder {
             meta::Kind::AsyncBlock { .. } => (),
             meta::Kind::ConstFn { .. } => (),
             meta::Kind::Import { .. } => (),
+            meta::Kind::Trait { .. } => (),
             meta::Kind::Module { .. } => (),
         }
 
         Ok(())
     }
 
+    /// Register that the type with the given hash implements a trait.
+    ///
+    /// Returns the index of the implementation among the traits implemented
+    /// by the type, or `None` if the type already implements the trait.
+    pub(crate) fn insert_trait_impl(
+        &mut self,
+        type_hash: Hash,
+        trait_hash: Hash,
+    ) -> alloc::Result<Option<usize>> {
+        let traits = self.traits.entry(type_hash).or_try_default()?;
+
+        if traits.contains(&trait_hash) {
+            return Ok(None);
+        }
+
+        let index = traits.len();
+        traits.try_push(trait_hash)?;
+        Ok(Some(index))
+    }
+
     /// Construct a new empty assembly associated with the current unit.
     pub(crate) fn new_assembly(&self, location: Location) -> Assembly {
         Assembly::new(location, self.label_count)
@@ -662,6 +710,8 @@ impl UnitBuilder {
         location: Location,
         item: &Item,
         instance: Option<(Hash, &str)>,
+        impl_trait: Option<Hash>,
+        protocol: Option<Protocol>,
         args: usize,
         captures: Option<usize>,
         assembly: Assembly,
@@ -685,7 +735,35 @@ impl UnitBuilder {
         if let Some((type_hash, name)) = instance {
             let instance_fn = Hash::associated_function(type_hash, name);
 
-            if self
+            if let Some(trait_hash) = impl_trait {
+                let trait_fn = Hash::associated_function(
+                    type_hash,
+                    Hash::associated_function(trait_hash, name),
+                );
+
+                if self
+                    .functions
+                    .try_insert(trait_fn, info)
+                    .with_span(location.span)?
+                    .is_some()
+                {
+                    return Err(compile::Error::new(
+                        location.span,
+                        ErrorKind::FunctionConflict {
+                            existing: signature,
+                        },
+                    ));
+                }
+
+                self.debug_mut()?
+                    .functions
+                    .try_insert(trait_fn, signature.try_clone()?)?;
+
+                self.trait_functions
+                    .entry(instance_fn)
+                    .or_try_default()?
+                    .try_push(info)?;
+            } else if self
                 .functions
                 .try_insert(instance_fn, info)
                 .with_span(location.span)?
@@ -702,6 +780,28 @@ impl UnitBuilder {
             self.debug_mut()?
                 .functions
                 .try_insert(instance_fn, signature.try_clone()?)?;
+
+            if let Some(protocol) = protocol {
+                let protocol_fn = Hash::associated_function(type_hash, protocol);
+
+                if self
+                    .functions
+                    .try_insert(protocol_fn, info)
+                    .with_span(location.span)?
+                    .is_some()
+                {
+                    return Err(compile::Error::new(
+                        location.span,
+                        ErrorKind::FunctionConflict {
+                            existing: signature,
+                        },
+                    ));
+                }
+
+                self.debug_mut()?
+                    .functions
+                    .try_insert(protocol_fn, signature.try_clone()?)?;
+            }
         }
 
         let hash = Hash::type_hash(item);
@@ -850,6 +950,13 @@ impl UnitBuilder {
                         .encode(Inst::IterNext { addr, jump, out })
                         .with_span(span)?;
                 }
+                AssemblyInst::JumpIfNotEqInteger { addr, value, label } => {
+                    write!(comment, "label:{}", label)?;
+                    let jump = build_label(label)?;
+                    storage
+                        .encode(Inst::JumpIfNotEqInteger { addr, value, jump })
+                        .with_span(span)?;
+                }
                 AssemblyInst::Raw { raw } => {
                     // Optimization to avoid performing lookups for recursive
                     // function calls.
@@ -895,10 +1002,16 @@ impl UnitBuilder {
                 Some(comment.try_into()?)
             };
 
-            self.debug_mut()?.instructions.try_insert(
-                at,
-                DebugInst::new(location.source_id, span, comment, labels),
-            )?;
+            let mut debug = DebugInst::new(location.source_id, span, comment, labels);
+
+            if let Some(variables) = assembly.variables.get(&pos) {
+                for (name, addr) in variables {
+                    let name = name.as_str().try_into()?;
+                    debug.push_variable(DebugVariable::new(name, *addr))?;
+                }
+            }
+
+            self.debug_mut()?.instructions.try_insert(at, debug)?;
         }
 
         Ok(())
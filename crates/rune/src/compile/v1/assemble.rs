@@ -2,16 +2,18 @@ use core::fmt;
 use core::mem::take;
 use core::slice;
 
+use crate::alloc;
 use crate::alloc::prelude::*;
-use crate::alloc::BTreeMap;
+use crate::alloc::{BTreeMap, HashMap, String};
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
 use crate::compile::{self, Assembly, ErrorKind, ItemId, ModId, Options, WithSpan};
+use crate::diagnostics::Suggestion;
 use crate::hir;
 use crate::query::{ConstFn, Query, Used};
 use crate::runtime::{
-    ConstValue, Inst, InstAddress, InstAssignOp, InstOp, InstRange, InstTarget, InstValue,
-    InstVariant, Label, Output, PanicReason, Protocol, TypeCheck,
+    ConstValue, ConstVariantData, Inst, InstAddress, InstAssignOp, InstI128, InstOp, InstRange,
+    InstTarget, InstU128, InstValue, InstVariant, Label, Output, PanicReason, Protocol, TypeCheck,
 };
 use crate::shared::FixedVec;
 use crate::{Hash, SourceId};
@@ -67,6 +69,8 @@ pub(crate) struct Ctxt<'a, 'hir, 'arena> {
     pub(crate) select_branches: Vec<(Label, &'hir hir::ExprSelectBranch<'hir>)>,
     /// Values to drop.
     pub(crate) drop: Vec<InstAddress>,
+    /// The annotated return type of the function being assembled.
+    pub(crate) return_type: Option<hir::TypeHint>,
 }
 
 impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
@@ -75,6 +79,25 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
         self.contexts.last().copied()
     }
 
+    /// Define a variable in the current scope and record it in the debug
+    /// information of the assembly.
+    fn define(
+        &mut self,
+        span: &'hir dyn Spanned,
+        name: hir::Name<'hir>,
+        addr: &Address<'_, 'hir>,
+    ) -> compile::Result<()> {
+        self.scopes.define(span, name, addr)?;
+
+        match name {
+            hir::Name::SelfValue => self.asm.variable("self", addr.addr())?,
+            hir::Name::Str(name) => self.asm.variable(name, addr.addr())?,
+            hir::Name::Id(..) => {}
+        }
+
+        Ok(())
+    }
+
     /// Calling a constant function by id and return the resuling value.
     pub(crate) fn call_const_fn(
         &mut self,
@@ -208,13 +231,22 @@ pub(crate) fn fn_from_item_fn<'a, 'hir>(
                     return Err(compile::Error::new(span, ErrorKind::UnsupportedSelf));
                 }
 
-                cx.scopes.define(span, hir::Name::SelfValue, needs)?;
+                cx.define(span, hir::Name::SelfValue, needs)?;
             }
             hir::FnArg::Pat(pat) => {
                 let asm = pattern_panic(cx, pat, move |cx, false_label| {
                     fn_arg_pat(cx, pat, needs, false_label)
                 })?;
 
+                asm.ignore();
+            }
+            hir::FnArg::Typed(pat, ty) => {
+                check_type(cx, ty, needs.addr())?;
+
+                let asm = pattern_panic(cx, pat, move |cx, false_label| {
+                    fn_arg_pat(cx, pat, needs, false_label)
+                })?;
+
                 asm.ignore();
             }
         }
@@ -222,13 +254,18 @@ pub(crate) fn fn_from_item_fn<'a, 'hir>(
         first = false;
     }
 
-    if hir.body.value.is_some() {
-        return_(cx, hir, &hir.body, block_without_scope)?.ignore();
+    cx.return_type = hir.output;
+
+    // The return is attributed to the value being returned or the closing
+    // brace of the body, so that it maps to the line where the function
+    // returns.
+    if let Some(value) = hir.body.value {
+        return_(cx, value, &hir.body, block_without_scope)?.ignore();
     } else {
         let mut needs = Any::ignore(&hir.body);
 
         if block_without_scope(cx, &hir.body, &mut needs)?.converging() {
-            cx.asm.push(Inst::ReturnUnit, hir)?;
+            cx.asm.push(Inst::ReturnUnit, &hir.body.span.tail())?;
         }
     }
 
@@ -246,7 +283,7 @@ pub(crate) fn async_block_secondary<'a, 'hir>(
     let linear = cx.scopes.linear(&hir.block, hir.captures.len())?;
 
     for (name, needs) in hir.captures.iter().copied().zip(&linear) {
-        cx.scopes.define(&hir.block, name, needs)?;
+        cx.define(&hir.block, name, needs)?;
     }
 
     return_(cx, &hir.block, &hir.block, block_without_scope)?.ignore();
@@ -277,7 +314,7 @@ pub(crate) fn expr_closure_secondary<'a, 'hir>(
         )?;
 
         for (capture, needs) in hir.captures.iter().copied().zip(&environment) {
-            cx.scopes.define(span, capture, needs)?;
+            cx.define(span, capture, needs)?;
         }
     }
 
@@ -291,6 +328,15 @@ pub(crate) fn expr_closure_secondary<'a, 'hir>(
                     fn_arg_pat(cx, pat, needs, false_label)
                 })?;
 
+                asm.ignore();
+            }
+            hir::FnArg::Typed(pat, ty) => {
+                check_type(cx, ty, needs.addr())?;
+
+                let asm = pattern_panic(cx, pat, move |cx, false_label| {
+                    fn_arg_pat(cx, pat, needs, false_label)
+                })?;
+
                 asm.ignore();
             }
         }
@@ -343,15 +389,42 @@ fn return_<'a, 'hir, T>(
     let mut needs = cx.scopes.defer(span).with_name("return value");
     converge!(asm(cx, hir, &mut needs)?, free(needs));
 
+    let addr = needs.addr()?.addr();
+
+    if let Some(ty) = cx.return_type {
+        check_type(cx, &ty, addr)?;
+    }
+
+    cx.asm.push(Inst::Return { addr }, span)?;
+
+    needs.free()?;
+    Ok(Asm::new(span, ()))
+}
+
+/// Check that the value at the given address matches a type annotation. This
+/// is only done when runtime type checks are enabled.
+fn check_type(
+    cx: &mut Ctxt<'_, '_, '_>,
+    ty: &hir::TypeHint,
+    addr: InstAddress,
+) -> compile::Result<()> {
+    if !cx.options.runtime_type_checks {
+        return Ok(());
+    }
+
+    let name = cx.q.type_name(ty.hash)?;
+    let slot = cx.q.unit.new_static_string(ty, &name)?;
+
     cx.asm.push(
-        Inst::Return {
-            addr: needs.addr()?.addr(),
+        Inst::CheckType {
+            hash: ty.hash,
+            slot,
+            addr,
         },
-        span,
+        ty,
     )?;
 
-    needs.free()?;
-    Ok(Asm::new(span, ()))
+    Ok(())
 }
 
 fn pattern_panic<'a, 'hir, 'arena, F>(
@@ -433,7 +506,7 @@ fn pat_binding_with<'a, 'hir>(
     }
 
     for (name, needs) in names.iter().copied().zip(linear.iter()) {
-        cx.scopes.define(needs.span(), name, needs)?;
+        cx.define(needs.span(), name, needs)?;
     }
 
     Ok(asm)
@@ -470,7 +543,7 @@ fn pat_binding_with_single<'a, 'hir>(
         ));
     };
 
-    cx.scopes.define(needs.span(), name, addr)?;
+    cx.define(needs.span(), name, addr)?;
     Ok(asm)
 }
 
@@ -612,6 +685,17 @@ fn pat_lit_inst<'a, 'hir>(
             out,
         },
         hir::Lit::Integer(value) => Inst::EqInteger { addr, value, out },
+        hir::Lit::U64(value) => Inst::EqU64 { addr, value, out },
+        hir::Lit::I128(value) => Inst::EqI128 {
+            addr,
+            value: InstI128::new(value),
+            out,
+        },
+        hir::Lit::U128(value) => Inst::EqU128 {
+            addr,
+            value: InstU128::new(value),
+            out,
+        },
         hir::Lit::Bool(value) => Inst::EqBool { addr, value, out },
         _ => return Ok(None),
     };
@@ -1048,8 +1132,14 @@ fn builtin_template<'a, 'hir>(
     }
 
     if hir.from_literal && expansions == 0 {
-        cx.q.diagnostics
-            .template_without_expansions(cx.source_id, span, cx.context())?;
+        let suggestion = template_to_string(cx, span.span())?;
+
+        cx.q.diagnostics.template_without_expansions(
+            cx.source_id,
+            span,
+            cx.context(),
+            suggestion,
+        )?;
     }
 
     if converge {
@@ -1082,8 +1172,10 @@ fn const_<'a, 'hir>(
     needs: &mut dyn Needs<'a, 'hir>,
 ) -> compile::Result<()> {
     let Some(addr) = needs.try_alloc_addr()? else {
+        let suggestion = remove_unused(cx, span.span())?;
+
         cx.q.diagnostics
-            .not_used(cx.source_id, span, cx.context())?;
+            .not_used(cx.source_id, span, cx.context(), suggestion)?;
         return Ok(());
     };
 
@@ -1102,6 +1194,15 @@ fn const_<'a, 'hir>(
         ConstValue::Integer(v) => {
             cx.asm.push(Inst::integer(v, out), span)?;
         }
+        ConstValue::U64(v) => {
+            cx.asm.push(Inst::unsigned(v, out), span)?;
+        }
+        ConstValue::I128(v) => {
+            cx.asm.push(Inst::i128(v, out), span)?;
+        }
+        ConstValue::U128(v) => {
+            cx.asm.push(Inst::u128(v, out), span)?;
+        }
         ConstValue::Float(v) => {
             cx.asm.push(Inst::float(v, out), span)?;
         }
@@ -1177,22 +1278,38 @@ fn const_<'a, 'hir>(
             linear.free()?;
         }
         ConstValue::Object(ref object) => {
-            let mut linear = cx.scopes.linear(span, object.len())?;
-
-            let mut entries = object.iter().try_collect::<Vec<_>>()?;
-            entries.sort_by_key(|k| k.0);
+            let (linear, slot) = const_fields(cx, object, span)?;
 
-            for ((_, value), needs) in entries.iter().copied().zip(&mut linear) {
-                const_(cx, value, span, needs)?;
-            }
+            cx.asm.push(
+                Inst::Object {
+                    addr: linear.addr(),
+                    slot,
+                    out,
+                },
+                span,
+            )?;
 
-            let slot =
-                cx.q.unit
-                    .new_static_object_keys_iter(span, entries.iter().map(|e| e.0))?;
+            linear.free()?;
+        }
+        ConstValue::EmptyStruct(ref rtti) => {
+            cx.asm.push(
+                Inst::EmptyStruct {
+                    hash: rtti.hash,
+                    out,
+                },
+                span,
+            )?;
+        }
+        ConstValue::TupleStruct(ref rtti, ref tuple) => {
+            const_call(cx, rtti.hash, tuple, span, out)?;
+        }
+        ConstValue::Struct(ref rtti, ref object) => {
+            let (linear, slot) = const_fields(cx, object, span)?;
 
             cx.asm.push(
-                Inst::Object {
+                Inst::Struct {
                     addr: linear.addr(),
+                    hash: rtti.hash,
                     slot,
                     out,
                 },
@@ -1201,11 +1318,86 @@ fn const_<'a, 'hir>(
 
             linear.free()?;
         }
+        ConstValue::Variant(ref rtti, ref data) => match data {
+            ConstVariantData::Empty => {
+                const_call(cx, rtti.hash, &[], span, out)?;
+            }
+            ConstVariantData::Tuple(tuple) => {
+                const_call(cx, rtti.hash, tuple, span, out)?;
+            }
+            ConstVariantData::Struct(object) => {
+                let (linear, slot) = const_fields(cx, object, span)?;
+
+                cx.asm.push(
+                    Inst::StructVariant {
+                        addr: linear.addr(),
+                        hash: rtti.hash,
+                        slot,
+                        out,
+                    },
+                    span,
+                )?;
+
+                linear.free()?;
+            }
+        },
     }
 
     Ok(())
 }
 
+/// Assemble the fields of a constant object into linear addresses, sorted by
+/// their keys, returning the addresses and the slot of the keys.
+fn const_fields<'a, 'hir>(
+    cx: &mut Ctxt<'a, 'hir, '_>,
+    object: &HashMap<String, ConstValue>,
+    span: &'hir dyn Spanned,
+) -> compile::Result<(Linear<'a, 'hir>, usize)> {
+    let mut linear = cx.scopes.linear(span, object.len())?;
+
+    let mut entries = object.iter().try_collect::<Vec<_>>()?;
+    entries.sort_by_key(|k| k.0);
+
+    for ((_, value), needs) in entries.iter().copied().zip(&mut linear) {
+        const_(cx, value, span, needs)?;
+    }
+
+    let slot =
+        cx.q.unit
+            .new_static_object_keys_iter(span, entries.iter().map(|e| e.0))?;
+
+    Ok((linear, slot))
+}
+
+/// Assemble a call to the constructor of a tuple struct or variant with
+/// constant arguments.
+fn const_call<'a, 'hir>(
+    cx: &mut Ctxt<'a, 'hir, '_>,
+    hash: Hash,
+    args: &[ConstValue],
+    span: &'hir dyn Spanned,
+    out: Output,
+) -> compile::Result<()> {
+    let mut linear = cx.scopes.linear(span, args.len())?;
+
+    for (value, needs) in args.iter().zip(&mut linear) {
+        const_(cx, value, span, needs)?;
+    }
+
+    cx.asm.push(
+        Inst::Call {
+            hash,
+            addr: linear.addr(),
+            args: args.len(),
+            out,
+        },
+        span,
+    )?;
+
+    linear.free()?;
+    Ok(())
+}
+
 /// Assemble an expression.
 #[instrument(span = hir)]
 fn expr<'a, 'hir>(
@@ -1442,6 +1634,8 @@ fn expr_binary<'a, 'hir>(
         ast::BinOp::As(..) => InstOp::As,
         ast::BinOp::Is(..) => InstOp::Is,
         ast::BinOp::IsNot(..) => InstOp::IsNot,
+        ast::BinOp::IsImpl(..) => InstOp::IsImpl,
+        ast::BinOp::IsNotImpl(..) => InstOp::IsNotImpl,
         ast::BinOp::And(..) => InstOp::And,
         ast::BinOp::Or(..) => InstOp::Or,
         ast::BinOp::Add(..) => InstOp::Add,
@@ -1745,6 +1939,26 @@ fn expr_call<'a, 'hir>(
 
             linear.free()?;
         }
+        hir::Call::Trait {
+            target,
+            trait_hash,
+            hash,
+        } => {
+            let linear = converge!(exprs_2(cx, span, slice::from_ref(target), hir.args)?);
+
+            cx.asm.push(
+                Inst::CallTrait {
+                    trait_hash,
+                    hash,
+                    addr: linear.addr(),
+                    args: args + 1,
+                    out: needs.alloc_output()?,
+                },
+                span,
+            )?;
+
+            linear.free()?;
+        }
         hir::Call::Meta { hash } => {
             let linear = converge!(exprs(cx, span, hir.args)?);
 
@@ -1892,8 +2106,10 @@ fn expr_call_closure<'a, 'hir>(
     needs: &mut dyn Needs<'a, 'hir>,
 ) -> compile::Result<Asm<'hir>> {
     let Some(out) = needs.try_alloc_output()? else {
+        let suggestion = remove_unused(cx, span.span())?;
+
         cx.q.diagnostics
-            .not_used(cx.source_id, span, cx.context())?;
+            .not_used(cx.source_id, span, cx.context(), suggestion)?;
         return Ok(Asm::new(span, ()));
     };
 
@@ -3027,8 +3243,10 @@ fn lit<'a, 'hir>(
 ) -> compile::Result<Asm<'hir>> {
     // Elide the entire literal if it's not needed.
     let Some(addr) = needs.try_alloc_addr()? else {
+        let suggestion = remove_unused(cx, span.span())?;
+
         cx.q.diagnostics
-            .not_used(cx.source_id, span, cx.context())?;
+            .not_used(cx.source_id, span, cx.context(), suggestion)?;
         return Ok(Asm::new(span, ()));
     };
 
@@ -3047,6 +3265,15 @@ fn lit<'a, 'hir>(
         hir::Lit::Integer(v) => {
             cx.asm.push(Inst::integer(v, out), span)?;
         }
+        hir::Lit::U64(v) => {
+            cx.asm.push(Inst::unsigned(v, out), span)?;
+        }
+        hir::Lit::I128(v) => {
+            cx.asm.push(Inst::i128(v, out), span)?;
+        }
+        hir::Lit::U128(v) => {
+            cx.asm.push(Inst::u128(v, out), span)?;
+        }
         hir::Lit::Float(v) => {
             cx.asm.push(Inst::float(v, out), span)?;
         }
@@ -3084,3 +3311,51 @@ fn local<'a, 'hir>(
 
     Ok(Asm::new(hir, ()))
 }
+
+/// Suggest removing an unused value which is used as a statement, including
+/// the semicolon following it.
+fn remove_unused(cx: &Ctxt<'_, '_, '_>, span: Span) -> alloc::Result<Option<Suggestion>> {
+    let Some(source) = cx.q.sources.get(cx.source_id) else {
+        return Ok(None);
+    };
+
+    let Some(rest) = source.as_str().get(span.end.into_usize()..) else {
+        return Ok(None);
+    };
+
+    let trimmed = rest.trim_start();
+
+    if !trimmed.starts_with(';') {
+        return Ok(None);
+    }
+
+    let end = span.end.into_usize() + (rest.len() - trimmed.len()) + 1;
+    let span = Span::new(span.start, end);
+    Ok(Some(Suggestion::remove("Remove the unused value", span)?))
+}
+
+/// Suggest rewriting a template string without expansions into a regular
+/// string literal.
+fn template_to_string(cx: &Ctxt<'_, '_, '_>, span: Span) -> alloc::Result<Option<Suggestion>> {
+    let Some(text) = cx.q.sources.source(cx.source_id, span) else {
+        return Ok(None);
+    };
+
+    let Some(inner) = text
+        .strip_prefix('`')
+        .and_then(|text| text.strip_suffix('`'))
+    else {
+        return Ok(None);
+    };
+
+    // Escape sequences differ between templates and strings.
+    if inner.contains(['"', '\\']) {
+        return Ok(None);
+    }
+
+    Ok(Some(Suggestion::new(
+        "Use a string literal",
+        span,
+        &try_format!("\"{inner}\""),
+    )?))
+}
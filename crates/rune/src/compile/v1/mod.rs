@@ -20,5 +20,7 @@ use self::slab::Slab;
 mod linear;
 use self::linear::Linear;
 
+pub(crate) mod optimize;
+
 mod display_named;
 use self::display_named::DisplayNamed;
@@ -0,0 +1,941 @@
+//! Optimization passes performed over the assembly of a single function.
+//!
+//! These are enabled through the `opt-level` compiler option and operate on
+//! the [`Assembly`] before it is encoded into the unit, so that labels can be
+//! freely moved around as instructions are removed.
+
+use core::mem;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, HashMap, Vec};
+use crate::compile;
+use crate::compile::assembly::{Assembly, AssemblyInst};
+use crate::runtime::{Inst, InstAddress, InstOp, InstRange, InstTarget, InstValue, Output};
+
+/// Run the optimization pipeline for the given level over an assembly which
+/// uses a stack frame of `size` slots.
+pub(crate) fn assembly(asm: &mut Assembly, size: usize, level: u8) -> compile::Result<()> {
+    if level == 0 {
+        return Ok(());
+    }
+
+    // Select is followed by a table of jumps which it indexes relative to its
+    // own position, so nothing in its vicinity can be moved.
+    let has_select = asm.instructions.iter().any(|(inst, _)| {
+        matches!(
+            inst,
+            AssemblyInst::Raw {
+                raw: Inst::Select { .. }
+            }
+        )
+    });
+
+    if has_select {
+        return Ok(());
+    }
+
+    fold_constants(asm, size)?;
+    thread_jumps(asm)?;
+
+    if level < 2 {
+        return Ok(());
+    }
+
+    // Removing a dead store might cause the stores it reads from to become
+    // dead, so keep going until nothing changes.
+    loop {
+        let Some(live) = Liveness::new(asm, size)? else {
+            return Ok(());
+        };
+
+        if !eliminate_dead_stores(asm, &live)? {
+            fuse_instructions(asm, &live)?;
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fold operations over constant operands and conditional jumps over constant
+/// conditions.
+///
+/// Constants are only tracked within a single basic block, which ends at any
+/// instruction which is the target of a jump.
+fn fold_constants(asm: &mut Assembly, size: usize) -> compile::Result<()> {
+    let mut consts = Constants {
+        values: try_vec![None; size],
+    };
+
+    let mut removed = try_vec![false; asm.instructions.len()];
+
+    for (pos, (inst, _)) in asm.instructions.iter_mut().enumerate() {
+        if asm.labels.contains_key(&pos) {
+            consts.clear();
+        }
+
+        let jump_if = matches!(inst, AssemblyInst::JumpIf { .. });
+
+        match inst {
+            AssemblyInst::JumpIf { addr, label } | AssemblyInst::JumpIfNot { addr, label } => {
+                let Some(InstValue::Bool(cond)) = consts.get(*addr) else {
+                    continue;
+                };
+
+                if cond == jump_if {
+                    let label = label.try_clone()?;
+                    *inst = AssemblyInst::Jump { label };
+                } else {
+                    removed[pos] = true;
+                }
+            }
+            AssemblyInst::IterNext { out, .. } => {
+                consts.set(*out, None);
+            }
+            AssemblyInst::Jump { .. } | AssemblyInst::JumpIfNotEqInteger { .. } => {}
+            AssemblyInst::Raw { raw } => {
+                fold_raw(raw, &mut consts);
+            }
+        }
+    }
+
+    compact(asm, &removed)
+}
+
+/// Fold a single raw instruction.
+fn fold_raw(raw: &mut Inst, consts: &mut Constants) {
+    match *raw {
+        Inst::Store { value, out } => {
+            consts.set(out, Some(value));
+        }
+        Inst::Copy { addr, out } => {
+            consts.set(out, consts.get(addr));
+        }
+        Inst::Move { addr, out } => {
+            let value = consts.get(addr);
+            consts.set(addr.output(), None);
+            consts.set(out, value);
+        }
+        Inst::Op { op, a, b, out } => {
+            let value = match (consts.get(a), consts.get(b)) {
+                (Some(a), Some(b)) => fold_op(op, a, b),
+                _ => None,
+            };
+
+            if let Some(value) = value {
+                *raw = Inst::Store { value, out };
+            }
+
+            consts.set(out, value);
+        }
+        Inst::Not { addr, out } => {
+            let value = match consts.get(addr) {
+                Some(InstValue::Bool(value)) => Some(InstValue::Bool(!value)),
+                Some(InstValue::Integer(value)) => Some(InstValue::Integer(!value)),
+                _ => None,
+            };
+
+            if let Some(value) = value {
+                *raw = Inst::Store { value, out };
+            }
+
+            consts.set(out, value);
+        }
+        Inst::Neg { addr, out } => {
+            let value = match consts.get(addr) {
+                Some(InstValue::Integer(value)) => value.checked_neg().map(InstValue::Integer),
+                _ => None,
+            };
+
+            if let Some(value) = value {
+                *raw = Inst::Store { value, out };
+            }
+
+            consts.set(out, value);
+        }
+        // Instructions which might move values out of or modify slots other
+        // than their output.
+        Inst::Call { .. }
+        | Inst::CallOffset { .. }
+        | Inst::CallAssociated { .. }
+        | Inst::CallTrait { .. }
+        | Inst::CallFn { .. }
+        | Inst::Drop { .. }
+        | Inst::Swap { .. }
+        | Inst::Assign { .. } => {
+            consts.clear();
+        }
+        ref inst => match output(inst) {
+            Some(out) => consts.set(out, None),
+            None => consts.clear(),
+        },
+    }
+}
+
+/// Fold a binary operation over two constant operands.
+///
+/// Operations which would raise an error at runtime are left alone so that
+/// the error is preserved.
+fn fold_op(op: InstOp, a: InstValue, b: InstValue) -> Option<InstValue> {
+    let value = match (a, b) {
+        (InstValue::Integer(a), InstValue::Integer(b)) => match op {
+            InstOp::Add => InstValue::Integer(a.checked_add(b)?),
+            InstOp::Sub => InstValue::Integer(a.checked_sub(b)?),
+            InstOp::Mul => InstValue::Integer(a.checked_mul(b)?),
+            InstOp::Div => InstValue::Integer(a.checked_div(b)?),
+            InstOp::Rem => InstValue::Integer(a.checked_rem(b)?),
+            InstOp::BitAnd => InstValue::Integer(a & b),
+            InstOp::BitXor => InstValue::Integer(a ^ b),
+            InstOp::BitOr => InstValue::Integer(a | b),
+            InstOp::Shl => InstValue::Integer(a.checked_shl(u32::try_from(b).ok()?)?),
+            InstOp::Shr => InstValue::Integer(a.checked_shr(u32::try_from(b).ok()?)?),
+            InstOp::Lt => InstValue::Bool(a < b),
+            InstOp::Gt => InstValue::Bool(a > b),
+            InstOp::Lte => InstValue::Bool(a <= b),
+            InstOp::Gte => InstValue::Bool(a >= b),
+            InstOp::Eq => InstValue::Bool(a == b),
+            InstOp::Neq => InstValue::Bool(a != b),
+            _ => return None,
+        },
+        (InstValue::Bool(a), InstValue::Bool(b)) => match op {
+            InstOp::BitAnd | InstOp::And => InstValue::Bool(a && b),
+            InstOp::BitXor => InstValue::Bool(a ^ b),
+            InstOp::BitOr | InstOp::Or => InstValue::Bool(a || b),
+            InstOp::Eq => InstValue::Bool(a == b),
+            InstOp::Neq => InstValue::Bool(a != b),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Redirect jumps which land on other jumps straight to their final
+/// destination, and remove jumps to the next instruction along with any code
+/// which can no longer be reached.
+fn thread_jumps(asm: &mut Assembly) -> compile::Result<()> {
+    let positions = label_positions(asm)?;
+    let len = asm.instructions.len();
+
+    for pos in 0..len {
+        let Some(label) = jump_label(&asm.instructions[pos].0) else {
+            continue;
+        };
+
+        let mut target = label.try_clone()?;
+        let mut hops = 0;
+
+        while hops < len {
+            let Some((AssemblyInst::Jump { label }, _)) = positions
+                .get(&target.index)
+                .and_then(|&p| asm.instructions.get(p))
+            else {
+                break;
+            };
+
+            if label.index == target.index {
+                break;
+            }
+
+            target = label.try_clone()?;
+            hops += 1;
+        }
+
+        // An unconditional jump to a return can simply return.
+        if let AssemblyInst::Jump { .. } = &asm.instructions[pos].0 {
+            if let Some((AssemblyInst::Raw { raw }, _)) = positions
+                .get(&target.index)
+                .and_then(|&p| asm.instructions.get(p))
+            {
+                if let Inst::Return { .. } | Inst::ReturnUnit = raw {
+                    asm.instructions[pos].0 = AssemblyInst::Raw { raw: *raw };
+                    continue;
+                }
+            }
+        }
+
+        if hops > 0 {
+            if let Some(label) = jump_label_mut(&mut asm.instructions[pos].0) {
+                *label = target;
+            }
+        }
+    }
+
+    let mut removed = try_vec![false; len];
+    let mut reachable = true;
+
+    for (pos, (inst, _)) in asm.instructions.iter().enumerate() {
+        if asm.labels.contains_key(&pos) {
+            reachable = true;
+        }
+
+        if !reachable {
+            removed[pos] = true;
+            continue;
+        }
+
+        reachable = !matches!(
+            inst,
+            AssemblyInst::Jump { .. }
+                | AssemblyInst::Raw {
+                    raw: Inst::Return { .. } | Inst::ReturnUnit | Inst::Panic { .. }
+                }
+        );
+    }
+
+    // A conditional jump over an unconditional jump is inverted to jump
+    // straight to where the unconditional jump goes.
+    for pos in 0..len {
+        let next = next_kept(&removed, pos + 1);
+
+        if removed[pos] || next >= len || asm.labels.contains_key(&next) {
+            continue;
+        }
+
+        let (AssemblyInst::Jump { label: other }, _) = &asm.instructions[next] else {
+            continue;
+        };
+
+        let (addr, label, jump_if) = match &asm.instructions[pos].0 {
+            AssemblyInst::JumpIf { addr, label } => (*addr, label, true),
+            AssemblyInst::JumpIfNot { addr, label } => (*addr, label, false),
+            _ => continue,
+        };
+
+        let Some(&target) = positions.get(&label.index) else {
+            continue;
+        };
+
+        if next_kept(&removed, target) != next_kept(&removed, next + 1) {
+            continue;
+        }
+
+        let label = other.try_clone()?;
+
+        asm.instructions[pos].0 = if jump_if {
+            AssemblyInst::JumpIfNot { addr, label }
+        } else {
+            AssemblyInst::JumpIf { addr, label }
+        };
+
+        removed[next] = true;
+    }
+
+    for pos in (0..len).rev() {
+        let (AssemblyInst::Jump { label }, _) = &asm.instructions[pos] else {
+            continue;
+        };
+
+        if removed[pos] {
+            continue;
+        }
+
+        let Some(&target) = positions.get(&label.index) else {
+            continue;
+        };
+
+        if next_kept(&removed, target) == next_kept(&removed, pos + 1) {
+            removed[pos] = true;
+        }
+    }
+
+    compact(asm, &removed)
+}
+
+/// Find the first position at or after `pos` which has not been removed.
+fn next_kept(removed: &[bool], mut pos: usize) -> usize {
+    while removed.get(pos).copied().unwrap_or_default() {
+        pos += 1;
+    }
+
+    pos
+}
+
+/// Remove stores, copies and moves into slots which are never read.
+///
+/// Returns `true` if anything was removed.
+fn eliminate_dead_stores(asm: &mut Assembly, live: &Liveness) -> compile::Result<bool> {
+    let mut removed = try_vec![false; asm.instructions.len()];
+    let mut any = false;
+
+    for (pos, (inst, _)) in asm.instructions.iter().enumerate() {
+        let AssemblyInst::Raw { raw } = inst else {
+            continue;
+        };
+
+        let dead = match *raw {
+            Inst::Store { out, .. } | Inst::Move { out, .. } => !live.is_live_after(pos, out),
+            Inst::Copy { addr, out } => {
+                out.as_addr() == Some(addr) || !live.is_live_after(pos, out)
+            }
+            _ => false,
+        };
+
+        if dead {
+            removed[pos] = true;
+            any = true;
+        }
+    }
+
+    compact(asm, &removed)?;
+    Ok(any)
+}
+
+/// Fuse sequences of instructions into a single instruction.
+fn fuse_instructions(asm: &mut Assembly, live: &Liveness) -> compile::Result<()> {
+    let mut removed = try_vec![false; asm.instructions.len()];
+
+    for pos in 1..asm.instructions.len() {
+        // Something else jumps to the second instruction, so it can't be
+        // fused with the first.
+        if asm.labels.contains_key(&pos) {
+            continue;
+        }
+
+        let (
+            AssemblyInst::Raw {
+                raw: Inst::EqInteger { addr, value, out },
+            },
+            _,
+        ) = asm.instructions[pos - 1]
+        else {
+            continue;
+        };
+
+        let (AssemblyInst::JumpIfNot { addr: cond, label }, _) = &asm.instructions[pos] else {
+            continue;
+        };
+
+        if out.as_addr() != Some(*cond) || live.is_live_after(pos, out) {
+            continue;
+        }
+
+        let label = label.try_clone()?;
+        asm.instructions[pos - 1].0 = AssemblyInst::JumpIfNotEqInteger { addr, value, label };
+        removed[pos] = true;
+    }
+
+    compact(asm, &removed)
+}
+
+/// Remove the instructions marked as removed, moving any labels and variables
+/// associated with them to the instruction which follows.
+fn compact(asm: &mut Assembly, removed: &[bool]) -> compile::Result<()> {
+    if !removed.contains(&true) {
+        return Ok(());
+    }
+
+    let is_removed = |pos: usize| removed.get(pos).copied().unwrap_or_default();
+
+    // The new position of every old position, including the one past the
+    // last instruction which labels are allowed to point to.
+    let mut positions = Vec::try_with_capacity(removed.len().saturating_add(1))?;
+    let mut kept = 0usize;
+
+    for pos in 0..=removed.len() {
+        positions.try_push(kept)?;
+
+        if !is_removed(pos) {
+            kept += 1;
+        }
+    }
+
+    for (pos, inst) in mem::take(&mut asm.instructions).into_iter().enumerate() {
+        if !is_removed(pos) {
+            asm.instructions.try_push(inst)?;
+        }
+    }
+
+    let mut labels = HashMap::<usize, (usize, Vec<_>)>::new();
+
+    for (pos, (_, old)) in mem::take(&mut asm.labels) {
+        labels
+            .entry(positions[pos])
+            .or_try_default()?
+            .1
+            .try_extend(old)?;
+    }
+
+    // Jump offsets must be dense, since they are used to allocate an offset
+    // table for the function.
+    let mut keys = labels.keys().copied().try_collect::<Vec<_>>()?;
+    keys.sort();
+
+    for (index, pos) in keys.into_iter().enumerate() {
+        if let Some((offset, labels)) = labels.get_mut(&pos) {
+            *offset = index;
+
+            for label in labels.iter() {
+                label.set_jump(index);
+            }
+        }
+    }
+
+    asm.labels = labels;
+
+    for (pos, comment) in mem::take(&mut asm.comments) {
+        if !is_removed(pos) {
+            asm.comments.try_insert(positions[pos], comment)?;
+        }
+    }
+
+    for (pos, variables) in mem::take(&mut asm.variables) {
+        asm.variables
+            .entry(positions[pos])
+            .or_try_default()?
+            .try_extend(variables)?;
+    }
+
+    Ok(())
+}
+
+/// The stack slots which are live before each instruction.
+struct Liveness {
+    /// Successors of each instruction.
+    successors: Vec<[Option<usize>; 2]>,
+    /// Slots which are live before each instruction.
+    live: Vec<Slots>,
+}
+
+impl Liveness {
+    /// Calculate liveness for the given assembly.
+    ///
+    /// Returns `None` if the assembly can't be analyzed.
+    fn new(asm: &Assembly, size: usize) -> compile::Result<Option<Self>> {
+        let positions = label_positions(asm)?;
+        let len = asm.instructions.len();
+
+        let mut successors = Vec::try_with_capacity(len)?;
+
+        for (pos, (inst, _)) in asm.instructions.iter().enumerate() {
+            let next = Some(pos + 1).filter(|&next| next < len);
+
+            let target = match jump_label(inst) {
+                Some(label) => {
+                    let Some(&target) = positions.get(&label.index) else {
+                        return Ok(None);
+                    };
+
+                    Some(target).filter(|&target| target < len)
+                }
+                None => None,
+            };
+
+            let entry = match inst {
+                AssemblyInst::Jump { .. } => [target, None],
+                AssemblyInst::Raw {
+                    raw: Inst::Return { .. } | Inst::ReturnUnit | Inst::Panic { .. },
+                } => [None, None],
+                _ => [next, target],
+            };
+
+            successors.try_push(entry)?;
+        }
+
+        let mut live = Vec::try_with_capacity(len)?;
+
+        for _ in 0..len {
+            live.try_push(Slots::new(size)?)?;
+        }
+
+        let mut this = Self { successors, live };
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for pos in (0..len).rev() {
+                let mut slots = this.live_after(pos)?;
+
+                if let Some(addr) = kills(&asm.instructions[pos].0).and_then(|out| out.as_addr()) {
+                    slots.remove(addr);
+                }
+
+                reads(&asm.instructions[pos].0, &mut slots);
+
+                if slots != this.live[pos] {
+                    this.live[pos] = slots;
+                    changed = true;
+                }
+            }
+        }
+
+        Ok(Some(this))
+    }
+
+    /// Calculate the slots which are live after the given instruction.
+    fn live_after(&self, pos: usize) -> alloc::Result<Slots> {
+        let mut slots = Slots::new(self.live.get(pos).map(Slots::len).unwrap_or_default())?;
+
+        for &next in self.successors[pos].iter().flatten() {
+            slots.union(&self.live[next]);
+        }
+
+        Ok(slots)
+    }
+
+    /// Test if the given output is read by anything after the given
+    /// instruction.
+    fn is_live_after(&self, pos: usize, out: Output) -> bool {
+        let Some(addr) = out.as_addr() else {
+            return false;
+        };
+
+        self.successors[pos]
+            .iter()
+            .flatten()
+            .any(|&next| self.live[next].contains(addr))
+    }
+}
+
+/// A set of stack slots.
+#[derive(PartialEq)]
+struct Slots {
+    len: usize,
+    bits: Vec<u64>,
+}
+
+impl Slots {
+    fn new(len: usize) -> alloc::Result<Self> {
+        Ok(Self {
+            len,
+            bits: try_vec![0; len.div_ceil(64)],
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn insert(&mut self, addr: InstAddress) {
+        let offset = addr.offset();
+
+        if offset < self.len {
+            self.bits[offset / 64] |= 1 << (offset % 64);
+        }
+    }
+
+    fn insert_range(&mut self, addr: InstAddress, count: usize) {
+        let end = addr.offset().saturating_add(count).min(self.len);
+
+        for offset in addr.offset()..end {
+            self.insert(InstAddress::new(offset));
+        }
+    }
+
+    fn remove(&mut self, addr: InstAddress) {
+        let offset = addr.offset();
+
+        if offset < self.len {
+            self.bits[offset / 64] &= !(1 << (offset % 64));
+        }
+    }
+
+    /// Test if the slot is live. Slots outside of the frame are
+    /// conservatively always considered live.
+    fn contains(&self, addr: InstAddress) -> bool {
+        let offset = addr.offset();
+
+        if offset >= self.len {
+            return true;
+        }
+
+        self.bits[offset / 64] & (1 << (offset % 64)) != 0
+    }
+
+    fn union(&mut self, other: &Slots) {
+        for (a, b) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *a |= *b;
+        }
+    }
+}
+
+/// Constant values known to be stored in stack slots.
+struct Constants {
+    values: Vec<Option<InstValue>>,
+}
+
+impl Constants {
+    fn get(&self, addr: InstAddress) -> Option<InstValue> {
+        self.values.get(addr.offset()).copied().flatten()
+    }
+
+    fn set(&mut self, out: Output, value: Option<InstValue>) {
+        if let Some(slot) = out
+            .as_addr()
+            .and_then(|addr| self.values.get_mut(addr.offset()))
+        {
+            *slot = value;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.values.fill(None);
+    }
+}
+
+/// Map label indexes to the position of the instruction they point to.
+fn label_positions(asm: &Assembly) -> alloc::Result<HashMap<usize, usize>> {
+    let mut positions = HashMap::new();
+
+    for (&pos, (_, labels)) in &asm.labels {
+        for label in labels {
+            positions.try_insert(label.index, pos)?;
+        }
+    }
+
+    Ok(positions)
+}
+
+fn jump_label(inst: &AssemblyInst) -> Option<&crate::runtime::Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label, .. }
+        | AssemblyInst::JumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. }
+        | AssemblyInst::JumpIfNotEqInteger { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+fn jump_label_mut(inst: &mut AssemblyInst) -> Option<&mut crate::runtime::Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label, .. }
+        | AssemblyInst::JumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. }
+        | AssemblyInst::JumpIfNotEqInteger { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+/// The output which is unconditionally written by an instruction if execution
+/// continues after it.
+fn kills(inst: &AssemblyInst) -> Option<Output> {
+    match inst {
+        AssemblyInst::Raw { raw } => output(raw),
+        // Only written to if the iterator produced a value.
+        AssemblyInst::IterNext { .. } => None,
+        AssemblyInst::Jump { .. }
+        | AssemblyInst::JumpIf { .. }
+        | AssemblyInst::JumpIfNot { .. }
+        | AssemblyInst::JumpIfNotEqInteger { .. } => None,
+    }
+}
+
+/// The output of an instruction, if it's the only slot it writes to.
+fn output(inst: &Inst) -> Option<Output> {
+    match *inst {
+        Inst::Not { out, .. }
+        | Inst::Neg { out, .. }
+        | Inst::Closure { out, .. }
+        | Inst::CallOffset { out, .. }
+        | Inst::Call { out, .. }
+        | Inst::CallAssociated { out, .. }
+        | Inst::CallTrait { out, .. }
+        | Inst::LoadInstanceFn { out, .. }
+        | Inst::CallFn { out, .. }
+        | Inst::IndexGet { out, .. }
+        | Inst::TupleIndexGetAt { out, .. }
+        | Inst::ObjectIndexGetAt { out, .. }
+        | Inst::Await { out, .. }
+        | Inst::LoadFn { out, .. }
+        | Inst::Store { out, .. }
+        | Inst::Copy { out, .. }
+        | Inst::Move { out, .. }
+        | Inst::Vec { out, .. }
+        | Inst::Tuple1 { out, .. }
+        | Inst::Tuple2 { out, .. }
+        | Inst::Tuple3 { out, .. }
+        | Inst::Tuple4 { out, .. }
+        | Inst::Tuple { out, .. }
+        | Inst::Environment { out, .. }
+        | Inst::Object { out, .. }
+        | Inst::Range { out, .. }
+        | Inst::EmptyStruct { out, .. }
+        | Inst::Struct { out, .. }
+        | Inst::StructVariant { out, .. }
+        | Inst::String { out, .. }
+        | Inst::Bytes { out, .. }
+        | Inst::StringConcat { out, .. }
+        | Inst::Format { out, .. }
+        | Inst::IsUnit { out, .. }
+        | Inst::Try { out, .. }
+        | Inst::EqByte { out, .. }
+        | Inst::EqChar { out, .. }
+        | Inst::EqInteger { out, .. }
+        | Inst::EqU64 { out, .. }
+        | Inst::EqI128 { out, .. }
+        | Inst::EqU128 { out, .. }
+        | Inst::EqBool { out, .. }
+        | Inst::EqString { out, .. }
+        | Inst::EqBytes { out, .. }
+        | Inst::MatchType { out, .. }
+        | Inst::MatchVariant { out, .. }
+        | Inst::MatchBuiltIn { out, .. }
+        | Inst::MatchSequence { out, .. }
+        | Inst::MatchObject { out, .. }
+        | Inst::Yield { out, .. }
+        | Inst::YieldUnit { out }
+        | Inst::Variant { out, .. }
+        | Inst::Op { out, .. } => Some(out),
+        Inst::Allocate { .. }
+        | Inst::TupleIndexSet { .. }
+        | Inst::ObjectIndexSet { .. }
+        | Inst::IndexSet { .. }
+        | Inst::Select { .. }
+        | Inst::Drop { .. }
+        | Inst::Swap { .. }
+        | Inst::Return { .. }
+        | Inst::ReturnUnit
+        | Inst::Jump { .. }
+        | Inst::JumpIf { .. }
+        | Inst::JumpIfNot { .. }
+        | Inst::JumpIfNotEqInteger { .. }
+        | Inst::CheckType { .. }
+        | Inst::Assign { .. }
+        | Inst::IterNext { .. }
+        | Inst::Panic { .. } => None,
+    }
+}
+
+/// Mark the slots read by an instruction as live.
+///
+/// This must never miss a slot which is read, but it is fine to include more
+/// slots than what is actually read.
+fn reads(inst: &AssemblyInst, slots: &mut Slots) {
+    let raw = match inst {
+        AssemblyInst::Jump { .. } => return,
+        AssemblyInst::JumpIf { addr, .. }
+        | AssemblyInst::JumpIfNot { addr, .. }
+        | AssemblyInst::IterNext { addr, .. }
+        | AssemblyInst::JumpIfNotEqInteger { addr, .. } => {
+            slots.insert(*addr);
+            return;
+        }
+        AssemblyInst::Raw { raw } => raw,
+    };
+
+    match *raw {
+        Inst::Allocate { .. }
+        | Inst::LoadFn { .. }
+        | Inst::Store { .. }
+        | Inst::ReturnUnit
+        | Inst::Jump { .. }
+        | Inst::EmptyStruct { .. }
+        | Inst::String { .. }
+        | Inst::Bytes { .. }
+        | Inst::YieldUnit { .. }
+        | Inst::Panic { .. } => {}
+        Inst::Not { addr, .. }
+        | Inst::Neg { addr, .. }
+        | Inst::LoadInstanceFn { addr, .. }
+        | Inst::TupleIndexGetAt { addr, .. }
+        | Inst::ObjectIndexGetAt { addr, .. }
+        | Inst::Await { addr, .. }
+        | Inst::Copy { addr, .. }
+        | Inst::Move { addr, .. }
+        | Inst::Drop { addr }
+        | Inst::Return { addr }
+        | Inst::Format { addr, .. }
+        | Inst::IsUnit { addr, .. }
+        | Inst::Try { addr, .. }
+        | Inst::EqByte { addr, .. }
+        | Inst::EqChar { addr, .. }
+        | Inst::EqInteger { addr, .. }
+        | Inst::EqU64 { addr, .. }
+        | Inst::EqI128 { addr, .. }
+        | Inst::EqU128 { addr, .. }
+        | Inst::EqBool { addr, .. }
+        | Inst::EqString { addr, .. }
+        | Inst::EqBytes { addr, .. }
+        | Inst::MatchType { addr, .. }
+        | Inst::CheckType { addr, .. }
+        | Inst::MatchVariant { addr, .. }
+        | Inst::MatchBuiltIn { addr, .. }
+        | Inst::MatchSequence { addr, .. }
+        | Inst::MatchObject { addr, .. }
+        | Inst::Yield { addr, .. }
+        | Inst::Variant { addr, .. }
+        | Inst::IterNext { addr, .. }
+        | Inst::JumpIfNotEqInteger { addr, .. } => {
+            slots.insert(addr);
+        }
+        Inst::JumpIf { cond, .. } | Inst::JumpIfNot { cond, .. } => {
+            slots.insert(cond);
+        }
+        Inst::Closure { addr, count, .. }
+        | Inst::Vec { addr, count, .. }
+        | Inst::Tuple { addr, count, .. }
+        | Inst::Environment { addr, count, .. } => {
+            slots.insert_range(addr, count);
+        }
+        Inst::CallOffset { addr, args, .. }
+        | Inst::Call { addr, args, .. }
+        | Inst::CallAssociated { addr, args, .. }
+        | Inst::CallTrait { addr, args, .. } => {
+            slots.insert_range(addr, args);
+        }
+        Inst::CallFn {
+            function,
+            addr,
+            args,
+            ..
+        } => {
+            slots.insert(function);
+            slots.insert_range(addr, args);
+        }
+        Inst::Select { addr, len, .. } | Inst::StringConcat { addr, len, .. } => {
+            slots.insert_range(addr, len);
+        }
+        Inst::IndexGet { target, index, .. } => {
+            slots.insert(target);
+            slots.insert(index);
+        }
+        Inst::TupleIndexSet { target, value, .. } | Inst::ObjectIndexSet { target, value, .. } => {
+            slots.insert(target);
+            slots.insert(value);
+        }
+        Inst::IndexSet {
+            target,
+            index,
+            value,
+        } => {
+            slots.insert(target);
+            slots.insert(index);
+            slots.insert(value);
+        }
+        Inst::Swap { a, b } | Inst::Op { a, b, .. } => {
+            slots.insert(a);
+            slots.insert(b);
+        }
+        Inst::Tuple1 { args, .. } => args.iter().for_each(|&addr| slots.insert(addr)),
+        Inst::Tuple2 { args, .. } => args.iter().for_each(|&addr| slots.insert(addr)),
+        Inst::Tuple3 { args, .. } => args.iter().for_each(|&addr| slots.insert(addr)),
+        Inst::Tuple4 { args, .. } => args.iter().for_each(|&addr| slots.insert(addr)),
+        // The number of fields depends on the object keys stored in the unit,
+        // so assume that everything from the first field onwards is read.
+        Inst::Object { addr, .. }
+        | Inst::Struct { addr, .. }
+        | Inst::StructVariant { addr, .. } => {
+            slots.insert_range(addr, usize::MAX);
+        }
+        Inst::Range { range, addr, .. } => {
+            let count = match range {
+                InstRange::RangeFull => 0,
+                InstRange::RangeFrom | InstRange::RangeTo | InstRange::RangeToInclusive => 1,
+                InstRange::Range | InstRange::RangeInclusive => 2,
+            };
+
+            slots.insert_range(addr, count);
+        }
+        Inst::Assign { target, value, .. } => {
+            let (InstTarget::Address(addr)
+            | InstTarget::Field(addr, _)
+            | InstTarget::TupleField(addr, _)) = target;
+            slots.insert(addr);
+            slots.insert(value);
+        }
+    }
+}
@@ -37,6 +37,13 @@ pub use self::warning::WarningDiagnostic;
 pub(crate) use self::warning::WarningDiagnosticKind;
 mod warning;
 
+pub use self::suggestion::{Edit, Suggestion};
+mod suggestion;
+
+pub(crate) use self::lint::Lints;
+pub use self::lint::{Lint, LintLevel};
+mod lint;
+
 pub use self::runtime_warning::RuntimeWarningDiagnostic;
 pub(crate) use self::runtime_warning::RuntimeWarningDiagnosticKind;
 mod runtime_warning;
@@ -68,6 +75,16 @@ pub enum Diagnostic {
     RuntimeWarning(RuntimeWarningDiagnostic),
 }
 
+/// A level configured for a lint through an attribute, which applies to
+/// everything inside of the given span.
+#[derive(Debug)]
+struct LintScope {
+    source_id: SourceId,
+    span: Span,
+    lint: Lint,
+    level: LintLevel,
+}
+
 /// The diagnostics mode to use.
 #[derive(Debug, Clone, Copy)]
 enum Mode {
@@ -115,6 +132,10 @@ pub struct Diagnostics {
     has_error: bool,
     /// Indicates if diagnostics contains warnings.
     has_warning: bool,
+    /// The configured level of every lint.
+    lints: Lints,
+    /// Lint levels configured through attributes.
+    lint_scopes: Vec<LintScope>,
 }
 
 impl Diagnostics {
@@ -124,6 +145,8 @@ impl Diagnostics {
             mode,
             has_error: false,
             has_warning: false,
+            lints: Lints::default(),
+            lint_scopes: Vec::new(),
         }
     }
 
@@ -190,6 +213,59 @@ impl Diagnostics {
         self.diagnostics
     }
 
+    /// Configure the level of lints for a new build, clearing any levels
+    /// configured through attributes by earlier builds.
+    pub(crate) fn configure_lints(&mut self, lints: Lints) {
+        self.lints = lints;
+        self.lint_scopes.clear();
+    }
+
+    /// Configure the level of a lint for everything inside of the given span.
+    pub(crate) fn lint_scope(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        lint: Lint,
+        level: LintLevel,
+    ) -> alloc::Result<()> {
+        self.lint_scopes.try_push(LintScope {
+            source_id,
+            span,
+            lint,
+            level,
+        })
+    }
+
+    /// Get the level of a lint at the given span.
+    ///
+    /// The innermost scope configuring the lint takes precedence, and if no
+    /// scope applies the configured level is used.
+    fn lint_level(&self, source_id: SourceId, span: Span, lint: Lint) -> LintLevel {
+        let mut level = self.lints.get(lint);
+        let mut innermost = None::<Span>;
+
+        for scope in &self.lint_scopes {
+            if scope.source_id != source_id
+                || scope.lint != lint
+                || scope.span.start > span.start
+                || scope.span.end < span.end
+            {
+                continue;
+            }
+
+            if let Some(innermost) = innermost {
+                if scope.span.range().len() > innermost.range().len() {
+                    continue;
+                }
+            }
+
+            innermost = Some(scope.span);
+            level = scope.level;
+        }
+
+        level
+    }
+
     /// Report an internal error.
     ///
     /// This should be used for programming invariants of the compiler which are
@@ -208,13 +284,15 @@ impl Diagnostics {
         source_id: SourceId,
         span: &dyn Spanned,
         context: Option<Span>,
+        suggestion: Option<Suggestion>,
     ) -> alloc::Result<()> {
-        self.warning(
+        self.warning_with(
             source_id,
             WarningDiagnosticKind::NotUsed {
                 span: span.span(),
                 context,
             },
+            suggestion,
         )
     }
 
@@ -261,13 +339,15 @@ impl Diagnostics {
         source_id: SourceId,
         span: &dyn Spanned,
         context: Option<Span>,
+        suggestion: Option<Suggestion>,
     ) -> alloc::Result<()> {
-        self.warning(
+        self.warning_with(
             source_id,
             WarningDiagnosticKind::TemplateWithoutExpansions {
                 span: span.span(),
                 context,
             },
+            suggestion,
         )
     }
 
@@ -282,13 +362,16 @@ impl Diagnostics {
         variant: &dyn Spanned,
         context: Option<Span>,
     ) -> alloc::Result<()> {
-        self.warning(
+        let suggestion = Suggestion::remove("Remove the parentheses", span.span())?;
+
+        self.warning_with(
             source_id,
             WarningDiagnosticKind::RemoveTupleCallParams {
                 span: span.span(),
                 variant: variant.span(),
                 context,
             },
+            Some(suggestion),
         )
     }
 
@@ -298,9 +381,12 @@ impl Diagnostics {
         source_id: SourceId,
         span: &dyn Spanned,
     ) -> alloc::Result<()> {
-        self.warning(
+        let suggestion = Suggestion::remove("Remove the semicolon", span.span())?;
+
+        self.warning_with(
             source_id,
             WarningDiagnosticKind::UnnecessarySemiColon { span: span.span() },
+            Some(suggestion),
         )
     }
 
@@ -312,13 +398,89 @@ impl Diagnostics {
         context: Option<Span>,
         message: String,
     ) -> alloc::Result<()> {
-        self.warning(
+        let suggestion = match deprecation_replacement(&message) {
+            Some(path) => Some(Suggestion::new(
+                format_args!("Replace with `{path}`"),
+                span.span(),
+                path,
+            )?),
+            None => None,
+        };
+
+        self.warning_with(
             source_id,
             WarningDiagnosticKind::UsedDeprecated {
                 span: span.span(),
                 context,
                 message,
             },
+            suggestion,
+        )
+    }
+
+    /// Indicate that an import is never used.
+    pub(crate) fn unused_import(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedImport { span: span.span() },
+        )
+    }
+
+    /// Indicate that a function is never used.
+    pub(crate) fn unused_function(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedFunction { span: span.span() },
+        )
+    }
+
+    /// Indicate that a binding shadows an earlier binding in the same
+    /// function.
+    pub(crate) fn shadowed_binding(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        shadowed: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::ShadowedBinding {
+                span: span.span(),
+                shadowed: shadowed.span(),
+            },
+        )
+    }
+
+    /// Indicate that a `return` at the end of a function is not needed.
+    pub(crate) fn needless_return(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::NeedlessReturn { span: span.span() },
+        )
+    }
+
+    /// Indicate that a `Result` produced by an expression statement is
+    /// discarded.
+    pub(crate) fn unused_result(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedResult { span: span.span() },
         )
     }
 
@@ -332,17 +494,50 @@ impl Diagnostics {
     where
         WarningDiagnosticKind: From<T>,
     {
-        if !self.mode.warnings() {
-            return Ok(());
+        self.warning_with(source_id, kind, None)
+    }
+
+    /// Push a warning with an optional suggested fix to the collection of
+    /// diagnostics.
+    pub(crate) fn warning_with<T>(
+        &mut self,
+        source_id: SourceId,
+        kind: T,
+        suggestion: Option<Suggestion>,
+    ) -> alloc::Result<()>
+    where
+        WarningDiagnosticKind: From<T>,
+    {
+        let kind = WarningDiagnosticKind::from(kind);
+
+        let level = self.lint_level(source_id, kind.span(), kind.lint());
+
+        match level {
+            LintLevel::Allow => return Ok(()),
+            LintLevel::Warn if !self.mode.warnings() => return Ok(()),
+            _ => {}
+        }
+
+        let mut suggestions = Vec::new();
+
+        if let Some(suggestion) = suggestion {
+            suggestions.try_push(suggestion)?;
         }
 
         self.diagnostics
             .try_push(Diagnostic::Warning(WarningDiagnostic {
                 source_id,
-                kind: kind.into(),
+                kind,
+                suggestions,
+                level,
             }))?;
 
-        self.has_warning = true;
+        if level == LintLevel::Deny {
+            self.has_error = true;
+        } else {
+            self.has_warning = true;
+        }
+
         Ok(())
     }
 
@@ -381,6 +576,27 @@ impl Diagnostics {
     }
 }
 
+/// Extract the path to replace a deprecated function with from deprecation
+/// messages like `Use String::from instead`.
+fn deprecation_replacement(message: &str) -> Option<&str> {
+    let path = message
+        .trim()
+        .trim_end_matches('.')
+        .strip_prefix("Use ")?
+        .strip_suffix(" instead")?
+        .trim_matches('`');
+
+    let is_path = path
+        .chars()
+        .all(|c| c == '_' || c == ':' || c == '<' || c == '>' || c.is_alphanumeric());
+
+    if path.is_empty() || !is_path {
+        return None;
+    }
+
+    Some(path)
+}
+
 impl Default for Diagnostics {
     fn default() -> Self {
         Self::with_mode(Mode::All)
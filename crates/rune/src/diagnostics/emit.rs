@@ -5,9 +5,14 @@ use core::fmt;
 use std::io;
 
 use codespan_reporting::diagnostic as d;
+use codespan_reporting::files::Files;
 use codespan_reporting::term;
 pub use codespan_reporting::term::termcolor;
+#[cfg(feature = "emit-json")]
+use codespan_reporting::term::termcolor::NoColor;
 use codespan_reporting::term::termcolor::WriteColor;
+#[cfg(feature = "emit-json")]
+use serde::Serialize;
 
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
@@ -15,7 +20,7 @@ use crate::alloc::{self, String};
 use crate::ast::{Span, Spanned};
 use crate::compile::{ErrorKind, LinkerError, Location};
 use crate::diagnostics::{
-    Diagnostic, FatalDiagnostic, FatalDiagnosticKind, RuntimeWarningDiagnostic,
+    Diagnostic, FatalDiagnostic, FatalDiagnosticKind, LintLevel, RuntimeWarningDiagnostic,
     RuntimeWarningDiagnosticKind, WarningDiagnostic, WarningDiagnosticKind,
 };
 use crate::hash::Hash;
@@ -39,6 +44,9 @@ pub enum EmitError {
     Alloc(alloc::Error),
     /// Codespan reporting error.
     CodespanReporting(codespan_reporting::files::Error),
+    /// JSON serialization error.
+    #[cfg(feature = "emit-json")]
+    Json(serde_json::Error),
 }
 
 impl fmt::Display for EmitError {
@@ -48,6 +56,8 @@ impl fmt::Display for EmitError {
             EmitError::Io(error) => error.fmt(f),
             EmitError::Alloc(error) => error.fmt(f),
             EmitError::CodespanReporting(error) => error.fmt(f),
+            #[cfg(feature = "emit-json")]
+            EmitError::Json(error) => error.fmt(f),
         }
     }
 }
@@ -70,6 +80,13 @@ impl From<codespan_reporting::files::Error> for EmitError {
     }
 }
 
+#[cfg(feature = "emit-json")]
+impl From<serde_json::Error> for EmitError {
+    fn from(error: serde_json::Error) -> Self {
+        EmitError::Json(error)
+    }
+}
+
 cfg_std! {
     impl std::error::Error for EmitError {
         fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -77,6 +94,8 @@ cfg_std! {
                 EmitError::Io(error) => Some(error),
                 EmitError::Alloc(error) => Some(error),
                 EmitError::CodespanReporting(error) => Some(error),
+                #[cfg(feature = "emit-json")]
+                EmitError::Json(error) => Some(error),
             }
         }
     }
@@ -159,6 +178,66 @@ impl Diagnostics {
 
         Ok(())
     }
+
+    /// Generate machine-readable diagnostics, writing one JSON object per
+    /// line for each diagnostic.
+    ///
+    /// Each object contains the `severity`, a stable `code` identifying the
+    /// kind of diagnostic, the `message`, a list of `spans` with file, line
+    /// and column ranges, any `notes` and the human-readable `rendered` form
+    /// of the diagnostic. This is modelled after `cargo
+    /// --message-format=json`.
+    ///
+    /// Lines and columns are 1-based, and columns count characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Diagnostics, Source, Sources};
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::memory("pub fn main() { a }")?)?;
+    ///
+    /// let mut diagnostics = Diagnostics::new();
+    ///
+    /// let _ = rune::prepare(&mut sources)
+    ///     .with_diagnostics(&mut diagnostics)
+    ///     .build();
+    ///
+    /// let mut out = Vec::new();
+    /// diagnostics.emit_json(&mut out, &sources)?;
+    ///
+    /// let out = String::from_utf8(out)?;
+    /// assert!(out.starts_with("{\"severity\":\"error\",\"code\":\"missing_local\""));
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[cfg(feature = "emit-json")]
+    #[cfg_attr(rune_docsrs, doc(cfg(feature = "emit-json")))]
+    pub fn emit_json<O>(&self, out: &mut O, sources: &Sources) -> Result<(), EmitError>
+    where
+        O: io::Write,
+    {
+        for diagnostic in self.diagnostics() {
+            let (code, diagnostic) = match diagnostic {
+                Diagnostic::Fatal(e) => (fatal_code(e), fatal_diagnostic(e, sources)?),
+                Diagnostic::Warning(w) => {
+                    // Warnings are described by their primary label, so use
+                    // that as the message instead of the generic heading.
+                    let mut diagnostic = warning_diagnostic(w, sources)?;
+                    diagnostic.message = w.try_to_string()?.into_std();
+                    (w.lint().name(), diagnostic)
+                }
+                Diagnostic::RuntimeWarning(w) => (
+                    runtime_warning_code(w),
+                    runtime_warning_diagnostic(w, None, None)?,
+                ),
+            };
+
+            json_diagnostic_emit(out, sources, code, &diagnostic)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl VmError {
@@ -537,6 +616,16 @@ fn warning_diagnostics_emit<O>(
 where
     O: WriteColor,
 {
+    let diagnostic = warning_diagnostic(this, sources)?;
+    term::emit(out, config, sources, &diagnostic)?;
+    Ok(())
+}
+
+/// Helper to build the diagnostic for a warning.
+fn warning_diagnostic(
+    this: &WarningDiagnostic,
+    sources: &Sources,
+) -> Result<d::Diagnostic<SourceId>, EmitError> {
     let mut notes = ::rust_alloc::vec::Vec::new();
     let mut labels = ::rust_alloc::vec::Vec::new();
 
@@ -569,6 +658,12 @@ where
                     .with_message("This code diverges"),
             );
         }
+        WarningDiagnosticKind::ShadowedBinding { shadowed, .. } => {
+            labels.push(
+                d::Label::secondary(this.source_id(), shadowed.range())
+                    .with_message("Previous binding"),
+            );
+        }
         _ => {}
     };
 
@@ -578,13 +673,23 @@ where
         );
     }
 
-    let diagnostic = d::Diagnostic::warning()
+    for suggestion in this.suggestions() {
+        notes.push(format!("Help: {suggestion}"));
+    }
+
+    if this.level() == LintLevel::Deny {
+        notes.push(format!("Note: The `{}` lint is denied", this.lint()));
+
+        return Ok(d::Diagnostic::error()
+            .with_message("Error")
+            .with_labels(labels)
+            .with_notes(notes));
+    }
+
+    Ok(d::Diagnostic::warning()
         .with_message("Warning")
         .with_labels(labels)
-        .with_notes(notes);
-
-    term::emit(out, config, sources, &diagnostic)?;
-    Ok(())
+        .with_notes(notes))
 }
 
 /// Helper to emit diagnostics for a runtime warning.
@@ -599,6 +704,17 @@ fn runtime_warning_diagnostics_emit<O>(
 where
     O: WriteColor,
 {
+    let diagnostic = runtime_warning_diagnostic(this, debug_info, context)?;
+    term::emit(out, config, sources, &diagnostic)?;
+    Ok(())
+}
+
+/// Helper to build the diagnostic for a runtime warning.
+fn runtime_warning_diagnostic(
+    this: &RuntimeWarningDiagnostic,
+    debug_info: Option<&DebugInfo>,
+    context: Option<&Context>,
+) -> Result<d::Diagnostic<SourceId>, EmitError> {
     let mut notes = ::rust_alloc::vec::Vec::new();
     let mut labels = ::rust_alloc::vec::Vec::new();
     let mut message = String::new();
@@ -635,13 +751,10 @@ where
         }
     };
 
-    let diagnostic = d::Diagnostic::warning()
+    Ok(d::Diagnostic::warning()
         .with_message(message)
         .with_labels(labels)
-        .with_notes(notes);
-
-    term::emit(out, config, sources, &diagnostic)?;
-    Ok(())
+        .with_notes(notes))
 }
 
 /// Custom shared helper for emitting diagnostics for a single error.
@@ -654,6 +767,21 @@ fn fatal_diagnostics_emit<O>(
 where
     O: WriteColor,
 {
+    if let FatalDiagnosticKind::Internal(message) = this.kind() {
+        writeln!(out, "internal error: {}", message)?;
+        return Ok(());
+    }
+
+    let diagnostic = fatal_diagnostic(this, sources)?;
+    term::emit(out, config, sources, &diagnostic)?;
+    Ok(())
+}
+
+/// Helper to build the diagnostic for a single error.
+fn fatal_diagnostic(
+    this: &FatalDiagnostic,
+    sources: &Sources,
+) -> Result<d::Diagnostic<SourceId>, EmitError> {
     let mut labels = ::rust_alloc::vec::Vec::new();
     let mut notes = ::rust_alloc::vec::Vec::new();
 
@@ -666,34 +794,26 @@ where
 
     match this.kind() {
         FatalDiagnosticKind::Internal(message) => {
-            writeln!(out, "internal error: {}", message)?;
-            return Ok(());
+            return Ok(d::Diagnostic::error().with_message(format!("internal error: {message}")));
         }
-        FatalDiagnosticKind::LinkError(error) => {
-            match error {
-                LinkerError::MissingFunction { hash, spans } => {
-                    let mut labels = ::rust_alloc::vec::Vec::new();
-
-                    for (span, source_id) in spans {
-                        labels.push(
-                            d::Label::primary(*source_id, span.range())
-                                .with_message("called here."),
-                        );
-                    }
-
-                    let diagnostic = d::Diagnostic::error()
-                        .with_message(format!(
-                            "linker error: missing function with hash `{}`",
-                            hash
-                        ))
-                        .with_labels(labels);
+        FatalDiagnosticKind::LinkError(error) => match error {
+            LinkerError::MissingFunction { hash, spans } => {
+                let mut labels = ::rust_alloc::vec::Vec::new();
 
-                    term::emit(out, config, sources, &diagnostic)?;
+                for (span, source_id) in spans {
+                    labels.push(
+                        d::Label::primary(*source_id, span.range()).with_message("called here."),
+                    );
                 }
-            }
 
-            return Ok(());
-        }
+                return Ok(d::Diagnostic::error()
+                    .with_message(format!(
+                        "linker error: missing function with hash `{}`",
+                        hash
+                    ))
+                    .with_labels(labels));
+            }
+        },
         FatalDiagnosticKind::CompileError(error) => {
             format_compile_error(
                 this,
@@ -706,13 +826,14 @@ where
         }
     };
 
-    let diagnostic = d::Diagnostic::error()
+    for suggestion in this.suggestions() {
+        notes.push(format!("Help: {suggestion}"));
+    }
+
+    return Ok(d::Diagnostic::error()
         .with_message(this.kind().try_to_string()?)
         .with_labels(labels)
-        .with_notes(notes);
-
-    term::emit(out, config, sources, &diagnostic)?;
-    return Ok(());
+        .with_notes(notes));
 
     fn format_compile_error(
         this: &FatalDiagnostic,
@@ -863,3 +984,101 @@ where
         Ok(())
     }
 }
+
+/// Get the code of a fatal diagnostic.
+#[cfg(feature = "emit-json")]
+fn fatal_code(this: &FatalDiagnostic) -> &'static str {
+    match this.kind() {
+        FatalDiagnosticKind::CompileError(error) => error.kind().code(),
+        FatalDiagnosticKind::LinkError(LinkerError::MissingFunction { .. }) => "missing_function",
+        FatalDiagnosticKind::Internal(..) => "internal",
+    }
+}
+
+/// Get the code of a runtime warning.
+#[cfg(feature = "emit-json")]
+fn runtime_warning_code(this: &RuntimeWarningDiagnostic) -> &'static str {
+    match this.kind() {
+        RuntimeWarningDiagnosticKind::UsedDeprecated { .. } => "used_deprecated",
+    }
+}
+
+#[cfg(feature = "emit-json")]
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    severity: &'static str,
+    code: &'static str,
+    message: &'a str,
+    spans: ::rust_alloc::vec::Vec<JsonSpan<'a>>,
+    notes: &'a [::rust_alloc::string::String],
+    rendered: ::rust_alloc::string::String,
+}
+
+#[cfg(feature = "emit-json")]
+#[derive(Serialize)]
+struct JsonSpan<'a> {
+    file: &'a str,
+    byte_start: usize,
+    byte_end: usize,
+    line_start: usize,
+    line_end: usize,
+    column_start: usize,
+    column_end: usize,
+    is_primary: bool,
+    label: Option<&'a str>,
+}
+
+/// Helper to emit a single diagnostic as a line of JSON.
+#[cfg(feature = "emit-json")]
+fn json_diagnostic_emit<O>(
+    out: &mut O,
+    sources: &Sources,
+    code: &'static str,
+    diagnostic: &d::Diagnostic<SourceId>,
+) -> Result<(), EmitError>
+where
+    O: io::Write,
+{
+    let severity = match diagnostic.severity {
+        d::Severity::Bug | d::Severity::Error => "error",
+        d::Severity::Warning => "warning",
+        d::Severity::Note => "note",
+        d::Severity::Help => "help",
+    };
+
+    let mut spans = ::rust_alloc::vec::Vec::with_capacity(diagnostic.labels.len());
+
+    for label in &diagnostic.labels {
+        let start = sources.location(label.file_id, label.range.start)?;
+        let end = sources.location(label.file_id, label.range.end)?;
+
+        spans.push(JsonSpan {
+            file: Files::name(sources, label.file_id)?,
+            byte_start: label.range.start,
+            byte_end: label.range.end,
+            line_start: start.line_number,
+            line_end: end.line_number,
+            column_start: start.column_number,
+            column_end: end.column_number,
+            is_primary: matches!(label.style, d::LabelStyle::Primary),
+            label: Some(label.message.as_str()).filter(|m| !m.is_empty()),
+        });
+    }
+
+    let mut rendered = NoColor::new(::rust_alloc::vec::Vec::new());
+    term::emit(&mut rendered, &term::Config::default(), sources, diagnostic)?;
+
+    let json = JsonDiagnostic {
+        severity,
+        code,
+        message: &diagnostic.message,
+        spans,
+        notes: &diagnostic.notes,
+        rendered: ::rust_alloc::string::String::from_utf8_lossy(&rendered.into_inner())
+            .into_owned(),
+    };
+
+    serde_json::to_writer(&mut *out, &json)?;
+    writeln!(out)?;
+    Ok(())
+}
@@ -5,6 +5,7 @@ use ::rust_alloc::boxed::Box;
 #[cfg(feature = "emit")]
 use crate::ast::{Span, Spanned};
 use crate::compile::{self, LinkerError};
+use crate::diagnostics::Suggestion;
 use crate::SourceId;
 
 /// Fatal diagnostic emitted during compilation. Fatal diagnostics indicates an
@@ -28,6 +29,14 @@ impl FatalDiagnostic {
         &self.kind
     }
 
+    /// Suggested changes which would address the error.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        match &*self.kind {
+            FatalDiagnosticKind::CompileError(error) => error.suggestions(),
+            _ => &[],
+        }
+    }
+
     /// The kind of the load error.
     #[cfg(test)]
     pub(crate) fn into_kind(self) -> FatalDiagnosticKind {
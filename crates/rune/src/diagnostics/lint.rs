@@ -0,0 +1,182 @@
+use core::fmt;
+
+use crate as rune;
+use crate::alloc::prelude::*;
+
+macro_rules! lints {
+    ($($(#[$meta:meta])* $variant:ident => $name:literal, $level:ident;)*) => {
+        /// A named lint, which can be configured to be allowed, to warn or to
+        /// deny compilation.
+        ///
+        /// The level of a lint can be configured through
+        /// [Options::lint][crate::Options::lint], with `#[allow(..)]`,
+        /// `#[warn(..)]` and `#[deny(..)]` attributes on items, with
+        /// `#![allow(..)]` and friends at the top of a module, and through the
+        /// `[lints]` table of a `Rune.toml` manifest.
+        #[derive(Debug, TryClone, Clone, Copy, PartialEq, Eq, Hash)]
+        #[try_clone(copy)]
+        #[non_exhaustive]
+        pub enum Lint {
+            $($(#[$meta])* $variant,)*
+        }
+
+        impl Lint {
+            /// All available lints.
+            pub const ALL: &'static [Lint] = &[$(Lint::$variant,)*];
+
+            /// The name of the lint, as used in attributes and manifests.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use rune::diagnostics::Lint;
+            ///
+            /// assert_eq!(Lint::UnusedImports.name(), "unused_imports");
+            /// ```
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Lint::$variant => $name,)*
+                }
+            }
+
+            /// Look up a lint by its name.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use rune::diagnostics::Lint;
+            ///
+            /// assert_eq!(Lint::from_name("needless_return"), Some(Lint::NeedlessReturn));
+            /// assert_eq!(Lint::from_name("not_a_lint"), None);
+            /// ```
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Lint::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// The level the lint has unless otherwise configured.
+            pub fn default_level(self) -> LintLevel {
+                match self {
+                    $(Lint::$variant => LintLevel::$level,)*
+                }
+            }
+        }
+    };
+}
+
+lints! {
+    /// A value or an item is produced but never used.
+    NotUsed => "not_used", Warn;
+    /// Code which can never be reached.
+    Unreachable => "unreachable", Warn;
+    /// A `let` binding with a pattern which might not match, like `let [a] = v`.
+    LetPatternMightPanic => "let_pattern_might_panic", Warn;
+    /// A template string without any expansions, like `` `Hello` ``.
+    TemplateWithoutExpansions => "template_without_expansions", Warn;
+    /// Parentheses when constructing a unit variant, like `None()`.
+    RemoveTupleCallParams => "remove_tuple_call_params", Warn;
+    /// A semicolon after an item which doesn't need one.
+    UnnecessarySemiColon => "unnecessary_semi_colon", Warn;
+    /// Use of a deprecated function.
+    UsedDeprecated => "used_deprecated", Warn;
+    /// An import which is never used.
+    UnusedImports => "unused_imports", Warn;
+    /// A function which is never used.
+    UnusedFunctions => "unused_functions", Warn;
+    /// A binding which shadows another binding in the same function, like
+    /// `let a = 1; let a = 2;`.
+    ShadowedBindings => "shadowed_bindings", Allow;
+    /// A `return` as the last statement of a function.
+    NeedlessReturn => "needless_return", Warn;
+    /// A `Result` produced by an expression statement which is discarded.
+    UnusedResult => "unused_result", Warn;
+}
+
+impl fmt::Display for Lint {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// The level at which a [Lint] is reported.
+#[derive(Debug, TryClone, Clone, Copy, PartialEq, Eq, Hash)]
+#[try_clone(copy)]
+#[non_exhaustive]
+pub enum LintLevel {
+    /// The lint is not reported.
+    Allow,
+    /// The lint is reported as a warning.
+    Warn,
+    /// The lint is reported as an error, which causes compilation to fail.
+    Deny,
+}
+
+impl LintLevel {
+    /// The name of the level, as used in attributes and manifests.
+    pub fn name(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        }
+    }
+
+    /// Look up a level by its name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::diagnostics::LintLevel;
+    ///
+    /// assert_eq!(LintLevel::from_name("deny"), Some(LintLevel::Deny));
+    /// assert_eq!(LintLevel::from_name("forbid"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(LintLevel::Allow),
+            "warn" => Some(LintLevel::Warn),
+            "deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// The configured level of every lint.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Lints {
+    levels: [LintLevel; Lint::ALL.len()],
+}
+
+impl Lints {
+    /// Get the configured level of a lint.
+    pub(crate) fn get(&self, lint: Lint) -> LintLevel {
+        self.levels[lint as usize]
+    }
+
+    /// Set the level of a lint.
+    pub(crate) fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels[lint as usize] = level;
+    }
+}
+
+impl Default for Lints {
+    fn default() -> Self {
+        let mut levels = [LintLevel::Warn; Lint::ALL.len()];
+
+        for &lint in Lint::ALL {
+            levels[lint as usize] = lint.default_level();
+        }
+
+        Self { levels }
+    }
+}
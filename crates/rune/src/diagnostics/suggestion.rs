@@ -0,0 +1,84 @@
+use core::fmt;
+
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::alloc::{self, String, Vec};
+use crate::ast::Span;
+
+/// A machine-applicable change which addresses a diagnostic.
+///
+/// All edits apply to the source the diagnostic is associated with.
+#[derive(Debug, TryClone)]
+pub struct Suggestion {
+    /// A short human-readable description of the change.
+    message: String,
+    /// The edits making up the change.
+    edits: Vec<Edit>,
+}
+
+impl Suggestion {
+    /// Construct a suggestion consisting of a single edit.
+    pub(crate) fn new<M>(message: M, span: Span, replacement: &str) -> alloc::Result<Self>
+    where
+        M: fmt::Display,
+    {
+        let mut edits = Vec::new();
+
+        edits.try_push(Edit {
+            span,
+            replacement: replacement.try_to_owned()?,
+        })?;
+
+        Ok(Self {
+            message: message.try_to_string()?,
+            edits,
+        })
+    }
+
+    /// Construct a suggestion which removes the given span.
+    pub(crate) fn remove<M>(message: M, span: Span) -> alloc::Result<Self>
+    where
+        M: fmt::Display,
+    {
+        Self::new(message, span, "")
+    }
+
+    /// A short human-readable description of the change.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The edits making up the change, ordered by where they apply.
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+}
+
+impl fmt::Display for Suggestion {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+/// A single edit which is part of a [Suggestion].
+#[derive(Debug, TryClone)]
+pub struct Edit {
+    /// The span being replaced.
+    #[try_clone(copy)]
+    span: Span,
+    /// The text to replace the span with.
+    replacement: String,
+}
+
+impl Edit {
+    /// The span being replaced, where an empty span indicates an insertion.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// The text to replace the span with.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
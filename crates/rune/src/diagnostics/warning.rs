@@ -1,8 +1,9 @@
 use core::fmt;
 
-use crate::alloc::String;
+use crate::alloc::{String, Vec};
 use crate::ast::Span;
 use crate::ast::Spanned;
+use crate::diagnostics::{Lint, LintLevel, Suggestion};
 use crate::SourceId;
 
 /// Warning diagnostic emitted during compilation. Warning diagnostics indicates
@@ -13,6 +14,11 @@ pub struct WarningDiagnostic {
     pub(crate) source_id: SourceId,
     /// The kind of the warning.
     pub(crate) kind: WarningDiagnosticKind,
+    /// Suggested changes which would address the warning.
+    pub(crate) suggestions: Vec<Suggestion>,
+    /// The level the warning was reported at, which is either
+    /// [LintLevel::Warn] or [LintLevel::Deny].
+    pub(crate) level: LintLevel,
 }
 
 impl WarningDiagnostic {
@@ -21,6 +27,23 @@ impl WarningDiagnostic {
         self.source_id
     }
 
+    /// Suggested changes which would address the warning.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
+    /// The lint which caused the warning.
+    pub fn lint(&self) -> Lint {
+        self.kind.lint()
+    }
+
+    /// The level the warning was reported at.
+    ///
+    /// If the lint is denied, the warning is treated as an error.
+    pub fn level(&self) -> LintLevel {
+        self.level
+    }
+
     /// The kind of the warning.
     #[cfg(feature = "emit")]
     pub(crate) fn kind(&self) -> &WarningDiagnosticKind {
@@ -49,15 +72,7 @@ impl WarningDiagnostic {
 impl Spanned for WarningDiagnostic {
     /// Get the span of the warning.
     fn span(&self) -> Span {
-        match &self.kind {
-            WarningDiagnosticKind::NotUsed { span, .. } => *span,
-            WarningDiagnosticKind::Unreachable { span, .. } => *span,
-            WarningDiagnosticKind::LetPatternMightPanic { span, .. } => *span,
-            WarningDiagnosticKind::TemplateWithoutExpansions { span, .. } => *span,
-            WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
-            WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
-            WarningDiagnosticKind::UsedDeprecated { span, .. } => *span,
-        }
+        self.kind.span()
     }
 }
 
@@ -133,6 +148,73 @@ pub(crate) enum WarningDiagnosticKind {
         /// Deprecated message.
         message: String,
     },
+    /// An import which is never used.
+    UnusedImport {
+        /// The span of the import.
+        span: Span,
+    },
+    /// A function which is never used.
+    UnusedFunction {
+        /// The span of the function.
+        span: Span,
+    },
+    /// A binding which shadows an earlier binding.
+    ShadowedBinding {
+        /// The span of the binding.
+        span: Span,
+        /// The span of the binding being shadowed.
+        shadowed: Span,
+    },
+    /// A `return` at the end of a function.
+    NeedlessReturn {
+        /// The span of the `return` expression.
+        span: Span,
+    },
+    /// A `Result` which is discarded.
+    UnusedResult {
+        /// The span of the expression producing the result.
+        span: Span,
+    },
+}
+
+impl WarningDiagnosticKind {
+    /// The lint which controls the warning.
+    pub(crate) fn lint(&self) -> Lint {
+        match self {
+            WarningDiagnosticKind::NotUsed { .. } => Lint::NotUsed,
+            WarningDiagnosticKind::Unreachable { .. } => Lint::Unreachable,
+            WarningDiagnosticKind::LetPatternMightPanic { .. } => Lint::LetPatternMightPanic,
+            WarningDiagnosticKind::TemplateWithoutExpansions { .. } => {
+                Lint::TemplateWithoutExpansions
+            }
+            WarningDiagnosticKind::RemoveTupleCallParams { .. } => Lint::RemoveTupleCallParams,
+            WarningDiagnosticKind::UnnecessarySemiColon { .. } => Lint::UnnecessarySemiColon,
+            WarningDiagnosticKind::UsedDeprecated { .. } => Lint::UsedDeprecated,
+            WarningDiagnosticKind::UnusedImport { .. } => Lint::UnusedImports,
+            WarningDiagnosticKind::UnusedFunction { .. } => Lint::UnusedFunctions,
+            WarningDiagnosticKind::ShadowedBinding { .. } => Lint::ShadowedBindings,
+            WarningDiagnosticKind::NeedlessReturn { .. } => Lint::NeedlessReturn,
+            WarningDiagnosticKind::UnusedResult { .. } => Lint::UnusedResult,
+        }
+    }
+
+    /// The span of the warning.
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            WarningDiagnosticKind::NotUsed { span, .. } => *span,
+            WarningDiagnosticKind::Unreachable { span, .. } => *span,
+            WarningDiagnosticKind::LetPatternMightPanic { span, .. } => *span,
+            WarningDiagnosticKind::TemplateWithoutExpansions { span, .. } => *span,
+            WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
+            WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
+            WarningDiagnosticKind::UsedDeprecated { span, .. } => *span,
+            WarningDiagnosticKind::UnusedImport { span, .. } => *span,
+            WarningDiagnosticKind::UnusedFunction { span, .. } => *span,
+            WarningDiagnosticKind::ShadowedBinding { span, .. } => *span,
+            WarningDiagnosticKind::NeedlessReturn { span, .. } => *span,
+            WarningDiagnosticKind::UnusedResult { span, .. } => *span,
+        }
+    }
 }
 
 impl fmt::Display for WarningDiagnosticKind {
@@ -156,6 +238,17 @@ impl fmt::Display for WarningDiagnosticKind {
             WarningDiagnosticKind::UsedDeprecated { message, .. } => {
                 write!(f, "Used deprecated function: {message}")
             }
+            WarningDiagnosticKind::UnusedImport { .. } => write!(f, "Unused import"),
+            WarningDiagnosticKind::UnusedFunction { .. } => write!(f, "Unused function"),
+            WarningDiagnosticKind::ShadowedBinding { .. } => {
+                write!(f, "Binding shadows an earlier binding")
+            }
+            WarningDiagnosticKind::NeedlessReturn { .. } => {
+                write!(f, "Unneeded `return` at the end of function")
+            }
+            WarningDiagnosticKind::UnusedResult { .. } => {
+                write!(f, "Unused `Result` which should be handled")
+            }
         }
     }
 }
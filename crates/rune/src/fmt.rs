@@ -6,7 +6,10 @@ mod tests;
 mod comments;
 mod error;
 mod indent_writer;
+mod options;
 mod printer;
+#[cfg(any(feature = "languageserver", test))]
+mod range;
 mod whitespace;
 
 use crate::alloc::prelude::*;
@@ -17,18 +20,91 @@ use crate::SourceId;
 use self::error::FormattingError;
 use self::printer::Printer;
 
+pub use self::options::FormatOptions;
+
+/// The name of the file which formatting options are loaded from, if it is
+/// present in the root of a project.
+pub const FORMAT_FILE: &str = "rune.fmt.toml";
+
 /// Format the given source.
-pub(crate) fn layout_source(source: &str) -> Result<Vec<u8>, FormattingError> {
+pub(crate) fn layout_source(
+    source: &str,
+    options: &FormatOptions,
+) -> Result<Vec<u8>, FormattingError> {
     let mut parser = Parser::new(source, SourceId::new(0), true);
 
     let ast = ast::File::parse(&mut parser)?;
-    let mut printer: Printer = Printer::new(source)?;
+    let mut printer: Printer = Printer::new(source, options)?;
     printer.visit_file(&ast)?;
     printer.commit()
 }
 
+/// Format the smallest part of the given source which covers `span`.
+///
+/// This finds the innermost sequence of items or statements which overlap
+/// with `span` and which can be laid out on lines of their own, and returns
+/// the region of the source which they cover together with its replacement.
+/// Returns `None` if no such part can be found.
+#[cfg(any(feature = "languageserver", test))]
+pub(crate) fn layout_span(
+    source: &str,
+    options: &FormatOptions,
+    span: crate::ast::Span,
+) -> Result<Option<(crate::ast::Span, Vec<u8>)>, FormattingError> {
+    let mut parser = Parser::new(source, SourceId::new(0), true);
+
+    let ast = ast::File::parse(&mut parser)?;
+
+    let Some(found) = range::find(source, &ast, span) else {
+        return Ok(None);
+    };
+
+    let mut printer = Printer::new_in(source, options, found.region, found.depth)?;
+
+    match found.target {
+        range::Target::Items(items) => printer.visit_items(items)?,
+        range::Target::Fns(fns) => printer.visit_impl_fns(fns)?,
+        range::Target::Stmts(stmts) => printer.visit_statements(stmts)?,
+    }
+
+    let mut formatted = printer.commit()?;
+
+    // The region is the last line of a file without a trailing newline.
+    if !source[found.region.range()].ends_with('\n') && formatted.last() == Some(&b'\n') {
+        formatted.pop();
+    }
+
+    Ok(Some((found.region, formatted)))
+}
+
 /// Format the given source.
 pub fn format_source(source: &str) -> Result<String, impl std::error::Error> {
-    let formatted = layout_source(source)?;
+    format_source_with(source, &FormatOptions::default())
+}
+
+/// Format the given source using the specified options.
+pub fn format_source_with(
+    source: &str,
+    options: &FormatOptions,
+) -> Result<String, impl std::error::Error> {
+    let formatted = layout_source(source, options)?;
     Ok::<String, FormattingError>(String::from_utf8(formatted).unwrap())
 }
+
+/// Load formatting options from a [`FORMAT_FILE`] in the given directory,
+/// returning `None` if the file doesn't exist.
+#[cfg(feature = "languageserver")]
+pub(crate) fn load_format_file(dir: &std::path::Path) -> anyhow::Result<Option<FormatOptions>> {
+    use anyhow::Context;
+
+    let path = dir.join(FORMAT_FILE);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error).context(path.display().try_to_string()?),
+    };
+
+    let options = toml::from_str(&contents).context(path.display().try_to_string()?)?;
+    Ok(Some(options))
+}
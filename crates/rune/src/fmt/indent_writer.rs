@@ -18,14 +18,16 @@ use super::whitespace::EmptyLine;
 pub(super) struct IndentedWriter {
     lines: Vec<Vec<u8>>,
     indent: usize,
+    indent_size: usize,
     needs_indent: bool,
 }
 
 impl IndentedWriter {
-    pub(super) fn new() -> alloc::Result<Self> {
+    pub(super) fn new(indent_size: usize) -> alloc::Result<Self> {
         Ok(Self {
             lines: try_vec![Vec::new()],
             indent: 0,
+            indent_size,
             needs_indent: true,
         })
     }
@@ -35,11 +37,11 @@ impl IndentedWriter {
     }
 
     pub(super) fn indent(&mut self) {
-        self.indent += 4;
+        self.indent += self.indent_size;
     }
 
     pub(super) fn dedent(&mut self) {
-        self.indent = self.indent.saturating_sub(4);
+        self.indent = self.indent.saturating_sub(self.indent_size);
     }
 
     fn write_indent(&mut self) -> alloc::Result<usize> {
@@ -93,7 +95,7 @@ impl TryWrite for IndentedWriter {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum ResolvedSpan {
     Empty(EmptyLine),
     Comment(Comment),
@@ -108,34 +110,69 @@ impl ResolvedSpan {
     }
 }
 
+/// A position in the output which can be restored to, used to try out a
+/// layout and discard it if it doesn't fit.
+pub(super) struct Checkpoint {
+    /// The number of lines which are untouched since the checkpoint.
+    keep: usize,
+    /// Copies of the lines after `keep`, which might be modified by comments
+    /// being added to the end of them.
+    tail: Vec<Vec<u8>>,
+    /// The line which was being written.
+    line: usize,
+    indent: usize,
+    needs_indent: bool,
+    queue_position: usize,
+    overflow: bool,
+}
+
 /// Writes a span to the writer, injecting comments and empty lines from the source file.
 pub(super) struct SpanInjectionWriter<'a> {
     writer: IndentedWriter,
     queued_spans: Vec<ResolvedSpan>,
+    /// The position of the next queued span to write.
+    queue_position: usize,
+    /// If we're currently writing everything on a single line.
+    flat: bool,
+    /// If what's being written on a single line doesn't fit.
+    overflow: bool,
     source: &'a str,
 }
 
 impl<'a> SpanInjectionWriter<'a> {
-    pub(super) fn new(writer: IndentedWriter, source: &'a str) -> Result<Self, FormattingError> {
+    /// Construct a new writer, injecting the comments and empty lines which
+    /// start inside of `region`.
+    pub(super) fn new(
+        writer: IndentedWriter,
+        source: &'a str,
+        region: Span,
+    ) -> Result<Self, FormattingError> {
         let comment_spans = super::comments::parse_comments(source)?;
         let empty_line_spans = super::whitespace::gather_empty_line_spans(source)?;
 
         let mut queued_spans = Vec::new();
         queued_spans.try_extend(comment_spans.into_iter().map(ResolvedSpan::Comment))?;
         queued_spans.try_extend(empty_line_spans.into_iter().map(ResolvedSpan::Empty))?;
+        queued_spans.retain(|span| {
+            let start = span.span().start;
+            region.start <= start && start < region.end
+        });
 
         queued_spans.sort_by_key(|span| span.span().start);
 
         Ok(Self {
             writer,
             queued_spans,
+            queue_position: 0,
+            flat: false,
+            overflow: false,
             source,
         })
     }
 
     pub(super) fn into_inner(mut self) -> Result<Vec<Vec<u8>>, FormattingError> {
-        while !self.queued_spans.is_empty() {
-            let span = self.queued_spans.remove(0);
+        while let Some(span) = self.queued_spans.get(self.queue_position).cloned() {
+            self.queue_position += 1;
             let mut empties = 0;
             self.write_span(span, &mut empties, usize::MAX)?;
         }
@@ -143,6 +180,90 @@ impl<'a> SpanInjectionWriter<'a> {
         Ok(self.writer.into_inner())
     }
 
+    /// Test if we're currently writing everything on a single line.
+    pub(super) fn is_flat(&self) -> bool {
+        self.flat
+    }
+
+    /// Set if everything should be written on a single line, returning the
+    /// previous value.
+    ///
+    /// Comments and empty lines encountered while flat are not written, but
+    /// cause the output to overflow so that the caller can tell that the
+    /// layout has to be broken up.
+    pub(super) fn set_flat(&mut self, flat: bool) -> bool {
+        core::mem::replace(&mut self.flat, flat)
+    }
+
+    /// Construct a checkpoint which can be restored to.
+    pub(super) fn checkpoint(&self) -> alloc::Result<Checkpoint> {
+        let keep = self.writer.lines.len().saturating_sub(2);
+        let mut tail = Vec::new();
+
+        for line in &self.writer.lines[keep..] {
+            tail.try_push(line.try_clone()?)?;
+        }
+
+        Ok(Checkpoint {
+            keep,
+            tail,
+            line: self.writer.lines.len().saturating_sub(1),
+            indent: self.writer.indent,
+            needs_indent: self.writer.needs_indent,
+            queue_position: self.queue_position,
+            overflow: self.overflow,
+        })
+    }
+
+    /// Restore the output to the given checkpoint.
+    pub(super) fn restore(&mut self, checkpoint: Checkpoint) -> alloc::Result<()> {
+        self.writer.lines.truncate(checkpoint.keep);
+        self.writer.lines.try_extend(checkpoint.tail)?;
+        self.writer.indent = checkpoint.indent;
+        self.writer.needs_indent = checkpoint.needs_indent;
+        self.queue_position = checkpoint.queue_position;
+        self.overflow = checkpoint.overflow;
+        Ok(())
+    }
+
+    /// Mark what's being written on a single line as not fitting.
+    pub(super) fn overflow(&mut self) {
+        self.overflow = true;
+    }
+
+    /// Test if everything written since the given checkpoint fits within the
+    /// given width, and no comments or empty lines had to be dropped.
+    pub(super) fn fits(&self, checkpoint: &Checkpoint, max_width: usize) -> bool {
+        !self.overflow
+            && self.writer.lines[checkpoint.line..]
+                .iter()
+                .all(|line| width(line) <= max_width)
+    }
+
+    /// Test if the line currently being written fits within the given width,
+    /// and no comments or empty lines had to be dropped.
+    pub(super) fn line_fits(&self, max_width: usize) -> bool {
+        !self.overflow && self.writer.lines.last().map_or(0, |line| width(line)) <= max_width
+    }
+
+    /// Test if there are any comments or empty lines left to write within the
+    /// given span.
+    pub(super) fn has_queued_spans(&self, span: Span) -> bool {
+        self.queued_spans[self.queue_position..]
+            .iter()
+            .any(|queued| queued.span().start >= span.start && queued.span().start < span.end)
+    }
+
+    /// Test if the first queued span at or after the given position is a
+    /// comment on the same line as the code before it.
+    pub(super) fn has_trailing_comment(&self, at: ByteIndex) -> bool {
+        let queued = self.queued_spans[self.queue_position..]
+            .iter()
+            .find(|queued| queued.span().start >= at);
+
+        matches!(queued, Some(ResolvedSpan::Comment(comment)) if !comment.on_new_line)
+    }
+
     fn extend_previous_line(&mut self, text: &[u8]) -> alloc::Result<()> {
         let Some(idx) = self.writer.lines.len().checked_sub(2) else {
             // TODO: bubble up an internal error?
@@ -194,12 +315,13 @@ impl<'a> SpanInjectionWriter<'a> {
 
         let mut empties = 0;
 
-        while let Some(queued_span) = self.queued_spans.first() {
+        while let Some(queued_span) = self.queued_spans.get(self.queue_position) {
             if queued_span.span().start > until {
                 break;
             }
 
-            let queued_span = self.queued_spans.remove(0);
+            let queued_span = queued_span.clone();
+            self.queue_position += 1;
             self.write_span(queued_span, &mut empties, limit)?;
         }
 
@@ -212,6 +334,11 @@ impl<'a> SpanInjectionWriter<'a> {
         empties: &mut usize,
         limit: usize,
     ) -> Result<(), FormattingError> {
+        if self.flat {
+            self.overflow = true;
+            return Ok(());
+        }
+
         match span {
             ResolvedSpan::Empty(_) => {
                 if *empties < limit {
@@ -269,6 +396,14 @@ impl<'a> SpanInjectionWriter<'a> {
     }
 }
 
+/// The width of a line in characters.
+fn width(line: &[u8]) -> usize {
+    match str::from_utf8(line) {
+        Ok(line) => line.chars().count(),
+        Err(..) => line.len(),
+    }
+}
+
 impl Deref for SpanInjectionWriter<'_> {
     type Target = IndentedWriter;
 
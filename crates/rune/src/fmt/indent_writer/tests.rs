@@ -5,7 +5,7 @@ use super::IndentedWriter;
 
 #[test]
 fn test_roundtrip() -> Result<()> {
-    let mut writer = IndentedWriter::new()?;
+    let mut writer = IndentedWriter::new(4)?;
     writer.try_write_str("hello\nworld\n")?;
     assert_eq!(
         writer.into_inner(),
@@ -16,7 +16,7 @@ fn test_roundtrip() -> Result<()> {
 
 #[test]
 fn test_roundtrip_with_indent() -> Result<()> {
-    let mut writer = IndentedWriter::new()?;
+    let mut writer = IndentedWriter::new(4)?;
     writer.indent();
     writer.try_write_str("hello\nworld\n")?;
     assert_eq!(
@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+/// Options which control how Rune source code is formatted.
+///
+/// Options can be loaded from a [`FORMAT_FILE`][super::FORMAT_FILE] in the
+/// root of a project, or from the `[format]` section of a `Rune.toml`
+/// manifest. Keys use the same names as the fields below, like
+/// `max_width = 80`.
+///
+/// # Examples
+///
+/// ```
+/// use rune::fmt::{self, FormatOptions};
+///
+/// let mut options = FormatOptions::default();
+/// options.indent_size(2);
+///
+/// let formatted = fmt::format_source_with("fn main() { 42 }", &options)?;
+/// assert_eq!(formatted, "fn main() {\n  42\n}\n");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct FormatOptions {
+    /// The maximum width of a line before lists and call chains are broken up
+    /// over multiple lines.
+    pub(crate) max_width: usize,
+    /// The number of spaces used for each level of indentation.
+    pub(crate) indent_size: usize,
+    /// Add a trailing comma after the last element of lists which are broken
+    /// up over multiple lines.
+    pub(crate) trailing_commas: bool,
+    /// Sort groups of consecutive imports and the items in import groups.
+    pub(crate) sort_imports: bool,
+}
+
+impl FormatOptions {
+    /// Set the maximum width of a line.
+    pub fn max_width(&mut self, max_width: usize) {
+        self.max_width = max_width;
+    }
+
+    /// Set the number of spaces used for each level of indentation.
+    pub fn indent_size(&mut self, indent_size: usize) {
+        self.indent_size = indent_size;
+    }
+
+    /// Set if a trailing comma should be added to lists which are broken up
+    /// over multiple lines.
+    pub fn trailing_commas(&mut self, enabled: bool) {
+        self.trailing_commas = enabled;
+    }
+
+    /// Set if imports should be sorted.
+    pub fn sort_imports(&mut self, enabled: bool) {
+        self.sort_imports = enabled;
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            indent_size: 4,
+            trailing_commas: true,
+            sort_imports: false,
+        }
+    }
+}
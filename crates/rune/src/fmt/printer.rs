@@ -4,11 +4,12 @@ use core::mem::take;
 
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
-use crate::ast::{self, Span, Spanned};
+use crate::ast::{self, ByteIndex, Span, Spanned};
 
 use super::error::FormattingError;
 use super::indent_writer::IndentedWriter;
 use super::indent_writer::SpanInjectionWriter;
+use super::options::FormatOptions;
 
 type Result<T> = core::result::Result<T, FormattingError>;
 
@@ -18,15 +19,66 @@ enum ItemKind {
     Group,
 }
 
+/// How a delimited list is laid out when it fits on a single line.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ListStyle {
+    /// Items are written directly inside of the delimiters, like `[1, 2]`.
+    Plain,
+    /// Items are padded with spaces, like `#{ a: 1 }`.
+    Padded,
+    /// Like [`ListStyle::Plain`], but a single item keeps its trailing comma,
+    /// like `(1,)`.
+    Tuple,
+}
+
+/// A link in a chain of method calls, field accesses, try and await
+/// expressions.
+enum Link<'a> {
+    Method(&'a ast::ExprCall, &'a ast::ExprFieldAccess),
+    Call(&'a ast::ExprCall),
+    Field(&'a ast::ExprFieldAccess),
+    Try(&'a ast::ExprTry),
+    Await(&'a ast::ExprAwait),
+}
+
 pub(super) struct Printer<'a> {
     writer: SpanInjectionWriter<'a>,
     source: &'a str,
+    options: &'a FormatOptions,
+    /// If what's currently being tried on a single line contains a block,
+    /// in which case it's not broken up again.
+    committed: bool,
 }
 
 impl<'a> Printer<'a> {
-    pub(super) fn new(source: &'a str) -> Result<Self> {
-        let writer = SpanInjectionWriter::new(IndentedWriter::new()?, source)?;
-        Ok(Self { writer, source })
+    pub(super) fn new(source: &'a str, options: &'a FormatOptions) -> Result<Self> {
+        Self::new_in(source, options, Span::new(0, source.len()), 0)
+    }
+
+    /// Construct a printer which only lays out the given region of the source,
+    /// starting out indented `depth` levels.
+    ///
+    /// Comments and empty lines outside of the region are ignored.
+    pub(super) fn new_in(
+        source: &'a str,
+        options: &'a FormatOptions,
+        region: Span,
+        depth: usize,
+    ) -> Result<Self> {
+        let mut writer = IndentedWriter::new(options.indent_size)?;
+
+        for _ in 0..depth {
+            writer.indent();
+        }
+
+        let writer = SpanInjectionWriter::new(writer, source, region)?;
+
+        Ok(Self {
+            writer,
+            source,
+            options,
+            committed: false,
+        })
     }
 
     pub(super) fn commit(self) -> Result<Vec<u8>> {
@@ -66,6 +118,125 @@ impl<'a> Printer<'a> {
         Ok(s)
     }
 
+    /// Try to write something on a single line, and if it doesn't fit within
+    /// the configured width or has to contain comments, write it broken up
+    /// over multiple lines instead.
+    ///
+    /// The callback is called with `true` if it should write the broken up
+    /// layout.
+    fn fits_or_break<F>(&mut self, start: ByteIndex, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, bool) -> Result<()>,
+    {
+        // Whatever contains us is being tried on a single line already.
+        if self.writer.is_flat() {
+            return f(self, false);
+        }
+
+        self.writer.write_queued_spans(start, usize::MAX)?;
+        let checkpoint = self.writer.checkpoint()?;
+
+        let committed = take(&mut self.committed);
+        self.writer.set_flat(true);
+        let result = f(self, false);
+        self.writer.set_flat(false);
+        let committed = core::mem::replace(&mut self.committed, committed);
+        result?;
+
+        if committed && self.writer.line_fits(self.options.max_width)
+            || self.writer.fits(&checkpoint, self.options.max_width)
+        {
+            return Ok(());
+        }
+
+        self.writer.restore(checkpoint)?;
+        f(self, true)
+    }
+
+    /// Prepare to write something which always spans multiple lines, like a
+    /// block, returning the flat state to restore once done or `None` if it
+    /// should be skipped.
+    fn begin_multiline(&mut self) -> Option<bool> {
+        if self.writer.is_flat() {
+            // Since blocks span multiple lines, whatever is being tried on a
+            // single line is committed to as long as the line up until the
+            // block fits. This ensures that every block is only written once.
+            if !self.writer.line_fits(self.options.max_width) {
+                self.writer.overflow();
+                return None;
+            }
+
+            self.committed = true;
+        }
+
+        Some(self.writer.set_flat(false))
+    }
+
+    /// Write a delimited list of items, either on a single line or with one
+    /// item on each line if `broken` is set.
+    fn visit_list<T>(
+        &mut self,
+        open: Span,
+        items: &[(T, Option<ast::Comma>)],
+        close: Span,
+        broken: bool,
+        style: ListStyle,
+        mut visit: impl FnMut(&mut Self, &T) -> Result<()>,
+    ) -> Result<()> {
+        self.writer.write_spanned_raw(open, false, false)?;
+
+        let Some(last) = items.len().checked_sub(1) else {
+            self.writer.write_spanned_raw(close, false, false)?;
+            return Ok(());
+        };
+
+        if broken {
+            self.writer.indent();
+            self.writer.newline()?;
+
+            for (index, (item, comma)) in items.iter().enumerate() {
+                visit(self, item)?;
+
+                if index < last || self.options.trailing_commas {
+                    match comma {
+                        Some(comma) => self.writer.write_spanned_raw(comma.span, false, false)?,
+                        None => self.writer.write_unspanned(",")?,
+                    }
+                }
+
+                self.writer.newline()?;
+            }
+
+            // Comments before the closing delimiter belong to the list.
+            self.writer.write_queued_spans(close.start, 0)?;
+            self.writer.dedent();
+        } else {
+            if style == ListStyle::Padded {
+                self.writer.write_unspanned(" ")?;
+            }
+
+            for (index, (item, comma)) in items.iter().enumerate() {
+                visit(self, item)?;
+
+                if index < last {
+                    match comma {
+                        Some(comma) => self.writer.write_spanned_raw(comma.span, false, true)?,
+                        None => self.writer.write_unspanned(", ")?,
+                    }
+                } else if style == ListStyle::Tuple && last == 0 {
+                    self.writer.write_unspanned(",")?;
+                }
+            }
+
+            if style == ListStyle::Padded {
+                self.writer.write_unspanned(" ")?;
+            }
+        }
+
+        self.writer.write_spanned_raw(close, false, false)?;
+        Ok(())
+    }
+
     pub(super) fn visit_file(&mut self, file: &ast::File) -> Result<()> {
         if let Some(shebang) = &file.shebang {
             self.writer.write_spanned_raw(shebang.span, true, false)?;
@@ -76,13 +247,102 @@ impl<'a> Printer<'a> {
             self.writer.newline()?;
         }
 
-        for item in &file.items {
-            self.visit_item(&item.0, item.1)?;
+        self.visit_items(&file.items)
+    }
+
+    /// Write a sequence of items, like the ones in a file or a module.
+    pub(super) fn visit_items(
+        &mut self,
+        items: &[(ast::Item, Option<ast::SemiColon>)],
+    ) -> Result<()> {
+        let mut index = 0;
+
+        while index < items.len() {
+            let end = self.import_group(items, index);
+
+            if end - index > 1 {
+                self.visit_sorted_imports(&items[index..end])?;
+            } else {
+                let (item, semi) = &items[index];
+                self.visit_item(item, *semi)?;
+            }
+
+            index = end;
         }
 
         Ok(())
     }
 
+    /// Find the end of a group of imports starting at `start` which can be
+    /// sorted, which are consecutive imports without any comments or empty
+    /// lines in between them.
+    fn import_group(&self, items: &[(ast::Item, Option<ast::SemiColon>)], start: usize) -> usize {
+        if !self.options.sort_imports || !matches!(items[start].0, ast::Item::Use(..)) {
+            return start + 1;
+        }
+
+        let group_start = item_span(&items[start]).start;
+        let mut end = start + 1;
+
+        while let Some(item) = items.get(end) {
+            if !matches!(item.0, ast::Item::Use(..))
+                || self
+                    .writer
+                    .has_queued_spans(Span::new(group_start, item_span(item).end))
+            {
+                break;
+            }
+
+            end += 1;
+        }
+
+        // A trailing comment on the last import has to stay where it is.
+        if end - start > 1
+            && self
+                .writer
+                .has_trailing_comment(item_span(&items[end - 1]).end)
+        {
+            end -= 1;
+        }
+
+        end
+    }
+
+    /// Write a group of imports sorted by their path.
+    fn visit_sorted_imports(
+        &mut self,
+        items: &[(ast::Item, Option<ast::SemiColon>)],
+    ) -> Result<()> {
+        let mut sorted = Vec::new();
+
+        for item in items {
+            let ast::Item::Use(usage) = &item.0 else {
+                continue;
+            };
+
+            sorted.try_push((self.sort_key(usage.path.span())?, item))?;
+        }
+
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (_, (item, semi)) in sorted {
+            self.visit_item(item, *semi)?;
+        }
+
+        Ok(())
+    }
+
+    /// The key used when sorting an import, which is the path with all
+    /// whitespace removed. Imports of `self` are sorted first.
+    fn sort_key(&self, span: Span) -> Result<(bool, ::rust_alloc::string::String)> {
+        let path = self.resolve(span)?;
+        let path = path
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<::rust_alloc::string::String>();
+        Ok((path != "self", path))
+    }
+
     pub(super) fn visit_attribute(&mut self, attribute: &ast::Attribute) -> Result<bool> {
         let ast::Attribute {
             hash,
@@ -131,6 +391,7 @@ impl<'a> Printer<'a> {
             ast::Item::Enum(item) => self.visit_enum(item, semi)?,
             ast::Item::Struct(item) => self.visit_struct(item, semi)?,
             ast::Item::Impl(item) => self.visit_impl(item, semi)?,
+            ast::Item::Trait(item) => self.visit_trait(item, semi)?,
             ast::Item::Mod(item) => self.visit_mod(item, semi)?,
             ast::Item::Const(item) => self.visit_const(item, semi)?,
             ast::Item::MacroCall(item) => self.visit_macro_call(item, semi)?,
@@ -221,6 +482,7 @@ impl<'a> Printer<'a> {
         let ast::ItemImpl {
             attributes,
             impl_,
+            trait_,
             path,
             open,
             functions,
@@ -233,18 +495,71 @@ impl<'a> Printer<'a> {
         }
 
         self.writer.write_spanned_raw(impl_.span, false, true)?;
+
+        if let Some((trait_, for_)) = trait_ {
+            self.visit_path(trait_)?;
+            self.writer.write_unspanned(" ")?;
+            self.writer.write_spanned_raw(for_.span, false, true)?;
+        }
+
         self.visit_path(path)?;
 
         self.writer.write_unspanned(" ")?;
         self.writer.write_spanned_raw(open.span, true, false)?;
 
         self.writer.indent();
+        self.visit_impl_fns(functions)?;
+        self.writer.dedent();
+        self.writer.write_spanned_raw(close.span, false, false)?;
+
+        if let Some(semi) = semi {
+            self.writer.write_spanned_raw(semi.span, false, false)?;
+        }
+
+        Ok(())
+    }
 
+    /// Write the functions in an impl block.
+    pub(super) fn visit_impl_fns(&mut self, functions: &[ast::ItemFn]) -> Result<()> {
         for function in functions {
             self.visit_fn(function, None)?;
             self.writer.newline()?;
         }
 
+        Ok(())
+    }
+
+    fn visit_trait(&mut self, item: &ast::ItemTrait, semi: Option<ast::SemiColon>) -> Result<()> {
+        let ast::ItemTrait {
+            attributes,
+            visibility,
+            trait_token,
+            name,
+            open,
+            functions,
+            close,
+        } = item;
+
+        for attribute in attributes {
+            self.visit_attribute(attribute)?;
+            self.writer.newline()?;
+        }
+
+        self.emit_visibility(visibility)?;
+        self.writer
+            .write_spanned_raw(trait_token.span, false, true)?;
+        self.writer.write_spanned_raw(name.span, false, false)?;
+
+        self.writer.write_unspanned(" ")?;
+        self.writer.write_spanned_raw(open.span, true, false)?;
+
+        self.writer.indent();
+
+        for function in functions {
+            self.visit_trait_fn(function)?;
+            self.writer.newline()?;
+        }
+
         self.writer.dedent();
         self.writer.write_spanned_raw(close.span, false, false)?;
 
@@ -255,6 +570,57 @@ impl<'a> Printer<'a> {
         Ok(())
     }
 
+    fn visit_trait_fn(&mut self, item: &ast::TraitFn) -> Result<()> {
+        let ast::TraitFn {
+            attributes,
+            async_token,
+            fn_token,
+            name,
+            args,
+            output,
+            body,
+            semi,
+        } = item;
+
+        for attribute in attributes {
+            self.visit_attribute(attribute)?;
+            self.writer.newline()?;
+        }
+
+        if let Some(async_token) = async_token {
+            self.writer
+                .write_spanned_raw(async_token.span, false, true)?;
+        }
+
+        self.writer.write_spanned_raw(fn_token.span, false, true)?;
+        self.writer.write_spanned_raw(name.span, false, false)?;
+
+        self.fits_or_break(args.open.span.start, |this, broken| {
+            this.visit_fn_args(args, body.is_some() || output.is_some(), broken)?;
+
+            if let Some((arrow, ty)) = output {
+                this.writer.write_spanned_raw(arrow.span, false, true)?;
+                this.visit_type(ty)?;
+
+                if body.is_some() {
+                    this.writer.write_unspanned(" ")?;
+                }
+            }
+
+            Ok(())
+        })?;
+
+        if let Some(body) = body {
+            self.visit_block(body)?;
+        }
+
+        if let Some(semi) = semi {
+            self.writer.write_spanned_raw(semi.span, false, false)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_struct(&mut self, item: &ast::ItemStruct, semi: Option<ast::SemiColon>) -> Result<()> {
         let ast::ItemStruct {
             id: _,
@@ -305,13 +671,9 @@ impl<'a> Printer<'a> {
                 self.writer.write_spanned_raw(body.open.span, true, false)?;
 
                 self.writer.indent();
-                for (field, comma) in body {
+                for (index, (field, comma)) in body.iter().enumerate() {
                     self.visit_field(field)?;
-                    if let Some(comma) = comma {
-                        self.writer.write_spanned_raw(comma.span, true, false)?;
-                    } else {
-                        self.writer.write_unspanned(",\n")?;
-                    }
+                    self.write_item_comma(*comma, index + 1 == body.len())?;
                 }
                 self.writer.dedent();
                 self.writer
@@ -344,14 +706,9 @@ impl<'a> Printer<'a> {
             .write_spanned_raw(variants.open.span, true, false)?;
 
         self.writer.indent();
-        for (variant, _comma) in variants {
+        for (index, (variant, comma)) in variants.iter().enumerate() {
             self.visit_variant(variant)?;
-            let span = if let Some(comma) = _comma {
-                comma.span
-            } else {
-                Span::new(0, 0)
-            };
-            self.writer.write_spanned_raw(span, true, false)?;
+            self.write_item_comma(*comma, index + 1 == variants.len())?;
         }
         self.writer.dedent();
         self.writer
@@ -412,13 +769,9 @@ impl<'a> Printer<'a> {
                     .write_spanned_raw(sbody.open.span, true, false)?;
 
                 self.writer.indent();
-                for (field, comma) in &sbody.braced {
+                for (index, (field, comma)) in sbody.iter().enumerate() {
                     self.visit_field(field)?;
-                    if let Some(comma) = comma {
-                        self.writer.write_spanned_raw(comma.span, true, false)?;
-                    } else {
-                        self.writer.write_unspanned(",\n")?;
-                    }
+                    self.write_item_comma(*comma, index + 1 == sbody.len())?;
                 }
                 self.writer.dedent();
                 self.writer
@@ -429,6 +782,23 @@ impl<'a> Printer<'a> {
         Ok(())
     }
 
+    /// Write the comma and newline following an item which is on its own
+    /// line, where the comma after the last item depends on the configured
+    /// use of trailing commas.
+    fn write_item_comma(&mut self, comma: Option<ast::Comma>, last: bool) -> Result<()> {
+        if last && !self.options.trailing_commas {
+            self.writer.newline()?;
+            return Ok(());
+        }
+
+        match comma {
+            Some(comma) => self.writer.write_spanned_raw(comma.span, true, false)?,
+            None => self.writer.write_unspanned(",\n")?,
+        }
+
+        Ok(())
+    }
+
     fn visit_field(&mut self, ast: &ast::Field) -> Result<()> {
         let ast::Field {
             attributes,
@@ -457,6 +827,7 @@ impl<'a> Printer<'a> {
             fn_token,
             name,
             args,
+            output,
             body,
         } = item;
 
@@ -479,40 +850,82 @@ impl<'a> Printer<'a> {
         self.writer.write_spanned_raw(fn_token.span, false, true)?;
         self.writer.write_spanned_raw(name.span, false, false)?;
 
-        self.writer
-            .write_spanned_raw(args.open.span, false, false)?;
-
-        let multiline = if args.len() > 5 {
-            self.writer.indent();
-            self.writer.newline()?;
-            true
-        } else {
-            false
-        };
+        self.fits_or_break(args.open.span.start, |this, broken| {
+            this.visit_fn_args(args, true, broken)?;
 
-        for (arg, comma) in args {
-            match arg {
-                ast::FnArg::SelfValue(selfvalue) => self.visit_self_value(selfvalue)?,
-                ast::FnArg::Pat(pattern) => self.visit_pattern(pattern)?,
+            if let Some((arrow, ty)) = output {
+                this.writer.write_spanned_raw(arrow.span, false, true)?;
+                this.visit_type(ty)?;
+                this.writer.write_unspanned(" ")?;
             }
 
-            if let Some(comma) = comma {
+            Ok(())
+        })?;
+
+        self.visit_block(body)?;
+
+        if let Some(semi) = semi {
+            self.writer.write_spanned_raw(semi.span, false, false)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn_args(
+        &mut self,
+        args: &ast::Parenthesized<ast::FnArg, ast::Comma>,
+        space_after: bool,
+        broken: bool,
+    ) -> Result<()> {
+        self.visit_list(
+            args.open.span,
+            args.as_slice(),
+            args.close.span,
+            broken,
+            ListStyle::Plain,
+            Self::visit_fn_arg,
+        )?;
+
+        if space_after {
+            self.writer.write_unspanned(" ")?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn_arg(&mut self, arg: &ast::FnArg) -> Result<()> {
+        match arg {
+            ast::FnArg::SelfValue(self_) => self.visit_self_value(self_)?,
+            ast::FnArg::Pat(pat) => self.visit_pattern(pat)?,
+            ast::FnArg::Typed(typed) => {
+                self.visit_pattern(&typed.pat)?;
                 self.writer
-                    .write_spanned_raw(comma.span, multiline, !multiline)?;
+                    .write_spanned_raw(typed.colon.span, false, true)?;
+                self.visit_type(&typed.ty)?;
             }
         }
 
-        if args.len() > 5 {
-            self.writer.dedent();
-            self.writer.newline()?;
-        }
+        Ok(())
+    }
 
-        self.writer
-            .write_spanned_raw(args.close.span, false, true)?;
-        self.visit_block(body)?;
+    fn visit_type(&mut self, ty: &ast::Type) -> Result<()> {
+        match ty {
+            ast::Type::Path(path) => self.visit_path(path)?,
+            ast::Type::Tuple(tuple) => {
+                self.writer
+                    .write_spanned_raw(tuple.open.span, false, false)?;
 
-        if let Some(semi) = semi {
-            self.writer.write_spanned_raw(semi.span, false, false)?;
+                for (ty, comma) in tuple {
+                    self.visit_type(ty)?;
+
+                    if let Some(comma) = comma {
+                        self.writer.write_spanned_raw(comma.span, false, true)?;
+                    }
+                }
+
+                self.writer
+                    .write_spanned_raw(tuple.close.span, false, false)?;
+            }
         }
 
         Ok(())
@@ -532,7 +945,7 @@ impl<'a> Printer<'a> {
 
         self.emit_visibility(visibility)?;
         self.writer.write_spanned_raw(use_token.span, false, true)?;
-        self.visit_item_use_path(path, None)?;
+        self.visit_item_use_path(path)?;
 
         if let Some(semi) = semi {
             self.writer.write_spanned_raw(semi.span, false, false)?;
@@ -541,11 +954,7 @@ impl<'a> Printer<'a> {
         Ok(())
     }
 
-    fn visit_item_use_path(
-        &mut self,
-        path: &ast::ItemUsePath,
-        comma: Option<ast::Comma>,
-    ) -> Result<()> {
+    fn visit_item_use_path(&mut self, path: &ast::ItemUsePath) -> Result<()> {
         let ast::ItemUsePath {
             global,
             first,
@@ -569,10 +978,6 @@ impl<'a> Printer<'a> {
             self.writer.write_spanned_raw(ident.span, false, false)?;
         }
 
-        if let Some(comma) = comma {
-            self.writer.write_spanned_raw(comma.span, false, true)?;
-        }
-
         Ok(())
     }
 
@@ -623,6 +1028,10 @@ impl<'a> Printer<'a> {
     }
 
     fn visit_expr(&mut self, expr: &ast::Expr) -> Result<()> {
+        if let Some((root, links)) = self.method_chain(expr)? {
+            return self.visit_method_chain(root, &links);
+        }
+
         match expr {
             ast::Expr::Path(path) => self.visit_path(path),
             ast::Expr::Lit(lit) => self.visit_lit(lit),
@@ -728,44 +1137,16 @@ impl<'a> Printer<'a> {
             self.visit_attribute(attr)?;
         }
 
-        self.writer
-            .write_spanned_raw(items.open.span, false, false)?;
-
-        let multiline = if items.len() > 10 {
-            self.writer.indent();
-            self.writer.newline()?;
-            true
-        } else {
-            false
-        };
-
-        let count = items.len();
-        for (idx, (item, comma)) in items.iter().enumerate() {
-            self.visit_expr(item)?;
-
-            if multiline {
-                if let Some(comma) = comma {
-                    self.writer.write_spanned_raw(comma.span, true, false)?;
-                }
-            } else {
-                let is_last = count == idx + 1;
-                if !is_last {
-                    if let Some(comma) = comma {
-                        self.writer.write_spanned_raw(comma.span, false, true)?;
-                    } else {
-                        write!(self.writer, ", ")?;
-                    }
-                }
-            }
-        }
-
-        if multiline {
-            self.writer.dedent();
-        }
-
-        self.writer
-            .write_spanned_raw(items.close.span, false, false)?;
-        Ok(())
+        self.fits_or_break(items.open.span.start, |this, broken| {
+            this.visit_list(
+                items.open.span,
+                items.as_slice(),
+                items.close.span,
+                broken,
+                ListStyle::Plain,
+                Self::visit_expr,
+            )
+        })
     }
 
     fn visit_object(&mut self, ast: &ast::ExprObject) -> Result<()> {
@@ -789,54 +1170,16 @@ impl<'a> Printer<'a> {
             }
         }
 
-        self.writer
-            .write_spanned_raw(assignments.open.span, false, false)?;
-
-        let has_items = !assignments.is_empty();
-        let multiline = if assignments.len() > 5 {
-            self.writer.indent();
-            self.writer.newline()?;
-            true
-        } else {
-            if has_items {
-                write!(self.writer, " ")?;
-            }
-            false
-        };
-
-        let count = assignments.len();
-        for (idx, (assignment, comma)) in assignments.iter().enumerate() {
-            self.visit_object_assignment(assignment)?;
-
-            if multiline {
-                if let Some(comma) = comma {
-                    self.writer.write_spanned_raw(comma.span, true, false)?;
-                } else {
-                    self.writer.write_unspanned(",\n")?;
-                }
-            } else {
-                let is_last = count == idx + 1;
-                if !is_last {
-                    if let Some(comma) = comma {
-                        self.writer.write_spanned_raw(comma.span, false, true)?;
-                    } else {
-                        write!(self.writer, ", ")?;
-                    }
-                }
-            }
-        }
-
-        if multiline {
-            self.writer.dedent();
-            self.writer.newline()?;
-        } else if has_items {
-            self.writer.write_unspanned(" ")?;
-        }
-
-        self.writer
-            .write_spanned_raw(assignments.close.span, false, false)?;
-
-        Ok(())
+        self.fits_or_break(assignments.open.span.start, |this, broken| {
+            this.visit_list(
+                assignments.open.span,
+                assignments.as_slice(),
+                assignments.close.span,
+                broken,
+                ListStyle::Padded,
+                Self::visit_object_assignment,
+            )
+        })
     }
 
     fn visit_object_assignment(&mut self, ast: &ast::FieldAssign) -> Result<()> {
@@ -870,6 +1213,10 @@ impl<'a> Printer<'a> {
             self.visit_attribute(attr)?;
         }
 
+        let Some(flat) = self.begin_multiline() else {
+            return Ok(());
+        };
+
         self.writer.write_spanned_raw(select.span, false, true)?;
         self.writer.write_spanned_raw(open.span, true, false)?;
         self.writer.indent();
@@ -888,6 +1235,7 @@ impl<'a> Printer<'a> {
 
         self.writer.write_spanned_raw(close.span, false, false)?;
 
+        self.writer.set_flat(flat);
         Ok(())
     }
 
@@ -910,6 +1258,7 @@ impl<'a> Printer<'a> {
         } = ast;
 
         self.visit_pattern(pat)?;
+
         self.writer.write_unspanned(" ")?;
         self.writer.write_spanned_raw(eq.span, false, true)?;
         self.visit_expr(expr)?;
@@ -1049,46 +1398,16 @@ impl<'a> Printer<'a> {
             self.writer.newline()?;
         }
 
-        self.writer
-            .write_spanned_raw(items.open.span, false, false)?;
-
-        let multiline = if items.len() >= 5 {
-            self.writer.indent();
-            self.writer.newline()?;
-            true
-        } else {
-            false
-        };
-
-        let count = items.len();
-        for (idx, (item, comma)) in items.iter().enumerate() {
-            self.visit_expr(item)?;
-            if multiline {
-                if let Some(comma) = comma {
-                    self.writer.write_spanned_raw(comma.span, true, false)?;
-                } else {
-                    self.writer.write_unspanned(",\n")?;
-                }
-            } else {
-                let is_last = idx == count - 1;
-                if !is_last {
-                    if let Some(comma) = comma {
-                        self.writer.write_spanned_raw(comma.span, false, true)?;
-                    } else {
-                        write!(self.writer, ", ")?;
-                    }
-                }
-            }
-        }
-
-        if multiline {
-            self.writer.dedent();
-        }
-
-        self.writer
-            .write_spanned_raw(items.close.span, false, false)?;
-
-        Ok(())
+        self.fits_or_break(items.open.span.start, |this, broken| {
+            this.visit_list(
+                items.open.span,
+                items.as_slice(),
+                items.close.span,
+                broken,
+                ListStyle::Tuple,
+                Self::visit_expr,
+            )
+        })
     }
 
     fn visit_field_access(&mut self, ast: &ast::ExprFieldAccess) -> Result<()> {
@@ -1134,25 +1453,145 @@ impl<'a> Printer<'a> {
         }
 
         self.visit_expr(expr)?;
-        self.writer
-            .write_spanned_raw(args.open.span, false, false)?;
+        self.visit_call_args(args)
+    }
+
+    fn visit_call_args(&mut self, args: &ast::Parenthesized<ast::Expr, ast::Comma>) -> Result<()> {
+        self.fits_or_break(args.open.span.start, |this, broken| {
+            this.visit_list(
+                args.open.span,
+                args.as_slice(),
+                args.close.span,
+                broken,
+                ListStyle::Plain,
+                Self::visit_expr,
+            )
+        })
+    }
+
+    /// Collect the links of a chain of method calls, returning the expression
+    /// at the root of the chain and the links from the outermost to the
+    /// innermost.
+    ///
+    /// Returns `None` if the expression isn't a chain of at least two method
+    /// calls, in which case it's written as usual.
+    fn method_chain<'e>(
+        &self,
+        mut expr: &'e ast::Expr,
+    ) -> Result<Option<(&'e ast::Expr, Vec<Link<'e>>)>> {
+        let mut links = Vec::new();
+        let mut methods = 0;
+
+        loop {
+            let link = match expr {
+                ast::Expr::Call(call) => match &*call.expr {
+                    ast::Expr::FieldAccess(field) => {
+                        methods += 1;
+                        expr = &field.expr;
+                        Link::Method(call, field)
+                    }
+                    _ => {
+                        expr = &call.expr;
+                        Link::Call(call)
+                    }
+                },
+                ast::Expr::FieldAccess(field) => {
+                    expr = &field.expr;
+                    Link::Field(field)
+                }
+                ast::Expr::Try(try_) => {
+                    expr = &try_.expr;
+                    Link::Try(try_)
+                }
+                ast::Expr::Await(await_) => {
+                    expr = &await_.expr;
+                    Link::Await(await_)
+                }
+                _ => break,
+            };
 
-        let count = args.parenthesized.len();
-        for (idx, (arg, comma)) in args.parenthesized.iter().enumerate() {
-            self.visit_expr(arg)?;
-            if idx != count - 1 {
-                if let Some(comma) = comma {
-                    self.writer.write_spanned_raw(comma.span, false, true)?;
-                } else {
-                    write!(self.writer, ", ")?;
+            links.try_push(link)?;
+        }
+
+        if methods < 2 {
+            return Ok(None);
+        }
+
+        Ok(Some((expr, links)))
+    }
+
+    /// Write a chain of method calls, which if it doesn't fit on a single line
+    /// is broken up with each method call on its own line.
+    fn visit_method_chain(&mut self, root: &ast::Expr, links: &[Link<'_>]) -> Result<()> {
+        for link in links {
+            let attributes = match link {
+                Link::Method(call, field) => {
+                    for attr in &call.attributes {
+                        self.visit_attribute(attr)?;
+                    }
+
+                    &field.attributes
                 }
+                Link::Call(call) => &call.attributes,
+                Link::Field(field) => &field.attributes,
+                Link::Try(try_) => &try_.attributes,
+                Link::Await(await_) => &await_.attributes,
+            };
+
+            for attr in attributes {
+                self.visit_attribute(attr)?;
             }
         }
 
-        self.writer
-            .write_spanned_raw(args.close.span, false, false)?;
+        self.visit_expr(root)?;
 
-        Ok(())
+        self.fits_or_break(root.span().end, |this, broken| {
+            let mut indented = false;
+
+            for link in links.iter().rev() {
+                match link {
+                    Link::Method(call, field) => {
+                        if broken {
+                            if !indented {
+                                this.writer.indent();
+                                indented = true;
+                            }
+
+                            this.writer.newline()?;
+                        }
+
+                        this.writer
+                            .write_spanned_raw(field.dot.span, false, false)?;
+                        this.visit_expr_field(&field.expr_field)?;
+                        this.visit_call_args(&call.args)?;
+                    }
+                    Link::Call(call) => {
+                        this.visit_call_args(&call.args)?;
+                    }
+                    Link::Field(field) => {
+                        this.writer
+                            .write_spanned_raw(field.dot.span, false, false)?;
+                        this.visit_expr_field(&field.expr_field)?;
+                    }
+                    Link::Try(try_) => {
+                        this.writer
+                            .write_spanned_raw(try_.try_token.span, false, false)?;
+                    }
+                    Link::Await(await_) => {
+                        this.writer
+                            .write_spanned_raw(await_.dot.span, false, false)?;
+                        this.writer
+                            .write_spanned_raw(await_.await_token.span, false, false)?;
+                    }
+                }
+            }
+
+            if indented {
+                this.writer.dedent();
+            }
+
+            Ok(())
+        })
     }
 
     fn visit_index(&mut self, ast: &ast::ExprIndex) -> Result<()> {
@@ -1279,10 +1718,8 @@ impl<'a> Printer<'a> {
             ast::ExprClosureArgs::List { args, open, close } => {
                 self.writer.write_spanned_raw(open.span, false, false)?;
                 for (arg, comma) in args {
-                    match arg {
-                        ast::FnArg::SelfValue(self_) => self.visit_self_value(self_)?,
-                        ast::FnArg::Pat(pat) => self.visit_pattern(pat)?,
-                    }
+                    self.visit_fn_arg(arg)?;
+
                     if let Some(comma) = comma {
                         self.writer.write_spanned_raw(comma.span, false, true)?;
                     }
@@ -1314,6 +1751,10 @@ impl<'a> Printer<'a> {
         self.writer.write_spanned_raw(match_.span, false, true)?;
         self.visit_expr(expr)?;
 
+        let Some(flat) = self.begin_multiline() else {
+            return Ok(());
+        };
+
         self.writer.write_unspanned(" ")?;
         self.writer.write_spanned_raw(open.span, true, false)?;
 
@@ -1335,6 +1776,7 @@ impl<'a> Printer<'a> {
 
         self.writer.write_spanned_raw(close.span, false, false)?;
 
+        self.writer.set_flat(flat);
         Ok(())
     }
 
@@ -1697,6 +2139,7 @@ impl<'a> Printer<'a> {
         }
 
         self.visit_pattern(pat)?;
+
         self.writer.write_unspanned(" ")?;
         self.writer.write_spanned_raw(eq.span, false, true)?;
         self.visit_expr(expr)?;
@@ -1807,9 +2250,25 @@ impl<'a> Printer<'a> {
             close,
         } = ast;
 
+        let Some(flat) = self.begin_multiline() else {
+            return Ok(());
+        };
+
         self.writer.write_spanned_raw(open.span, true, false)?;
         self.writer.indent();
+        self.visit_statements(statements)?;
+
+        self.writer
+            .write_queued_spans(close.span.start, usize::MAX)?;
+        self.writer.dedent();
+        self.writer.write_spanned_raw(close.span, false, false)?;
 
+        self.writer.set_flat(flat);
+        Ok(())
+    }
+
+    /// Write a sequence of statements, like the ones in a block.
+    pub(super) fn visit_statements(&mut self, statements: &[ast::Stmt]) -> Result<()> {
         let mut last_item = ItemKind::None;
         let mut first = true;
 
@@ -1817,11 +2276,6 @@ impl<'a> Printer<'a> {
             self.visit_statement(statement, &mut last_item, take(&mut first))?;
         }
 
-        self.writer
-            .write_queued_spans(close.span.start, usize::MAX)?;
-        self.writer.dedent();
-        self.writer.write_spanned_raw(close.span, false, false)?;
-
         Ok(())
     }
 
@@ -1885,6 +2339,7 @@ impl<'a> Printer<'a> {
             let_token,
             mut_token,
             pat,
+            ty,
             eq,
             expr,
             semi,
@@ -1901,6 +2356,12 @@ impl<'a> Printer<'a> {
         }
 
         self.visit_pattern(pat)?;
+
+        if let Some((colon, ty)) = ty {
+            self.writer.write_spanned_raw(colon.span, false, true)?;
+            self.visit_type(ty)?;
+        }
+
         self.writer.write_unspanned(" ")?;
         self.writer.write_spanned_raw(eq.span, false, true)?;
         self.visit_expr(expr)?;
@@ -2039,16 +2500,26 @@ impl<'a> Printer<'a> {
                     close,
                 } = braced_group;
 
-                self.writer.write_spanned_raw(open.span, false, false)?;
+                let mut items = Vec::new();
 
-                for (item, comma) in braced {
-                    self.visit_item_use_path(item, *comma)?;
+                for item in braced {
+                    items.try_push((self.sort_key(item.0.span())?, item))?;
+                }
 
-                    if let Some(comma) = comma {
-                        self.writer.write_spanned_raw(comma.span, false, true)?;
-                    } else {
+                // Items can only be moved around if there are no comments
+                // which would be moved along with them.
+                if self.options.sort_imports && !self.writer.has_queued_spans(braced_group.span()) {
+                    items.sort_by(|a, b| a.0.cmp(&b.0));
+                }
+
+                self.writer.write_spanned_raw(open.span, false, false)?;
+
+                for (index, (_, (item, _))) in items.into_iter().enumerate() {
+                    if index > 0 {
                         self.writer.write_unspanned(", ")?;
                     }
+
+                    self.visit_item_use_path(item)?;
                 }
 
                 self.writer.write_spanned_raw(close.span, false, false)?;
@@ -2102,3 +2573,11 @@ impl<'a> Printer<'a> {
         Ok(())
     }
 }
+
+/// The span of an item including its trailing semicolon.
+pub(super) fn item_span((item, semi): &(ast::Item, Option<ast::SemiColon>)) -> Span {
+    match semi {
+        Some(semi) => item.span().join(semi.span),
+        None => item.span(),
+    }
+}
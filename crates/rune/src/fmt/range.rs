@@ -0,0 +1,240 @@
+//! Finding the part of a file which should be formatted when only a range of
+//! it is requested.
+
+use crate::ast::{self, Span, Spanned};
+
+use super::printer::item_span;
+
+/// A sequence of nodes which can be laid out independently of what surrounds
+/// them.
+pub(super) enum Target<'a> {
+    /// Items in a file or a module.
+    Items(&'a [(ast::Item, Option<ast::SemiColon>)]),
+    /// Functions in an impl block.
+    Fns(&'a [ast::ItemFn]),
+    /// Statements in a block.
+    Stmts(&'a [ast::Stmt]),
+}
+
+/// The nodes found for a range.
+pub(super) struct Found<'a> {
+    pub(super) target: Target<'a>,
+    /// The number of levels the nodes are indented.
+    pub(super) depth: usize,
+    /// The region of the source covered by the nodes, from the start of the
+    /// line of the first node to the end of the line of the last one,
+    /// including its newline.
+    pub(super) region: Span,
+}
+
+/// Find the innermost sequence of nodes in the file which covers `span`, and
+/// which starts and ends on lines of its own.
+pub(super) fn find<'a>(source: &str, file: &'a ast::File, span: Span) -> Option<Found<'a>> {
+    find_in_items(source, &file.items, span, 0)
+}
+
+fn find_in_items<'a>(
+    source: &str,
+    items: &'a [(ast::Item, Option<ast::SemiColon>)],
+    span: Span,
+    depth: usize,
+) -> Option<Found<'a>> {
+    let (items, region) = select(source, items, span, item_span)?;
+
+    if let [(item, _)] = items {
+        if let Some(found) = find_in_item(source, item, span, depth) {
+            return Some(found);
+        }
+    }
+
+    Some(Found {
+        target: Target::Items(items),
+        depth,
+        region,
+    })
+}
+
+fn find_in_item<'a>(
+    source: &str,
+    item: &'a ast::Item,
+    span: Span,
+    depth: usize,
+) -> Option<Found<'a>> {
+    if !contains(item.span(), span) {
+        return None;
+    }
+
+    match item {
+        ast::Item::Fn(item) => find_in_block(source, &item.body, span, depth + 1),
+        ast::Item::Impl(item) => find_in_fns(source, &item.functions, span, depth + 1),
+        ast::Item::Mod(item) => match &item.body {
+            ast::ItemModBody::InlineBody(body) => {
+                find_in_items(source, &body.file.items, span, depth + 1)
+            }
+            ast::ItemModBody::EmptyBody(..) => None,
+        },
+        _ => None,
+    }
+}
+
+fn find_in_fns<'a>(
+    source: &str,
+    fns: &'a [ast::ItemFn],
+    span: Span,
+    depth: usize,
+) -> Option<Found<'a>> {
+    let (fns, region) = select(source, fns, span, Spanned::span)?;
+
+    if let [item] = fns {
+        if contains(item.span(), span) {
+            if let Some(found) = find_in_block(source, &item.body, span, depth + 1) {
+                return Some(found);
+            }
+        }
+    }
+
+    Some(Found {
+        target: Target::Fns(fns),
+        depth,
+        region,
+    })
+}
+
+fn find_in_block<'a>(
+    source: &str,
+    block: &'a ast::Block,
+    span: Span,
+    depth: usize,
+) -> Option<Found<'a>> {
+    if !contains(block.span(), span) {
+        return None;
+    }
+
+    let (stmts, region) = select(source, &block.statements, span, Spanned::span)?;
+
+    if let [stmt] = stmts {
+        if contains(stmt.span(), span) {
+            let found = match stmt {
+                ast::Stmt::Local(local) => find_in_expr(source, &local.expr, span, depth),
+                ast::Stmt::Item(item, _) => find_in_item(source, item, span, depth),
+                ast::Stmt::Expr(expr) => find_in_expr(source, expr, span, depth),
+                ast::Stmt::Semi(semi) => find_in_expr(source, &semi.expr, span, depth),
+            };
+
+            if found.is_some() {
+                return found;
+            }
+        }
+    }
+
+    Some(Found {
+        target: Target::Stmts(stmts),
+        depth,
+        region,
+    })
+}
+
+/// Look for blocks in an expression which are always laid out one level
+/// deeper than the statement containing them.
+fn find_in_expr<'a>(
+    source: &str,
+    expr: &'a ast::Expr,
+    span: Span,
+    depth: usize,
+) -> Option<Found<'a>> {
+    let depth = depth + 1;
+
+    match expr {
+        ast::Expr::Block(expr) => find_in_block(source, &expr.block, span, depth),
+        ast::Expr::If(expr) => {
+            let blocks = core::iter::once(&*expr.block)
+                .chain(expr.expr_else_ifs.iter().map(|e| &*e.block))
+                .chain(expr.expr_else.iter().map(|e| &*e.block));
+
+            for block in blocks {
+                if let Some(found) = find_in_block(source, block, span, depth) {
+                    return Some(found);
+                }
+            }
+
+            None
+        }
+        ast::Expr::While(expr) => find_in_block(source, &expr.body, span, depth),
+        ast::Expr::For(expr) => find_in_block(source, &expr.body, span, depth),
+        ast::Expr::Loop(expr) => find_in_block(source, &expr.body, span, depth),
+        _ => None,
+    }
+}
+
+/// Select the nodes which overlap with `span`, extended so that the first
+/// and the last node don't share their lines with any other nodes.
+///
+/// Returns `None` if no nodes overlap, or if the selected nodes share their
+/// lines with something else, like the braces of a block.
+fn select<'a, T>(
+    source: &str,
+    nodes: &'a [T],
+    span: Span,
+    span_of: impl Fn(&T) -> Span,
+) -> Option<(&'a [T], Span)> {
+    let overlaps = |node: &T| {
+        let s = span_of(node);
+
+        if span.start == span.end {
+            s.start <= span.start && span.start <= s.end
+        } else {
+            s.start < span.end && span.start < s.end
+        }
+    };
+
+    let mut start = nodes.iter().position(overlaps)?;
+    let mut end = nodes.iter().rposition(overlaps)? + 1;
+
+    while start > 0
+        && same_line(
+            source,
+            span_of(&nodes[start - 1]).end,
+            span_of(&nodes[start]).start,
+        )
+    {
+        start -= 1;
+    }
+
+    while end < nodes.len()
+        && same_line(
+            source,
+            span_of(&nodes[end - 1]).end,
+            span_of(&nodes[end]).start,
+        )
+    {
+        end += 1;
+    }
+
+    let first = span_of(&nodes[start]).start.into_usize();
+    let last = span_of(&nodes[end - 1]).end.into_usize();
+
+    let line_start = source[..first].rfind('\n').map_or(0, |n| n + 1);
+
+    if !source[line_start..first].trim().is_empty() {
+        return None;
+    }
+
+    let line_end = source[last..]
+        .find('\n')
+        .map_or(source.len(), |n| last + n + 1);
+    let rest = source[last..line_end].trim();
+
+    if !rest.is_empty() && !rest.starts_with("//") {
+        return None;
+    }
+
+    Some((&nodes[start..end], Span::new(line_start, line_end)))
+}
+
+fn same_line(source: &str, from: ast::ByteIndex, to: ast::ByteIndex) -> bool {
+    !source[from.into_usize()..to.into_usize()].contains('\n')
+}
+
+fn contains(outer: Span, inner: Span) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
@@ -4,7 +4,7 @@ use crate::fmt::FormattingError;
 use crate::support::Result;
 
 pub(crate) fn layout_string(contents: String) -> Result<Vec<u8>, FormattingError> {
-    super::layout_source(&contents)
+    super::layout_source(&contents, &super::FormatOptions::default())
 }
 
 #[test]
@@ -7,6 +7,8 @@ pub(crate) use self::arena::Arena;
 mod hir;
 pub(crate) use self::hir::*;
 
+pub(crate) mod check;
+
 pub(crate) mod lowering;
 
 pub(crate) mod scopes;
@@ -0,0 +1,659 @@
+//! A best-effort static checker which operates over lowered functions.
+//!
+//! Types are only tracked where they can be trivially determined, such as for
+//! literals, annotated bindings and calls to functions with known return
+//! types. Anything which can't be determined is treated as unknown and never
+//! reported, so the checker only reports obvious mistakes.
+//!
+//! The checker always runs to report lints which depend on the same
+//! information, like shadowed bindings and unused results. Type errors are
+//! only reported if type checking is enabled.
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, Vec};
+use crate::ast::{self, Span, Spanned};
+use crate::compile::{self, meta, ErrorKind};
+use crate::hir;
+use crate::query::Query;
+use crate::runtime::static_type;
+use crate::{Hash, SourceId};
+
+/// Type checking context.
+pub(crate) struct Ctxt<'a, 'hir, 'arena> {
+    /// Query system used to look up signatures.
+    q: Query<'a, 'arena>,
+    /// The source being checked.
+    source_id: SourceId,
+    /// Variables which are currently in scope along with their known type
+    /// and the span of their binding.
+    scopes: Vec<(hir::Name<'hir>, Option<Hash>, Span)>,
+    /// The annotated return type of the function being checked.
+    return_type: Option<hir::TypeHint>,
+    /// If type errors should be reported.
+    type_check: bool,
+}
+
+/// The parts of a signature used by the checker.
+struct Signature {
+    native: bool,
+    args: Option<usize>,
+    return_type: Option<Hash>,
+    argument_types: Vec<Option<Hash>>,
+}
+
+/// Check a lowered function, reporting any errors and lints as diagnostics.
+pub(crate) fn item_fn<'hir>(
+    q: Query<'_, '_>,
+    source_id: SourceId,
+    hir: &hir::ItemFn<'hir>,
+    type_check: bool,
+) -> alloc::Result<()> {
+    let mut cx = Ctxt {
+        q,
+        source_id,
+        scopes: Vec::new(),
+        return_type: hir.output,
+        type_check,
+    };
+
+    for arg in hir.args {
+        match *arg {
+            hir::FnArg::SelfValue(span) => {
+                cx.scopes.try_push((hir::Name::SelfValue, None, span))?;
+            }
+            hir::FnArg::Pat(pat) => {
+                cx.bind(pat, None)?;
+            }
+            hir::FnArg::Typed(pat, ty) => {
+                cx.bind(pat, Some(ty.hash))?;
+            }
+        }
+    }
+
+    let actual = cx.block(&hir.body)?;
+
+    if let (Some(expected), Some(value)) = (hir.output, hir.body.value) {
+        cx.expect(expected.hash, actual, value)?;
+    }
+
+    let last = match (hir.body.value, hir.body.statements.last()) {
+        (Some(value), _) => Some(value),
+        (None, Some(hir::Stmt::Expr(expr))) => Some(*expr),
+        _ => None,
+    };
+
+    if let Some(
+        expr @ hir::Expr {
+            kind: hir::ExprKind::Return(..),
+            ..
+        },
+    ) = last
+    {
+        cx.q.diagnostics.needless_return(source_id, expr)?;
+    }
+
+    Ok(())
+}
+
+impl<'hir> Ctxt<'_, 'hir, '_> {
+    /// Bind the variables in a pattern, associating them with the given type
+    /// if the pattern is a plain binding.
+    fn bind(&mut self, pat: &hir::PatBinding<'hir>, ty: Option<Hash>) -> alloc::Result<()> {
+        let ty = match (pat.pat.kind, pat.names) {
+            (hir::PatKind::Path(hir::PatPathKind::Ident(..)), [_]) => ty,
+            _ => None,
+        };
+
+        for &name in pat.names {
+            if let hir::Name::Str(string) = name {
+                let shadowed = self.scopes.iter().rev().find(|(n, ..)| *n == name);
+
+                if let (Some(&(_, _, shadowed)), false) = (shadowed, string.starts_with('_')) {
+                    self.q
+                        .diagnostics
+                        .shadowed_binding(self.source_id, &pat.pat, &shadowed)?;
+                }
+            }
+
+            self.scopes.try_push((name, ty, pat.pat.span))?;
+        }
+
+        Ok(())
+    }
+
+    /// Look up the known type of a variable.
+    fn lookup(&self, name: hir::Name<'hir>) -> Option<Hash> {
+        self.scopes
+            .iter()
+            .rev()
+            .find(|(n, ..)| *n == name)
+            .and_then(|(_, ty, _)| *ty)
+    }
+
+    /// Look up the signature of the function with the given hash.
+    fn signature(&self, hash: Hash) -> alloc::Result<Option<Signature>> {
+        let (native, signature) = match self.q.meta_by_hash(hash) {
+            Some(meta) => (meta.context, meta.kind.as_signature()),
+            None => (
+                true,
+                self.q
+                    .context
+                    .lookup_meta_by_hash(hash)
+                    .find_map(|meta| meta.kind.as_signature()),
+            ),
+        };
+
+        let Some(signature) = signature else {
+            return Ok(None);
+        };
+
+        // The return value of an async function is a future.
+        let return_type = match signature.is_async() {
+            true => None,
+            false => signature.return_type(),
+        };
+
+        let mut argument_types = Vec::new();
+
+        if let Some(args) = signature.args() {
+            for n in 0..args {
+                argument_types.try_push(signature.argument_type(n))?;
+            }
+        }
+
+        Ok(Some(Signature {
+            native,
+            args: signature.args(),
+            return_type,
+            argument_types,
+        }))
+    }
+
+    /// Report a type error, if type checking is enabled.
+    fn error(&mut self, span: &dyn Spanned, kind: ErrorKind) -> alloc::Result<()> {
+        if !self.type_check {
+            return Ok(());
+        }
+
+        self.q
+            .diagnostics
+            .error(self.source_id, compile::Error::new(span, kind))
+    }
+
+    /// Report a type mismatch unless the actual type matches the expected one.
+    fn expect(
+        &mut self,
+        expected: Hash,
+        actual: Option<Hash>,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        let Some(actual) = actual else {
+            return Ok(());
+        };
+
+        if expected == actual {
+            return Ok(());
+        }
+
+        let kind = ErrorKind::TypeMismatch {
+            expected: self.q.type_name(expected)?.try_into()?,
+            actual: self.q.type_name(actual)?.try_into()?,
+        };
+
+        self.error(span, kind)
+    }
+
+    fn block(&mut self, hir: &hir::Block<'hir>) -> alloc::Result<Option<Hash>> {
+        let len = self.scopes.len();
+
+        for stmt in hir.statements {
+            match *stmt {
+                hir::Stmt::Local(local) => {
+                    let actual = self.expr(&local.expr)?;
+
+                    let ty = match local.ty {
+                        Some(ty) => {
+                            self.expect(ty.hash, actual, &local.expr)?;
+                            Some(ty.hash)
+                        }
+                        None => actual,
+                    };
+
+                    self.bind(&local.pat, ty)?;
+                }
+                hir::Stmt::Expr(expr) => {
+                    if self.expr(expr)? == Some(static_type::RESULT_TYPE.hash) {
+                        self.q.diagnostics.unused_result(self.source_id, expr)?;
+                    }
+                }
+            }
+        }
+
+        let ty = match hir.value {
+            Some(value) => self.expr(value)?,
+            None => None,
+        };
+
+        self.scopes.truncate(len);
+        Ok(ty)
+    }
+
+    fn condition(&mut self, hir: &hir::Condition<'hir>) -> alloc::Result<()> {
+        match *hir {
+            hir::Condition::Expr(expr) => {
+                self.expr(expr)?;
+            }
+            hir::Condition::ExprLet(expr_let) => {
+                self.expr(&expr_let.expr)?;
+                self.bind(&expr_let.pat, None)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn exprs(&mut self, exprs: &[hir::Expr<'hir>]) -> alloc::Result<Vec<Option<Hash>>> {
+        let mut types = Vec::new();
+
+        for expr in exprs {
+            types.try_push(self.expr(expr)?)?;
+        }
+
+        Ok(types)
+    }
+
+    fn expr(&mut self, hir: &hir::Expr<'hir>) -> alloc::Result<Option<Hash>> {
+        let ty = match hir.kind {
+            hir::ExprKind::Variable(name) => self.lookup(name),
+            hir::ExprKind::Lit(lit) => Some(match lit {
+                hir::Lit::Bool(..) => static_type::BOOL_TYPE.hash,
+                hir::Lit::Integer(..) => static_type::INTEGER_TYPE.hash,
+                hir::Lit::U64(..) => static_type::U64_TYPE.hash,
+                hir::Lit::I128(..) => static_type::I128_TYPE.hash,
+                hir::Lit::U128(..) => static_type::U128_TYPE.hash,
+                hir::Lit::Float(..) => static_type::FLOAT_TYPE.hash,
+                hir::Lit::Byte(..) => static_type::BYTE_TYPE.hash,
+                hir::Lit::Char(..) => static_type::CHAR_TYPE.hash,
+                hir::Lit::Str(..) => static_type::STRING_TYPE.hash,
+                hir::Lit::ByteStr(..) => static_type::BYTES_TYPE.hash,
+            }),
+            hir::ExprKind::Call(call) => self.expr_call(call, hir)?,
+            hir::ExprKind::FieldAccess(field_access) => {
+                self.expr_field_access(field_access, hir)?;
+                None
+            }
+            hir::ExprKind::Binary(binary) => self.expr_binary(binary)?,
+            hir::ExprKind::Unary(unary) => {
+                let ty = self.expr(&unary.expr)?;
+
+                match unary.op {
+                    ast::UnOp::Not(..) if ty == Some(static_type::BOOL_TYPE.hash) => ty,
+                    ast::UnOp::Neg(..) => ty,
+                    _ => None,
+                }
+            }
+            hir::ExprKind::Assign(assign) => {
+                self.expr(&assign.lhs)?;
+                self.expr(&assign.rhs)?;
+                Some(static_type::TUPLE_TYPE.hash)
+            }
+            hir::ExprKind::Loop(expr_loop) => {
+                let len = self.scopes.len();
+
+                if let Some(condition) = expr_loop.condition {
+                    self.condition(condition)?;
+                }
+
+                self.block(&expr_loop.body)?;
+                self.scopes.truncate(len);
+                None
+            }
+            hir::ExprKind::For(expr_for) => {
+                self.expr(&expr_for.iter)?;
+                let len = self.scopes.len();
+                self.bind(&expr_for.binding, None)?;
+                self.block(&expr_for.body)?;
+                self.scopes.truncate(len);
+                None
+            }
+            hir::ExprKind::Let(expr_let) => {
+                self.expr(&expr_let.expr)?;
+                self.bind(&expr_let.pat, None)?;
+                Some(static_type::BOOL_TYPE.hash)
+            }
+            hir::ExprKind::If(conditional) => {
+                for branch in conditional.branches {
+                    let len = self.scopes.len();
+                    self.condition(branch.condition)?;
+                    self.block(&branch.block)?;
+                    self.scopes.truncate(len);
+                }
+
+                if let Some(fallback) = conditional.fallback {
+                    self.block(fallback)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Match(expr_match) => {
+                self.expr(&expr_match.expr)?;
+
+                for branch in expr_match.branches {
+                    let len = self.scopes.len();
+                    self.bind(&branch.pat, None)?;
+
+                    if let Some(condition) = branch.condition {
+                        self.expr(condition)?;
+                    }
+
+                    self.expr(&branch.body)?;
+                    self.scopes.truncate(len);
+                }
+
+                None
+            }
+            hir::ExprKind::Select(select) => {
+                self.exprs(select.exprs)?;
+
+                for branch in select.branches {
+                    let len = self.scopes.len();
+                    self.bind(&branch.pat, None)?;
+                    self.expr(&branch.body)?;
+                    self.scopes.truncate(len);
+                }
+
+                if let Some(default) = select.default {
+                    self.expr(default)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Return(value) => {
+                if let Some(value) = value {
+                    let actual = self.expr(value)?;
+
+                    if let Some(expected) = self.return_type {
+                        self.expect(expected.hash, actual, value)?;
+                    }
+                }
+
+                None
+            }
+            hir::ExprKind::Break(expr_break) => {
+                if let Some(expr) = expr_break.expr {
+                    self.expr(expr)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Yield(expr) => {
+                if let Some(expr) = expr {
+                    self.expr(expr)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Index(index) => {
+                self.expr(&index.target)?;
+                self.expr(&index.index)?;
+                None
+            }
+            hir::ExprKind::Await(expr) | hir::ExprKind::Try(expr) => {
+                self.expr(expr)?;
+                None
+            }
+            hir::ExprKind::Group(expr) => self.expr(expr)?,
+            hir::ExprKind::Block(block) => self.block(block)?,
+            hir::ExprKind::Object(object) => {
+                for assign in object.assignments {
+                    self.expr(&assign.assign)?;
+                }
+
+                match object.kind {
+                    hir::ExprObjectKind::EmptyStruct { hash }
+                    | hir::ExprObjectKind::Struct { hash }
+                    | hir::ExprObjectKind::ExternalType { hash, .. } => Some(hash),
+                    hir::ExprObjectKind::Anonymous => Some(static_type::OBJECT_TYPE.hash),
+                    hir::ExprObjectKind::StructVariant { .. } => None,
+                }
+            }
+            hir::ExprKind::Tuple(seq) => {
+                self.exprs(seq.items)?;
+                Some(static_type::TUPLE_TYPE.hash)
+            }
+            hir::ExprKind::Vec(seq) => {
+                self.exprs(seq.items)?;
+                Some(static_type::VEC_TYPE.hash)
+            }
+            hir::ExprKind::Range(range) => {
+                match *range {
+                    hir::ExprRange::RangeFrom { start } => {
+                        self.expr(&start)?;
+                    }
+                    hir::ExprRange::RangeFull => {}
+                    hir::ExprRange::RangeInclusive { start, end }
+                    | hir::ExprRange::Range { start, end } => {
+                        self.expr(&start)?;
+                        self.expr(&end)?;
+                    }
+                    hir::ExprRange::RangeToInclusive { end } | hir::ExprRange::RangeTo { end } => {
+                        self.expr(&end)?;
+                    }
+                }
+
+                None
+            }
+            hir::ExprKind::Template(template) => {
+                self.exprs(template.exprs)?;
+                Some(static_type::STRING_TYPE.hash)
+            }
+            hir::ExprKind::Format(format) => {
+                self.expr(&format.value)?;
+                None
+            }
+            hir::ExprKind::Type(..)
+            | hir::ExprKind::Fn(..)
+            | hir::ExprKind::Path
+            | hir::ExprKind::Continue(..)
+            | hir::ExprKind::AsyncBlock(..)
+            | hir::ExprKind::CallClosure(..)
+            | hir::ExprKind::Const(..) => None,
+        };
+
+        Ok(ty)
+    }
+
+    fn expr_call(
+        &mut self,
+        hir: &hir::ExprCall<'hir>,
+        span: &dyn Spanned,
+    ) -> alloc::Result<Option<Hash>> {
+        let (hash, mut types) = match hir.call {
+            hir::Call::Meta { hash } => (Some(hash), Vec::new()),
+            hir::Call::Associated { target, hash } => {
+                let target = self.expr(target)?;
+                let mut types = Vec::new();
+                types.try_push(target)?;
+                let hash = target.map(|target| Hash::associated_function(target, hash));
+                (hash, types)
+            }
+            hir::Call::Trait { target, .. } => {
+                let target = self.expr(target)?;
+                let mut types = Vec::new();
+                types.try_push(target)?;
+                (None, types)
+            }
+            hir::Call::Expr { expr } => {
+                self.expr(expr)?;
+                (None, Vec::new())
+            }
+            hir::Call::Var { .. } | hir::Call::ConstFn { .. } => (None, Vec::new()),
+        };
+
+        for arg in hir.args {
+            types.try_push(self.expr(arg)?)?;
+        }
+
+        let Some(hash) = hash else {
+            return Ok(None);
+        };
+
+        let Some(signature) = self.signature(hash)? else {
+            return Ok(None);
+        };
+
+        if let Some(expected) = signature.args {
+            if expected != types.len() {
+                let kind = ErrorKind::UnsupportedArgumentCount {
+                    expected,
+                    actual: types.len(),
+                };
+
+                self.error(span, kind)?;
+                return Ok(signature.return_type);
+            }
+        }
+
+        // Arguments passed to the call which map to types, which excludes the
+        // instance of associated calls.
+        let offset = types.len() - hir.args.len();
+
+        for (n, arg) in hir.args.iter().enumerate() {
+            let expected = signature.argument_types.get(n + offset).copied().flatten();
+            let actual = types[n + offset];
+
+            let (Some(expected), Some(actual)) = (expected, actual) else {
+                continue;
+            };
+
+            if signature.native && is_coercible(expected, actual) {
+                continue;
+            }
+
+            self.expect(expected, Some(actual), arg)?;
+        }
+
+        Ok(signature.return_type)
+    }
+
+    fn expr_field_access(
+        &mut self,
+        hir: &hir::ExprFieldAccess<'hir>,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        let target = self.expr(&hir.expr)?;
+
+        let (Some(target), hir::ExprField::Ident(field)) = (target, hir.expr_field) else {
+            return Ok(());
+        };
+
+        let Some(meta) = self.q.meta_by_hash(target) else {
+            return Ok(());
+        };
+
+        let exists = match &meta.kind {
+            meta::Kind::Struct {
+                fields: meta::Fields::Named(named),
+                ..
+            } => named.fields.contains_key(field),
+            meta::Kind::Struct { .. } => false,
+            _ => return Ok(()),
+        };
+
+        if exists {
+            return Ok(());
+        }
+
+        let kind = ErrorKind::UnknownField {
+            field: field.try_into()?,
+            item: self.q.pool.item(meta.item_meta.item).try_to_owned()?,
+        };
+
+        self.error(span, kind)
+    }
+
+    fn expr_binary(&mut self, hir: &hir::ExprBinary<'hir>) -> alloc::Result<Option<Hash>> {
+        let lhs = self.expr(&hir.lhs)?;
+        let rhs = self.expr(&hir.rhs)?;
+
+        let ty = match hir.op {
+            ast::BinOp::Eq(..)
+            | ast::BinOp::Neq(..)
+            | ast::BinOp::Gt(..)
+            | ast::BinOp::Lt(..)
+            | ast::BinOp::Gte(..)
+            | ast::BinOp::Lte(..)
+            | ast::BinOp::Is(..)
+            | ast::BinOp::IsNot(..)
+            | ast::BinOp::IsImpl(..)
+            | ast::BinOp::IsNotImpl(..)
+            | ast::BinOp::And(..)
+            | ast::BinOp::Or(..) => Some(static_type::BOOL_TYPE.hash),
+            ast::BinOp::As(..) => match hir.rhs.kind {
+                hir::ExprKind::Type(ty) => Some(ty.into_hash()),
+                _ => None,
+            },
+            ast::BinOp::Add(..)
+            | ast::BinOp::Sub(..)
+            | ast::BinOp::Mul(..)
+            | ast::BinOp::Div(..)
+            | ast::BinOp::Rem(..) => {
+                let (Some(lhs), Some(rhs)) = (lhs, rhs) else {
+                    return Ok(None);
+                };
+
+                if !is_number(lhs) || !is_number(rhs) {
+                    return Ok(None);
+                }
+
+                if lhs == rhs {
+                    return Ok(Some(lhs));
+                }
+
+                // Integers of different kinds are widened to the wider kind.
+                match (integer_rank(lhs), integer_rank(rhs)) {
+                    (Some(a), Some(b)) => Some(if a >= b { lhs } else { rhs }),
+                    _ => {
+                        self.expect(lhs, Some(rhs), &hir.rhs)?;
+                        return Ok(None);
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        Ok(ty)
+    }
+}
+
+/// Test if the given type is a built-in number.
+fn is_number(hash: Hash) -> bool {
+    hash == static_type::INTEGER_TYPE.hash
+        || hash == static_type::U64_TYPE.hash
+        || hash == static_type::I128_TYPE.hash
+        || hash == static_type::U128_TYPE.hash
+        || hash == static_type::FLOAT_TYPE.hash
+}
+
+/// Test if a value of the actual type is converted into the expected type when
+/// passed to a native function.
+fn is_coercible(expected: Hash, actual: Hash) -> bool {
+    (integer_rank(expected).is_some() && integer_rank(actual).is_some())
+        || (expected == static_type::VEC_TYPE.hash && actual == static_type::TUPLE_TYPE.hash)
+}
+
+/// Get the rank of an integer type, where integers of a lower rank are widened
+/// into integers of a higher rank in mixed operations.
+fn integer_rank(hash: Hash) -> Option<u8> {
+    if hash == static_type::INTEGER_TYPE.hash {
+        Some(0)
+    } else if hash == static_type::U64_TYPE.hash {
+        Some(1)
+    } else if hash == static_type::I128_TYPE.hash {
+        Some(2)
+    } else if hash == static_type::U128_TYPE.hash {
+        Some(3)
+    } else {
+        None
+    }
+}
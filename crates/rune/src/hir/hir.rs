@@ -226,6 +226,9 @@ pub(crate) struct Expr<'hir> {
 pub(crate) enum Lit<'hir> {
     Bool(bool),
     Integer(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
     Float(f64),
     Byte(u8),
     Char(char),
@@ -445,6 +448,14 @@ pub(crate) enum Call<'hir> {
         /// Hash of the fn being called.
         hash: Hash,
     },
+    Trait {
+        /// The target expression being called.
+        target: &'hir Expr<'hir>,
+        /// Hash of the trait the function belongs to.
+        trait_hash: Hash,
+        /// Hash of the fn being called.
+        hash: Hash,
+    },
     Meta {
         /// Hash being called.
         hash: Hash,
@@ -718,6 +729,8 @@ pub(crate) struct ItemFn<'hir> {
     pub(crate) span: Span,
     /// The arguments of the function.
     pub(crate) args: &'hir [FnArg<'hir>],
+    /// The annotated return type of the function.
+    pub(crate) output: Option<TypeHint>,
     /// The body of the function.
     pub(crate) body: Block<'hir>,
 }
@@ -731,6 +744,20 @@ pub(crate) enum FnArg<'hir> {
     SelfValue(Span),
     /// Function argument is a pattern binding.
     Pat(&'hir PatBinding<'hir>),
+    /// Function argument is a pattern binding with a type annotation.
+    Typed(&'hir PatBinding<'hir>, TypeHint),
+}
+
+/// A resolved type annotation.
+#[derive(Debug, TryClone, Clone, Copy, Spanned)]
+#[try_clone(copy)]
+#[non_exhaustive]
+pub(crate) struct TypeHint {
+    /// The span of the annotation.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The hash of the annotated type.
+    pub(crate) hash: Hash,
 }
 
 /// A block of statements.
@@ -780,6 +807,8 @@ pub(crate) struct Local<'hir> {
     pub(crate) span: Span,
     /// The name of the binding.
     pub(crate) pat: PatBinding<'hir>,
+    /// The annotated type of the binding.
+    pub(crate) ty: Option<TypeHint>,
     /// The expression the binding is assigned to.
     pub(crate) expr: Expr<'hir>,
 }
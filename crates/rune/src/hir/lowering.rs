@@ -8,7 +8,7 @@ use crate::alloc::try_format;
 use crate::alloc::{self, Box, HashMap, HashSet};
 use crate::ast::{self, Spanned};
 use crate::compile::meta;
-use crate::compile::{self, DynLocation, ErrorKind, Item, ItemId, WithSpan};
+use crate::compile::{self, ComponentRef, DynLocation, ErrorKind, Item, ItemId, WithSpan};
 use crate::hash::{Hash, ParametersBuilder};
 use crate::hir;
 use crate::indexing;
@@ -25,6 +25,7 @@ enum Needs {
     #[default]
     Value,
     Type,
+    Trait,
 }
 
 pub(crate) struct Ctxt<'hir, 'a, 'arena> {
@@ -127,6 +128,7 @@ pub(crate) fn empty_fn<'hir>(
     Ok(hir::ItemFn {
         span: span.span(),
         args: &[],
+        output: None,
         body: statements(cx, None, &ast.statements, span)?,
     })
 }
@@ -142,6 +144,10 @@ pub(crate) fn item_fn<'hir>(
     Ok(hir::ItemFn {
         span: ast.span(),
         args: iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?),
+        output: match &ast.output {
+            Some((_, ty)) => Some(type_hint(cx, ty)?),
+            None => None,
+        },
         body: block(cx, None, &ast.body)?,
     })
 }
@@ -533,6 +539,21 @@ pub(crate) fn expr_object<'hir>(
         ast::ObjectIdent::Anonymous(..) => hir::ExprObjectKind::Anonymous,
     };
 
+    if let hir::ExprObjectKind::Struct { hash }
+    | hir::ExprObjectKind::StructVariant { hash }
+    | hir::ExprObjectKind::ExternalType { hash, .. } = kind
+    {
+        for assign in assignments.iter() {
+            cx.q.visitor
+                .visit_field_use(
+                    &DynLocation::new(cx.source_id, &assign.key.0),
+                    hash,
+                    assign.key.1,
+                )
+                .with_span(assign.key.0)?;
+        }
+    }
+
     Ok(hir::ExprKind::Object(alloc!(hir::ExprObject {
         kind,
         assignments,
@@ -654,6 +675,7 @@ pub(crate) fn expr<'hir>(
         ast::Expr::Binary(ast) => {
             let rhs_needs = match &ast.op {
                 ast::BinOp::As(..) | ast::BinOp::Is(..) | ast::BinOp::IsNot(..) => Needs::Type,
+                ast::BinOp::IsImpl(..) | ast::BinOp::IsNotImpl(..) => Needs::Trait,
                 _ => Needs::Value,
             };
 
@@ -785,6 +807,9 @@ pub(crate) fn pat_const_value<'hir>(
             ConstValue::String(ref string) => hir::Lit::Str(alloc_str!(string.as_ref())),
             ConstValue::Bytes(ref bytes) => hir::Lit::ByteStr(alloc_bytes!(bytes.as_ref())),
             ConstValue::Integer(integer) => hir::Lit::Integer(integer),
+            ConstValue::U64(unsigned) => hir::Lit::U64(unsigned),
+            ConstValue::I128(integer) => hir::Lit::I128(integer),
+            ConstValue::U128(unsigned) => hir::Lit::U128(unsigned),
             ConstValue::Vec(ref items) => {
                 let items = iter!(items.iter(), items.len(), |value| pat_const_value(
                     cx, value, span
@@ -928,6 +953,27 @@ pub(crate) fn lit<'hir>(
 
                     Ok(hir::Lit::Byte(n))
                 }
+                (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::U64(..))) => {
+                    let Some(n) = int.to_u64() else {
+                        return Err(compile::Error::new(ast, ErrorKind::BadNumberOutOfBounds));
+                    };
+
+                    Ok(hir::Lit::U64(n))
+                }
+                (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::I128(..))) => {
+                    let Some(n) = int.to_i128() else {
+                        return Err(compile::Error::new(ast, ErrorKind::BadNumberOutOfBounds));
+                    };
+
+                    Ok(hir::Lit::I128(n))
+                }
+                (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::U128(..))) => {
+                    let Some(n) = int.to_u128() else {
+                        return Err(compile::Error::new(ast, ErrorKind::BadNumberOutOfBounds));
+                    };
+
+                    Ok(hir::Lit::U128(n))
+                }
                 (ast::NumberValue::Integer(int), _) => {
                     let Some(n) = int.to_i64() else {
                         return Err(compile::Error::new(ast, ErrorKind::BadNumberOutOfBounds));
@@ -1000,6 +1046,13 @@ pub(crate) fn expr_unary<'hir>(
 
             Ok(hir::ExprKind::Lit(hir::Lit::Integer(n)))
         }
+        (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::I128(..))) => {
+            let Some(n) = int.neg().to_i128() else {
+                return Err(compile::Error::new(ast, ErrorKind::BadNumberOutOfBounds));
+            };
+
+            Ok(hir::ExprKind::Lit(hir::Lit::I128(n)))
+        }
         _ => Err(compile::Error::new(ast, ErrorKind::BadNumberOutOfBounds)),
     }
 }
@@ -1200,6 +1253,18 @@ fn fn_arg<'hir>(
             hir::FnArg::SelfValue(ast.span())
         }
         ast::FnArg::Pat(ast) => hir::FnArg::Pat(alloc!(pat_binding(cx, ast)?)),
+        ast::FnArg::Typed(ast) => {
+            let ty = type_hint(cx, &ast.ty)?;
+            hir::FnArg::Typed(alloc!(pat_binding(cx, &ast.pat)?), ty)
+        }
+    })
+}
+
+/// Lower a type annotation.
+fn type_hint(cx: &mut Ctxt<'_, '_, '_>, ast: &ast::Type) -> compile::Result<hir::TypeHint> {
+    Ok(hir::TypeHint {
+        span: ast.span(),
+        hash: cx.q.convert_type(cx.source_id, ast)?,
     })
 }
 
@@ -1210,9 +1275,15 @@ fn local<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Local) -> compile::Result
     let expr = expr(cx, &ast.expr)?;
     let pat = pat_binding(cx, &ast.pat)?;
 
+    let ty = match &ast.ty {
+        Some((_, ty)) => Some(type_hint(cx, ty)?),
+        None => None,
+    };
+
     Ok(hir::Local {
         span: ast.span(),
         pat,
+        ty,
         expr,
     })
 }
@@ -1414,6 +1485,14 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                         };
 
                         for binding in bindings.iter() {
+                            cx.q.visitor
+                                .visit_field_use(
+                                    &DynLocation::new(cx.source_id, binding),
+                                    meta.hash,
+                                    binding.key(),
+                                )
+                                .with_span(binding)?;
+
                             if !fields.remove(binding.key()) {
                                 return Err(compile::Error::new(
                                     ast,
@@ -1540,18 +1619,11 @@ pub(crate) fn expr_path<'hir>(
         }
     }
 
-    let kind = if !parameters.parameters.is_empty() {
-        ErrorKind::MissingItemParameters {
-            item: cx.q.pool.item(named.item).try_to_owned()?,
-            parameters: parameters.parameters.into_iter().try_collect()?,
-        }
-    } else {
-        ErrorKind::MissingItem {
-            item: cx.q.pool.item(named.item).try_to_owned()?,
-        }
-    };
-
-    Err(compile::Error::new(ast, kind))
+    Err(cx.q.missing_item(
+        &DynLocation::new(cx.source_id, ast),
+        named.item,
+        &parameters,
+    )?)
 }
 
 /// Compile an item.
@@ -1606,6 +1678,16 @@ fn expr_path_meta<'hir>(
                 "something that can be used as a value",
             )),
         }
+    } else if let Needs::Trait = cx.needs.get() {
+        let meta::Kind::Trait { .. } = &meta.kind else {
+            return Err(compile::Error::expected_meta(
+                span,
+                meta.info(cx.q.pool)?,
+                "a trait",
+            ));
+        };
+
+        Ok(hir::ExprKind::Type(Type::new(meta.hash)))
     } else {
         let Some(type_hash) = meta.type_hash_of() else {
             return Err(compile::Error::expected_meta(
@@ -1818,8 +1900,25 @@ fn expr_call<'hir>(
                 let named = cx.q.convert_path(path)?;
                 let parameters = generics_parameters(cx, &named)?;
 
-                let meta = cx.lookup_meta(path, named.item, parameters)?;
-                debug_assert_eq!(meta.item_meta.item, named.item);
+                let Some(meta) = cx.try_lookup_meta(path, named.item, &parameters)? else {
+                    if let Some(call) = expr_call_trait(cx, ast, named.item)? {
+                        return Ok(call);
+                    }
+
+                    return Err(cx.q.missing_item(
+                        &DynLocation::new(cx.source_id, path),
+                        named.item,
+                        &parameters,
+                    )?);
+                };
+
+                // Functions implementing traits can be called through the
+                // implementing type, while they are declared in an item unique
+                // to the implementation.
+                debug_assert!(
+                    meta.item_meta.item == named.item
+                        || matches!(meta.kind, meta::Kind::Function { .. })
+                );
 
                 match &meta.kind {
                     meta::Kind::Struct {
@@ -1931,6 +2030,61 @@ fn expr_call<'hir>(
     })
 }
 
+/// Lower a call to a trait function through its path, like `Trait::name(a)`,
+/// into an instance call on the first argument.
+fn expr_call_trait<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    ast: &ast::ExprCall,
+    item: ItemId,
+) -> compile::Result<Option<hir::ExprCall<'hir>>> {
+    alloc_with!(cx, ast);
+
+    let item = cx.q.pool.item(item);
+
+    let (Some(parent), Some(ComponentRef::Str(name))) = (item.parent(), item.last()) else {
+        return Ok(None);
+    };
+
+    let name = alloc_str!(name);
+    let parent = parent.try_to_owned()?;
+    let parent = cx.q.pool.alloc_item(parent)?;
+
+    let Some(meta) = cx.try_lookup_meta(&ast.expr, parent, &GenericsParameters::default())? else {
+        return Ok(None);
+    };
+
+    let meta::Kind::Trait { functions } = &meta.kind else {
+        return Ok(None);
+    };
+
+    if !functions.iter().any(|f| f.name.as_ref() == name) {
+        return Ok(None);
+    }
+
+    let mut args = ast.args.iter();
+
+    let Some((target, _)) = args.next() else {
+        return Err(compile::Error::new(
+            &ast.args,
+            ErrorKind::UnsupportedArgumentCount {
+                expected: 1,
+                actual: 0,
+            },
+        ));
+    };
+
+    cx.q.unit.insert_debug_ident(name)?;
+
+    Ok(Some(hir::ExprCall {
+        call: hir::Call::Trait {
+            target: alloc!(expr(cx, target)?),
+            trait_hash: meta.hash,
+            hash: Hash::associated_function(meta.hash, name),
+        },
+        args: iter!(args, |(ast, _)| expr(cx, ast)?),
+    }))
+}
+
 #[instrument(span = ast)]
 fn expr_field_access<'hir>(
     cx: &mut Ctxt<'hir, '_, '_>,
@@ -8,7 +8,8 @@ use crate::ast::{self, Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{ItemId, ItemMeta};
 use crate::parse::NonZeroId;
-use crate::runtime::Call;
+use crate::runtime::{Call, Protocol};
+use crate::Hash;
 
 pub(crate) use self::index::{IndexItem, Indexer};
 pub(crate) use self::items::Items;
@@ -88,8 +89,11 @@ pub(crate) struct Function {
     /// If this is a bench function.
     pub(crate) is_bench: bool,
     /// The impl item this function is registered in.
-    #[allow(unused)]
     pub(crate) impl_item: Option<NonZeroId>,
+    /// The trait this function is implemented for, if any.
+    pub(crate) impl_trait: Option<Hash>,
+    /// The protocol this function implements, if any.
+    pub(crate) protocol: Option<Protocol>,
 }
 
 #[derive(Debug, TryClone, Clone, Copy)]
@@ -4,23 +4,24 @@ use core::mem::{replace, take};
 
 use crate::alloc::path::PathBuf;
 use crate::alloc::prelude::*;
-use crate::alloc::{self, Box, HashMap, Vec, VecDeque};
+use crate::alloc::{self, try_format, Box, HashMap, Vec, VecDeque};
 use crate::ast::spanned;
 use crate::ast::{self, OptionSpanned, Span, Spanned};
 use crate::compile::attrs;
 use crate::compile::meta;
 use crate::compile::{self, Doc, DynLocation, ErrorKind, Location, ModId, Visibility, WithSpan};
+use crate::diagnostics::{Lint, LintLevel};
 use crate::indexing::{self, Indexed, Items, Layer, Scopes};
 use crate::macros::MacroCompiler;
 use crate::parse::{NonZeroId, Parse, Parser, Resolve};
 use crate::query::{
     BuiltInFile, BuiltInFormat, BuiltInLine, BuiltInMacro, BuiltInTemplate, ItemImplEntry, Query,
-    QueryImplFn,
+    QueryImplFn, QueryTraitDefaults,
 };
 use crate::runtime::format;
-use crate::runtime::Call;
+use crate::runtime::{Call, Protocol};
 use crate::worker::{Import, ImportKind, LoadFileKind, Task};
-use crate::SourceId;
+use crate::{Hash, SourceId};
 
 use rune_macros::instrument;
 
@@ -91,6 +92,54 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
         self.macro_depth = self.macro_depth.wrapping_sub(1);
     }
 
+    /// Parse lint attributes like `#[allow(unused_imports)]`, and configure
+    /// the level of the named lints for everything inside of `span`.
+    fn lint_attributes(
+        &mut self,
+        p: &mut attrs::Parser,
+        attributes: &[ast::Attribute],
+        span: Span,
+    ) -> compile::Result<()> {
+        let mut lints = Vec::new();
+
+        for result in p.parse_all::<attrs::Allow>(resolve_context!(self.q), attributes)? {
+            for (ident, _) in &result?.1.lints {
+                lints.try_push((*ident, LintLevel::Allow))?;
+            }
+        }
+
+        for result in p.parse_all::<attrs::Warn>(resolve_context!(self.q), attributes)? {
+            for (ident, _) in &result?.1.lints {
+                lints.try_push((*ident, LintLevel::Warn))?;
+            }
+        }
+
+        for result in p.parse_all::<attrs::Deny>(resolve_context!(self.q), attributes)? {
+            for (ident, _) in &result?.1.lints {
+                lints.try_push((*ident, LintLevel::Deny))?;
+            }
+        }
+
+        for (ident, level) in lints {
+            let name = ident.resolve(resolve_context!(self.q))?;
+
+            let Some(lint) = Lint::from_name(name) else {
+                return Err(compile::Error::new(
+                    ident,
+                    ErrorKind::UnknownLint {
+                        name: name.try_into()?,
+                    },
+                ));
+            };
+
+            self.q
+                .diagnostics
+                .lint_scope(self.source_id, span, lint, level)?;
+        }
+
+        Ok(())
+    }
+
     /// Try to expand an internal macro.
     fn try_expand_internal_macro(
         &mut self,
@@ -516,6 +565,10 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
             .with_span(span)?;
     }
 
+    if let Some(span) = ast.option_span() {
+        idx.lint_attributes(&mut p, &ast.attributes, span)?;
+    }
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -704,6 +757,8 @@ pub(crate) fn empty_block_fn(
             is_test: false,
             is_bench: false,
             impl_item: None,
+            impl_trait: None,
+            protocol: None,
         }),
     })?;
 
@@ -715,12 +770,14 @@ pub(crate) fn item_fn_immediate(
     idx: &mut Indexer<'_, '_>,
     mut ast: ast::ItemFn,
 ) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    idx.lint_attributes(&mut p, &ast.attributes, ast.span())?;
+
     let name = ast.name.resolve(resolve_context!(idx.q))?;
 
     let visibility = ast_to_visibility(&ast.visibility)?;
 
-    let mut p = attrs::Parser::new(&ast.attributes)?;
-
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
     let guard = idx.items.push_name(name.as_ref())?;
@@ -737,11 +794,22 @@ pub(crate) fn item_fn_immediate(
     idx.scopes.push()?;
 
     for (arg, _) in &mut ast.args {
-        if let ast::FnArg::Pat(p) = arg {
-            pat(idx, p)?;
+        match arg {
+            ast::FnArg::SelfValue(..) => {}
+            ast::FnArg::Pat(p) => {
+                pat(idx, p)?;
+            }
+            ast::FnArg::Typed(typed) => {
+                pat(idx, &mut typed.pat)?;
+                ty(idx, &mut typed.ty)?;
+            }
         }
     }
 
+    if let Some((_, output)) = &mut ast.output {
+        ty(idx, output)?;
+    }
+
     // Take and restore item nesting.
     let last = idx.nested_item.replace(ast.descriptive_span());
     block(idx, &mut ast.body)?;
@@ -802,6 +870,34 @@ pub(crate) fn item_fn_immediate(
         _ => false,
     };
 
+    let protocol = match p.try_parse::<attrs::Protocol>(resolve_context!(idx.q), &ast.attributes)? {
+        Some((attr, protocol)) => {
+            let protocol = protocol.protocol(resolve_context!(idx.q))?;
+
+            if !ast.is_instance() || idx.item.impl_item.is_none() {
+                return Err(compile::Error::msg(
+                    attr,
+                    "The #[protocol] attribute is only supported on functions receiving `self`",
+                ));
+            }
+
+            // Unary protocols only receive `self`, while callables receive
+            // any number of arguments after it.
+            if protocol.hash != Protocol::CALL_HASH && ast.args.len() != 1 {
+                return Err(compile::Error::new(
+                    &ast.args,
+                    ErrorKind::UnsupportedArgumentCount {
+                        expected: 1,
+                        actual: ast.args.len(),
+                    },
+                ));
+            }
+
+            Some(protocol)
+        }
+        None => None,
+    };
+
     if let Some(attrs) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             attrs,
@@ -834,6 +930,10 @@ pub(crate) fn item_fn_immediate(
         };
     }
 
+    if idx.item.impl_trait.is_some() {
+        idx.q.insert_trait_function(item_meta.item)?;
+    }
+
     let entry = indexing::Entry {
         item_meta,
         indexed: Indexed::Function(indexing::Function {
@@ -843,6 +943,8 @@ pub(crate) fn item_fn_immediate(
             is_test,
             is_bench,
             impl_item: idx.item.impl_item,
+            impl_trait: idx.item.impl_trait,
+            protocol,
         }),
     };
 
@@ -1051,6 +1153,11 @@ fn local(idx: &mut Indexer<'_, '_>, ast: &mut ast::Local) -> compile::Result<()>
     // declaration and use that instead of capturing from the outside.
     expr(idx, &mut ast.expr)?;
     pat(idx, &mut ast.pat)?;
+
+    if let Some((_, t)) = &mut ast.ty {
+        ty(idx, t)?;
+    }
+
     Ok(())
 }
 
@@ -1343,6 +1450,8 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
+    idx.lint_attributes(&mut p, &ast.attributes, ast.span())?;
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -1437,6 +1546,8 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
+    idx.lint_attributes(&mut p, &ast.attributes, ast.span())?;
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -1502,7 +1613,11 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
 
 #[instrument(span = ast)]
 fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Result<()> {
-    if let Some(first) = ast.attributes.first() {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    idx.lint_attributes(&mut p, &ast.attributes, ast.span())?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
             "Attributes on impl blocks are not supported",
@@ -1511,11 +1626,20 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
 
     path(idx, &mut ast.path)?;
 
+    let trait_ = match ast.trait_ {
+        Some((mut trait_, _)) => {
+            path(idx, &mut trait_)?;
+            Some(Box::try_new(trait_)?)
+        }
+        None => None,
+    };
+
     let location = Location::new(idx.source_id, ast.path.span());
     let id = idx.q.gen.next();
 
     idx.q.inner.impl_item_queue.try_push_back(ItemImplEntry {
         path: Box::try_new(ast.path)?,
+        trait_,
         location,
         id,
         root: idx.root.clone(),
@@ -1533,12 +1657,103 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
     Ok(())
 }
 
+#[instrument(span = ast)]
+fn item_trait(idx: &mut Indexer<'_, '_>, ast: ast::ItemTrait) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+
+    idx.lint_attributes(&mut p, &ast.attributes, ast.span())?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "Attributes on traits are not supported",
+        ));
+    }
+
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let guard = idx.items.push_name(name.as_ref())?;
+    let idx_item = idx.item.replace();
+
+    let visibility = ast_to_visibility(&ast.visibility)?;
+    let item_meta = idx.q.insert_new_item(
+        &idx.items,
+        &DynLocation::new(idx.source_id, &ast),
+        idx.item.module,
+        visibility,
+        &docs,
+    )?;
+
+    let mut functions = Vec::<meta::TraitFn>::new();
+    let mut defaults = Vec::new();
+
+    for f in &ast.functions {
+        let name = f.name.resolve(resolve_context!(idx.q))?;
+
+        if functions
+            .iter()
+            .any(|existing| existing.name.as_ref() == name)
+        {
+            return Err(compile::Error::msg(
+                f.descriptive_span(),
+                try_format!("Function `{name}` is declared multiple times in trait"),
+            ));
+        }
+
+        match f.to_item_fn()? {
+            Some(item_fn) => {
+                defaults.try_push(item_fn)?;
+            }
+            None => {
+                // Required functions are never indexed, so only doc comments
+                // are supported on them.
+                let mut p = attrs::Parser::new(&f.attributes)?;
+                Doc::collect_from(resolve_context!(idx.q), &mut p, &f.attributes)?;
+
+                let first = p.remaining(&f.attributes).next();
+
+                if let Some(first) = first {
+                    return Err(compile::Error::msg(
+                        first,
+                        "Attributes on functions are not supported",
+                    ));
+                }
+            }
+        }
+
+        functions.try_push(meta::TraitFn {
+            name: name.try_into()?,
+            has_default: f.body.is_some(),
+        })?;
+    }
+
+    idx.q.inner.trait_defaults.try_insert(
+        item_meta.item,
+        QueryTraitDefaults {
+            module: idx.item.module,
+            source_id: idx.source_id,
+            root: idx.root.clone(),
+            functions: defaults,
+        },
+    )?;
+
+    idx.q
+        .index_meta(&ast, item_meta, meta::Kind::Trait { functions })?;
+
+    idx.item = idx_item;
+    idx.items.pop(guard).with_span(&ast)?;
+    Ok(())
+}
+
 #[instrument(span = ast)]
 fn item_mod(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemMod) -> compile::Result<()> {
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
+    idx.lint_attributes(&mut p, &ast.attributes, ast.span())?;
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -1589,6 +1804,8 @@ fn item_const(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemConst) -> compile::Re
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
+    idx.lint_attributes(&mut p, &ast.attributes, ast.span())?;
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -1635,6 +1852,9 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
         ast::Item::Impl(item) => {
             item_impl(idx, item)?;
         }
+        ast::Item::Trait(item) => {
+            item_trait(idx, item)?;
+        }
         ast::Item::Mod(item) => {
             item_mod(idx, item)?;
         }
@@ -1662,7 +1882,11 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
         }
         // NB: imports are ignored during indexing.
         ast::Item::Use(item_use) => {
-            if let Some(span) = item_use.attributes.first() {
+            let mut p = attrs::Parser::new(&item_use.attributes)?;
+
+            idx.lint_attributes(&mut p, &item_use.attributes, item_use.span())?;
+
+            if let Some(span) = p.remaining(&item_use.attributes).next() {
                 return Err(compile::Error::msg(
                     span,
                     "Attributes on uses are not supported",
@@ -1710,6 +1934,22 @@ fn path(idx: &mut Indexer<'_, '_>, ast: &mut ast::Path) -> compile::Result<()> {
     Ok(())
 }
 
+#[instrument(span = ast)]
+fn ty(idx: &mut Indexer<'_, '_>, ast: &mut ast::Type) -> compile::Result<()> {
+    match ast {
+        ast::Type::Path(p) => {
+            path(idx, p)?;
+        }
+        ast::Type::Tuple(tuple) => {
+            for (t, _) in tuple {
+                ty(idx, t)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[instrument(span = ast)]
 fn path_segment(idx: &mut Indexer<'_, '_>, ast: &mut ast::PathSegment) -> compile::Result<()> {
     if let ast::PathSegment::Generics(generics) = ast {
@@ -1767,6 +2007,10 @@ fn expr_closure(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprClosure) -> compil
             ast::FnArg::Pat(p) => {
                 pat(idx, p)?;
             }
+            ast::FnArg::Typed(typed) => {
+                pat(idx, &mut typed.pat)?;
+                ty(idx, &mut typed.ty)?;
+            }
         }
     }
 
@@ -2011,6 +2255,8 @@ pub(crate) struct IndexItem {
     pub(crate) module: ModId,
     /// Set if we are inside of an impl self.
     pub(crate) impl_item: Option<NonZeroId>,
+    /// The hash of the trait being implemented, if any.
+    pub(crate) impl_trait: Option<Hash>,
     /// Whether the item has been inserted or not.
     pub(crate) id: Option<NonZeroId>,
 }
@@ -2020,14 +2266,20 @@ impl IndexItem {
         Self {
             module,
             impl_item: None,
+            impl_trait: None,
             id: None,
         }
     }
 
-    pub(crate) fn with_impl_item(module: ModId, impl_item: NonZeroId) -> Self {
+    pub(crate) fn with_impl_item(
+        module: ModId,
+        impl_item: NonZeroId,
+        impl_trait: Option<Hash>,
+    ) -> Self {
         Self {
             module,
             impl_item: Some(impl_item),
+            impl_trait,
             id: None,
         }
     }
@@ -2037,6 +2289,7 @@ impl IndexItem {
         IndexItem {
             module: self.module,
             impl_item: self.impl_item,
+            impl_trait: self.impl_trait,
             id: take(&mut self.id),
         }
     }
@@ -2046,6 +2299,7 @@ impl IndexItem {
         IndexItem {
             module: replace(&mut self.module, module),
             impl_item: self.impl_item,
+            impl_trait: self.impl_trait,
             id: take(&mut self.id),
         }
     }
@@ -2055,6 +2309,7 @@ impl IndexItem {
         IndexItem {
             module: self.module,
             impl_item: replace(&mut self.impl_item, Some(item)),
+            impl_trait: take(&mut self.impl_trait),
             id: take(&mut self.id),
         }
     }
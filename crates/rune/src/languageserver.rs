@@ -2,11 +2,21 @@
 
 #![allow(clippy::too_many_arguments)]
 
+mod code_actions;
 mod completion;
 mod connection;
 pub mod envelope;
+mod formatting;
 mod fs;
+mod hover;
+mod inlay_hints;
+mod references;
+mod semantic_tokens;
+mod signature_help;
 mod state;
+mod symbols;
+#[cfg(test)]
+mod testing;
 mod url;
 
 use lsp::notification::Notification;
@@ -16,7 +26,7 @@ use tokio::sync::Notify;
 
 use crate::alloc::String;
 use crate::languageserver::connection::stdio;
-use crate::languageserver::envelope::Code;
+use crate::languageserver::envelope::{Code, RequestError};
 use crate::languageserver::state::State;
 use crate::support::Result;
 use crate::workspace::MANIFEST_FILE;
@@ -75,7 +85,22 @@ pub async fn run(context: Context, options: Options) -> Result<()> {
                         match incoming.method.as_str() {
                             $(<$req_ty>::METHOD => {
                                 let params = <$req_ty as Request>::Params::deserialize(incoming.params)?;
-                                let result = $req_handle(&mut state, params).await?;
+
+                                let result = match $req_handle(&mut state, params).await {
+                                    Ok(result) => result,
+                                    Err(error) => {
+                                        let Some(error) = error.downcast_ref::<RequestError>() else {
+                                            return Err(error);
+                                        };
+
+                                        state.output
+                                            .error(incoming.id, error.code, error.message, None::<()>)
+                                            .await?;
+
+                                        continue;
+                                    }
+                                };
+
                                 state.output.response(incoming.id, result).await?;
                             })*
                             $(<$notif_ty>::METHOD => {
@@ -100,11 +125,25 @@ pub async fn run(context: Context, options: Options) -> Result<()> {
                     req(lsp::request::Shutdown, shutdown),
                     req(lsp::request::GotoDefinition, goto_definition),
                     req(lsp::request::Completion, completion),
+                    req(lsp::request::HoverRequest, hover),
+                    req(lsp::request::References, references),
+                    req(lsp::request::PrepareRenameRequest, prepare_rename),
+                    req(lsp::request::Rename, rename),
+                    req(lsp::request::DocumentSymbolRequest, document_symbol),
+                    req(lsp::request::WorkspaceSymbolRequest, workspace_symbol),
+                    req(lsp::request::SemanticTokensFullRequest, semantic_tokens_full),
+                    req(lsp::request::SemanticTokensRangeRequest, semantic_tokens_range),
+                    req(lsp::request::SignatureHelpRequest, signature_help),
+                    req(lsp::request::CodeActionRequest, code_action),
+                    req(lsp::request::InlayHintRequest, inlay_hint),
                     req(lsp::request::Formatting, formatting),
+                    req(lsp::request::RangeFormatting, range_formatting),
+                    req(lsp::request::OnTypeFormatting, on_type_formatting),
                     notif(lsp::notification::DidOpenTextDocument, did_open_text_document),
                     notif(lsp::notification::DidChangeTextDocument, did_change_text_document),
                     notif(lsp::notification::DidCloseTextDocument, did_close_text_document),
                     notif(lsp::notification::DidSaveTextDocument, did_save_text_document),
+                    notif(lsp::notification::DidChangeWatchedFiles, did_change_watched_files),
                     notif(lsp::notification::Initialized, initialized),
                 }
             },
@@ -142,6 +181,50 @@ async fn initialize(
             }),
         }),
         document_formatting_provider: Some(lsp::OneOf::Left(true)),
+        document_range_formatting_provider: Some(lsp::OneOf::Left(true)),
+        document_on_type_formatting_provider: Some(lsp::DocumentOnTypeFormattingOptions {
+            first_trigger_character: "}".into(),
+            more_trigger_character: Some(vec![";".into()]),
+        }),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+        references_provider: Some(lsp::OneOf::Left(true)),
+        rename_provider: Some(lsp::OneOf::Right(lsp::RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
+        workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+        signature_help_provider: Some(lsp::SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: Some(vec![")".into()]),
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        inlay_hint_provider: Some(lsp::OneOf::Left(true)),
+        code_action_provider: Some(lsp::CodeActionProviderCapability::Options(
+            lsp::CodeActionOptions {
+                code_action_kinds: Some(vec![lsp::CodeActionKind::QUICKFIX]),
+                work_done_progress_options: lsp::WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                resolve_provider: None,
+            },
+        )),
+        semantic_tokens_provider: Some(
+            lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                lsp::SemanticTokensOptions {
+                    work_done_progress_options: lsp::WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                    legend: semantic_tokens::legend(),
+                    range: Some(true),
+                    full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
+                },
+            ),
+        ),
         ..Default::default()
     };
 
@@ -220,14 +303,172 @@ async fn completion(
     Ok(Some(lsp::CompletionResponse::Array(results.into_std())))
 }
 
+/// Handle hover request.
+async fn hover(state: &mut State<'_>, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
+    state.hover(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle find references request.
+async fn references(
+    state: &mut State<'_>,
+    params: lsp::ReferenceParams,
+) -> Result<Option<::rust_alloc::vec::Vec<lsp::Location>>> {
+    let locations = state.references(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        params.context.include_declaration,
+    )?;
+
+    Ok(locations.map(|locations| locations.into_std()))
+}
+
+/// Handle prepare rename request.
+async fn prepare_rename(
+    state: &mut State<'_>,
+    params: lsp::TextDocumentPositionParams,
+) -> Result<Option<lsp::PrepareRenameResponse>> {
+    state.prepare_rename(&params.text_document.uri, params.position)
+}
+
+/// Handle rename request.
+async fn rename(
+    state: &mut State<'_>,
+    params: lsp::RenameParams,
+) -> Result<Option<lsp::WorkspaceEdit>> {
+    state.rename(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        &params.new_name,
+    )
+}
+
+/// Handle document symbol request.
+async fn document_symbol(
+    state: &mut State<'_>,
+    params: lsp::DocumentSymbolParams,
+) -> Result<Option<lsp::DocumentSymbolResponse>> {
+    let symbols = state.document_symbols(&params.text_document.uri)?;
+    Ok(Some(lsp::DocumentSymbolResponse::Nested(
+        symbols.into_std(),
+    )))
+}
+
+/// Handle workspace symbol request.
+async fn workspace_symbol(
+    state: &mut State<'_>,
+    params: lsp::WorkspaceSymbolParams,
+) -> Result<Option<lsp::WorkspaceSymbolResponse>> {
+    let symbols = state.workspace_symbols(&params.query)?;
+    Ok(Some(lsp::WorkspaceSymbolResponse::Flat(symbols.into_std())))
+}
+
+/// Handle semantic tokens request for a whole document.
+async fn semantic_tokens_full(
+    state: &mut State<'_>,
+    params: lsp::SemanticTokensParams,
+) -> Result<Option<lsp::SemanticTokensResult>> {
+    let Some(data) = state.semantic_tokens(&params.text_document.uri, None)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(lsp::SemanticTokensResult::Tokens(
+        lsp::SemanticTokens {
+            result_id: None,
+            data: data.into_std(),
+        },
+    )))
+}
+
+/// Handle semantic tokens request for a range of a document.
+async fn semantic_tokens_range(
+    state: &mut State<'_>,
+    params: lsp::SemanticTokensRangeParams,
+) -> Result<Option<lsp::SemanticTokensRangeResult>> {
+    let Some(data) = state.semantic_tokens(&params.text_document.uri, Some(params.range))? else {
+        return Ok(None);
+    };
+
+    Ok(Some(lsp::SemanticTokensRangeResult::Tokens(
+        lsp::SemanticTokens {
+            result_id: None,
+            data: data.into_std(),
+        },
+    )))
+}
+
+/// Handle signature help request.
+async fn signature_help(
+    state: &mut State<'_>,
+    params: lsp::SignatureHelpParams,
+) -> Result<Option<lsp::SignatureHelp>> {
+    state.signature_help(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle code action request.
+async fn code_action(
+    state: &mut State<'_>,
+    params: lsp::CodeActionParams,
+) -> Result<Option<lsp::CodeActionResponse>> {
+    let actions = state.code_actions(&params.text_document.uri, params.range)?;
+    Ok(Some(actions.into_std()))
+}
+
+/// Handle inlay hint request.
+async fn inlay_hint(
+    state: &mut State<'_>,
+    params: lsp::InlayHintParams,
+) -> Result<Option<::rust_alloc::vec::Vec<lsp::InlayHint>>> {
+    let Some(hints) = state.inlay_hints(&params.text_document.uri, params.range)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(hints.into_std()))
+}
+
 /// Handle formatting request.
 async fn formatting(
     state: &mut State<'_>,
     params: lsp::DocumentFormattingParams,
 ) -> Result<Option<::rust_alloc::vec::Vec<lsp::TextEdit>>> {
-    state
-        .format(&params.text_document.uri)
-        .map(|option| option.map(|formatted| vec![formatted]))
+    let Some(edits) = state.format(&params.text_document.uri)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(edits.into_std()))
+}
+
+/// Handle range formatting request.
+async fn range_formatting(
+    state: &mut State<'_>,
+    params: lsp::DocumentRangeFormattingParams,
+) -> Result<Option<::rust_alloc::vec::Vec<lsp::TextEdit>>> {
+    let Some(edits) = state.format_range(&params.text_document.uri, params.range)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(edits.into_std()))
+}
+
+/// Handle on type formatting request.
+async fn on_type_formatting(
+    state: &mut State<'_>,
+    params: lsp::DocumentOnTypeFormattingParams,
+) -> Result<Option<::rust_alloc::vec::Vec<lsp::TextEdit>>> {
+    let position = params.text_document_position;
+
+    let Some(edits) =
+        state.format_on_type(&position.text_document.uri, position.position, &params.ch)?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(edits.into_std()))
 }
 
 /// Handle open text document.
@@ -304,3 +545,16 @@ async fn did_save_text_document(
     s.rebuild_interest();
     Ok(())
 }
+
+/// Handle files being created, changed or deleted on disk.
+async fn did_change_watched_files(
+    s: &mut State<'_>,
+    params: lsp::DidChangeWatchedFilesParams,
+) -> Result<()> {
+    for change in params.changes {
+        s.workspace_mut().mark_changed(change.uri);
+    }
+
+    s.rebuild_interest();
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use lsp::Url;
+
+use crate::alloc::Vec;
+use crate::diagnostics::Suggestion;
+
+use super::state::span_to_lsp_range;
+
+/// A fix for a diagnostic which can be applied to a source.
+struct QuickFix {
+    /// The diagnostic being fixed.
+    diagnostic: lsp::Diagnostic,
+    /// The title of the fix.
+    title: ::rust_alloc::string::String,
+    /// The edits to apply.
+    edits: ::rust_alloc::vec::Vec<lsp::TextEdit>,
+}
+
+/// Quick fixes for every diagnostic in the workspace which has suggestions.
+#[derive(Default)]
+pub(super) struct QuickFixes {
+    by_url: BTreeMap<Url, Vec<QuickFix>>,
+}
+
+impl QuickFixes {
+    /// Register the suggestions of the given diagnostic, which was reported
+    /// for the given source.
+    pub(super) fn insert(
+        &mut self,
+        source: &crate::Source,
+        url: &Url,
+        diagnostic: &lsp::Diagnostic,
+        suggestions: &[Suggestion],
+    ) -> Result<()> {
+        for suggestion in suggestions {
+            let mut edits = ::rust_alloc::vec::Vec::new();
+
+            for edit in suggestion.edits() {
+                let Some(range) = span_to_lsp_range(source, edit.span()) else {
+                    continue;
+                };
+
+                edits.push(lsp::TextEdit::new(range, edit.replacement().into()));
+            }
+
+            if edits.is_empty() {
+                continue;
+            }
+
+            self.by_url
+                .entry(url.clone())
+                .or_default()
+                .try_push(QuickFix {
+                    diagnostic: diagnostic.clone(),
+                    title: suggestion.message().into(),
+                    edits,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Get code actions fixing diagnostics which overlap the given range.
+    pub(super) fn code_actions(
+        &self,
+        url: &Url,
+        range: lsp::Range,
+    ) -> Result<Vec<lsp::CodeActionOrCommand>> {
+        let mut output = Vec::new();
+
+        let Some(fixes) = self.by_url.get(url) else {
+            return Ok(output);
+        };
+
+        for fix in fixes {
+            if fix.diagnostic.range.end < range.start || range.end < fix.diagnostic.range.start {
+                continue;
+            }
+
+            // Only prefer a fix if it's the only one available for the
+            // diagnostic, like when there are several candidate imports.
+            let is_preferred = fixes
+                .iter()
+                .filter(|other| other.diagnostic == fix.diagnostic)
+                .count()
+                == 1;
+
+            let mut changes = std::collections::HashMap::new();
+            changes.insert(url.clone(), fix.edits.clone());
+
+            output.try_push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                title: fix.title.clone(),
+                kind: Some(lsp::CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![fix.diagnostic.clone()]),
+                edit: Some(lsp::WorkspaceEdit::new(changes)),
+                is_preferred: Some(is_preferred),
+                ..Default::default()
+            }))?;
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::languageserver::testing::{range, url, Harness};
+    use crate::support::Result;
+    use crate::Context;
+
+    const SOURCE: &str = r#"struct Point {};
+
+pub fn main() {
+    let map = HashMap::new();
+    map
+}
+"#;
+
+    #[test]
+    fn test_code_actions() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        let mut code_actions = |range: lsp::Range| {
+            let params = lsp::CodeActionParams {
+                text_document: lsp::TextDocumentIdentifier { uri: main.clone() },
+                range,
+                context: Default::default(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            };
+
+            let actions = harness
+                .block_on(super::super::code_action(&mut state, params))?
+                .expect("expected code actions");
+
+            let mut output = ::rust_alloc::vec::Vec::new();
+
+            for action in actions {
+                let lsp::CodeActionOrCommand::CodeAction(action) = action else {
+                    panic!("expected code action");
+                };
+
+                output.push(action);
+            }
+
+            Ok::<_, crate::support::Error>(output)
+        };
+
+        let missing = range(SOURCE, "HashMap::new", 0);
+
+        let [action] = &code_actions(range(SOURCE, "HashMap", 0))?[..] else {
+            panic!("expected one code action");
+        };
+
+        assert_eq!(action.title, "Import `std::collections::HashMap`");
+        assert_eq!(action.kind, Some(lsp::CodeActionKind::QUICKFIX));
+        assert_eq!(action.is_preferred, Some(true));
+
+        let [diagnostic] = action.diagnostics.as_deref().expect("expected diagnostics") else {
+            panic!("expected one diagnostic");
+        };
+
+        assert_eq!(diagnostic.range, missing);
+        assert_eq!(diagnostic.message, "Missing item `HashMap::new`");
+
+        let changes = action
+            .edit
+            .as_ref()
+            .and_then(|edit| edit.changes.as_ref())
+            .expect("expected changes");
+
+        let start = lsp::Position::new(0, 0);
+
+        assert_eq!(
+            changes.get(&main),
+            Some(&vec![lsp::TextEdit::new(
+                lsp::Range::new(start, start),
+                "use std::collections::HashMap;\n".into(),
+            )])
+        );
+
+        let [action] = &code_actions(range(SOURCE, "};", 0))?[..] else {
+            panic!("expected one code action");
+        };
+
+        assert_eq!(action.title, "Remove the semicolon");
+
+        let changes = action
+            .edit
+            .as_ref()
+            .and_then(|edit| edit.changes.as_ref())
+            .expect("expected changes");
+
+        assert_eq!(
+            changes.get(&main),
+            Some(&vec![lsp::TextEdit::new(range(SOURCE, ";", 0), "".into())])
+        );
+
+        assert!(code_actions(range(SOURCE, "pub fn main", 0))?.is_empty());
+        Ok(())
+    }
+}
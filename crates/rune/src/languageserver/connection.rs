@@ -45,10 +45,17 @@ impl Input {
     }
 }
 
+/// The writer an output connection writes to.
+enum Writer {
+    Stdout(io::Stdout),
+    #[cfg(test)]
+    Sink(io::Sink),
+}
+
 /// Output connection.
 #[derive(Clone)]
 pub(super) struct Output {
-    stdout: Arc<Mutex<io::Stdout>>,
+    stdout: Arc<Mutex<Writer>>,
 }
 
 impl Output {
@@ -148,9 +155,17 @@ impl Output {
         write!(m, "\r\n")?;
         m.append(bytes);
 
-        let mut stdout = self.stdout.lock().await;
-        stdout.write_all(&m).await?;
-        stdout.flush().await?;
+        match &mut *self.stdout.lock().await {
+            Writer::Stdout(stdout) => {
+                stdout.write_all(&m).await?;
+                stdout.flush().await?;
+            }
+            #[cfg(test)]
+            Writer::Sink(sink) => {
+                sink.write_all(&m).await?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -166,12 +181,20 @@ pub(super) fn stdio() -> Result<(Input, Output)> {
     };
 
     let output = Output {
-        stdout: Arc::new(Mutex::new(stdout)),
+        stdout: Arc::new(Mutex::new(Writer::Stdout(stdout))),
     };
 
     Ok((input, output))
 }
 
+/// Setup an output which discards everything written to it.
+#[cfg(test)]
+pub(super) fn sink() -> Output {
+    Output {
+        stdout: Arc::new(Mutex::new(Writer::Sink(io::sink()))),
+    }
+}
+
 #[derive(Debug)]
 pub(super) enum ContentType {
     JsonRPC,
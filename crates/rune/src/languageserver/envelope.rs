@@ -112,6 +112,30 @@ pub(super) struct ResponseError<'a, D> {
     pub(super) data: Option<D>,
 }
 
+/// An error which is responded with to the request that caused it, rather
+/// than stopping the server.
+#[derive(Debug)]
+pub(super) struct RequestError {
+    pub(super) code: Code,
+    pub(super) message: &'static str,
+}
+
+impl RequestError {
+    /// Construct a new request error.
+    pub(super) const fn new(code: Code, message: &'static str) -> Self {
+        Self { code, message }
+    }
+}
+
+impl fmt::Display for RequestError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
 #[derive(Debug, PartialEq, TryClone, Clone, Copy, Hash, Eq)]
 #[try_clone(copy)]
 pub(super) struct V2;
@@ -0,0 +1,134 @@
+use core::ops::Range;
+
+use anyhow::{Context as _, Result};
+use similar::{DiffTag, TextDiff};
+
+use crate::alloc::Vec;
+
+use super::state::Source;
+
+/// Compute the edits which turn `old` into `new`, where `old` is the text of
+/// the source starting at byte `offset`.
+///
+/// Only the parts which differ are replaced, so that the cursor position and
+/// undo history of the client stay intact.
+pub(super) fn text_edits(
+    source: &Source,
+    offset: usize,
+    old: &str,
+    new: &str,
+) -> Result<Vec<lsp::TextEdit>> {
+    let mut edits = Vec::new();
+
+    for (range, text) in diff(old, new)? {
+        let start = source
+            .byte_to_lsp_position(offset + range.start)
+            .context("Edit starts outside of source")?;
+        let end = source
+            .byte_to_lsp_position(offset + range.end)
+            .context("Edit ends outside of source")?;
+
+        edits.try_push(lsp::TextEdit::new(lsp::Range::new(start, end), text.into()))?;
+    }
+
+    Ok(edits)
+}
+
+/// Diff two strings, returning the byte ranges in `old` which should be
+/// replaced with the associated text from `new`.
+///
+/// Lines are diffed first, after which replaced lines are refined by diffing
+/// their characters.
+fn diff<'a>(old: &str, new: &'a str) -> Result<Vec<(Range<usize>, &'a str)>> {
+    let mut edits = Vec::new();
+
+    let lines = TextDiff::from_lines(old, new);
+    let old_offsets = offsets(lines.old_slices())?;
+    let new_offsets = offsets(lines.new_slices())?;
+
+    for op in lines.ops() {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+
+        let old_range = old_offsets[old_range.start]..old_offsets[old_range.end];
+        let new_range = new_offsets[new_range.start]..new_offsets[new_range.end];
+
+        match tag {
+            DiffTag::Equal => {}
+            DiffTag::Delete | DiffTag::Insert => {
+                edits.try_push((old_range, &new[new_range]))?;
+            }
+            DiffTag::Replace => {
+                let old_text = &old[old_range.clone()];
+                let new_text = &new[new_range.clone()];
+
+                let chars = TextDiff::from_chars(old_text, new_text);
+                let old_chars = offsets(chars.old_slices())?;
+                let new_chars = offsets(chars.new_slices())?;
+
+                for op in chars.ops() {
+                    let (tag, old, new) = op.as_tag_tuple();
+
+                    if tag == DiffTag::Equal {
+                        continue;
+                    }
+
+                    let old = old_range.start + old_chars[old.start]
+                        ..old_range.start + old_chars[old.end];
+
+                    edits.try_push((old, &new_text[new_chars[new.start]..new_chars[new.end]]))?;
+                }
+            }
+        }
+    }
+
+    Ok(edits)
+}
+
+/// Calculate the byte offset at which each slice starts, followed by the
+/// offset at which the last one ends.
+fn offsets(slices: &[&str]) -> Result<Vec<usize>> {
+    let mut offsets = Vec::try_with_capacity(slices.len() + 1)?;
+    let mut offset = 0;
+    offsets.try_push(offset)?;
+
+    for slice in slices {
+        offset += slice.len();
+        offsets.try_push(offset)?;
+    }
+
+    Ok(offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff;
+
+    fn apply(old: &str, new: &str) -> ::rust_alloc::string::String {
+        let mut out = ::rust_alloc::string::String::from(old);
+
+        for (range, text) in diff(old, new).unwrap().into_iter().rev() {
+            out.replace_range(range, text);
+        }
+
+        out
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = "fn main() {\n  let a=1;\n\n\n  a\n}\n";
+        let new = "fn main() {\n    let a = 1;\n\n    a\n}\n";
+        assert_eq!(apply(old, new), new);
+
+        let edits = diff(old, new).unwrap();
+        assert!(edits
+            .iter()
+            .all(|(range, _)| range.start >= "fn main() {\n".len()));
+        assert!(edits
+            .iter()
+            .all(|(range, text)| range.len() + text.len() <= 3));
+
+        assert!(diff(new, new).unwrap().is_empty());
+        assert_eq!(apply("", "fn main() {}\n"), "fn main() {}\n");
+        assert_eq!(apply("fn main() {}\n", ""), "");
+    }
+}
@@ -0,0 +1,445 @@
+use anyhow::Result;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{try_format, String, Vec};
+use crate::compile::context::ContextMeta;
+use crate::compile::meta;
+use crate::compile::Item;
+use crate::runtime::debug::DebugArgs;
+use crate::{Context, Hash};
+
+use super::state::{Definition, DefinitionKind, Source};
+
+/// Render hover documentation for the given definition.
+///
+/// Returns `None` if there is nothing interesting to show.
+pub(super) fn hover_for_definition(
+    context: &Context,
+    workspace_source: &Source,
+    definition: &Definition,
+) -> Result<Option<String>> {
+    if let DefinitionKind::Local = definition.kind {
+        let Some(name) = workspace_source.definition_text(definition) else {
+            return Ok(None);
+        };
+
+        let mut out = String::new();
+        write!(out, "```rune\nlet {name}\n```")?;
+        return Ok(Some(out));
+    }
+
+    let Some(hash) = definition.hash else {
+        return Ok(None);
+    };
+
+    if definition.is_context() {
+        let Some(meta) = context.lookup_meta_by_hash(hash).next() else {
+            return Ok(None);
+        };
+
+        let mut out = String::new();
+        write_native(context, meta, &mut out)?;
+        return Ok(Some(out));
+    }
+
+    let Some(item) = definition.item.as_deref() else {
+        return Ok(None);
+    };
+
+    let mut out = String::new();
+    out.try_push_str("```rune\n")?;
+    write_script_signature(workspace_source, definition.kind, hash, item, &mut out)?;
+    out.try_push_str("\n```\n")?;
+    write_hash(hash, &mut out)?;
+
+    if let Some(data) = workspace_source.get_docs_by_hash(hash) {
+        if let Some(deprecated) = &data.deprecated {
+            write!(out, "\n\n**Deprecated:** {deprecated}")?;
+        }
+
+        write_docs(data.docs.iter().map(|s| s.as_str()), &mut out)?;
+    }
+
+    Ok(Some(out))
+}
+
+/// Render hover documentation for every native instance function matching
+/// the given name.
+///
+/// Instance function calls are resolved at runtime, so this is the best we
+/// can do when hovering over something like `value.len()`.
+pub(super) fn hover_for_instance_fn(context: &Context, name: &str) -> Result<Option<String>> {
+    let mut out = String::new();
+
+    for (meta, _) in context.iter_functions() {
+        let meta::Kind::Function {
+            associated: Some(meta::AssociatedKind::Instance(n)),
+            ..
+        } = &meta.kind
+        else {
+            continue;
+        };
+
+        if n.as_ref() != name {
+            continue;
+        }
+
+        if !out.is_empty() {
+            out.try_push_str("\n\n---\n\n")?;
+        }
+
+        write_native(context, meta, &mut out)?;
+    }
+
+    if out.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(out))
+}
+
+/// Write the signature of a script item.
+fn write_script_signature(
+    workspace_source: &Source,
+    kind: DefinitionKind,
+    hash: Hash,
+    item: &Item,
+    out: &mut String,
+) -> Result<()> {
+    let fields = workspace_source
+        .get_docs_by_hash(hash)
+        .and_then(|data| data.kind.as_ref())
+        .and_then(|kind| match kind {
+            meta::Kind::Struct { fields, .. } | meta::Kind::Variant { fields, .. } => Some(fields),
+            _ => None,
+        });
+
+    match kind {
+        DefinitionKind::EmptyStruct | DefinitionKind::TupleStruct | DefinitionKind::Struct => {
+            write!(out, "struct {item}")?;
+            write_fields(fields, out)?;
+        }
+        DefinitionKind::UnitVariant
+        | DefinitionKind::TupleVariant
+        | DefinitionKind::StructVariant => {
+            write!(out, "{item}")?;
+            write_fields(fields, out)?;
+        }
+        DefinitionKind::Enum => {
+            write!(out, "enum {item}")?;
+        }
+        DefinitionKind::Function | DefinitionKind::AssociatedFunction => {
+            let signature = workspace_source
+                .unit()
+                .and_then(|unit| unit.debug_info())
+                .and_then(|debug_info| debug_info.functions.get(&hash));
+
+            match signature.map(|s| &s.args) {
+                Some(DebugArgs::Named(args)) => {
+                    write!(out, "fn {item}(")?;
+                    write_joined(args.iter().map(|a| a.as_ref()), out)?;
+                    out.try_push(')')?;
+                }
+                Some(DebugArgs::TupleArgs(n)) => {
+                    write!(out, "fn {item}(")?;
+                    write_numbered(*n, out)?;
+                    out.try_push(')')?;
+                }
+                Some(DebugArgs::EmptyArgs) => {
+                    write!(out, "fn {item}()")?;
+                }
+                None => {
+                    write!(out, "fn {item}(..)")?;
+                }
+            }
+        }
+        DefinitionKind::Const => {
+            write!(out, "const {item}")?;
+        }
+        DefinitionKind::Type => {
+            write!(out, "type {item}")?;
+        }
+        DefinitionKind::Module => {
+            write!(out, "mod {item}")?;
+        }
+        DefinitionKind::Local => {}
+    }
+
+    Ok(())
+}
+
+/// Write the fields of a struct or variant.
+fn write_fields(fields: Option<&meta::Fields>, out: &mut String) -> Result<()> {
+    match fields {
+        Some(meta::Fields::Named(named)) => {
+            let mut names = named.fields.iter().try_collect::<Vec<_>>()?;
+            names.sort_by_key(|(_, field)| field.position);
+
+            out.try_push_str(" { ")?;
+            write_joined(names.iter().map(|(name, _)| name.as_ref()), out)?;
+            out.try_push_str(" }")?;
+        }
+        Some(meta::Fields::Unnamed(n)) => {
+            out.try_push('(')?;
+            write_numbered(*n, out)?;
+            out.try_push(')')?;
+        }
+        Some(meta::Fields::Empty) | None => {}
+    }
+
+    Ok(())
+}
+
+/// Write documentation for a native item.
+fn write_native(context: &Context, meta: &ContextMeta, out: &mut String) -> Result<()> {
+    out.try_push_str("```rune\n")?;
+
+    let name = native_name(context, meta)?;
+
+    match &meta.kind {
+        meta::Kind::Function { signature, .. } => {
+            if signature.is_async {
+                out.try_push_str("async ")?;
+            }
+
+            write!(out, "fn {name}(")?;
+
+            let names = meta.docs.args().unwrap_or_default();
+            let count = signature.args.unwrap_or(names.len()).max(names.len());
+
+            for n in 0..count {
+                if n > 0 {
+                    out.try_push_str(", ")?;
+                }
+
+                match names.get(n) {
+                    Some(name) => out.try_push_str(name)?,
+                    None => write!(out, "_{n}")?,
+                }
+
+                let ty = signature
+                    .argument_types
+                    .get(n)
+                    .copied()
+                    .flatten()
+                    .and_then(|hash| type_name(context, hash));
+
+                if let Some(ty) = ty {
+                    write!(out, ": {ty}")?;
+                }
+            }
+
+            out.try_push(')')?;
+
+            if let Some(ty) = signature
+                .return_type
+                .and_then(|hash| type_name(context, hash))
+            {
+                write!(out, " -> {ty}")?;
+            }
+        }
+        meta::Kind::Struct { .. } | meta::Kind::Type { .. } => {
+            write!(out, "struct {name}")?;
+        }
+        meta::Kind::Enum { .. } => {
+            write!(out, "enum {name}")?;
+        }
+        meta::Kind::Variant { .. } => {
+            write!(out, "{name}")?;
+        }
+        meta::Kind::Const => {
+            write!(out, "const {name}")?;
+        }
+        meta::Kind::Module => {
+            write!(out, "mod {name}")?;
+        }
+        meta::Kind::Macro | meta::Kind::AttributeMacro => {
+            write!(out, "macro {name}")?;
+        }
+        _ => {
+            write!(out, "{name}")?;
+        }
+    }
+
+    out.try_push_str("\n```\n")?;
+    write_hash(meta.hash, out)?;
+
+    if let Some(deprecated) = &meta.deprecated {
+        write!(out, "\n\n**Deprecated:** {deprecated}")?;
+    }
+
+    write_docs(meta.docs.lines().iter().map(|s| s.as_str()), out)?;
+    Ok(())
+}
+
+/// Get the name of a native item.
+///
+/// Associated functions don't have an item, so they are named after their
+/// container instead.
+pub(super) fn native_name(context: &Context, meta: &ContextMeta) -> Result<String> {
+    let name = match (&meta.item, &meta.kind) {
+        (Some(item), _) => item.try_to_string()?,
+        (
+            None,
+            meta::Kind::Function {
+                associated: Some(associated),
+                container,
+                ..
+            },
+        ) => {
+            let container = container
+                .and_then(|hash| context.lookup_meta_by_hash(hash).next())
+                .and_then(|meta| meta.item.as_deref());
+
+            match container {
+                Some(container) => try_format!("{container}::{associated}"),
+                None => associated.try_to_string()?,
+            }
+        }
+        (None, _) => meta.hash.try_to_string()?,
+    };
+
+    Ok(name)
+}
+
+/// Look up the name of a type by its hash.
+pub(super) fn type_name(context: &Context, hash: Hash) -> Option<&Item> {
+    context.lookup_meta_by_hash(hash).next()?.item.as_deref()
+}
+
+/// Write the type hash line.
+fn write_hash(hash: Hash, out: &mut String) -> Result<()> {
+    write!(out, "Type hash: `{hash}`")?;
+    Ok(())
+}
+
+/// Write documentation lines, separated from the header by a rule.
+fn write_docs<'a>(lines: impl IntoIterator<Item = &'a str>, out: &mut String) -> Result<()> {
+    let mut lines = lines.into_iter().peekable();
+
+    if lines.peek().is_none() {
+        return Ok(());
+    }
+
+    out.try_push_str("\n\n---\n\n")?;
+
+    for line in lines {
+        // Doc comments are captured verbatim, which includes the leading
+        // space after `///`.
+        out.try_push_str(line.strip_prefix(' ').unwrap_or(line))?;
+        out.try_push('\n')?;
+    }
+
+    Ok(())
+}
+
+fn write_joined<'a>(items: impl IntoIterator<Item = &'a str>, out: &mut String) -> Result<()> {
+    let mut it = items.into_iter();
+
+    if let Some(first) = it.next() {
+        out.try_push_str(first)?;
+    }
+
+    for item in it {
+        out.try_push_str(", ")?;
+        out.try_push_str(item)?;
+    }
+
+    Ok(())
+}
+
+fn write_numbered(n: usize, out: &mut String) -> Result<()> {
+    for n in 0..n {
+        if n > 0 {
+            out.try_push_str(", ")?;
+        }
+
+        write!(out, "_{n}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rune;
+    use crate::languageserver::testing::{position, range, url, Harness};
+    use crate::support::Result;
+    use crate::{Context, Hash, Module};
+
+    /// Greet the given name.
+    #[rune::function]
+    fn greet(name: &str) -> i64 {
+        name.len() as i64
+    }
+
+    const SOURCE: &str = r#"
+/// Add two numbers together.
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    let total = add(1, 2);
+    greet("world") + total
+}
+"#;
+
+    #[test]
+    fn test_hover() -> Result<()> {
+        let mut module = Module::new();
+        module.function_meta(greet)?;
+
+        let mut context = Context::with_default_modules()?;
+        context.install(module)?;
+
+        let harness = Harness::new()?;
+        let mut state = harness.state(context);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        let mut hover = |needle: &str, n: usize| {
+            let params = lsp::HoverParams {
+                text_document_position_params: lsp::TextDocumentPositionParams {
+                    text_document: lsp::TextDocumentIdentifier { uri: main.clone() },
+                    position: position(SOURCE, needle, n),
+                },
+                work_done_progress_params: Default::default(),
+            };
+
+            let hover = harness
+                .block_on(super::super::hover(&mut state, params))?
+                .expect("expected hover");
+
+            let lsp::HoverContents::Markup(contents) = hover.contents else {
+                panic!("expected markup contents");
+            };
+
+            assert_eq!(hover.range, Some(range(SOURCE, needle, n)));
+            Ok::<_, crate::support::Error>(contents.value)
+        };
+
+        assert_eq!(hover("total", 1)?, "```rune\nlet total\n```");
+
+        let add = Hash::type_hash(["add"]);
+
+        assert_eq!(
+            hover("add", 1)?,
+            format!(
+                "```rune\nfn add(a, b)\n```\nType hash: `{add}`\n\n---\n\nAdd two numbers together.\n"
+            )
+        );
+
+        let greet = Hash::type_hash(["greet"]);
+
+        assert_eq!(
+            hover("greet", 0)?,
+            format!(
+                "```rune\nfn greet(name: ::std::string::String) -> ::std::i64\n```\nType hash: `{greet}`\n\n---\n\nGreet the given name.\n"
+            )
+        );
+
+        Ok(())
+    }
+}
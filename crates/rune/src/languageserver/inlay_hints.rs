@@ -0,0 +1,522 @@
+use anyhow::Result;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{String, Vec};
+use crate::ast::{self, Span};
+use crate::compile::meta;
+use crate::parse::Lexer;
+use crate::runtime::static_type;
+use crate::{Context, Hash, SourceId};
+
+use super::references::item_name;
+use super::signature_help::{self, Call};
+use super::state::{DefinitionKind, Source};
+
+/// Compute inlay hints for the given byte range of a source.
+///
+/// This shows the names of parameters at call sites of functions taking more
+/// than one argument, and the types of `let` bindings whose value has a type
+/// which is known statically.
+pub(super) fn inlay_hints(
+    context: &Context,
+    source: &Source,
+    range: Span,
+) -> Result<Vec<lsp::InlayHint>> {
+    let text = source.try_to_string()?;
+    let mut lexer = Lexer::new(&text, SourceId::empty(), true);
+    let mut tokens = Vec::new();
+
+    // Lexing errors are reported as diagnostics, so we just provide hints for
+    // what we can up until that point.
+    while let Ok(Some(token)) = lexer.next() {
+        if !matches!(
+            token.kind,
+            ast::Kind::Whitespace | ast::Kind::Comment | ast::Kind::MultilineComment(..)
+        ) {
+            tokens.try_push(token)?;
+        }
+    }
+
+    let mut hints = Vec::new();
+
+    for (n, token) in tokens.iter().enumerate() {
+        if token.span.start < range.start || token.span.end > range.end {
+            continue;
+        }
+
+        match token.kind {
+            ast::Kind::Open(ast::Delimiter::Parenthesis) => {
+                parameter_hints(context, source, &text, &tokens, n, &mut hints)?;
+            }
+            ast::Kind::Let => {
+                type_hint(context, source, &text, &tokens, n, &mut hints)?;
+            }
+            _ => {}
+        }
+    }
+
+    let mut output = Vec::new();
+
+    for (offset, label, kind) in hints {
+        let Some(position) = source.byte_to_lsp_position(offset) else {
+            continue;
+        };
+
+        let parameter = kind == lsp::InlayHintKind::PARAMETER;
+
+        output.try_push(lsp::InlayHint {
+            position,
+            label: lsp::InlayHintLabel::String(label.into_std()),
+            kind: Some(kind),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: Some(parameter),
+            data: None,
+        })?;
+    }
+
+    Ok(output)
+}
+
+/// Add hints for the names of the arguments in a call, whose argument list is
+/// opened by the token at `open`.
+fn parameter_hints(
+    context: &Context,
+    source: &Source,
+    text: &str,
+    tokens: &[ast::Token],
+    open: usize,
+    hints: &mut Vec<(usize, String, lsp::InlayHintKind)>,
+) -> Result<()> {
+    // Declarations like `fn foo(a, b)` look like calls.
+    if let [.., ast::Token {
+        kind: ast::Kind::Fn,
+        ..
+    }, _] = &tokens[..open]
+    {
+        return Ok(());
+    }
+
+    let Some(call) = signature_help::call_path(text, &tokens[..open], 0)? else {
+        return Ok(());
+    };
+
+    let Some((arguments, _)) = arguments(tokens, open)? else {
+        return Ok(());
+    };
+
+    if arguments.len() < 2 {
+        return Ok(());
+    }
+
+    // We can't tell which function is being called if there are several
+    // candidates, like instance functions with the same name.
+    let [signature] = &signature_help::signatures(context, source, &call)?[..] else {
+        return Ok(());
+    };
+
+    let skip = usize::from(call.instance && signature.receiver);
+
+    for (argument, name) in arguments.iter().zip(signature.names.iter().skip(skip)) {
+        // Unnamed parameters are rendered as `_0`, `_1`, and so forth.
+        if name.starts_with('_') || text.get(argument.range()) == Some(name.as_str()) {
+            continue;
+        }
+
+        hints.try_push((
+            argument.start.into_usize(),
+            try_format!("{name}:"),
+            lsp::InlayHintKind::PARAMETER,
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Add a hint for the type of a `let` binding starting at the token at `at`,
+/// if it can be determined.
+fn type_hint(
+    context: &Context,
+    source: &Source,
+    text: &str,
+    tokens: &[ast::Token],
+    at: usize,
+    hints: &mut Vec<(usize, String, lsp::InlayHintKind)>,
+) -> Result<()> {
+    let [_, name, ast::Token {
+        kind: ast::Kind::Eq,
+        ..
+    }, rest @ ..] = &tokens[at..]
+    else {
+        return Ok(());
+    };
+
+    if !matches!(name.kind, ast::Kind::Ident(..)) {
+        return Ok(());
+    }
+
+    let mut depth = 0usize;
+    let mut end = rest.len();
+
+    for (n, token) in rest.iter().enumerate() {
+        match token.kind {
+            ast::Kind::Open(..) => depth += 1,
+            ast::Kind::Close(..) if depth == 0 => {
+                end = n;
+                break;
+            }
+            ast::Kind::Close(..) => depth -= 1,
+            ast::Kind::SemiColon if depth == 0 => {
+                end = n;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let Some(ty) = expr_type(context, source, text, &rest[..end])? else {
+        return Ok(());
+    };
+
+    hints.try_push((
+        name.span.end.into_usize(),
+        try_format!(": {ty}"),
+        lsp::InlayHintKind::TYPE,
+    ))?;
+
+    Ok(())
+}
+
+/// Determine the type of the expression made up of the given tokens.
+fn expr_type(
+    context: &Context,
+    source: &Source,
+    text: &str,
+    tokens: &[ast::Token],
+) -> Result<Option<String>> {
+    let hash = match tokens {
+        [token] => match token.kind {
+            ast::Kind::Number(ast::NumberSource::Text(number)) => {
+                match text.get(number.suffix.range()) {
+                    Some(suffix) if !suffix.is_empty() => {
+                        return Ok(Some(suffix.try_to_owned()?));
+                    }
+                    _ if number.is_fractional => static_type::FLOAT_TYPE.hash,
+                    _ => static_type::INTEGER_TYPE.hash,
+                }
+            }
+            ast::Kind::Str(..) => static_type::STRING_TYPE.hash,
+            ast::Kind::ByteStr(..) => static_type::BYTES_TYPE.hash,
+            ast::Kind::Char(..) => static_type::CHAR_TYPE.hash,
+            ast::Kind::Byte(..) => static_type::BYTE_TYPE.hash,
+            ast::Kind::True | ast::Kind::False => static_type::BOOL_TYPE.hash,
+            _ => return path_type(context, source, text, tokens, None),
+        },
+        [ast::Token {
+            kind: ast::Kind::Open(ast::Delimiter::Bracket),
+            ..
+        }, ..]
+            if is_closed_by_last(tokens, 0)? =>
+        {
+            static_type::VEC_TYPE.hash
+        }
+        [ast::Token {
+            kind: ast::Kind::Pound,
+            ..
+        }, ast::Token {
+            kind: ast::Kind::Open(ast::Delimiter::Brace),
+            ..
+        }, ..]
+            if is_closed_by_last(tokens, 1)? =>
+        {
+            static_type::OBJECT_TYPE.hash
+        }
+        [ast::Token {
+            kind: ast::Kind::Open(ast::Delimiter::Parenthesis),
+            ..
+        }, ..] => match arguments(tokens, 0)? {
+            Some((arguments, close)) if close + 1 == tokens.len() && arguments.len() > 1 => {
+                static_type::TUPLE_TYPE.hash
+            }
+            _ => return Ok(None),
+        },
+        _ => {
+            // A path followed by the arguments of a call or the fields of a
+            // struct constructor.
+            let Some(open) = tokens
+                .iter()
+                .position(|t| matches!(t.kind, ast::Kind::Open(..)))
+            else {
+                return path_type(context, source, text, tokens, None);
+            };
+
+            let ast::Kind::Open(delimiter) = tokens[open].kind else {
+                return Ok(None);
+            };
+
+            if delimiter == ast::Delimiter::Bracket || !is_closed_by_last(tokens, open)? {
+                return Ok(None);
+            }
+
+            return path_type(context, source, text, &tokens[..open], Some(delimiter));
+        }
+    };
+
+    type_name(context, hash)
+}
+
+/// Determine the type produced by a path, which is optionally followed by the
+/// arguments of a call or the fields of a struct constructor.
+fn path_type(
+    context: &Context,
+    source: &Source,
+    text: &str,
+    tokens: &[ast::Token],
+    delimiter: Option<ast::Delimiter>,
+) -> Result<Option<String>> {
+    let Some(call) = signature_help::call_path(text, tokens, 0)? else {
+        return Ok(None);
+    };
+
+    // Paths must make up the whole expression.
+    if call.instance || call.path.len() + leading_keywords(tokens) != tokens.len().div_ceil(2) {
+        return Ok(None);
+    }
+
+    let Some(&name) = call.path.last() else {
+        return Ok(None);
+    };
+
+    let definition = source
+        .find_definition_at(Span::point(call.span.start))
+        .filter(|(_, def)| def.item.as_deref().and_then(item_name) == Some(name));
+
+    let Some((_, definition)) = definition else {
+        if delimiter != Some(ast::Delimiter::Parenthesis) {
+            return Ok(None);
+        }
+
+        // The source might not build, so fall back to looking up native
+        // functions by name.
+        return native_return_type(context, &call);
+    };
+
+    let Some(item) = definition.item.as_deref() else {
+        return Ok(None);
+    };
+
+    let ty = match (definition.kind, delimiter) {
+        (DefinitionKind::EmptyStruct, None)
+        | (DefinitionKind::TupleStruct, Some(ast::Delimiter::Parenthesis))
+        | (DefinitionKind::Struct, Some(ast::Delimiter::Brace)) => item,
+        (DefinitionKind::UnitVariant, None)
+        | (DefinitionKind::TupleVariant, Some(ast::Delimiter::Parenthesis))
+        | (DefinitionKind::StructVariant, Some(ast::Delimiter::Brace)) => {
+            let Some(parent) = item.parent() else {
+                return Ok(None);
+            };
+
+            parent
+        }
+        (
+            DefinitionKind::Function | DefinitionKind::AssociatedFunction,
+            Some(ast::Delimiter::Parenthesis),
+        ) if definition.is_context() => {
+            let Some(hash) = definition.hash else {
+                return Ok(None);
+            };
+
+            let Some(meta) = context.lookup_meta_by_hash(hash).next() else {
+                return Ok(None);
+            };
+
+            return return_type(context, meta);
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(item_name(ty).map(str::try_to_owned).transpose()?)
+}
+
+/// Look up the return type of a native function by the path used to call it.
+fn native_return_type(context: &Context, call: &Call<'_>) -> Result<Option<String>> {
+    let Some(meta) = signature_help::lookup_native(context, &call.path)? else {
+        return Ok(None);
+    };
+
+    return_type(context, meta)
+}
+
+/// Get the declared return type of a native function.
+fn return_type(
+    context: &Context,
+    meta: &crate::compile::context::ContextMeta,
+) -> Result<Option<String>> {
+    let meta::Kind::Function { signature, .. } = &meta.kind else {
+        return Ok(None);
+    };
+
+    let Some(hash) = signature.return_type else {
+        return Ok(None);
+    };
+
+    type_name(context, hash)
+}
+
+/// Get the short name of the type with the given hash.
+fn type_name(context: &Context, hash: Hash) -> Result<Option<String>> {
+    let Some(item) = super::hover::type_name(context, hash) else {
+        return Ok(None);
+    };
+
+    Ok(item_name(item).map(str::try_to_owned).transpose()?)
+}
+
+/// Count the keywords like `crate` or `super` which are part of a path, but
+/// not of the components it's resolved through.
+fn leading_keywords(tokens: &[ast::Token]) -> usize {
+    tokens
+        .iter()
+        .filter(|t| {
+            matches!(
+                t.kind,
+                ast::Kind::Crate | ast::Kind::Super | ast::Kind::SelfValue
+            )
+        })
+        .count()
+}
+
+/// Test if the delimiter opened by the token at `open` is closed by the last
+/// token.
+fn is_closed_by_last(tokens: &[ast::Token], open: usize) -> Result<bool> {
+    Ok(matches!(arguments(tokens, open)?, Some((_, close)) if close + 1 == tokens.len()))
+}
+
+/// Find the spans of the comma-separated arguments inside of the delimiter
+/// opened by the token at `open`, and the index of the token closing it.
+fn arguments(tokens: &[ast::Token], open: usize) -> Result<Option<(Vec<Span>, usize)>> {
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None::<Span>;
+    let mut end = None::<Span>;
+
+    for (n, token) in tokens.iter().enumerate().skip(open + 1) {
+        match token.kind {
+            ast::Kind::Close(..) if depth == 0 => {
+                if let (Some(start), Some(end)) = (start, end) {
+                    arguments.try_push(start.join(end))?;
+                }
+
+                return Ok(Some((arguments, n)));
+            }
+            ast::Kind::Comma if depth == 0 => {
+                if let (Some(start), Some(end)) = (start.take(), end.take()) {
+                    arguments.try_push(start.join(end))?;
+                }
+
+                continue;
+            }
+            ast::Kind::Open(..) => depth += 1,
+            ast::Kind::Close(..) => depth -= 1,
+            _ => {}
+        }
+
+        start.get_or_insert(token.span);
+        end = Some(token.span);
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rust_alloc::string::String;
+    use ::rust_alloc::vec::Vec;
+
+    use crate::languageserver::testing::{position, range, url, Harness};
+    use crate::support::Result;
+    use crate::Context;
+
+    const SOURCE: &str = r#"
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    let a = 1;
+    let name = "rune";
+    let total = add(a, 2);
+    let values = [1, 2];
+    total
+}
+"#;
+
+    #[test]
+    fn test_inlay_hints() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        let mut inlay_hints = |range: lsp::Range| {
+            let params = lsp::InlayHintParams {
+                text_document: lsp::TextDocumentIdentifier { uri: main.clone() },
+                range,
+                work_done_progress_params: Default::default(),
+            };
+
+            let hints = harness
+                .block_on(super::super::inlay_hint(&mut state, params))?
+                .expect("expected inlay hints");
+
+            let mut output = Vec::new();
+
+            for hint in hints {
+                let lsp::InlayHintLabel::String(label) = hint.label else {
+                    panic!("expected string label");
+                };
+
+                output.push((hint.position, label, hint.kind));
+            }
+
+            Ok::<_, crate::support::Error>(output)
+        };
+
+        let all = lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(u32::MAX, 0));
+
+        let ty = |needle: &str, label: &str| {
+            let position = range(SOURCE, needle, 0).end;
+            (
+                position,
+                String::from(label),
+                Some(lsp::InlayHintKind::TYPE),
+            )
+        };
+
+        let b = (
+            position(SOURCE, "2);", 0),
+            String::from("b:"),
+            Some(lsp::InlayHintKind::PARAMETER),
+        );
+
+        // The first argument is named like the parameter, so it has no hint,
+        // and the type of calls to script functions isn't known.
+        assert_eq!(
+            inlay_hints(all)?,
+            [
+                ty("let a", ": i64"),
+                ty("let name", ": String"),
+                b.clone(),
+                ty("let values", ": Vec"),
+            ]
+        );
+
+        assert_eq!(
+            inlay_hints(range(SOURCE, "let total = add(a, 2);", 0))?,
+            [b]
+        );
+        assert!(inlay_hints(range(SOURCE, "total\n}", 0))?.is_empty());
+        Ok(())
+    }
+}
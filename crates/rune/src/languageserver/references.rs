@@ -0,0 +1,582 @@
+use core::mem;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use lsp::Url;
+
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::alloc::{self, Box, HashMap, Vec};
+use crate::ast::{self, Span};
+use crate::compile::{ComponentRef, Item};
+use crate::parse::Lexer;
+use crate::{Hash, SourceId};
+
+use super::state::{DefinitionKind, Index};
+
+/// Something which can be referenced from several places in a workspace.
+#[derive(Debug, TryClone, PartialEq, Eq, Hash)]
+pub(super) enum Target {
+    /// A local variable, identified by the span it is declared at.
+    Local(
+        #[try_clone(with = Clone::clone)] Url,
+        #[try_clone(copy)] Span,
+    ),
+    /// An item, identified by its hash.
+    Item(#[try_clone(copy)] Hash),
+    /// A named field of the struct or variant with the given hash.
+    Field(#[try_clone(copy)] Hash, Box<str>),
+}
+
+/// A single place where a target is referenced.
+#[derive(Debug)]
+pub(super) struct Reference {
+    /// The range of the name being referenced.
+    pub(super) range: lsp::Range,
+    /// If the reference is the declaration of the target.
+    pub(super) declaration: bool,
+}
+
+/// A reverse index from targets to every place they are referenced, covering
+/// every source in the workspace.
+#[derive(Default)]
+pub(super) struct References {
+    /// Targets mapped to the locations where they are referenced.
+    targets: HashMap<Target, BTreeMap<(Url, Span), Reference>>,
+    /// The targets referenced in each source, by the span of the name.
+    by_url: HashMap<Url, BTreeMap<Span, Vec<Target>>>,
+}
+
+impl References {
+    /// Index all references found while building the given sources.
+    pub(super) fn index(
+        &mut self,
+        sources: &crate::Sources,
+        id_to_url: &HashMap<SourceId, Url>,
+        indexes: &HashMap<SourceId, Arc<Index>>,
+    ) -> Result<()> {
+        for (source_id, index) in indexes {
+            let (Some(url), Some(source)) = (id_to_url.get(source_id), sources.get(*source_id))
+            else {
+                continue;
+            };
+
+            let idents = Idents::lex(source.as_str())?;
+
+            for (span, definition) in &index.definitions {
+                match definition.kind {
+                    DefinitionKind::Local => {
+                        let Some(used) = idents.leading(*span) else {
+                            continue;
+                        };
+
+                        let Some(name) = idents.name(used) else {
+                            continue;
+                        };
+
+                        let Some(declared) = idents.binding(definition.source.span(), name) else {
+                            continue;
+                        };
+
+                        let target = Target::Local(url.clone(), declared);
+                        self.insert(source, url, declared, true, &target)?;
+                        self.insert(source, url, used, false, &target)?;
+                    }
+                    DefinitionKind::Module => {}
+                    _ => {
+                        let (Some(hash), Some(name)) = (
+                            definition.hash,
+                            definition.item.as_deref().and_then(item_name),
+                        ) else {
+                            continue;
+                        };
+
+                        if let Some(span) = idents.find_last(*span, name) {
+                            self.insert(source, url, span, false, &Target::Item(hash))?;
+                        }
+                    }
+                }
+            }
+
+            for (span, field) in &index.fields {
+                let target = Target::Field(field.hash, field.name.try_clone()?);
+
+                if let Some(span) = idents.find(*span, &field.name) {
+                    self.insert(source, url, span, false, &target)?;
+                }
+            }
+
+            for declaration in &index.declarations {
+                // Modules can't be renamed since their uses aren't indexed.
+                if let DefinitionKind::Module = declaration.kind {
+                    continue;
+                }
+
+                let Some(name) = item_name(&declaration.item) else {
+                    continue;
+                };
+
+                let Some(name_span) = idents.find(declaration.span, name) else {
+                    continue;
+                };
+
+                self.insert(
+                    source,
+                    url,
+                    name_span,
+                    true,
+                    &Target::Item(declaration.hash),
+                )?;
+
+                // Fields are declared in order after the name of the item.
+                let mut rest = Span::new(name_span.end, declaration.span.end);
+
+                for field in &declaration.fields {
+                    let Some(span) = idents.find(rest, field) else {
+                        break;
+                    };
+
+                    let target = Target::Field(declaration.hash, field.try_clone()?);
+                    self.insert(source, url, span, true, &target)?;
+                    rest = Span::new(span.end, declaration.span.end);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the target referenced at the given byte offset in the given
+    /// source.
+    ///
+    /// If multiple targets are referenced at the same location, such as with
+    /// shorthand fields like `Point { x }`, locals are preferred.
+    pub(super) fn target_at(&self, url: &Url, offset: usize) -> Option<(Span, &Target)> {
+        let spans = self.by_url.get(url)?;
+
+        let (span, targets) =
+            spans
+                .range(..=Span::new(offset, u32::MAX))
+                .rev()
+                .find(|(span, _)| {
+                    span.start.into_usize() <= offset && offset <= span.end.into_usize()
+                })?;
+
+        let target = targets
+            .iter()
+            .find(|target| matches!(target, Target::Local(..)))
+            .or_else(|| targets.first())?;
+
+        Some((*span, target))
+    }
+
+    /// Iterate over all references to the given target.
+    pub(super) fn references(
+        &self,
+        target: &Target,
+    ) -> impl Iterator<Item = (&Url, Span, &Reference)> + '_ {
+        self.targets
+            .get(target)
+            .into_iter()
+            .flatten()
+            .map(|((url, span), reference)| (url, *span, reference))
+    }
+
+    /// Test if the given target can be renamed.
+    ///
+    /// Only targets declared in the workspace can be renamed, which excludes
+    /// items provided by the native context.
+    pub(super) fn is_renamable(&self, target: &Target) -> bool {
+        self.references(target).any(|(_, _, r)| r.declaration)
+    }
+
+    /// Get the name of the field if the reference to the given target at the
+    /// given location is a shorthand field like `Point { x }`, which names
+    /// both a field and a local.
+    pub(super) fn shorthand_field<'a>(
+        &'a self,
+        url: &Url,
+        span: Span,
+        target: &'a Target,
+    ) -> Option<&'a str> {
+        let targets = self.by_url.get(url)?.get(&span)?;
+
+        targets.iter().find_map(|other| match (target, other) {
+            (Target::Local(..), Target::Field(_, name)) => Some(name.as_ref()),
+            (Target::Field(_, name), Target::Local(..)) => Some(name.as_ref()),
+            _ => None,
+        })
+    }
+
+    fn insert(
+        &mut self,
+        source: &crate::Source,
+        url: &Url,
+        span: Span,
+        declaration: bool,
+        target: &Target,
+    ) -> alloc::Result<()> {
+        let Some(range) = super::state::span_to_lsp_range(source, span) else {
+            return Ok(());
+        };
+
+        let references = self.targets.entry(target.try_clone()?).or_try_default()?;
+        references.insert((url.clone(), span), Reference { range, declaration });
+
+        let spans = self.by_url.entry(url.clone()).or_try_default()?;
+        let targets = spans.entry(span).or_default();
+
+        if !targets.contains(target) {
+            targets.try_push(target.try_clone()?)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Test if the given string is a valid identifier to rename something to.
+pub(super) fn is_valid_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    (first == '_' || first.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphanumeric())
+        && ast::Kind::from_keyword(name).is_none()
+}
+
+/// Get the name of the last component of the item.
+pub(super) fn item_name(item: &Item) -> Option<&str> {
+    match item.last()? {
+        ComponentRef::Str(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// The tokens lexed from a source, used to find identifiers by name without
+/// matching inside of comments or string literals.
+pub(super) struct Idents<'a> {
+    text: &'a str,
+    tokens: Vec<ast::Token>,
+}
+
+impl<'a> Idents<'a> {
+    /// Lex the given text.
+    pub(super) fn lex(text: &'a str) -> alloc::Result<Self> {
+        let mut lexer = Lexer::new(text, SourceId::empty(), true);
+        let mut tokens = Vec::new();
+
+        // Lexing errors are reported as diagnostics, so we just find what we
+        // can up until that point.
+        while let Ok(Some(token)) = lexer.next() {
+            if matches!(
+                token.kind,
+                ast::Kind::Whitespace | ast::Kind::Comment | ast::Kind::MultilineComment(..)
+            ) {
+                continue;
+            }
+
+            tokens.try_push(token)?;
+        }
+
+        Ok(Self { text, tokens })
+    }
+
+    /// Get the text of the given identifier.
+    pub(super) fn name(&self, span: Span) -> Option<&'a str> {
+        self.text.get(span.range())
+    }
+
+    /// Find the span of the first identifier `name` inside of the given span.
+    pub(super) fn find(&self, span: Span, name: &str) -> Option<Span> {
+        self.within(span)
+            .map(|(_, token)| token)
+            .find(|token| self.is_ident(token, name))
+            .map(|token| token.span)
+    }
+
+    /// Find the span of the last identifier `name` inside of the given span.
+    pub(super) fn find_last(&self, span: Span, name: &str) -> Option<Span> {
+        self.within(span)
+            .map(|(_, token)| token)
+            .rfind(|token| self.is_ident(token, name))
+            .map(|token| token.span)
+    }
+
+    /// Find the span of the first token of the given kind inside of the given
+    /// span.
+    pub(super) fn find_kind(&self, span: Span, kind: ast::Kind) -> Option<Span> {
+        self.within(span)
+            .map(|(_, token)| token)
+            .find(|token| token.kind == kind)
+            .map(|token| token.span)
+    }
+
+    /// Get the identifier a variable is used through at the given span.
+    ///
+    /// Variables which are called like `f(1)` are used with the span of the
+    /// whole call, so this picks out the leading token if it's an identifier.
+    pub(super) fn leading(&self, span: Span) -> Option<Span> {
+        let (_, token) = self.within(span).next()?;
+
+        match token.kind {
+            ast::Kind::Ident(ast::LitSource::Text(..)) => Some(token.span),
+            _ => None,
+        }
+    }
+
+    /// Find the span of the binding of the local `name` inside of the given
+    /// span.
+    ///
+    /// Variables are declared with the span of whatever introduced them, like
+    /// a whole function for arguments, so identifiers naming items or path
+    /// segments like in `fn name(..)` or `Some(name)` are skipped.
+    pub(super) fn binding(&self, span: Span, name: &str) -> Option<Span> {
+        self.within(span)
+            .find(|&(n, token)| {
+                if !self.is_ident(token, name) {
+                    return false;
+                }
+
+                let before = n
+                    .checked_sub(1)
+                    .and_then(|n| self.tokens.get(n))
+                    .map(|token| token.kind);
+                let after = self.tokens.get(n + 1).map(|token| token.kind);
+
+                !matches!(
+                    before,
+                    Some(K![fn] | K![struct] | K![enum] | K![const] | K![mod] | K![.] | K![::])
+                ) && !matches!(after, Some(K![::] | K!['('] | K!['{'] | K![!]))
+            })
+            .map(|(_, token)| token.span)
+    }
+
+    /// Iterate over the spans of identifiers used to call macros, like
+    /// `println` in `println!("Hello")`.
+    pub(super) fn macro_calls(&self) -> impl Iterator<Item = Span> + '_ {
+        self.tokens.windows(3).filter_map(|window| match window {
+            [ident, bang, open]
+                if matches!(
+                    (ident.kind, bang.kind, open.kind),
+                    (
+                        ast::Kind::Ident(ast::LitSource::Text(..)),
+                        K![!],
+                        ast::Kind::Open(..)
+                    )
+                ) =>
+            {
+                Some(ident.span)
+            }
+            _ => None,
+        })
+    }
+
+    /// Map spans of tokens in this text to the spans of the same tokens in
+    /// `to`, which is an edited version of this text.
+    ///
+    /// Tokens are matched from the start and the end of both texts up until
+    /// the first token which differs, so tokens inside of the edited region
+    /// can't be mapped.
+    pub(super) fn map_to<'b>(&'b self, to: &'b Idents<'b>) -> TokenMap<'b> {
+        // Kinds like numbers carry spans, so only their discriminant is
+        // compared alongside the text of the token.
+        let eq = |a: &ast::Token, b: &ast::Token| {
+            mem::discriminant(&a.kind) == mem::discriminant(&b.kind)
+                && self.text.get(a.span.range()) == to.text.get(b.span.range())
+        };
+
+        let prefix = self
+            .tokens
+            .iter()
+            .zip(&to.tokens)
+            .take_while(|(a, b)| eq(a, b))
+            .count();
+
+        let suffix = self
+            .tokens
+            .iter()
+            .rev()
+            .zip(to.tokens.iter().rev())
+            .take(self.tokens.len().min(to.tokens.len()) - prefix)
+            .take_while(|(a, b)| eq(a, b))
+            .count();
+
+        TokenMap {
+            from: &self.tokens,
+            to: &to.tokens,
+            prefix,
+            suffix,
+        }
+    }
+
+    /// Iterate over the tokens which are entirely inside of the given span,
+    /// together with their index.
+    fn within(&self, span: Span) -> impl DoubleEndedIterator<Item = (usize, &ast::Token)> {
+        let start = self.tokens.partition_point(|t| t.span.start < span.start);
+        let end = self.tokens.partition_point(|t| t.span.start < span.end);
+
+        self.tokens[start..end]
+            .iter()
+            .enumerate()
+            .map(move |(n, token)| (start + n, token))
+            .filter(move |(_, token)| token.span.end <= span.end)
+    }
+
+    fn is_ident(&self, token: &ast::Token, name: &str) -> bool {
+        matches!(token.kind, ast::Kind::Ident(ast::LitSource::Text(..)))
+            && self.text.get(token.span.range()) == Some(name)
+    }
+}
+
+/// A mapping of token spans between two versions of a text, constructed with
+/// [`Idents::map_to`].
+pub(super) struct TokenMap<'a> {
+    from: &'a [ast::Token],
+    to: &'a [ast::Token],
+    prefix: usize,
+    suffix: usize,
+}
+
+impl TokenMap<'_> {
+    /// Map the span of a token to the span of the same token in the edited
+    /// text.
+    pub(super) fn map(&self, span: Span) -> Option<Span> {
+        let start = self.from.partition_point(|t| t.span.start < span.start);
+
+        let n = start
+            + self.from[start..]
+                .iter()
+                .take_while(|t| t.span.start == span.start)
+                .position(|t| t.span == span)?;
+
+        if n < self.prefix {
+            return Some(self.to.get(n)?.span);
+        }
+
+        let from_end = self.from.len() - n;
+
+        if from_end <= self.suffix {
+            return Some(self.to.get(self.to.len() - from_end)?.span);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::languageserver::envelope::{Code, RequestError};
+    use crate::languageserver::testing::{position, range, url, Harness};
+    use crate::support::Result;
+    use crate::Context;
+
+    const SOURCE: &str = r#"struct Point { x, y }
+
+/// Make a point out of `value`.
+fn make(value) {
+    // The value becomes the x coordinate.
+    let label = "value";
+    let message = `value is {label}`;
+    /* value */
+    Point { x: value, y: message }
+}
+
+pub fn main() {
+    // make a point.
+    let point = make(1);
+    point.x
+}
+"#;
+
+    #[test]
+    fn test_references() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        let mut references = |needle: &str, n: usize| {
+            let params = lsp::ReferenceParams {
+                text_document_position: lsp::TextDocumentPositionParams {
+                    text_document: lsp::TextDocumentIdentifier { uri: main.clone() },
+                    position: position(SOURCE, needle, n),
+                },
+                context: lsp::ReferenceContext {
+                    include_declaration: true,
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            };
+
+            let locations = harness
+                .block_on(super::super::references(&mut state, params))?
+                .expect("expected references");
+
+            let mut ranges = ::rust_alloc::vec::Vec::new();
+
+            for location in locations {
+                assert_eq!(location.uri, main);
+                ranges.push(location.range);
+            }
+
+            Ok::<_, crate::support::Error>(ranges)
+        };
+
+        // Mentions in comments, string literals and template text are skipped.
+        assert_eq!(
+            references("value", 6)?,
+            [range(SOURCE, "value", 1), range(SOURCE, "value", 6)]
+        );
+
+        assert_eq!(
+            references("make", 2)?,
+            [range(SOURCE, "make", 0), range(SOURCE, "make", 2)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        let mut rename = |needle: &str, n: usize, new_name: &str| {
+            let params = lsp::RenameParams {
+                text_document_position: lsp::TextDocumentPositionParams {
+                    text_document: lsp::TextDocumentIdentifier { uri: main.clone() },
+                    position: position(SOURCE, needle, n),
+                },
+                new_name: new_name.into(),
+                work_done_progress_params: Default::default(),
+            };
+
+            harness.block_on(super::super::rename(&mut state, params))
+        };
+
+        let edit = rename("value", 1, "input")?.expect("expected edit");
+        let mut edits = edit.changes.expect("expected changes").remove(&main);
+
+        if let Some(edits) = &mut edits {
+            edits.sort_by_key(|edit| edit.range.start);
+        }
+
+        let expected =
+            [1, 6].map(|n| lsp::TextEdit::new(range(SOURCE, "value", n), "input".into()));
+        assert_eq!(edits.as_deref(), Some(&expected[..]));
+
+        let error = rename("value", 1, "fn").expect_err("expected error");
+        let error = error
+            .downcast_ref::<RequestError>()
+            .expect("expected request error");
+        assert_eq!(error.code, Code::InvalidParams);
+        Ok(())
+    }
+}
@@ -0,0 +1,557 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{HashMap, Vec};
+use crate::ast::{self, Span};
+use crate::compile::Item;
+
+use super::references::{item_name, Idents};
+use super::state::{Definition, DefinitionKind, Index, Source};
+
+/// The types of tokens we classify, in the order they appear in the legend.
+#[derive(Debug, Clone, Copy)]
+enum TokenType {
+    Namespace,
+    Type,
+    Struct,
+    Enum,
+    EnumMember,
+    Function,
+    NativeFunction,
+    Macro,
+    Variable,
+    Parameter,
+    Property,
+}
+
+/// A function provided by the native context, which editors fall back to
+/// highlighting as a function.
+const NATIVE_FUNCTION: lsp::SemanticTokenType = lsp::SemanticTokenType::new("nativeFunction");
+
+const TOKEN_TYPES: [lsp::SemanticTokenType; 11] = [
+    lsp::SemanticTokenType::NAMESPACE,
+    lsp::SemanticTokenType::TYPE,
+    lsp::SemanticTokenType::STRUCT,
+    lsp::SemanticTokenType::ENUM,
+    lsp::SemanticTokenType::ENUM_MEMBER,
+    lsp::SemanticTokenType::FUNCTION,
+    NATIVE_FUNCTION,
+    lsp::SemanticTokenType::MACRO,
+    lsp::SemanticTokenType::VARIABLE,
+    lsp::SemanticTokenType::PARAMETER,
+    lsp::SemanticTokenType::PROPERTY,
+];
+
+/// The token is where something is declared.
+const DECLARATION: u32 = 1 << 0;
+/// The token is a constant.
+const READONLY: u32 = 1 << 1;
+/// The token refers to something provided by the native context.
+const DEFAULT_LIBRARY: u32 = 1 << 2;
+
+const TOKEN_MODIFIERS: [lsp::SemanticTokenModifier; 3] = [
+    lsp::SemanticTokenModifier::DECLARATION,
+    lsp::SemanticTokenModifier::READONLY,
+    lsp::SemanticTokenModifier::DEFAULT_LIBRARY,
+];
+
+/// The legend describing the tokens we produce.
+pub(super) fn legend() -> lsp::SemanticTokensLegend {
+    lsp::SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+/// Classify identifiers in the given source, optionally limited to the given
+/// byte range.
+///
+/// The index of the source refers to the text it was last built from, so
+/// identifiers are classified in that text and then mapped onto the current
+/// text, which might have been edited since.
+pub(super) fn semantic_tokens(
+    source: &Source,
+    range: Option<Span>,
+) -> Result<Vec<lsp::SemanticToken>> {
+    let text = source.try_to_string()?;
+    let current = Idents::lex(&text)?;
+
+    let mut tokens = BTreeMap::new();
+
+    if let Some(built) = source.build_text() {
+        let idents = Idents::lex(built)?;
+        let map = idents.map_to(&current);
+
+        for (span, token) in classify_index(built, &idents, source.index())? {
+            if let Some(span) = map.map(span) {
+                tokens.insert(span, token);
+            }
+        }
+    }
+
+    // Macros are expanded before anything is visited, so they are classified
+    // from the current text.
+    for span in current.macro_calls() {
+        tokens.insert(span, (TokenType::Macro, 0));
+    }
+
+    let mut output = Vec::new();
+    let mut last = lsp::Position::default();
+    let mut last_end = 0;
+
+    for (span, (token, modifiers)) in tokens {
+        if let Some(range) = range {
+            if span.start < range.start || span.end > range.end {
+                continue;
+            }
+        }
+
+        // Overlapping tokens are not permitted.
+        if span.start.into_usize() < last_end {
+            continue;
+        }
+
+        let (Some(position), Some(name)) = (
+            source.byte_to_lsp_position(span.start.into_usize()),
+            text.get(span.range()),
+        ) else {
+            continue;
+        };
+
+        let delta_start = if position.line == last.line {
+            position.character - last.character
+        } else {
+            position.character
+        };
+
+        output.try_push(lsp::SemanticToken {
+            delta_line: position.line - last.line,
+            delta_start,
+            length: name.encode_utf16().count() as u32,
+            token_type: token as u32,
+            token_modifiers_bitset: modifiers,
+        })?;
+
+        last = position;
+        last_end = span.end.into_usize();
+    }
+
+    Ok(output)
+}
+
+/// Classify identifiers in the text a source was built from using its index.
+fn classify_index(
+    text: &str,
+    idents: &Idents<'_>,
+    index: &Index,
+) -> Result<BTreeMap<Span, (TokenType, u32)>> {
+    let mut tokens = BTreeMap::new();
+
+    for (span, field) in &index.fields {
+        if let Some(span) = idents.find(*span, &field.name) {
+            tokens.insert(span, (TokenType::Property, 0));
+        }
+    }
+
+    for declaration in &index.declarations {
+        let Some(name) = item_name(&declaration.item) else {
+            continue;
+        };
+
+        let Some(name_span) = idents.find(declaration.span, name) else {
+            continue;
+        };
+
+        let (token, modifiers) = classify(declaration.kind);
+        tokens.insert(name_span, (token, modifiers | DECLARATION));
+
+        let mut rest = Span::new(name_span.end, declaration.span.end);
+
+        for field in &declaration.fields {
+            let Some(span) = idents.find(rest, field) else {
+                break;
+            };
+
+            tokens.insert(span, (TokenType::Property, DECLARATION));
+            rest = Span::new(span.end, declaration.span.end);
+        }
+    }
+
+    // The kinds of all items known in this source, used to classify the
+    // leading components of paths.
+    let mut kinds = HashMap::new();
+
+    for declaration in &index.declarations {
+        kinds.try_insert(&*declaration.item, declaration.kind)?;
+    }
+
+    for definition in index.definitions.values() {
+        if let Some(item) = &definition.item {
+            kinds.try_insert(&**item, definition.kind)?;
+        }
+    }
+
+    for (span, definition) in &index.definitions {
+        classify_definition(text, idents, *span, definition, &kinds, &mut tokens);
+    }
+
+    Ok(tokens)
+}
+
+/// Classify every identifier in the path a definition was used through.
+fn classify_definition(
+    text: &str,
+    idents: &Idents<'_>,
+    span: Span,
+    definition: &Definition,
+    kinds: &HashMap<&Item, DefinitionKind>,
+    tokens: &mut BTreeMap<Span, (TokenType, u32)>,
+) {
+    if let DefinitionKind::Local = definition.kind {
+        let Some(used) = idents.leading(span) else {
+            return;
+        };
+
+        let Some(declared) = idents
+            .name(used)
+            .and_then(|name| idents.binding(definition.source.span(), name))
+        else {
+            return;
+        };
+
+        let token = if is_parameter(text, idents, definition.source.span(), declared) {
+            TokenType::Parameter
+        } else {
+            TokenType::Variable
+        };
+
+        tokens.insert(declared, (token, DECLARATION));
+        tokens.insert(used, (token, 0));
+        return;
+    }
+
+    let Some(path) = text.get(span.range()) else {
+        return;
+    };
+
+    // Some items are visited with the span of whatever caused them to be
+    // looked up, like the type of `self` in an instance function, which we
+    // can't classify.
+    if !path
+        .chars()
+        .all(|c| is_ident(c) || c == ':' || c.is_whitespace())
+    {
+        return;
+    }
+
+    let mut words = path_words(path, span).peekable();
+    let mut parent = None;
+
+    while let Some(word) = words.next() {
+        if words.peek().is_some() {
+            parent = Some(word);
+            tokens.insert(word, (TokenType::Namespace, 0));
+            continue;
+        }
+
+        let (mut token, mut modifiers) = classify(definition.kind);
+
+        if definition.is_context() {
+            if let TokenType::Function = token {
+                token = TokenType::NativeFunction;
+            }
+
+            modifiers |= DEFAULT_LIBRARY;
+        }
+
+        tokens.insert(word, (token, modifiers));
+    }
+
+    let Some(parent) = parent else {
+        return;
+    };
+
+    // The segment immediately preceding the name might be a type rather than
+    // a module, like in `Point::new` or `Option::Some`.
+    let parent_kind = definition
+        .item
+        .as_deref()
+        .and_then(Item::parent)
+        .and_then(|item| kinds.get(item));
+
+    let token = match (parent_kind, definition.kind) {
+        (Some(kind), _) => match classify(*kind) {
+            (TokenType::Function, _) => return,
+            (token, _) => token,
+        },
+        (None, DefinitionKind::AssociatedFunction) => TokenType::Type,
+        (
+            None,
+            DefinitionKind::UnitVariant
+            | DefinitionKind::TupleVariant
+            | DefinitionKind::StructVariant,
+        ) => TokenType::Enum,
+        _ => return,
+    };
+
+    tokens.insert(parent, (token, 0));
+}
+
+/// Convert the kind of a definition into a token type and modifiers.
+fn classify(kind: DefinitionKind) -> (TokenType, u32) {
+    match kind {
+        DefinitionKind::EmptyStruct | DefinitionKind::TupleStruct | DefinitionKind::Struct => {
+            (TokenType::Struct, 0)
+        }
+        DefinitionKind::UnitVariant
+        | DefinitionKind::TupleVariant
+        | DefinitionKind::StructVariant => (TokenType::EnumMember, 0),
+        DefinitionKind::Enum => (TokenType::Enum, 0),
+        DefinitionKind::Function | DefinitionKind::AssociatedFunction => (TokenType::Function, 0),
+        DefinitionKind::Const => (TokenType::Variable, READONLY),
+        DefinitionKind::Type => (TokenType::Type, 0),
+        DefinitionKind::Local => (TokenType::Variable, 0),
+        DefinitionKind::Module => (TokenType::Namespace, 0),
+    }
+}
+
+/// Iterate over the spans of the identifiers making up a path, skipping
+/// keywords like `crate` and `super`.
+fn path_words(path: &str, span: Span) -> impl Iterator<Item = Span> + '_ {
+    let start = span.start.into_usize();
+
+    path.split(|c: char| !is_ident(c))
+        .filter(|word| !word.is_empty() && !is_keyword(word))
+        .map(move |word| {
+            let offset = word.as_ptr() as usize - path.as_ptr() as usize;
+            Span::new(start + offset, start + offset + word.len())
+        })
+}
+
+/// Test if a local declared at `name` is a parameter of the function or
+/// closure spanning `declared`.
+///
+/// Arguments are declared with the span of the whole function or closure, so
+/// this checks if the name is inside of its argument list.
+fn is_parameter(text: &str, idents: &Idents<'_>, declared: Span, name: Span) -> bool {
+    let Some(declared_text) = text.get(declared.range()) else {
+        return false;
+    };
+
+    let offset = name.start.into_usize() - declared.start.into_usize();
+
+    let closure = declared_text
+        .trim_start()
+        .trim_start_matches("async")
+        .trim_start()
+        .trim_start_matches("move")
+        .trim_start();
+
+    let (open, close) = if closure.starts_with('|') {
+        let open = declared_text.len() - closure.len();
+
+        let Some(close) = declared_text[open + 1..].find('|') else {
+            return false;
+        };
+
+        (open, open + 1 + close)
+    } else {
+        let Some(fn_span) = idents.find_kind(declared, K![fn]) else {
+            return false;
+        };
+
+        let fn_end = fn_span.end.into_usize() - declared.start.into_usize();
+
+        let Some(open) = declared_text[fn_end..].find('(') else {
+            return false;
+        };
+
+        let open = fn_end + open;
+        let mut depth = 0usize;
+        let mut close = None;
+
+        for (n, c) in declared_text[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        close = Some(open + n);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let Some(close) = close else {
+            return false;
+        };
+
+        (open, close)
+    };
+
+    open < offset && offset < close
+}
+
+fn is_ident(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+fn is_keyword(word: &str) -> bool {
+    ast::Kind::from_keyword(word).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rust_alloc::vec::Vec;
+
+    use lsp::SemanticTokenType as T;
+
+    use crate::languageserver::state::State;
+    use crate::languageserver::testing::{range, url, Harness};
+    use crate::support::Result;
+    use crate::Context;
+
+    use super::{DECLARATION, DEFAULT_LIBRARY, NATIVE_FUNCTION, READONLY, TOKEN_TYPES};
+
+    const SOURCE: &str = r#"
+const LIMIT = 10;
+
+struct Point { x }
+
+fn make(value) {
+    let point = Point { x: value };
+    point.x + LIMIT
+}
+
+pub fn main() {
+    let empty = Option::None;
+    println!("{}", char::from_i64(97));
+    make(1)
+}
+"#;
+
+    /// Request semantic tokens for the given url and decode them into
+    /// absolute ranges.
+    fn semantic_tokens(
+        harness: &Harness,
+        state: &mut State<'_>,
+        url: &lsp::Url,
+    ) -> Result<Vec<(lsp::Range, T, u32)>> {
+        let params = lsp::SemanticTokensParams {
+            text_document: lsp::TextDocumentIdentifier { uri: url.clone() },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let Some(lsp::SemanticTokensResult::Tokens(tokens)) =
+            harness.block_on(super::super::semantic_tokens_full(state, params))?
+        else {
+            panic!("expected semantic tokens");
+        };
+
+        // Decode the delta-encoded tokens into absolute positions.
+        let mut actual = Vec::new();
+        let mut position = lsp::Position::default();
+
+        for token in tokens.data {
+            if token.delta_line != 0 {
+                position.character = 0;
+            }
+
+            position.line += token.delta_line;
+            position.character += token.delta_start;
+
+            let end = lsp::Position {
+                character: position.character + token.length,
+                ..position
+            };
+
+            actual.push((
+                lsp::Range {
+                    start: position,
+                    end,
+                },
+                TOKEN_TYPES[token.token_type as usize].clone(),
+                token.token_modifiers_bitset,
+            ));
+        }
+
+        Ok(actual)
+    }
+
+    /// The tokens expected for [`SOURCE`], located in the given text.
+    fn expected(text: &str) -> Vec<(lsp::Range, T, u32)> {
+        let token =
+            |needle: &str, n: usize, ty: T, modifiers: u32| (range(text, needle, n), ty, modifiers);
+
+        vec![
+            token("LIMIT", 0, T::VARIABLE, DECLARATION | READONLY),
+            token("Point", 0, T::STRUCT, DECLARATION),
+            token("x", 0, T::PROPERTY, DECLARATION),
+            token("make", 0, T::FUNCTION, DECLARATION),
+            token("value", 0, T::PARAMETER, DECLARATION),
+            token("point", 0, T::VARIABLE, DECLARATION),
+            token("Point", 1, T::STRUCT, 0),
+            token("x", 1, T::PROPERTY, 0),
+            token("value", 1, T::PARAMETER, 0),
+            token("point", 1, T::VARIABLE, 0),
+            token("LIMIT", 1, T::VARIABLE, READONLY),
+            token("main", 0, T::FUNCTION, DECLARATION),
+            token("Option", 0, T::ENUM, 0),
+            token("None", 0, T::ENUM_MEMBER, DEFAULT_LIBRARY),
+            token("println", 0, T::MACRO, 0),
+            token("char", 0, T::NAMESPACE, 0),
+            token("from_i64", 0, NATIVE_FUNCTION, DEFAULT_LIBRARY),
+            token("make", 1, T::FUNCTION, 0),
+        ]
+    }
+
+    #[test]
+    fn test_semantic_tokens() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        assert_eq!(
+            semantic_tokens(&harness, &mut state, &main)?,
+            expected(SOURCE)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_semantic_tokens_after_edit() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        const INSERTED: &str = "const OTHER = 1;";
+
+        let start = lsp::Position::new(0, 0);
+
+        state
+            .workspace_mut()
+            .get_mut(&main)
+            .expect("expected source")
+            .modify_lsp_range(lsp::Range::new(start, start), INSERTED)?;
+
+        // Tokens are mapped onto the edited text without a rebuild, and
+        // what's been inserted is left unclassified until the next one.
+        let edited = format!("{INSERTED}{SOURCE}");
+
+        assert_eq!(
+            semantic_tokens(&harness, &mut state, &main)?,
+            expected(&edited)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,596 @@
+use anyhow::Result;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{HashMap, HashSet, String, Vec};
+use crate::ast::{self, Span};
+use crate::compile::context::ContextMeta;
+use crate::compile::{meta, ComponentRef, Item, ItemBuf, Prelude};
+use crate::parse::Lexer;
+use crate::runtime::debug::{DebugArgs, DebugSignature};
+use crate::{Context, Hash, SourceId};
+
+use super::state::Source;
+
+/// A call to a function.
+pub(super) struct Call<'a> {
+    /// The components of the path being called, excluding leading keywords
+    /// like `crate` or `super`.
+    pub(super) path: Vec<&'a str>,
+    /// The span of the last component of the path.
+    pub(super) span: Span,
+    /// If the call is an instance function call like `value.foo(..)`.
+    pub(super) instance: bool,
+    /// The index of the argument the cursor is at.
+    active: u32,
+}
+
+/// A rendered signature.
+pub(super) struct Signature {
+    /// The label of the signature.
+    label: String,
+    /// The UTF-16 offsets of each parameter in the label.
+    parameters: Vec<[u32; 2]>,
+    /// The names of each parameter.
+    pub(super) names: Vec<String>,
+    /// If the first parameter is `self`.
+    pub(super) receiver: bool,
+    /// Documentation for the function.
+    docs: String,
+}
+
+impl Signature {
+    fn new() -> Self {
+        Self {
+            label: String::new(),
+            parameters: Vec::new(),
+            names: Vec::new(),
+            receiver: false,
+            docs: String::new(),
+        }
+    }
+
+    /// Push a parameter to the label, with an optional type.
+    fn parameter(&mut self, name: &str, ty: Option<&Item>) -> Result<()> {
+        if !self.parameters.is_empty() {
+            self.label.try_push_str(", ")?;
+        }
+
+        if self.parameters.is_empty() && name == "self" {
+            self.receiver = true;
+        }
+
+        let start = self.label.encode_utf16().count() as u32;
+        self.label.try_push_str(name)?;
+
+        if let Some(ty) = ty {
+            write!(self.label, ": {ty}")?;
+        }
+
+        let end = self.label.encode_utf16().count() as u32;
+        self.parameters.try_push([start, end])?;
+        self.names.try_push(name.try_to_owned()?)?;
+        Ok(())
+    }
+
+    /// Push documentation lines.
+    fn docs<'a>(&mut self, lines: impl IntoIterator<Item = &'a str>) -> Result<()> {
+        for line in lines {
+            // Doc comments are captured verbatim, which includes the leading
+            // space after `///`.
+            self.docs
+                .try_push_str(line.strip_prefix(' ').unwrap_or(line))?;
+            self.docs.try_push('\n')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compute signature help for the call surrounding the given byte offset.
+pub(super) fn signature_help(
+    context: &Context,
+    source: &Source,
+    offset: usize,
+) -> Result<Option<lsp::SignatureHelp>> {
+    let text = source.try_to_string()?;
+
+    let Some(call) = call_at(&text, offset)? else {
+        return Ok(None);
+    };
+
+    let signatures = signatures(context, source, &call)?;
+
+    if signatures.is_empty() {
+        return Ok(None);
+    }
+
+    let mut output = ::rust_alloc::vec::Vec::new();
+    let mut active_signature = None;
+
+    for (n, signature) in signatures.into_iter().enumerate() {
+        // The receiver is passed implicitly to instance function calls.
+        let active = if call.instance && signature.receiver {
+            call.active + 1
+        } else {
+            call.active
+        };
+
+        if active_signature.is_none() && (active as usize) < signature.parameters.len() {
+            active_signature = Some(n as u32);
+        }
+
+        let parameters = signature
+            .parameters
+            .iter()
+            .map(|&offsets| lsp::ParameterInformation {
+                label: lsp::ParameterLabel::LabelOffsets(offsets),
+                documentation: None,
+            })
+            .collect();
+
+        let documentation = if signature.docs.is_empty() {
+            None
+        } else {
+            Some(lsp::Documentation::MarkupContent(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: signature.docs.into_std(),
+            }))
+        };
+
+        output.push(lsp::SignatureInformation {
+            label: signature.label.into_std(),
+            documentation,
+            parameters: Some(parameters),
+            active_parameter: Some(active),
+        });
+    }
+
+    Ok(Some(lsp::SignatureHelp {
+        signatures: output,
+        active_signature: Some(active_signature.unwrap_or_default()),
+        active_parameter: None,
+    }))
+}
+
+/// Find the signatures of the functions which might be called by the given
+/// call.
+pub(super) fn signatures(
+    context: &Context,
+    source: &Source,
+    call: &Call<'_>,
+) -> Result<Vec<Signature>> {
+    let Some(&name) = call.path.last() else {
+        return Ok(Vec::new());
+    };
+
+    let debug_functions = source
+        .unit()
+        .and_then(|unit| unit.debug_info())
+        .map(|debug_info| &debug_info.functions);
+
+    let mut signatures = Vec::new();
+
+    if call.instance {
+        // Instance functions are only resolved at runtime, so the best we
+        // can do is to offer every function with a matching name.
+        for signature in script_signatures(source, debug_functions, &call.path)? {
+            if signature.receiver {
+                signatures.try_push(signature)?;
+            }
+        }
+
+        for (meta, _) in context.iter_functions() {
+            if let meta::Kind::Function {
+                associated: Some(meta::AssociatedKind::Instance(n)),
+                ..
+            } = &meta.kind
+            {
+                if n.as_ref() == name {
+                    signatures.try_extend(native_signature(context, meta)?)?;
+                }
+            }
+        }
+    } else {
+        let definition = source
+            .find_definition_at(Span::point(call.span.start))
+            .filter(|(_, def)| {
+                let item = def.item.as_deref().and_then(|item| item.last());
+                item == Some(ComponentRef::Str(name))
+            })
+            .and_then(|(_, def)| Some((def.hash?, def.is_context())));
+
+        match definition {
+            Some((hash, true)) => {
+                if let Some(meta) = context.lookup_meta_by_hash(hash).next() {
+                    signatures.try_extend(native_signature(context, meta)?)?;
+                }
+            }
+            Some((hash, false)) => {
+                if let Some(signature) = debug_functions.and_then(|f| f.get(&hash)) {
+                    signatures.try_push(script_signature(source, hash, signature)?)?;
+                }
+            }
+            None => {
+                // The source might not build while a call is being typed, so
+                // fall back to looking functions up by name.
+                signatures.try_extend(script_signatures(source, debug_functions, &call.path)?)?;
+
+                if let Some(meta) = lookup_native(context, &call.path)? {
+                    signatures.try_extend(native_signature(context, meta)?)?;
+                }
+            }
+        }
+    }
+
+    Ok(signatures)
+}
+
+/// Find the call surrounding the given byte offset, by lexing everything
+/// leading up to it and walking backwards to the unclosed parenthesis.
+fn call_at(text: &str, offset: usize) -> Result<Option<Call<'_>>> {
+    let Some(text) = text.get(..offset) else {
+        return Ok(None);
+    };
+
+    let mut lexer = Lexer::new(text, SourceId::empty(), true);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next() {
+            Ok(Some(token)) => tokens.try_push(token)?,
+            Ok(None) => break,
+            // Most likely an unterminated string, in which case we're not
+            // looking at an argument list.
+            Err(..) => return Ok(None),
+        }
+    }
+
+    let mut depth = 0usize;
+    let mut active = 0u32;
+    let mut open = None;
+
+    for (n, token) in tokens.iter().enumerate().rev() {
+        match token.kind {
+            ast::Kind::Close(..) => {
+                depth += 1;
+            }
+            ast::Kind::Open(delimiter) => {
+                if depth > 0 {
+                    depth -= 1;
+                    continue;
+                }
+
+                if delimiter != ast::Delimiter::Parenthesis {
+                    return Ok(None);
+                }
+
+                open = Some(n);
+                break;
+            }
+            ast::Kind::Comma if depth == 0 => {
+                active += 1;
+            }
+            // Statements can't appear in argument lists.
+            ast::Kind::SemiColon if depth == 0 => {
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
+    let Some(open) = open else {
+        return Ok(None);
+    };
+
+    call_path(text, &tokens[..open], active)
+}
+
+/// Find the path being called by a call whose arguments are opened right after
+/// the given tokens.
+pub(super) fn call_path<'a>(
+    text: &'a str,
+    tokens: &[ast::Token],
+    active: u32,
+) -> Result<Option<Call<'a>>> {
+    let mut path = Vec::new();
+    let mut span = None;
+    let mut instance = false;
+    let mut rest = tokens.iter().rev();
+
+    while let Some(token) = rest.next() {
+        match token.kind {
+            ast::Kind::Ident(..) => {
+                let Some(ident) = text.get(token.span.range()) else {
+                    return Ok(None);
+                };
+
+                path.try_push(ident)?;
+                span.get_or_insert(token.span);
+            }
+            ast::Kind::Crate | ast::Kind::Super | ast::Kind::SelfValue => {}
+            _ => return Ok(None),
+        }
+
+        match rest.next().map(|t| t.kind) {
+            Some(ast::Kind::ColonColon) => {}
+            Some(ast::Kind::Dot) => {
+                instance = true;
+                break;
+            }
+            _ => break,
+        }
+    }
+
+    let Some(span) = span else {
+        return Ok(None);
+    };
+
+    path.reverse();
+
+    Ok(Some(Call {
+        path,
+        span,
+        instance,
+        active,
+    }))
+}
+
+/// Render the signatures of all script functions whose path ends with the
+/// given path.
+fn script_signatures(
+    source: &Source,
+    debug_functions: Option<&HashMap<Hash, DebugSignature>>,
+    path: &[&str],
+) -> Result<Vec<Signature>> {
+    let mut signatures = Vec::new();
+
+    // Functions can be registered under several hashes, like instance
+    // functions, so only render each path once.
+    let mut seen = HashSet::new();
+
+    for (hash, signature) in debug_functions.into_iter().flatten() {
+        if !item_ends_with(&signature.path, path) || !seen.try_insert(&signature.path)? {
+            continue;
+        }
+
+        signatures.try_push(script_signature(source, *hash, signature)?)?;
+    }
+
+    Ok(signatures)
+}
+
+/// Test if the given item ends with the given path.
+fn item_ends_with(item: &Item, path: &[&str]) -> bool {
+    let mut it = item.iter();
+
+    for expected in path.iter().rev() {
+        match it.next_back() {
+            Some(ComponentRef::Str(c)) if c == *expected => {}
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+/// Look up a native function by the path used to call it.
+pub(super) fn lookup_native<'a>(
+    context: &'a Context,
+    path: &[&str],
+) -> Result<Option<&'a ContextMeta>> {
+    let Some((first, rest)) = path.split_first() else {
+        return Ok(None);
+    };
+
+    let prelude = Prelude::with_default_prelude()?;
+
+    let mut item = match prelude.get(first) {
+        Some(item) => item.try_to_owned()?,
+        None => ItemBuf::with_crate(first)?,
+    };
+
+    for c in rest {
+        item.push(*c)?;
+    }
+
+    if let Some(mut metas) = context.lookup_meta(&item) {
+        if let Some(meta) = metas.find(|meta| matches!(meta.kind, meta::Kind::Function { .. })) {
+            return Ok(Some(meta));
+        }
+    }
+
+    // Associated functions like `String::new` are only registered by hash.
+    let (Some(parent), Some(ComponentRef::Str(name))) = (item.parent(), item.last()) else {
+        return Ok(None);
+    };
+
+    let Some(mut metas) = context.lookup_meta(parent) else {
+        return Ok(None);
+    };
+
+    let Some(ty) = metas.next() else {
+        return Ok(None);
+    };
+
+    let hash = Hash::associated_function(ty.hash, name);
+    Ok(context.lookup_meta_by_hash(hash).next())
+}
+
+/// Render the signature of a native function.
+fn native_signature(context: &Context, meta: &ContextMeta) -> Result<Option<Signature>> {
+    let meta::Kind::Function {
+        signature,
+        associated,
+        ..
+    } = &meta.kind
+    else {
+        return Ok(None);
+    };
+
+    let name = super::hover::native_name(context, meta)?;
+    let mut output = Signature::new();
+
+    if signature.is_async {
+        output.label.try_push_str("async ")?;
+    }
+
+    write!(output.label, "fn {name}(")?;
+
+    let names = meta.docs.args().unwrap_or_default();
+    let count = signature.args.unwrap_or(names.len()).max(names.len());
+
+    for n in 0..count {
+        let ty = signature
+            .argument_types
+            .get(n)
+            .copied()
+            .flatten()
+            .and_then(|hash| super::hover::type_name(context, hash));
+
+        match names.get(n) {
+            Some(name) => output.parameter(name, ty)?,
+            None => output.parameter(&try_format!("_{n}"), ty)?,
+        }
+    }
+
+    output.label.try_push(')')?;
+
+    if let Some(ty) = signature
+        .return_type
+        .and_then(|hash| super::hover::type_name(context, hash))
+    {
+        write!(output.label, " -> {ty}")?;
+    }
+
+    if let Some(meta::AssociatedKind::Instance(..)) = associated {
+        output.receiver = true;
+    }
+
+    output.docs(meta.docs.lines().iter().map(|s| s.as_str()))?;
+    Ok(Some(output))
+}
+
+/// Render the signature of a script function.
+fn script_signature(source: &Source, hash: Hash, signature: &DebugSignature) -> Result<Signature> {
+    let mut output = Signature::new();
+    write!(output.label, "fn {}(", signature.path)?;
+
+    match &signature.args {
+        DebugArgs::Named(args) => {
+            for arg in args.iter() {
+                output.parameter(arg, None)?;
+            }
+        }
+        DebugArgs::TupleArgs(n) => {
+            for n in 0..*n {
+                output.parameter(&try_format!("_{n}"), None)?;
+            }
+        }
+        DebugArgs::EmptyArgs => {}
+    }
+
+    output.label.try_push(')')?;
+
+    if let Some(data) = source.get_docs_by_hash(hash) {
+        output.docs(data.docs.iter().map(|s| s.as_str()))?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rune;
+    use crate::languageserver::testing::{position, url, Harness};
+    use crate::support::Result;
+    use crate::{Context, Module};
+
+    /// Greet the given name.
+    #[rune::function]
+    fn greet(name: &str, times: i64) -> i64 {
+        name.len() as i64 * times
+    }
+
+    const SOURCE: &str = r#"
+/// Add two numbers together.
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    let total = add(1, 2);
+    greet("world", 3) + total
+}
+"#;
+
+    #[test]
+    fn test_signature_help() -> Result<()> {
+        let mut module = Module::new();
+        module.function_meta(greet)?;
+
+        let mut context = Context::with_default_modules()?;
+        context.install(module)?;
+
+        let harness = Harness::new()?;
+        let mut state = harness.state(context);
+        let main = url("main.rn");
+        harness.open(&mut state, &[(&main, SOURCE)])?;
+
+        let mut signature_help = |needle: &str| {
+            let params = lsp::SignatureHelpParams {
+                context: None,
+                text_document_position_params: lsp::TextDocumentPositionParams {
+                    text_document: lsp::TextDocumentIdentifier { uri: main.clone() },
+                    position: position(SOURCE, needle, 0),
+                },
+                work_done_progress_params: Default::default(),
+            };
+
+            harness.block_on(super::super::signature_help(&mut state, params))
+        };
+
+        let help = signature_help("2);")?.expect("expected signature help");
+        assert_eq!(help.active_signature, Some(0));
+        let [signature] = &help.signatures[..] else {
+            panic!("expected one signature: {help:?}");
+        };
+
+        assert_eq!(signature.label, "fn add(a, b)");
+        assert_eq!(signature.active_parameter, Some(1));
+        assert_eq!(
+            signature.parameters,
+            Some(vec![
+                lsp::ParameterInformation {
+                    label: lsp::ParameterLabel::LabelOffsets([7, 8]),
+                    documentation: None,
+                },
+                lsp::ParameterInformation {
+                    label: lsp::ParameterLabel::LabelOffsets([10, 11]),
+                    documentation: None,
+                },
+            ])
+        );
+        assert_eq!(
+            signature.documentation,
+            Some(lsp::Documentation::MarkupContent(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: "Add two numbers together.\n".into(),
+            }))
+        );
+
+        let help = signature_help("\"world\"")?.expect("expected signature help");
+        let [signature] = &help.signatures[..] else {
+            panic!("expected one signature: {help:?}");
+        };
+
+        assert_eq!(
+            signature.label,
+            "fn greet(name: ::std::string::String, times: ::std::i64) -> ::std::i64"
+        );
+        assert_eq!(signature.active_parameter, Some(0));
+
+        assert!(signature_help("let total")?.is_none());
+        Ok(())
+    }
+}
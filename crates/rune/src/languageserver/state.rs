@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fmt;
+use std::mem::take;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -10,23 +11,29 @@ use tokio::sync::Notify;
 
 use crate as rune;
 use crate::alloc::prelude::*;
-use crate::alloc::{self, HashMap, String, Vec};
+use crate::alloc::{self, Box, HashMap, String, Vec};
 use crate::ast::{Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{
-    self, CompileVisitor, ComponentRef, Item, LinkerError, Located, Location, MetaError, MetaRef,
-    SourceMeta, WithSpan,
+    self, CompileVisitor, ComponentRef, Item, ItemBuf, LinkerError, Located, Location, MetaError,
+    MetaRef, SourceMeta, WithSpan,
 };
-use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind, LintLevel, Suggestion};
 use crate::doc::VisitorData;
+use crate::fmt::FormatOptions;
+use crate::languageserver::code_actions::QuickFixes;
 use crate::languageserver::connection::Output;
+use crate::languageserver::envelope::{Code, RequestError};
+use crate::languageserver::references::{self, References, Target};
+use crate::languageserver::symbols::Symbols;
 use crate::languageserver::Language;
 use crate::workspace::{self, WorkspaceError};
-use crate::{BuildError, Context, Options, SourceId, Unit};
+use crate::{Context, Hash, Options, SourceId, Unit};
 
 #[derive(Default)]
 struct Reporter {
     by_url: BTreeMap<Url, Vec<lsp::Diagnostic>>,
+    fixes: QuickFixes,
 }
 
 impl Reporter {
@@ -43,16 +50,19 @@ impl Reporter {
     }
 }
 
-#[derive(Default)]
+/// The sources making up a single build, and the URLs they were loaded from.
 struct Build {
     id_to_url: HashMap<SourceId, Url>,
-    sources: crate::Sources,
+    sources: Arc<crate::Sources>,
 }
 
 impl Build {
-    pub(super) fn populate(&mut self, reporter: &mut Reporter) -> Result<()> {
-        for id in self.sources.source_ids() {
-            let Some(source) = self.sources.get(id) else {
+    /// Construct a build out of the given sources.
+    fn new(sources: crate::Sources) -> Result<Self> {
+        let mut id_to_url = HashMap::new();
+
+        for id in sources.source_ids() {
+            let Some(source) = sources.get(id) else {
                 continue;
             };
 
@@ -64,32 +74,52 @@ impl Build {
                 continue;
             };
 
-            reporter.ensure(&url);
-            self.id_to_url.try_insert(id, url)?;
+            id_to_url.try_insert(id, url)?;
         }
 
-        Ok(())
+        Ok(Self {
+            id_to_url,
+            sources: Arc::new(sources),
+        })
     }
 
-    pub(super) fn visit(&mut self, visited: &mut HashSet<Url>) {
-        for id in self.sources.source_ids() {
-            let Some(source) = self.sources.get(id) else {
-                continue;
-            };
-
-            let Some(path) = source.path() else {
-                continue;
-            };
-
-            let Ok(url) = crate::languageserver::url::from_file_path(path) else {
-                continue;
-            };
+    /// Ensure that diagnostics are reported for every source in the build,
+    /// which clears any previously reported ones.
+    fn populate(&self, reporter: &mut Reporter) {
+        for url in self.id_to_url.values() {
+            reporter.ensure(url);
+        }
+    }
 
+    fn visit(&self, visited: &mut HashSet<Url>) {
+        for url in self.id_to_url.values() {
             visited.insert(url.clone());
         }
     }
 }
 
+/// The output of building a script, which is reused until any of the sources
+/// it depends on change.
+struct BuildOutput {
+    build: Build,
+    /// Every URL the build was loaded from or tried to load a module from.
+    dependencies: HashSet<Url>,
+    diagnostics: crate::Diagnostics,
+    indexes: HashMap<SourceId, Arc<Index>>,
+    docs: Arc<crate::doc::Visitor>,
+    unit: Option<Unit>,
+    /// Files parsed by the build, so that rebuilding it only parses the
+    /// sources which have changed.
+    parse_cache: compile::ParseCache,
+}
+
+impl BuildOutput {
+    /// Test if the build depends on any of the given changed URLs.
+    fn is_affected_by(&self, changed: &HashSet<Url>) -> bool {
+        changed.iter().any(|url| self.dependencies.contains(url))
+    }
+}
+
 /// Shared server state.
 pub(super) struct State<'a> {
     /// The output abstraction.
@@ -107,6 +137,17 @@ pub(super) struct State<'a> {
     stopped: bool,
     /// Sources used in the project.
     workspace: Workspace,
+    /// Every reference to locals, items and fields in the project.
+    references: References,
+    /// Every item declared in the project.
+    symbols: Symbols,
+    /// Fixes suggested by diagnostics in the project.
+    fixes: QuickFixes,
+    /// Outputs of builds, keyed by the URL of the script they were built
+    /// from.
+    builds: HashMap<Url, BuildOutput>,
+    /// Formatting options loaded from the workspace.
+    format_options: FormatOptions,
 }
 
 impl<'a> State<'a> {
@@ -125,6 +166,11 @@ impl<'a> State<'a> {
             initialized: bool::default(),
             stopped: bool::default(),
             workspace: Workspace::default(),
+            references: References::default(),
+            symbols: Symbols::default(),
+            fixes: QuickFixes::default(),
+            builds: HashMap::new(),
+            format_options: FormatOptions::default(),
         }
     }
 
@@ -168,7 +214,11 @@ impl<'a> State<'a> {
     ) -> Option<lsp::Location> {
         let source = self.workspace.get(uri)?;
         let offset = source.lsp_position_to_offset(position);
-        let def = source.find_definition_at(Span::point(offset))?;
+        let (_, def) = source.find_definition_at(Span::point(offset))?;
+
+        if def.is_context() {
+            return None;
+        }
 
         let url = match def.source.path() {
             Some(path) => crate::languageserver::url::from_file_path(path).ok()?,
@@ -197,6 +247,214 @@ impl<'a> State<'a> {
         Some(location)
     }
 
+    /// Render hover information at the given uri and LSP position.
+    #[tracing::instrument(skip_all)]
+    pub(super) fn hover(&self, uri: &Url, position: lsp::Position) -> Result<Option<lsp::Hover>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = source.lsp_position_to_offset(position);
+
+        let (span, contents) = 'found: {
+            if let Some((span, def)) = source.find_definition_at(Span::point(offset)) {
+                if let Some(contents) =
+                    super::hover::hover_for_definition(&self.context, source, def)?
+                {
+                    break 'found (span, contents);
+                }
+            }
+
+            // Instance functions are only resolved at runtime, so fall back
+            // to looking up native functions by name.
+            let Some((span, ident)) = source.ident_at(offset)? else {
+                return Ok(None);
+            };
+
+            if source.char_before(span) != Some('.') {
+                return Ok(None);
+            }
+
+            let Some(contents) = super::hover::hover_for_instance_fn(&self.context, &ident)? else {
+                return Ok(None);
+            };
+
+            (span, contents)
+        };
+
+        Ok(Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: contents.into_std(),
+            }),
+            range: source.span_to_lsp_range(span),
+        }))
+    }
+
+    /// Find the target referenced at the given uri and LSP position.
+    fn target_at(&self, uri: &Url, position: lsp::Position) -> Option<(Span, &Target)> {
+        let source = self.workspace.get(uri)?;
+        let offset = source.lsp_position_to_offset(position);
+        self.references.target_at(uri, offset)
+    }
+
+    /// Find all references to whatever is at the given uri and LSP position.
+    pub(super) fn references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Result<Option<Vec<lsp::Location>>> {
+        let Some((_, target)) = self.target_at(uri, position) else {
+            return Ok(None);
+        };
+
+        let mut locations = Vec::new();
+
+        for (url, _, reference) in self.references.references(target) {
+            if reference.declaration && !include_declaration {
+                continue;
+            }
+
+            locations.try_push(lsp::Location {
+                uri: url.clone(),
+                range: reference.range,
+            })?;
+        }
+
+        Ok(Some(locations))
+    }
+
+    /// Test if whatever is at the given uri and LSP position can be renamed.
+    pub(super) fn prepare_rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::PrepareRenameResponse>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let Some((span, target)) = self.target_at(uri, position) else {
+            return Ok(None);
+        };
+
+        if !self.references.is_renamable(target) {
+            return Ok(None);
+        }
+
+        let Some(range) = source.span_to_lsp_range(span) else {
+            return Ok(None);
+        };
+
+        Ok(Some(lsp::PrepareRenameResponse::Range(range)))
+    }
+
+    /// Rename whatever is at the given uri and LSP position.
+    pub(super) fn rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Result<Option<lsp::WorkspaceEdit>> {
+        if !references::is_valid_ident(new_name) {
+            return Err(RequestError::new(Code::InvalidParams, "Not a valid identifier").into());
+        }
+
+        let Some((_, target)) = self.target_at(uri, position) else {
+            return Ok(None);
+        };
+
+        if !self.references.is_renamable(target) {
+            return Ok(None);
+        }
+
+        let mut changes = std::collections::HashMap::<_, ::rust_alloc::vec::Vec<_>>::new();
+
+        for (url, span, reference) in self.references.references(target) {
+            // Shorthand fields like `Point { x }` name both a field and a
+            // local, so they need to be expanded instead.
+            let new_text = match (target, self.references.shorthand_field(url, span, target)) {
+                (Target::Local(..), Some(field)) => format!("{field}: {new_name}"),
+                (Target::Field(..), Some(field)) => format!("{new_name}: {field}"),
+                _ => new_name.into(),
+            };
+
+            changes
+                .entry(url.clone())
+                .or_default()
+                .push(lsp::TextEdit::new(reference.range, new_text));
+        }
+
+        Ok(Some(lsp::WorkspaceEdit::new(changes)))
+    }
+
+    /// Get code actions applicable to the given range of the given uri.
+    pub(super) fn code_actions(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+    ) -> Result<Vec<lsp::CodeActionOrCommand>> {
+        self.fixes.code_actions(uri, range)
+    }
+
+    /// Get the hierarchy of items declared in the given uri.
+    pub(super) fn document_symbols(&self, uri: &Url) -> Result<Vec<lsp::DocumentSymbol>> {
+        self.symbols.document_symbols(uri)
+    }
+
+    /// Find items declared anywhere in the workspace matching the given
+    /// query.
+    pub(super) fn workspace_symbols(&self, query: &str) -> Result<Vec<lsp::SymbolInformation>> {
+        self.symbols.workspace_symbols(query)
+    }
+
+    /// Compute signature help for the call surrounding the given uri and LSP
+    /// position.
+    pub(super) fn signature_help(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::SignatureHelp>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = source.lsp_position_to_offset(position);
+        super::signature_help::signature_help(&self.context, source, offset)
+    }
+
+    /// Classify identifiers in the given uri, optionally limited to the given
+    /// LSP range.
+    pub(super) fn semantic_tokens(
+        &self,
+        uri: &Url,
+        range: Option<lsp::Range>,
+    ) -> Result<Option<Vec<lsp::SemanticToken>>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let range = range.map(|range| source.lsp_range_to_span(range));
+        let tokens = super::semantic_tokens::semantic_tokens(source, range)?;
+        Ok(Some(tokens))
+    }
+
+    /// Compute inlay hints for the given uri and LSP range.
+    pub(super) fn inlay_hints(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+    ) -> Result<Option<Vec<lsp::InlayHint>>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let range = source.lsp_range_to_span(range);
+        let hints = super::inlay_hints::inlay_hints(&self.context, source, range)?;
+        Ok(Some(hints))
+    }
+
     /// Find definition at the given uri and LSP position.
     #[tracing::instrument(skip_all)]
     pub(super) fn complete(
@@ -258,59 +516,113 @@ impl<'a> State<'a> {
         Ok(Some(results))
     }
 
-    pub(super) fn format(&mut self, uri: &Url) -> Result<Option<lsp::TextEdit>> {
-        let sources = &mut self.workspace.sources;
+    /// Format the whole document.
+    pub(super) fn format(&self, uri: &Url) -> Result<Option<Vec<lsp::TextEdit>>> {
+        let sources = &self.workspace.sources;
         tracing::trace!(uri = ?uri.try_to_string()?, uri_exists = sources.get(uri).is_some());
 
-        let Some(workspace_source) = sources.get_mut(uri) else {
+        let Some(workspace_source) = sources.get(uri) else {
             return Ok(None);
         };
 
         let source = workspace_source.content.try_to_string()?;
 
-        let Ok(formatted) = crate::fmt::layout_source(&source) else {
+        let Ok(formatted) = crate::fmt::layout_source(&source, &self.format_options) else {
             return Ok(None);
         };
 
         let formatted = String::from_utf8(formatted).context("Format produced invalid utf8")?;
+        let edits = super::formatting::text_edits(workspace_source, 0, &source, &formatted)?;
+        Ok(Some(edits))
+    }
 
-        // Only modify if changed
-        Ok(if source != formatted {
-            workspace_source.content = Rope::from_str(&formatted);
-            self.rebuild_interest();
-
-            Some(lsp::TextEdit::new(
-                // Range over full document
-                lsp::Range::new(
-                    lsp::Position::new(0, 0),
-                    lsp::Position::new(u32::MAX, u32::MAX),
-                ),
-                formatted.into_std(),
-            ))
-        } else {
-            None
-        })
+    /// Format the smallest part of the document which covers the given range.
+    pub(super) fn format_range(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+    ) -> Result<Option<Vec<lsp::TextEdit>>> {
+        let Some(workspace_source) = self.workspace.sources.get(uri) else {
+            return Ok(None);
+        };
+
+        let span = workspace_source.lsp_range_to_span(range);
+        self.format_span(workspace_source, span)
+    }
+
+    /// Format the part of the document affected by typing `ch`, which ends at
+    /// the given position.
+    pub(super) fn format_on_type(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        ch: &str,
+    ) -> Result<Option<Vec<lsp::TextEdit>>> {
+        let Some(workspace_source) = self.workspace.sources.get(uri) else {
+            return Ok(None);
+        };
+
+        let end = workspace_source.lsp_position_to_offset(position);
+        let span = Span::new(end.saturating_sub(ch.len()), end);
+        self.format_span(workspace_source, span)
+    }
+
+    fn format_span(
+        &self,
+        workspace_source: &Source,
+        span: Span,
+    ) -> Result<Option<Vec<lsp::TextEdit>>> {
+        let source = workspace_source.content.try_to_string()?;
+
+        let Ok(Some((region, formatted))) =
+            crate::fmt::layout_span(&source, &self.format_options, span)
+        else {
+            return Ok(None);
+        };
+
+        let formatted = String::from_utf8(formatted).context("Format produced invalid utf8")?;
+
+        let edits = super::formatting::text_edits(
+            workspace_source,
+            region.start.into_usize(),
+            &source[region.range()],
+            &formatted,
+        )?;
+
+        Ok(Some(edits))
     }
 
     /// Rebuild the project.
+    ///
+    /// Scripts are only recompiled if any of the sources they depend on have
+    /// changed since the last rebuild. Recompiling a script reuses the files
+    /// parsed by its previous build, but indexes and compiles all of its items
+    /// again.
     pub(super) async fn rebuild(&mut self) -> Result<()> {
         // Keep track of URLs visited as part of workspace builds.
         let mut visited = HashSet::new();
         // Workspace results.
         let mut workspace_results = Vec::new();
-        // Build results.
+        // Build outputs, paired with the URL of the script they were built
+        // from.
         let mut script_results = Vec::new();
         // Emitted diagnostics, grouped by URL.
         let mut reporter = Reporter::default();
+        // Outputs from the previous rebuild which might be reused.
+        let mut cached = take(&mut self.builds);
+        // Sources which have changed since the previous rebuild.
+        let changed = take(&mut self.workspace.changed);
+        // Formatting options found in the workspace.
+        let mut format_options = None;
 
         if let Some((workspace_url, workspace_path)) = &self.workspace.manifest_path {
             let mut diagnostics = workspace::Diagnostics::default();
-            let mut build = Build::default();
+            let mut sources = crate::Sources::new();
 
             let result = self.load_workspace(
                 workspace_url,
                 workspace_path,
-                &mut build,
+                &mut sources,
                 &mut diagnostics,
                 &self.workspace,
             );
@@ -323,15 +635,33 @@ impl<'a> State<'a> {
                         tracing::error!("caused by: {error}");
                     }
                 }
-                Ok(script_builds) => {
-                    for script_build in script_builds {
-                        script_results
-                            .try_push(self.build_scripts(script_build, Some(&mut visited))?)?;
+                Ok((script_builds, format)) => {
+                    format_options = format;
+
+                    for (url, path) in script_builds {
+                        let output = self.build_scripts(&mut cached, &changed, &url, || {
+                            let source = match self.workspace.sources.get(&url) {
+                                Some(source) => source.try_to_string()?,
+                                None => match std::fs::read_to_string(&path) {
+                                    Ok(string) => String::try_from(string)?,
+                                    Err(error) => {
+                                        return Err(error).context(path.display().try_to_string()?)
+                                    }
+                                },
+                            };
+
+                            let mut sources = crate::Sources::new();
+                            sources.insert(crate::Source::with_path(&url, source, &path)?)?;
+                            Ok(sources)
+                        })?;
+
+                        output.build.visit(&mut visited);
+                        script_results.try_push((url, output))?;
                     }
                 }
             };
 
-            workspace_results.try_push((diagnostics, build))?;
+            workspace_results.try_push((diagnostics, Build::new(sources)?))?;
         }
 
         for (url, source) in &self.workspace.sources {
@@ -346,37 +676,45 @@ impl<'a> State<'a> {
 
             tracing::trace!(url = ?url.try_to_string()?, "build plain source");
 
-            let mut build = Build::default();
+            let output = self.build_scripts(&mut cached, &changed, url, || {
+                let input = match url.to_file_path() {
+                    Ok(path) => crate::Source::with_path(url, source.try_to_string()?, path)?,
+                    Err(..) => crate::Source::new(url, source.try_to_string()?)?,
+                };
 
-            let input = match url.to_file_path() {
-                Ok(path) => crate::Source::with_path(url, source.try_to_string()?, path)?,
-                Err(..) => crate::Source::new(url, source.try_to_string()?)?,
-            };
+                let mut sources = crate::Sources::new();
+                sources.insert(input)?;
+                Ok(sources)
+            })?;
 
-            build.sources.insert(input)?;
-            script_results.try_push(self.build_scripts(build, None)?)?;
+            script_results.try_push((url.clone(), output))?;
         }
 
+        let mut references = References::default();
+        let mut symbols = Symbols::default();
+
         // We need to pupulate diagnostics for everything we know about, in
         // order to clear errors which might've previously been set.
         for url in self.workspace.removed.drain(..) {
             reporter.ensure(&url);
         }
 
-        for (diagnostics, mut build) in workspace_results {
-            build.populate(&mut reporter)?;
-            emit_workspace(diagnostics, &build, &mut reporter)?;
+        for (diagnostics, build) in &workspace_results {
+            build.populate(&mut reporter);
+            emit_workspace(diagnostics, build, &mut reporter)?;
         }
 
-        for (diagnostics, mut build, source_visitor, doc_visitor, unit) in script_results {
-            build.populate(&mut reporter)?;
-            emit_scripts(diagnostics, &build, &mut reporter)?;
+        for (_, output) in &script_results {
+            let build = &output.build;
+
+            build.populate(&mut reporter);
+            emit_scripts(&output.diagnostics, build, &mut reporter)?;
 
-            let sources = Arc::new(build.sources);
-            let doc_visitor = Arc::new(doc_visitor);
+            references.index(&build.sources, &build.id_to_url, &output.indexes)?;
+            symbols.index(&build.sources, &build.id_to_url, &output.indexes)?;
 
-            for (source_id, value) in source_visitor.into_indexes() {
-                let Some(url) = build.id_to_url.get(&source_id) else {
+            for (source_id, index) in &output.indexes {
+                let Some(url) = build.id_to_url.get(source_id) else {
                     continue;
                 };
 
@@ -384,17 +722,29 @@ impl<'a> State<'a> {
                     continue;
                 };
 
-                source.index = value;
-                source.build_sources = Some(sources.clone());
+                source.index = index.clone();
+                source.build_sources = Some(build.sources.clone());
+                source.build_source_id = Some(*source_id);
 
-                if let Ok(unit) = &unit {
+                if let Some(unit) = &output.unit {
                     source.unit = Some(unit.try_clone()?);
                 }
 
-                source.docs = Some(doc_visitor.clone());
+                source.docs = Some(output.docs.clone());
             }
         }
 
+        self.references = references;
+        self.symbols = symbols;
+        self.fixes = reporter.fixes;
+        self.format_options = format_options.unwrap_or_default();
+
+        // Builds with errors are kept as well, since any module they failed
+        // to load is one of their dependencies.
+        for (url, output) in script_results {
+            self.builds.try_insert(url, output)?;
+        }
+
         for (url, diagnostics) in reporter.by_url {
             tracing::info!(
                 url = ?url.try_to_string()?,
@@ -421,10 +771,10 @@ impl<'a> State<'a> {
         &self,
         url: &Url,
         path: &Path,
-        manifest_build: &mut Build,
+        manifest_sources: &mut crate::Sources,
         diagnostics: &mut workspace::Diagnostics,
         workspace: &Workspace,
-    ) -> Result<Vec<Build>, anyhow::Error> {
+    ) -> Result<(Vec<(Url, PathBuf)>, Option<FormatOptions>), anyhow::Error> {
         tracing::info!(url = ?url.try_to_string(), "building workspace");
 
         let source = match workspace.sources.get(url) {
@@ -437,13 +787,11 @@ impl<'a> State<'a> {
             },
         };
 
-        manifest_build
-            .sources
-            .insert(crate::Source::with_path(url, source, path)?)?;
+        manifest_sources.insert(crate::Source::with_path(url, source, path)?)?;
 
         let mut source_loader = WorkspaceSourceLoader::new(&self.workspace.sources);
 
-        let manifest = workspace::prepare(&mut manifest_build.sources)
+        let manifest = workspace::prepare(manifest_sources)
             .with_diagnostics(diagnostics)
             .with_source_loader(&mut source_loader)
             .build()?;
@@ -456,63 +804,91 @@ impl<'a> State<'a> {
             };
 
             tracing::trace!("Found manifest source: {}", url);
-
-            let source = match workspace.sources.get(&url) {
-                Some(source) => source.chunks().try_collect::<String>()?,
-                None => match std::fs::read_to_string(&p.found.path) {
-                    Ok(string) => String::try_from(string)?,
-                    Err(err) => return Err(err).context(p.found.path.display().try_to_string()?),
-                },
-            };
-
-            let mut build = Build::default();
-            build
-                .sources
-                .insert(crate::Source::with_path(&url, source, p.found.path)?)?;
-
-            script_builds.try_push(build)?;
+            script_builds.try_push((url, p.found.path))?;
         }
 
-        Ok(script_builds)
+        // A format file next to the manifest takes precedence over the
+        // `[format]` section of the manifest.
+        let format = match path.parent().map(crate::fmt::load_format_file) {
+            Some(Ok(Some(format))) => Some(format),
+            Some(Err(error)) => {
+                tracing::warn!("error loading format file: {error}");
+                manifest.format.clone()
+            }
+            _ => manifest.format.clone(),
+        };
+
+        Ok((script_builds, format))
     }
 
+    /// Build the script at the given URL out of the sources returned by
+    /// `load`, unless none of the sources the cached output from last building
+    /// it depends on have changed.
+    ///
+    /// Only parsing is cached across builds. Once any dependency has changed,
+    /// the whole script is indexed and compiled again.
     fn build_scripts(
         &self,
-        mut build: Build,
-        built: Option<&mut HashSet<Url>>,
-    ) -> Result<(
-        crate::Diagnostics,
-        Build,
-        Visitor,
-        crate::doc::Visitor,
-        Result<Unit, BuildError>,
-    )> {
+        cached: &mut HashMap<Url, BuildOutput>,
+        changed: &HashSet<Url>,
+        url: &Url,
+        load: impl FnOnce() -> Result<crate::Sources>,
+    ) -> Result<BuildOutput> {
+        let mut parse_cache = compile::ParseCache::new();
+
+        if let Some(output) = cached.remove(url) {
+            if !output.is_affected_by(changed) {
+                tracing::trace!(url = ?url.try_to_string()?, "reusing build");
+                return Ok(output);
+            }
+
+            parse_cache = output.parse_cache;
+        }
+
+        let mut sources = load()?;
         let mut diagnostics = crate::Diagnostics::new();
         let mut source_visitor = Visitor::default();
         let mut doc_visitor = crate::doc::Visitor::new(Item::new())?;
 
         let mut source_loader = ScriptSourceLoader::new(&self.workspace.sources);
 
-        let unit = crate::prepare(&mut build.sources)
+        let unit = crate::prepare(&mut sources)
             .with_context(&self.context)
             .with_diagnostics(&mut diagnostics)
             .with_options(&self.options)
             .with_visitor(&mut doc_visitor)?
             .with_visitor(&mut source_visitor)?
             .with_source_loader(&mut source_loader)
+            .with_parse_cache(&mut parse_cache)
             .build();
 
-        if let Some(built) = built {
-            build.visit(built);
+        let mut indexes = HashMap::new();
+
+        for (source_id, index) in source_visitor.into_indexes() {
+            indexes.try_insert(source_id, Arc::new(index))?;
         }
 
-        Ok((diagnostics, build, source_visitor, doc_visitor, unit))
+        let build = Build::new(sources)?;
+
+        let mut dependencies = source_loader.probed;
+        dependencies.insert(url.clone());
+        build.visit(&mut dependencies);
+
+        Ok(BuildOutput {
+            build,
+            dependencies,
+            diagnostics,
+            indexes,
+            docs: Arc::new(doc_visitor),
+            unit: unit.ok(),
+            parse_cache,
+        })
     }
 }
 
 /// Emit diagnostics workspace.
 fn emit_workspace(
-    diagnostics: workspace::Diagnostics,
+    diagnostics: &workspace::Diagnostics,
     build: &Build,
     reporter: &mut Reporter,
 ) -> Result<()> {
@@ -530,7 +906,7 @@ fn emit_workspace(
         tracing::trace!(?diagnostic, "workspace diagnostic");
 
         let workspace::Diagnostic::Fatal(f) = diagnostic;
-        report(build, reporter, f.source_id(), f.error(), to_error)?;
+        report(build, reporter, f.source_id(), f.error(), &[], to_error)?;
     }
 
     Ok(())
@@ -538,7 +914,7 @@ fn emit_workspace(
 
 /// Emit regular compile diagnostics.
 fn emit_scripts(
-    diagnostics: crate::Diagnostics,
+    diagnostics: &crate::Diagnostics,
     build: &Build,
     reporter: &mut Reporter,
 ) -> Result<()> {
@@ -558,7 +934,7 @@ fn emit_scripts(
         match diagnostic {
             Diagnostic::Fatal(f) => match f.kind() {
                 FatalDiagnosticKind::CompileError(e) => {
-                    report(build, reporter, f.source_id(), e, to_error)?;
+                    report(build, reporter, f.source_id(), e, f.suggestions(), to_error)?;
                 }
                 FatalDiagnosticKind::LinkError(e) => match e {
                     LinkerError::MissingFunction { hash, spans } => {
@@ -587,8 +963,18 @@ fn emit_scripts(
                     report_without_span(build, reporter, f.source_id(), e, to_error)?;
                 }
             },
+            Diagnostic::Warning(e) if e.level() == LintLevel::Deny => {
+                report(build, reporter, e.source_id(), e, e.suggestions(), to_error)?;
+            }
             Diagnostic::Warning(e) => {
-                report(build, reporter, e.source_id(), e, to_warning)?;
+                report(
+                    build,
+                    reporter,
+                    e.source_id(),
+                    e,
+                    e.suggestions(),
+                    to_warning,
+                )?;
             }
             Diagnostic::RuntimeWarning(_) => {}
         }
@@ -606,6 +992,9 @@ pub(super) struct Workspace {
     sources: HashMap<Url, Source>,
     /// A source that has been removed.
     removed: Vec<Url>,
+    /// Sources which have been opened, modified, closed, or changed on disk
+    /// since the last rebuild.
+    changed: HashSet<Url>,
 }
 
 impl Workspace {
@@ -620,11 +1009,13 @@ impl Workspace {
             content: Rope::from_str(text.as_str()),
             index: Default::default(),
             build_sources: None,
+            build_source_id: None,
             language,
             unit: None,
             docs: None,
         };
 
+        self.changed.insert(url.clone());
         self.sources.try_insert(url, source)
     }
 
@@ -633,19 +1024,27 @@ impl Workspace {
         self.sources.get(url)
     }
 
-    /// Get the mutable source at the given url.
+    /// Get the mutable source at the given url, marking it as changed.
     pub(super) fn get_mut(&mut self, url: &Url) -> Option<&mut Source> {
-        self.sources.get_mut(url)
+        let source = self.sources.get_mut(url)?;
+        self.changed.insert(url.clone());
+        Some(source)
     }
 
     /// Remove the given url as a source.
     pub(super) fn remove(&mut self, url: &Url) -> Result<()> {
         if self.sources.remove(url).is_some() {
+            self.changed.insert(url.clone());
             self.removed.try_push(url.clone())?;
         }
 
         Ok(())
     }
+
+    /// Mark the file at the given url as changed on disk.
+    pub(super) fn mark_changed(&mut self, url: Url) {
+        self.changed.insert(url);
+    }
 }
 
 /// A single open source.
@@ -653,10 +1052,12 @@ pub(super) struct Source {
     /// The content of the current source.
     content: Rope,
     /// Indexes used to answer queries.
-    index: Index,
+    index: Arc<Index>,
     /// Loaded Rune sources for this source file. Will be present after the
     /// source file has been built.
     build_sources: Option<Arc<crate::Sources>>,
+    /// The id of this source in `build_sources`.
+    build_source_id: Option<SourceId>,
     /// The language of the source.
     language: Language,
     /// The compiled unit
@@ -666,18 +1067,119 @@ pub(super) struct Source {
 }
 
 impl Source {
-    /// Find the definition at the given span.
-    pub(super) fn find_definition_at(&self, span: Span) -> Option<&Definition> {
-        let (found_span, definition) = self.index.definitions.range(..=span).next_back()?;
+    /// Find the definition at the given span, and the span it was found at.
+    pub(super) fn find_definition_at(&self, span: Span) -> Option<(Span, &Definition)> {
+        // Definitions starting where the span starts sort after it if they're
+        // longer, so we look for the last one starting at or before it.
+        let (found_span, definition) = self
+            .index
+            .definitions
+            .range(..=Span::new(span.start, u32::MAX))
+            .next_back()?;
 
         if span.start >= found_span.start && span.end <= found_span.end {
             tracing::trace!("found {:?}", definition);
-            return Some(definition);
+            return Some((*found_span, definition));
         }
 
         None
     }
 
+    /// Get the source text a definition points to, as it was when the source
+    /// was last built.
+    pub(super) fn definition_text(&self, definition: &Definition) -> Option<&str> {
+        self.build_sources
+            .as_ref()?
+            .source(definition.source.source_id(), definition.source.span())
+    }
+
+    /// Get the text of this source as it was when it was last built, which is
+    /// what the spans in its index refer to.
+    pub(super) fn build_text(&self) -> Option<&str> {
+        let sources = self.build_sources.as_ref()?;
+        Some(sources.get(self.build_source_id?)?.as_str())
+    }
+
+    /// Get the indexes built for this source.
+    pub(super) fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Get the compiled unit this source was last built into.
+    pub(super) fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
+    /// Find the identifier surrounding the given offset.
+    pub(super) fn ident_at(&self, offset: usize) -> alloc::Result<Option<(Span, String)>> {
+        fn is_ident(c: char) -> bool {
+            c.is_alphanumeric() || c == '_'
+        }
+
+        let Ok(offset) = self.content.try_byte_to_char(offset) else {
+            return Ok(None);
+        };
+
+        let mut start = offset;
+
+        while start > 0 && is_ident(self.content.char(start - 1)) {
+            start -= 1;
+        }
+
+        let mut end = offset;
+
+        while end < self.content.len_chars() && is_ident(self.content.char(end)) {
+            end += 1;
+        }
+
+        if start == end {
+            return Ok(None);
+        }
+
+        let ident = self
+            .content
+            .slice(start..end)
+            .chars()
+            .try_collect::<String>()?;
+
+        let span = Span::new(
+            self.content.char_to_byte(start),
+            self.content.char_to_byte(end),
+        );
+
+        Ok(Some((span, ident)))
+    }
+
+    /// Get the first non-whitespace character preceding the given span.
+    pub(super) fn char_before(&self, span: Span) -> Option<char> {
+        let start = self
+            .content
+            .try_byte_to_char(span.start.into_usize())
+            .ok()?;
+        self.content
+            .chars_at(start)
+            .reversed()
+            .find(|c| !c.is_whitespace())
+    }
+
+    /// Convert a span in the current content into an lsp range.
+    pub(super) fn span_to_lsp_range(&self, span: Span) -> Option<lsp::Range> {
+        Some(lsp::Range::new(
+            self.byte_to_lsp_position(span.start.into_usize())?,
+            self.byte_to_lsp_position(span.end.into_usize())?,
+        ))
+    }
+
+    /// Convert a byte offset in the current content into an lsp position.
+    pub(super) fn byte_to_lsp_position(&self, offset: usize) -> Option<lsp::Position> {
+        let char = self.content.try_byte_to_char(offset).ok()?;
+        let line = self.content.try_char_to_line(char).ok()?;
+        let line_start = self.content.line_to_char(line);
+        let character =
+            self.content.char_to_utf16_cu(char) - self.content.char_to_utf16_cu(line_start);
+        Some(lsp::Position::new(line as u32, character as u32))
+    }
+
     /// Modify the given lsp range in the file.
     pub(super) fn modify_lsp_range(&mut self, range: lsp::Range, content: &str) -> Result<()> {
         let start = rope_utf16_position(&self.content, range.start)?;
@@ -691,11 +1193,26 @@ impl Source {
         Ok(())
     }
 
+    /// Convert an lsp range into a span in the current content.
+    pub(super) fn lsp_range_to_span(&self, range: lsp::Range) -> Span {
+        Span::new(
+            self.lsp_position_to_offset(range.start),
+            self.lsp_position_to_offset(range.end),
+        )
+    }
+
     /// Offset in the rope to lsp position.
-    fn lsp_position_to_offset(&self, position: lsp::Position) -> usize {
-        let line = self.content.line_to_char(position.line as usize);
-        self.content
-            .utf16_cu_to_char(line + position.character as usize)
+    ///
+    /// Positions past the end of the content are clamped to the end.
+    pub(super) fn lsp_position_to_offset(&self, position: lsp::Position) -> usize {
+        let Ok(line) = self.content.try_line_to_char(position.line as usize) else {
+            return self.content.len_bytes();
+        };
+
+        let line = self.content.char_to_utf16_cu(line);
+        let offset = (line + position.character as usize).min(self.content.len_utf16_cu());
+        let char = self.content.utf16_cu_to_char(offset);
+        self.content.char_to_byte(char)
     }
 
     /// Iterate over the text chunks in the source.
@@ -736,7 +1253,7 @@ impl fmt::Display for Source {
 }
 
 /// Convert the given span into an lsp range.
-fn span_to_lsp_range(source: &crate::Source, span: Span) -> Option<lsp::Range> {
+pub(super) fn span_to_lsp_range(source: &crate::Source, span: Span) -> Option<lsp::Range> {
     let (line, character) = source.pos_to_utf16cu_linecol(span.start.into_usize());
     let start = lsp::Position::new(line as u32, character as u32);
     let (line, character) = source.pos_to_utf16cu_linecol(span.end.into_usize());
@@ -779,6 +1296,7 @@ fn report<E, R>(
     reporter: &mut Reporter,
     source_id: SourceId,
     error: E,
+    suggestions: &[Suggestion],
     report: R,
 ) -> Result<()>
 where
@@ -799,7 +1317,11 @@ where
         return Ok(());
     };
 
-    reporter.entry(url).try_push(report(range, error)?)?;
+    let diagnostic = report(range, error)?;
+    reporter
+        .fixes
+        .insert(source, url, &diagnostic, suggestions)?;
+    reporter.entry(url).try_push(diagnostic)?;
     Ok(())
 }
 
@@ -866,7 +1388,35 @@ where
 #[derive(Default)]
 pub(super) struct Index {
     /// Spans mapping to their corresponding definitions.
-    definitions: BTreeMap<Span, Definition>,
+    pub(super) definitions: BTreeMap<Span, Definition>,
+    /// Spans mapping to named fields used in object literals and patterns.
+    pub(super) fields: BTreeMap<Span, FieldUse>,
+    /// Items declared in the source.
+    pub(super) declarations: Vec<Declaration>,
+}
+
+/// A named field used in an object literal or an object pattern.
+#[derive(Debug)]
+pub(super) struct FieldUse {
+    /// The hash of the struct or variant the field belongs to.
+    pub(super) hash: Hash,
+    /// The name of the field.
+    pub(super) name: Box<str>,
+}
+
+/// An item declared in a source.
+#[derive(Debug)]
+pub(super) struct Declaration {
+    /// The span of the whole declaration.
+    pub(super) span: Span,
+    /// The hash of the declared item.
+    pub(super) hash: Hash,
+    /// The declared item.
+    pub(super) item: ItemBuf,
+    /// The kind of the declared item.
+    pub(super) kind: DefinitionKind,
+    /// Named fields declared by a struct or a struct variant.
+    pub(super) fields: Vec<Box<str>>,
 }
 
 /// A definition source.
@@ -878,12 +1428,14 @@ pub(super) enum DefinitionSource {
     Location(Location),
     /// A complete compile source.
     SourceMeta(SourceMeta),
+    /// An item provided by the native context, which has no source.
+    Context,
 }
 
 impl DefinitionSource {
-    fn span(&self) -> Span {
+    pub(super) fn span(&self) -> Span {
         match self {
-            Self::Source(..) => Span::empty(),
+            Self::Source(..) | Self::Context => Span::empty(),
             Self::Location(location) => location.span,
             Self::SourceMeta(compile_source) => compile_source.location.span,
         }
@@ -894,6 +1446,7 @@ impl DefinitionSource {
             Self::Source(source_id) => *source_id,
             Self::Location(location) => location.source_id,
             Self::SourceMeta(compile_source) => compile_source.location.source_id,
+            Self::Context => SourceId::empty(),
         }
     }
 
@@ -911,6 +1464,17 @@ pub(super) struct Definition {
     pub(super) kind: DefinitionKind,
     /// The id of the source id the definition corresponds to.
     pub(super) source: DefinitionSource,
+    /// The hash of the item being defined, if it is a meta item.
+    pub(super) hash: Option<Hash>,
+    /// The item being defined, if it is a meta item.
+    pub(super) item: Option<ItemBuf>,
+}
+
+impl Definition {
+    /// Test if the definition is provided by the native context.
+    pub(super) fn is_context(&self) -> bool {
+        matches!(self.source, DefinitionSource::Context)
+    }
 }
 
 #[derive(Debug, TryClone, Clone, Copy)]
@@ -934,6 +1498,10 @@ pub(super) enum DefinitionKind {
     Function,
     /// An associated function.
     AssociatedFunction,
+    /// A constant.
+    Const,
+    /// An opaque type.
+    Type,
     /// A local variable.
     Local,
     /// A module that can be jumped to.
@@ -953,11 +1521,58 @@ impl Visitor {
 }
 
 impl CompileVisitor for Visitor {
-    fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) -> Result<(), MetaError> {
+    fn register_meta(&mut self, meta: MetaRef<'_>) -> Result<(), MetaError> {
+        if meta.context {
+            return Ok(());
+        }
+
         let Some(source) = meta.source else {
             return Ok(());
         };
 
+        let (kind, fields) = match &meta.kind {
+            meta::Kind::Struct { fields, .. } => (DefinitionKind::Struct, named_fields(fields)?),
+            meta::Kind::Variant { fields, .. } => {
+                (DefinitionKind::StructVariant, named_fields(fields)?)
+            }
+            meta::Kind::Enum { .. } => (DefinitionKind::Enum, Vec::new()),
+            meta::Kind::Function {
+                associated: None, ..
+            }
+            | meta::Kind::ConstFn { .. } => (DefinitionKind::Function, Vec::new()),
+            meta::Kind::Function {
+                associated: Some(..),
+                ..
+            } => (DefinitionKind::AssociatedFunction, Vec::new()),
+            meta::Kind::Const => (DefinitionKind::Const, Vec::new()),
+            meta::Kind::Module => (DefinitionKind::Module, Vec::new()),
+            _ => return Ok(()),
+        };
+
+        let declaration = Declaration {
+            span: source.location.span,
+            hash: meta.hash,
+            item: meta.item.try_to_owned()?,
+            kind,
+            fields,
+        };
+
+        let index = self
+            .indexes
+            .entry(source.location.source_id)
+            .or_try_default()?;
+
+        index.declarations.try_push(declaration)?;
+        Ok(())
+    }
+
+    fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) -> Result<(), MetaError> {
+        let source = match meta.source {
+            Some(source) => DefinitionSource::SourceMeta(source.try_clone()?),
+            None if meta.context => DefinitionSource::Context,
+            None => return Ok(()),
+        };
+
         let kind = match &meta.kind {
             meta::Kind::Struct {
                 fields: meta::Fields::Empty,
@@ -991,12 +1606,16 @@ impl CompileVisitor for Visitor {
                 associated: Some(..),
                 ..
             } => DefinitionKind::AssociatedFunction,
+            meta::Kind::Const => DefinitionKind::Const,
+            meta::Kind::Type { .. } => DefinitionKind::Type,
             _ => return Ok(()),
         };
 
         let definition = Definition {
             kind,
-            source: DefinitionSource::SourceMeta(source.try_clone()?),
+            source,
+            hash: Some(meta.hash),
+            item: Some(meta.item.try_to_owned()?),
         };
 
         let location = location.location();
@@ -1019,6 +1638,8 @@ impl CompileVisitor for Visitor {
         let definition = Definition {
             kind: DefinitionKind::Local,
             source: DefinitionSource::Location(Location::new(source_id, var_span.span())),
+            hash: None,
+            item: None,
         };
 
         let index = self.indexes.entry(source_id).or_try_default()?;
@@ -1030,12 +1651,32 @@ impl CompileVisitor for Visitor {
         Ok(())
     }
 
+    fn visit_field_use(
+        &mut self,
+        location: &dyn Located,
+        hash: Hash,
+        field: &str,
+    ) -> Result<(), MetaError> {
+        let location = location.location();
+
+        let field = FieldUse {
+            hash,
+            name: field.try_into()?,
+        };
+
+        let index = self.indexes.entry(location.source_id).or_try_default()?;
+        index.fields.insert(location.span, field);
+        Ok(())
+    }
+
     fn visit_mod(&mut self, location: &dyn Located) -> Result<(), MetaError> {
         let location = location.location();
 
         let definition = Definition {
             kind: DefinitionKind::Module,
             source: DefinitionSource::Source(location.source_id),
+            hash: None,
+            item: None,
         };
 
         let index = self.indexes.entry(location.source_id).or_try_default()?;
@@ -1048,9 +1689,27 @@ impl CompileVisitor for Visitor {
     }
 }
 
+/// Collect the names of named fields, in the order they are declared.
+fn named_fields(fields: &meta::Fields) -> alloc::Result<Vec<Box<str>>> {
+    let mut names = Vec::new();
+
+    if let meta::Fields::Named(named) = fields {
+        let mut fields = named.fields.iter().try_collect::<Vec<_>>()?;
+        fields.sort_by_key(|(_, field)| field.position);
+
+        for (name, _) in fields {
+            names.try_push(name.try_clone()?)?;
+        }
+    }
+
+    Ok(names)
+}
+
 struct ScriptSourceLoader<'a> {
     sources: &'a HashMap<Url, Source>,
     base: compile::FileSourceLoader,
+    /// Every URL a module was loaded or tried to be loaded from.
+    probed: HashSet<Url>,
 }
 
 impl<'a> ScriptSourceLoader<'a> {
@@ -1059,6 +1718,7 @@ impl<'a> ScriptSourceLoader<'a> {
         Self {
             sources,
             base: compile::FileSourceLoader::new(),
+            probed: HashSet::new(),
         }
     }
 
@@ -1070,6 +1730,10 @@ impl<'a> ScriptSourceLoader<'a> {
     ) -> compile::Result<Option<[(Url, PathBuf); 2]>> {
         let mut base = root.try_to_owned()?;
 
+        if !base.pop() {
+            return Ok(None);
+        }
+
         let mut it = item.iter().peekable();
         let mut last = None;
 
@@ -1118,6 +1782,10 @@ impl<'a> crate::compile::SourceLoader for ScriptSourceLoader<'a> {
         tracing::trace!("load {} (root: {})", item, root.display());
 
         if let Some(candidates) = Self::candidates(root, item, span)? {
+            for (url, _) in &candidates {
+                self.probed.insert(url.clone());
+            }
+
             for (url, path) in candidates {
                 if let Some(s) = self.sources.get(&url) {
                     return Ok(crate::Source::with_path(url, s.try_to_string()?, path)?);
@@ -1156,3 +1824,133 @@ impl<'a> workspace::SourceLoader for WorkspaceSourceLoader<'a> {
         self.base.load(span, path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ::rust_alloc::string::String;
+    use ::rust_alloc::vec::Vec;
+
+    use crate::languageserver::testing::{range, url, Harness};
+    use crate::support::Result;
+    use crate::Context;
+
+    use super::State;
+
+    const FIRST: &str = "pub fn first() {\n    1\n}\n";
+    const SECOND: &str = "pub fn second() {\n    2\n}\n";
+
+    /// Get the sources the given uri was last built from.
+    fn build_sources(state: &State<'_>, uri: &lsp::Url) -> Arc<crate::Sources> {
+        let source = state.workspace.get(uri).expect("expected open source");
+        source.build_sources.clone().expect("expected a build")
+    }
+
+    /// Get the sources of the cached build of the script at the given uri.
+    fn cached_sources(state: &State<'_>, uri: &lsp::Url) -> Arc<crate::Sources> {
+        state.builds[uri].build.sources.clone()
+    }
+
+    /// Get the names of the symbols declared in the given uri.
+    fn symbols(state: &State<'_>, uri: &lsp::Url) -> Result<Vec<String>> {
+        let symbols = state.document_symbols(uri)?;
+        Ok(symbols.into_iter().map(|s| s.name).collect())
+    }
+
+    #[test]
+    fn test_rebuild_changed_sources() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let first = url("first.rn");
+        let second = url("second.rn");
+        harness.open(&mut state, &[(&first, FIRST), (&second, SECOND)])?;
+
+        let first_sources = build_sources(&state, &first);
+        let second_sources = build_sources(&state, &second);
+
+        // Nothing changed, so both builds are reused.
+        harness.block_on(state.rebuild())?;
+        assert!(Arc::ptr_eq(&first_sources, &build_sources(&state, &first)));
+        assert!(Arc::ptr_eq(
+            &second_sources,
+            &build_sources(&state, &second)
+        ));
+
+        let params = lsp::DidChangeTextDocumentParams {
+            text_document: lsp::VersionedTextDocumentIdentifier {
+                uri: second.clone(),
+                version: 1,
+            },
+            content_changes: vec![lsp::TextDocumentContentChangeEvent {
+                range: Some(range(SECOND, "second", 0)),
+                range_length: None,
+                text: "renamed".into(),
+            }],
+        };
+
+        harness.block_on(super::super::did_change_text_document(&mut state, params))?;
+        harness.block_on(state.rebuild())?;
+
+        // Only the changed source is rebuilt.
+        assert!(Arc::ptr_eq(&first_sources, &build_sources(&state, &first)));
+        assert!(!Arc::ptr_eq(
+            &second_sources,
+            &build_sources(&state, &second)
+        ));
+
+        assert_eq!(symbols(&state, &first)?, ["first"]);
+        assert_eq!(symbols(&state, &second)?, ["renamed"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rebuild_dependents() -> Result<()> {
+        const MAIN: &str = "mod util;\n\npub fn main() {\n    util::value()\n}\n";
+        const UTIL: &str = "pub fn value() {\n    1\n}\n";
+
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        let util = url("util.rn");
+        harness.open(&mut state, &[(&main, MAIN)])?;
+
+        let main_sources = cached_sources(&state, &main);
+        assert!(state.builds[&main].diagnostics.has_error());
+
+        // Builds with errors are reused too as long as nothing they depend on
+        // changes.
+        harness.block_on(state.rebuild())?;
+        assert!(Arc::ptr_eq(&main_sources, &cached_sources(&state, &main)));
+
+        // Creating the missing module rebuilds the script which failed to
+        // load it.
+        harness.open(&mut state, &[(&util, UTIL)])?;
+        assert!(!Arc::ptr_eq(&main_sources, &cached_sources(&state, &main)));
+        assert!(!state.builds[&main].diagnostics.has_error());
+        assert_eq!(state.builds[&main].parse_cache.len(), 2);
+
+        let main_sources = cached_sources(&state, &main);
+
+        let params = lsp::DidChangeTextDocumentParams {
+            text_document: lsp::VersionedTextDocumentIdentifier {
+                uri: util.clone(),
+                version: 1,
+            },
+            content_changes: vec![lsp::TextDocumentContentChangeEvent {
+                range: Some(range(UTIL, "value", 0)),
+                range_length: None,
+                text: "other".into(),
+            }],
+        };
+
+        harness.block_on(super::super::did_change_text_document(&mut state, params))?;
+        harness.block_on(state.rebuild())?;
+
+        // Modifying the module rebuilds the script which depends on it.
+        assert!(!Arc::ptr_eq(&main_sources, &cached_sources(&state, &main)));
+        assert!(state.builds[&main].diagnostics.has_error());
+        assert_eq!(state.builds[&main].parse_cache.len(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,467 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use lsp::Url;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, Box, HashMap, String, Vec};
+use crate::ast::Span;
+use crate::compile::{Item, ItemBuf};
+use crate::SourceId;
+
+use super::references::{item_name, Idents};
+use super::state::{span_to_lsp_range, DefinitionKind, Index};
+
+/// An item declared somewhere in the workspace.
+struct Symbol {
+    /// The declared item.
+    item: ItemBuf,
+    /// The name of the item.
+    name: Box<str>,
+    /// The kind of the symbol.
+    kind: lsp::SymbolKind,
+    /// The range of the whole declaration.
+    range: lsp::Range,
+    /// The range of the name of the declaration.
+    selection_range: lsp::Range,
+    /// Named fields declared by the item, and the range of their names.
+    fields: Vec<(Box<str>, lsp::Range)>,
+}
+
+/// Every item declared in the workspace, grouped by the source they are
+/// declared in.
+#[derive(Default)]
+pub(super) struct Symbols {
+    /// Symbols in each source, ordered by where they are declared.
+    by_url: HashMap<Url, BTreeMap<Span, Symbol>>,
+}
+
+impl Symbols {
+    /// Index all declarations found while building the given sources.
+    pub(super) fn index(
+        &mut self,
+        sources: &crate::Sources,
+        id_to_url: &HashMap<SourceId, Url>,
+        indexes: &HashMap<SourceId, Arc<Index>>,
+    ) -> Result<()> {
+        for (source_id, index) in indexes {
+            let (Some(url), Some(source)) = (id_to_url.get(source_id), sources.get(*source_id))
+            else {
+                continue;
+            };
+
+            let symbols = self.by_url.entry(url.clone()).or_try_default()?;
+            let idents = Idents::lex(source.as_str())?;
+
+            for declaration in &index.declarations {
+                // The same source might be part of several builds, in which
+                // case the first one wins.
+                if symbols.contains_key(&declaration.span) {
+                    continue;
+                }
+
+                let Some(name) = item_name(&declaration.item) else {
+                    continue;
+                };
+
+                let Some(name_span) = idents.find(declaration.span, name) else {
+                    continue;
+                };
+
+                let (Some(range), Some(selection_range)) = (
+                    span_to_lsp_range(source, declaration.span),
+                    span_to_lsp_range(source, name_span),
+                ) else {
+                    continue;
+                };
+
+                let mut fields = Vec::new();
+
+                // Fields are declared in order after the name of the item.
+                let mut rest = Span::new(name_span.end, declaration.span.end);
+
+                for field in &declaration.fields {
+                    let Some(span) = idents.find(rest, field) else {
+                        break;
+                    };
+
+                    if let Some(range) = span_to_lsp_range(source, span) {
+                        fields.try_push((field.try_clone()?, range))?;
+                    }
+
+                    rest = Span::new(span.end, declaration.span.end);
+                }
+
+                let symbol = Symbol {
+                    item: declaration.item.try_clone()?,
+                    name: name.try_into()?,
+                    kind: symbol_kind(declaration.kind),
+                    range,
+                    selection_range,
+                    fields,
+                };
+
+                symbols.insert(declaration.span, symbol);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the hierarchy of symbols declared in the given source.
+    ///
+    /// Items are nested under the closest parent item declared in the same
+    /// source, so associated functions end up under their type and items in
+    /// inline modules under the module.
+    pub(super) fn document_symbols(&self, url: &Url) -> Result<Vec<lsp::DocumentSymbol>> {
+        let Some(symbols) = self.by_url.get(url) else {
+            return Ok(Vec::new());
+        };
+
+        let symbols = symbols.values().try_collect::<Vec<_>>()?;
+
+        let mut by_item = HashMap::<&Item, usize>::new();
+
+        for (n, symbol) in symbols.iter().enumerate() {
+            by_item.try_insert(&*symbol.item, n)?;
+        }
+
+        let mut roots = Vec::new();
+        let mut children = HashMap::<usize, Vec<usize>>::new();
+
+        for (n, symbol) in symbols.iter().enumerate() {
+            let mut parent = symbol.item.parent();
+
+            let found = loop {
+                let Some(item) = parent else {
+                    break None;
+                };
+
+                if let Some(&p) = by_item.get(item) {
+                    break Some(p);
+                }
+
+                parent = item.parent();
+            };
+
+            match found {
+                Some(p) => children.entry(p).or_try_default()?.try_push(n)?,
+                None => roots.try_push(n)?,
+            }
+        }
+
+        let mut output = Vec::new();
+
+        for n in roots {
+            output.try_push(build_document_symbol(&symbols, &children, n)?)?;
+        }
+
+        Ok(output)
+    }
+
+    /// Find all symbols in the workspace whose name fuzzily matches the given
+    /// query.
+    pub(super) fn workspace_symbols(&self, query: &str) -> Result<Vec<lsp::SymbolInformation>> {
+        let mut output = Vec::new();
+
+        for (url, symbols) in &self.by_url {
+            for symbol in symbols.values() {
+                if !fuzzy_match(query, &symbol.name) {
+                    continue;
+                }
+
+                let container_name = match symbol.item.parent() {
+                    Some(parent) if !parent.is_empty() => Some(parent.try_to_string()?.into_std()),
+                    _ => None,
+                };
+
+                #[allow(deprecated)]
+                output.try_push(lsp::SymbolInformation {
+                    name: symbol.name.as_ref().try_to_owned()?.into_std(),
+                    kind: symbol.kind,
+                    tags: None,
+                    deprecated: None,
+                    location: lsp::Location::new(url.clone(), symbol.selection_range),
+                    container_name,
+                })?;
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Recursively build a document symbol and its children.
+fn build_document_symbol(
+    symbols: &[&Symbol],
+    children: &HashMap<usize, Vec<usize>>,
+    n: usize,
+) -> Result<lsp::DocumentSymbol> {
+    let symbol = symbols[n];
+    let mut range = symbol.range;
+    let mut output = ::rust_alloc::vec::Vec::new();
+
+    for (name, field) in &symbol.fields {
+        output.push(new_document_symbol(
+            name,
+            lsp::SymbolKind::FIELD,
+            *field,
+            *field,
+            None,
+        )?);
+    }
+
+    for &child in children.get(&n).into_iter().flatten() {
+        let child = build_document_symbol(symbols, children, child)?;
+
+        // Modules are only declared with the span of their name, so make
+        // sure they enclose their items.
+        if symbol.kind == lsp::SymbolKind::MODULE {
+            range.start = range.start.min(child.range.start);
+            range.end = range.end.max(child.range.end);
+        }
+
+        output.push(child);
+    }
+
+    let children = if output.is_empty() {
+        None
+    } else {
+        Some(output)
+    };
+
+    Ok(new_document_symbol(
+        &symbol.name,
+        symbol.kind,
+        range,
+        symbol.selection_range,
+        children,
+    )?)
+}
+
+fn new_document_symbol(
+    name: &str,
+    kind: lsp::SymbolKind,
+    range: lsp::Range,
+    selection_range: lsp::Range,
+    children: Option<::rust_alloc::vec::Vec<lsp::DocumentSymbol>>,
+) -> alloc::Result<lsp::DocumentSymbol> {
+    #[allow(deprecated)]
+    Ok(lsp::DocumentSymbol {
+        name: String::try_from(name)?.into_std(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children,
+    })
+}
+
+/// Convert the kind of a declaration into a symbol kind.
+fn symbol_kind(kind: DefinitionKind) -> lsp::SymbolKind {
+    match kind {
+        DefinitionKind::EmptyStruct | DefinitionKind::TupleStruct | DefinitionKind::Struct => {
+            lsp::SymbolKind::STRUCT
+        }
+        DefinitionKind::UnitVariant
+        | DefinitionKind::TupleVariant
+        | DefinitionKind::StructVariant => lsp::SymbolKind::ENUM_MEMBER,
+        DefinitionKind::Enum => lsp::SymbolKind::ENUM,
+        DefinitionKind::Function => lsp::SymbolKind::FUNCTION,
+        DefinitionKind::AssociatedFunction => lsp::SymbolKind::METHOD,
+        DefinitionKind::Const => lsp::SymbolKind::CONSTANT,
+        DefinitionKind::Type => lsp::SymbolKind::CLASS,
+        DefinitionKind::Local => lsp::SymbolKind::VARIABLE,
+        DefinitionKind::Module => lsp::SymbolKind::MODULE,
+    }
+}
+
+/// Test if every character in the query appears in order in the name,
+/// ignoring case.
+fn fuzzy_match(query: &str, name: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| name.any(|c| c == q))
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rust_alloc::string::String;
+    use ::rust_alloc::vec::Vec;
+
+    use crate::languageserver::testing::{range, url, Harness};
+    use crate::support::Result;
+    use crate::Context;
+
+    const MAIN: &str = r#"
+struct Point { x, y }
+
+enum Shape {
+    Circle,
+}
+
+impl Point {
+    fn new() {
+        Point { x: 0, y: 0 }
+    }
+}
+
+const ORIGIN = 0;
+
+pub fn main() {
+    Point::new()
+}
+"#;
+
+    const OTHER: &str = r#"
+pub fn helper() {
+    1
+}
+"#;
+
+    /// Flatten document symbols into their depth, name, kind, range and
+    /// selection range.
+    fn flatten(
+        symbols: &[lsp::DocumentSymbol],
+        depth: usize,
+        out: &mut Vec<(usize, String, lsp::SymbolKind, lsp::Range, lsp::Range)>,
+    ) {
+        for symbol in symbols {
+            out.push((
+                depth,
+                symbol.name.clone(),
+                symbol.kind,
+                symbol.range,
+                symbol.selection_range,
+            ));
+
+            flatten(
+                symbol.children.as_deref().unwrap_or_default(),
+                depth + 1,
+                out,
+            );
+        }
+    }
+
+    #[test]
+    fn test_symbols() -> Result<()> {
+        let harness = Harness::new()?;
+        let mut state = harness.state(Context::with_default_modules()?);
+        let main = url("main.rn");
+        let other = url("other.rn");
+        harness.open(&mut state, &[(&main, MAIN), (&other, OTHER)])?;
+
+        let params = lsp::DocumentSymbolParams {
+            text_document: lsp::TextDocumentIdentifier { uri: main.clone() },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let Some(lsp::DocumentSymbolResponse::Nested(symbols)) =
+            harness.block_on(super::super::document_symbol(&mut state, params))?
+        else {
+            panic!("expected nested document symbols");
+        };
+
+        let mut actual = Vec::new();
+        flatten(&symbols, 0, &mut actual);
+
+        let symbol = |depth: usize, name: &str, kind, declaration: &str| {
+            let range = range(MAIN, declaration, 0);
+            (
+                depth,
+                String::from(name),
+                kind,
+                range,
+                self::range(MAIN, name, 0),
+            )
+        };
+
+        let expected = vec![
+            symbol(0, "Point", lsp::SymbolKind::STRUCT, "struct Point { x, y }"),
+            symbol(1, "x", lsp::SymbolKind::FIELD, "x"),
+            symbol(1, "y", lsp::SymbolKind::FIELD, "y"),
+            symbol(
+                1,
+                "new",
+                lsp::SymbolKind::FUNCTION,
+                "fn new() {\n        Point { x: 0, y: 0 }\n    }",
+            ),
+            symbol(
+                0,
+                "Shape",
+                lsp::SymbolKind::ENUM,
+                "enum Shape {\n    Circle,\n}",
+            ),
+            symbol(1, "Circle", lsp::SymbolKind::ENUM_MEMBER, "Circle"),
+            symbol(0, "ORIGIN", lsp::SymbolKind::CONSTANT, "const ORIGIN = 0"),
+            symbol(
+                0,
+                "main",
+                lsp::SymbolKind::FUNCTION,
+                "pub fn main() {\n    Point::new()\n}",
+            ),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let mut workspace_symbols = |query: &str| {
+            let params = lsp::WorkspaceSymbolParams {
+                query: query.into(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            };
+
+            let Some(lsp::WorkspaceSymbolResponse::Flat(symbols)) =
+                harness.block_on(super::super::workspace_symbol(&mut state, params))?
+            else {
+                panic!("expected flat workspace symbols");
+            };
+
+            let symbols = symbols
+                .into_iter()
+                .map(|s| (s.name, s.kind, s.location, s.container_name))
+                .collect::<Vec<_>>();
+
+            Ok::<_, crate::support::Error>(symbols)
+        };
+
+        assert_eq!(
+            workspace_symbols("pnt")?,
+            [(
+                String::from("Point"),
+                lsp::SymbolKind::STRUCT,
+                lsp::Location::new(main.clone(), range(MAIN, "Point", 0)),
+                None,
+            )]
+        );
+
+        assert_eq!(
+            workspace_symbols("CIRC")?,
+            [(
+                String::from("Circle"),
+                lsp::SymbolKind::ENUM_MEMBER,
+                lsp::Location::new(main.clone(), range(MAIN, "Circle", 0)),
+                Some(String::from("Shape")),
+            )]
+        );
+
+        assert_eq!(
+            workspace_symbols("helper")?,
+            [(
+                String::from("helper"),
+                lsp::SymbolKind::FUNCTION,
+                lsp::Location::new(other.clone(), range(OTHER, "helper", 0)),
+                None,
+            )]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+//! Helpers to drive the language server state in tests.
+
+use core::future::Future;
+
+use lsp::Url;
+use tokio::runtime::Runtime;
+use tokio::sync::Notify;
+
+use crate::alloc::String;
+use crate::languageserver::connection;
+use crate::languageserver::state::State;
+use crate::languageserver::Language;
+use crate::support::Result;
+use crate::{Context, Options};
+
+/// A harness owning everything needed to construct a [`State`].
+pub(super) struct Harness {
+    runtime: Runtime,
+    notify: Notify,
+}
+
+impl Harness {
+    /// Construct a new harness.
+    pub(super) fn new() -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+
+        Ok(Self {
+            runtime,
+            notify: Notify::new(),
+        })
+    }
+
+    /// Construct a new server state building scripts with the given context.
+    pub(super) fn state(&self, context: Context) -> State<'_> {
+        State::new(
+            connection::sink(),
+            &self.notify,
+            context,
+            Options::default(),
+        )
+    }
+
+    /// Run the given future to completion.
+    pub(super) fn block_on<F>(&self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        self.runtime.block_on(future)
+    }
+
+    /// Open the given sources in the state and rebuild it.
+    pub(super) fn open(&self, state: &mut State<'_>, sources: &[(&Url, &str)]) -> Result<()> {
+        for (url, text) in sources {
+            let text = String::try_from(*text)?;
+
+            state
+                .workspace_mut()
+                .insert_source((*url).clone(), text, Language::Rune)?;
+        }
+
+        self.block_on(state.rebuild())?;
+        Ok(())
+    }
+}
+
+/// Construct the URL of a file with the given name in the test workspace.
+pub(super) fn url(name: &str) -> Url {
+    Url::parse(&format!("file:///workspace/{name}")).expect("valid url")
+}
+
+/// Get the position of the `n`th occurrence of `needle` in the given ASCII
+/// text.
+pub(super) fn position(text: &str, needle: &str, n: usize) -> lsp::Position {
+    range(text, needle, n).start
+}
+
+/// Get the range of the `n`th occurrence of `needle` in the given ASCII text.
+pub(super) fn range(text: &str, needle: &str, n: usize) -> lsp::Range {
+    let (offset, _) = text
+        .match_indices(needle)
+        .nth(n)
+        .unwrap_or_else(|| panic!("missing occurrence {n} of `{needle}`"));
+
+    lsp::Range {
+        start: offset_to_position(text, offset),
+        end: offset_to_position(text, offset + needle.len()),
+    }
+}
+
+fn offset_to_position(text: &str, offset: usize) -> lsp::Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let character = offset - before.rfind('\n').map_or(0, |n| n + 1);
+
+    lsp::Position {
+        line: line as u32,
+        character: character as u32,
+    }
+}
@@ -287,6 +287,12 @@ pub(crate) use rune_macros::__internal_impl_any;
 ///   instance function that can be defined externally.
 /// * Instance functions can be made a protocol function
 ///   `#[rune::function(protocol = STRING_DISPLAY)]`.
+/// * A function can be made callable from constant contexts using
+///   `#[rune::function(const)]`, in which case it may be called by path when
+///   evaluating `const` items at compile time. Native types may be passed
+///   between constant functions, but the final value of a `const` item must be
+///   representable as a [`ConstValue`][crate::runtime::ConstValue], so a
+///   constant can't hold a native type.
 ///
 /// # Instance and associated functions
 ///
@@ -197,6 +197,7 @@ pub(crate) enum ModuleItemKind {
     Macro(ModuleMacro),
     AttributeMacro(ModuleAttributeMacro),
     InternalEnum(InternalEnum),
+    Trait(ModuleTrait),
 }
 
 pub(crate) struct ModuleItem {
@@ -208,6 +209,7 @@ pub(crate) struct ModuleItem {
 #[derive(TryClone)]
 pub(crate) struct ModuleFunction {
     pub(crate) handler: Arc<FunctionHandler>,
+    pub(crate) is_const: bool,
     #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
@@ -319,6 +321,7 @@ impl fmt::Debug for ItemMut<'_> {
 /// * [`Module::function_meta`].
 pub struct ItemFnMut<'a> {
     docs: &'a mut Docs,
+    is_const: &'a mut bool,
     #[cfg(feature = "doc")]
     deprecated: &'a mut Option<Box<str>>,
     #[cfg(feature = "doc")]
@@ -370,6 +373,22 @@ impl ItemFnMut<'_> {
         Ok(self)
     }
 
+    /// Mark the given function as callable in constant contexts, like in the
+    /// initializer of a `const` item.
+    ///
+    /// A constant function is called once during compilation and its return
+    /// value is stored in the unit, so it should be free of side effects.
+    ///
+    /// Values of native types can be passed between constant functions, but
+    /// since they can't be stored as a [`ConstValue`] they can't be the final
+    /// value of a `const` item.
+    ///
+    /// This is what `#[rune::function(const)]` does.
+    pub fn is_const(self, is_const: bool) -> Self {
+        *self.is_const = is_const;
+        self
+    }
+
     /// Indicate the number of arguments this function accepts.
     pub fn args(self, #[cfg_attr(not(feature = "doc"), allow(unused))] args: usize) -> Self {
         #[cfg(feature = "doc")]
@@ -552,6 +571,79 @@ where
     }
 }
 
+/// A trait declared in a module.
+#[derive(Default)]
+pub(crate) struct ModuleTrait {
+    /// The names of functions which implementations have to provide.
+    pub(crate) functions: Vec<Box<str>>,
+}
+
+/// Access trait metadata mutably.
+///
+/// This is returned by [`Module::define_trait`].
+pub struct TraitMut<'a> {
+    trait_: &'a mut ModuleTrait,
+    common: &'a mut ModuleItemCommon,
+}
+
+impl TraitMut<'_> {
+    /// Set documentation for an inserted trait.
+    ///
+    /// This completely replaces any existing documentation.
+    pub fn docs<I>(self, docs: I) -> Result<Self, ContextError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.common.docs.set_docs(docs)?;
+        Ok(self)
+    }
+
+    /// Set static documentation for an inserted trait.
+    ///
+    /// This completely replaces any existing documentation.
+    pub fn static_docs(self, docs: &'static [&'static str]) -> Result<Self, ContextError> {
+        self.common.docs.set_docs(docs)?;
+        Ok(self)
+    }
+
+    /// Mark the given trait as deprecated.
+    pub fn deprecated<S>(
+        self,
+        #[cfg_attr(not(feature = "doc"), allow(unused))] deprecated: S,
+    ) -> Result<Self, ContextError>
+    where
+        S: AsRef<str>,
+    {
+        #[cfg(feature = "doc")]
+        {
+            self.common.deprecated = Some(deprecated.as_ref().try_into()?);
+        }
+
+        Ok(self)
+    }
+
+    /// Declare a function which every implementation of the trait has to
+    /// provide.
+    pub fn function(self, name: &str) -> Result<Self, ContextError> {
+        if self.trait_.functions.iter().any(|f| f.as_ref() == name) {
+            return Err(ContextError::ConflictingTraitFunction {
+                name: name.try_into()?,
+            });
+        }
+
+        self.trait_.functions.try_push(name.try_into()?)?;
+        Ok(self)
+    }
+}
+
+impl fmt::Debug for TraitMut<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraitMut").finish_non_exhaustive()
+    }
+}
+
 /// Access internal enum metadata mutably.
 pub struct InternalEnumMut<'a, T>
 where
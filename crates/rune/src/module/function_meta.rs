@@ -523,6 +523,8 @@ pub struct FunctionMetaData {
     pub docs: &'static [&'static str],
     #[doc(hidden)]
     pub arguments: &'static [&'static str],
+    #[doc(hidden)]
+    pub is_const: bool,
 }
 
 /// Trait implement allowing the collection of function argument types.
@@ -16,7 +16,7 @@ use crate::module::{
     AssociatedKey, Async, EnumMut, Function, FunctionKind, InstallWith, InstanceFunction,
     InternalEnum, InternalEnumMut, ItemFnMut, ItemMut, ModuleAssociated, ModuleAssociatedKind,
     ModuleAttributeMacro, ModuleFunction, ModuleItem, ModuleItemCommon, ModuleItemKind,
-    ModuleMacro, ModuleType, Plain, TypeMut, TypeSpecification, VariantMut,
+    ModuleMacro, ModuleTrait, ModuleType, Plain, TraitMut, TypeMut, TypeSpecification, VariantMut,
 };
 use crate::runtime::{
     AttributeMacroHandler, ConstValue, FromValue, FullTypeOf, FunctionHandler, GeneratorState,
@@ -830,6 +830,55 @@ impl Module {
         })
     }
 
+    /// Declare a trait which scripts can implement with `impl Trait for Type`.
+    ///
+    /// The functions declared through [`TraitMut::function`] have to be
+    /// provided by every implementation of the trait.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Module;
+    ///
+    /// let mut module = Module::with_crate("plugins")?;
+    ///
+    /// module
+    ///     .define_trait("Plugin")?
+    ///     .docs(["A plugin which can be loaded by the host."])?
+    ///     .function("name")?
+    ///     .function("run")?;
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn define_trait<N>(&mut self, name: N) -> Result<TraitMut<'_>, ContextError>
+    where
+        N: IntoComponent,
+    {
+        let item = ItemBuf::with_item([name])?;
+        let hash = Hash::type_hash(&item);
+
+        if !self.names.try_insert(Name::Item(hash))? {
+            return Err(ContextError::ConflictingTrait { item, hash });
+        }
+
+        self.items.try_push(ModuleItem {
+            item,
+            common: ModuleItemCommon::default(),
+            kind: ModuleItemKind::Trait(ModuleTrait::default()),
+        })?;
+
+        let item = self.items.last_mut().unwrap();
+
+        let trait_ = match &mut item.kind {
+            ModuleItemKind::Trait(trait_) => trait_,
+            _ => unreachable!(),
+        };
+
+        Ok(TraitMut {
+            trait_,
+            common: &mut item.common,
+        })
+    }
+
     /// Register a constant value, at a crate, module or associated level.
     ///
     /// # Examples
@@ -1249,14 +1298,18 @@ impl Module {
                 docs.set_docs(meta.docs)?;
                 docs.set_arguments(meta.arguments)?;
                 let deprecated = meta.deprecated.map(TryInto::try_into).transpose()?;
-                self.function_inner(data, docs, deprecated)
+                Ok(self
+                    .function_inner(data, docs, deprecated)?
+                    .is_const(meta.is_const))
             }
             FunctionMetaKind::AssociatedFunction(data) => {
                 let mut docs = Docs::EMPTY;
                 docs.set_docs(meta.docs)?;
                 docs.set_arguments(meta.arguments)?;
                 let deprecated = meta.deprecated.map(TryInto::try_into).transpose()?;
-                self.insert_associated_function(data, docs, deprecated)
+                Ok(self
+                    .insert_associated_function(data, docs, deprecated)?
+                    .is_const(meta.is_const))
             }
         }
     }
@@ -1739,6 +1792,7 @@ impl Module {
             common: ModuleItemCommon { docs, deprecated },
             kind: ModuleItemKind::Function(ModuleFunction {
                 handler: data.handler,
+                is_const: false,
                 #[cfg(feature = "doc")]
                 is_async: data.is_async,
                 #[cfg(feature = "doc")]
@@ -1752,7 +1806,6 @@ impl Module {
 
         let last = self.items.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleItemKind::Function(f) => f,
             _ => unreachable!(),
@@ -1760,6 +1813,7 @@ impl Module {
 
         Ok(ItemFnMut {
             docs: &mut last.common.docs,
+            is_const: &mut last_fn.is_const,
             #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
             #[cfg(feature = "doc")]
@@ -1789,6 +1843,7 @@ impl Module {
             common: ModuleItemCommon { docs, deprecated },
             kind: ModuleAssociatedKind::Function(ModuleFunction {
                 handler: data.handler,
+                is_const: false,
                 #[cfg(feature = "doc")]
                 is_async: data.is_async,
                 #[cfg(feature = "doc")]
@@ -1802,7 +1857,6 @@ impl Module {
 
         let last = self.associated.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleAssociatedKind::Function(f) => f,
             _ => unreachable!(),
@@ -1810,6 +1864,7 @@ impl Module {
 
         Ok(ItemFnMut {
             docs: &mut last.common.docs,
+            is_const: &mut last_fn.is_const,
             #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
             #[cfg(feature = "doc")]
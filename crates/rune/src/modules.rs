@@ -20,6 +20,7 @@ pub mod fmt;
 pub mod future;
 pub mod generator;
 pub mod hash;
+pub mod i128;
 pub mod i64;
 pub mod io;
 pub mod iter;
@@ -34,4 +35,6 @@ pub mod stream;
 pub mod string;
 pub mod test;
 pub mod tuple;
+pub mod u128;
+pub mod u64;
 pub mod vec;
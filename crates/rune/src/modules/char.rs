@@ -34,7 +34,7 @@ pub fn module() -> Result<Module, ContextError> {
 /// let c = char::from_i64(80);
 /// assert!(c.is_some());
 /// ```
-#[rune::function]
+#[rune::function(const)]
 fn from_i64(value: i64) -> VmResult<Option<Value>> {
     if value < 0 {
         VmResult::err(VmErrorKind::Underflow)
@@ -57,7 +57,7 @@ fn from_i64(value: i64) -> VmResult<Option<Value>> {
 /// let c = char::from_i64(80)?;
 /// assert_eq!(c.to_i64(), 80);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn to_i64(value: char) -> VmResult<Value> {
     VmResult::Ok(vm_try!(Value::try_from(value as i64)))
 }
@@ -81,7 +81,7 @@ fn to_i64(value: char) -> VmResult<Value> {
 /// // love is many things, but it is not alphabetic
 /// assert!(!c.is_alphabetic());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_alphabetic(c: char) -> bool {
     char::is_alphabetic(c)
@@ -106,7 +106,7 @@ fn is_alphabetic(c: char) -> bool {
 /// assert!('و'.is_alphanumeric());
 /// assert!('藏'.is_alphanumeric());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_alphanumeric(c: char) -> bool {
     char::is_alphanumeric(c)
@@ -132,7 +132,7 @@ fn is_alphanumeric(c: char) -> bool {
 /// assert!('\u{009c}'.is_control());
 /// assert!(!'q'.is_control());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_control(c: char) -> bool {
     char::is_control(c)
@@ -162,7 +162,7 @@ fn is_control(c: char) -> bool {
 /// assert!(!'中'.is_lowercase());
 /// assert!(!' '.is_lowercase());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_lowercase(c: char) -> bool {
     char::is_lowercase(c)
@@ -202,7 +202,7 @@ fn is_lowercase(c: char) -> bool {
 /// assert!(!'藏'.is_numeric());
 /// assert!(!'三'.is_numeric());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_numeric(c: char) -> bool {
     char::is_numeric(c)
@@ -232,7 +232,7 @@ fn is_numeric(c: char) -> bool {
 /// assert!(!'中'.is_uppercase());
 /// assert!(!' '.is_uppercase());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_uppercase(c: char) -> bool {
     char::is_uppercase(c)
@@ -261,7 +261,7 @@ fn is_uppercase(c: char) -> bool {
 ///
 /// assert!(!'越'.is_whitespace());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_whitespace(c: char) -> bool {
     char::is_whitespace(c)
@@ -310,7 +310,7 @@ fn is_whitespace(c: char) -> bool {
 /// // this panics
 /// let _ = '1'.to_digit(37);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn to_digit(c: char, radix: u32) -> VmResult<Option<u32>> {
     if radix > 36 {
@@ -22,6 +22,15 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<u8>()?.docs(["The primitive byte type."])?;
     module.ty::<f64>()?.docs(["The primitive float type."])?;
     module.ty::<i64>()?.docs(["The primitive integer type."])?;
+    module
+        .ty::<u64>()?
+        .docs(["The primitive unsigned integer type."])?;
+    module
+        .ty::<i128>()?
+        .docs(["The primitive 128-bit integer type."])?;
+    module
+        .ty::<u128>()?
+        .docs(["The primitive unsigned 128-bit integer type."])?;
 
     module.function_meta(panic)?;
     module.function_meta(is_readable)?;
@@ -71,7 +71,7 @@ fn parse(s: &str) -> Result<f64, ParseFloatError> {
 /// let n = 7.0_f64.to::<i64>();
 /// assert_eq!(n, 7);
 /// ```
-#[rune::function(instance, path = to::<i64>)]
+#[rune::function(const, instance, path = to::<i64>)]
 fn to_integer(value: f64) -> i64 {
     value as i64
 }
@@ -87,7 +87,7 @@ fn to_integer(value: f64) -> i64 {
 /// assert!(nan.is_nan());
 /// assert!(!f.is_nan());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn is_nan(this: f64) -> bool {
     this.is_nan()
 }
@@ -109,7 +109,7 @@ fn is_nan(this: f64) -> bool {
 /// assert!(inf.is_infinite());
 /// assert!(neg_inf.is_infinite());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn is_infinite(this: f64) -> bool {
     this.is_infinite()
 }
@@ -130,7 +130,7 @@ fn is_infinite(this: f64) -> bool {
 /// assert!(!inf.is_finite());
 /// assert!(!neg_inf.is_finite());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn is_finite(this: f64) -> bool {
     this.is_finite()
 }
@@ -156,7 +156,7 @@ fn is_finite(this: f64) -> bool {
 /// ```
 ///
 /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn is_subnormal(this: f64) -> bool {
     this.is_subnormal()
 }
@@ -181,7 +181,7 @@ fn is_subnormal(this: f64) -> bool {
 /// assert!(!lower_than_min.is_normal());
 /// ```
 /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn is_normal(this: f64) -> bool {
     this.is_normal()
 }
@@ -202,7 +202,7 @@ fn is_normal(this: f64) -> bool {
 ///
 /// assert_eq!(x.max(y), y);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn max(this: f64, other: f64) -> f64 {
     this.max(other)
 }
@@ -223,7 +223,7 @@ fn max(this: f64, other: f64) -> f64 {
 ///
 /// assert_eq!(x.min(y), x);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 fn min(this: f64, other: f64) -> f64 {
     this.min(other)
 }
@@ -245,7 +245,7 @@ fn min(this: f64, other: f64) -> f64 {
 /// assert!(negative.sqrt().is_nan());
 /// assert!(negative_zero.sqrt() == negative_zero);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[cfg(feature = "std")]
 fn sqrt(this: f64) -> f64 {
     this.sqrt()
@@ -267,7 +267,7 @@ fn sqrt(this: f64) -> f64 {
 ///
 /// assert!(f64::NAN.abs().is_nan());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[cfg(feature = "std")]
 fn abs(this: f64) -> f64 {
     this.abs()
@@ -283,7 +283,7 @@ fn abs(this: f64) -> f64 {
 ///
 /// assert!(abs_difference < 1e-10);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[cfg(feature = "std")]
 fn powf(this: f64, other: f64) -> f64 {
     this.powf(other)
@@ -303,7 +303,7 @@ fn powf(this: f64, other: f64) -> f64 {
 ///
 /// assert!(abs_difference < 1e-10);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[cfg(feature = "std")]
 fn powi(this: f64, other: i32) -> f64 {
     this.powi(other)
@@ -322,7 +322,7 @@ fn powi(this: f64, other: i32) -> f64 {
 /// assert!(g.floor() == 3.0);
 /// assert!(h.floor() == -4.0);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[cfg(feature = "std")]
 fn floor(this: f64) -> f64 {
     this.floor()
@@ -339,7 +339,7 @@ fn floor(this: f64) -> f64 {
 /// assert_eq!(f.ceil(), 4.0);
 /// assert_eq!(g.ceil(), 4.0);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[cfg(feature = "std")]
 fn ceil(this: f64) -> f64 {
     this.ceil()
@@ -363,7 +363,7 @@ fn ceil(this: f64) -> f64 {
 /// assert_eq!(i.round(), 4.0);
 /// assert_eq!(j.round(), 5.0);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[cfg(feature = "std")]
 fn round(this: f64) -> f64 {
     this.round()
@@ -0,0 +1,519 @@
+//! Signed 128-bit integers.
+
+use core::cmp::Ordering;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::string::TryToString;
+use crate::runtime::{VmErrorKind, VmResult};
+use crate::{ContextError, Module};
+
+/// Signed 128-bit integers.
+///
+/// This provides methods for computing over and parsing 128-bit signed
+/// integers.
+///
+/// 128-bit integers are written using the `i128` suffix, like `42i128`. When
+/// mixed with narrower integers in arithmetic, both operands are converted into
+/// an `i128`. Use `as` to convert between them explicitly.
+///
+/// Native functions returning an `i128` always produce an `i128`, no matter
+/// how small the value is.
+///
+/// # Examples
+///
+/// ```rune
+/// let n = 40i128 + 2i128;
+/// assert_eq!(n, 42i128);
+/// assert_eq!(n as i64, 42);
+/// assert_eq!(42 as i128, n);
+/// assert_eq!(n - 43, -1i128);
+/// ```
+#[rune::module(::std::i128)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::from_meta(self::module_meta)?;
+
+    module.function("parse", parse).build()?;
+    module.function_meta(to_float)?;
+
+    module.function_meta(max)?;
+    module.function_meta(min)?;
+    module.function_meta(pow)?;
+
+    module.function_meta(checked_add)?;
+    module.function_meta(checked_sub)?;
+    module.function_meta(checked_div)?;
+    module.function_meta(checked_mul)?;
+    module.function_meta(checked_rem)?;
+
+    module.function_meta(wrapping_add)?;
+    module.function_meta(wrapping_sub)?;
+    module.function_meta(wrapping_div)?;
+    module.function_meta(wrapping_mul)?;
+    module.function_meta(wrapping_rem)?;
+
+    module.function_meta(saturating_add)?;
+    module.function_meta(saturating_sub)?;
+    module.function_meta(saturating_mul)?;
+    module.function_meta(saturating_pow)?;
+
+    module.function_meta(partial_eq)?;
+    module.function_meta(eq)?;
+    module.function_meta(partial_cmp)?;
+    module.function_meta(cmp)?;
+    module.function_meta(to_string)?;
+
+    module.constant("MIN", i128::MIN).build()?.docs([
+        "The smallest value that can be represented by this integer type",
+        "(&minus;2<sup>127</sup>).",
+        "",
+        "# Examples",
+        "",
+        "Basic usage:",
+        "",
+        "```rune",
+        "assert_eq!(i128::MIN, -170141183460469231731687303715884105728i128);",
+        "```",
+    ])?;
+
+    module.constant("MAX", i128::MAX).build()?.docs([
+        "The largest value that can be represented by this integer type",
+        "(2<sup>127</sup> &minus; 1).",
+        "",
+        "# Examples",
+        "",
+        "Basic usage:",
+        "",
+        "```rune",
+        "assert_eq!(i128::MAX, 170141183460469231731687303715884105727i128);",
+        "```",
+    ])?;
+
+    Ok(module)
+}
+
+/// Parse a `i128`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(i128::parse("10")?, 10i128);
+/// assert_eq!(i128::parse("-10")?, -10i128);
+/// ```
+fn parse(s: &str) -> Result<i128, ParseIntError> {
+    str::parse::<i128>(s)
+}
+
+/// Convert a `i128` to a `float`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!(10i128.to::<f64>() is f64);
+/// ```
+#[rune::function(instance, path = to::<f64>)]
+#[inline]
+fn to_float(value: i128) -> f64 {
+    value as f64
+}
+
+/// Compares and returns the maximum of two values.
+///
+/// Returns the second argument if the comparison determines them to be equal.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(2i128, 1i128.max(2i128));
+/// assert_eq!(2i128, 2i128.max(2i128));
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn max(this: i128, other: i128) -> i128 {
+    i128::max(this, other)
+}
+
+/// Compares and returns the minimum of two values.
+///
+/// Returns the first argument if the comparison determines them to be equal.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(1i128, 1i128.min(2i128));
+/// assert_eq!(2i128, 2i128.min(2i128));
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn min(this: i128, other: i128) -> i128 {
+    i128::min(this, other)
+}
+
+/// Raises self to the power of `exp`, using exponentiation by squaring.
+///
+/// # Overflow behavior
+///
+/// This function will wrap on overflow.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// let x = 2i128;
+///
+/// assert_eq!(x.pow(5), 32i128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn pow(this: i128, pow: u32) -> i128 {
+    i128::wrapping_pow(this, pow)
+}
+
+/// Checked integer addition. Computes `self + rhs`, returning `None` if
+/// overflow occurred.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!((i128::MAX - 2i128).checked_add(1i128), Some(i128::MAX - 1i128));
+/// assert_eq!((i128::MAX - 2i128).checked_add(3i128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_add(this: i128, rhs: i128) -> Option<i128> {
+    i128::checked_add(this, rhs)
+}
+
+/// Checked integer subtraction. Computes `self - rhs`, returning `None` if
+/// overflow occurred.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(1i128.checked_sub(1i128), Some(0i128));
+/// assert_eq!(i128::MIN.checked_sub(1i128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_sub(this: i128, rhs: i128) -> Option<i128> {
+    i128::checked_sub(this, rhs)
+}
+
+/// Checked integer division. Computes `self / rhs`, returning `None` if `rhs ==
+/// 0`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(128i128.checked_div(2i128), Some(64i128));
+/// assert_eq!(1i128.checked_div(0i128), None);
+/// assert_eq!(i128::MIN.checked_div(-1i128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_div(this: i128, rhs: i128) -> Option<i128> {
+    i128::checked_div(this, rhs)
+}
+
+/// Checked integer multiplication. Computes `self * rhs`, returning `None` if
+/// overflow occurred.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(i128::MAX.checked_mul(1i128), Some(i128::MAX));
+/// assert_eq!(i128::MAX.checked_mul(2i128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_mul(this: i128, rhs: i128) -> Option<i128> {
+    i128::checked_mul(this, rhs)
+}
+
+/// Checked integer remainder. Computes `self % rhs`, returning `None` if `rhs
+/// == 0`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(5i128.checked_rem(2i128), Some(1i128));
+/// assert_eq!(5i128.checked_rem(0i128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_rem(this: i128, rhs: i128) -> Option<i128> {
+    i128::checked_rem(this, rhs)
+}
+
+/// Wrapping (modular) addition. Computes `self + rhs`, wrapping around at the
+/// boundary of the type.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(200i128.wrapping_add(55i128), 255i128);
+/// assert_eq!(i128::MAX.wrapping_add(2i128), i128::MIN + 1i128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_add(this: i128, rhs: i128) -> i128 {
+    i128::wrapping_add(this, rhs)
+}
+
+/// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping around at
+/// the boundary of the type.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100i128.wrapping_sub(100i128), 0i128);
+/// assert_eq!(i128::MIN.wrapping_sub(1i128), i128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_sub(this: i128, rhs: i128) -> i128 {
+    i128::wrapping_sub(this, rhs)
+}
+
+/// Wrapping (modular) division. Computes `self / rhs`.
+///
+/// The only case where such wrapping can occur is when one divides `MIN / -1`
+/// on a signed type, which is equivalent to `-MIN`, a positive value that is
+/// too large to represent in the type. In such a case, this function returns
+/// `MIN` itself.
+///
+/// # Panics
+///
+/// This function will panic if `rhs` is 0.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100i128.wrapping_div(10i128), 10i128);
+/// assert_eq!(i128::MIN.wrapping_div(-1i128), i128::MIN);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_div(this: i128, rhs: i128) -> VmResult<i128> {
+    if rhs == 0 {
+        return VmResult::err(VmErrorKind::DivideByZero);
+    }
+
+    VmResult::Ok(i128::wrapping_div(this, rhs))
+}
+
+/// Wrapping (modular) multiplication. Computes `self * rhs`, wrapping around at
+/// the boundary of the type.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(10i128.wrapping_mul(12i128), 120i128);
+/// assert_eq!(i128::MAX.wrapping_mul(2i128), -2i128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_mul(this: i128, rhs: i128) -> i128 {
+    i128::wrapping_mul(this, rhs)
+}
+
+/// Wrapping (modular) remainder. Computes `self % rhs`.
+///
+/// Such wrap-around never actually occurs mathematically, but `MIN % -1`
+/// overflows on a signed type. In such a case, this function returns `0`.
+///
+/// # Panics
+///
+/// This function will panic if `rhs` is 0.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100i128.wrapping_rem(10i128), 0i128);
+/// assert_eq!(i128::MIN.wrapping_rem(-1i128), 0i128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_rem(this: i128, rhs: i128) -> VmResult<i128> {
+    if rhs == 0 {
+        return VmResult::err(VmErrorKind::DivideByZero);
+    }
+
+    VmResult::Ok(i128::wrapping_rem(this, rhs))
+}
+
+/// Saturating integer addition. Computes `self + rhs`, saturating at the
+/// numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100i128.saturating_add(1i128), 101i128);
+/// assert_eq!(i128::MAX.saturating_add(100i128), i128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_add(this: i128, rhs: i128) -> i128 {
+    i128::saturating_add(this, rhs)
+}
+
+/// Saturating integer subtraction. Computes `self - rhs`, saturating at the
+/// numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100i128.saturating_sub(27i128), 73i128);
+/// assert_eq!(i128::MIN.saturating_sub(127i128), i128::MIN);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_sub(this: i128, rhs: i128) -> i128 {
+    i128::saturating_sub(this, rhs)
+}
+
+/// Saturating integer multiplication. Computes `self * rhs`, saturating at the
+/// numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(2i128.saturating_mul(10i128), 20i128);
+/// assert_eq!(i128::MAX.saturating_mul(10i128), i128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_mul(this: i128, rhs: i128) -> i128 {
+    i128::saturating_mul(this, rhs)
+}
+
+/// Saturating integer exponentiation. Computes `self.pow(exp)`, saturating at
+/// the numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(4i128.saturating_pow(3), 64i128);
+/// assert_eq!(i128::MAX.saturating_pow(2), i128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_pow(this: i128, rhs: u32) -> i128 {
+    i128::saturating_pow(this, rhs)
+}
+
+/// Test two integers for partial equality.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::partial_eq;
+///
+/// assert_eq!(partial_eq(5i128, 5i128), true);
+/// assert_eq!(partial_eq(5i128, 10i128), false);
+/// assert_eq!(partial_eq(10i128, 5i128), false);
+/// ```
+#[rune::function(instance, protocol = PARTIAL_EQ)]
+#[inline]
+fn partial_eq(this: i128, rhs: i128) -> bool {
+    this.eq(&rhs)
+}
+
+/// Test two integers for total equality.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::eq;
+///
+/// assert_eq!(eq(5i128, 5i128), true);
+/// assert_eq!(eq(5i128, 10i128), false);
+/// assert_eq!(eq(10i128, 5i128), false);
+/// ```
+#[rune::function(instance, protocol = EQ)]
+#[inline]
+fn eq(this: i128, rhs: i128) -> bool {
+    this.eq(&rhs)
+}
+
+/// Perform a partial ordered comparison between two integers.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::partial_cmp;
+///
+/// assert_eq!(partial_cmp(5i128, 10i128), Some(Ordering::Less));
+/// assert_eq!(partial_cmp(10i128, 5i128), Some(Ordering::Greater));
+/// assert_eq!(partial_cmp(5i128, 5i128), Some(Ordering::Equal));
+/// ```
+#[rune::function(instance, protocol = PARTIAL_CMP)]
+#[inline]
+fn partial_cmp(this: i128, rhs: i128) -> Option<Ordering> {
+    this.partial_cmp(&rhs)
+}
+
+/// Perform a totally ordered comparison between two integers.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::cmp;
+///
+/// assert_eq!(cmp(5i128, 10i128), Ordering::Less);
+/// assert_eq!(cmp(10i128, 5i128), Ordering::Greater);
+/// assert_eq!(cmp(5i128, 5i128), Ordering::Equal);
+/// ```
+#[rune::function(instance, protocol = CMP)]
+#[inline]
+fn cmp(this: i128, rhs: i128) -> Ordering {
+    this.cmp(&rhs)
+}
+
+/// Returns the number as a string.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(10i128.to_string(), "10");
+/// assert_eq!(i128::MAX.to_string(), "170141183460469231731687303715884105727");
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn to_string(this: i128) -> VmResult<alloc::String> {
+    VmResult::Ok(vm_try!(this.try_to_string()))
+}
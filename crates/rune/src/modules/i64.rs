@@ -99,7 +99,7 @@ fn parse(s: &str) -> Result<i64, ParseIntError> {
 /// ```rune
 /// assert!(10.to::<f64>() is f64);
 /// ```
-#[rune::function(instance, path = to::<f64>)]
+#[rune::function(const, instance, path = to::<f64>)]
 #[inline]
 fn to_float(value: i64) -> f64 {
     value as f64
@@ -115,7 +115,7 @@ fn to_float(value: i64) -> f64 {
 /// assert_eq!(2, 1.max(2));
 /// assert_eq!(2, 2.max(2));
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn max(this: i64, other: i64) -> i64 {
     i64::max(this, other)
@@ -131,7 +131,7 @@ fn max(this: i64, other: i64) -> i64 {
 /// assert_eq!(1, 1.min(2));
 /// assert_eq!(2, 2.min(2));
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn min(this: i64, other: i64) -> i64 {
     i64::min(this, other)
@@ -153,7 +153,7 @@ fn min(this: i64, other: i64) -> i64 {
 /// assert_eq!(10.abs(), 10);
 /// assert_eq!((-10).abs(), 10);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn abs(this: i64) -> i64 {
     i64::wrapping_abs(this)
@@ -174,7 +174,7 @@ fn abs(this: i64) -> i64 {
 ///
 /// assert_eq!(x.pow(5), 32);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn pow(this: i64, pow: u32) -> i64 {
     i64::wrapping_pow(this, pow)
@@ -191,7 +191,7 @@ fn pow(this: i64, pow: u32) -> i64 {
 /// assert_eq!((i64::MAX - 2).checked_add(1), Some(i64::MAX - 1));
 /// assert_eq!((i64::MAX - 2).checked_add(3), None);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn checked_add(this: i64, rhs: i64) -> Option<i64> {
     i64::checked_add(this, rhs)
@@ -208,7 +208,7 @@ fn checked_add(this: i64, rhs: i64) -> Option<i64> {
 /// assert_eq!((i64::MIN + 2).checked_sub(1), Some(i64::MIN + 1));
 /// assert_eq!((i64::MIN + 2).checked_sub(3), None);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn checked_sub(this: i64, rhs: i64) -> Option<i64> {
     i64::checked_sub(this, rhs)
@@ -226,7 +226,7 @@ fn checked_sub(this: i64, rhs: i64) -> Option<i64> {
 /// assert_eq!(i64::MIN.checked_div(-1), None);
 /// assert_eq!((1).checked_div(0), None);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn checked_div(this: i64, rhs: i64) -> Option<i64> {
     i64::checked_div(this, rhs)
@@ -243,7 +243,7 @@ fn checked_div(this: i64, rhs: i64) -> Option<i64> {
 /// assert_eq!(i64::MAX.checked_mul(1), Some(i64::MAX));
 /// assert_eq!(i64::MAX.checked_mul(2), None);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn checked_mul(this: i64, rhs: i64) -> Option<i64> {
     i64::checked_mul(this, rhs)
@@ -261,7 +261,7 @@ fn checked_mul(this: i64, rhs: i64) -> Option<i64> {
 /// assert_eq!(5.checked_rem(0), None);
 /// assert_eq!(i64::MIN.checked_rem(-1), None);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn checked_rem(this: i64, rhs: i64) -> Option<i64> {
     i64::checked_rem(this, rhs)
@@ -278,7 +278,7 @@ fn checked_rem(this: i64, rhs: i64) -> Option<i64> {
 /// assert_eq!(100.wrapping_add(27), 127);
 /// assert_eq!(i64::MAX.wrapping_add(2), i64::MIN + 1);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn wrapping_add(this: i64, rhs: i64) -> i64 {
     i64::wrapping_add(this, rhs)
@@ -295,7 +295,7 @@ fn wrapping_add(this: i64, rhs: i64) -> i64 {
 // assert_eq!(0.wrapping_sub(127), -127);
 // assert_eq!((-2).wrapping_sub(i64::MAX), i64::MAX);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn wrapping_sub(this: i64, rhs: i64) -> i64 {
     i64::wrapping_sub(this, rhs)
@@ -320,7 +320,7 @@ fn wrapping_sub(this: i64, rhs: i64) -> i64 {
 /// ```rune
 /// assert_eq!(100.wrapping_div(10), 10);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn wrapping_div(this: i64, rhs: i64) -> VmResult<i64> {
     if rhs == 0 {
@@ -340,7 +340,7 @@ fn wrapping_div(this: i64, rhs: i64) -> VmResult<i64> {
 /// ```rune
 /// assert_eq!(10.wrapping_mul(12), 120);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn wrapping_mul(this: i64, rhs: i64) -> i64 {
     i64::wrapping_mul(this, rhs)
@@ -364,7 +364,7 @@ fn wrapping_mul(this: i64, rhs: i64) -> i64 {
 /// ```rune
 /// assert_eq!(100.wrapping_rem(10), 0);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn wrapping_rem(this: i64, rhs: i64) -> VmResult<i64> {
     if rhs == 0 {
@@ -386,7 +386,7 @@ fn wrapping_rem(this: i64, rhs: i64) -> VmResult<i64> {
 /// assert_eq!(i64::MAX.saturating_add(100), i64::MAX);
 /// assert_eq!(i64::MIN.saturating_add(-1), i64::MIN);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn saturating_add(this: i64, rhs: i64) -> i64 {
     i64::saturating_add(this, rhs)
@@ -404,7 +404,7 @@ fn saturating_add(this: i64, rhs: i64) -> i64 {
 /// assert_eq!(i64::MIN.saturating_sub(100), i64::MIN);
 /// assert_eq!(i64::MAX.saturating_sub(-1), i64::MAX);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn saturating_sub(this: i64, rhs: i64) -> i64 {
     i64::saturating_sub(this, rhs)
@@ -422,7 +422,7 @@ fn saturating_sub(this: i64, rhs: i64) -> i64 {
 /// assert_eq!(i64::MAX.saturating_mul(10), i64::MAX);
 /// assert_eq!(i64::MIN.saturating_mul(10), i64::MIN);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn saturating_mul(this: i64, rhs: i64) -> i64 {
     i64::saturating_mul(this, rhs)
@@ -441,7 +441,7 @@ fn saturating_mul(this: i64, rhs: i64) -> i64 {
 /// assert_eq!(i64::MIN.saturating_abs(), i64::MAX);
 /// assert_eq!((i64::MIN + 1).saturating_abs(), i64::MAX);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn saturating_abs(this: i64) -> i64 {
     i64::saturating_abs(this)
@@ -459,7 +459,7 @@ fn saturating_abs(this: i64) -> i64 {
 /// assert_eq!(i64::MIN.saturating_pow(2), i64::MAX);
 /// assert_eq!(i64::MIN.saturating_pow(3), i64::MIN);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn saturating_pow(this: i64, rhs: u32) -> i64 {
     i64::saturating_pow(this, rhs)
@@ -480,7 +480,7 @@ fn saturating_pow(this: i64, rhs: u32) -> i64 {
 /// assert_eq!(0.signum(), 0);
 /// assert_eq!((-10).signum(), -1);
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn signum(this: i64) -> i64 {
     i64::signum(this)
@@ -497,7 +497,7 @@ fn signum(this: i64) -> i64 {
 /// assert!(10.is_positive());
 /// assert!(!(-10).is_positive());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_positive(this: i64) -> bool {
     i64::is_positive(this)
@@ -514,7 +514,7 @@ fn is_positive(this: i64) -> bool {
 /// assert!((-10).is_negative());
 /// assert!(!10.is_negative());
 /// ```
-#[rune::function(instance)]
+#[rune::function(const, instance)]
 #[inline]
 fn is_negative(this: i64) -> bool {
     i64::is_negative(this)
@@ -1,21 +1,36 @@
 //! Working with numbers.
 
+mod big_int;
+mod decimal;
+
 use core::num::{ParseFloatError, ParseIntError};
 
+use num::bigint::ParseBigIntError;
+
 use crate as rune;
 use crate::{ContextError, Module};
 
+use self::big_int::BigInt;
+
 /// Working with numbers.
 ///
 /// This module provides types generic for working over numbers, such as errors
 /// when a number cannot be parsed.
+///
+/// It also provides the arbitrary-precision [`BigInt`] and [`Decimal`] types,
+/// which can be used where 64-bit integers are too small or where floats can't
+/// represent values exactly, like when doing money math.
 #[rune::module(::std::num)]
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::from_meta(self::module_meta)?;
     module.ty::<ParseFloatError>()?;
     module.ty::<ParseIntError>()?;
+    module.ty::<ParseBigIntError>()?;
+    big_int::setup(&mut module)?;
+    decimal::setup(&mut module)?;
     Ok(module)
 }
 
 crate::__internal_impl_any!(::std::num, ParseFloatError);
 crate::__internal_impl_any!(::std::num, ParseIntError);
+crate::__internal_impl_any!(::std::num, ParseBigIntError);
@@ -0,0 +1,549 @@
+use core::cmp::Ordering;
+
+use num::{FromPrimitive, Signed, ToPrimitive, Zero};
+
+use crate as rune;
+use crate::alloc::fmt::TryWrite;
+use crate::runtime::{
+    Formatter, Hasher, Protocol, TypeOf, Value, ValueKind, VmErrorKind, VmResult,
+};
+use crate::{Any, ContextError, Module};
+
+pub(super) fn setup(m: &mut Module) -> Result<(), ContextError> {
+    m.ty::<BigInt>()?;
+
+    m.function_meta(BigInt::parse)?;
+    m.function_meta(BigInt::from_i64)?;
+    m.function_meta(BigInt::from_f64)?;
+    m.function_meta(BigInt::to_i64)?;
+    m.function_meta(BigInt::to_f64)?;
+    m.function_meta(BigInt::abs)?;
+    m.function_meta(BigInt::pow)?;
+
+    m.function_meta(BigInt::add)?;
+    m.function_meta(BigInt::sub)?;
+    m.function_meta(BigInt::mul)?;
+    m.function_meta(BigInt::div)?;
+    m.function_meta(BigInt::rem)?;
+    m.function_meta(BigInt::neg)?;
+    m.function_meta(BigInt::add_assign)?;
+    m.function_meta(BigInt::sub_assign)?;
+    m.function_meta(BigInt::mul_assign)?;
+    m.function_meta(BigInt::div_assign)?;
+    m.function_meta(BigInt::rem_assign)?;
+
+    m.function_meta(BigInt::partial_eq)?;
+    m.function_meta(BigInt::eq)?;
+    m.function_meta(BigInt::partial_cmp)?;
+    m.function_meta(BigInt::cmp)?;
+    m.function_meta(BigInt::hash)?;
+    m.function_meta(BigInt::clone)?;
+    m.function_meta(BigInt::string_display)?;
+    m.function_meta(BigInt::string_debug)?;
+    Ok(())
+}
+
+/// An arbitrary-precision integer.
+///
+/// Arithmetic on big integers never overflows. The right-hand side of an
+/// operation can be another big integer, or a signed or unsigned integer.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::num::BigInt;
+///
+/// let n = BigInt::from_i64(i64::MAX);
+/// n *= 4;
+///
+/// assert_eq!(n, BigInt::parse("36893488147419103228")?);
+/// assert_eq!(n.to_i64(), None);
+/// assert_eq!(format!("{n}"), "36893488147419103228");
+/// ```
+#[derive(Any, Clone)]
+#[rune(item = ::std::num)]
+pub(crate) struct BigInt {
+    inner: num::BigInt,
+}
+
+impl BigInt {
+    /// Parse a decimal string into a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let n = BigInt::parse("-123456789012345678901234567890")?;
+    /// assert_eq!(format!("{n}"), "-123456789012345678901234567890");
+    /// assert!(BigInt::parse("1.5").is_err());
+    /// ```
+    #[rune::function(path = Self::parse)]
+    fn parse(s: &str) -> Result<BigInt, num::bigint::ParseBigIntError> {
+        Ok(Self { inner: s.parse()? })
+    }
+
+    /// Construct a big integer from an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(-42).to_i64(), Some(-42));
+    /// ```
+    #[rune::function(path = Self::from_i64)]
+    fn from_i64(value: i64) -> BigInt {
+        Self {
+            inner: num::BigInt::from(value),
+        }
+    }
+
+    /// Construct a big integer from a float, discarding its fractional part.
+    ///
+    /// Returns `None` if the float is not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_f64(1e20), Some(BigInt::parse("100000000000000000000")?));
+    /// assert_eq!(BigInt::from_f64(-2.9), Some(BigInt::from_i64(-2)));
+    /// assert_eq!(BigInt::from_f64(f64::NAN), None);
+    /// ```
+    #[rune::function(path = Self::from_f64)]
+    fn from_f64(value: f64) -> Option<BigInt> {
+        Some(Self {
+            inner: num::BigInt::from_f64(value)?,
+        })
+    }
+
+    /// Convert the big integer into an integer, returning `None` if it is out
+    /// of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(i64::MIN).to_i64(), Some(i64::MIN));
+    /// assert_eq!((BigInt::from_i64(i64::MAX) + 1).to_i64(), None);
+    /// ```
+    #[rune::function]
+    fn to_i64(&self) -> Option<i64> {
+        self.inner.to_i64()
+    }
+
+    /// Convert the big integer into the nearest float.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(42).to_f64(), 42.0);
+    /// ```
+    #[rune::function]
+    fn to_f64(&self) -> f64 {
+        self.inner.to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// Compute the absolute value of the big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(-10).abs(), BigInt::from_i64(10));
+    /// ```
+    #[rune::function]
+    fn abs(&self) -> BigInt {
+        Self {
+            inner: self.inner.abs(),
+        }
+    }
+
+    /// Raise the big integer to the power of `exp`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let n = BigInt::from_i64(2).pow(100);
+    /// assert_eq!(format!("{n}"), "1267650600228229401496703205376");
+    /// ```
+    #[rune::function]
+    fn pow(&self, exp: u32) -> BigInt {
+        Self {
+            inner: num::pow(self.inner.clone(), exp as usize),
+        }
+    }
+
+    /// Add to a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(i64::MAX) + 1, BigInt::parse("9223372036854775808")?);
+    /// ```
+    #[rune::function(protocol = ADD)]
+    fn add(&self, other: Value) -> VmResult<BigInt> {
+        let other = vm_try!(operand(Protocol::ADD, &other));
+        VmResult::Ok(Self {
+            inner: &self.inner + other,
+        })
+    }
+
+    /// Subtract from a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(i64::MIN) - 1, BigInt::parse("-9223372036854775809")?);
+    /// ```
+    #[rune::function(protocol = SUB)]
+    fn sub(&self, other: Value) -> VmResult<BigInt> {
+        let other = vm_try!(operand(Protocol::SUB, &other));
+        VmResult::Ok(Self {
+            inner: &self.inner - other,
+        })
+    }
+
+    /// Multiply a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(i64::MAX) * 2, BigInt::parse("18446744073709551614")?);
+    /// ```
+    #[rune::function(protocol = MUL)]
+    fn mul(&self, other: Value) -> VmResult<BigInt> {
+        let other = vm_try!(operand(Protocol::MUL, &other));
+        VmResult::Ok(Self {
+            inner: &self.inner * other,
+        })
+    }
+
+    /// Divide a big integer, rounding towards zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(-7) / 2, BigInt::from_i64(-3));
+    /// ```
+    #[rune::function(protocol = DIV)]
+    fn div(&self, other: Value) -> VmResult<BigInt> {
+        let other = vm_try!(divisor(Protocol::DIV, &other));
+        VmResult::Ok(Self {
+            inner: &self.inner / other,
+        })
+    }
+
+    /// Compute the remainder of dividing a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(BigInt::from_i64(-7) % 2, BigInt::from_i64(-1));
+    /// ```
+    #[rune::function(protocol = REM)]
+    fn rem(&self, other: Value) -> VmResult<BigInt> {
+        let other = vm_try!(divisor(Protocol::REM, &other));
+        VmResult::Ok(Self {
+            inner: &self.inner % other,
+        })
+    }
+
+    /// Negate a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(-BigInt::from_i64(i64::MIN), BigInt::parse("9223372036854775808")?);
+    /// ```
+    #[rune::function(protocol = NEG)]
+    fn neg(&self) -> BigInt {
+        Self {
+            inner: -&self.inner,
+        }
+    }
+
+    /// Add to a big integer in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let n = BigInt::from_i64(1);
+    /// n += BigInt::from_i64(2);
+    /// assert_eq!(n, 3);
+    /// ```
+    #[rune::function(protocol = ADD_ASSIGN)]
+    fn add_assign(&mut self, other: Value) -> VmResult<()> {
+        self.inner += vm_try!(operand(Protocol::ADD_ASSIGN, &other));
+        VmResult::Ok(())
+    }
+
+    /// Subtract from a big integer in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let n = BigInt::from_i64(1);
+    /// n -= 2;
+    /// assert_eq!(n, -1);
+    /// ```
+    #[rune::function(protocol = SUB_ASSIGN)]
+    fn sub_assign(&mut self, other: Value) -> VmResult<()> {
+        self.inner -= vm_try!(operand(Protocol::SUB_ASSIGN, &other));
+        VmResult::Ok(())
+    }
+
+    /// Multiply a big integer in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let n = BigInt::from_i64(3);
+    /// n *= 2;
+    /// assert_eq!(n, 6);
+    /// ```
+    #[rune::function(protocol = MUL_ASSIGN)]
+    fn mul_assign(&mut self, other: Value) -> VmResult<()> {
+        self.inner *= vm_try!(operand(Protocol::MUL_ASSIGN, &other));
+        VmResult::Ok(())
+    }
+
+    /// Divide a big integer in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let n = BigInt::from_i64(7);
+    /// n /= 2;
+    /// assert_eq!(n, 3);
+    /// ```
+    #[rune::function(protocol = DIV_ASSIGN)]
+    fn div_assign(&mut self, other: Value) -> VmResult<()> {
+        self.inner /= vm_try!(divisor(Protocol::DIV_ASSIGN, &other));
+        VmResult::Ok(())
+    }
+
+    /// Compute the remainder of dividing a big integer in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let n = BigInt::from_i64(7);
+    /// n %= 2;
+    /// assert_eq!(n, 1);
+    /// ```
+    #[rune::function(protocol = REM_ASSIGN)]
+    fn rem_assign(&mut self, other: Value) -> VmResult<()> {
+        self.inner %= vm_try!(divisor(Protocol::REM_ASSIGN, &other));
+        VmResult::Ok(())
+    }
+
+    /// Test a big integer for partial equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert!(BigInt::from_i64(10) == BigInt::from_i64(10));
+    /// assert!(BigInt::from_i64(10) == 10);
+    /// assert!(BigInt::from_i64(10) != 11);
+    /// ```
+    #[rune::function(protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, other: Value) -> VmResult<bool> {
+        let other = vm_try!(operand(Protocol::PARTIAL_EQ, &other));
+        VmResult::Ok(self.inner == other)
+    }
+
+    /// Test a big integer for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    /// use std::ops::eq;
+    ///
+    /// assert!(eq(BigInt::from_i64(10), BigInt::from_i64(10)));
+    /// ```
+    #[rune::function(protocol = EQ)]
+    fn eq(&self, other: Value) -> VmResult<bool> {
+        let other = vm_try!(operand(Protocol::EQ, &other));
+        VmResult::Ok(self.inner == other)
+    }
+
+    /// Perform a partial ordered comparison with a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert!(BigInt::from_i64(10) < BigInt::from_i64(11));
+    /// assert!(BigInt::from_i64(10) > 9);
+    /// ```
+    #[rune::function(protocol = PARTIAL_CMP)]
+    fn partial_cmp(&self, other: Value) -> VmResult<Option<Ordering>> {
+        let other = vm_try!(operand(Protocol::PARTIAL_CMP, &other));
+        VmResult::Ok(Some(self.inner.cmp(&other)))
+    }
+
+    /// Perform a totally ordered comparison with a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::num::BigInt;
+    /// use std::ops::cmp;
+    ///
+    /// assert_eq!(cmp(BigInt::from_i64(10), BigInt::from_i64(11)), Ordering::Less);
+    /// ```
+    #[rune::function(protocol = CMP)]
+    fn cmp(&self, other: Value) -> VmResult<Ordering> {
+        let other = vm_try!(operand(Protocol::CMP, &other));
+        VmResult::Ok(self.inner.cmp(&other))
+    }
+
+    /// Hash a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    /// use std::ops::hash;
+    ///
+    /// assert_eq!(hash(BigInt::from_i64(10)), hash(BigInt::parse("10")?));
+    /// ```
+    #[rune::function(protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        hasher.write_u8(self.inner.sign() as u8);
+
+        for digit in self.inner.magnitude().iter_u64_digits() {
+            hasher.write_u64(digit);
+        }
+    }
+
+    /// Clone a big integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// let a = BigInt::from_i64(1);
+    /// let b = a.clone();
+    /// b += 1;
+    ///
+    /// assert_eq!(a, 1);
+    /// assert_eq!(b, 2);
+    /// ```
+    #[rune::function]
+    fn clone(&self) -> BigInt {
+        Clone::clone(self)
+    }
+
+    /// Write a big integer in decimal notation.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(format!("{}", BigInt::from_i64(-42)), "-42");
+    /// ```
+    #[rune::function(protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{}", self.inner);
+        VmResult::Ok(())
+    }
+
+    /// Write a big integer for debugging.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::BigInt;
+    ///
+    /// assert_eq!(format!("{:?}", BigInt::from_i64(-42)), "-42");
+    /// ```
+    #[rune::function(protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{}", self.inner);
+        VmResult::Ok(())
+    }
+
+    /// Access the underlying integer.
+    pub(super) fn as_inner(&self) -> &num::BigInt {
+        &self.inner
+    }
+}
+
+/// Convert the right-hand side of an operation into an integer.
+fn operand(protocol: Protocol, value: &Value) -> VmResult<num::BigInt> {
+    let integer = match &*vm_try!(value.borrow_kind_ref()) {
+        ValueKind::Integer(n) => num::BigInt::from(*n),
+        ValueKind::U64(n) => num::BigInt::from(*n),
+        ValueKind::I128(n) => num::BigInt::from(*n),
+        ValueKind::U128(n) => num::BigInt::from(*n),
+        ValueKind::Any(any) => match any.downcast_borrow_ref::<BigInt>() {
+            Ok(n) => n.inner.clone(),
+            Err(..) => {
+                return VmResult::err(VmErrorKind::UnsupportedBinaryOperation {
+                    op: protocol.name,
+                    lhs: BigInt::type_info(),
+                    rhs: any.type_info(),
+                });
+            }
+        },
+        kind => {
+            return VmResult::err(VmErrorKind::UnsupportedBinaryOperation {
+                op: protocol.name,
+                lhs: BigInt::type_info(),
+                rhs: kind.type_info(),
+            });
+        }
+    };
+
+    VmResult::Ok(integer)
+}
+
+/// Convert the right-hand side of a division into a non-zero integer.
+fn divisor(protocol: Protocol, value: &Value) -> VmResult<num::BigInt> {
+    let divisor = vm_try!(operand(protocol, value));
+
+    if divisor.is_zero() {
+        return VmResult::err(VmErrorKind::DivideByZero);
+    }
+
+    VmResult::Ok(divisor)
+}
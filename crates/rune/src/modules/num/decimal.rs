@@ -0,0 +1,894 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+use num::{Integer, Signed, ToPrimitive, Zero};
+
+use crate as rune;
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::String;
+use crate::runtime::{
+    Formatter, Hasher, Protocol, TypeOf, Value, ValueKind, VmErrorKind, VmResult,
+};
+use crate::{Any, ContextError, Module};
+
+use super::BigInt;
+
+/// The smallest number of fractional digits in the result of a division.
+const DIVISION_SCALE: u32 = 16;
+
+/// The largest exponent which can be parsed.
+const MAX_EXPONENT: u32 = u16::MAX as u32;
+
+pub(super) fn setup(m: &mut Module) -> Result<(), ContextError> {
+    m.ty::<Decimal>()?;
+    m.ty::<ParseDecimalError>()?;
+    m.function_meta(ParseDecimalError::string_display)?;
+
+    m.function_meta(Decimal::new)?;
+    m.function_meta(Decimal::parse)?;
+    m.function_meta(Decimal::from_i64)?;
+    m.function_meta(Decimal::from_f64)?;
+    m.function_meta(Decimal::to_i64)?;
+    m.function_meta(Decimal::to_f64)?;
+    m.function_meta(Decimal::scale)?;
+    m.function_meta(Decimal::round)?;
+    m.function_meta(Decimal::abs)?;
+
+    m.function_meta(Decimal::add)?;
+    m.function_meta(Decimal::sub)?;
+    m.function_meta(Decimal::mul)?;
+    m.function_meta(Decimal::div)?;
+    m.function_meta(Decimal::rem)?;
+    m.function_meta(Decimal::neg)?;
+    m.function_meta(Decimal::add_assign)?;
+    m.function_meta(Decimal::sub_assign)?;
+    m.function_meta(Decimal::mul_assign)?;
+    m.function_meta(Decimal::div_assign)?;
+    m.function_meta(Decimal::rem_assign)?;
+
+    m.function_meta(Decimal::partial_eq)?;
+    m.function_meta(Decimal::eq)?;
+    m.function_meta(Decimal::partial_cmp)?;
+    m.function_meta(Decimal::cmp)?;
+    m.function_meta(Decimal::hash)?;
+    m.function_meta(Decimal::clone)?;
+    m.function_meta(Decimal::string_display)?;
+    m.function_meta(Decimal::string_debug)?;
+    Ok(())
+}
+
+/// An arbitrary-precision decimal number with a fixed scale.
+///
+/// A decimal is an integer together with a scale, which is the number of
+/// digits after the decimal point. Unlike floats, decimals represent numbers
+/// like `0.1` exactly which makes them suitable for money math.
+///
+/// The scale of a decimal is preserved by arithmetic:
+///
+/// * Adding, subtracting or taking the remainder of two decimals uses the
+///   larger of their scales.
+/// * Multiplying two decimals adds their scales together.
+/// * Dividing two decimals produces at least 16 fractional digits, rounded
+///   half away from zero. Trailing zeros beyond the larger of their scales
+///   are removed.
+///
+/// Use [`Decimal::round`] to get a specific scale. The right-hand side of an
+/// operation can be another decimal, a big integer, or a signed or unsigned
+/// integer. Floats are not accepted, since they would silently lose
+/// precision. Use [`Decimal::from_f64`] to convert them explicitly.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::num::Decimal;
+///
+/// let price = Decimal::parse("19.99")?;
+/// let total = price * 3;
+///
+/// assert_eq!(format!("{total}"), "59.97");
+/// assert_eq!(Decimal::parse("0.1")? + Decimal::parse("0.2")?, Decimal::parse("0.3")?);
+///
+/// let share = total / 7;
+/// assert_eq!(format!("{share}"), "8.5671428571428571");
+/// assert_eq!(format!("{}", share.round(2)), "8.57");
+/// ```
+#[derive(Any, Clone)]
+#[rune(item = ::std::num)]
+pub(crate) struct Decimal {
+    mantissa: num::BigInt,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Construct a decimal from an integer mantissa and a scale.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// let d = Decimal::new(-1250, 2);
+    /// assert_eq!(format!("{d}"), "-12.50");
+    /// assert_eq!(d.scale(), 2);
+    /// ```
+    #[rune::function(path = Self::new)]
+    fn new(mantissa: i64, scale: u32) -> Decimal {
+        Self {
+            mantissa: num::BigInt::from(mantissa),
+            scale,
+        }
+    }
+
+    /// Parse a decimal from a string.
+    ///
+    /// The scale of the decimal is the number of digits after the decimal
+    /// point, adjusted by the exponent if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::parse("-12.50")?), "-12.50");
+    /// assert_eq!(format!("{}", Decimal::parse("1.5e3")?), "1500");
+    /// assert_eq!(format!("{}", Decimal::parse("15e-3")?), "0.015");
+    /// assert!(Decimal::parse("1.2.3").is_err());
+    /// ```
+    #[rune::function(path = Self::parse)]
+    fn parse(s: &str) -> Result<Decimal, ParseDecimalError> {
+        Self::from_str(s)
+    }
+
+    /// Construct a decimal from an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(Decimal::from_i64(42), Decimal::parse("42.00")?);
+    /// ```
+    #[rune::function(path = Self::from_i64)]
+    fn from_i64(value: i64) -> Decimal {
+        Self {
+            mantissa: num::BigInt::from(value),
+            scale: 0,
+        }
+    }
+
+    /// Construct a decimal from the shortest representation of a float which
+    /// converts back into the same float.
+    ///
+    /// Returns `None` if the float is not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(Decimal::from_f64(0.1), Some(Decimal::parse("0.1")?));
+    /// assert_eq!(Decimal::from_f64(f64::INFINITY), None);
+    /// ```
+    #[rune::function(path = Self::from_f64)]
+    fn from_f64(value: f64) -> Option<Decimal> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let mut buffer = ryu::Buffer::new();
+        let decimal = Self::from_str(buffer.format_finite(value)).ok()?;
+        Some(decimal.normalize(0))
+    }
+
+    /// Convert the decimal into an integer, discarding its fractional part.
+    ///
+    /// Returns `None` if the integer is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(Decimal::parse("-12.99")?.to_i64(), Some(-12));
+    /// assert_eq!(Decimal::parse("1e20")?.to_i64(), None);
+    /// ```
+    #[rune::function]
+    fn to_i64(&self) -> Option<i64> {
+        (&self.mantissa / pow10(self.scale)).to_i64()
+    }
+
+    /// Convert the decimal into the nearest float.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(Decimal::parse("0.1")?.to_f64(), 0.1);
+    /// ```
+    #[rune::function]
+    fn to_f64(&self) -> VmResult<f64> {
+        let mut string = String::new();
+        vm_write!(string, "{self}");
+        VmResult::Ok(string.parse().unwrap_or(f64::NAN))
+    }
+
+    /// Get the scale of the decimal, which is the number of digits after the
+    /// decimal point.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(Decimal::parse("1.250")?.scale(), 3);
+    /// assert_eq!(Decimal::from_i64(1).scale(), 0);
+    /// ```
+    #[rune::function]
+    fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Round the decimal to the given scale, rounding half away from zero.
+    ///
+    /// If the scale is larger than the scale of the decimal, zeros are added
+    /// to it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::parse("2.345")?.round(2)), "2.35");
+    /// assert_eq!(format!("{}", Decimal::parse("-2.345")?.round(2)), "-2.35");
+    /// assert_eq!(format!("{}", Decimal::parse("2.344")?.round(2)), "2.34");
+    /// assert_eq!(format!("{}", Decimal::parse("2.5")?.round(3)), "2.500");
+    /// ```
+    #[rune::function]
+    fn round(&self, scale: u32) -> Decimal {
+        if scale >= self.scale {
+            return Self {
+                mantissa: self.rescaled(scale),
+                scale,
+            };
+        }
+
+        Self {
+            mantissa: div_round(&self.mantissa, &pow10(self.scale - scale)),
+            scale,
+        }
+    }
+
+    /// Compute the absolute value of the decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(Decimal::parse("-1.5")?.abs(), Decimal::parse("1.5")?);
+    /// ```
+    #[rune::function]
+    fn abs(&self) -> Decimal {
+        Self {
+            mantissa: self.mantissa.abs(),
+            scale: self.scale,
+        }
+    }
+
+    /// Add to a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::parse("1.5")? + Decimal::parse("0.25")?), "1.75");
+    /// assert_eq!(format!("{}", Decimal::parse("1.50")? + 1), "2.50");
+    /// ```
+    #[rune::function(protocol = ADD)]
+    fn add(&self, other: Value) -> VmResult<Decimal> {
+        let other = vm_try!(operand(Protocol::ADD, &other));
+        let (a, b, scale) = aligned(self, &other);
+        VmResult::Ok(Self {
+            mantissa: a + b,
+            scale,
+        })
+    }
+
+    /// Subtract from a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::parse("1.5")? - Decimal::parse("0.25")?), "1.25");
+    /// ```
+    #[rune::function(protocol = SUB)]
+    fn sub(&self, other: Value) -> VmResult<Decimal> {
+        let other = vm_try!(operand(Protocol::SUB, &other));
+        let (a, b, scale) = aligned(self, &other);
+        VmResult::Ok(Self {
+            mantissa: a - b,
+            scale,
+        })
+    }
+
+    /// Multiply a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::parse("1.10")? * Decimal::parse("1.1")?), "1.210");
+    /// ```
+    #[rune::function(protocol = MUL)]
+    fn mul(&self, other: Value) -> VmResult<Decimal> {
+        let other = vm_try!(operand(Protocol::MUL, &other));
+        VmResult::Ok(vm_try!(self.checked_mul(&other)))
+    }
+
+    /// Divide a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::parse("10.00")? / 4), "2.50");
+    /// assert_eq!(format!("{}", Decimal::from_i64(2) / 3), "0.6666666666666667");
+    /// ```
+    #[rune::function(protocol = DIV)]
+    fn div(&self, other: Value) -> VmResult<Decimal> {
+        let other = vm_try!(operand(Protocol::DIV, &other));
+        self.checked_div(&other)
+    }
+
+    /// Compute the remainder of dividing a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::parse("7.5")? % 2), "1.5");
+    /// ```
+    #[rune::function(protocol = REM)]
+    fn rem(&self, other: Value) -> VmResult<Decimal> {
+        let other = vm_try!(operand(Protocol::REM, &other));
+        self.checked_rem(&other)
+    }
+
+    /// Negate a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", -Decimal::parse("1.50")?), "-1.50");
+    /// ```
+    #[rune::function(protocol = NEG)]
+    fn neg(&self) -> Decimal {
+        Self {
+            mantissa: -&self.mantissa,
+            scale: self.scale,
+        }
+    }
+
+    /// Add to a decimal in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// let d = Decimal::parse("1.5")?;
+    /// d += Decimal::parse("0.25")?;
+    /// assert_eq!(format!("{d}"), "1.75");
+    /// ```
+    #[rune::function(protocol = ADD_ASSIGN)]
+    fn add_assign(&mut self, other: Value) -> VmResult<()> {
+        let other = vm_try!(operand(Protocol::ADD_ASSIGN, &other));
+        let (a, b, scale) = aligned(self, &other);
+        self.mantissa = a + b;
+        self.scale = scale;
+        VmResult::Ok(())
+    }
+
+    /// Subtract from a decimal in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// let d = Decimal::parse("1.5")?;
+    /// d -= 2;
+    /// assert_eq!(format!("{d}"), "-0.5");
+    /// ```
+    #[rune::function(protocol = SUB_ASSIGN)]
+    fn sub_assign(&mut self, other: Value) -> VmResult<()> {
+        let other = vm_try!(operand(Protocol::SUB_ASSIGN, &other));
+        let (a, b, scale) = aligned(self, &other);
+        self.mantissa = a - b;
+        self.scale = scale;
+        VmResult::Ok(())
+    }
+
+    /// Multiply a decimal in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// let d = Decimal::parse("1.5")?;
+    /// d *= 3;
+    /// assert_eq!(format!("{d}"), "4.5");
+    /// ```
+    #[rune::function(protocol = MUL_ASSIGN)]
+    fn mul_assign(&mut self, other: Value) -> VmResult<()> {
+        let other = vm_try!(operand(Protocol::MUL_ASSIGN, &other));
+        *self = vm_try!(self.checked_mul(&other));
+        VmResult::Ok(())
+    }
+
+    /// Divide a decimal in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// let d = Decimal::parse("4.5")?;
+    /// d /= 3;
+    /// assert_eq!(format!("{d}"), "1.5");
+    /// ```
+    #[rune::function(protocol = DIV_ASSIGN)]
+    fn div_assign(&mut self, other: Value) -> VmResult<()> {
+        let other = vm_try!(operand(Protocol::DIV_ASSIGN, &other));
+        *self = vm_try!(self.checked_div(&other));
+        VmResult::Ok(())
+    }
+
+    /// Compute the remainder of dividing a decimal in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// let d = Decimal::parse("7.5")?;
+    /// d %= 2;
+    /// assert_eq!(format!("{d}"), "1.5");
+    /// ```
+    #[rune::function(protocol = REM_ASSIGN)]
+    fn rem_assign(&mut self, other: Value) -> VmResult<()> {
+        let other = vm_try!(operand(Protocol::REM_ASSIGN, &other));
+        *self = vm_try!(self.checked_rem(&other));
+        VmResult::Ok(())
+    }
+
+    /// Test a decimal for partial equality.
+    ///
+    /// Decimals which only differ in trailing zeros are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert!(Decimal::parse("1.50")? == Decimal::parse("1.5")?);
+    /// assert!(Decimal::parse("2.00")? == 2);
+    /// ```
+    #[rune::function(protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, other: Value) -> VmResult<bool> {
+        let other = vm_try!(operand(Protocol::PARTIAL_EQ, &other));
+        let (a, b, _) = aligned(self, &other);
+        VmResult::Ok(a == b)
+    }
+
+    /// Test a decimal for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    /// use std::ops::eq;
+    ///
+    /// assert!(eq(Decimal::parse("1.50")?, Decimal::parse("1.5")?));
+    /// ```
+    #[rune::function(protocol = EQ)]
+    fn eq(&self, other: Value) -> VmResult<bool> {
+        let other = vm_try!(operand(Protocol::EQ, &other));
+        let (a, b, _) = aligned(self, &other);
+        VmResult::Ok(a == b)
+    }
+
+    /// Perform a partial ordered comparison with a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert!(Decimal::parse("1.05")? < Decimal::parse("1.5")?);
+    /// assert!(Decimal::parse("2.01")? > 2);
+    /// ```
+    #[rune::function(protocol = PARTIAL_CMP)]
+    fn partial_cmp(&self, other: Value) -> VmResult<Option<Ordering>> {
+        let other = vm_try!(operand(Protocol::PARTIAL_CMP, &other));
+        let (a, b, _) = aligned(self, &other);
+        VmResult::Ok(Some(a.cmp(&b)))
+    }
+
+    /// Perform a totally ordered comparison with a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::num::Decimal;
+    /// use std::ops::cmp;
+    ///
+    /// assert_eq!(cmp(Decimal::parse("1.05")?, Decimal::parse("1.5")?), Ordering::Less);
+    /// ```
+    #[rune::function(protocol = CMP)]
+    fn cmp(&self, other: Value) -> VmResult<Ordering> {
+        let other = vm_try!(operand(Protocol::CMP, &other));
+        let (a, b, _) = aligned(self, &other);
+        VmResult::Ok(a.cmp(&b))
+    }
+
+    /// Hash a decimal.
+    ///
+    /// Decimals which are equal have the same hash, regardless of their scale.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    /// use std::ops::hash;
+    ///
+    /// assert_eq!(hash(Decimal::parse("1.50")?), hash(Decimal::parse("1.5")?));
+    /// ```
+    #[rune::function(protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        let normalized = self.clone().normalize(0);
+        hasher.write_u8(normalized.mantissa.sign() as u8);
+
+        for digit in normalized.mantissa.magnitude().iter_u64_digits() {
+            hasher.write_u64(digit);
+        }
+
+        hasher.write_u64(u64::from(normalized.scale));
+    }
+
+    /// Clone a decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// let a = Decimal::parse("1.5")?;
+    /// let b = a.clone();
+    /// b += 1;
+    ///
+    /// assert_eq!(format!("{a}"), "1.5");
+    /// assert_eq!(format!("{b}"), "2.5");
+    /// ```
+    #[rune::function]
+    fn clone(&self) -> Decimal {
+        Clone::clone(self)
+    }
+
+    /// Write a decimal with all of the digits of its scale.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{}", Decimal::new(5, 3)), "0.005");
+    /// ```
+    #[rune::function(protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{self}");
+        VmResult::Ok(())
+    }
+
+    /// Write a decimal for debugging.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::num::Decimal;
+    ///
+    /// assert_eq!(format!("{:?}", Decimal::new(-5, 3)), "-0.005");
+    /// ```
+    #[rune::function(protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{self}");
+        VmResult::Ok(())
+    }
+
+    fn from_str(s: &str) -> Result<Self, ParseDecimalError> {
+        let (negative, s) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (s, exponent) = match s.find(['e', 'E']) {
+            Some(n) => (&s[..n], Some(&s[n + 1..])),
+            None => (s, None),
+        };
+
+        let (integer, fraction) = match s.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (s, ""),
+        };
+
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(ParseDecimalError::new(ParseDecimalErrorKind::Empty));
+        }
+
+        let digits = integer.bytes().chain(fraction.bytes());
+        let mut mantissa = num::BigInt::zero();
+
+        for digit in digits {
+            if !digit.is_ascii_digit() {
+                return Err(ParseDecimalError::new(ParseDecimalErrorKind::InvalidDigit));
+            }
+
+            mantissa = mantissa * 10u32 + u32::from(digit - b'0');
+        }
+
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        let Ok(mut scale) = i64::try_from(fraction.len()) else {
+            return Err(ParseDecimalError::new(ParseDecimalErrorKind::Exponent));
+        };
+
+        if let Some(exponent) = exponent {
+            let Ok(exponent) = exponent.parse::<i32>() else {
+                return Err(ParseDecimalError::new(ParseDecimalErrorKind::Exponent));
+            };
+
+            if exponent.unsigned_abs() > MAX_EXPONENT {
+                return Err(ParseDecimalError::new(ParseDecimalErrorKind::Exponent));
+            }
+
+            scale -= i64::from(exponent);
+        }
+
+        if scale < 0 {
+            mantissa *= pow10(scale.unsigned_abs() as u32);
+            scale = 0;
+        }
+
+        let Ok(scale) = u32::try_from(scale) else {
+            return Err(ParseDecimalError::new(ParseDecimalErrorKind::Exponent));
+        };
+
+        Ok(Self { mantissa, scale })
+    }
+
+    /// The mantissa of the decimal at a scale which is at least as large as
+    /// its own.
+    fn rescaled(&self, scale: u32) -> num::BigInt {
+        debug_assert!(scale >= self.scale);
+
+        if scale == self.scale {
+            return self.mantissa.clone();
+        }
+
+        &self.mantissa * pow10(scale - self.scale)
+    }
+
+    /// Remove trailing zeros from the decimal, as long as its scale stays at
+    /// least `min`.
+    fn normalize(mut self, min: u32) -> Self {
+        let ten = num::BigInt::from(10u32);
+
+        while self.scale > min && !self.mantissa.is_zero() {
+            let (quotient, remainder) = self.mantissa.div_rem(&ten);
+
+            if !remainder.is_zero() {
+                break;
+            }
+
+            self.mantissa = quotient;
+            self.scale -= 1;
+        }
+
+        if self.mantissa.is_zero() {
+            self.scale = self.scale.min(min);
+        }
+
+        self
+    }
+
+    fn checked_mul(&self, other: &Decimal) -> VmResult<Decimal> {
+        let Some(scale) = self.scale.checked_add(other.scale) else {
+            return VmResult::err(VmErrorKind::Overflow);
+        };
+
+        VmResult::Ok(Self {
+            mantissa: &self.mantissa * &other.mantissa,
+            scale,
+        })
+    }
+
+    fn checked_div(&self, other: &Decimal) -> VmResult<Decimal> {
+        if other.mantissa.is_zero() {
+            return VmResult::err(VmErrorKind::DivideByZero);
+        }
+
+        let min = self.scale.max(other.scale);
+        let scale = min.max(DIVISION_SCALE);
+
+        // Scale the dividend so that the quotient has the expected scale.
+        let Some(shift) = scale.checked_add(other.scale) else {
+            return VmResult::err(VmErrorKind::Overflow);
+        };
+
+        let mantissa = div_round(&self.rescaled(shift), &other.mantissa);
+        VmResult::Ok(Self { mantissa, scale }.normalize(min))
+    }
+
+    fn checked_rem(&self, other: &Decimal) -> VmResult<Decimal> {
+        if other.mantissa.is_zero() {
+            return VmResult::err(VmErrorKind::DivideByZero);
+        }
+
+        let (a, b, scale) = aligned(self, other);
+
+        VmResult::Ok(Self {
+            mantissa: a % b,
+            scale,
+        })
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.magnitude().to_str_radix(10);
+        let scale = self.scale as usize;
+
+        if self.mantissa.is_negative() {
+            f.write_str("-")?;
+        }
+
+        if scale == 0 {
+            return f.write_str(&digits);
+        }
+
+        if digits.len() <= scale {
+            f.write_str("0.")?;
+
+            for _ in digits.len()..scale {
+                f.write_str("0")?;
+            }
+
+            return f.write_str(&digits);
+        }
+
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{integer}.{fraction}")
+    }
+}
+
+/// An error raised when a decimal can't be parsed.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::num::Decimal;
+///
+/// match Decimal::parse("1.5x") {
+///     Err(error) => assert_eq!(format!("{error}"), "invalid digit found in decimal"),
+///     Ok(..) => panic!("expected an error"),
+/// }
+/// ```
+#[derive(Any, Debug)]
+#[rune(item = ::std::num)]
+pub(crate) struct ParseDecimalError {
+    kind: ParseDecimalErrorKind,
+}
+
+#[derive(Debug)]
+enum ParseDecimalErrorKind {
+    Empty,
+    InvalidDigit,
+    Exponent,
+}
+
+impl ParseDecimalError {
+    fn new(kind: ParseDecimalErrorKind) -> Self {
+        Self { kind }
+    }
+
+    #[rune::function(protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{self}");
+        VmResult::Ok(())
+    }
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseDecimalErrorKind::Empty => write!(f, "cannot parse decimal from empty string"),
+            ParseDecimalErrorKind::InvalidDigit => write!(f, "invalid digit found in decimal"),
+            ParseDecimalErrorKind::Exponent => write!(f, "decimal exponent out of range"),
+        }
+    }
+}
+
+/// Construct `10^n`.
+fn pow10(n: u32) -> num::BigInt {
+    num::pow(num::BigInt::from(10u32), n as usize)
+}
+
+/// Divide two integers, rounding half away from zero.
+fn div_round(a: &num::BigInt, b: &num::BigInt) -> num::BigInt {
+    let (quotient, remainder) = a.div_rem(b);
+
+    if remainder.abs() * 2u32 < b.abs() {
+        return quotient;
+    }
+
+    if a.is_negative() == b.is_negative() {
+        quotient + 1u32
+    } else {
+        quotient - 1u32
+    }
+}
+
+/// Get the mantissas of two decimals at the larger of their scales.
+fn aligned(a: &Decimal, b: &Decimal) -> (num::BigInt, num::BigInt, u32) {
+    let scale = a.scale.max(b.scale);
+    (a.rescaled(scale), b.rescaled(scale), scale)
+}
+
+/// Convert the right-hand side of an operation into a decimal.
+fn operand(protocol: Protocol, value: &Value) -> VmResult<Decimal> {
+    let mantissa = match &*vm_try!(value.borrow_kind_ref()) {
+        ValueKind::Integer(n) => num::BigInt::from(*n),
+        ValueKind::U64(n) => num::BigInt::from(*n),
+        ValueKind::I128(n) => num::BigInt::from(*n),
+        ValueKind::U128(n) => num::BigInt::from(*n),
+        ValueKind::Any(any) => {
+            if let Ok(decimal) = any.downcast_borrow_ref::<Decimal>() {
+                return VmResult::Ok(decimal.clone());
+            }
+
+            match any.downcast_borrow_ref::<BigInt>() {
+                Ok(n) => n.as_inner().clone(),
+                Err(..) => {
+                    return VmResult::err(VmErrorKind::UnsupportedBinaryOperation {
+                        op: protocol.name,
+                        lhs: Decimal::type_info(),
+                        rhs: any.type_info(),
+                    });
+                }
+            }
+        }
+        kind => {
+            return VmResult::err(VmErrorKind::UnsupportedBinaryOperation {
+                op: protocol.name,
+                lhs: Decimal::type_info(),
+                rhs: kind.type_info(),
+            });
+        }
+    };
+
+    VmResult::Ok(Decimal { mantissa, scale: 0 })
+}
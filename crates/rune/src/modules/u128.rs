@@ -0,0 +1,517 @@
+//! Unsigned 128-bit integers.
+
+use core::cmp::Ordering;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::string::TryToString;
+use crate::runtime::{VmErrorKind, VmResult};
+use crate::{ContextError, Module};
+
+/// Unsigned 128-bit integers.
+///
+/// This provides methods for computing over and parsing 128-bit unsigned
+/// integers.
+///
+/// Unsigned 128-bit integers are written using the `u128` suffix, like
+/// `42u128`. When mixed with other integers in arithmetic, both operands are
+/// converted into a `u128`, raising an error if either doesn't fit. Use `as` to
+/// convert between them explicitly.
+///
+/// Native functions returning a `u128` always produce a `u128`, no matter
+/// how small the value is.
+///
+/// # Examples
+///
+/// ```rune
+/// let n = 40u128 + 2u128;
+/// assert_eq!(n, 42u128);
+/// assert_eq!(n as i64, 42);
+/// assert_eq!(42 as u128, n);
+/// assert_eq!(n + 1u64, 43u128);
+/// ```
+#[rune::module(::std::u128)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::from_meta(self::module_meta)?;
+
+    module.function("parse", parse).build()?;
+    module.function_meta(to_float)?;
+
+    module.function_meta(max)?;
+    module.function_meta(min)?;
+    module.function_meta(pow)?;
+
+    module.function_meta(checked_add)?;
+    module.function_meta(checked_sub)?;
+    module.function_meta(checked_div)?;
+    module.function_meta(checked_mul)?;
+    module.function_meta(checked_rem)?;
+
+    module.function_meta(wrapping_add)?;
+    module.function_meta(wrapping_sub)?;
+    module.function_meta(wrapping_div)?;
+    module.function_meta(wrapping_mul)?;
+    module.function_meta(wrapping_rem)?;
+
+    module.function_meta(saturating_add)?;
+    module.function_meta(saturating_sub)?;
+    module.function_meta(saturating_mul)?;
+    module.function_meta(saturating_pow)?;
+
+    module.function_meta(partial_eq)?;
+    module.function_meta(eq)?;
+    module.function_meta(partial_cmp)?;
+    module.function_meta(cmp)?;
+    module.function_meta(to_string)?;
+
+    module.constant("MIN", u128::MIN).build()?.docs([
+        "The smallest value that can be represented by this integer type (0).",
+        "",
+        "# Examples",
+        "",
+        "Basic usage:",
+        "",
+        "```rune",
+        "assert_eq!(u128::MIN, 0u128);",
+        "```",
+    ])?;
+
+    module.constant("MAX", u128::MAX).build()?.docs([
+        "The largest value that can be represented by this integer type",
+        "(2<sup>128</sup> &minus; 1).",
+        "",
+        "# Examples",
+        "",
+        "Basic usage:",
+        "",
+        "```rune",
+        "assert_eq!(u128::MAX, 340282366920938463463374607431768211455u128);",
+        "```",
+    ])?;
+
+    Ok(module)
+}
+
+/// Parse a `u128`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(u128::parse("10")?, 10u128);
+/// assert!(u128::parse("-10").is_err());
+/// ```
+fn parse(s: &str) -> Result<u128, ParseIntError> {
+    str::parse::<u128>(s)
+}
+
+/// Convert a `u128` to a `float`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!(10u128.to::<f64>() is f64);
+/// ```
+#[rune::function(instance, path = to::<f64>)]
+#[inline]
+fn to_float(value: u128) -> f64 {
+    value as f64
+}
+
+/// Compares and returns the maximum of two values.
+///
+/// Returns the second argument if the comparison determines them to be equal.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(2u128, 1u128.max(2u128));
+/// assert_eq!(2u128, 2u128.max(2u128));
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn max(this: u128, other: u128) -> u128 {
+    u128::max(this, other)
+}
+
+/// Compares and returns the minimum of two values.
+///
+/// Returns the first argument if the comparison determines them to be equal.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(1u128, 1u128.min(2u128));
+/// assert_eq!(2u128, 2u128.min(2u128));
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn min(this: u128, other: u128) -> u128 {
+    u128::min(this, other)
+}
+
+/// Raises self to the power of `exp`, using exponentiation by squaring.
+///
+/// # Overflow behavior
+///
+/// This function will wrap on overflow.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// let x = 2u128;
+///
+/// assert_eq!(x.pow(5), 32u128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn pow(this: u128, pow: u32) -> u128 {
+    u128::wrapping_pow(this, pow)
+}
+
+/// Checked integer addition. Computes `self + rhs`, returning `None` if
+/// overflow occurred.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!((u128::MAX - 2u128).checked_add(1u128), Some(u128::MAX - 1u128));
+/// assert_eq!((u128::MAX - 2u128).checked_add(3u128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_add(this: u128, rhs: u128) -> Option<u128> {
+    u128::checked_add(this, rhs)
+}
+
+/// Checked integer subtraction. Computes `self - rhs`, returning `None` if
+/// overflow occurred.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(1u128.checked_sub(1u128), Some(0u128));
+/// assert_eq!(0u128.checked_sub(1u128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_sub(this: u128, rhs: u128) -> Option<u128> {
+    u128::checked_sub(this, rhs)
+}
+
+/// Checked integer division. Computes `self / rhs`, returning `None` if `rhs ==
+/// 0`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(128u128.checked_div(2u128), Some(64u128));
+/// assert_eq!(1u128.checked_div(0u128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_div(this: u128, rhs: u128) -> Option<u128> {
+    u128::checked_div(this, rhs)
+}
+
+/// Checked integer multiplication. Computes `self * rhs`, returning `None` if
+/// overflow occurred.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(u128::MAX.checked_mul(1u128), Some(u128::MAX));
+/// assert_eq!(u128::MAX.checked_mul(2u128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_mul(this: u128, rhs: u128) -> Option<u128> {
+    u128::checked_mul(this, rhs)
+}
+
+/// Checked integer remainder. Computes `self % rhs`, returning `None` if `rhs
+/// == 0`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(5u128.checked_rem(2u128), Some(1u128));
+/// assert_eq!(5u128.checked_rem(0u128), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_rem(this: u128, rhs: u128) -> Option<u128> {
+    u128::checked_rem(this, rhs)
+}
+
+/// Wrapping (modular) addition. Computes `self + rhs`, wrapping around at the
+/// boundary of the type.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(200u128.wrapping_add(55u128), 255u128);
+/// assert_eq!(u128::MAX.wrapping_add(2u128), 1u128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_add(this: u128, rhs: u128) -> u128 {
+    u128::wrapping_add(this, rhs)
+}
+
+/// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping around at
+/// the boundary of the type.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100u128.wrapping_sub(100u128), 0u128);
+/// assert_eq!(0u128.wrapping_sub(1u128), u128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_sub(this: u128, rhs: u128) -> u128 {
+    u128::wrapping_sub(this, rhs)
+}
+
+/// Wrapping (modular) division. Computes `self / rhs`.
+///
+/// Wrapped division on unsigned types is just normal division. There's no way
+/// wrapping could ever happen. This function exists, so that all operations are
+/// accounted for in the wrapping operations.
+///
+/// # Panics
+///
+/// This function will panic if `rhs` is 0.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100u128.wrapping_div(10u128), 10u128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_div(this: u128, rhs: u128) -> VmResult<u128> {
+    if rhs == 0 {
+        return VmResult::err(VmErrorKind::DivideByZero);
+    }
+
+    VmResult::Ok(u128::wrapping_div(this, rhs))
+}
+
+/// Wrapping (modular) multiplication. Computes `self * rhs`, wrapping around at
+/// the boundary of the type.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(10u128.wrapping_mul(12u128), 120u128);
+/// assert_eq!(u128::MAX.wrapping_mul(2u128), u128::MAX - 1u128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_mul(this: u128, rhs: u128) -> u128 {
+    u128::wrapping_mul(this, rhs)
+}
+
+/// Wrapping (modular) remainder. Computes `self % rhs`.
+///
+/// Wrapped remainder calculation on unsigned types is just the regular
+/// remainder calculation. There's no way wrapping could ever happen. This
+/// function exists, so that all operations are accounted for in the wrapping
+/// operations.
+///
+/// # Panics
+///
+/// This function will panic if `rhs` is 0.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100u128.wrapping_rem(10u128), 0u128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_rem(this: u128, rhs: u128) -> VmResult<u128> {
+    if rhs == 0 {
+        return VmResult::err(VmErrorKind::DivideByZero);
+    }
+
+    VmResult::Ok(u128::wrapping_rem(this, rhs))
+}
+
+/// Saturating integer addition. Computes `self + rhs`, saturating at the
+/// numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100u128.saturating_add(1u128), 101u128);
+/// assert_eq!(u128::MAX.saturating_add(100u128), u128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_add(this: u128, rhs: u128) -> u128 {
+    u128::saturating_add(this, rhs)
+}
+
+/// Saturating integer subtraction. Computes `self - rhs`, saturating at the
+/// numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100u128.saturating_sub(27u128), 73u128);
+/// assert_eq!(13u128.saturating_sub(127u128), 0u128);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_sub(this: u128, rhs: u128) -> u128 {
+    u128::saturating_sub(this, rhs)
+}
+
+/// Saturating integer multiplication. Computes `self * rhs`, saturating at the
+/// numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(2u128.saturating_mul(10u128), 20u128);
+/// assert_eq!(u128::MAX.saturating_mul(10u128), u128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_mul(this: u128, rhs: u128) -> u128 {
+    u128::saturating_mul(this, rhs)
+}
+
+/// Saturating integer exponentiation. Computes `self.pow(exp)`, saturating at
+/// the numeric bounds instead of overflowing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(4u128.saturating_pow(3), 64u128);
+/// assert_eq!(u128::MAX.saturating_pow(2), u128::MAX);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn saturating_pow(this: u128, rhs: u32) -> u128 {
+    u128::saturating_pow(this, rhs)
+}
+
+/// Test two unsigned integers for partial equality.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::partial_eq;
+///
+/// assert_eq!(partial_eq(5u128, 5u128), true);
+/// assert_eq!(partial_eq(5u128, 10u128), false);
+/// assert_eq!(partial_eq(10u128, 5u128), false);
+/// ```
+#[rune::function(instance, protocol = PARTIAL_EQ)]
+#[inline]
+fn partial_eq(this: u128, rhs: u128) -> bool {
+    this.eq(&rhs)
+}
+
+/// Test two unsigned integers for total equality.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::eq;
+///
+/// assert_eq!(eq(5u128, 5u128), true);
+/// assert_eq!(eq(5u128, 10u128), false);
+/// assert_eq!(eq(10u128, 5u128), false);
+/// ```
+#[rune::function(instance, protocol = EQ)]
+#[inline]
+fn eq(this: u128, rhs: u128) -> bool {
+    this.eq(&rhs)
+}
+
+/// Perform a partial ordered comparison between two unsigned integers.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::partial_cmp;
+///
+/// assert_eq!(partial_cmp(5u128, 10u128), Some(Ordering::Less));
+/// assert_eq!(partial_cmp(10u128, 5u128), Some(Ordering::Greater));
+/// assert_eq!(partial_cmp(5u128, 5u128), Some(Ordering::Equal));
+/// ```
+#[rune::function(instance, protocol = PARTIAL_CMP)]
+#[inline]
+fn partial_cmp(this: u128, rhs: u128) -> Option<Ordering> {
+    this.partial_cmp(&rhs)
+}
+
+/// Perform a totally ordered comparison between two unsigned integers.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::cmp;
+///
+/// assert_eq!(cmp(5u128, 10u128), Ordering::Less);
+/// assert_eq!(cmp(10u128, 5u128), Ordering::Greater);
+/// assert_eq!(cmp(5u128, 5u128), Ordering::Equal);
+/// ```
+#[rune::function(instance, protocol = CMP)]
+#[inline]
+fn cmp(this: u128, rhs: u128) -> Ordering {
+    this.cmp(&rhs)
+}
+
+/// Returns the number as a string.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(10u128.to_string(), "10");
+/// assert_eq!(u128::MAX.to_string(), "340282366920938463463374607431768211455");
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn to_string(this: u128) -> VmResult<alloc::String> {
+    VmResult::Ok(vm_try!(this.try_to_string()))
+}
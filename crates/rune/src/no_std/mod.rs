@@ -9,6 +9,7 @@ pub struct RawEnv {
     pub(crate) context: Option<NonNull<()>>,
     pub(crate) unit: Option<NonNull<()>>,
     pub(crate) diagnostics: Option<NonNull<()>>,
+    pub(crate) coverage: Option<NonNull<()>>,
 }
 
 impl RawEnv {
@@ -18,6 +19,7 @@ impl RawEnv {
             context: None,
             unit: None,
             diagnostics: None,
+            coverage: None,
         }
     }
 }
@@ -21,6 +21,7 @@ use crate::hir;
 use crate::indexing;
 use crate::parse::NonZeroId;
 use crate::runtime::format;
+use crate::SourceId;
 
 /// Indication whether a value is being evaluated because it's being used or not.
 #[derive(Debug, TryClone, Clone, Copy)]
@@ -153,9 +154,24 @@ pub(crate) struct QueryImplFn {
     pub(crate) ast: Box<ast::ItemFn>,
 }
 
+/// The default functions of a trait, which are indexed into every
+/// implementation of the trait that doesn't provide its own.
+pub(crate) struct QueryTraitDefaults {
+    /// The module the trait is declared in.
+    pub(crate) module: ModId,
+    /// The source the trait is declared in.
+    pub(crate) source_id: SourceId,
+    ///See [Indexer][crate::indexing::Indexer].
+    pub(crate) root: Option<PathBuf>,
+    /// Non-expanded ast of the default functions.
+    pub(crate) functions: Vec<ast::ItemFn>,
+}
+
 pub(crate) struct ItemImplEntry {
     /// Non-expanded ast of the path.
     pub(crate) path: Box<ast::Path>,
+    /// Non-expanded ast of the path to the trait being implemented, if any.
+    pub(crate) trait_: Option<Box<ast::Path>>,
     /// Location where the item impl is defined and is being expanded.
     pub(crate) location: Location,
     /// The item impl being expanded.
@@ -7,7 +7,7 @@ use ::rust_alloc::sync::Arc;
 
 use crate::alloc::borrow::Cow;
 use crate::alloc::prelude::*;
-use crate::alloc::{self, BTreeMap, HashSet, VecDeque};
+use crate::alloc::{self, BTreeMap, BTreeSet, HashSet, String, VecDeque};
 use crate::alloc::{hash_map, HashMap};
 use crate::ast::{Span, Spanned};
 use crate::compile::context::ContextMeta;
@@ -18,14 +18,16 @@ use crate::compile::{
     Item, ItemBuf, ItemId, ItemMeta, Located, Location, MetaError, ModId, ModMeta, Names, Pool,
     Prelude, SourceLoader, SourceMeta, UnitBuilder, Visibility, WithSpan,
 };
+use crate::diagnostics::Suggestion;
 use crate::hir;
 use crate::indexing::{self, FunctionAst, Indexed, Items};
 use crate::macros::Storage;
 use crate::parse::{Id, NonZeroId, Opaque, Resolve, ResolveContext};
 use crate::query::{
     Build, BuildEntry, BuiltInMacro, ConstFn, GenericsParameters, ItemImplEntry, Named,
-    QueryImplFn, QueryPath, Used,
+    QueryImplFn, QueryPath, QueryTraitDefaults, Used,
 };
+use crate::runtime::static_type;
 #[cfg(feature = "doc")]
 use crate::runtime::Call;
 use crate::runtime::ConstValue;
@@ -61,6 +63,8 @@ const IMPORT_RECURSION_LIMIT: usize = 128;
 pub(crate) struct QueryInner<'arena> {
     /// Resolved meta about every single item during a compilation.
     meta: HashMap<(ItemId, Hash), meta::Meta>,
+    /// Index from the hash of a resolved meta to its key in `meta`.
+    meta_by_hash: HashMap<Hash, (ItemId, Hash)>,
     /// Build queue.
     pub(crate) queue: VecDeque<BuildEntry>,
     /// Set of used items.
@@ -78,6 +82,11 @@ pub(crate) struct QueryInner<'arena> {
     pub(crate) impl_functions: HashMap<NonZeroId, Vec<QueryImplFn>>,
     /// Queue of impl items to process.
     pub(crate) impl_item_queue: VecDeque<ItemImplEntry>,
+    /// Default functions of traits.
+    pub(crate) trait_defaults: HashMap<ItemId, QueryTraitDefaults>,
+    /// Functions implementing traits, by the item they would have if they were
+    /// declared directly on the implementing type.
+    trait_functions: HashMap<ItemId, Vec<ItemId>>,
     /// The result of internally resolved macros.
     internal_macros: HashMap<NonZeroId, Arc<BuiltInMacro>>,
     /// Associated between `id` and `Item`. Use to look up items through
@@ -344,7 +353,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
         }
 
         let Some(metas) = self.context.lookup_meta(self.pool.item(item)) else {
-            return Ok(None);
+            return self.query_trait_function(location, item, parameters);
         };
 
         let (meta, parameters) = match self
@@ -352,7 +361,15 @@ impl<'a, 'arena> Query<'a, 'arena> {
             .with_span(location.as_spanned())?
         {
             ContextMatch::None => return Ok(None),
-            ContextMatch::Meta(meta) => return Ok(Some(meta.try_clone()?)),
+            ContextMatch::Meta(meta) => {
+                let meta = meta.try_clone()?;
+
+                self.visitor
+                    .visit_meta(location, meta.as_meta_ref(self.pool))
+                    .with_span(location.as_spanned())?;
+
+                return Ok(Some(meta));
+            }
             ContextMatch::Context(meta, parameters) => (meta, parameters),
         };
 
@@ -403,18 +420,145 @@ impl<'a, 'arena> Query<'a, 'arena> {
             return Ok(meta);
         }
 
-        let kind = if !parameters.parameters.is_empty() {
-            ErrorKind::MissingItemParameters {
-                item: self.pool.item(item).try_to_owned()?,
-                parameters: parameters.as_boxed()?,
-            }
-        } else {
+        Err(self.missing_item(location, item, parameters)?)
+    }
+
+    /// Construct an error for an item which is missing, suggesting imports
+    /// which might make it available.
+    pub(crate) fn missing_item(
+        &self,
+        location: &dyn Located,
+        item: ItemId,
+        parameters: &GenericsParameters,
+    ) -> compile::Result<compile::Error> {
+        if !parameters.is_empty() {
+            return Ok(compile::Error::new(
+                location.as_spanned(),
+                ErrorKind::MissingItemParameters {
+                    item: self.pool.item(item).try_to_owned()?,
+                    parameters: parameters.as_boxed()?,
+                },
+            ));
+        }
+
+        let mut error = compile::Error::new(
+            location.as_spanned(),
             ErrorKind::MissingItem {
                 item: self.pool.item(item).try_to_owned()?,
+            },
+        );
+
+        for suggestion in self.suggest_imports(location.location())? {
+            error = error.with_suggestion(suggestion);
+        }
+
+        Ok(error)
+    }
+
+    /// Suggest imports for the first component of the path at the given
+    /// location, like `use std::collections::HashMap;` for `HashMap::new`.
+    fn suggest_imports(&self, location: Location) -> alloc::Result<Vec<Suggestion>> {
+        /// The maximum number of imports to suggest.
+        const LIMIT: usize = 8;
+
+        let mut suggestions = Vec::new();
+
+        let Some(source) = self.sources.get(location.source_id) else {
+            return Ok(suggestions);
+        };
+
+        let Some(path) = source.get(location.span.range()) else {
+            return Ok(suggestions);
+        };
+
+        let mut segments = path
+            .split("::")
+            .map(str::trim)
+            .take_while(|s| !s.is_empty() && s.chars().all(|c| c == '_' || c.is_alphanumeric()));
+
+        let Some(name) = segments.next() else {
+            return Ok(suggestions);
+        };
+
+        if ast::Kind::from_keyword(name).is_some() {
+            return Ok(suggestions);
+        }
+
+        let rest = segments.try_collect::<Vec<_>>()?;
+
+        // Items paired with whether they are declared in the script.
+        let mut items = Vec::new();
+
+        for meta in self.context.iter_meta() {
+            if let Some(item) = &meta.item {
+                if is_importable(&meta.kind) {
+                    items.try_push((&**item, false))?;
+                }
+            }
+        }
+
+        for meta in self.inner.meta.values() {
+            if is_importable(&meta.kind) {
+                items.try_push((self.pool.item(meta.item_meta.item), true))?;
+            }
+        }
+
+        for (item, entries) in &self.inner.indexed {
+            let importable = entries.iter().any(|entry| match &entry.indexed {
+                Indexed::Function(f) => f.impl_item.is_none() && !f.is_instance,
+                Indexed::Variant(..) | Indexed::Import(..) => false,
+                _ => true,
+            });
+
+            if importable {
+                items.try_push((self.pool.item(*item), true))?;
+            }
+        }
+
+        let mut candidates = BTreeSet::new();
+
+        for (item, in_crate) in items {
+            let Some(path) = import_path(item, name, in_crate)? else {
+                continue;
+            };
+
+            // Only suggest imports through which the rest of the path
+            // resolves, like `HashMap::new`.
+            if !rest.is_empty() {
+                let mut full = item.try_to_owned()?;
+
+                for segment in &rest {
+                    full.push(*segment)?;
+                }
+
+                if !self.context.contains_prefix(&full)?
+                    && !self.inner.names.contains_prefix(&full)?
+                {
+                    continue;
+                }
             }
+
+            candidates.try_insert(path)?;
+        }
+
+        let offset = import_offset(source.as_str());
+
+        // Make sure the import ends up on a line of its own.
+        let newline = if source.as_str()[..offset].ends_with('\n') || offset == 0 {
+            ""
+        } else {
+            "\n"
         };
 
-        Err(compile::Error::new(location.as_spanned(), kind))
+        for path in candidates.into_iter().take(LIMIT) {
+            suggestions.try_push(Suggestion::new(
+                format_args!("Import `{path}`"),
+                Span::new(offset, offset),
+                &try_format!("{newline}use {path};\n"),
+            )?)?;
+        }
+
+        Ok(suggestions)
     }
 
     pub(crate) fn lookup_deprecation(&self, hash: Hash) -> Option<&str> {
@@ -541,6 +685,10 @@ impl<'a, 'arena> Query<'a, 'arena> {
             hash_map::Entry::Vacant(e) => e.try_insert(meta)?,
         };
 
+        self.inner
+            .meta_by_hash
+            .try_insert(meta.hash, (meta.item_meta.item, meta.parameters))?;
+
         Ok(&meta.item_meta)
     }
 
@@ -664,6 +812,64 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(())
     }
 
+    /// Record that the function with the given item implements a trait, so
+    /// that it can also be looked up through the implementing type.
+    pub(crate) fn insert_trait_function(&mut self, item: ItemId) -> compile::Result<()> {
+        let function = self.pool.item(item);
+
+        // The function is indexed in an item unique to the implementation,
+        // like `Type::$0::name`.
+        let Some(name) = function.last() else {
+            return Ok(());
+        };
+
+        let Some(ty) = function.parent().and_then(Item::parent) else {
+            return Ok(());
+        };
+
+        let ty = ty.extended(name)?;
+        let ty = self.pool.alloc_item(ty)?;
+
+        self.inner
+            .trait_functions
+            .entry(ty)
+            .or_try_default()?
+            .try_push(item)?;
+
+        Ok(())
+    }
+
+    /// Look up a function implementing a trait through the item it would have
+    /// if it was declared directly on the implementing type.
+    ///
+    /// This only succeeds if the function is provided by exactly one of the
+    /// traits implemented by the type.
+    fn query_trait_function(
+        &mut self,
+        location: &dyn Located,
+        item: ItemId,
+        parameters: &GenericsParameters,
+    ) -> compile::Result<Option<meta::Meta>> {
+        if !parameters.is_empty() {
+            return Ok(None);
+        }
+
+        let Some(&[function]) = self.inner.trait_functions.get(&item).map(|f| &f[..]) else {
+            return Ok(None);
+        };
+
+        let Some(meta) = self.query_meta(location.as_spanned(), function, Default::default())?
+        else {
+            return Ok(None);
+        };
+
+        self.visitor
+            .visit_meta(location, meta.as_meta_ref(self.pool))
+            .with_span(location.as_spanned())?;
+
+        Ok(Some(meta))
+    }
+
     /// Same as `index`, but also queues the indexed entry up for building.
     #[tracing::instrument(skip_all)]
     pub(crate) fn index_and_build(&mut self, entry: indexing::Entry) -> compile::Result<()> {
@@ -897,6 +1103,28 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(None)
     }
 
+    /// Get already resolved meta by its hash.
+    pub(crate) fn meta_by_hash(&self, hash: Hash) -> Option<&meta::Meta> {
+        let key = self.inner.meta_by_hash.get(&hash)?;
+        self.inner.meta.get(key)
+    }
+
+    /// Get the name of the type with the given hash, for use in diagnostics.
+    pub(crate) fn type_name(&self, hash: Hash) -> alloc::Result<String> {
+        let item = match self.meta_by_hash(hash) {
+            Some(meta) => Some(self.pool.item(meta.item_meta.item)),
+            None => self
+                .context
+                .lookup_meta_by_hash(hash)
+                .find_map(|meta| meta.item.as_deref()),
+        };
+
+        match item.and_then(|item| item.last()) {
+            Some(name) => Ok(try_format!("{name}")),
+            None => Ok(try_format!("{hash}")),
+        }
+    }
+
     /// Perform a default path conversion.
     pub(crate) fn convert_path<'ast>(
         &mut self,
@@ -905,6 +1133,43 @@ impl<'a, 'arena> Query<'a, 'arena> {
         self.convert_path_with(path, false, Used::Used, Used::Used)
     }
 
+    /// Resolve a type annotation into the hash of the type it refers to.
+    pub(crate) fn convert_type(
+        &mut self,
+        source_id: SourceId,
+        ast: &ast::Type,
+    ) -> compile::Result<Hash> {
+        let path = match ast {
+            ast::Type::Path(path) => path,
+            ast::Type::Tuple(..) => return Ok(static_type::TUPLE_TYPE.hash),
+        };
+
+        let named = self.convert_path(path)?;
+
+        if let Some((spanned, _)) = named.parameters.iter().flatten().next() {
+            return Err(compile::Error::new(
+                spanned.span(),
+                ErrorKind::UnsupportedGenerics,
+            ));
+        }
+
+        let meta = self.lookup_meta(
+            &DynLocation::new(source_id, path),
+            named.item,
+            GenericsParameters::default(),
+        )?;
+
+        let Some(hash) = meta.type_hash_of() else {
+            return Err(compile::Error::expected_meta(
+                path,
+                meta.info(self.pool)?,
+                "a type",
+            ));
+        };
+
+        Ok(hash)
+    }
+
     /// Perform a path conversion with custom configuration.
     #[tracing::instrument(skip(self, path))]
     pub(crate) fn convert_path_with<'ast>(
@@ -1352,6 +1617,32 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 parameters: Hash::EMPTY,
             },
             Indexed::Function(f) => {
+                #[cfg(feature = "doc")]
+                let (return_type, argument_types) = match &f.ast {
+                    FunctionAst::Item(ast) => {
+                        let source_id = item_meta.location.source_id;
+
+                        let return_type = match &ast.output {
+                            Some((_, ty)) => Some(self.convert_type(source_id, ty)?),
+                            None => None,
+                        };
+
+                        let mut argument_types = Vec::new();
+
+                        for (arg, _) in &ast.args {
+                            let ty = match arg.ty() {
+                                Some(ty) => Some(self.convert_type(source_id, ty)?),
+                                None => None,
+                            };
+
+                            argument_types.try_push(ty)?;
+                        }
+
+                        (return_type, argument_types.try_into_boxed_slice()?)
+                    }
+                    FunctionAst::Empty(..) => (None, Box::default()),
+                };
+
                 let kind = meta::Kind::Function {
                     associated: match (f.is_instance, &f.ast) {
                         (true, FunctionAst::Item(ast)) => {
@@ -1369,9 +1660,9 @@ impl<'a, 'arena> Query<'a, 'arena> {
                         #[cfg(feature = "doc")]
                         args: Some(f.ast.args()),
                         #[cfg(feature = "doc")]
-                        return_type: None,
+                        return_type,
                         #[cfg(feature = "doc")]
-                        argument_types: Box::default(),
+                        argument_types,
                     },
                     parameters: Hash::EMPTY,
                     #[cfg(feature = "doc")]
@@ -1801,3 +2092,79 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Some(self.inner.captures.get(&hash)?)
     }
 }
+
+/// Test if an item of the given kind can be imported with a `use`.
+fn is_importable(kind: &meta::Kind) -> bool {
+    match kind {
+        meta::Kind::Function { associated, .. } => associated.is_none(),
+        meta::Kind::Type { .. }
+        | meta::Kind::Struct { .. }
+        | meta::Kind::Enum { .. }
+        | meta::Kind::Macro
+        | meta::Kind::Const
+        | meta::Kind::ConstFn { .. }
+        | meta::Kind::Module => true,
+        _ => false,
+    }
+}
+
+/// Construct the path to use to import the given item, if its last
+/// component is the given name.
+fn import_path(item: &Item, name: &str, in_crate: bool) -> alloc::Result<Option<String>> {
+    if item.last() != Some(ComponentRef::Str(name)) {
+        return Ok(None);
+    }
+
+    let mut path = String::new();
+
+    if in_crate {
+        path.try_push_str("crate")?;
+    }
+
+    for component in item {
+        let (ComponentRef::Crate(c) | ComponentRef::Str(c)) = component else {
+            return Ok(None);
+        };
+
+        if !path.is_empty() {
+            path.try_push_str("::")?;
+        }
+
+        path.try_push_str(c)?;
+    }
+
+    Ok(Some(path))
+}
+
+/// Find the offset at which a new import should be inserted, which is after
+/// the last top-level import or any leading inner attributes and comments.
+fn import_offset(source: &str) -> usize {
+    let mut offset = 0;
+    let mut header = 0;
+    let mut in_header = true;
+    let mut in_import = false;
+    let mut imports = None;
+
+    for line in source.split_inclusive('\n') {
+        offset += line.len();
+
+        if in_header && (line.starts_with("//!") || line.starts_with("#!")) {
+            header = offset;
+            continue;
+        }
+
+        in_header = false;
+
+        if line.starts_with("use ") || line.starts_with("pub use ") {
+            in_import = true;
+        }
+
+        // Imports might span multiple lines.
+        if in_import && line.trim_end().ends_with(';') {
+            in_import = false;
+            imports = Some(offset);
+        }
+    }
+
+    imports.unwrap_or(header)
+}
@@ -25,11 +25,17 @@ mod call;
 pub use self::call::Call;
 
 mod const_value;
-pub use self::const_value::ConstValue;
+pub use self::const_value::{ConstValue, ConstVariantData};
+
+mod coverage;
+pub use self::coverage::Coverage;
 
 pub mod debug;
 pub use self::debug::{DebugInfo, DebugInst};
 
+mod debugger;
+pub use self::debugger::{DebugFrame, DebugLocation, DebugStep, DebugStop, Debugger};
+
 mod env;
 
 pub mod format;
@@ -58,8 +64,8 @@ pub use self::guarded_args::GuardedArgs;
 
 mod inst;
 pub use self::inst::{
-    Inst, InstAddress, InstAssignOp, InstOp, InstRange, InstTarget, InstValue, InstVariant,
-    IntoOutput, Output, PanicReason, TypeCheck,
+    Inst, InstAddress, InstAssignOp, InstI128, InstOp, InstRange, InstTarget, InstU128, InstValue,
+    InstVariant, IntoOutput, Output, PanicReason, TypeCheck,
 };
 
 mod iterator;
@@ -82,6 +88,13 @@ mod protocol;
 pub use self::protocol::Protocol;
 
 mod protocol_caller;
+
+#[cfg(feature = "std")]
+mod profiler;
+#[cfg(feature = "std")]
+pub(crate) use self::profiler::ProfileCursor;
+#[cfg(feature = "std")]
+pub use self::profiler::{ProfileFrame, ProfileSample, Profiler};
 pub(crate) use self::protocol_caller::{EnvProtocolCaller, ProtocolCaller};
 
 mod range_from;
@@ -178,6 +191,13 @@ mod vm_execution;
 pub(crate) use self::vm_execution::ExecutionState;
 pub use self::vm_execution::{VmExecution, VmSendExecution};
 
+#[cfg(feature = "byte-code")]
+mod vm_snapshot;
+#[cfg(feature = "byte-code")]
+pub(crate) use self::vm_snapshot::VmSnapshotErrorKind;
+#[cfg(feature = "byte-code")]
+pub use self::vm_snapshot::{VmSnapshot, VmSnapshotError};
+
 mod vm_halt;
 pub(crate) use self::vm_halt::{VmHalt, VmHaltInfo};
 
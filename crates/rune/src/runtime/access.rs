@@ -166,6 +166,12 @@ impl Snapshot {
     pub(crate) fn is_readable(&self) -> bool {
         self.0 & MASK == 0
     }
+
+    /// Test if the snapshot indicates that the value has been taken.
+    #[cfg(feature = "byte-code")]
+    pub(crate) fn is_taken(&self) -> bool {
+        self.0 & MOVED != 0
+    }
 }
 
 impl fmt::Display for Snapshot {
@@ -1,10 +1,12 @@
+use ::rust_alloc::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
 use crate::alloc::prelude::*;
 use crate::alloc::{self, Box, HashMap, String, Vec};
 use crate::runtime::{
-    self, Bytes, FromValue, Object, OwnedTuple, ToValue, TypeInfo, Value, ValueKind, VmErrorKind,
-    VmResult,
+    self, Bytes, EmptyStruct, FromValue, Object, OwnedTuple, Rtti, Struct, ToValue, TupleStruct,
+    TypeInfo, Value, ValueKind, Variant, VariantData, VariantRtti, VmErrorKind, VmResult,
 };
 
 /// A constant value.
@@ -20,6 +22,12 @@ pub enum ConstValue {
     Bool(bool),
     /// An integer constant.
     Integer(i64),
+    /// An unsigned integer constant.
+    U64(u64),
+    /// A 128-bit integer constant.
+    I128(i128),
+    /// An unsigned 128-bit integer constant.
+    U128(u128),
     /// An float constant.
     Float(f64),
     /// A string constant designated by its slot.
@@ -34,6 +42,45 @@ pub enum ConstValue {
     Object(HashMap<String, ConstValue>),
     /// An option.
     Option(Option<Box<ConstValue>>),
+    /// An empty struct.
+    EmptyStruct(Arc<Rtti>),
+    /// A tuple struct.
+    TupleStruct(Arc<Rtti>, Box<[ConstValue]>),
+    /// A struct with named fields.
+    Struct(Arc<Rtti>, HashMap<String, ConstValue>),
+    /// An enum variant.
+    Variant(Arc<VariantRtti>, ConstVariantData),
+}
+
+/// The data of a constant enum variant.
+#[derive(Debug, Deserialize, Serialize)]
+pub enum ConstVariantData {
+    /// A unit variant.
+    Empty,
+    /// A tuple variant.
+    Tuple(Box<[ConstValue]>),
+    /// A struct variant.
+    Struct(HashMap<String, ConstValue>),
+}
+
+impl ConstVariantData {
+    fn as_value(&self) -> alloc::Result<VariantData> {
+        Ok(match self {
+            Self::Empty => VariantData::Empty,
+            Self::Tuple(tuple) => VariantData::Tuple(tuple_as_value(tuple)?),
+            Self::Struct(object) => VariantData::Struct(object_as_value(object)?),
+        })
+    }
+}
+
+impl TryClone for ConstVariantData {
+    fn try_clone(&self) -> alloc::Result<Self> {
+        Ok(match self {
+            Self::Empty => Self::Empty,
+            Self::Tuple(value) => Self::Tuple(value.try_clone()?),
+            Self::Struct(value) => Self::Struct(value.try_clone()?),
+        })
+    }
 }
 
 impl ConstValue {
@@ -49,6 +96,9 @@ impl ConstValue {
             Self::Char(c) => Value::try_from(*c)?,
             Self::Bool(b) => Value::try_from(*b)?,
             Self::Integer(n) => Value::try_from(*n)?,
+            Self::U64(n) => Value::try_from(*n)?,
+            Self::I128(n) => Value::try_from(*n)?,
+            Self::U128(n) => Value::try_from(*n)?,
             Self::Float(n) => Value::try_from(*n)?,
             Self::String(string) => Value::try_from(string.try_clone()?)?,
             Self::Bytes(b) => Value::try_from(b.try_clone()?)?,
@@ -65,25 +115,21 @@ impl ConstValue {
 
                 Value::try_from(v)?
             }
-            Self::Tuple(tuple) => {
-                let mut t = Vec::try_with_capacity(tuple.len())?;
-
-                for value in tuple.iter() {
-                    t.try_push(value.as_value()?)?;
-                }
-
-                Value::try_from(OwnedTuple::try_from(t)?)?
-            }
-            Self::Object(object) => {
-                let mut o = Object::with_capacity(object.len())?;
-
-                for (key, value) in object {
-                    let key = key.try_clone()?;
-                    o.insert(key, value.as_value()?)?;
-                }
-
-                Value::try_from(o)?
-            }
+            Self::Tuple(tuple) => Value::try_from(tuple_as_value(tuple)?)?,
+            Self::Object(object) => Value::try_from(object_as_value(object)?)?,
+            Self::EmptyStruct(rtti) => Value::try_from(EmptyStruct { rtti: rtti.clone() })?,
+            Self::TupleStruct(rtti, tuple) => Value::try_from(TupleStruct {
+                rtti: rtti.clone(),
+                data: tuple_as_value(tuple)?,
+            })?,
+            Self::Struct(rtti, object) => Value::try_from(Struct {
+                rtti: rtti.clone(),
+                data: object_as_value(object)?,
+            })?,
+            Self::Variant(rtti, data) => Value::try_from(Variant {
+                rtti: rtti.clone(),
+                data: data.as_value()?,
+            })?,
         })
     }
 
@@ -105,11 +151,18 @@ impl ConstValue {
             Self::String(..) => TypeInfo::StaticType(crate::runtime::static_type::STRING_TYPE),
             Self::Bytes(..) => TypeInfo::StaticType(crate::runtime::static_type::BYTES_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(crate::runtime::static_type::INTEGER_TYPE),
+            Self::U64(..) => TypeInfo::StaticType(crate::runtime::static_type::U64_TYPE),
+            Self::I128(..) => TypeInfo::StaticType(crate::runtime::static_type::I128_TYPE),
+            Self::U128(..) => TypeInfo::StaticType(crate::runtime::static_type::U128_TYPE),
             Self::Float(..) => TypeInfo::StaticType(crate::runtime::static_type::FLOAT_TYPE),
             Self::Vec(..) => TypeInfo::StaticType(crate::runtime::static_type::VEC_TYPE),
             Self::Tuple(..) => TypeInfo::StaticType(crate::runtime::static_type::TUPLE_TYPE),
             Self::Object(..) => TypeInfo::StaticType(crate::runtime::static_type::OBJECT_TYPE),
             Self::Option(..) => TypeInfo::StaticType(crate::runtime::static_type::OPTION_TYPE),
+            Self::EmptyStruct(rtti) | Self::TupleStruct(rtti, ..) | Self::Struct(rtti, ..) => {
+                TypeInfo::Typed(rtti.clone())
+            }
+            Self::Variant(rtti, ..) => TypeInfo::Variant(rtti.clone()),
         }
     }
 }
@@ -122,6 +175,9 @@ impl TryClone for ConstValue {
             ConstValue::Char(char) => ConstValue::Char(*char),
             ConstValue::Bool(bool) => ConstValue::Bool(*bool),
             ConstValue::Integer(integer) => ConstValue::Integer(*integer),
+            ConstValue::U64(unsigned) => ConstValue::U64(*unsigned),
+            ConstValue::I128(integer) => ConstValue::I128(*integer),
+            ConstValue::U128(unsigned) => ConstValue::U128(*unsigned),
             ConstValue::Float(float) => ConstValue::Float(*float),
             ConstValue::String(value) => ConstValue::String(value.try_clone()?),
             ConstValue::Bytes(value) => ConstValue::Bytes(value.try_clone()?),
@@ -129,6 +185,14 @@ impl TryClone for ConstValue {
             ConstValue::Tuple(value) => ConstValue::Tuple(value.try_clone()?),
             ConstValue::Object(value) => ConstValue::Object(value.try_clone()?),
             ConstValue::Option(value) => ConstValue::Option(value.try_clone()?),
+            ConstValue::EmptyStruct(rtti) => ConstValue::EmptyStruct(rtti.clone()),
+            ConstValue::TupleStruct(rtti, value) => {
+                ConstValue::TupleStruct(rtti.clone(), value.try_clone()?)
+            }
+            ConstValue::Struct(rtti, value) => ConstValue::Struct(rtti.clone(), value.try_clone()?),
+            ConstValue::Variant(rtti, value) => {
+                ConstValue::Variant(rtti.clone(), value.try_clone()?)
+            }
         })
     }
 }
@@ -141,6 +205,9 @@ impl FromValue for ConstValue {
             ValueKind::Char(c) => Self::Char(c),
             ValueKind::Bool(b) => Self::Bool(b),
             ValueKind::Integer(n) => Self::Integer(n),
+            ValueKind::U64(n) => Self::U64(n),
+            ValueKind::I128(n) => Self::I128(n),
+            ValueKind::U128(n) => Self::U128(n),
             ValueKind::Float(f) => Self::Float(f),
             ValueKind::String(s) => Self::String(s),
             ValueKind::Option(option) => Self::Option(match option {
@@ -157,23 +224,27 @@ impl FromValue for ConstValue {
 
                 Self::Vec(const_vec)
             }
-            ValueKind::Tuple(tuple) => {
-                let mut const_tuple = vm_try!(Vec::try_with_capacity(tuple.len()));
-
-                for value in Vec::from(tuple.into_inner()) {
-                    vm_try!(const_tuple.try_push(vm_try!(Self::from_value(value))));
-                }
-
-                Self::Tuple(vm_try!(const_tuple.try_into_boxed_slice()))
+            ValueKind::Tuple(tuple) => Self::Tuple(vm_try!(tuple_from_value(tuple))),
+            ValueKind::Object(object) => Self::Object(vm_try!(object_from_value(object))),
+            ValueKind::EmptyStruct(value) => Self::EmptyStruct(value.rtti),
+            ValueKind::TupleStruct(value) => {
+                Self::TupleStruct(value.rtti, vm_try!(tuple_from_value(value.data)))
             }
-            ValueKind::Object(object) => {
-                let mut const_object = vm_try!(HashMap::try_with_capacity(object.len()));
-
-                for (key, value) in object {
-                    vm_try!(const_object.try_insert(key, vm_try!(Self::from_value(value))));
-                }
+            ValueKind::Struct(value) => {
+                Self::Struct(value.rtti, vm_try!(object_from_value(value.data)))
+            }
+            ValueKind::Variant(Variant { rtti, data }) => {
+                let data = match data {
+                    VariantData::Empty => ConstVariantData::Empty,
+                    VariantData::Tuple(tuple) => {
+                        ConstVariantData::Tuple(vm_try!(tuple_from_value(tuple)))
+                    }
+                    VariantData::Struct(object) => {
+                        ConstVariantData::Struct(vm_try!(object_from_value(object)))
+                    }
+                };
 
-                Self::Object(const_object)
+                Self::Variant(rtti, data)
             }
             actual => {
                 return VmResult::err(VmErrorKind::ConstNotSupported {
@@ -189,3 +260,44 @@ impl ToValue for ConstValue {
         VmResult::Ok(vm_try!(ConstValue::as_value(&self)))
     }
 }
+
+fn tuple_as_value(tuple: &[ConstValue]) -> alloc::Result<OwnedTuple> {
+    let mut t = Vec::try_with_capacity(tuple.len())?;
+
+    for value in tuple.iter() {
+        t.try_push(value.as_value()?)?;
+    }
+
+    OwnedTuple::try_from(t)
+}
+
+fn object_as_value(object: &HashMap<String, ConstValue>) -> alloc::Result<Object> {
+    let mut o = Object::with_capacity(object.len())?;
+
+    for (key, value) in object {
+        let key = key.try_clone()?;
+        o.insert(key, value.as_value()?)?;
+    }
+
+    Ok(o)
+}
+
+fn tuple_from_value(tuple: OwnedTuple) -> VmResult<Box<[ConstValue]>> {
+    let mut const_tuple = vm_try!(Vec::try_with_capacity(tuple.len()));
+
+    for value in Vec::from(tuple.into_inner()) {
+        vm_try!(const_tuple.try_push(vm_try!(ConstValue::from_value(value))));
+    }
+
+    VmResult::Ok(vm_try!(const_tuple.try_into_boxed_slice()))
+}
+
+fn object_from_value(object: Object) -> VmResult<HashMap<String, ConstValue>> {
+    let mut const_object = vm_try!(HashMap::try_with_capacity(object.len()));
+
+    for (key, value) in object {
+        vm_try!(const_object.try_insert(key, vm_try!(ConstValue::from_value(value))));
+    }
+
+    VmResult::Ok(const_object)
+}
@@ -0,0 +1,110 @@
+//! Instruction-level coverage for a virtual machine.
+
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use ::rust_alloc::sync::Arc;
+
+use crate::alloc::{self, Box, Vec};
+use crate::runtime::debug::{DebugInst, DebugSignature};
+use crate::runtime::{Unit, UnitStorage};
+
+/// Records which instructions of a unit have been executed, and how many
+/// times.
+///
+/// Coverage is enabled on a virtual machine through [`Vm::set_coverage`], and
+/// only records instructions which are executed while running the unit it was
+/// constructed for. Clones of a coverage share the same counters, and virtual
+/// machines which are spawned by a covered one, like the ones running async
+/// functions, generators and closures, record into the same coverage.
+///
+/// [`Vm::set_coverage`]: crate::Vm::set_coverage
+#[derive(Clone)]
+pub struct Coverage {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// The unit coverage is being recorded for.
+    unit: Arc<Unit>,
+    /// Hits by instruction pointer.
+    hits: Box<[AtomicUsize]>,
+}
+
+impl Coverage {
+    /// Construct coverage for the given unit without any instructions
+    /// executed.
+    pub fn new(unit: Arc<Unit>) -> alloc::Result<Self> {
+        let len = unit.instructions().end();
+        let mut hits = Vec::try_with_capacity(len)?;
+
+        for _ in 0..len {
+            hits.try_push(AtomicUsize::new(0))?;
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                unit,
+                hits: hits.try_into_boxed_slice()?,
+            }),
+        })
+    }
+
+    /// The unit coverage is being recorded for.
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.inner.unit
+    }
+
+    /// Get the number of times the instruction at the given instruction
+    /// pointer has been executed.
+    pub fn hits(&self, ip: usize) -> usize {
+        self.inner
+            .hits
+            .get(ip)
+            .map_or(0, |hits| hits.load(Ordering::Relaxed))
+    }
+
+    /// Iterate over every instruction which has debug information, together
+    /// with the number of times it has been executed.
+    ///
+    /// Instructions which have not been executed are included with zero hits.
+    pub fn instructions(&self) -> impl Iterator<Item = (usize, &DebugInst, usize)> + '_ {
+        self.unit()
+            .debug_info()
+            .into_iter()
+            .flat_map(|debug| debug.instructions.iter())
+            .map(|(&ip, inst)| (ip, inst, self.hits(ip)))
+    }
+
+    /// Iterate over every function in the unit by its entry offset, together
+    /// with the number of times it has been called.
+    pub fn functions(&self) -> impl Iterator<Item = (usize, &DebugSignature, usize)> + '_ {
+        self.unit().debug_info().into_iter().flat_map(move |debug| {
+            debug.functions_rev.iter().flat_map(move |(&entry, hash)| {
+                let signature = debug.functions.get(hash)?;
+                Some((entry, signature, self.hits(entry)))
+            })
+        })
+    }
+
+    /// Record that the instruction at the given instruction pointer was
+    /// executed in the given unit.
+    #[inline]
+    pub(crate) fn record(&self, unit: &Arc<Unit>, ip: usize) {
+        if !Arc::ptr_eq(&self.inner.unit, unit) {
+            return;
+        }
+
+        if let Some(hits) = self.inner.hits.get(ip) {
+            hits.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl fmt::Debug for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coverage")
+            .field("instructions", &self.inner.hits.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -6,23 +6,23 @@ use serde::{Deserialize, Serialize};
 
 use crate as rune;
 use crate::alloc::prelude::*;
-use crate::alloc::{Box, HashMap, Vec};
+use crate::alloc::{self, BTreeMap, Box, HashMap, Vec};
 use crate::ast::Span;
 use crate::compile::ItemBuf;
 use crate::hash::Hash;
-use crate::runtime::DebugLabel;
+use crate::runtime::{DebugLabel, InstAddress};
 use crate::SourceId;
 
 /// Debug information about a unit.
 #[derive(Debug, TryClone, Default, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct DebugInfo {
-    /// Debug information on each instruction.
-    pub instructions: HashMap<usize, DebugInst>,
+    /// Debug information on each instruction, ordered by instruction pointer.
+    pub instructions: BTreeMap<usize, DebugInst>,
     /// Function signatures.
     pub functions: HashMap<Hash, DebugSignature>,
-    /// Reverse lookup of a function.
-    pub functions_rev: HashMap<usize, Hash>,
+    /// Reverse lookup of a function, ordered by entry offset.
+    pub functions_rev: BTreeMap<usize, Hash>,
     /// Hash to identifier.
     pub hash_to_ident: HashMap<Hash, Box<str>>,
 }
@@ -40,6 +40,45 @@ impl DebugInfo {
         Some((hash, signature))
     }
 
+    /// Get the entry offset of the function which contains the given
+    /// instruction pointer.
+    pub fn function_entry_at(&self, ip: usize) -> Option<usize> {
+        let (&entry, _) = self.functions_rev.range(..=ip).next_back()?;
+        Some(entry)
+    }
+
+    /// Get the closest instruction which precedes the given instruction
+    /// pointer.
+    ///
+    /// This is useful to find the call instruction of a stored call frame,
+    /// since it stores the instruction pointer to return to.
+    pub fn instruction_before(&self, ip: usize) -> Option<(usize, &DebugInst)> {
+        let (&at, inst) = self.instructions.range(..ip).next_back()?;
+        Some((at, inst))
+    }
+
+    /// Get the variables which are available at the given instruction
+    /// pointer, in the order in which they were declared.
+    ///
+    /// A variable is shadowed by later declarations with the same name or
+    /// which reuse its address.
+    pub fn variables_at(&self, ip: usize) -> alloc::Result<Vec<&DebugVariable>> {
+        let mut output = Vec::<&DebugVariable>::new();
+
+        let Some(entry) = self.function_entry_at(ip) else {
+            return Ok(output);
+        };
+
+        for inst in self.instructions.range(entry..=ip).map(|(_, inst)| inst) {
+            for variable in &inst.variables {
+                output.retain(|v| v.name != variable.name && v.addr != variable.addr);
+                output.try_push(variable)?;
+            }
+        }
+
+        Ok(output)
+    }
+
     /// Access an identifier for the given hash - if it exists.
     pub fn ident_for_hash(&self, hash: Hash) -> Option<&str> {
         Some(self.hash_to_ident.get(&hash)?)
@@ -58,6 +97,8 @@ pub struct DebugInst {
     pub comment: Option<Box<str>>,
     /// Label associated with the location.
     pub labels: Vec<DebugLabel>,
+    /// Variables which become available at this instruction.
+    variables: Vec<DebugVariable>,
 }
 
 impl DebugInst {
@@ -73,8 +114,37 @@ impl DebugInst {
             span,
             comment,
             labels,
+            variables: Vec::new(),
         }
     }
+
+    /// Get the variables which become available at this instruction.
+    pub fn variables(&self) -> &[DebugVariable] {
+        &self.variables
+    }
+
+    /// Declare a variable which becomes available at this instruction.
+    pub(crate) fn push_variable(&mut self, variable: DebugVariable) -> alloc::Result<()> {
+        self.variables.try_push(variable)
+    }
+}
+
+/// Debug information on a named variable.
+#[derive(Debug, TryClone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: Box<str>,
+    /// The address of the variable relative to the top of the stack frame.
+    #[try_clone(copy)]
+    pub addr: InstAddress,
+}
+
+impl DebugVariable {
+    /// Construct a new debug variable.
+    pub fn new(name: Box<str>, addr: InstAddress) -> Self {
+        Self { name, addr }
+    }
 }
 
 /// Debug information on function arguments.
@@ -0,0 +1,196 @@
+//! Support for driving a virtual machine under a debugger.
+
+use crate::alloc::{self, BTreeSet, HashMap};
+use crate::ast::Spanned;
+use crate::runtime::{Value, Vm};
+use crate::{SourceId, Sources};
+
+/// Breakpoints and stepping state used to drive a [`VmExecution`] under a
+/// debugger.
+///
+/// Lines are zero-based.
+///
+/// [`VmExecution`]: crate::runtime::VmExecution
+#[derive(Debug, Default)]
+pub struct Debugger {
+    /// Breakpoints by source and line.
+    breakpoints: HashMap<SourceId, BTreeSet<usize>>,
+    /// The location and call depth of the last instruction which had debug
+    /// information.
+    last: Option<(DebugLocation, usize)>,
+}
+
+impl Debugger {
+    /// Construct a new debugger without any breakpoints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a breakpoint on the given line of a source.
+    ///
+    /// Returns `true` if the breakpoint was not already set.
+    pub fn set_breakpoint(&mut self, source_id: SourceId, line: usize) -> alloc::Result<bool> {
+        let lines = match self.breakpoints.get_mut(&source_id) {
+            Some(lines) => lines,
+            None => self
+                .breakpoints
+                .entry(source_id)
+                .or_try_insert(BTreeSet::new())?,
+        };
+
+        Ok(lines.try_insert(line)?)
+    }
+
+    /// Remove all breakpoints in the given source.
+    pub fn clear_breakpoints(&mut self, source_id: SourceId) {
+        self.breakpoints.remove(&source_id);
+    }
+
+    /// Test if there is a breakpoint on the given line of a source.
+    pub fn has_breakpoint(&self, source_id: SourceId, line: usize) -> bool {
+        self.breakpoints
+            .get(&source_id)
+            .is_some_and(|lines| lines.contains(&line))
+    }
+}
+
+/// How far execution should proceed before stopping again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DebugStep {
+    /// Run until a breakpoint is hit or execution completes.
+    Continue,
+    /// Run a single instruction.
+    Instruction,
+    /// Run until a different source line is reached, stopping inside of any
+    /// function being called.
+    Into,
+    /// Run until a different source line is reached in the current function
+    /// or one of its callers.
+    Over,
+    /// Run until the current function returns.
+    Out,
+}
+
+/// The reason execution stopped.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DebugStop {
+    /// A breakpoint was hit.
+    Breakpoint,
+    /// The requested [`DebugStep`] completed.
+    Step,
+    /// Execution completed with the given value.
+    Exited(Value),
+}
+
+/// A call frame which can be inspected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DebugFrame {
+    /// The instruction being executed in the frame.
+    ///
+    /// For frames other than the innermost one, this is the instruction
+    /// performing the call.
+    pub ip: usize,
+    /// The top of the stack for the frame, which addresses of locals are
+    /// relative to.
+    pub top: usize,
+}
+
+/// A location in a source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DebugLocation {
+    /// The source of the location.
+    pub source_id: SourceId,
+    /// The zero-based line of the location.
+    pub line: usize,
+    /// The zero-based column of the location.
+    pub column: usize,
+}
+
+impl DebugLocation {
+    /// Look up the location of the instruction at the given instruction
+    /// pointer in the unit being executed by the virtual machine.
+    pub fn at(vm: &Vm, sources: &Sources, ip: usize) -> Option<Self> {
+        let inst = vm.unit().debug_info()?.instruction_at(ip)?;
+        let source = sources.get(inst.source_id)?;
+        let (line, column) = source.pos_to_utf8_linecol(inst.span.span().start.into_usize());
+
+        Some(Self {
+            source_id: inst.source_id,
+            line,
+            column,
+        })
+    }
+
+    fn is_same_line(&self, other: &Self) -> bool {
+        self.source_id == other.source_id && self.line == other.line
+    }
+}
+
+/// Tracks the progress of a single [`DebugStep`].
+pub(crate) struct Stepper {
+    step: DebugStep,
+    /// The number of call frames when the step started.
+    depth: usize,
+    /// The location when the step started.
+    start: Option<DebugLocation>,
+}
+
+impl Stepper {
+    pub(crate) fn new(vm: &Vm, sources: &Sources, step: DebugStep) -> Self {
+        Self {
+            step,
+            depth: vm.call_frames().len(),
+            start: DebugLocation::at(vm, sources, vm.ip()),
+        }
+    }
+
+    /// Test if execution should stop after an instruction has been executed.
+    pub(crate) fn stop(
+        &self,
+        debugger: &mut Debugger,
+        vm: &Vm,
+        sources: &Sources,
+    ) -> Option<DebugStop> {
+        let depth = vm.call_frames().len();
+
+        // Instructions without debug information are treated as being part of
+        // the previous line.
+        let Some(location) = DebugLocation::at(vm, sources, vm.ip()) else {
+            let stop = match self.step {
+                DebugStep::Instruction => true,
+                DebugStep::Out => depth < self.depth,
+                _ => false,
+            };
+
+            return stop.then_some(DebugStop::Step);
+        };
+
+        let entered = !debugger
+            .last
+            .replace((location, depth))
+            .is_some_and(|(last, last_depth)| last_depth == depth && last.is_same_line(&location));
+
+        if entered && debugger.has_breakpoint(location.source_id, location.line) {
+            return Some(DebugStop::Breakpoint);
+        }
+
+        let moved = depth != self.depth
+            || !self
+                .start
+                .is_some_and(|start| start.is_same_line(&location));
+
+        let stop = match self.step {
+            DebugStep::Continue => false,
+            DebugStep::Instruction => true,
+            DebugStep::Into => moved,
+            DebugStep::Over => depth < self.depth || depth == self.depth && moved,
+            DebugStep::Out => depth < self.depth,
+        };
+
+        stop.then_some(DebugStop::Step)
+    }
+}
@@ -15,7 +15,7 @@ mod no_std;
 use ::rust_alloc::sync::Arc;
 
 use crate::runtime::vm_diagnostics::VmDiagnosticsObj;
-use crate::runtime::{RuntimeContext, Unit, VmErrorKind, VmResult};
+use crate::runtime::{Coverage, RuntimeContext, Unit, VmErrorKind, VmResult};
 
 /// Access shared parts of the environment.
 ///
@@ -44,6 +44,17 @@ where
     c(context, unit)
 }
 
+/// Get the coverage being recorded by the virtual machine which is currently
+/// running, if any.
+pub(crate) fn coverage() -> Option<Coverage> {
+    let env = self::no_std::rune_env_get();
+
+    // Safety: coverage can only be registered publicly through [`Guard`],
+    // which makes sure that it is live for the duration of the registration.
+    let coverage = unsafe { env.coverage?.as_ref() };
+    Some(coverage.clone())
+}
+
 /// Call the given closure with access to the checked environment accessing it
 /// exclusively.
 ///
@@ -93,11 +104,13 @@ impl Guard {
         context: NonNull<Arc<RuntimeContext>>,
         unit: NonNull<Arc<Unit>>,
         diagnostics: Option<NonNull<VmDiagnosticsObj>>,
+        coverage: Option<NonNull<Coverage>>,
     ) -> Guard {
         let env = self::no_std::rune_env_replace(Env {
             context: Some(context),
             unit: Some(unit),
             diagnostics,
+            coverage,
         });
         Guard { env }
     }
@@ -114,6 +127,7 @@ struct Env {
     context: Option<NonNull<Arc<RuntimeContext>>>,
     unit: Option<NonNull<Arc<Unit>>>,
     diagnostics: Option<NonNull<VmDiagnosticsObj>>,
+    coverage: Option<NonNull<Coverage>>,
 }
 
 impl Env {
@@ -122,6 +136,7 @@ impl Env {
             context: None,
             unit: None,
             diagnostics: None,
+            coverage: None,
         }
     }
 }
@@ -33,6 +33,7 @@ unsafe fn from_env(env: Env) -> RawEnv {
         context: env.context.map(|ptr| ptr.cast()),
         unit: env.unit.map(|ptr| ptr.cast()),
         diagnostics: env.diagnostics.map(|ptr| ptr.cast()),
+        coverage: env.coverage.map(|ptr| ptr.cast()),
     }
 }
 
@@ -41,5 +42,6 @@ unsafe fn from_raw_env(env: RawEnv) -> Env {
         context: env.context.map(|ptr| ptr.cast()),
         unit: env.unit.map(|ptr| ptr.cast()),
         diagnostics: env.diagnostics.map(|ptr| ptr.cast()),
+        coverage: env.coverage.map(|ptr| ptr.cast()),
     }
 }
@@ -4,6 +4,7 @@ use core::fmt;
 use core::iter;
 use core::mem::take;
 use core::num::NonZeroUsize;
+use core::ops;
 use core::str;
 
 use musli::{Decode, Encode};
@@ -106,22 +107,38 @@ impl FormatSpec {
     }
 
     /// get traits out of an integer.
-    fn int_traits(&self, n: i64) -> (i64, Alignment, char, Option<char>) {
+    fn int_traits<T>(&self, n: T) -> (T, Alignment, char, Option<char>)
+    where
+        T: Copy + Default + PartialOrd + ops::Neg<Output = T>,
+    {
+        let zero = T::default();
+
         if self.flags.test(Flag::SignAwareZeroPad) {
-            if n < 0 {
+            if n < zero {
                 (-n, Alignment::Right, '0', Some('-'))
             } else {
                 (n, Alignment::Right, '0', None)
             }
-        } else if self.flags.test(Flag::SignPlus) && n >= 0 {
+        } else if self.flags.test(Flag::SignPlus) && n >= zero {
             (n, self.align, self.fill, Some('+'))
         } else {
             (n, self.align, self.fill, None)
         }
     }
 
+    /// get traits out of an unsigned integer.
+    fn unsigned_traits(&self) -> (Alignment, char, Option<char>) {
+        if self.flags.test(Flag::SignAwareZeroPad) {
+            (Alignment::Right, '0', None)
+        } else if self.flags.test(Flag::SignPlus) {
+            (self.align, self.fill, Some('+'))
+        } else {
+            (self.align, self.fill, None)
+        }
+    }
+
     /// Format the given number.
-    fn format_number(&self, buf: &mut String, n: i64) -> VmResult<()> {
+    fn format_number(&self, buf: &mut String, n: impl itoa::Integer) -> VmResult<()> {
         let mut buffer = itoa::Buffer::new();
         vm_try!(buf.try_push_str(buffer.format(n)));
         VmResult::Ok(())
@@ -222,6 +239,21 @@ impl FormatSpec {
                 vm_try!(self.format_number(f.buf_mut(), n));
                 vm_try!(self.format_fill(f, align, fill, sign));
             }
+            ValueKind::U64(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_try!(self.format_number(f.buf_mut(), n));
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::I128(n) => {
+                let (n, align, fill, sign) = self.int_traits(n);
+                vm_try!(self.format_number(f.buf_mut(), n));
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::U128(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_try!(self.format_number(f.buf_mut(), n));
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
             ValueKind::Float(n) => {
                 let (n, align, fill, sign) = self.float_traits(n);
                 vm_try!(self.format_float(f.buf_mut(), n));
@@ -250,6 +282,21 @@ impl FormatSpec {
                 vm_try!(self.format_number(f.buf_mut(), n));
                 vm_try!(self.format_fill(f, align, fill, sign));
             }
+            ValueKind::U64(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_try!(self.format_number(f.buf_mut(), n));
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::I128(n) => {
+                let (n, align, fill, sign) = self.int_traits(n);
+                vm_try!(self.format_number(f.buf_mut(), n));
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::U128(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_try!(self.format_number(f.buf_mut(), n));
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
             ValueKind::Float(n) => {
                 let (n, align, fill, sign) = self.float_traits(n);
                 vm_try!(self.format_float(f.buf_mut(), n));
@@ -270,6 +317,21 @@ impl FormatSpec {
                 vm_write!(f.buf_mut(), "{:X}", n);
                 vm_try!(self.format_fill(f, align, fill, sign));
             }
+            ValueKind::U64(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_write!(f.buf_mut(), "{:X}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::I128(n) => {
+                let (n, align, fill, sign) = self.int_traits(n);
+                vm_write!(f.buf_mut(), "{:X}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::U128(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_write!(f.buf_mut(), "{:X}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
             _ => {
                 return VmResult::err(VmErrorKind::IllegalFormat);
             }
@@ -285,6 +347,21 @@ impl FormatSpec {
                 vm_write!(f.buf_mut(), "{:x}", n);
                 vm_try!(self.format_fill(f, align, fill, sign));
             }
+            ValueKind::U64(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_write!(f.buf_mut(), "{:x}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::I128(n) => {
+                let (n, align, fill, sign) = self.int_traits(n);
+                vm_write!(f.buf_mut(), "{:x}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::U128(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_write!(f.buf_mut(), "{:x}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
             _ => {
                 return VmResult::err(VmErrorKind::IllegalFormat);
             }
@@ -300,6 +377,21 @@ impl FormatSpec {
                 vm_write!(f.buf_mut(), "{:b}", n);
                 vm_try!(self.format_fill(f, align, fill, sign));
             }
+            ValueKind::U64(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_write!(f.buf_mut(), "{:b}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::I128(n) => {
+                let (n, align, fill, sign) = self.int_traits(n);
+                vm_write!(f.buf_mut(), "{:b}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
+            ValueKind::U128(n) => {
+                let (align, fill, sign) = self.unsigned_traits();
+                vm_write!(f.buf_mut(), "{:b}", n);
+                vm_try!(self.format_fill(f, align, fill, sign));
+            }
             _ => {
                 return VmResult::err(VmErrorKind::IllegalFormat);
             }
@@ -469,19 +469,12 @@ impl FromValue for char {
     }
 }
 
-impl FromValue for i64 {
-    #[inline]
-    fn from_value(value: Value) -> VmResult<Self> {
-        VmResult::Ok(vm_try!(value.as_integer()))
-    }
-}
-
 macro_rules! impl_number {
     ($ty:ty) => {
         impl FromValue for $ty {
             #[inline]
             fn from_value(value: Value) -> VmResult<Self> {
-                VmResult::Ok(vm_try!(value.try_as_integer()))
+                VmResult::Ok(vm_try!(value.try_as_wide_integer()))
             }
         }
     };
@@ -495,6 +488,7 @@ impl_number!(usize);
 impl_number!(i8);
 impl_number!(i16);
 impl_number!(i32);
+impl_number!(i64);
 impl_number!(i128);
 impl_number!(isize);
 
@@ -8,9 +8,9 @@ use crate::alloc::prelude::*;
 use crate::alloc::{self, Box, Vec};
 use crate::module;
 use crate::runtime::{
-    Args, Call, ConstValue, FromValue, FunctionHandler, InstAddress, Output, OwnedTuple, Rtti,
-    RuntimeContext, Stack, Unit, Value, ValueKind, VariantRtti, Vm, VmCall, VmErrorKind, VmHalt,
-    VmResult,
+    Args, Call, ConstValue, FromValue, FunctionHandler, InstAddress, Output, OwnedTuple, Protocol,
+    Rtti, RuntimeContext, Stack, ToValue, Unit, UnitFn, Value, ValueKind, VariantRtti, Vm, VmCall,
+    VmErrorKind, VmHalt, VmResult,
 };
 use crate::shared::AssertSend;
 use crate::Any;
@@ -25,6 +25,7 @@ use crate::Hash;
 /// * Regular functions.
 /// * Closures (which might or might not capture their environment).
 /// * Built-in constructors for tuple types (tuple structs, tuple variants).
+/// * Values whose type implements the [`Protocol::CALL`] protocol.
 ///
 /// # Examples
 ///
@@ -261,6 +262,45 @@ impl Function {
         Self(FunctionImpl::from_tuple_variant(rtti, args))
     }
 
+    /// Create a function pointer which calls the given value through the
+    /// [`Protocol::CALL`] protocol, or `None` if its type doesn't implement
+    /// it.
+    pub(crate) fn from_callable(
+        context: &Arc<RuntimeContext>,
+        unit: &Arc<Unit>,
+        value: &Value,
+    ) -> VmResult<Option<Self>> {
+        let hash = Hash::associated_function(vm_try!(value.type_hash()), Protocol::CALL);
+
+        let target = if let Some(UnitFn::Offset {
+            offset, call, args, ..
+        }) = unit.function(hash)
+        {
+            FnCallTarget::Offset(FnOffset {
+                context: context.clone(),
+                unit: unit.clone(),
+                offset,
+                call,
+                args,
+                hash,
+            })
+        } else if let Some(handler) = context.function(hash) {
+            FnCallTarget::Handler(FnHandler {
+                handler: handler.clone(),
+                hash,
+            })
+        } else {
+            return VmResult::Ok(None);
+        };
+
+        VmResult::Ok(Some(Self(FunctionImpl {
+            inner: Inner::FnCallable(FnCallable {
+                target,
+                value: value.clone(),
+            }),
+        })))
+    }
+
     /// Type [Hash][struct@Hash] of the underlying function.
     ///
     /// # Examples
@@ -501,7 +541,7 @@ where
 
 impl<V> FunctionImpl<V>
 where
-    V: TryClone,
+    V: TryClone + ToValue,
     OwnedTuple: TryFrom<Box<[V]>>,
     VmErrorKind: From<<OwnedTuple as TryFrom<Box<[V]>>>::Error>,
 {
@@ -555,6 +595,33 @@ where
                     vm_try!(args.try_into_vec())
                 ))
             }
+            Inner::FnCallable(callable) => {
+                // The value being called is passed as the first argument.
+                let mut values = vm_try!(Vec::try_with_capacity(args.count().wrapping_add(1)));
+                vm_try!(values.try_push(vm_try!(vm_try!(callable.value.try_clone()).to_value())));
+
+                for value in vm_try!(args.try_into_vec()) {
+                    vm_try!(values.try_push(value));
+                }
+
+                match &callable.target {
+                    FnCallTarget::Handler(handler) => {
+                        let count = values.len();
+                        let mut stack = vm_try!(Stack::with_capacity(count));
+                        vm_try!(values.into_stack(&mut stack));
+                        vm_try!((handler.handler)(
+                            &mut stack,
+                            InstAddress::ZERO,
+                            count,
+                            InstAddress::ZERO.output()
+                        ));
+                        vm_try!(stack.at(InstAddress::ZERO)).clone()
+                    }
+                    FnCallTarget::Offset(fn_offset) => {
+                        vm_try!(fn_offset.call(values, ()))
+                    }
+                }
+            }
         };
 
         T::from_value(value)
@@ -601,7 +668,7 @@ where
     ) -> VmResult<Option<VmHalt>> {
         let reason = match &self.inner {
             Inner::FnHandler(handler) => {
-                vm_try!((handler.handler)(vm.stack_mut(), addr, args, out));
+                vm_try!(vm.call_native_fn(handler.hash, &*handler.handler, addr, args, out));
                 None
             }
             Inner::FnOffset(fn_offset) => {
@@ -660,6 +727,34 @@ where
 
                 None
             }
+            Inner::FnCallable(callable) => {
+                // The value being called is passed as the first argument.
+                let value = vm_try!(vm_try!(callable.value.try_clone()).to_value());
+
+                match &callable.target {
+                    FnCallTarget::Handler(handler) => {
+                        vm_try!(vm.call_native_fn_with_target(
+                            handler.hash,
+                            &*handler.handler,
+                            value,
+                            addr,
+                            args,
+                            out
+                        ));
+
+                        None
+                    }
+                    FnCallTarget::Offset(fn_offset) => {
+                        if let Some(vm_call) =
+                            vm_try!(fn_offset.call_target_with_vm(vm, value, addr, args, out))
+                        {
+                            return VmResult::Ok(Some(VmHalt::VmCall(vm_call)));
+                        }
+
+                        None
+                    }
+                }
+            }
         };
 
         VmResult::Ok(reason)
@@ -757,6 +852,10 @@ where
             Inner::FnTupleStruct(func) => func.rtti.hash,
             Inner::FnUnitVariant(func) => func.rtti.hash,
             Inner::FnTupleVariant(func) => func.rtti.hash,
+            Inner::FnCallable(callable) => match &callable.target {
+                FnCallTarget::Handler(FnHandler { hash, .. })
+                | FnCallTarget::Offset(FnOffset { hash, .. }) => *hash,
+            },
         }
     }
 }
@@ -783,6 +882,10 @@ impl FunctionImpl<Value> {
             Inner::FnTupleStruct(inner) => Inner::FnTupleStruct(inner),
             Inner::FnUnitVariant(inner) => Inner::FnUnitVariant(inner),
             Inner::FnTupleVariant(inner) => Inner::FnTupleVariant(inner),
+            Inner::FnCallable(callable) => Inner::FnCallable(FnCallable {
+                target: callable.target,
+                value: vm_try!(FromValue::from_value(callable.value)),
+            }),
         };
 
         VmResult::Ok(FunctionImpl { inner })
@@ -817,6 +920,9 @@ impl fmt::Debug for Function {
             Inner::FnTupleVariant(tuple) => {
                 write!(f, "variant tuple {}", tuple.rtti.item)?;
             }
+            Inner::FnCallable(callable) => {
+                write!(f, "callable {:?}", callable.value)?;
+            }
         }
 
         Ok(())
@@ -846,6 +952,8 @@ enum Inner<V> {
     FnUnitVariant(FnUnitVariant),
     /// Constructor for a tuple variant.
     FnTupleVariant(FnTupleVariant),
+    /// A value which is called through the [`Protocol::CALL`] protocol.
+    FnCallable(FnCallable<V>),
 }
 
 impl<V> TryClone for Inner<V>
@@ -861,6 +969,7 @@ where
             Inner::FnTupleStruct(inner) => Inner::FnTupleStruct(inner.clone()),
             Inner::FnUnitVariant(inner) => Inner::FnUnitVariant(inner.clone()),
             Inner::FnTupleVariant(inner) => Inner::FnTupleVariant(inner.clone()),
+            Inner::FnCallable(inner) => Inner::FnCallable(inner.try_clone()?),
         })
     }
 }
@@ -906,6 +1015,7 @@ impl FnOffset {
         let mut vm = Vm::new(self.context.clone(), self.unit.clone());
 
         vm.set_ip(self.offset);
+        vm.instrument_from_env();
         vm_try!(args.into_stack(vm.stack_mut()));
         vm_try!(extra.into_stack(vm.stack_mut()));
 
@@ -927,25 +1037,65 @@ impl FnOffset {
     ) -> VmResult<Option<VmCall>> {
         vm_try!(check_args(args.wrapping_add(extra.count()), self.args));
 
+        let (isolated, vm_call) = self.prepare_call(vm, out);
+
+        vm_try!(vm.push_call_frame(self.offset, addr, args, isolated, out));
+        vm_try!(extra.into_stack(vm.stack_mut()));
+        VmResult::Ok(vm_call)
+    }
+
+    /// Perform a call with the given virtual machine, passing `target` as the
+    /// first argument ahead of the `args` arguments at `addr`.
+    fn call_target_with_vm(
+        &self,
+        vm: &mut Vm,
+        target: Value,
+        addr: InstAddress,
+        args: usize,
+        out: Output,
+    ) -> VmResult<Option<VmCall>> {
+        vm_try!(check_args(args.wrapping_add(1), self.args));
+
+        let seq = vm_try!(vm.stack().slice_at(addr, args));
+        let seq = vm_try!(seq.iter().cloned().try_collect::<Vec<_>>());
+
+        let (isolated, vm_call) = self.prepare_call(vm, out);
+
+        // The new call frame starts out empty, and is populated with the
+        // target followed by the arguments.
+        vm_try!(vm.push_call_frame(self.offset, addr, 0, isolated, out));
+        vm_try!(vm.stack_mut().push(target));
+
+        for value in seq {
+            vm_try!(vm.stack_mut().push(value));
+        }
+
+        VmResult::Ok(vm_call)
+    }
+
+    /// Test if a call with the given virtual machine has to be isolated, and
+    /// construct the call which has to be performed by the caller if so.
+    fn prepare_call(&self, vm: &Vm, out: Output) -> (bool, Option<VmCall>) {
         let same_unit = matches!(self.call, Call::Immediate if vm.is_same_unit(&self.unit));
         let same_context =
             matches!(self.call, Call::Immediate if vm.is_same_context(&self.context));
 
-        vm_try!(vm.push_call_frame(self.offset, addr, args, !same_context, out));
-        vm_try!(extra.into_stack(vm.stack_mut()));
-
         // Fast path, just allocate a call frame and keep running.
         if same_context && same_unit {
             tracing::trace!("same context and unit");
-            return VmResult::Ok(None);
+            return (false, None);
         }
 
-        VmResult::Ok(Some(VmCall::new(
+        // Calls into a different context or unit have to be isolated, so that
+        // the execution can restore them once the call returns.
+        let vm_call = VmCall::new(
             self.call,
             (!same_context).then(|| self.context.clone()),
             (!same_unit).then(|| self.unit.clone()),
             out,
-        )))
+        );
+
+        (true, Some(vm_call))
     }
 }
 
@@ -1010,14 +1160,62 @@ struct FnTupleVariant {
     args: usize,
 }
 
+#[derive(Debug)]
+struct FnCallable<V> {
+    /// The protocol function which is called.
+    target: FnCallTarget,
+    /// The value being called.
+    value: V,
+}
+
+impl<V> TryClone for FnCallable<V>
+where
+    V: TryClone,
+{
+    #[inline]
+    fn try_clone(&self) -> alloc::Result<Self> {
+        Ok(Self {
+            target: self.target.clone(),
+            value: self.value.try_clone()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum FnCallTarget {
+    /// The protocol is implemented by a native function.
+    Handler(FnHandler),
+    /// The protocol is implemented by a function in a unit.
+    Offset(FnOffset),
+}
+
 impl FromValue for SyncFunction {
     #[inline]
     fn from_value(value: Value) -> VmResult<Self> {
-        vm_try!(value.into_function()).into_sync()
+        vm_try!(Function::from_value(value)).into_sync()
+    }
+}
+
+impl FromValue for Function {
+    fn from_value(value: Value) -> VmResult<Self> {
+        if !matches!(&*vm_try!(value.borrow_kind_ref()), ValueKind::Function(..)) {
+            // The protocol can only be looked up if a virtual machine is
+            // running, otherwise this fails below like any other value which
+            // isn't a function.
+            let callable = crate::runtime::env::shared(|context, unit| {
+                Function::from_callable(context, unit, &value)
+            });
+
+            if let VmResult::Ok(Some(function)) = callable {
+                return VmResult::Ok(function);
+            }
+        }
+
+        VmResult::Ok(vm_try!(value.into_function()))
     }
 }
 
-from_value2!(
+from_value_ref!(
     Function,
     into_function_ref,
     into_function_mut,
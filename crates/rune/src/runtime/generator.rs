@@ -3,7 +3,11 @@ use core::iter;
 
 use crate::alloc::clone::TryClone;
 use crate::runtime::{GeneratorState, Iterator, Value, Vm, VmErrorKind, VmExecution, VmResult};
+#[cfg(feature = "byte-code")]
+use crate::runtime::{RuntimeContext, Unit, VmSnapshot, VmSnapshotError, VmSnapshotErrorKind};
 use crate::Any;
+#[cfg(feature = "byte-code")]
+use ::rust_alloc::sync::Arc;
 
 /// The return value of a function producing a generator.
 ///
@@ -92,6 +96,20 @@ where
 
         VmResult::Ok(state)
     }
+
+    /// Take a snapshot of the generator which can be serialized and later
+    /// restored with [`Generator::restore`].
+    ///
+    /// See [`VmSnapshot`] for an example.
+    #[cfg(feature = "byte-code")]
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmSnapshotError> {
+        match &self.execution {
+            Some(execution) => execution.snapshot(),
+            None => Err(VmSnapshotError::from(
+                VmSnapshotErrorKind::GeneratorComplete,
+            )),
+        }
+    }
 }
 
 impl Generator<&mut Vm> {
@@ -104,6 +122,21 @@ impl Generator<&mut Vm> {
 }
 
 impl Generator<Vm> {
+    /// Restore a generator from a snapshot taken with
+    /// [`Generator::snapshot`].
+    ///
+    /// The unit must be the same as the one the snapshot was taken from.
+    #[cfg(feature = "byte-code")]
+    pub fn restore(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        snapshot: &VmSnapshot,
+    ) -> Result<Self, VmSnapshotError> {
+        Ok(Self::from_execution(VmExecution::restore(
+            context, unit, snapshot,
+        )?))
+    }
+
     /// Convert into iterator
     pub fn rune_iter(self) -> Iterator {
         Iterator::from("std::ops::generator::Iter", self.into_iter())
@@ -48,6 +48,21 @@ impl Hasher {
         self.hasher.write_i64(value);
     }
 
+    /// Hash a 64-bit unsigned integer.
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.hasher.write_u64(value);
+    }
+
+    /// Hash a 128-bit signed integer.
+    pub(crate) fn write_i128(&mut self, value: i128) {
+        self.hasher.write_i128(value);
+    }
+
+    /// Hash a 128-bit unsigned integer.
+    pub(crate) fn write_u128(&mut self, value: u128) {
+        self.hasher.write_u128(value);
+    }
+
     /// Hash an 8-bit unsigned integer.
     pub(crate) fn write_u8(&mut self, value: u8) {
         self.hasher.write_u8(value);
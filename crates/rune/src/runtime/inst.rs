@@ -105,6 +105,10 @@ pub enum Inst {
     /// Not operator. Takes a boolean from the top of the stack  and inverts its
     /// logical value.
     ///
+    /// Other values are inverted through the [`Protocol::NOT`] protocol.
+    ///
+    /// [`Protocol::NOT`]: crate::runtime::Protocol::NOT
+    ///
     /// # Operation
     ///
     /// ```text
@@ -119,6 +123,10 @@ pub enum Inst {
     },
     /// Negate the numerical value on the stack.
     ///
+    /// Other values are negated through the [`Protocol::NEG`] protocol.
+    ///
+    /// [`Protocol::NEG`]: crate::runtime::Protocol::NEG
+    ///
     /// # Operation
     ///
     /// ```text
@@ -203,6 +211,26 @@ pub enum Inst {
         /// Whether the return value should be kept or not.
         out: Output,
     },
+    /// Call a function of a trait implemented by the object at address
+    /// `addr`.
+    ///
+    /// Errors if the type of the object doesn't implement the trait. The
+    /// number of arguments specified should include this object.
+    ///
+    /// The return value of the function call will be written to `out`.
+    #[musli(packed)]
+    CallTrait {
+        /// The hash of the trait.
+        trait_hash: Hash,
+        /// The hash of the trait function to call.
+        hash: Hash,
+        /// The address of arguments being passed.
+        addr: InstAddress,
+        /// The number of arguments passed in at `addr`.
+        args: usize,
+        /// Whether the return value should be kept or not.
+        out: Output,
+    },
     /// Look up an instance function.
     ///
     /// The instance being used is stored at `addr`, and the function hash to look up is `hash`.
@@ -510,6 +538,28 @@ pub enum Inst {
         /// The offset to jump if the condition is true.
         jump: usize,
     },
+    /// Jump to the given offset if the value at `addr` is not the given
+    /// integer.
+    ///
+    /// This is produced by the optimizer as a fusion of [`Inst::EqInteger`]
+    /// followed by [`Inst::JumpIfNot`] when the result of the comparison is not
+    /// used for anything else.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => *noop*
+    /// ```
+    #[musli(packed)]
+    JumpIfNotEqInteger {
+        /// Address of the value to compare.
+        addr: InstAddress,
+        /// The value to test against.
+        value: i64,
+        /// The offset to jump to if the value is not equal.
+        jump: usize,
+    },
     /// Construct a push a vector value onto the stack. The number of elements
     /// in the vector are determined by `count` and are popped from the stack.
     ///
@@ -871,6 +921,57 @@ pub enum Inst {
         /// Where to store the result of the comparison.
         out: Output,
     },
+    /// Test if the top of the stack is a specific unsigned integer.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    #[musli(packed)]
+    EqU64 {
+        /// Address of the value to compare.
+        addr: InstAddress,
+        /// The value to test against.
+        value: u64,
+        /// Where to store the result of the comparison.
+        out: Output,
+    },
+    /// Test if the top of the stack is a specific 128-bit integer.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    #[musli(packed)]
+    EqI128 {
+        /// Address of the value to compare.
+        addr: InstAddress,
+        /// The value to test against.
+        value: InstI128,
+        /// Where to store the result of the comparison.
+        out: Output,
+    },
+    /// Test if the top of the stack is a specific unsigned 128-bit integer.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    #[musli(packed)]
+    EqU128 {
+        /// Address of the value to compare.
+        addr: InstAddress,
+        /// The value to test against.
+        value: InstU128,
+        /// Where to store the result of the comparison.
+        out: Output,
+    },
 
     /// Test if the top of the stack is a specific boolean.
     ///
@@ -940,6 +1041,25 @@ pub enum Inst {
         /// Where to store the output.
         out: Output,
     },
+    /// Check that the value at the given address has the given type, raising
+    /// an error if it doesn't. This is used to enforce type annotations when
+    /// runtime type checks are enabled.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => *noop*
+    /// ```
+    #[musli(packed)]
+    CheckType {
+        /// The type hash to check against.
+        hash: Hash,
+        /// The static string slot containing the name of the expected type.
+        slot: usize,
+        /// The address of the value to check.
+        addr: InstAddress,
+    },
     /// Test if the specified variant matches. This is distinct from
     /// [Inst::MatchType] because it will match immediately on the variant type
     /// if appropriate which is possible for internal types, but external types
@@ -1177,6 +1297,30 @@ impl Inst {
         }
     }
 
+    /// Construct an instruction to push an unsigned integer.
+    pub fn unsigned(v: u64, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::U64(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push a 128-bit integer.
+    pub fn i128(v: i128, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::I128(InstI128::new(v)),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push an unsigned 128-bit integer.
+    pub fn u128(v: u128, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::U128(InstU128::new(v)),
+            out,
+        }
+    }
+
     /// Construct an instruction to push a float.
     pub fn float(v: f64, out: Output) -> Self {
         Self::Store {
@@ -1616,6 +1760,28 @@ pub enum InstOp {
     /// => <boolean>
     /// ```
     IsNot,
+    /// Test if the type of the top of the stack implements the trait in the
+    /// second item on the stack.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <trait>
+    /// <value>
+    /// => <boolean>
+    /// ```
+    IsImpl,
+    /// Test if the type of the top of the stack does not implement the trait
+    /// in the second item on the stack.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <trait>
+    /// <value>
+    /// => <boolean>
+    /// ```
+    IsNotImpl,
     /// Pop two values from the stack and test if they are both boolean true.
     ///
     /// # Operation
@@ -1699,6 +1865,12 @@ impl fmt::Display for InstOp {
             Self::IsNot => {
                 write!(f, "is not")?;
             }
+            Self::IsImpl => {
+                write!(f, "is impl")?;
+            }
+            Self::IsNotImpl => {
+                write!(f, "is not impl")?;
+            }
             Self::And => {
                 write!(f, "&&")?;
             }
@@ -1730,6 +1902,15 @@ pub enum InstValue {
     /// An integer.
     #[musli(packed)]
     Integer(i64),
+    /// An unsigned integer.
+    #[musli(packed)]
+    U64(u64),
+    /// A 128-bit integer.
+    #[musli(packed)]
+    I128(InstI128),
+    /// An unsigned 128-bit integer.
+    #[musli(packed)]
+    U128(InstU128),
     /// A float.
     #[musli(packed)]
     Float(f64),
@@ -1747,6 +1928,9 @@ impl InstValue {
             Self::Byte(v) => Value::try_from(v),
             Self::Char(v) => Value::try_from(v),
             Self::Integer(v) => Value::try_from(v),
+            Self::U64(v) => Value::try_from(v),
+            Self::I128(v) => Value::try_from(v.get()),
+            Self::U128(v) => Value::try_from(v.get()),
             Self::Float(v) => Value::try_from(v),
             Self::Type(v) => Value::try_from(v),
         }
@@ -1767,6 +1951,9 @@ impl fmt::Display for InstValue {
             }
             Self::Char(v) => write!(f, "{:?}", v)?,
             Self::Integer(v) => write!(f, "{}", v)?,
+            Self::U64(v) => write!(f, "{}u64", v)?,
+            Self::I128(v) => write!(f, "{}", v)?,
+            Self::U128(v) => write!(f, "{}", v)?,
             Self::Float(v) => write!(f, "{}", v)?,
             Self::Type(v) => write!(f, "{}", v.into_hash())?,
         }
@@ -1775,6 +1962,61 @@ impl fmt::Display for InstValue {
     }
 }
 
+macro_rules! inst_wide_integer {
+    ($(#[$meta:meta])* $name:ident, $ty:ty, $suffix:literal) => {
+        $(#[$meta])*
+        ///
+        /// The value is stored as little-endian bytes, so that it doesn't raise
+        /// the alignment and with it the size of [`Inst`].
+        #[derive(TryClone, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Decode, Encode)]
+        #[try_clone(copy)]
+        #[repr(transparent)]
+        #[serde(transparent)]
+        #[musli(transparent)]
+        pub struct $name {
+            bytes: [u8; 16],
+        }
+
+        impl $name {
+            /// Construct a new instruction value.
+            pub fn new(value: $ty) -> Self {
+                Self {
+                    bytes: value.to_le_bytes(),
+                }
+            }
+
+            /// Get the stored value.
+            pub fn get(self) -> $ty {
+                <$ty>::from_le_bytes(self.bytes)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.get().fmt(f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!("{}", $suffix), self.get())
+            }
+        }
+    };
+}
+
+inst_wide_integer! {
+    /// A 128-bit integer stored in an instruction.
+    InstI128, i128, "i128"
+}
+
+inst_wide_integer! {
+    /// An unsigned 128-bit integer stored in an instruction.
+    InstU128, u128, "u128"
+}
+
 /// A variant that can be constructed.
 #[derive(Debug, TryClone, Clone, Copy, Serialize, Deserialize, Decode, Encode)]
 #[try_clone(copy)]
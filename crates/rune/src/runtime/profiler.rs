@@ -0,0 +1,379 @@
+//! Profiling of the functions executed by a virtual machine.
+
+use core::cell::RefCell;
+use core::fmt;
+use core::mem::take;
+use core::time::Duration;
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use ::rust_alloc::sync::Arc;
+
+use crate::alloc::{self, HashMap, Vec};
+use crate::hash::Hash;
+use crate::runtime::Unit;
+
+std::thread_local!(static CURRENT: RefCell<Option<Profiler>> = const { RefCell::new(None) });
+
+/// A frame in a profiled call stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProfileFrame {
+    /// A function in the profiled unit, identified by the instruction pointer
+    /// of its entry.
+    ///
+    /// See [`DebugInfo::function_at`] for how to look up its signature.
+    ///
+    /// [`DebugInfo::function_at`]: crate::runtime::DebugInfo::function_at
+    Function(usize),
+    /// A native function, identified by its hash.
+    Native(Hash),
+}
+
+/// What was recorded for a single call stack.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ProfileSample {
+    /// The call stack, starting with the outermost frame.
+    pub stack: Vec<ProfileFrame>,
+    /// The number of times the innermost frame was called from the rest of the
+    /// stack.
+    pub calls: u64,
+    /// The number of instructions executed in the innermost frame.
+    pub instructions: u64,
+    /// Time spent in the innermost frame, excluding time spent in the
+    /// functions it called.
+    pub time: Duration,
+}
+
+/// Attributes executed instructions and time to the call stacks of the
+/// functions in a unit.
+///
+/// Profiling is enabled on a virtual machine through [`Vm::set_profiler`].
+/// Instructions are counted exactly, while time is measured whenever a function
+/// is called or returns. Clones of a profiler share what is recorded, and
+/// virtual machines which are spawned by a profiled one, like the ones running
+/// async functions, generators and closures, record into the same profiler.
+///
+/// [`Vm::set_profiler`]: crate::Vm::set_profiler
+#[derive(Clone)]
+pub struct Profiler {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// The unit being profiled.
+    unit: Arc<Unit>,
+    state: Mutex<State>,
+}
+
+struct State {
+    /// Nodes in the tree of call stacks, where the first node is the root.
+    nodes: Vec<Node>,
+    /// The node which time is currently being attributed to.
+    active: Option<usize>,
+    /// When time was last attributed.
+    last: Instant,
+}
+
+struct Node {
+    frame: Option<ProfileFrame>,
+    parent: usize,
+    children: HashMap<ProfileFrame, usize>,
+    calls: u64,
+    instructions: u64,
+    time: Duration,
+}
+
+impl Node {
+    fn new(frame: Option<ProfileFrame>, parent: usize) -> Self {
+        Self {
+            frame,
+            parent,
+            children: HashMap::new(),
+            calls: 0,
+            instructions: 0,
+            time: Duration::ZERO,
+        }
+    }
+}
+
+impl State {
+    /// Attribute the time since it was last attributed to the active node.
+    fn charge(&mut self) {
+        let now = Instant::now();
+
+        if let Some(node) = self.active.and_then(|index| self.nodes.get_mut(index)) {
+            node.time += now.saturating_duration_since(self.last);
+        }
+
+        self.last = now;
+    }
+
+    /// Enter the given frame from the given node.
+    fn enter(&mut self, parent: usize, frame: ProfileFrame) -> alloc::Result<usize> {
+        let index = match self.nodes[parent].children.get(&frame) {
+            Some(&index) => index,
+            None => {
+                let index = self.nodes.len();
+                self.nodes.try_push(Node::new(Some(frame), parent))?;
+                self.nodes[parent].children.try_insert(frame, index)?;
+                index
+            }
+        };
+
+        self.nodes[index].calls += 1;
+        Ok(index)
+    }
+}
+
+impl Profiler {
+    /// Construct a profiler for the given unit without anything recorded.
+    pub fn new(unit: Arc<Unit>) -> alloc::Result<Self> {
+        let mut nodes = Vec::new();
+        nodes.try_push(Node::new(None, 0))?;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                unit,
+                state: Mutex::new(State {
+                    nodes,
+                    active: None,
+                    last: Instant::now(),
+                }),
+            }),
+        })
+    }
+
+    /// The unit being profiled.
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.inner.unit
+    }
+
+    /// Get what has been recorded for every call stack.
+    pub fn samples(&self) -> alloc::Result<Vec<ProfileSample>> {
+        let state = self.lock();
+        let mut samples = Vec::new();
+
+        for node in state.nodes.iter().skip(1) {
+            let mut stack = Vec::new();
+            let mut current = node;
+
+            while let Some(frame) = current.frame {
+                stack.try_push(frame)?;
+                current = &state.nodes[current.parent];
+            }
+
+            stack.reverse();
+
+            samples.try_push(ProfileSample {
+                stack,
+                calls: node.calls,
+                instructions: node.instructions,
+                time: node.time,
+            })?;
+        }
+
+        Ok(samples)
+    }
+
+    /// Get the profiler of the virtual machine which is currently running, if
+    /// any.
+    pub(crate) fn current() -> Option<Profiler> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profiler").finish_non_exhaustive()
+    }
+}
+
+/// The call stack of a single virtual machine being profiled.
+#[derive(Debug)]
+pub(crate) struct ProfileCursor {
+    profiler: Profiler,
+    /// The node the call stack is rooted in. If not set, this is determined
+    /// when the virtual machine first runs.
+    base: Option<usize>,
+    /// Nodes for every frame of the virtual machine, innermost last.
+    path: Vec<usize>,
+    /// Instructions executed in the innermost frame which have not been
+    /// recorded yet.
+    pending: u64,
+    /// The active node and profiler before the virtual machine started
+    /// running, or `None` if it isn't running.
+    previous: Option<(Option<usize>, Option<Profiler>)>,
+}
+
+impl ProfileCursor {
+    pub(crate) fn new(profiler: Profiler) -> Self {
+        Self {
+            profiler,
+            base: None,
+            path: Vec::new(),
+            pending: 0,
+            previous: None,
+        }
+    }
+
+    /// The profiler being recorded into.
+    pub(crate) fn profiler(&self) -> &Profiler {
+        &self.profiler
+    }
+
+    /// Construct a cursor for a virtual machine which is spawned from the
+    /// current frame.
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            base: self.current(),
+            ..Self::new(self.profiler.clone())
+        }
+    }
+
+    /// Forget the call stack, since the virtual machine is about to start
+    /// executing a new function.
+    pub(crate) fn reset(&mut self) {
+        self.path.clear();
+    }
+
+    /// Start recording for a virtual machine running the given unit.
+    pub(crate) fn begin(&mut self, unit: &Arc<Unit>) {
+        if !Arc::ptr_eq(&self.profiler.inner.unit, unit) {
+            return;
+        }
+
+        let mut state = self.profiler.lock();
+        state.charge();
+
+        let base = *self.base.get_or_insert(state.active.unwrap_or(0));
+        let active = state.active.replace(*self.path.last().unwrap_or(&base));
+        drop(state);
+
+        let profiler = CURRENT.with(|current| current.replace(Some(self.profiler.clone())));
+        self.previous = Some((active, profiler));
+    }
+
+    /// Stop recording, since the virtual machine stopped running.
+    pub(crate) fn end(&mut self) {
+        let Some((active, previous)) = self.previous.take() else {
+            return;
+        };
+
+        let profiler = self.profiler.clone();
+        let mut state = profiler.lock();
+        self.flush(&mut state);
+        state.active = active;
+        drop(state);
+
+        CURRENT.with(|current| current.replace(previous));
+    }
+
+    /// Record an instruction at the given instruction pointer, with the given
+    /// number of call frames.
+    #[inline]
+    pub(crate) fn instruction(
+        &mut self,
+        unit: &Unit,
+        frames: usize,
+        ip: usize,
+    ) -> alloc::Result<()> {
+        if self.previous.is_none() {
+            return Ok(());
+        }
+
+        // The outermost function doesn't have a call frame.
+        let depth = frames.wrapping_add(1);
+
+        if self.path.len() != depth {
+            self.sync(unit, depth, ip)?;
+        }
+
+        self.pending = self.pending.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Enter a native function.
+    pub(crate) fn enter_native(&mut self, hash: Hash) -> alloc::Result<()> {
+        if self.previous.is_none() {
+            return Ok(());
+        }
+
+        let profiler = self.profiler.clone();
+        let mut state = profiler.lock();
+        self.flush(&mut state);
+        self.push(&mut state, ProfileFrame::Native(hash))
+    }
+
+    /// Exit a native function entered with [`ProfileCursor::enter_native`].
+    pub(crate) fn exit_native(&mut self) {
+        if self.previous.is_none() {
+            return;
+        }
+
+        let profiler = self.profiler.clone();
+        let mut state = profiler.lock();
+        self.flush(&mut state);
+        self.path.pop();
+        state.active = self.current();
+    }
+
+    /// Update the call stack to have the given depth, where the innermost
+    /// frame is at the given instruction pointer.
+    #[cold]
+    fn sync(&mut self, unit: &Unit, depth: usize, ip: usize) -> alloc::Result<()> {
+        let profiler = self.profiler.clone();
+        let mut state = profiler.lock();
+        self.flush(&mut state);
+
+        self.path.truncate(depth);
+
+        if self.path.len() < depth {
+            // Calls always start at the entry of a function, but a virtual
+            // machine might start recording anywhere.
+            let entry = match unit.debug_info() {
+                Some(debug) if !debug.functions_rev.contains_key(&ip) => {
+                    debug.function_entry_at(ip).unwrap_or(ip)
+                }
+                _ => ip,
+            };
+
+            while self.path.len() < depth {
+                self.push(&mut state, ProfileFrame::Function(entry))?;
+            }
+        }
+
+        state.active = self.current();
+        Ok(())
+    }
+
+    fn push(&mut self, state: &mut State, frame: ProfileFrame) -> alloc::Result<()> {
+        let parent = self.current().unwrap_or(0);
+        let index = state.enter(parent, frame)?;
+        self.path.try_push(index)?;
+        state.active = Some(index);
+        Ok(())
+    }
+
+    /// Record pending instructions and time.
+    fn flush(&mut self, state: &mut State) {
+        if let Some(node) = self.current().and_then(|index| state.nodes.get_mut(index)) {
+            node.instructions += take(&mut self.pending);
+        }
+
+        state.charge();
+    }
+
+    fn current(&self) -> Option<usize> {
+        self.path.last().copied().or(self.base)
+    }
+}
@@ -64,6 +64,7 @@ impl ProtocolCaller for EnvProtocolCaller {
 
                 let mut vm = Vm::with_stack(context.clone(), unit.clone(), stack);
                 vm.set_ip(offset);
+                vm.instrument_from_env();
                 return call.call_with_vm(vm);
             }
 
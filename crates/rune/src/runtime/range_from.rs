@@ -93,6 +93,9 @@ impl RangeFrom {
             ValueKind::Byte(start) => VmResult::Ok(Iterator::from(NAME, start..)),
             ValueKind::Char(start) => VmResult::Ok(Iterator::from(NAME, start..)),
             ValueKind::Integer(start) => VmResult::Ok(Iterator::from(NAME, start..)),
+            ValueKind::U64(start) => VmResult::Ok(Iterator::from(NAME, start..)),
+            ValueKind::I128(start) => VmResult::Ok(Iterator::from(NAME, start..)),
+            ValueKind::U128(start) => VmResult::Ok(Iterator::from(NAME, start..)),
             ref start => VmResult::err(VmErrorKind::UnsupportedIterRangeFrom {
                 start: start.type_info(),
             }),
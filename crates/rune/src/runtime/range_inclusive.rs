@@ -109,6 +109,15 @@ impl RangeInclusive {
             (ValueKind::Integer(start), ValueKind::Integer(end)) => {
                 VmResult::Ok(Iterator::from_double_ended(NAME, *start..=*end))
             }
+            (ValueKind::U64(start), ValueKind::U64(end)) => {
+                VmResult::Ok(Iterator::from_double_ended(NAME, *start..=*end))
+            }
+            (ValueKind::I128(start), ValueKind::I128(end)) => {
+                VmResult::Ok(Iterator::from_double_ended(NAME, *start..=*end))
+            }
+            (ValueKind::U128(start), ValueKind::U128(end)) => {
+                VmResult::Ok(Iterator::from_double_ended(NAME, *start..=*end))
+            }
             (start, end) => VmResult::err(VmErrorKind::UnsupportedIterRangeInclusive {
                 start: start.type_info(),
                 end: end.type_info(),
@@ -56,6 +56,14 @@ impl<T> Shared<T> {
         unsafe { self.inner.as_ref().access.snapshot() }
     }
 
+    /// Get a pointer which identifies the shared value.
+    ///
+    /// Clones of the same shared value will return the same pointer.
+    #[cfg(feature = "byte-code")]
+    pub(crate) fn as_ptr(&self) -> *const () {
+        self.inner.as_ptr().cast_const().cast()
+    }
+
     /// Take the interior value, if we have exlusive access to it and there
     /// are no other live exlusive or shared references.
     ///
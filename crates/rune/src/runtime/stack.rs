@@ -97,6 +97,15 @@ impl Stack {
         Ok(())
     }
 
+    /// Construct a stack from its values and the offset of the current stack
+    /// frame.
+    ///
+    /// The caller must make sure that `top` is within the bounds of `stack`.
+    #[cfg(feature = "byte-code")]
+    pub(crate) fn from_parts(stack: Vec<Value>, top: usize) -> Self {
+        Self { stack, top }
+    }
+
     /// Construct a new stack with the given capacity pre-allocated.
     ///
     /// ```
@@ -381,6 +390,13 @@ impl Stack {
         Ok(replace(&mut self.top, old_len))
     }
 
+    /// Truncate the current stack frame so that it ends at the given address.
+    pub(crate) fn truncate(&mut self, addr: InstAddress) {
+        if let Some(len) = self.top.checked_add(addr.offset()) {
+            self.stack.truncate(len);
+        }
+    }
+
     /// Pop the current stack top and modify it to a different one.
     ///
     /// This asserts that the size of the current stack frame is exactly zero
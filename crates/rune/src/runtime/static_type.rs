@@ -82,13 +82,43 @@ impl_static_type!(u16 => INTEGER_TYPE);
 impl_static_type!(i16 => INTEGER_TYPE);
 impl_static_type!(u32 => INTEGER_TYPE);
 impl_static_type!(i32 => INTEGER_TYPE);
-impl_static_type!(u64 => INTEGER_TYPE);
 impl_static_type!(i64 => INTEGER_TYPE);
-impl_static_type!(u128 => INTEGER_TYPE);
-impl_static_type!(i128 => INTEGER_TYPE);
 impl_static_type!(usize => INTEGER_TYPE);
 impl_static_type!(isize => INTEGER_TYPE);
 
+/// Hash for `::std::u64`.
+pub(crate) const U64_TYPE_HASH: Hash = ::rune_macros::hash!(::std::u64);
+
+/// The specialized type information for an unsigned integer type.
+pub(crate) static U64_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u64"),
+    hash: U64_TYPE_HASH,
+};
+
+impl_static_type!(u64 => U64_TYPE);
+
+/// Hash for `::std::i128`.
+pub(crate) const I128_TYPE_HASH: Hash = ::rune_macros::hash!(::std::i128);
+
+/// The specialized type information for a 128-bit integer type.
+pub(crate) static I128_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("i128"),
+    hash: I128_TYPE_HASH,
+};
+
+impl_static_type!(i128 => I128_TYPE);
+
+/// Hash for `::std::u128`.
+pub(crate) const U128_TYPE_HASH: Hash = ::rune_macros::hash!(::std::u128);
+
+/// The specialized type information for an unsigned 128-bit integer type.
+pub(crate) static U128_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u128"),
+    hash: U128_TYPE_HASH,
+};
+
+impl_static_type!(u128 => U128_TYPE);
+
 /// Hash for `::std::f64`.
 pub(crate) const FLOAT_TYPE_HASH: Hash = ::rune_macros::hash!(::std::f64);
 
@@ -77,6 +77,10 @@ where
 
 /// Trait for converting types into the dynamic [`Value`] container.
 ///
+/// Integer conversions depend only on the native type: `u64`, `i128` and
+/// `u128` always become the corresponding script type, while all other integer
+/// types become an `i64`, raising an error if the value doesn't fit.
+///
 /// # Examples
 ///
 /// ```
@@ -248,13 +252,10 @@ macro_rules! number_value_trait {
 
 number_value_trait!(u16);
 number_value_trait!(u32);
-number_value_trait!(u64);
-number_value_trait!(u128);
 number_value_trait!(usize);
 number_value_trait!(i8);
 number_value_trait!(i16);
 number_value_trait!(i32);
-number_value_trait!(i128);
 number_value_trait!(isize);
 
 impl ToValue for f32 {
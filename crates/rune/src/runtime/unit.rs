@@ -46,6 +46,11 @@ pub struct Unit<S = DefaultStorage> {
     logic: Logic<S>,
     /// Debug info if available for unit.
     debug: Option<Box<DebugInfo>>,
+    /// Cached fingerprint of the unit, see [`Unit::fingerprint`].
+    #[cfg(feature = "byte-code")]
+    #[serde(skip)]
+    #[try_clone(with = Clone::clone)]
+    fingerprint: once_cell::race::OnceBox<Hash>,
 }
 
 /// Instructions from a single source file.
@@ -74,6 +79,9 @@ pub struct Logic<S = DefaultStorage> {
     variant_rtti: hash::Map<Arc<VariantRtti>>,
     /// Named constants
     constants: hash::Map<ConstValue>,
+    /// The traits implemented by each type, by type hash.
+    #[serde(default)]
+    traits: hash::Map<Vec<Hash>>,
 }
 
 impl<S> Unit<S> {
@@ -82,6 +90,8 @@ impl<S> Unit<S> {
         Ok(Self {
             logic: data,
             debug: debug.map(Box::try_new).transpose()?,
+            #[cfg(feature = "byte-code")]
+            fingerprint: once_cell::race::OnceBox::new(),
         })
     }
 
@@ -97,6 +107,7 @@ impl<S> Unit<S> {
         variant_rtti: hash::Map<Arc<VariantRtti>>,
         debug: Option<Box<DebugInfo>>,
         constants: hash::Map<ConstValue>,
+        traits: hash::Map<Vec<Hash>>,
     ) -> Self {
         Self {
             logic: Logic {
@@ -108,8 +119,11 @@ impl<S> Unit<S> {
                 rtti,
                 variant_rtti,
                 constants,
+                traits,
             },
             debug,
+            #[cfg(feature = "byte-code")]
+            fingerprint: once_cell::race::OnceBox::new(),
         }
     }
 
@@ -206,6 +220,14 @@ impl<S> Unit<S> {
     pub(crate) fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.logic.constants.get(&hash)
     }
+
+    /// Test if the type with the given hash implements the given trait.
+    pub(crate) fn implements(&self, type_hash: Hash, trait_hash: Hash) -> bool {
+        self.logic
+            .traits
+            .get(&type_hash)
+            .is_some_and(|traits| traits.contains(&trait_hash))
+    }
 }
 
 impl<S> Unit<S>
@@ -230,6 +252,47 @@ where
     pub(crate) fn iter_instructions(&self) -> impl Iterator<Item = (usize, Inst)> + '_ {
         self.logic.storage.iter()
     }
+
+    /// Calculate a hash which identifies the instructions and functions of
+    /// this unit.
+    ///
+    /// This is used to make sure that a snapshot of a virtual machine is only
+    /// restored against the unit it was taken from. The fingerprint is only
+    /// calculated once and cached on the unit.
+    #[cfg(feature = "byte-code")]
+    pub(crate) fn fingerprint(&self) -> Result<Hash, EncodeError> {
+        let hash = self.fingerprint.get_or_try_init(|| {
+            let hash = self.compute_fingerprint()?;
+            Ok::<_, EncodeError>(rust_alloc::boxed::Box::new(hash))
+        })?;
+
+        Ok(*hash)
+    }
+
+    #[cfg(feature = "byte-code")]
+    fn compute_fingerprint(&self) -> Result<Hash, EncodeError> {
+        let mut bytes = rust_alloc::vec::Vec::new();
+
+        for (_, inst) in self.logic.storage.iter() {
+            musli::storage::encode(&mut bytes, &inst)?;
+        }
+
+        let mut functions = Vec::try_with_capacity(self.logic.functions.len())?;
+
+        for (hash, f) in self.logic.functions.iter() {
+            if let UnitFn::Offset { offset, .. } = f {
+                functions.try_push((*hash, *offset))?;
+            }
+        }
+
+        functions.sort();
+
+        for (hash, offset) in functions {
+            musli::storage::encode(&mut bytes, &(hash, offset))?;
+        }
+
+        Ok(Hash::static_bytes(&bytes))
+    }
 }
 
 /// The kind and necessary information on registered functions.
@@ -569,6 +569,12 @@ impl Value {
         self.inner.snapshot()
     }
 
+    /// Get a pointer which identifies the value, shared by all of its clones.
+    #[cfg(feature = "byte-code")]
+    pub(crate) fn as_ptr(&self) -> *const () {
+        self.inner.as_ptr()
+    }
+
     /// Construct an empty value.
     pub(crate) fn empty() -> alloc::Result<Self> {
         Ok(Self {
@@ -639,6 +645,18 @@ impl Value {
                 let mut buffer = itoa::Buffer::new();
                 vm_try!(f.push_str(buffer.format(*integer)));
             }
+            ValueKind::U64(unsigned) => {
+                let mut buffer = itoa::Buffer::new();
+                vm_try!(f.push_str(buffer.format(*unsigned)));
+            }
+            ValueKind::I128(value) => {
+                let mut buffer = itoa::Buffer::new();
+                vm_try!(f.push_str(buffer.format(*value)));
+            }
+            ValueKind::U128(value) => {
+                let mut buffer = itoa::Buffer::new();
+                vm_try!(f.push_str(buffer.format(*value)));
+            }
             ValueKind::Float(float) => {
                 let mut buffer = ryu::Buffer::new();
                 vm_try!(f.push_str(buffer.format(*float)));
@@ -687,6 +705,9 @@ impl Value {
             ValueKind::Byte(value) => ValueKind::Byte(*value),
             ValueKind::Char(value) => ValueKind::Char(*value),
             ValueKind::Integer(value) => ValueKind::Integer(*value),
+            ValueKind::U64(value) => ValueKind::U64(*value),
+            ValueKind::I128(value) => ValueKind::I128(*value),
+            ValueKind::U128(value) => ValueKind::U128(*value),
             ValueKind::Float(value) => ValueKind::Float(*value),
             ValueKind::Type(value) => ValueKind::Type(*value),
             ValueKind::Ordering(value) => ValueKind::Ordering(*value),
@@ -769,6 +790,15 @@ impl Value {
             ValueKind::Integer(value) => {
                 vm_write!(f, "{:?}", value);
             }
+            ValueKind::U64(value) => {
+                vm_write!(f, "{:?}", value);
+            }
+            ValueKind::I128(value) => {
+                vm_write!(f, "{:?}", value);
+            }
+            ValueKind::U128(value) => {
+                vm_write!(f, "{:?}", value);
+            }
             ValueKind::Float(value) => {
                 vm_write!(f, "{:?}", value);
             }
@@ -976,7 +1006,7 @@ impl Value {
     /// Try to coerce value into a usize.
     #[inline]
     pub fn as_usize(&self) -> Result<usize, RuntimeError> {
-        self.try_as_integer()
+        self.try_as_wide_integer()
     }
 
     /// Get the value as a string.
@@ -1086,6 +1116,36 @@ impl Value {
         as_integer,
     }
 
+    copy_into! {
+        /// Coerce into [`u64`] unsigned integer.
+        U64(u64),
+        into_u64_ref,
+        into_u64_mut,
+        borrow_u64_ref,
+        borrow_u64_mut,
+        as_u64,
+    }
+
+    copy_into! {
+        /// Coerce into [`i128`] integer.
+        I128(i128),
+        into_i128_ref,
+        into_i128_mut,
+        borrow_i128_ref,
+        borrow_i128_mut,
+        as_i128,
+    }
+
+    copy_into! {
+        /// Coerce into [`u128`] unsigned integer.
+        U128(u128),
+        into_u128_ref,
+        into_u128_mut,
+        borrow_u128_ref,
+        borrow_u128_mut,
+        as_u128,
+    }
+
     copy_into! {
         /// Coerce into [`f64`] float.
         Float(f64),
@@ -1509,6 +1569,9 @@ impl Value {
                 (ValueKind::Byte(a), ValueKind::Byte(b)) => return VmResult::Ok(*a == *b),
                 (ValueKind::Char(a), ValueKind::Char(b)) => return VmResult::Ok(*a == *b),
                 (ValueKind::Integer(a), ValueKind::Integer(b)) => return VmResult::Ok(*a == *b),
+                (ValueKind::U64(a), ValueKind::U64(b)) => return VmResult::Ok(*a == *b),
+                (ValueKind::I128(a), ValueKind::I128(b)) => return VmResult::Ok(*a == *b),
+                (ValueKind::U128(a), ValueKind::U128(b)) => return VmResult::Ok(*a == *b),
                 (ValueKind::Float(a), ValueKind::Float(b)) => return VmResult::Ok(*a == *b),
                 (ValueKind::Type(a), ValueKind::Type(b)) => return VmResult::Ok(*a == *b),
                 (ValueKind::Bytes(a), ValueKind::Bytes(b)) => {
@@ -1573,7 +1636,11 @@ impl Value {
                     (Err(a), Err(b)) => return Value::partial_eq_with(a, b, caller),
                     _ => return VmResult::Ok(false),
                 },
-                _ => {}
+                (a, b) => {
+                    if let Some(ordering) = a.cmp_integers(b) {
+                        return VmResult::Ok(ordering == Ordering::Equal);
+                    }
+                }
             }
 
             match &*a {
@@ -1616,10 +1683,28 @@ impl Value {
         caller: &mut impl ProtocolCaller,
     ) -> VmResult<()> {
         match &*vm_try!(self.borrow_kind_ref()) {
+            // Integers of different kinds which compare equal must hash the
+            // same, so they are hashed through their narrowest representation.
             ValueKind::Integer(value) => {
                 hasher.write_i64(*value);
                 return VmResult::Ok(());
             }
+            ValueKind::U64(value) => {
+                hash_integer(hasher, i128::from(*value));
+                return VmResult::Ok(());
+            }
+            ValueKind::I128(value) => {
+                hash_integer(hasher, *value);
+                return VmResult::Ok(());
+            }
+            ValueKind::U128(value) => {
+                match i128::try_from(*value) {
+                    Ok(value) => hash_integer(hasher, value),
+                    Err(..) => hasher.write_u128(*value),
+                }
+
+                return VmResult::Ok(());
+            }
             ValueKind::Byte(value) => {
                 hasher.write_u8(*value);
                 return VmResult::Ok(());
@@ -1698,6 +1783,9 @@ impl Value {
                 return VmResult::Ok(matches!(ordering, Ordering::Equal));
             }
             (ValueKind::Integer(a), ValueKind::Integer(b)) => return VmResult::Ok(*a == *b),
+            (ValueKind::U64(a), ValueKind::U64(b)) => return VmResult::Ok(*a == *b),
+            (ValueKind::I128(a), ValueKind::I128(b)) => return VmResult::Ok(*a == *b),
+            (ValueKind::U128(a), ValueKind::U128(b)) => return VmResult::Ok(*a == *b),
             (ValueKind::Type(a), ValueKind::Type(b)) => return VmResult::Ok(*a == *b),
             (ValueKind::Bytes(a), ValueKind::Bytes(b)) => {
                 return VmResult::Ok(*a == *b);
@@ -1771,7 +1859,11 @@ impl Value {
                 (Err(a), Err(b)) => return Value::eq_with(a, b, caller),
                 _ => return VmResult::Ok(false),
             },
-            _ => {}
+            (a, b) => {
+                if let Some(ordering) = a.cmp_integers(b) {
+                    return VmResult::Ok(ordering == Ordering::Equal);
+                }
+            }
         }
 
         if let CallResult::Ok(value) =
@@ -1823,6 +1915,15 @@ impl Value {
             (ValueKind::Integer(a), ValueKind::Integer(b)) => {
                 return VmResult::Ok(a.partial_cmp(b));
             }
+            (ValueKind::U64(a), ValueKind::U64(b)) => {
+                return VmResult::Ok(a.partial_cmp(b));
+            }
+            (ValueKind::I128(a), ValueKind::I128(b)) => {
+                return VmResult::Ok(a.partial_cmp(b));
+            }
+            (ValueKind::U128(a), ValueKind::U128(b)) => {
+                return VmResult::Ok(a.partial_cmp(b));
+            }
             (ValueKind::Type(a), ValueKind::Type(b)) => return VmResult::Ok(a.partial_cmp(b)),
             (ValueKind::Bytes(a), ValueKind::Bytes(b)) => {
                 return VmResult::Ok(a.partial_cmp(b));
@@ -1894,7 +1995,11 @@ impl Value {
                 (Ok(..), Err(..)) => return VmResult::Ok(Some(Ordering::Greater)),
                 (Err(..), Ok(..)) => return VmResult::Ok(Some(Ordering::Less)),
             },
-            _ => {}
+            (a, b) => {
+                if let Some(ordering) = a.cmp_integers(b) {
+                    return VmResult::Ok(Some(ordering));
+                }
+            }
         }
 
         if let CallResult::Ok(value) =
@@ -1948,6 +2053,9 @@ impl Value {
                 return VmResult::Ok(ordering);
             }
             (ValueKind::Integer(a), ValueKind::Integer(b)) => return VmResult::Ok(a.cmp(b)),
+            (ValueKind::U64(a), ValueKind::U64(b)) => return VmResult::Ok(a.cmp(b)),
+            (ValueKind::I128(a), ValueKind::I128(b)) => return VmResult::Ok(a.cmp(b)),
+            (ValueKind::U128(a), ValueKind::U128(b)) => return VmResult::Ok(a.cmp(b)),
             (ValueKind::Type(a), ValueKind::Type(b)) => return VmResult::Ok(a.cmp(b)),
             (ValueKind::Bytes(a), ValueKind::Bytes(b)) => {
                 return VmResult::Ok(a.cmp(b));
@@ -2019,7 +2127,11 @@ impl Value {
                 (Ok(..), Err(..)) => return VmResult::Ok(Ordering::Greater),
                 (Err(..), Ok(..)) => return VmResult::Ok(Ordering::Less),
             },
-            _ => {}
+            (a, b) => {
+                if let Some(ordering) = a.cmp_integers(b) {
+                    return VmResult::Ok(ordering);
+                }
+            }
         }
 
         if let CallResult::Ok(value) =
@@ -2066,6 +2178,56 @@ impl Value {
             )),
         }
     }
+
+    /// Try to coerce the current value as the specified integer `T`, accepting
+    /// any kind of integer.
+    ///
+    /// Unlike [`Value::try_as_integer`], this also coerces unsigned and 128-bit
+    /// integers, as long as the value fits in `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Value, VmResult};
+    ///
+    /// let value = rune::to_value(u64::MAX)?;
+    ///
+    /// assert_eq!(value.try_as_wide_integer::<u128>(), Ok(u64::MAX as u128));
+    /// assert!(value.try_as_wide_integer::<i64>().is_err());
+    ///
+    /// # Ok::<(), rune::support::Error>(())
+    /// ```
+    pub fn try_as_wide_integer<T>(&self) -> Result<T, RuntimeError>
+    where
+        T: TryFrom<i64> + TryFrom<u64> + TryFrom<i128> + TryFrom<u128>,
+    {
+        let (result, from) = match *self.borrow_kind_ref()? {
+            ValueKind::Integer(integer) => {
+                (T::try_from(integer).ok(), VmIntegerRepr::from(integer))
+            }
+            ValueKind::U64(unsigned) => (T::try_from(unsigned).ok(), VmIntegerRepr::from(unsigned)),
+            ValueKind::I128(value) => (T::try_from(value).ok(), VmIntegerRepr::from(value)),
+            ValueKind::U128(value) => (T::try_from(value).ok(), VmIntegerRepr::from(value)),
+            ref actual => return Err(RuntimeError::expected::<i64>(actual.type_info())),
+        };
+
+        match result {
+            Some(number) => Ok(number),
+            None => Err(RuntimeError::new(
+                VmErrorKind::ValueToIntegerCoercionError {
+                    from,
+                    to: any::type_name::<T>(),
+                },
+            )),
+        }
+    }
+}
+
+fn hash_integer(hasher: &mut Hasher, value: i128) {
+    match i64::try_from(value) {
+        Ok(value) => hasher.write_i64(value),
+        Err(..) => hasher.write_i128(value),
+    }
 }
 
 impl fmt::Debug for Value {
@@ -2184,6 +2346,9 @@ impl_from! {
     Bool => bool,
     Char => char,
     Integer => i64,
+    U64 => u64,
+    I128 => i128,
+    U128 => u128,
     Float => f64,
     Type => Type,
     Ordering => Ordering,
@@ -2289,6 +2454,12 @@ pub(crate) enum ValueKind {
     Char(char),
     /// A number.
     Integer(i64),
+    /// An unsigned number.
+    U64(u64),
+    /// A 128-bit number.
+    I128(i128),
+    /// An unsigned 128-bit number.
+    U128(u128),
     /// A float.
     Float(f64),
     /// A type hash. Describes a type in the virtual machine.
@@ -2350,6 +2521,29 @@ pub(crate) enum ValueKind {
 }
 
 impl ValueKind {
+    /// Compare two integers of any kind by value, returning `None` if either
+    /// isn't an integer.
+    pub(crate) fn cmp_integers(&self, other: &ValueKind) -> Option<Ordering> {
+        fn split(kind: &ValueKind) -> Option<Result<u128, i128>> {
+            match *kind {
+                ValueKind::Integer(value) => Some(u128::try_from(value).map_err(|_| value.into())),
+                ValueKind::U64(value) => Some(Ok(value.into())),
+                ValueKind::I128(value) => Some(u128::try_from(value).map_err(|_| value)),
+                ValueKind::U128(value) => Some(Ok(value)),
+                _ => None,
+            }
+        }
+
+        let ordering = match (split(self)?, split(other)?) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Err(a), Err(b)) => a.cmp(&b),
+            (Ok(..), Err(..)) => Ordering::Greater,
+            (Err(..), Ok(..)) => Ordering::Less,
+        };
+
+        Some(ordering)
+    }
+
     pub(crate) fn type_info(&self) -> TypeInfo {
         match self {
             ValueKind::Bool(..) => TypeInfo::StaticType(crate::runtime::static_type::BOOL_TYPE),
@@ -2358,6 +2552,9 @@ impl ValueKind {
             ValueKind::Integer(..) => {
                 TypeInfo::StaticType(crate::runtime::static_type::INTEGER_TYPE)
             }
+            ValueKind::U64(..) => TypeInfo::StaticType(crate::runtime::static_type::U64_TYPE),
+            ValueKind::I128(..) => TypeInfo::StaticType(crate::runtime::static_type::I128_TYPE),
+            ValueKind::U128(..) => TypeInfo::StaticType(crate::runtime::static_type::U128_TYPE),
             ValueKind::Float(..) => TypeInfo::StaticType(crate::runtime::static_type::FLOAT_TYPE),
             ValueKind::Type(..) => TypeInfo::StaticType(crate::runtime::static_type::TYPE),
             ValueKind::Ordering(..) => {
@@ -2423,6 +2620,9 @@ impl ValueKind {
             ValueKind::Byte(..) => crate::runtime::static_type::BYTE_TYPE.hash,
             ValueKind::Char(..) => crate::runtime::static_type::CHAR_TYPE.hash,
             ValueKind::Integer(..) => crate::runtime::static_type::INTEGER_TYPE.hash,
+            ValueKind::U64(..) => crate::runtime::static_type::U64_TYPE.hash,
+            ValueKind::I128(..) => crate::runtime::static_type::I128_TYPE.hash,
+            ValueKind::U128(..) => crate::runtime::static_type::U128_TYPE.hash,
             ValueKind::Float(..) => crate::runtime::static_type::FLOAT_TYPE.hash,
             ValueKind::Type(..) => crate::runtime::static_type::TYPE.hash,
             ValueKind::Ordering(..) => crate::runtime::static_type::ORDERING_TYPE.hash,
@@ -31,6 +31,9 @@ impl ser::Serialize for Value {
             ValueKind::Char(c) => serializer.serialize_char(*c),
             ValueKind::Byte(c) => serializer.serialize_u8(*c),
             ValueKind::Integer(integer) => serializer.serialize_i64(*integer),
+            ValueKind::U64(unsigned) => serializer.serialize_u64(*unsigned),
+            ValueKind::I128(integer) => serializer.serialize_i128(*integer),
+            ValueKind::U128(unsigned) => serializer.serialize_u128(*unsigned),
             ValueKind::Float(float) => serializer.serialize_f64(*float),
             ValueKind::Type(..) => Err(ser::Error::custom("cannot serialize types")),
             ValueKind::Ordering(..) => Err(ser::Error::custom("cannot serialize orderings")),
@@ -187,7 +190,10 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Value::try_from(v as i64).map_err(E::custom)
+        match i64::try_from(v) {
+            Ok(v) => Value::try_from(v).map_err(E::custom),
+            Err(..) => Value::try_from(v).map_err(E::custom),
+        }
     }
 
     #[inline]
@@ -219,7 +225,12 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Value::try_from(v as i64).map_err(E::custom)
+        // Numbers which fit are deserialized as signed integers, since that is
+        // what most scripts expect.
+        match i64::try_from(v) {
+            Ok(v) => Value::try_from(v).map_err(E::custom),
+            Err(..) => Value::try_from(v).map_err(E::custom),
+        }
     }
 
     #[inline]
@@ -227,7 +238,10 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Value::try_from(v as i64).map_err(E::custom)
+        match i64::try_from(v) {
+            Ok(v) => Value::try_from(v).map_err(E::custom),
+            Err(..) => Value::try_from(v).map_err(E::custom),
+        }
     }
 
     #[inline]
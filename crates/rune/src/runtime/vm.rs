@@ -6,6 +6,8 @@ use core::slice;
 
 use ::rust_alloc::sync::Arc;
 
+use musli::{Decode, Encode};
+
 use crate::alloc::prelude::*;
 use crate::alloc::{self, String};
 use crate::hash::{Hash, IntoHash, ToTypeHash};
@@ -14,16 +16,20 @@ use crate::runtime::budget;
 use crate::runtime::future::SelectFuture;
 use crate::runtime::unit::{UnitFn, UnitStorage};
 use crate::runtime::{
-    self, Args, Awaited, BorrowMut, Bytes, Call, ControlFlow, EmptyStruct, Format, FormatSpec,
-    Formatter, FromValue, Function, Future, Generator, GuardedArgs, Inst, InstAddress,
-    InstAssignOp, InstOp, InstRange, InstTarget, InstValue, InstVariant, Object, Output,
-    OwnedTuple, Panic, Protocol, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo,
-    RangeToInclusive, RuntimeContext, Select, Stack, Stream, Struct, Type, TypeCheck, TypeOf, Unit,
-    Value, ValueKind, Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution, VmHalt,
-    VmIntegerRepr, VmResult, VmSendExecution,
+    self, Args, Awaited, BorrowMut, Bytes, Call, ControlFlow, Coverage, DebugFrame, EmptyStruct,
+    Format, FormatSpec, Formatter, FromValue, Function, FunctionHandler, Future, Generator,
+    GuardedArgs, Inst, InstAddress, InstAssignOp, InstI128, InstOp, InstRange, InstTarget,
+    InstU128, InstValue, InstVariant, Object, Output, OwnedTuple, Panic, Protocol, Range,
+    RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, RuntimeContext, Select, Stack,
+    Stream, Struct, Type, TypeCheck, TypeOf, Unit, Value, ValueKind, Variant, VariantData, Vec,
+    VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmResult, VmSendExecution,
 };
 
 use super::{VmDiagnostics, VmDiagnosticsObj};
+#[cfg(feature = "std")]
+use crate::runtime::{ProfileCursor, Profiler};
+#[cfg(feature = "byte-code")]
+use crate::runtime::{VmSnapshot, VmSnapshotError};
 
 /// Small helper function to build errors.
 fn err<T, E>(error: E) -> VmResult<T>
@@ -88,6 +94,26 @@ macro_rules! target_value {
     }};
 }
 
+/// Call a native function handler, attributing the call to the native function
+/// with the given hash if the virtual machine is being profiled.
+macro_rules! call_native {
+    ($vm:ident, $hash:expr, $call:expr) => {{
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &mut $vm.profiler {
+            vm_try!(profiler.enter_native($hash));
+        }
+
+        let result = $call;
+
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &mut $vm.profiler {
+            profiler.exit_native();
+        }
+
+        result
+    }};
+}
+
 /// A stack which references variables indirectly from a slab.
 #[derive(Debug)]
 pub struct Vm {
@@ -103,6 +129,11 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: alloc::Vec<CallFrame>,
+    /// Coverage being recorded, if any.
+    coverage: Option<Coverage>,
+    /// The call stack being profiled, if any.
+    #[cfg(feature = "std")]
+    profiler: Option<ProfileCursor>,
 }
 
 impl Vm {
@@ -128,6 +159,32 @@ impl Vm {
             last_ip_len: 0,
             stack,
             call_frames: alloc::Vec::new(),
+            coverage: None,
+            #[cfg(feature = "std")]
+            profiler: None,
+        }
+    }
+
+    /// Construct a virtual machine from the parts of a restored snapshot.
+    #[cfg(feature = "byte-code")]
+    pub(crate) fn from_parts(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        ip: usize,
+        last_ip_len: u8,
+        stack: Stack,
+        call_frames: alloc::Vec<CallFrame>,
+    ) -> Self {
+        Self {
+            context,
+            unit,
+            ip,
+            last_ip_len,
+            stack,
+            call_frames,
+            coverage: None,
+            #[cfg(feature = "std")]
+            profiler: None,
         }
     }
 
@@ -159,12 +216,130 @@ impl Vm {
         self.ip = ip;
     }
 
+    /// Set the coverage to record executed instructions into, or `None` to
+    /// stop recording coverage.
+    ///
+    /// Virtual machines which are constructed to run functions called from
+    /// this one, like async functions, generators and closures passed to
+    /// native functions, record into the same coverage.
+    #[inline]
+    pub fn set_coverage(&mut self, coverage: Option<Coverage>) {
+        self.coverage = coverage;
+    }
+
+    /// Get the coverage being recorded, if any.
+    #[inline]
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Set the profiler to record executed functions into, or `None` to stop
+    /// profiling.
+    ///
+    /// Like with [`Vm::set_coverage`], virtual machines which are constructed
+    /// to run functions called from this one record into the same profiler.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn set_profiler(&mut self, profiler: Option<Profiler>) {
+        self.profiler = profiler.map(ProfileCursor::new);
+    }
+
+    /// Get the profiler being recorded into, if any.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref().map(ProfileCursor::profiler)
+    }
+
+    /// Record coverage and profile a virtual machine which is constructed to
+    /// run a function called from this one.
+    pub(crate) fn instrument_child(&self, vm: &mut Vm) {
+        vm.coverage = self.coverage.clone();
+
+        #[cfg(feature = "std")]
+        {
+            vm.profiler = self.profiler.as_ref().map(ProfileCursor::fork);
+        }
+    }
+
+    /// Record coverage and profile a virtual machine which is constructed to
+    /// run a function called from native code, into whatever the currently
+    /// running virtual machine records into.
+    pub(crate) fn instrument_from_env(&mut self) {
+        self.coverage = crate::runtime::env::coverage();
+
+        #[cfg(feature = "std")]
+        {
+            self.profiler = Profiler::current().map(ProfileCursor::new);
+        }
+    }
+
     /// Get the stack.
     #[inline]
     pub fn call_frames(&self) -> &[CallFrame] {
         &self.call_frames
     }
 
+    /// Get the call frames which can be inspected by a debugger, starting
+    /// with the innermost one.
+    ///
+    /// Frames are only collected up until the first isolated frame, since
+    /// frames beyond it might belong to a different unit.
+    pub fn debug_frames(&self) -> alloc::Result<alloc::Vec<DebugFrame>> {
+        let mut frames = alloc::Vec::new();
+
+        frames.try_push(DebugFrame {
+            ip: self.ip,
+            top: self.stack.top(),
+        })?;
+
+        let debug = self.unit.debug_info();
+
+        for frame in self.call_frames.iter().rev() {
+            if frame.isolated {
+                break;
+            }
+
+            let ip = debug
+                .and_then(|debug| debug.instruction_before(frame.ip))
+                .map_or(frame.ip, |(ip, _)| ip);
+
+            frames.try_push(DebugFrame { ip, top: frame.top })?;
+        }
+
+        Ok(frames)
+    }
+
+    /// Get the named local variables which are available in the given frame,
+    /// in the order in which they were declared.
+    pub fn debug_locals(&self, frame: &DebugFrame) -> alloc::Result<alloc::Vec<(&str, Value)>> {
+        let mut locals = alloc::Vec::new();
+
+        let Some(debug) = self.unit.debug_info() else {
+            return Ok(locals);
+        };
+
+        for variable in debug.variables_at(frame.ip)? {
+            let index = frame.top.wrapping_add(variable.addr.offset());
+
+            if let Some(value) = self.stack.get(index) {
+                locals.try_push((variable.name.as_ref(), value.clone()))?;
+            }
+        }
+
+        Ok(locals)
+    }
+
+    /// Get the named local variable in the given frame.
+    pub fn debug_local(&self, frame: &DebugFrame, name: &str) -> alloc::Result<Option<Value>> {
+        let locals = self.debug_locals(frame)?;
+
+        Ok(locals
+            .into_iter()
+            .find(|(local, _)| *local == name)
+            .map(|(_, value)| value))
+    }
+
     /// Get the stack.
     #[inline]
     pub fn stack(&self) -> &Stack {
@@ -236,6 +411,41 @@ impl Vm {
         self.ip.wrapping_sub(self.last_ip_len as usize)
     }
 
+    /// Access the length of the last instruction that was executed.
+    #[cfg(feature = "byte-code")]
+    pub(crate) fn last_ip_len(&self) -> u8 {
+        self.last_ip_len
+    }
+
+    /// Take a snapshot of the virtual machine which can be serialized and
+    /// later restored with [`Vm::restore`].
+    ///
+    /// This captures the stack, call frames and instruction pointer of the
+    /// virtual machine, and every value which can be reached from the stack.
+    /// Taking a snapshot fails if any reachable value can't be serialized,
+    /// like native types or futures.
+    ///
+    /// To take a snapshot of a suspended execution, like a generator that
+    /// has yielded, use [`VmExecution::snapshot`] or [`Generator::snapshot`]
+    /// instead.
+    #[cfg(feature = "byte-code")]
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmSnapshotError> {
+        VmSnapshot::new(self, None)
+    }
+
+    /// Restore a virtual machine from a snapshot taken with [`Vm::snapshot`].
+    ///
+    /// The unit must be the same as the one the snapshot was taken from.
+    #[cfg(feature = "byte-code")]
+    pub fn restore(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        snapshot: &VmSnapshot,
+    ) -> Result<Self, VmSnapshotError> {
+        let (vm, _) = snapshot.restore(context, unit)?;
+        Ok(vm)
+    }
+
     /// Reset this virtual machine, freeing all memory used.
     pub fn clear(&mut self) {
         self.ip = 0;
@@ -561,6 +771,12 @@ impl Vm {
         self.ip = offset;
         self.stack.clear();
         self.call_frames.clear();
+
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &mut self.profiler {
+            profiler.reset();
+        }
+
         Ok(())
     }
 
@@ -603,7 +819,11 @@ impl Vm {
             vm_try!(self.stack.push(target));
             // Safety: We hold onto the guard for the duration of this call.
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut self.stack)) };
-            vm_try!(handler(&mut self.stack, addr, count, out));
+            vm_try!(call_native!(
+                self,
+                hash,
+                handler(&mut self.stack, addr, count, out)
+            ));
             return VmResult::Ok(CallResult::Ok(()));
         }
 
@@ -620,6 +840,48 @@ impl Vm {
         })
     }
 
+    /// Call the native function `handler` with the given `hash`.
+    pub(crate) fn call_native_fn(
+        &mut self,
+        hash: Hash,
+        handler: &FunctionHandler,
+        addr: InstAddress,
+        args: usize,
+        out: Output,
+    ) -> VmResult<()> {
+        vm_try!(self.called_function_hook(hash));
+        call_native!(self, hash, handler(&mut self.stack, addr, args, out))
+    }
+
+    /// Call the native function `handler` with the given `hash`, passing
+    /// `target` as the first argument ahead of the `args` arguments at `addr`.
+    ///
+    /// The arguments are copied to the top of the stack, and are removed once
+    /// the call returns.
+    pub(crate) fn call_native_fn_with_target(
+        &mut self,
+        hash: Hash,
+        handler: &FunctionHandler,
+        target: Value,
+        addr: InstAddress,
+        args: usize,
+        out: Output,
+    ) -> VmResult<()> {
+        let seq = vm_try!(self.stack.slice_at(addr, args));
+        let seq = vm_try!(seq.iter().cloned().try_collect::<alloc::Vec<_>>());
+
+        let top = self.stack.addr();
+        vm_try!(self.stack.push(target));
+
+        for value in seq {
+            vm_try!(self.stack.push(value));
+        }
+
+        let result = self.call_native_fn(hash, handler, top, args.wrapping_add(1), out);
+        self.stack.truncate(top);
+        result
+    }
+
     /// Helper to call a field function.
     #[inline(always)]
     fn call_field_fn<N, A>(
@@ -642,7 +904,11 @@ impl Vm {
             vm_try!(self.called_function_hook(hash));
             vm_try!(self.stack.push(target));
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut self.stack)) };
-            vm_try!(handler(&mut self.stack, addr, count, out));
+            vm_try!(call_native!(
+                self,
+                hash,
+                handler(&mut self.stack, addr, count, out)
+            ));
             return VmResult::Ok(CallResult::Ok(()));
         }
 
@@ -669,7 +935,11 @@ impl Vm {
             let addr = self.stack.addr();
             vm_try!(self.stack.push(target));
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut self.stack)) };
-            vm_try!(handler(&mut self.stack, addr, count, out));
+            vm_try!(call_native!(
+                self,
+                hash,
+                handler(&mut self.stack, addr, count, out)
+            ));
             return VmResult::Ok(CallResult::Ok(()));
         }
 
@@ -1131,6 +1401,15 @@ impl Vm {
                     runtime::static_type::INTEGER_TYPE_HASH => {
                         vm_try!(Value::try_from($value as i64))
                     }
+                    runtime::static_type::U64_TYPE_HASH => {
+                        vm_try!(Value::try_from($value as u64))
+                    }
+                    runtime::static_type::I128_TYPE_HASH => {
+                        vm_try!(Value::try_from($value as i128))
+                    }
+                    runtime::static_type::U128_TYPE_HASH => {
+                        vm_try!(Value::try_from($value as u128))
+                    }
                     ty => {
                         return err(VmErrorKind::UnsupportedAs {
                             value: <$from as TypeOf>::type_info(),
@@ -1143,6 +1422,9 @@ impl Vm {
 
         let value = match &*vm_try!(a.borrow_kind_ref()) {
             ValueKind::Integer(a) => convert!(i64, *a, ty),
+            ValueKind::U64(a) => convert!(u64, *a, ty),
+            ValueKind::I128(a) => convert!(i128, *a, ty),
+            ValueKind::U128(a) => convert!(u128, *a, ty),
             ValueKind::Float(a) => convert!(f64, *a, ty),
             ValueKind::Byte(a) => convert!(u8, *a, ty),
             kind => {
@@ -1171,6 +1453,20 @@ impl Vm {
         VmResult::Ok(vm_try!(a.type_hash()) == ty.into_hash())
     }
 
+    fn test_implements(&mut self, lhs: InstAddress, rhs: InstAddress) -> VmResult<bool> {
+        let b = vm_try!(self.stack.at(rhs)).clone();
+        let a = vm_try!(self.stack.at(lhs)).clone();
+
+        let ValueKind::Type(ty) = *vm_try!(b.borrow_kind_ref()) else {
+            return err(VmErrorKind::UnsupportedIs {
+                value: vm_try!(a.type_info()),
+                test_type: vm_try!(b.type_info()),
+            });
+        };
+
+        VmResult::Ok(self.unit.implements(vm_try!(a.type_hash()), ty.into_hash()))
+    }
+
     fn internal_boolean_op(
         &mut self,
         bool_op: impl FnOnce(bool, bool) -> bool,
@@ -1215,6 +1511,7 @@ impl Vm {
             let stack = iter.iter().cloned().try_collect::<Stack>()?;
             let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
             vm.ip = offset;
+            self.instrument_child(&mut vm);
             *self.stack.at_mut(at)? = Value::try_from(Generator::new(vm))?;
         }
 
@@ -1235,6 +1532,7 @@ impl Vm {
             let stack = stack.iter().cloned().try_collect::<Stack>()?;
             let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
             vm.ip = offset;
+            self.instrument_child(&mut vm);
             *self.stack.at_mut(at)? = Value::try_from(Stream::new(vm))?;
         }
 
@@ -1255,6 +1553,7 @@ impl Vm {
             let stack = stack.iter().cloned().try_collect::<Stack>()?;
             let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
             vm.ip = offset;
+            self.instrument_child(&mut vm);
             let mut execution = vm.into_execution();
             let future = Future::new(async move { execution.async_complete().await })?;
             *self.stack.at_mut(at)? = Value::try_from(future)?;
@@ -1300,6 +1599,9 @@ impl Vm {
         protocol: Protocol,
         error: fn() -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
+        u64_op: fn(u64, u64) -> Option<u64>,
+        i128_op: fn(i128, i128) -> Option<i128>,
+        u128_op: fn(u128, u128) -> Option<u128>,
         float_op: fn(f64, f64) -> f64,
         rhs: InstAddress,
     ) -> VmResult<()> {
@@ -1317,12 +1619,35 @@ impl Vm {
                         *lhs = out;
                         return VmResult::Ok(());
                     }
+                    (ValueKind::U64(lhs), ValueKind::U64(rhs)) => {
+                        let out = vm_try!(u64_op(*lhs, *rhs).ok_or_else(error));
+                        *lhs = out;
+                        return VmResult::Ok(());
+                    }
+                    (ValueKind::I128(lhs), ValueKind::I128(rhs)) => {
+                        let out = vm_try!(i128_op(*lhs, *rhs).ok_or_else(error));
+                        *lhs = out;
+                        return VmResult::Ok(());
+                    }
+                    (ValueKind::U128(lhs), ValueKind::U128(rhs)) => {
+                        let out = vm_try!(u128_op(*lhs, *rhs).ok_or_else(error));
+                        *lhs = out;
+                        return VmResult::Ok(());
+                    }
                     (ValueKind::Float(lhs), ValueKind::Float(rhs)) => {
                         let out = float_op(*lhs, *rhs);
                         *lhs = out;
                         return VmResult::Ok(());
                     }
-                    _ => {}
+                    (lhs, rhs) => {
+                        let out =
+                            widened_op(protocol.name, lhs, rhs, error, u64_op, i128_op, u128_op);
+
+                        if let Some(out) = out {
+                            *lhs = vm_try!(out);
+                            return VmResult::Ok(());
+                        }
+                    }
                 }
 
                 TargetFallback::Value(lhs.clone(), rhs)
@@ -1393,6 +1718,9 @@ impl Vm {
         protocol: Protocol,
         error: fn() -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
+        u64_op: fn(u64, u64) -> Option<u64>,
+        i128_op: fn(i128, i128) -> Option<i128>,
+        u128_op: fn(u128, u128) -> Option<u128>,
         float_op: fn(f64, f64) -> f64,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -1410,11 +1738,34 @@ impl Vm {
                 vm_try!(out.store(&mut self.stack, value));
                 return VmResult::Ok(());
             }
+            (ValueKind::U64(lhs), ValueKind::U64(rhs)) => {
+                let value = vm_try!(u64_op(*lhs, *rhs).ok_or_else(error));
+                vm_try!(out.store(&mut self.stack, value));
+                return VmResult::Ok(());
+            }
+            (ValueKind::I128(lhs), ValueKind::I128(rhs)) => {
+                let value = vm_try!(i128_op(*lhs, *rhs).ok_or_else(error));
+                vm_try!(out.store(&mut self.stack, value));
+                return VmResult::Ok(());
+            }
+            (ValueKind::U128(lhs), ValueKind::U128(rhs)) => {
+                let value = vm_try!(u128_op(*lhs, *rhs).ok_or_else(error));
+                vm_try!(out.store(&mut self.stack, value));
+                return VmResult::Ok(());
+            }
             (ValueKind::Float(lhs), ValueKind::Float(rhs)) => {
                 vm_try!(out.store(&mut self.stack, float_op(*lhs, *rhs)));
                 return VmResult::Ok(());
             }
-            _ => {}
+            (lhs, rhs) => {
+                let value = widened_op(protocol.name, lhs, rhs, error, u64_op, i128_op, u128_op);
+
+                if let Some(value) = value {
+                    let value = vm_try!(value);
+                    vm_try!(out.store(&mut self.stack, vm_try!(Value::try_from(value))));
+                    return VmResult::Ok(());
+                }
+            }
         };
 
         let lhs = lhs.clone();
@@ -1438,6 +1789,9 @@ impl Vm {
         &mut self,
         protocol: Protocol,
         integer_op: fn(i64, i64) -> i64,
+        u64_op: fn(u64, u64) -> u64,
+        i128_op: fn(i128, i128) -> i128,
+        u128_op: fn(u128, u128) -> u128,
         byte_op: fn(u8, u8) -> u8,
         bool_op: fn(bool, bool) -> bool,
         lhs: InstAddress,
@@ -1455,6 +1809,18 @@ impl Vm {
                 vm_try!(out.store(&mut self.stack, integer_op(*lhs, *rhs)));
                 return VmResult::Ok(());
             }
+            (ValueKind::U64(lhs), ValueKind::U64(rhs)) => {
+                vm_try!(out.store(&mut self.stack, u64_op(*lhs, *rhs)));
+                return VmResult::Ok(());
+            }
+            (ValueKind::I128(lhs), ValueKind::I128(rhs)) => {
+                vm_try!(out.store(&mut self.stack, i128_op(*lhs, *rhs)));
+                return VmResult::Ok(());
+            }
+            (ValueKind::U128(lhs), ValueKind::U128(rhs)) => {
+                vm_try!(out.store(&mut self.stack, u128_op(*lhs, *rhs)));
+                return VmResult::Ok(());
+            }
             (ValueKind::Byte(lhs), ValueKind::Byte(rhs)) => {
                 vm_try!(out.store(&mut self.stack, byte_op(*lhs, *rhs)));
                 return VmResult::Ok(());
@@ -1484,6 +1850,9 @@ impl Vm {
         target: InstTarget,
         protocol: Protocol,
         integer_op: fn(&mut i64, i64),
+        u64_op: fn(&mut u64, u64),
+        i128_op: fn(&mut i128, i128),
+        u128_op: fn(&mut u128, u128),
         byte_op: fn(&mut u8, u8),
         bool_op: fn(&mut bool, bool),
         rhs: InstAddress,
@@ -1501,6 +1870,18 @@ impl Vm {
                         integer_op(lhs, *rhs);
                         return VmResult::Ok(());
                     }
+                    (ValueKind::U64(lhs), ValueKind::U64(rhs)) => {
+                        u64_op(lhs, *rhs);
+                        return VmResult::Ok(());
+                    }
+                    (ValueKind::I128(lhs), ValueKind::I128(rhs)) => {
+                        i128_op(lhs, *rhs);
+                        return VmResult::Ok(());
+                    }
+                    (ValueKind::U128(lhs), ValueKind::U128(rhs)) => {
+                        u128_op(lhs, *rhs);
+                        return VmResult::Ok(());
+                    }
                     (ValueKind::Byte(lhs), ValueKind::Byte(rhs)) => {
                         byte_op(lhs, *rhs);
                         return VmResult::Ok(());
@@ -1525,6 +1906,9 @@ impl Vm {
         protocol: Protocol,
         error: fn() -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
+        u64_op: fn(u64, i64) -> Option<u64>,
+        i128_op: fn(i128, i64) -> Option<i128>,
+        u128_op: fn(u128, i64) -> Option<u128>,
         byte_op: fn(u8, i64) -> Option<u8>,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -1542,6 +1926,21 @@ impl Vm {
                 vm_try!(out.store(&mut self.stack, integer));
                 return VmResult::Ok(());
             }
+            (ValueKind::U64(lhs), ValueKind::Integer(rhs)) => {
+                let unsigned = vm_try!(u64_op(*lhs, *rhs).ok_or_else(error));
+                vm_try!(out.store(&mut self.stack, unsigned));
+                return VmResult::Ok(());
+            }
+            (ValueKind::I128(lhs), ValueKind::Integer(rhs)) => {
+                let i128 = vm_try!(i128_op(*lhs, *rhs).ok_or_else(error));
+                vm_try!(out.store(&mut self.stack, i128));
+                return VmResult::Ok(());
+            }
+            (ValueKind::U128(lhs), ValueKind::Integer(rhs)) => {
+                let u128 = vm_try!(u128_op(*lhs, *rhs).ok_or_else(error));
+                vm_try!(out.store(&mut self.stack, u128));
+                return VmResult::Ok(());
+            }
             (ValueKind::Byte(lhs), ValueKind::Integer(rhs)) => {
                 let byte = vm_try!(byte_op(*lhs, *rhs).ok_or_else(error));
                 vm_try!(out.store(&mut self.stack, byte));
@@ -1569,6 +1968,9 @@ impl Vm {
         protocol: Protocol,
         error: fn() -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
+        u64_op: fn(u64, i64) -> Option<u64>,
+        i128_op: fn(i128, i64) -> Option<i128>,
+        u128_op: fn(u128, i64) -> Option<u128>,
         byte_op: fn(u8, i64) -> Option<u8>,
         rhs: InstAddress,
     ) -> VmResult<()> {
@@ -1586,6 +1988,21 @@ impl Vm {
                         *lhs = out;
                         return VmResult::Ok(());
                     }
+                    (ValueKind::U64(lhs), ValueKind::Integer(rhs)) => {
+                        let out = vm_try!(u64_op(*lhs, *rhs).ok_or_else(error));
+                        *lhs = out;
+                        return VmResult::Ok(());
+                    }
+                    (ValueKind::I128(lhs), ValueKind::Integer(rhs)) => {
+                        let out = vm_try!(i128_op(*lhs, *rhs).ok_or_else(error));
+                        *lhs = out;
+                        return VmResult::Ok(());
+                    }
+                    (ValueKind::U128(lhs), ValueKind::Integer(rhs)) => {
+                        let out = vm_try!(u128_op(*lhs, *rhs).ok_or_else(error));
+                        *lhs = out;
+                        return VmResult::Ok(());
+                    }
                     (ValueKind::Byte(lhs), ValueKind::Integer(rhs)) => {
                         let out = vm_try!(byte_op(*lhs, *rhs).ok_or_else(error));
                         *lhs = out;
@@ -1698,6 +2115,28 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    /// Fused integer comparison and jump-if-not instruction.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_jump_if_not_eq_integer(
+        &mut self,
+        addr: InstAddress,
+        value: i64,
+        jump: usize,
+    ) -> VmResult<()> {
+        let v = vm_try!(self.stack.at(addr));
+
+        let is_match = match *vm_try!(v.borrow_kind_ref()) {
+            ValueKind::Integer(actual) => actual == value,
+            _ => false,
+        };
+
+        if !is_match {
+            self.ip = vm_try!(self.unit.translate(jump));
+        }
+
+        VmResult::Ok(())
+    }
+
     /// Construct a new vec.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_vec(&mut self, addr: InstAddress, count: usize, out: Output) -> VmResult<()> {
@@ -1776,13 +2215,18 @@ impl Vm {
         let value = vm_try!(self.stack.at(operand));
 
         let value = match *vm_try!(value.borrow_kind_ref()) {
-            ValueKind::Bool(value) => vm_try!(Value::try_from(!value)),
-            ValueKind::Integer(value) => vm_try!(Value::try_from(!value)),
-            ValueKind::Byte(value) => vm_try!(Value::try_from(!value)),
-            ref other => {
-                let operand = other.type_info();
-                return err(VmErrorKind::UnsupportedUnaryOperation { op: "!", operand });
-            }
+            ValueKind::Bool(value) => Some(vm_try!(Value::try_from(!value))),
+            ValueKind::Integer(value) => Some(vm_try!(Value::try_from(!value))),
+            ValueKind::U64(value) => Some(vm_try!(Value::try_from(!value))),
+            ValueKind::I128(value) => Some(vm_try!(Value::try_from(!value))),
+            ValueKind::U128(value) => Some(vm_try!(Value::try_from(!value))),
+            ValueKind::Byte(value) => Some(vm_try!(Value::try_from(!value))),
+            _ => None,
+        };
+
+        let Some(value) = value else {
+            let value = vm_try!(self.stack.at(operand)).clone();
+            return self.internal_unary(Protocol::NOT, "!", value, out);
         };
 
         vm_try!(out.store(&mut self.stack, value));
@@ -1794,18 +2238,48 @@ impl Vm {
         let value = vm_try!(self.stack.at(addr));
 
         let value = match *vm_try!(value.borrow_kind_ref()) {
-            ValueKind::Float(value) => vm_try!(Value::try_from(-value)),
-            ValueKind::Integer(value) => vm_try!(Value::try_from(-value)),
-            ref other => {
-                let operand = other.type_info();
-                return err(VmErrorKind::UnsupportedUnaryOperation { op: "-", operand });
+            ValueKind::Float(value) => Some(vm_try!(Value::try_from(-value))),
+            ValueKind::Integer(value) => {
+                let value = vm_try!(value.checked_neg().ok_or(VmErrorKind::Overflow));
+                Some(vm_try!(Value::try_from(value)))
+            }
+            ValueKind::I128(value) => {
+                let value = vm_try!(value.checked_neg().ok_or(VmErrorKind::Overflow));
+                Some(vm_try!(Value::try_from(value)))
             }
+            _ => None,
+        };
+
+        let Some(value) = value else {
+            let value = vm_try!(self.stack.at(addr)).clone();
+            return self.internal_unary(Protocol::NEG, "-", value, out);
         };
 
         vm_try!(out.store(&mut self.stack, value));
         VmResult::Ok(())
     }
 
+    /// Call the protocol function implementing a unary operation on a value
+    /// which isn't handled by the virtual machine.
+    fn internal_unary(
+        &mut self,
+        protocol: Protocol,
+        op: &'static str,
+        value: Value,
+        out: Output,
+    ) -> VmResult<()> {
+        if let CallResult::Unsupported(value) =
+            vm_try!(self.call_instance_fn(value, protocol, (), out))
+        {
+            return err(VmErrorKind::UnsupportedUnaryOperation {
+                op,
+                operand: vm_try!(value.type_info()),
+            });
+        }
+
+        VmResult::Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_op(
         &mut self,
@@ -1820,6 +2294,9 @@ impl Vm {
                     Protocol::ADD,
                     || VmErrorKind::Overflow,
                     i64::checked_add,
+                    u64::checked_add,
+                    i128::checked_add,
+                    u128::checked_add,
                     ops::Add::add,
                     lhs,
                     rhs,
@@ -1831,6 +2308,9 @@ impl Vm {
                     Protocol::SUB,
                     || VmErrorKind::Underflow,
                     i64::checked_sub,
+                    u64::checked_sub,
+                    i128::checked_sub,
+                    u128::checked_sub,
                     ops::Sub::sub,
                     lhs,
                     rhs,
@@ -1842,6 +2322,9 @@ impl Vm {
                     Protocol::MUL,
                     || VmErrorKind::Overflow,
                     i64::checked_mul,
+                    u64::checked_mul,
+                    i128::checked_mul,
+                    u128::checked_mul,
                     ops::Mul::mul,
                     lhs,
                     rhs,
@@ -1853,6 +2336,9 @@ impl Vm {
                     Protocol::DIV,
                     || VmErrorKind::DivideByZero,
                     i64::checked_div,
+                    u64::checked_div,
+                    i128::checked_div,
+                    u128::checked_div,
                     ops::Div::div,
                     lhs,
                     rhs,
@@ -1864,6 +2350,9 @@ impl Vm {
                     Protocol::REM,
                     || VmErrorKind::DivideByZero,
                     i64::checked_rem,
+                    u64::checked_rem,
+                    i128::checked_rem,
+                    u128::checked_rem,
                     ops::Rem::rem,
                     lhs,
                     rhs,
@@ -1875,6 +2364,9 @@ impl Vm {
                 vm_try!(self.internal_infallible_bitwise_bool(
                     Protocol::BIT_AND,
                     i64::bitand,
+                    u64::bitand,
+                    i128::bitand,
+                    u128::bitand,
                     u8::bitand,
                     bool::bitand,
                     lhs,
@@ -1887,6 +2379,9 @@ impl Vm {
                 vm_try!(self.internal_infallible_bitwise_bool(
                     Protocol::BIT_XOR,
                     i64::bitxor,
+                    u64::bitxor,
+                    i128::bitxor,
+                    u128::bitxor,
                     u8::bitxor,
                     bool::bitxor,
                     lhs,
@@ -1899,6 +2394,9 @@ impl Vm {
                 vm_try!(self.internal_infallible_bitwise_bool(
                     Protocol::BIT_OR,
                     i64::bitor,
+                    u64::bitor,
+                    i128::bitor,
+                    u128::bitor,
                     u8::bitor,
                     bool::bitor,
                     lhs,
@@ -1912,6 +2410,9 @@ impl Vm {
                     || VmErrorKind::Overflow,
                     |a, b| a.checked_shl(u32::try_from(b).ok()?),
                     |a, b| a.checked_shl(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shl(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shl(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shl(u32::try_from(b).ok()?),
                     lhs,
                     rhs,
                     out,
@@ -1923,6 +2424,9 @@ impl Vm {
                     || VmErrorKind::Underflow,
                     |a, b| a.checked_shr(u32::try_from(b).ok()?),
                     |a, b| a.checked_shr(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
                     lhs,
                     rhs,
                     out
@@ -1985,6 +2489,14 @@ impl Vm {
                 let is_instance = vm_try!(self.test_is_instance(lhs, rhs));
                 vm_try!(out.store(&mut self.stack, !is_instance));
             }
+            InstOp::IsImpl => {
+                let implements = vm_try!(self.test_implements(lhs, rhs));
+                vm_try!(out.store(&mut self.stack, implements));
+            }
+            InstOp::IsNotImpl => {
+                let implements = vm_try!(self.test_implements(lhs, rhs));
+                vm_try!(out.store(&mut self.stack, !implements));
+            }
         }
 
         VmResult::Ok(())
@@ -2004,6 +2516,9 @@ impl Vm {
                     Protocol::ADD_ASSIGN,
                     || VmErrorKind::Overflow,
                     i64::checked_add,
+                    u64::checked_add,
+                    i128::checked_add,
+                    u128::checked_add,
                     ops::Add::add,
                     value,
                 ));
@@ -2014,6 +2529,9 @@ impl Vm {
                     Protocol::SUB_ASSIGN,
                     || VmErrorKind::Underflow,
                     i64::checked_sub,
+                    u64::checked_sub,
+                    i128::checked_sub,
+                    u128::checked_sub,
                     ops::Sub::sub,
                     value,
                 ));
@@ -2024,6 +2542,9 @@ impl Vm {
                     Protocol::MUL_ASSIGN,
                     || VmErrorKind::Overflow,
                     i64::checked_mul,
+                    u64::checked_mul,
+                    i128::checked_mul,
+                    u128::checked_mul,
                     ops::Mul::mul,
                     value,
                 ));
@@ -2034,6 +2555,9 @@ impl Vm {
                     Protocol::DIV_ASSIGN,
                     || VmErrorKind::DivideByZero,
                     i64::checked_div,
+                    u64::checked_div,
+                    i128::checked_div,
+                    u128::checked_div,
                     ops::Div::div,
                     value,
                 ));
@@ -2044,6 +2568,9 @@ impl Vm {
                     Protocol::REM_ASSIGN,
                     || VmErrorKind::DivideByZero,
                     i64::checked_rem,
+                    u64::checked_rem,
+                    i128::checked_rem,
+                    u128::checked_rem,
                     ops::Rem::rem,
                     value,
                 ));
@@ -2055,6 +2582,9 @@ impl Vm {
                     ops::BitAndAssign::bitand_assign,
                     ops::BitAndAssign::bitand_assign,
                     ops::BitAndAssign::bitand_assign,
+                    ops::BitAndAssign::bitand_assign,
+                    ops::BitAndAssign::bitand_assign,
+                    ops::BitAndAssign::bitand_assign,
                     value,
                 ));
             }
@@ -2065,6 +2595,9 @@ impl Vm {
                     ops::BitXorAssign::bitxor_assign,
                     ops::BitXorAssign::bitxor_assign,
                     ops::BitXorAssign::bitxor_assign,
+                    ops::BitXorAssign::bitxor_assign,
+                    ops::BitXorAssign::bitxor_assign,
+                    ops::BitXorAssign::bitxor_assign,
                     value,
                 ));
             }
@@ -2075,6 +2608,9 @@ impl Vm {
                     ops::BitOrAssign::bitor_assign,
                     ops::BitOrAssign::bitor_assign,
                     ops::BitOrAssign::bitor_assign,
+                    ops::BitOrAssign::bitor_assign,
+                    ops::BitOrAssign::bitor_assign,
+                    ops::BitOrAssign::bitor_assign,
                     value,
                 ));
             }
@@ -2085,6 +2621,9 @@ impl Vm {
                     || VmErrorKind::Overflow,
                     |a, b| a.checked_shl(u32::try_from(b).ok()?),
                     |a, b| a.checked_shl(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shl(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shl(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shl(u32::try_from(b).ok()?),
                     value,
                 ));
             }
@@ -2095,6 +2634,9 @@ impl Vm {
                     || VmErrorKind::Underflow,
                     |a, b| a.checked_shr(u32::try_from(b).ok()?),
                     |a, b| a.checked_shr(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
                     value,
                 ));
             }
@@ -2695,6 +3237,45 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_eq_u64(&mut self, addr: InstAddress, value: u64, out: Output) -> VmResult<()> {
+        let v = vm_try!(self.stack.at(addr));
+
+        let is_match = match *vm_try!(v.borrow_kind_ref()) {
+            ValueKind::U64(actual) => actual == value,
+            _ => false,
+        };
+
+        vm_try!(out.store(&mut self.stack, is_match));
+        VmResult::Ok(())
+    }
+
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_eq_i128(&mut self, addr: InstAddress, value: InstI128, out: Output) -> VmResult<()> {
+        let v = vm_try!(self.stack.at(addr));
+
+        let is_match = match *vm_try!(v.borrow_kind_ref()) {
+            ValueKind::I128(actual) => actual == value.get(),
+            _ => false,
+        };
+
+        vm_try!(out.store(&mut self.stack, is_match));
+        VmResult::Ok(())
+    }
+
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_eq_u128(&mut self, addr: InstAddress, value: InstU128, out: Output) -> VmResult<()> {
+        let v = vm_try!(self.stack.at(addr));
+
+        let is_match = match *vm_try!(v.borrow_kind_ref()) {
+            ValueKind::U128(actual) => actual == value.get(),
+            _ => false,
+        };
+
+        vm_try!(out.store(&mut self.stack, is_match));
+        VmResult::Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_eq_bool(&mut self, addr: InstAddress, value: bool, out: Output) -> VmResult<()> {
         let v = vm_try!(self.stack.at(addr));
@@ -2775,6 +3356,36 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_check_type(&mut self, hash: Hash, slot: usize, addr: InstAddress) -> VmResult<()> {
+        let value = vm_try!(self.stack.at(addr));
+        let actual = vm_try!(value.type_hash());
+
+        if actual == hash {
+            return VmResult::Ok(());
+        }
+
+        // Wider integer types also accept plain integers, since that is what
+        // integer literals and native functions taking them accept.
+        if actual == runtime::static_type::INTEGER_TYPE_HASH
+            && matches!(
+                hash,
+                runtime::static_type::U64_TYPE_HASH
+                    | runtime::static_type::I128_TYPE_HASH
+                    | runtime::static_type::U128_TYPE_HASH
+            )
+        {
+            return VmResult::Ok(());
+        }
+
+        let expected = vm_try!(self.unit.lookup_string(slot));
+
+        err(VmErrorKind::TypeAnnotationMismatch {
+            expected: vm_try!(expected.as_str().try_to_owned()),
+            actual: vm_try!(value.type_info()),
+        })
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_match_variant(
         &mut self,
@@ -2990,7 +3601,11 @@ impl Vm {
                 .function(hash)
                 .ok_or(VmErrorKind::MissingFunction { hash }));
 
-            vm_try!(handler(&mut self.stack, addr, args, out));
+            vm_try!(call_native!(
+                self,
+                hash,
+                handler(&mut self.stack, addr, args, out)
+            ));
             return VmResult::Ok(());
         };
 
@@ -3106,7 +3721,11 @@ impl Vm {
 
         if let Some(handler) = self.context.function(hash) {
             vm_try!(self.called_function_hook(hash));
-            vm_try!(handler(&mut self.stack, addr, args, out));
+            vm_try!(call_native!(
+                self,
+                hash,
+                handler(&mut self.stack, addr, args, out)
+            ));
             return VmResult::Ok(());
         }
 
@@ -3116,6 +3735,28 @@ impl Vm {
         })
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_call_trait(
+        &mut self,
+        trait_hash: Hash,
+        hash: Hash,
+        addr: InstAddress,
+        args: usize,
+        out: Output,
+    ) -> VmResult<()> {
+        let instance = vm_try!(self.stack.at(addr));
+        let type_hash = vm_try!(instance.type_hash());
+
+        if !self.unit.implements(type_hash, trait_hash) {
+            return err(VmErrorKind::MissingTraitImpl {
+                trait_hash,
+                instance: vm_try!(instance.type_info()),
+            });
+        }
+
+        self.op_call_associated(hash, addr, args, out)
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     #[tracing::instrument(skip(self))]
     fn op_call_fn(
@@ -3128,14 +3769,25 @@ impl Vm {
         let function = vm_try!(self.stack.at(function)).clone();
 
         let ty = match *vm_try!(function.borrow_kind_ref()) {
-            ValueKind::Type(ty) => ty,
+            ValueKind::Type(ty) => Some(ty),
             ValueKind::Function(ref function) => {
                 return function.call_with_vm(self, addr, args, out);
             }
-            ref actual => {
-                let actual = actual.type_info();
-                return err(VmErrorKind::UnsupportedCallFn { actual });
-            }
+            _ => None,
+        };
+
+        let Some(ty) = ty else {
+            let Some(callable) = vm_try!(Function::from_callable(
+                &self.context,
+                &self.unit,
+                &function
+            )) else {
+                return err(VmErrorKind::UnsupportedCallFn {
+                    actual: vm_try!(function.type_info()),
+                });
+            };
+
+            return callable.call_with_vm(self, addr, args, out);
         };
 
         vm_try!(self.op_call(ty.into_hash(), addr, args, out));
@@ -3204,6 +3856,7 @@ impl Vm {
             NonNull::from(&self.context),
             NonNull::from(&self.unit),
             None,
+            self.coverage.as_ref().map(NonNull::from),
         );
         f()
     }
@@ -3226,8 +3879,25 @@ impl Vm {
             NonNull::from(&self.context),
             NonNull::from(&self.unit),
             diagnostics,
+            self.coverage.as_ref().map(NonNull::from),
         );
 
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &mut self.profiler {
+            profiler.begin(&self.unit);
+        }
+
+        let result = self.run_instructions();
+
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &mut self.profiler {
+            profiler.end();
+        }
+
+        result
+    }
+
+    fn run_instructions(&mut self) -> VmResult<VmHalt> {
         let mut budget = budget::acquire();
 
         loop {
@@ -3244,6 +3914,15 @@ impl Vm {
 
             tracing::trace!(ip = ?self.ip, ?inst);
 
+            if let Some(coverage) = &self.coverage {
+                coverage.record(&self.unit, self.ip);
+            }
+
+            #[cfg(feature = "std")]
+            if let Some(profiler) = &mut self.profiler {
+                vm_try!(profiler.instruction(&self.unit, self.call_frames.len(), self.ip));
+            }
+
             self.ip = self.ip.wrapping_add(inst_len);
             self.last_ip_len = inst_len as u8;
 
@@ -3290,6 +3969,15 @@ impl Vm {
                 } => {
                     vm_try!(self.op_call_associated(hash, addr, args, out));
                 }
+                Inst::CallTrait {
+                    trait_hash,
+                    hash,
+                    addr,
+                    args,
+                    out,
+                } => {
+                    vm_try!(self.op_call_trait(trait_hash, hash, addr, args, out));
+                }
                 Inst::CallFn {
                     function,
                     addr,
@@ -3379,6 +4067,9 @@ impl Vm {
                 Inst::JumpIfNot { cond, jump } => {
                     vm_try!(self.op_jump_if_not(cond, jump));
                 }
+                Inst::JumpIfNotEqInteger { addr, value, jump } => {
+                    vm_try!(self.op_jump_if_not_eq_integer(addr, value, jump));
+                }
                 Inst::Vec { addr, count, out } => {
                     vm_try!(self.op_vec(addr, count, out));
                 }
@@ -3459,6 +4150,15 @@ impl Vm {
                 Inst::EqInteger { addr, value, out } => {
                     vm_try!(self.op_eq_integer(addr, value, out));
                 }
+                Inst::EqU64 { addr, value, out } => {
+                    vm_try!(self.op_eq_u64(addr, value, out));
+                }
+                Inst::EqI128 { addr, value, out } => {
+                    vm_try!(self.op_eq_i128(addr, value, out));
+                }
+                Inst::EqU128 { addr, value, out } => {
+                    vm_try!(self.op_eq_u128(addr, value, out));
+                }
                 Inst::EqBool {
                     addr,
                     value: boolean,
@@ -3484,6 +4184,9 @@ impl Vm {
                 Inst::MatchType { hash, addr, out } => {
                     vm_try!(self.op_match_type(hash, addr, out));
                 }
+                Inst::CheckType { hash, slot, addr } => {
+                    vm_try!(self.op_check_type(hash, slot, addr));
+                }
                 Inst::MatchVariant {
                     enum_hash,
                     variant_hash,
@@ -3545,6 +4248,9 @@ impl TryClone for Vm {
             last_ip_len: self.last_ip_len,
             stack: self.stack.try_clone()?,
             call_frames: self.call_frames.try_clone()?,
+            coverage: self.coverage.clone(),
+            #[cfg(feature = "std")]
+            profiler: self.profiler.as_ref().map(ProfileCursor::fork),
         })
     }
 }
@@ -3566,7 +4272,7 @@ impl AsRef<Vm> for Vm {
 /// A call frame.
 ///
 /// This is used to store the return point after an instruction has been run.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Encode, Decode)]
 #[non_exhaustive]
 pub struct CallFrame {
     /// The stored instruction pointer.
@@ -3611,3 +4317,68 @@ fn check_args(args: usize, expected: usize) -> Result<(), VmErrorKind> {
 
     Ok(())
 }
+
+/// Apply an integer operation to integers of different kinds by converting
+/// both operands to the wider of the two kinds.
+///
+/// Returns `None` if either operand isn't an integer. Raises
+/// [`VmErrorKind::IntegerWideningError`] if an operand doesn't fit in the wider
+/// kind, and the error of the operation if it fails.
+fn widened_op(
+    op: &'static str,
+    lhs: &ValueKind,
+    rhs: &ValueKind,
+    error: fn() -> VmErrorKind,
+    u64_op: fn(u64, u64) -> Option<u64>,
+    i128_op: fn(i128, i128) -> Option<i128>,
+    u128_op: fn(u128, u128) -> Option<u128>,
+) -> Option<VmResult<ValueKind>> {
+    fn rank(kind: &ValueKind) -> Option<u8> {
+        match kind {
+            ValueKind::Integer(..) => Some(0),
+            ValueKind::U64(..) => Some(1),
+            ValueKind::I128(..) => Some(2),
+            ValueKind::U128(..) => Some(3),
+            _ => None,
+        }
+    }
+
+    fn widen<T>(kind: &ValueKind) -> Option<T>
+    where
+        T: TryFrom<i64> + TryFrom<u64> + TryFrom<i128> + TryFrom<u128>,
+    {
+        match *kind {
+            ValueKind::Integer(value) => T::try_from(value).ok(),
+            ValueKind::U64(value) => T::try_from(value).ok(),
+            ValueKind::I128(value) => T::try_from(value).ok(),
+            ValueKind::U128(value) => T::try_from(value).ok(),
+            _ => None,
+        }
+    }
+
+    let widened = match rank(lhs)?.max(rank(rhs)?) {
+        1 => widen(lhs)
+            .zip(widen(rhs))
+            .map(|(a, b)| u64_op(a, b).map(ValueKind::U64)),
+        2 => widen(lhs)
+            .zip(widen(rhs))
+            .map(|(a, b)| i128_op(a, b).map(ValueKind::I128)),
+        3 => widen(lhs)
+            .zip(widen(rhs))
+            .map(|(a, b)| u128_op(a, b).map(ValueKind::U128)),
+        _ => return None,
+    };
+
+    let Some(value) = widened else {
+        return Some(err(VmErrorKind::IntegerWideningError {
+            op,
+            lhs: lhs.type_info(),
+            rhs: rhs.type_info(),
+        }));
+    };
+
+    match value {
+        Some(value) => Some(VmResult::Ok(value)),
+        None => Some(err(error())),
+    }
+}
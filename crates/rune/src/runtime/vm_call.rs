@@ -90,8 +90,9 @@ impl VmCall {
         let context = self.context.unwrap_or_else(|| vm.context().clone());
         let unit = self.unit.unwrap_or_else(|| vm.unit().clone());
 
-        let mut vm = Vm::with_stack(context, unit, new_stack);
-        vm.set_ip(ip);
-        VmResult::Ok(vm)
+        let mut new_vm = Vm::with_stack(context, unit, new_stack);
+        new_vm.set_ip(ip);
+        vm.instrument_child(&mut new_vm);
+        VmResult::Ok(new_vm)
     }
 }
@@ -573,6 +573,10 @@ pub(crate) enum VmErrorKind {
         hash: Hash,
         instance: TypeInfo,
     },
+    MissingTraitImpl {
+        trait_hash: Hash,
+        instance: TypeInfo,
+    },
     IpOutOfBounds {
         ip: usize,
         length: usize,
@@ -582,6 +586,11 @@ pub(crate) enum VmErrorKind {
         lhs: TypeInfo,
         rhs: TypeInfo,
     },
+    IntegerWideningError {
+        op: &'static str,
+        lhs: TypeInfo,
+        rhs: TypeInfo,
+    },
     UnsupportedUnaryOperation {
         op: &'static str,
         operand: TypeInfo,
@@ -716,6 +725,10 @@ pub(crate) enum VmErrorKind {
         target: TypeInfo,
         field: String,
     },
+    TypeAnnotationMismatch {
+        expected: String,
+        actual: TypeInfo,
+    },
     MissingVariantName,
     MissingStructField {
         target: &'static str,
@@ -776,6 +789,15 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::MissingInstanceFunction { hash, instance } => {
                 write!(f, "Missing instance function `{hash}` for `{instance}`",)
             }
+            VmErrorKind::MissingTraitImpl {
+                trait_hash,
+                instance,
+            } => {
+                write!(
+                    f,
+                    "Type `{instance}` does not implement trait `{trait_hash}`",
+                )
+            }
             VmErrorKind::IpOutOfBounds { ip, length } => write!(
                 f,
                 "Instruction pointer `{ip}` is out-of-bounds `0-{length}`",
@@ -786,6 +808,12 @@ impl fmt::Display for VmErrorKind {
                     "Unsupported binary operation `{op}` on `{lhs}` and `{rhs}`",
                 )
             }
+            VmErrorKind::IntegerWideningError { op, lhs, rhs } => {
+                write!(
+                    f,
+                    "Failed to convert `{lhs}` and `{rhs}` into the same integer type for `{op}`",
+                )
+            }
             VmErrorKind::UnsupportedUnaryOperation { op, operand } => {
                 write!(f, "Unsupported unary operation `{op}` on {operand}",)
             }
@@ -915,6 +943,12 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::MissingField { target, field } => {
                 write!(f, "Missing field `{field}` on `{target}`",)
             }
+            VmErrorKind::TypeAnnotationMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Expected value of type `{expected}` but found `{actual}`",
+                )
+            }
             VmErrorKind::MissingVariantName {} => {
                 write!(f, "missing variant name in runtime information")
             }
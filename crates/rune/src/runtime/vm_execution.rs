@@ -6,13 +6,18 @@ use ::rust_alloc::sync::Arc;
 
 use crate::alloc::prelude::*;
 use crate::runtime::budget;
+use crate::runtime::debugger::Stepper;
+use crate::runtime::{DebugStep, DebugStop};
 use crate::runtime::{
-    Generator, GeneratorState, InstAddress, Output, RuntimeContext, Stream, Unit, Value, Vm,
-    VmErrorKind, VmHalt, VmHaltInfo, VmResult,
+    Debugger, Generator, GeneratorState, InstAddress, Output, RuntimeContext, Stream, Unit, Value,
+    Vm, VmErrorKind, VmHalt, VmHaltInfo, VmResult,
 };
 use crate::shared::AssertSend;
+use crate::Sources;
 
 use super::VmDiagnostics;
+#[cfg(feature = "byte-code")]
+use super::{VmSnapshot, VmSnapshotError, VmSnapshotErrorKind};
 
 /// The state of an execution. We keep track of this because it's important to
 /// correctly interact with functions that yield (like generators and streams)
@@ -162,6 +167,27 @@ where
         self.head.as_mut()
     }
 
+    /// Take a snapshot of the execution which can be serialized and later
+    /// restored with [`VmExecution::restore`].
+    ///
+    /// An execution can only be snapshotted before it has started or while it
+    /// is suspended at a `yield`, and not while it's calling into a different
+    /// unit.
+    #[cfg(feature = "byte-code")]
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmSnapshotError> {
+        if !self.states.is_empty() {
+            return Err(VmSnapshotError::from(VmSnapshotErrorKind::NestedExecution));
+        }
+
+        let resume = match self.state {
+            ExecutionState::Initial => None,
+            ExecutionState::Resumed(out) => Some(out),
+            _ => return Err(VmSnapshotError::from(VmSnapshotErrorKind::NotSuspended)),
+        };
+
+        VmSnapshot::new(self.head.as_ref(), resume)
+    }
+
     /// Complete the current execution without support for async instructions.
     ///
     /// This will error if the execution is suspended through yielding.
@@ -440,6 +466,58 @@ where
         VmResult::Ok(None)
     }
 
+    /// Run the execution under a debugger until the given [`DebugStep`] completes,
+    /// a breakpoint is hit or execution completes, without support for async
+    /// instructions.
+    ///
+    /// The sources are used to map instructions to source lines, and must be
+    /// the sources the unit was compiled from.
+    ///
+    /// If any async instructions are encountered, this will error.
+    pub fn debug(
+        &mut self,
+        debugger: &mut Debugger,
+        sources: &Sources,
+        step: DebugStep,
+    ) -> VmResult<DebugStop> {
+        let stepper = Stepper::new(self.vm(), sources, step);
+
+        loop {
+            if let Some(value) = vm_try!(self.step()) {
+                return VmResult::Ok(DebugStop::Exited(value));
+            }
+
+            if let Some(stop) = stepper.stop(debugger, self.vm(), sources) {
+                return VmResult::Ok(stop);
+            }
+        }
+    }
+
+    /// Run the execution under a debugger until the given [`DebugStep`] completes,
+    /// a breakpoint is hit or execution completes, with support for async
+    /// instructions.
+    ///
+    /// The sources are used to map instructions to source lines, and must be
+    /// the sources the unit was compiled from.
+    pub async fn async_debug(
+        &mut self,
+        debugger: &mut Debugger,
+        sources: &Sources,
+        step: DebugStep,
+    ) -> VmResult<DebugStop> {
+        let stepper = Stepper::new(self.vm(), sources, step);
+
+        loop {
+            if let Some(value) = vm_try!(self.async_step().await) {
+                return VmResult::Ok(DebugStop::Exited(value));
+            }
+
+            if let Some(stop) = stepper.stop(debugger, self.vm(), sources) {
+                return VmResult::Ok(stop);
+            }
+        }
+    }
+
     /// End execution and perform debug checks.
     pub(crate) fn end(&mut self) -> VmResult<Value> {
         let ExecutionState::Exited(addr) = self.state else {
@@ -491,7 +569,8 @@ impl VmExecution<&mut Vm> {
     /// Convert the current execution into one which owns its virtual machine.
     pub fn into_owned(self) -> VmExecution<Vm> {
         let stack = take(self.head.stack_mut());
-        let head = Vm::with_stack(self.head.context().clone(), self.head.unit().clone(), stack);
+        let mut head = Vm::with_stack(self.head.context().clone(), self.head.unit().clone(), stack);
+        self.head.instrument_child(&mut head);
 
         VmExecution {
             head,
@@ -501,6 +580,32 @@ impl VmExecution<&mut Vm> {
     }
 }
 
+impl VmExecution<Vm> {
+    /// Restore an execution from a snapshot taken with
+    /// [`VmExecution::snapshot`].
+    ///
+    /// The unit must be the same as the one the snapshot was taken from.
+    #[cfg(feature = "byte-code")]
+    pub fn restore(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        snapshot: &VmSnapshot,
+    ) -> Result<Self, VmSnapshotError> {
+        let (head, resume) = snapshot.restore(context, unit)?;
+
+        let state = match resume {
+            Some(out) => ExecutionState::Resumed(out),
+            None => ExecutionState::Initial,
+        };
+
+        Ok(Self {
+            head,
+            state,
+            states: Vec::new(),
+        })
+    }
+}
+
 /// A wrapper that makes [`VmExecution`] [`Send`].
 ///
 /// This is accomplished by preventing any [`Value`] from escaping the [`Vm`].
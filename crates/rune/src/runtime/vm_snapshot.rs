@@ -0,0 +1,729 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+use ::rust_alloc::string::String as StdString;
+use ::rust_alloc::sync::Arc;
+use ::rust_alloc::vec::Vec as StdVec;
+
+use musli::{Decode, Encode};
+
+use crate::alloc::{self, HashMap, String};
+use crate::runtime::unit::EncodeError;
+use crate::runtime::{
+    AccessError, Bytes, CallFrame, ControlFlow, EmptyStruct, GeneratorState, Object, Output,
+    OwnedTuple, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Rtti,
+    RuntimeContext, Stack, Struct, TupleStruct, Type, TypeInfo, Unit, Value, ValueKind, Variant,
+    VariantData, VariantRtti, Vec, Vm,
+};
+use crate::Hash;
+
+/// A serializable snapshot of a suspended virtual machine.
+///
+/// A snapshot captures the stack, call frames and instruction pointer of a
+/// virtual machine together with every value reachable from its stack. Values
+/// which are shared are only stored once, so aliasing is preserved when the
+/// snapshot is restored.
+///
+/// Snapshots are taken with [`Vm::snapshot`], [`VmExecution::snapshot`] or
+/// [`Generator::snapshot`], and can be converted to and from bytes with
+/// [`VmSnapshot::to_bytes`] and [`VmSnapshot::from_bytes`].
+///
+/// [`VmExecution::snapshot`]: crate::runtime::VmExecution::snapshot
+/// [`Generator::snapshot`]: crate::runtime::Generator::snapshot
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::{Generator, VmSnapshot};
+///
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+/// let context = Arc::new(context.runtime()?);
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn count() {
+///             let n = 0;
+///
+///             loop {
+///                 n += 1;
+///                 yield n;
+///             }
+///         }
+///
+///         pub fn main() {
+///             count()
+///         }
+///     }
+/// };
+///
+/// let unit = Arc::new(rune::prepare(&mut sources).build()?);
+///
+/// let mut vm = Vm::new(context.clone(), unit.clone());
+/// let mut generator: Generator<Vm> = rune::from_value(vm.call(["main"], ())?)?;
+/// generator.next().into_result()?;
+///
+/// let bytes = generator.snapshot()?.to_bytes()?;
+///
+/// let snapshot = VmSnapshot::from_bytes(&bytes)?;
+/// let mut generator = Generator::restore(context, unit, &snapshot)?;
+/// let n: i64 = rune::from_value(generator.next().into_result()?.unwrap())?;
+/// assert_eq!(n, 2);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Debug, Encode, Decode)]
+#[non_exhaustive]
+pub struct VmSnapshot {
+    /// The fingerprint of the unit the snapshot was taken from.
+    unit: Hash,
+    /// Where the value an execution is resumed with should be stored.
+    resume: Option<Output>,
+    /// The instruction pointer.
+    ip: usize,
+    /// The length of the last instruction.
+    last_ip_len: u8,
+    /// The top of the current stack frame.
+    top: usize,
+    /// Indexes of the values on the stack.
+    stack: StdVec<usize>,
+    /// Call frames of the virtual machine.
+    call_frames: StdVec<CallFrame>,
+    /// All values which are reachable from the stack.
+    values: StdVec<ValueSnapshot>,
+}
+
+impl VmSnapshot {
+    /// Take a snapshot of the given virtual machine.
+    pub(crate) fn new(vm: &Vm, resume: Option<Output>) -> Result<Self, VmSnapshotError> {
+        let mut encoder = Encoder::default();
+
+        let mut stack = StdVec::new();
+
+        for value in vm.stack().iter() {
+            stack.push(encoder.value(value)?);
+        }
+
+        while let Some((index, value)) = encoder.queue.pop() {
+            let snapshot = encoder.kind(&value)?;
+            encoder.values[index] = Some(snapshot);
+        }
+
+        let values = encoder
+            .values
+            .into_iter()
+            .map(|value| value.ok_or(VmSnapshotErrorKind::BadValue))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            unit: vm.unit().fingerprint()?,
+            resume,
+            ip: vm.ip(),
+            last_ip_len: vm.last_ip_len(),
+            top: vm.stack().top(),
+            stack,
+            call_frames: vm.call_frames().to_vec(),
+            values,
+        })
+    }
+
+    /// Restore a virtual machine from the snapshot.
+    ///
+    /// Returns the restored virtual machine and where the value an execution
+    /// is resumed with should be stored.
+    pub(crate) fn restore(
+        &self,
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+    ) -> Result<(Vm, Option<Output>), VmSnapshotError> {
+        let actual = unit.fingerprint()?;
+
+        if actual != self.unit {
+            return Err(VmSnapshotError::from(VmSnapshotErrorKind::UnitMismatch {
+                expected: self.unit,
+                actual,
+            }));
+        }
+
+        self.validate(&unit)?;
+
+        let mut values = alloc::Vec::try_with_capacity(self.values.len())?;
+
+        for _ in 0..self.values.len() {
+            values.try_push(Value::empty()?)?;
+        }
+
+        let decoder = Decoder {
+            unit: &unit,
+            values: &values,
+        };
+
+        for (value, snapshot) in values.iter().zip(&self.values) {
+            if let ValueSnapshot::Taken = snapshot {
+                value.clone().take_kind()?;
+                continue;
+            }
+
+            let kind = decoder.kind(snapshot)?;
+            *value.borrow_kind_mut()? = kind;
+        }
+
+        let mut stack = alloc::Vec::try_with_capacity(self.stack.len())?;
+
+        for &index in &self.stack {
+            stack.try_push(decoder.value(index)?)?;
+        }
+
+        let mut call_frames = alloc::Vec::try_with_capacity(self.call_frames.len())?;
+
+        for frame in &self.call_frames {
+            call_frames.try_push(*frame)?;
+        }
+
+        let stack = Stack::from_parts(stack, self.top);
+        let vm = Vm::from_parts(context, unit, self.ip, self.last_ip_len, stack, call_frames);
+        Ok((vm, self.resume))
+    }
+
+    /// Check that the stack layout and instruction pointers of the snapshot
+    /// are consistent with the unit it is being restored against.
+    ///
+    /// The snapshot might have been truncated or tampered with, so these
+    /// can't be trusted to be valid.
+    fn validate(&self, unit: &Unit) -> Result<(), VmSnapshotError> {
+        let is_valid_ip = |ip: usize| matches!(unit.instruction_at(ip), Ok(Some(..)));
+
+        if self.top > self.stack.len() || !is_valid_ip(self.ip) {
+            return Err(VmSnapshotError::from(VmSnapshotErrorKind::BadValue));
+        }
+
+        // Each call frame stores the top of the stack of its caller, so they
+        // must be in increasing order and within the current stack frame.
+        let mut top = 0;
+
+        for frame in &self.call_frames {
+            if frame.top < top || frame.top > self.top || !is_valid_ip(frame.ip) {
+                return Err(VmSnapshotError::from(VmSnapshotErrorKind::BadValue));
+            }
+
+            top = frame.top;
+        }
+
+        Ok(())
+    }
+
+    /// Get the hash of the unit the snapshot was taken from.
+    pub fn unit_hash(&self) -> Hash {
+        self.unit
+    }
+
+    /// Serialize the snapshot into bytes.
+    pub fn to_bytes(&self) -> Result<StdVec<u8>, VmSnapshotError> {
+        Ok(musli::storage::to_vec(self)?)
+    }
+
+    /// Deserialize a snapshot from bytes produced by [`VmSnapshot::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VmSnapshotError> {
+        Ok(musli::storage::from_slice(bytes)?)
+    }
+}
+
+/// A single serialized value, which refers to other values by index.
+#[derive(Debug, Encode, Decode)]
+enum ValueSnapshot {
+    /// A value which has been moved out of, and can no longer be accessed.
+    Taken,
+    EmptyTuple,
+    Bool(bool),
+    Byte(u8),
+    Char(char),
+    Integer(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    Float(f64),
+    Type(Hash),
+    Ordering(i8),
+    String(StdString),
+    Bytes(StdVec<u8>),
+    Vec(StdVec<usize>),
+    Tuple(StdVec<usize>),
+    Object(StdVec<(StdString, usize)>),
+    RangeFrom(usize),
+    RangeFull,
+    RangeInclusive(usize, usize),
+    RangeToInclusive(usize),
+    RangeTo(usize),
+    Range(usize, usize),
+    Continue(usize),
+    Break(usize),
+    Yielded(usize),
+    Complete(usize),
+    None,
+    Some(usize),
+    Ok(usize),
+    Err(usize),
+    EmptyStruct(Hash),
+    TupleStruct(Hash, StdVec<usize>),
+    Struct(Hash, StdVec<(StdString, usize)>),
+    EmptyVariant(Hash),
+    TupleVariant(Hash, StdVec<usize>),
+    StructVariant(Hash, StdVec<(StdString, usize)>),
+}
+
+/// Assigns indexes to values as they are encountered.
+#[derive(Default)]
+struct Encoder {
+    seen: HashMap<*const (), usize>,
+    values: StdVec<Option<ValueSnapshot>>,
+    queue: StdVec<(usize, Value)>,
+}
+
+impl Encoder {
+    /// Get the index of the given value, queueing it up to be encoded if it
+    /// hasn't been seen before.
+    fn value(&mut self, value: &Value) -> Result<usize, VmSnapshotError> {
+        let ptr = value.as_ptr();
+
+        if let Some(index) = self.seen.get(&ptr) {
+            return Ok(*index);
+        }
+
+        let index = self.values.len();
+        self.seen.try_insert(ptr, index)?;
+        self.values.push(None);
+        self.queue.push((index, value.clone()));
+        Ok(index)
+    }
+
+    fn values<'a>(
+        &mut self,
+        values: impl IntoIterator<Item = &'a Value>,
+    ) -> Result<StdVec<usize>, VmSnapshotError> {
+        let mut output = StdVec::new();
+
+        for value in values {
+            output.push(self.value(value)?);
+        }
+
+        Ok(output)
+    }
+
+    fn object(&mut self, object: &Object) -> Result<StdVec<(StdString, usize)>, VmSnapshotError> {
+        let mut output = StdVec::new();
+
+        for (key, value) in object.iter() {
+            output.push((StdString::from(key.as_str()), self.value(value)?));
+        }
+
+        Ok(output)
+    }
+
+    fn kind(&mut self, value: &Value) -> Result<ValueSnapshot, VmSnapshotError> {
+        if value.snapshot().is_taken() {
+            return Ok(ValueSnapshot::Taken);
+        }
+
+        let kind = value.borrow_kind_ref()?;
+
+        Ok(match &*kind {
+            ValueKind::EmptyTuple => ValueSnapshot::EmptyTuple,
+            ValueKind::Bool(value) => ValueSnapshot::Bool(*value),
+            ValueKind::Byte(value) => ValueSnapshot::Byte(*value),
+            ValueKind::Char(value) => ValueSnapshot::Char(*value),
+            ValueKind::Integer(value) => ValueSnapshot::Integer(*value),
+            ValueKind::U64(value) => ValueSnapshot::U64(*value),
+            ValueKind::I128(value) => ValueSnapshot::I128(*value),
+            ValueKind::U128(value) => ValueSnapshot::U128(*value),
+            ValueKind::Float(value) => ValueSnapshot::Float(*value),
+            ValueKind::Type(ty) => ValueSnapshot::Type(ty.into_hash()),
+            ValueKind::Ordering(ordering) => ValueSnapshot::Ordering(*ordering as i8),
+            ValueKind::String(string) => ValueSnapshot::String(StdString::from(string.as_str())),
+            ValueKind::Bytes(bytes) => ValueSnapshot::Bytes(StdVec::from(bytes.as_slice())),
+            ValueKind::Vec(vec) => ValueSnapshot::Vec(self.values(vec.iter())?),
+            ValueKind::Tuple(tuple) => ValueSnapshot::Tuple(self.values(tuple.iter())?),
+            ValueKind::Object(object) => ValueSnapshot::Object(self.object(object)?),
+            ValueKind::RangeFrom(range) => ValueSnapshot::RangeFrom(self.value(&range.start)?),
+            ValueKind::RangeFull(..) => ValueSnapshot::RangeFull,
+            ValueKind::RangeInclusive(range) => {
+                ValueSnapshot::RangeInclusive(self.value(&range.start)?, self.value(&range.end)?)
+            }
+            ValueKind::RangeToInclusive(range) => {
+                ValueSnapshot::RangeToInclusive(self.value(&range.end)?)
+            }
+            ValueKind::RangeTo(range) => ValueSnapshot::RangeTo(self.value(&range.end)?),
+            ValueKind::Range(range) => {
+                ValueSnapshot::Range(self.value(&range.start)?, self.value(&range.end)?)
+            }
+            ValueKind::ControlFlow(ControlFlow::Continue(value)) => {
+                ValueSnapshot::Continue(self.value(value)?)
+            }
+            ValueKind::ControlFlow(ControlFlow::Break(value)) => {
+                ValueSnapshot::Break(self.value(value)?)
+            }
+            ValueKind::GeneratorState(GeneratorState::Yielded(value)) => {
+                ValueSnapshot::Yielded(self.value(value)?)
+            }
+            ValueKind::GeneratorState(GeneratorState::Complete(value)) => {
+                ValueSnapshot::Complete(self.value(value)?)
+            }
+            ValueKind::Option(None) => ValueSnapshot::None,
+            ValueKind::Option(Some(value)) => ValueSnapshot::Some(self.value(value)?),
+            ValueKind::Result(Ok(value)) => ValueSnapshot::Ok(self.value(value)?),
+            ValueKind::Result(Err(value)) => ValueSnapshot::Err(self.value(value)?),
+            ValueKind::EmptyStruct(empty) => ValueSnapshot::EmptyStruct(empty.rtti.hash),
+            ValueKind::TupleStruct(tuple) => {
+                ValueSnapshot::TupleStruct(tuple.rtti.hash, self.values(tuple.data.iter())?)
+            }
+            ValueKind::Struct(object) => {
+                ValueSnapshot::Struct(object.rtti.hash, self.object(&object.data)?)
+            }
+            ValueKind::Variant(variant) => match &variant.data {
+                VariantData::Empty => ValueSnapshot::EmptyVariant(variant.rtti.hash),
+                VariantData::Tuple(tuple) => {
+                    ValueSnapshot::TupleVariant(variant.rtti.hash, self.values(tuple.iter())?)
+                }
+                VariantData::Struct(object) => {
+                    ValueSnapshot::StructVariant(variant.rtti.hash, self.object(object)?)
+                }
+            },
+            kind => {
+                return Err(VmSnapshotError::from(VmSnapshotErrorKind::Unsupported {
+                    type_info: kind.type_info(),
+                }));
+            }
+        })
+    }
+}
+
+/// Converts serialized values back into the values they were taken from.
+struct Decoder<'a> {
+    unit: &'a Unit,
+    values: &'a [Value],
+}
+
+impl Decoder<'_> {
+    fn value(&self, index: usize) -> Result<Value, VmSnapshotError> {
+        match self.values.get(index) {
+            Some(value) => Ok(value.clone()),
+            None => Err(VmSnapshotError::from(VmSnapshotErrorKind::BadValue)),
+        }
+    }
+
+    fn values(&self, indexes: &[usize]) -> Result<alloc::Vec<Value>, VmSnapshotError> {
+        let mut output = alloc::Vec::try_with_capacity(indexes.len())?;
+
+        for &index in indexes {
+            output.try_push(self.value(index)?)?;
+        }
+
+        Ok(output)
+    }
+
+    fn tuple(&self, indexes: &[usize]) -> Result<OwnedTuple, VmSnapshotError> {
+        Ok(OwnedTuple::try_from(self.values(indexes)?)?)
+    }
+
+    fn object(&self, fields: &[(StdString, usize)]) -> Result<Object, VmSnapshotError> {
+        let mut object = Object::with_capacity(fields.len())?;
+
+        for (key, index) in fields {
+            object.insert(String::try_from(key.as_str())?, self.value(*index)?)?;
+        }
+
+        Ok(object)
+    }
+
+    fn kind(&self, snapshot: &ValueSnapshot) -> Result<ValueKind, VmSnapshotError> {
+        Ok(match snapshot {
+            ValueSnapshot::Taken => {
+                return Err(VmSnapshotError::from(VmSnapshotErrorKind::BadValue))
+            }
+            ValueSnapshot::EmptyTuple => ValueKind::EmptyTuple,
+            ValueSnapshot::Bool(value) => ValueKind::Bool(*value),
+            ValueSnapshot::Byte(value) => ValueKind::Byte(*value),
+            ValueSnapshot::Char(value) => ValueKind::Char(*value),
+            ValueSnapshot::Integer(value) => ValueKind::Integer(*value),
+            ValueSnapshot::U64(value) => ValueKind::U64(*value),
+            ValueSnapshot::I128(value) => ValueKind::I128(*value),
+            ValueSnapshot::U128(value) => ValueKind::U128(*value),
+            ValueSnapshot::Float(value) => ValueKind::Float(*value),
+            ValueSnapshot::Type(hash) => ValueKind::Type(Type::new(*hash)),
+            ValueSnapshot::Ordering(ordering) => ValueKind::Ordering(match ordering {
+                -1 => Ordering::Less,
+                0 => Ordering::Equal,
+                1 => Ordering::Greater,
+                _ => return Err(VmSnapshotError::from(VmSnapshotErrorKind::BadValue)),
+            }),
+            ValueSnapshot::String(string) => ValueKind::String(String::try_from(string.as_str())?),
+            ValueSnapshot::Bytes(bytes) => ValueKind::Bytes(Bytes::from_slice(bytes.as_slice())?),
+            ValueSnapshot::Vec(indexes) => ValueKind::Vec(Vec::from(self.values(indexes)?)),
+            ValueSnapshot::Tuple(indexes) => ValueKind::Tuple(self.tuple(indexes)?),
+            ValueSnapshot::Object(fields) => ValueKind::Object(self.object(fields)?),
+            ValueSnapshot::RangeFrom(start) => {
+                ValueKind::RangeFrom(RangeFrom::new(self.value(*start)?))
+            }
+            ValueSnapshot::RangeFull => ValueKind::RangeFull(RangeFull::new()),
+            ValueSnapshot::RangeInclusive(start, end) => ValueKind::RangeInclusive(
+                RangeInclusive::new(self.value(*start)?, self.value(*end)?),
+            ),
+            ValueSnapshot::RangeToInclusive(end) => {
+                ValueKind::RangeToInclusive(RangeToInclusive::new(self.value(*end)?))
+            }
+            ValueSnapshot::RangeTo(end) => ValueKind::RangeTo(RangeTo::new(self.value(*end)?)),
+            ValueSnapshot::Range(start, end) => {
+                ValueKind::Range(Range::new(self.value(*start)?, self.value(*end)?))
+            }
+            ValueSnapshot::Continue(value) => {
+                ValueKind::ControlFlow(ControlFlow::Continue(self.value(*value)?))
+            }
+            ValueSnapshot::Break(value) => {
+                ValueKind::ControlFlow(ControlFlow::Break(self.value(*value)?))
+            }
+            ValueSnapshot::Yielded(value) => {
+                ValueKind::GeneratorState(GeneratorState::Yielded(self.value(*value)?))
+            }
+            ValueSnapshot::Complete(value) => {
+                ValueKind::GeneratorState(GeneratorState::Complete(self.value(*value)?))
+            }
+            ValueSnapshot::None => ValueKind::Option(None),
+            ValueSnapshot::Some(value) => ValueKind::Option(Some(self.value(*value)?)),
+            ValueSnapshot::Ok(value) => ValueKind::Result(Ok(self.value(*value)?)),
+            ValueSnapshot::Err(value) => ValueKind::Result(Err(self.value(*value)?)),
+            ValueSnapshot::EmptyStruct(hash) => ValueKind::EmptyStruct(EmptyStruct {
+                rtti: self.rtti(*hash)?,
+            }),
+            ValueSnapshot::TupleStruct(hash, indexes) => ValueKind::TupleStruct(TupleStruct {
+                rtti: self.rtti(*hash)?,
+                data: self.tuple(indexes)?,
+            }),
+            ValueSnapshot::Struct(hash, fields) => ValueKind::Struct(Struct {
+                rtti: self.rtti(*hash)?,
+                data: self.object(fields)?,
+            }),
+            ValueSnapshot::EmptyVariant(hash) => ValueKind::Variant(Variant {
+                rtti: self.variant_rtti(*hash)?,
+                data: VariantData::Empty,
+            }),
+            ValueSnapshot::TupleVariant(hash, indexes) => ValueKind::Variant(Variant {
+                rtti: self.variant_rtti(*hash)?,
+                data: VariantData::Tuple(self.tuple(indexes)?),
+            }),
+            ValueSnapshot::StructVariant(hash, fields) => ValueKind::Variant(Variant {
+                rtti: self.variant_rtti(*hash)?,
+                data: VariantData::Struct(self.object(fields)?),
+            }),
+        })
+    }
+
+    fn rtti(&self, hash: Hash) -> Result<Arc<Rtti>, VmSnapshotError> {
+        match self.unit.lookup_rtti(hash) {
+            Some(rtti) => Ok(rtti.clone()),
+            None => Err(VmSnapshotError::from(VmSnapshotErrorKind::MissingRtti {
+                hash,
+            })),
+        }
+    }
+
+    fn variant_rtti(&self, hash: Hash) -> Result<Arc<VariantRtti>, VmSnapshotError> {
+        match self.unit.lookup_variant_rtti(hash) {
+            Some(rtti) => Ok(rtti.clone()),
+            None => Err(VmSnapshotError::from(VmSnapshotErrorKind::MissingRtti {
+                hash,
+            })),
+        }
+    }
+}
+
+/// An error raised when taking or restoring a [`VmSnapshot`].
+#[derive(Debug)]
+pub struct VmSnapshotError {
+    kind: VmSnapshotErrorKind,
+}
+
+impl From<VmSnapshotErrorKind> for VmSnapshotError {
+    #[inline]
+    fn from(kind: VmSnapshotErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl From<AccessError> for VmSnapshotError {
+    #[inline]
+    fn from(error: AccessError) -> Self {
+        Self::from(VmSnapshotErrorKind::AccessError { error })
+    }
+}
+
+impl From<alloc::Error> for VmSnapshotError {
+    #[inline]
+    fn from(error: alloc::Error) -> Self {
+        Self::from(VmSnapshotErrorKind::AllocError { error })
+    }
+}
+
+impl From<musli::storage::Error> for VmSnapshotError {
+    #[inline]
+    fn from(error: musli::storage::Error) -> Self {
+        Self::from(VmSnapshotErrorKind::StorageError { error })
+    }
+}
+
+impl From<EncodeError> for VmSnapshotError {
+    #[inline]
+    fn from(error: EncodeError) -> Self {
+        Self::from(VmSnapshotErrorKind::EncodeError { error })
+    }
+}
+
+impl fmt::Display for VmSnapshotError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            VmSnapshotErrorKind::Unsupported { type_info } => {
+                write!(f, "Value of type `{type_info}` cannot be serialized")
+            }
+            VmSnapshotErrorKind::UnitMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Snapshot was taken from unit `{expected}` but is being restored into unit `{actual}`"
+                )
+            }
+            VmSnapshotErrorKind::MissingRtti { hash } => {
+                write!(f, "Missing runtime information for type with hash `{hash}`")
+            }
+            VmSnapshotErrorKind::BadValue => write!(f, "Snapshot contains a bad value"),
+            VmSnapshotErrorKind::NestedExecution => write!(
+                f,
+                "Execution which is calling into another unit cannot be snapshotted"
+            ),
+            VmSnapshotErrorKind::NotSuspended => write!(
+                f,
+                "Execution can only be snapshotted before it starts or when it has yielded"
+            ),
+            VmSnapshotErrorKind::GeneratorComplete => {
+                write!(f, "Completed generator cannot be snapshotted")
+            }
+            VmSnapshotErrorKind::AccessError { error } => error.fmt(f),
+            VmSnapshotErrorKind::AllocError { error } => error.fmt(f),
+            VmSnapshotErrorKind::StorageError { .. } => write!(f, "Storage error"),
+            VmSnapshotErrorKind::EncodeError { error } => error.fmt(f),
+        }
+    }
+}
+
+cfg_std! {
+    impl std::error::Error for VmSnapshotError {
+        #[inline]
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match &self.kind {
+                VmSnapshotErrorKind::AccessError { error } => Some(error),
+                VmSnapshotErrorKind::AllocError { error } => Some(error),
+                VmSnapshotErrorKind::StorageError { error } => Some(error),
+                VmSnapshotErrorKind::EncodeError { error } => Some(error),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum VmSnapshotErrorKind {
+    Unsupported { type_info: TypeInfo },
+    UnitMismatch { expected: Hash, actual: Hash },
+    MissingRtti { hash: Hash },
+    BadValue,
+    NestedExecution,
+    NotSuspended,
+    GeneratorComplete,
+    AccessError { error: AccessError },
+    AllocError { error: alloc::Error },
+    StorageError { error: musli::storage::Error },
+    EncodeError { error: EncodeError },
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rust_alloc::sync::Arc;
+
+    use crate::runtime::{CallFrame, Generator, Output, RuntimeContext};
+    use crate::support::Result;
+    use crate::{Context, Unit, Vm};
+
+    use super::{VmSnapshot, VmSnapshotErrorKind};
+
+    fn snapshot() -> Result<(Arc<RuntimeContext>, Arc<Unit>, VmSnapshot)> {
+        let context = Context::with_default_modules()?;
+
+        let mut sources = crate::sources! {
+            entry => {
+                fn count(n) {
+                    let values = [n];
+                    yield values.len();
+                    values.len()
+                }
+
+                pub fn main() {
+                    count(1)
+                }
+            }
+        };
+
+        let unit = crate::prepare(&mut sources)
+            .with_context(&context)
+            .build()?;
+        let context = Arc::new(context.runtime()?);
+        let unit = Arc::new(unit);
+
+        let mut vm = Vm::new(context.clone(), unit.clone());
+        let mut generator: Generator<Vm> = crate::from_value(vm.call(["main"], ())?)?;
+        generator.next().into_result()?;
+        let snapshot = generator.snapshot()?;
+        Ok((context, unit, snapshot))
+    }
+
+    #[test]
+    fn test_tampered_snapshot() -> Result<()> {
+        let (context, unit, snapshot) = snapshot()?;
+        snapshot.restore(context.clone(), unit.clone())?;
+
+        let tampered: [fn(&mut VmSnapshot); 5] = [
+            |s| s.top = s.stack.len() + 1,
+            |s| s.ip = usize::MAX,
+            |s| {
+                s.top = 1;
+                s.stack.clear();
+            },
+            |s| {
+                s.call_frames.push(CallFrame {
+                    ip: 0,
+                    top: s.top + 1,
+                    isolated: false,
+                    out: Output::discard(),
+                })
+            },
+            |s| {
+                s.call_frames.push(CallFrame {
+                    ip: usize::MAX,
+                    top: s.top,
+                    isolated: false,
+                    out: Output::discard(),
+                })
+            },
+        ];
+
+        for tamper in tampered {
+            let mut snapshot = VmSnapshot::from_bytes(&snapshot.to_bytes()?)?;
+            tamper(&mut snapshot);
+
+            let Err(error) = snapshot.restore(context.clone(), unit.clone()) else {
+                panic!("expected tampered snapshot to be rejected");
+            };
+
+            assert!(matches!(error.kind, VmSnapshotErrorKind::BadValue));
+        }
+
+        Ok(())
+    }
+}
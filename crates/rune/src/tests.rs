@@ -404,6 +404,7 @@ macro_rules! prelude {
 }
 
 mod attribute;
+mod big_num;
 mod binary;
 mod bug_326;
 mod bug_344;
@@ -414,6 +415,7 @@ mod bug_454;
 mod bug_700;
 mod bugfixes;
 mod builtin_macros;
+mod callable;
 mod capture;
 mod char;
 mod collections;
@@ -430,10 +432,15 @@ mod compiler_visibility;
 mod compiler_warnings;
 mod continue_;
 mod core_macros;
+mod coverage;
 mod custom_macros;
+mod debugger;
 mod deprecation;
 mod derive_from_to_value;
 mod destructuring;
+#[cfg(feature = "emit-json")]
+mod diagnostic_json;
+mod diagnostic_suggestions;
 mod esoteric_impls;
 mod external_constructor;
 mod external_generic;
@@ -446,13 +453,18 @@ mod generics;
 mod getter_setter;
 mod instance;
 mod int;
+mod int128;
 mod iter;
 mod iterator;
+mod lints;
 mod loops;
 mod macros;
 mod moved;
+mod optimize;
 mod option;
+mod parse_cache;
 mod patterns;
+mod profiler;
 mod quote;
 mod range;
 mod reference_error;
@@ -460,11 +472,14 @@ mod rename_type;
 mod result;
 mod stmt_reordering;
 mod string_debug;
+mod traits;
 mod tuple;
+mod type_annotations;
 mod type_name_native;
 mod type_name_rune;
 mod unit_constants;
 mod unreachable;
+mod unsigned;
 mod variants;
 mod vm_arithmetic;
 mod vm_assign_exprs;
@@ -485,6 +500,7 @@ mod vm_not_used;
 mod vm_option;
 mod vm_pat;
 mod vm_result;
+mod vm_snapshot;
 mod vm_streams;
 mod vm_test_from_value_derive;
 mod vm_test_imports;
@@ -0,0 +1,86 @@
+prelude!();
+
+use VmErrorKind::*;
+
+#[test]
+fn test_big_int() {
+    let s: String = rune! {
+        use std::num::BigInt;
+
+        pub fn main() {
+            let n = BigInt::from_i64(i64::MAX);
+            n *= BigInt::from_i64(i64::MAX);
+            n += 1u64;
+            format!("{n}")
+        }
+    };
+    assert_eq!(s, "85070591730234615847396907784232501250");
+
+    let out: (Option<i64>, bool, bool) = rune! {
+        use std::num::BigInt;
+
+        pub fn main() {
+            let a = BigInt::parse("-9223372036854775808")?;
+            (a.to_i64(), a < 0, BigInt::parse("12x").is_err())
+        }
+    };
+    assert_eq!(out, (Some(i64::MIN), true, true));
+}
+
+#[test]
+fn test_decimal() {
+    let s: String = rune! {
+        use std::num::Decimal;
+
+        pub fn main() {
+            let total = Decimal::from_i64(0);
+
+            for price in ["0.10", "0.20", "19.99"] {
+                total += Decimal::parse(price)?;
+            }
+
+            format!("{total}")
+        }
+    };
+    assert_eq!(s, "20.29");
+
+    let s: String = rune! {
+        use std::num::Decimal;
+
+        pub fn main() {
+            let vat = Decimal::parse("100.00")? * Decimal::parse("0.25")?;
+            format!("{} {}", vat, (Decimal::from_i64(10) / 3).round(2))
+        }
+    };
+    assert_eq!(s, "25.0000 3.33");
+
+    let out: (bool, Option<i64>, f64) = rune! {
+        use std::num::Decimal;
+        use std::ops::hash;
+
+        pub fn main() {
+            let a = Decimal::parse("1.50")?;
+            let b = Decimal::from_f64(1.5).unwrap();
+            (a == b && hash(a) == hash(b), a.to_i64(), a.to_f64())
+        }
+    };
+    assert_eq!(out, (true, Some(1), 1.5));
+}
+
+#[test]
+fn test_big_num_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { std::num::BigInt::from_i64(1) / 0 }"#,
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::num::Decimal::from_i64(1) % std::num::Decimal::new(0, 2) }"#,
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::num::Decimal::from_i64(1) + 1.0 }"#,
+        UnsupportedBinaryOperation { op: "add", .. } => {}
+    );
+}
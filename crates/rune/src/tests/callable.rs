@@ -0,0 +1,216 @@
+//! Tests for values which are called through the `CALL` protocol.
+
+prelude!();
+
+use crate::runtime::{ProfileFrame, Profiler};
+
+use VmErrorKind::*;
+
+#[derive(Any, Debug)]
+#[rune(item = ::native_crate)]
+pub struct Multiplier {
+    factor: i64,
+}
+
+impl Multiplier {
+    #[rune::function(protocol = CALL)]
+    fn call(&self, value: i64) -> i64 {
+        self.factor * value
+    }
+}
+
+fn make_native_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("native_crate")?;
+    module.ty::<Multiplier>()?;
+    module.function_meta(Multiplier::call)?;
+    Ok(module)
+}
+
+#[test]
+fn test_native_call() {
+    let m = Multiplier { factor: 3 };
+
+    let (value, values) = rune_n! {
+        make_native_module().expect("failed making native module"),
+        (m,),
+        (i64, Vec<i64>) =>
+        pub fn main(m) {
+            (m(2), [1, 2, 3].iter().map(m).collect::<Vec>())
+        }
+    };
+
+    assert_eq!(value, 6);
+    assert_eq!(values, [3, 6, 9]);
+}
+
+#[test]
+fn test_script_call() {
+    let _: () = rune! {
+        struct Adder {
+            base,
+        }
+
+        impl Adder {
+            #[protocol(CALL)]
+            fn call(self, a, b) {
+                self.base + a + b
+            }
+        }
+
+        pub fn main() {
+            let adder = Adder { base: 10 };
+            assert_eq!(adder(1, 2), 13);
+
+            let values = [1, 2, 3].iter().map(|v| adder(v, v)).collect::<Vec>();
+            assert_eq!(values, [12, 14, 16]);
+
+            let sum = [(1, 2), (3, 4)].iter().map(|(a, b)| adder(a, b)).fold(0, |a, b| a + b);
+            assert_eq!(sum, 30);
+        }
+    };
+
+    let _: () = rune! {
+        struct Square;
+
+        impl Square {
+            #[protocol(CALL)]
+            fn call(self, v) {
+                v * v
+            }
+        }
+
+        pub fn main() {
+            let values = [1, 2, 3].iter().map(Square).collect::<Vec>();
+            assert_eq!(values, [1, 4, 9]);
+        }
+    };
+}
+
+#[test]
+fn test_call_errors() {
+    assert_vm_error!(
+        r#"
+        struct Adder;
+
+        impl Adder {
+            #[protocol(CALL)]
+            fn call(self, a, b) {
+                a + b
+            }
+        }
+
+        pub fn main() {
+            let adder = Adder;
+            adder(1)
+        }
+        "#,
+        BadArgumentCount { actual: 2, expected: 3 } => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        struct Empty;
+
+        pub fn main() {
+            let empty = Empty;
+            empty()
+        }
+        "#,
+        UnsupportedCallFn { .. } => {}
+    );
+}
+
+#[test]
+fn test_native_call_hooks() -> Result<()> {
+    let mut module = make_native_module()?;
+
+    module
+        .raw_function("stack_len", |stack, _, _, out| {
+            let len = stack.len() as i64;
+            out.store(stack, len)
+        })
+        .build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = crate::tests::sources(
+        r#"
+        pub fn main(m) {
+            let before = native_crate::stack_len();
+            let sum = 0;
+
+            for n in 0..10 {
+                sum += m(n);
+            }
+
+            (sum, before == native_crate::stack_len())
+        }
+        "#,
+    );
+
+    let unit = Arc::new(prepare(&mut sources).with_context(&context).build()?);
+    let profiler = Profiler::new(unit.clone())?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), unit);
+    vm.set_profiler(Some(profiler.clone()));
+
+    let output = vm.call(["main"], (Multiplier { factor: 2 },))?;
+    let (sum, balanced): (i64, bool) = from_value(output)?;
+
+    assert_eq!(sum, 90);
+    assert!(balanced, "arguments to native callables should be removed");
+
+    // Calls to native callables are visible to the profiler.
+    let hash = Hash::associated_function(<Multiplier as Any>::type_hash(), Protocol::CALL);
+
+    let calls = profiler
+        .samples()?
+        .into_iter()
+        .filter(|sample| sample.stack.last() == Some(&ProfileFrame::Native(hash)))
+        .map(|sample| sample.calls)
+        .sum::<u64>();
+
+    assert_eq!(calls, 10);
+    Ok(())
+}
+
+#[test]
+fn test_call_protocol_attribute() {
+    // Functions named `call` don't make values callable unless they are
+    // marked with the attribute.
+    assert_vm_error!(
+        r#"
+        struct Adder;
+
+        impl Adder {
+            fn call(self, a, b) {
+                a + b
+            }
+        }
+
+        pub fn main() {
+            let adder = Adder;
+            adder(1, 2)
+        }
+        "#,
+        UnsupportedCallFn { .. } => {}
+    );
+
+    let _: () = rune! {
+        struct Adder;
+
+        impl Adder {
+            #[protocol(CALL)]
+            fn add(self, a, b) {
+                a + b
+            }
+        }
+
+        pub fn main() {
+            let adder = Adder;
+            assert_eq!(adder(1, 2), 3);
+            assert_eq!(adder.add(2, 3), 5);
+        }
+    };
+}
@@ -33,3 +33,19 @@ fn test_number_literals() {
         span!(16, 82), BadNumberOutOfBounds { .. }
     };
 }
+
+#[test]
+fn test_unsigned_literals() {
+    assert_parse!(r#"pub fn main() { 18446744073709551615u64 }"#);
+    assert_parse!(r#"pub fn main() { 0xffffffffffffffffu64 }"#);
+
+    assert_errors! {
+        r#"pub fn main() { 18446744073709551616u64 }"#,
+        span!(16, 39), BadNumberOutOfBounds { .. }
+    };
+
+    assert_errors! {
+        r#"pub fn main() { -1u64 }"#,
+        span!(16, 21), BadNumberOutOfBounds { .. }
+    };
+}
@@ -0,0 +1,70 @@
+prelude!();
+
+use std::collections::HashMap;
+
+use crate::runtime::Coverage;
+
+const SOURCE: &str = r#"
+fn pick(x) {
+    if x > 0 {
+        x
+    } else {
+        0
+    }
+}
+
+async fn later(x) {
+    x + 1
+}
+
+pub async fn main() {
+    let v = [1, 2, 3].iter().map(|x| pick(x)).collect::<Vec>();
+    later(v[0]).await
+}
+"#;
+
+#[test]
+fn test_coverage() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    let source_id = sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::new(unit);
+
+    let coverage = Coverage::new(unit.clone())?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), unit);
+    vm.set_coverage(Some(coverage.clone()));
+
+    let output = block_on(vm.async_call(["main"], ()))?;
+    assert_eq!(from_value::<i64>(output)?, 2);
+
+    let source = sources.get(source_id).context("missing source")?;
+
+    // Hits by zero-based line.
+    let mut lines = HashMap::<usize, usize>::new();
+
+    for (_, inst, hits) in coverage.instructions() {
+        let (line, _) = source.pos_to_utf8_linecol(inst.span.start.into_usize());
+        let count = lines.entry(line).or_default();
+        *count = (*count).max(hits);
+    }
+
+    assert_eq!(lines.get(&2), Some(&3));
+    assert_eq!(lines.get(&5), Some(&0));
+    assert_eq!(lines.get(&10), Some(&1));
+    assert_eq!(lines.get(&14), Some(&3));
+    assert_eq!(lines.get(&15), Some(&1));
+
+    let calls = coverage
+        .functions()
+        .map(|(_, signature, hits)| Ok((signature.path.try_to_string()?, hits)))
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    assert_eq!(calls.get("pick"), Some(&3));
+    assert_eq!(calls.get("later"), Some(&1));
+    assert_eq!(calls.get("main"), Some(&1));
+    Ok(())
+}
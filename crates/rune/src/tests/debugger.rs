@@ -0,0 +1,191 @@
+prelude!();
+
+use crate::runtime::{DebugLocation, DebugStep, DebugStop, Debugger, Inst};
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    let sum = a + b;
+    sum
+}
+
+pub fn main() {
+    let a = 1;
+    let b = add(a, 2);
+    let c = add(b, 3);
+    c
+}
+"#;
+
+fn prepare_vm() -> Result<(Vm, Sources)> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    Ok((vm, sources))
+}
+
+fn line(vm: &Vm, sources: &Sources) -> Option<usize> {
+    Some(DebugLocation::at(vm, sources, vm.ip())?.line)
+}
+
+#[test]
+fn test_breakpoints_and_locals() -> Result<()> {
+    let (mut vm, sources) = prepare_vm()?;
+    let source_id = sources.source_ids().next().context("missing source")?;
+
+    let mut debugger = Debugger::new();
+    debugger.set_breakpoint(source_id, 2)?;
+    debugger.set_breakpoint(source_id, 9)?;
+
+    let mut execution = vm.execute(["main"], ())?;
+
+    let stop = execution
+        .debug(&mut debugger, &sources, DebugStep::Continue)
+        .into_result()?;
+    assert!(matches!(stop, DebugStop::Breakpoint));
+    assert_eq!(line(execution.vm(), &sources), Some(2));
+
+    let vm = execution.vm();
+    let frames = vm.debug_frames()?;
+    assert_eq!(frames.len(), 2);
+
+    let names = vm
+        .debug_locals(&frames[0])?
+        .into_iter()
+        .map(|(name, _)| name.to_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["a", "b"]);
+
+    let a = vm.debug_local(&frames[0], "a")?.context("missing a")?;
+    assert_eq!(from_value::<i64>(a)?, 1);
+
+    let a = vm.debug_local(&frames[1], "a")?.context("missing a")?;
+    assert_eq!(from_value::<i64>(a)?, 1);
+    assert!(vm.debug_local(&frames[1], "c")?.is_none());
+
+    let stop = execution
+        .debug(&mut debugger, &sources, DebugStep::Continue)
+        .into_result()?;
+    assert!(matches!(stop, DebugStop::Breakpoint));
+    assert_eq!(line(execution.vm(), &sources), Some(9));
+
+    let frames = execution.vm().debug_frames()?;
+    let b = execution.vm().debug_local(&frames[0], "b")?;
+    assert_eq!(from_value::<i64>(b.context("missing b")?)?, 3);
+
+    let stop = execution
+        .debug(&mut debugger, &sources, DebugStep::Continue)
+        .into_result()?;
+    assert!(matches!(stop, DebugStop::Breakpoint));
+    assert_eq!(line(execution.vm(), &sources), Some(2));
+
+    let stop = execution
+        .debug(&mut debugger, &sources, DebugStep::Continue)
+        .into_result()?;
+    let DebugStop::Exited(value) = stop else {
+        panic!("expected execution to exit");
+    };
+
+    assert_eq!(from_value::<i64>(value)?, 6);
+    Ok(())
+}
+
+#[test]
+fn test_stepping() -> Result<()> {
+    let (mut vm, sources) = prepare_vm()?;
+    let source_id = sources.source_ids().next().context("missing source")?;
+
+    let mut debugger = Debugger::new();
+    debugger.set_breakpoint(source_id, 8)?;
+
+    let mut execution = vm.execute(["main"], ())?;
+
+    execution
+        .debug(&mut debugger, &sources, DebugStep::Continue)
+        .into_result()?;
+    assert_eq!(line(execution.vm(), &sources), Some(8));
+
+    execution
+        .debug(&mut debugger, &sources, DebugStep::Over)
+        .into_result()?;
+    assert_eq!(line(execution.vm(), &sources), Some(9));
+    assert_eq!(execution.vm().call_frames().len(), 0);
+
+    execution
+        .debug(&mut debugger, &sources, DebugStep::Into)
+        .into_result()?;
+    assert_eq!(line(execution.vm(), &sources), Some(2));
+    assert_eq!(execution.vm().call_frames().len(), 1);
+
+    execution
+        .debug(&mut debugger, &sources, DebugStep::Into)
+        .into_result()?;
+    assert_eq!(line(execution.vm(), &sources), Some(3));
+
+    // The call is the last instruction on its line, so stepping out stops at
+    // the next line of the caller.
+    execution
+        .debug(&mut debugger, &sources, DebugStep::Out)
+        .into_result()?;
+    assert_eq!(line(execution.vm(), &sources), Some(10));
+    assert_eq!(execution.vm().call_frames().len(), 0);
+
+    let stop = execution
+        .debug(&mut debugger, &sources, DebugStep::Over)
+        .into_result()?;
+    assert!(matches!(stop, DebugStop::Exited(..)));
+    Ok(())
+}
+
+#[test]
+fn test_return_spans() -> Result<()> {
+    const SOURCE: &str = r#"fn value() {
+    let a = 1;
+    a + 1
+}
+
+fn unit() {
+    let a = 1;
+}
+
+pub fn main() {
+    value();
+    unit();
+}
+"#;
+
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let debug = unit.debug_info().context("missing debug info")?;
+
+    let mut returns = Vec::new();
+
+    for (ip, inst) in unit.iter_instructions() {
+        if !matches!(inst, Inst::Return { .. } | Inst::ReturnUnit) {
+            continue;
+        }
+
+        let inst = debug
+            .instruction_at(ip)
+            .context("missing debug instruction")?;
+        let source = sources.get(inst.source_id).context("missing source")?;
+        let (line, column) = source.pos_to_utf8_linecol(inst.span.start.into_usize());
+        let text = &SOURCE[inst.span.start.into_usize()..inst.span.end.into_usize()];
+        returns.push((line, column, text));
+    }
+
+    returns.sort();
+
+    // Returns map to the returned value, or to the point right after the
+    // closing brace of functions which return nothing.
+    assert_eq!(returns, [(2, 4, "a + 1"), (7, 1, ""), (12, 1, "")]);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+prelude!();
+
+use serde_json::Value as Json;
+
+/// Compile the given source and collect its diagnostics as parsed JSON lines.
+fn emit_json(source: &str) -> std::vec::Vec<Json> {
+    let context = crate::Context::with_default_modules().expect("setting up default modules");
+
+    let mut sources = Sources::new();
+    sources
+        .insert(Source::new("main", source).unwrap())
+        .unwrap();
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = crate::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    let mut out = std::vec::Vec::new();
+    diagnostics.emit_json(&mut out, &sources).unwrap();
+
+    let out = std::string::String::from_utf8(out).unwrap();
+    out.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_error_json() {
+    let diagnostics = emit_json("pub fn main() {\n    let a = b;\n}");
+    assert_eq!(diagnostics.len(), 1);
+
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["code"], "missing_local");
+    assert_eq!(diagnostic["message"], "No local variable `b`");
+    assert!(diagnostic["rendered"]
+        .as_str()
+        .unwrap()
+        .starts_with("error: No local variable `b`"));
+
+    let spans = diagnostic["spans"].as_array().unwrap();
+    assert_eq!(spans.len(), 1);
+
+    let span = &spans[0];
+    assert_eq!(span["file"], "main");
+    assert_eq!(span["byte_start"], 28);
+    assert_eq!(span["byte_end"], 29);
+    assert_eq!(span["line_start"], 2);
+    assert_eq!(span["line_end"], 2);
+    assert_eq!(span["column_start"], 13);
+    assert_eq!(span["column_end"], 14);
+    assert_eq!(span["is_primary"], true);
+}
+
+#[test]
+fn test_warning_json() {
+    let diagnostics = emit_json("pub fn main() {\n    return;\n    1\n}");
+    assert_eq!(diagnostics.len(), 1);
+
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["severity"], "warning");
+    assert_eq!(diagnostic["code"], "unreachable");
+    assert_eq!(diagnostic["message"], "Unreachable code");
+    assert!(diagnostic["rendered"]
+        .as_str()
+        .unwrap()
+        .starts_with("warning: Unreachable code"));
+
+    let spans = diagnostic["spans"].as_array().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_eq!(spans[0]["is_primary"], true);
+    assert_eq!(spans[0]["line_start"], 3);
+    assert_eq!(spans[0]["label"], "Unreachable code");
+
+    assert_eq!(spans[1]["is_primary"], false);
+    assert_eq!(spans[1]["line_start"], 2);
+    assert_eq!(spans[1]["label"], "This code diverges");
+}
+
+#[test]
+fn test_notes_json() {
+    let diagnostics = emit_json("pub fn main() { let a = `Hello`; }");
+
+    let diagnostic = diagnostics
+        .iter()
+        .find(|d| d["code"] == "template_without_expansions")
+        .unwrap();
+
+    assert_eq!(diagnostic["severity"], "warning");
+    assert_eq!(
+        diagnostic["message"],
+        "Using a template string without expansions, like `Hello World`"
+    );
+
+    let notes = diagnostic["notes"].as_array().unwrap();
+    assert!(notes
+        .iter()
+        .any(|n| n.as_str().unwrap().starts_with("Help: ")));
+}
@@ -0,0 +1,103 @@
+prelude!();
+
+use crate::diagnostics::{Diagnostic, Suggestion};
+
+/// Apply the first suggestion of every diagnostic produced by compiling the
+/// given source.
+fn apply_suggestions(source: &str) -> std::string::String {
+    let mut diagnostics = Diagnostics::new();
+    let _ = crate::tests::compile_helper(source, &mut diagnostics);
+
+    let mut edits = std::vec::Vec::new();
+
+    for diagnostic in diagnostics.diagnostics() {
+        let suggestions: &[Suggestion] = match diagnostic {
+            Diagnostic::Fatal(fatal) => fatal.suggestions(),
+            Diagnostic::Warning(warning) => warning.suggestions(),
+            _ => &[],
+        };
+
+        if let Some(suggestion) = suggestions.first() {
+            edits.extend(
+                suggestion
+                    .edits()
+                    .iter()
+                    .map(|e| (e.span(), e.replacement())),
+            );
+        }
+    }
+
+    edits.sort_by_key(|(span, _)| span.start);
+
+    let mut output = source.to_owned();
+
+    for (span, replacement) in edits.into_iter().rev() {
+        output.replace_range(span.range(), replacement);
+    }
+
+    output
+}
+
+#[test]
+fn test_suggest_string_literal() {
+    assert_eq!(
+        apply_suggestions(r#"pub fn main() { `Hello World` }"#),
+        r#"pub fn main() { "Hello World" }"#
+    );
+}
+
+#[test]
+fn test_suggest_remove_variant_parens() {
+    assert_eq!(
+        apply_suggestions(r#"pub fn main() { None() }"#),
+        r#"pub fn main() { None }"#
+    );
+}
+
+#[test]
+fn test_suggest_remove_unused_value() {
+    assert_eq!(
+        apply_suggestions(r#"pub fn main() { 1; 2 }"#),
+        r#"pub fn main() {  2 }"#
+    );
+}
+
+#[test]
+fn test_suggest_remove_semi_colon() {
+    assert_eq!(
+        apply_suggestions(r#"pub fn main() {} fn foo() {}; "#),
+        r#"pub fn main() {} fn foo() {} "#
+    );
+}
+
+#[test]
+fn test_no_suggestion_for_unused_function() {
+    // Unused functions are only warned about, since deleting them is rarely
+    // the fix.
+    assert_eq!(
+        apply_suggestions(r#"fn foo() {} pub fn main() {}"#),
+        r#"fn foo() {} pub fn main() {}"#
+    );
+}
+
+#[test]
+#[cfg(feature = "doc")]
+fn test_suggest_deprecation_replacement() {
+    assert_eq!(
+        apply_suggestions(r#"pub fn main() { String::from_str("a") }"#),
+        r#"pub fn main() { String::from("a") }"#
+    );
+}
+
+#[test]
+fn test_suggest_import() {
+    assert_eq!(
+        apply_suggestions("pub fn main() { HashMap::new() }"),
+        "use std::collections::HashMap;\npub fn main() { HashMap::new() }"
+    );
+
+    assert_eq!(
+        apply_suggestions("use std::iter;\n\nmod a { pub struct Bar; }\npub fn main() { Bar }"),
+        "use std::iter;\nuse crate::a::Bar;\n\nmod a { pub struct Bar; }\npub fn main() { Bar }"
+    );
+}
@@ -300,3 +300,61 @@ fn eq_struct() -> Result<()> {
     test_case!([==], PARTIAL_EQ, 2, 1, false);
     Ok(())
 }
+
+#[test]
+fn unary_ops() -> Result<()> {
+    macro_rules! test_case {
+        ([$($op:tt)*], $protocol:ident, $initial:literal, $expected:literal) => {{
+            #[derive(Debug, Default, Any)]
+            struct External {
+                value: i64,
+            }
+
+            impl External {
+                fn value(&self) -> i64 {
+                    $($op)* self.value
+                }
+            }
+
+            let mut module = Module::new();
+            module.ty::<External>()?;
+
+            module.associated_function(Protocol::$protocol, External::value)?;
+
+            let mut context = Context::with_default_modules()?;
+            context.install(module)?;
+
+            let mut sources = Sources::new();
+            sources.insert(Source::new(
+                "test",
+                format!(r#"
+                pub fn type(number) {{
+                    {op} number
+                }}
+                "#, op = stringify!($($op)*)),
+            )?)?;
+
+            let unit = prepare(&mut sources)
+                .with_context(&context)
+                .build()?;
+
+            let unit = Arc::new(unit);
+
+            let vm = Vm::new(Arc::new(context.runtime()?), unit);
+
+            {
+                let mut foo = External::default();
+                foo.value = $initial;
+
+                let output = vm.try_clone()?.call(["type"], (&mut foo,))?;
+                let a = <i64 as FromValue>::from_value(output).into_result()?;
+
+                assert_eq!(a, $expected, "{} != {} (value)", foo.value, $expected);
+            }
+        }};
+    }
+
+    test_case!([-], NEG, 2, -2);
+    test_case!([!], NOT, 0b1010, -11);
+    Ok(())
+}
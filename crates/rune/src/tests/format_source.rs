@@ -2,7 +2,7 @@
 
 prelude!();
 
-use crate::fmt::format_source;
+use crate::fmt::{format_source, format_source_with, FormatOptions};
 
 #[track_caller]
 fn assert_format_source(source: &str, expected: Option<&str>) -> Result<()> {
@@ -13,6 +13,24 @@ fn assert_format_source(source: &str, expected: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+#[track_caller]
+fn assert_format_source_with(source: &str, expected: &str, options: &FormatOptions) -> Result<()> {
+    let formated = format_source_with(source, options)?;
+    assert_eq!(formated, expected);
+
+    // Formatting should be stable.
+    let again = format_source_with(&formated, options)?;
+    assert_eq!(again, expected);
+
+    Ok(())
+}
+
+fn max_width(max_width: usize) -> FormatOptions {
+    let mut options = FormatOptions::default();
+    options.max_width(max_width);
+    options
+}
+
 /// https://github.com/rune-rs/rune/issues/684
 #[test]
 #[ignore = "formatting has changed and should be reviewed"]
@@ -199,3 +217,395 @@ pub async fn main() {
 
     assert_format_source(source, None)
 }
+
+#[test]
+fn fmt_type_annotations() -> Result<()> {
+    let source = r#"fn add(a:i64,b : i64)->i64{let c:i64=a+b;c}
+
+fn pair((a,b):(i64,String))->(i64,String){(a,b)}"#;
+    let expected = r#"fn add(a: i64, b: i64) -> i64 {
+    let c: i64 = a + b;
+    c
+}
+
+fn pair((a, b): (i64, String)) -> (i64, String) {
+    (a, b)
+}
+"#;
+
+    assert_format_source(source, Some(expected))
+}
+
+#[test]
+fn fmt_break_call_args() -> Result<()> {
+    let source =
+        r#"pub fn main() { let value = call(first_argument, second_argument, third_argument); }"#;
+    let expected = r#"pub fn main() {
+    let value = call(
+        first_argument,
+        second_argument,
+        third_argument,
+    );
+}
+"#;
+
+    assert_format_source_with(source, expected, &max_width(40))?;
+
+    let expected = r#"pub fn main() {
+    let value = call(first_argument, second_argument, third_argument);
+}
+"#;
+
+    assert_format_source_with(source, expected, &max_width(80))
+}
+
+#[test]
+fn fmt_break_fn_args() -> Result<()> {
+    let source = r#"fn function(first_argument, second_argument) -> Result { Ok(()) }"#;
+    let expected = r#"fn function(
+    first_argument,
+    second_argument,
+) -> Result {
+    Ok(())
+}
+"#;
+
+    assert_format_source_with(source, expected, &max_width(40))
+}
+
+#[test]
+fn fmt_break_object_and_vec() -> Result<()> {
+    let source = r#"pub fn main() { let object = #{ name: "a long name", values: [first, second, third] }; }"#;
+    let expected = r#"pub fn main() {
+    let object = #{
+        name: "a long name",
+        values: [first, second, third],
+    };
+}
+"#;
+
+    assert_format_source_with(source, expected, &max_width(50))?;
+
+    let expected = r#"pub fn main() {
+    let object = #{
+        name: "a long name",
+        values: [
+            first,
+            second,
+            third,
+        ],
+    };
+}
+"#;
+
+    assert_format_source_with(source, expected, &max_width(30))
+}
+
+#[test]
+fn fmt_break_method_chain() -> Result<()> {
+    let source = r#"pub fn main() { let values = items.iter().map(|x| x * 2).filter(|x| x > 10).collect::<Vec>()?; values.len() }"#;
+    let expected = r#"pub fn main() {
+    let values = items
+        .iter()
+        .map(|x| x * 2)
+        .filter(|x| x > 10)
+        .collect::<Vec>()?;
+    values.len()
+}
+"#;
+
+    assert_format_source_with(source, expected, &max_width(60))
+}
+
+#[test]
+fn fmt_break_with_comment() -> Result<()> {
+    let source = r#"pub fn main() {
+    call(1, // one
+        2);
+}
+"#;
+    let expected = r#"pub fn main() {
+    call(
+        1, // one
+        2,
+    );
+}
+"#;
+
+    assert_format_source(source, Some(expected))
+}
+
+#[test]
+fn fmt_break_nested_blocks() -> Result<()> {
+    let source = r#"pub fn main() {
+    // A comment which is long enough to not fit within the configured width.
+    outer(first, |a| { inner(second, |b| { innermost(third_argument, fourth_argument) }) })
+}"#;
+    let expected = r#"pub fn main() {
+    // A comment which is long enough to not fit within the configured width.
+    outer(first, |a| {
+        inner(second, |b| {
+            innermost(
+                third_argument,
+                fourth_argument,
+            )
+        })
+    })
+}
+"#;
+
+    assert_format_source_with(source, expected, &max_width(40))
+}
+
+#[test]
+fn fmt_indent_size() -> Result<()> {
+    let source = r#"pub fn main() { if true { 1 } else { 2 } }"#;
+    let expected = r#"pub fn main() {
+  if true {
+    1
+  } else {
+    2
+  }
+}
+"#;
+
+    let mut options = FormatOptions::default();
+    options.indent_size(2);
+    assert_format_source_with(source, expected, &options)
+}
+
+#[test]
+fn fmt_trailing_commas() -> Result<()> {
+    let source = r#"struct Point { x, y, }
+
+pub fn main() { call(first_argument, second_argument) }"#;
+    let expected = r#"struct Point {
+    x,
+    y
+}
+
+pub fn main() {
+    call(
+        first_argument,
+        second_argument
+    )
+}
+"#;
+
+    let mut options = max_width(30);
+    options.trailing_commas(false);
+    assert_format_source_with(source, expected, &options)
+}
+
+#[test]
+fn fmt_sort_imports() -> Result<()> {
+    let source = r#"use std::string;
+use std::{iter, self, collections};
+use std::any;
+
+// Separate group.
+use b::c;
+use a::b;
+"#;
+
+    let expected = r#"use std::any;
+use std::string;
+use std::{self, collections, iter};
+
+// Separate group.
+use a::b;
+use b::c;
+"#;
+
+    let mut options = FormatOptions::default();
+    options.sort_imports(true);
+    assert_format_source_with(source, expected, &options)?;
+
+    let expected = r#"use std::string;
+use std::{iter, self, collections};
+use std::any;
+
+// Separate group.
+use b::c;
+use a::b;
+"#;
+
+    assert_format_source(source, Some(expected))
+}
+
+#[test]
+fn fmt_tuples() -> Result<()> {
+    let source = r#"pub fn main() { let a = (1,); let b = (1, 2,); }"#;
+    let expected = r#"pub fn main() {
+    let a = (1,);
+    let b = (1, 2);
+}
+"#;
+
+    assert_format_source(source, Some(expected))
+}
+
+/// Format the part of `source` covering the first occurrence of `needle`,
+/// and return the resulting source.
+fn layout_span(source: &str, needle: &str) -> Result<String> {
+    let start = source.find(needle).expect("needle in source");
+    let span = ast::Span::new(start, start + needle.len());
+
+    let Some((region, formatted)) =
+        crate::fmt::layout_span(source, &FormatOptions::default(), span)?
+    else {
+        return Ok(source.to_owned());
+    };
+
+    let mut out = String::new();
+    out.push_str(&source[..region.start.into_usize()]);
+    out.push_str(core::str::from_utf8(&formatted)?);
+    out.push_str(&source[region.end.into_usize()..]);
+    Ok(out)
+}
+
+#[test]
+fn fmt_span_statements() -> Result<()> {
+    let source = r#"fn main() {
+  let a=1;
+    let b  =   2; let c=3;
+  a+b+c
+}
+
+fn other( a,b ) {  }
+"#;
+
+    let expected = r#"fn main() {
+  let a=1;
+    let b = 2;
+    let c = 3;
+  a+b+c
+}
+
+fn other( a,b ) {  }
+"#;
+
+    assert_eq!(layout_span(source, "b  =")?, expected);
+
+    let expected = r#"fn main() {
+    let a = 1;
+    let b = 2;
+    let c = 3;
+  a+b+c
+}
+
+fn other( a,b ) {  }
+"#;
+
+    assert_eq!(layout_span(source, "a=1;\n    let b")?, expected);
+    Ok(())
+}
+
+#[test]
+fn fmt_span_nested() -> Result<()> {
+    let source = r#"fn main() {
+    if true {
+        for x in y {
+              foo(  x ) ;
+        }
+    }
+}
+"#;
+
+    let expected = r#"fn main() {
+    if true {
+        for x in y {
+            foo(x);
+        }
+    }
+}
+"#;
+
+    assert_eq!(layout_span(source, ";")?, expected);
+    Ok(())
+}
+
+#[test]
+fn fmt_span_closing_brace() -> Result<()> {
+    // Typing a closing brace formats the statement it closes.
+    let source = r#"fn main() {
+    let a   = 1;
+    while a {
+      let b=a;
+         b }
+}
+"#;
+
+    let expected = r#"fn main() {
+    let a   = 1;
+    while a {
+        let b = a;
+        b
+    }
+}
+"#;
+
+    assert_eq!(layout_span(source, "b }")?, expected);
+    Ok(())
+}
+
+#[test]
+fn fmt_span_items() -> Result<()> {
+    let source = r#"// A comment.
+fn  a( ) { 1 }
+
+mod inner {
+    struct Foo { a,b }
+    impl Foo {
+        fn new( ) {   Foo { a: 1, b: 2 } } // Trailing.
+    }
+}
+"#;
+
+    let expected = r#"// A comment.
+fn a() {
+    1
+}
+
+mod inner {
+    struct Foo { a,b }
+    impl Foo {
+        fn new( ) {   Foo { a: 1, b: 2 } } // Trailing.
+    }
+}
+"#;
+
+    assert_eq!(layout_span(source, "fn  a")?, expected);
+
+    let expected = r#"// A comment.
+fn  a( ) { 1 }
+
+mod inner {
+    struct Foo { a,b }
+    impl Foo {
+        fn new() {
+            Foo { a: 1, b: 2 }
+        } // Trailing.
+    }
+}
+"#;
+
+    assert_eq!(layout_span(source, "new")?, expected);
+
+    let expected = r#"// A comment.
+fn  a( ) { 1 }
+
+mod inner {
+    struct Foo {
+        a,
+        b,
+    }
+    impl Foo {
+        fn new( ) {   Foo { a: 1, b: 2 } } // Trailing.
+    }
+}
+"#;
+
+    assert_eq!(layout_span(source, "a,b")?, expected);
+    Ok(())
+}
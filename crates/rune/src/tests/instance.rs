@@ -1,5 +1,8 @@
 prelude!();
 
+use ErrorKind::*;
+use VmErrorKind::*;
+
 #[test]
 fn test_basic_self() {
     let _: () = rune! {
@@ -42,3 +45,110 @@ fn test_chaining() {
         }
     };
 }
+
+#[test]
+fn test_unary_protocols() {
+    let _: () = rune! {
+        struct Vector {
+            x,
+            y,
+        }
+
+        impl Vector {
+            #[protocol(NEG)]
+            fn negate(self) {
+                Vector { x: -self.x, y: -self.y }
+            }
+        }
+
+        pub fn main() {
+            let v = -Vector { x: 1, y: -2 };
+            assert_eq!(v.x, -1);
+            assert_eq!(v.y, 2);
+        }
+    };
+
+    let _: () = rune! {
+        struct Mask {
+            bits,
+        }
+
+        impl Mask {
+            #[protocol(NOT)]
+            fn invert(self) {
+                Mask { bits: !self.bits }
+            }
+        }
+
+        pub fn main() {
+            let mask = !Mask { bits: 0b1010 };
+            assert_eq!(mask.bits, !0b1010);
+            assert_eq!(mask.invert().bits, 0b1010);
+        }
+    };
+}
+
+#[test]
+fn test_unary_protocol_errors() {
+    // Functions only implement a protocol when marked with the attribute.
+    assert_vm_error!(
+        r#"
+        struct Vector;
+
+        impl Vector {
+            fn neg(self) {
+                Vector
+            }
+        }
+
+        pub fn main() {
+            -Vector
+        }
+        "#,
+        UnsupportedUnaryOperation { op: "-", .. } => {}
+    );
+
+    assert_errors! {
+        r#"
+        struct Vector;
+
+        impl Vector {
+            #[protocol(NEG)]
+            fn neg(self, other) {
+                other
+            }
+        }
+        "#,
+        span, UnsupportedArgumentCount { expected: 1, actual: 2 } => {
+            assert_eq!(span, span!(94, 107));
+        }
+    };
+
+    assert_errors! {
+        r#"
+        #[protocol(NOT)]
+        fn invert(value) {
+            value
+        }
+        "#,
+        span, Custom { .. } => {
+            assert_eq!(span, span!(9, 25));
+        }
+    };
+
+    assert_errors! {
+        r#"
+        struct Vector;
+
+        impl Vector {
+            #[protocol(ADD)]
+            fn add(self) {
+                self
+            }
+        }
+        "#,
+        span, Custom { .. } => {
+            assert_eq!(span, span!(70, 73));
+        }
+    };
+}
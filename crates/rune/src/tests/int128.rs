@@ -0,0 +1,247 @@
+prelude!();
+
+use ErrorKind::*;
+use VmErrorKind::*;
+
+#[test]
+fn test_int128_fns() {
+    let n: i128 = rune! {
+        pub fn main() {
+            i128::MAX.wrapping_add(2i128)
+        }
+    };
+    assert_eq!(n, i128::MIN + 1);
+
+    let n: Option<u128> = rune! {
+        pub fn main() {
+            0u128.checked_sub(1u128)
+        }
+    };
+    assert_eq!(n, None);
+
+    let n: u128 = rune! {
+        pub fn main() {
+            u128::parse("340282366920938463463374607431768211455")?
+        }
+    };
+    assert_eq!(n, u128::MAX);
+
+    let n: i128 = rune! {
+        pub fn main() {
+            std::i128::min(-1i128, 2i128)
+        }
+    };
+    assert_eq!(n, -1);
+}
+
+#[test]
+fn test_int128_as() {
+    let n: i128 = rune! {
+        pub fn main() {
+            -1 as i128
+        }
+    };
+    assert_eq!(n, -1);
+
+    let n: u128 = rune! {
+        pub fn main() {
+            u64::MAX as u128 + 1u128
+        }
+    };
+    assert_eq!(n, u64::MAX as u128 + 1);
+
+    let n: i64 = rune! {
+        pub fn main() {
+            (1i128 << 64) as i64
+        }
+    };
+    assert_eq!(n, 0);
+
+    let n: u128 = rune! {
+        pub fn main() {
+            -1i128 as u128
+        }
+    };
+    assert_eq!(n, u128::MAX);
+}
+
+#[test]
+fn test_int128_values() {
+    let out: String = rune! {
+        pub fn main() {
+            let n = 340282366920938463463374607431768211455u128;
+            let m = -170141183460469231731687303715884105728i128;
+            format!("{n} {n:x} {m:?} {}", 10i128 == 10i128)
+        }
+    };
+    assert_eq!(
+        out,
+        "340282366920938463463374607431768211455 ffffffffffffffffffffffffffffffff -170141183460469231731687303715884105728 true"
+    );
+
+    let out: bool = rune! {
+        pub fn main() {
+            match 2u128 {
+                1u128 => false,
+                2u128 => true,
+                _ => false,
+            }
+        }
+    };
+    assert!(out);
+
+    let out: u128 = rune! {
+        const N = 1u128 << 127;
+
+        pub fn main() {
+            N - 1u128 + N
+        }
+    };
+    assert_eq!(out, u128::MAX);
+
+    let out: i128 = rune! {
+        pub fn main() {
+            let sum = 0i128;
+
+            for n in -2i128..=4i128 {
+                sum += n;
+            }
+
+            sum
+        }
+    };
+    assert_eq!(out, 7);
+
+    let out: bool = rune! {
+        pub fn main() {
+            1i128 < 2i128 && 1i128 is i128 && 1u128 is u128 && !(1 is i128)
+        }
+    };
+    assert!(out);
+}
+
+#[test]
+fn test_int128_mixed() {
+    let out: (i128, u128, bool, bool) = rune! {
+        pub fn main() {
+            let n = 1u128;
+            n *= 2u64;
+            ((1i128 + 1) * -1, n, (1u64 + 1i128) is i128, 2u128 == 2i128 && -1 < 1u128)
+        }
+    };
+    assert_eq!(out, (-2, 2, true, true));
+
+    let out: (i128, i128, u128, u128) = rune! {
+        pub fn main() {
+            (-9 / 2i128, -9i128 % 4u64, 9u64 / 2u128, 9 % 4u128)
+        }
+    };
+    assert_eq!(out, (-4, -1, 4, 1));
+}
+
+#[test]
+fn test_int128_host_boundary() -> Result<()> {
+    let mut module = Module::new();
+    module.function("small", || 41i128).build()?;
+    module.function("wide", || i64::MAX as i128 + 1).build()?;
+    module.function("large", || u128::MAX).build()?;
+    module.function("id", |value: u128| value).build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    // Native 128-bit values keep their type regardless of their size.
+    let out: (bool, bool, bool, i128) = run(
+        &context,
+        r#"
+        pub fn main() {
+            (
+                small() is i128 && wide() is i128,
+                large() is u128,
+                small() + 1i128 == 42i128,
+                wide() - 1i128,
+            )
+        }
+        "#,
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (true, true, true, i64::MAX as i128));
+
+    let out: (u128, u128) = run(
+        &context,
+        "pub fn main() { (large(), id(40) + 2u128) }",
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (u128::MAX, 42));
+
+    // Integers mixed with native 128-bit values are widened.
+    let out: (i128, bool) = run(
+        &context,
+        "pub fn main() { (small() + 1, (small() + 1) is i128) }",
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (42, true));
+
+    let value = rune::to_value(41i128)?;
+    assert_eq!(value.as_i128()?, 41);
+    assert_eq!(rune::from_value::<i32>(value)?, 41);
+
+    let value = rune::to_value(i128::MIN)?;
+    assert_eq!(rune::from_value::<i128>(value.clone())?, i128::MIN);
+    assert!(rune::from_value::<i64>(value).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_int128_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { -1i128 + 1u128 }"#,
+        IntegerWideningError { op: "add", .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { -4i128 / 2u128 }"#,
+        IntegerWideningError { op: "div", .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { -4 % 2u128 }"#,
+        IntegerWideningError { op: "rem", .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = i128::MIN; -n }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = i64::MIN; -n }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { 1i128 - 2u128 }"#,
+        Underflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = u128::MAX; n + 1u128 }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { -(1u128) }"#,
+        UnsupportedUnaryOperation { op: "-", .. } => {}
+    );
+
+    assert_errors! {
+        r#"pub fn main() { 340282366920938463463374607431768211456u128 }"#,
+        span!(16, 59), BadNumberOutOfBounds { .. }
+    };
+}
@@ -0,0 +1,200 @@
+prelude!();
+
+use crate::ast::Spanned;
+use crate::Options;
+use diagnostics::{Diagnostic, Lint, LintLevel, WarningDiagnostic};
+use ErrorKind::UnknownLint;
+
+/// Compile the given source with the given options, returning the build error
+/// if any and all warnings which were reported.
+fn lints(source: &str, options: &Options) -> (bool, std::vec::Vec<WarningDiagnostic>) {
+    let context = crate::Context::with_default_modules().expect("setting up default modules");
+
+    let mut sources = Sources::new();
+    sources
+        .insert(Source::new("main", source).unwrap())
+        .unwrap();
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = crate::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(options)
+        .build();
+
+    let warnings = diagnostics
+        .into_diagnostics()
+        .into_iter()
+        .filter_map(|d| match d {
+            Diagnostic::Warning(warning) => Some(warning),
+            _ => None,
+        })
+        .collect();
+
+    (result.is_err(), warnings)
+}
+
+/// Collect the lints and levels reported when compiling the given source.
+fn reported(source: &str) -> std::vec::Vec<(Lint, LintLevel)> {
+    let (_, warnings) = lints(source, &Options::default());
+    warnings.iter().map(|w| (w.lint(), w.level())).collect()
+}
+
+#[test]
+fn test_default_levels() {
+    assert_eq!(Lint::NeedlessReturn.default_level(), LintLevel::Warn);
+    assert_eq!(Lint::ShadowedBindings.default_level(), LintLevel::Allow);
+
+    for &lint in Lint::ALL {
+        assert_eq!(Lint::from_name(lint.name()), Some(lint));
+    }
+}
+
+#[test]
+fn test_needless_return() {
+    assert_eq!(
+        reported("pub fn main() { return 1; }"),
+        [(Lint::NeedlessReturn, LintLevel::Warn)]
+    );
+
+    assert_eq!(
+        reported("pub fn main() { return 1 }"),
+        [(Lint::NeedlessReturn, LintLevel::Warn)]
+    );
+
+    assert_eq!(reported("pub fn main(a) { if a { return 1; } 2 }"), []);
+}
+
+#[test]
+fn test_unused_result() {
+    assert_eq!(
+        reported("fn f() -> Result { Ok(1) } pub fn main() { f(); }"),
+        [(Lint::UnusedResult, LintLevel::Warn)]
+    );
+
+    assert_eq!(
+        reported("fn f() -> Result { Ok(1) } pub fn main() { let _ = f(); }"),
+        []
+    );
+}
+
+#[test]
+fn test_unused_imports_and_functions() {
+    assert_eq!(
+        reported("use std::iter::range; pub fn main() {}"),
+        [(Lint::UnusedImports, LintLevel::Warn)]
+    );
+
+    assert_eq!(
+        reported("fn unused() {} pub fn main() {}"),
+        [(Lint::UnusedFunctions, LintLevel::Warn)]
+    );
+}
+
+#[test]
+fn test_shadowed_bindings() {
+    let source = "pub fn main() { let a = 1; let a = a + 1; let _b = 1; let _b = 2; a }";
+
+    assert_eq!(reported(source), []);
+
+    let mut options = Options::default();
+    options.lint(Lint::ShadowedBindings, LintLevel::Warn);
+    let (error, warnings) = lints(source, &options);
+
+    assert!(!error);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].lint(), Lint::ShadowedBindings);
+    assert_eq!(warnings[0].span(), span!(31, 32));
+}
+
+#[test]
+fn test_options_levels() {
+    let source = "pub fn main() { return 1; }";
+
+    let mut options = Options::default();
+    options.lint(Lint::NeedlessReturn, LintLevel::Allow);
+    let (error, warnings) = lints(source, &options);
+    assert!(!error);
+    assert!(warnings.is_empty());
+
+    let mut options = Options::default();
+    options.lint(Lint::NeedlessReturn, LintLevel::Deny);
+    let (error, warnings) = lints(source, &options);
+    assert!(error);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].level(), LintLevel::Deny);
+}
+
+#[test]
+fn test_item_attributes() {
+    assert_eq!(
+        reported("#[allow(needless_return)] pub fn main() { return 1; }"),
+        []
+    );
+
+    assert_eq!(
+        reported("#[deny(needless_return)] pub fn main() { return 1; }"),
+        [(Lint::NeedlessReturn, LintLevel::Deny)]
+    );
+
+    assert_eq!(
+        reported("#[warn(shadowed_bindings)] pub fn main() { let a = 1; let a = 2; a }"),
+        [(Lint::ShadowedBindings, LintLevel::Warn)]
+    );
+
+    assert_eq!(
+        reported("#[allow(unused_functions)] fn unused() {} pub fn main() {}"),
+        []
+    );
+
+    assert_eq!(
+        reported("#[allow(unused_imports)] use std::iter::range; pub fn main() {}"),
+        []
+    );
+}
+
+#[test]
+fn test_nested_attributes() {
+    let source = r#"
+    #[deny(needless_return)]
+    mod inner {
+        #[allow(needless_return)]
+        pub fn a() { return 1; }
+        pub fn b() { return 2; }
+    }
+
+    pub fn main() { inner::a() + inner::b() }
+    "#;
+
+    assert_eq!(reported(source), [(Lint::NeedlessReturn, LintLevel::Deny)]);
+}
+
+#[test]
+fn test_file_attributes() {
+    let (error, warnings) = lints(
+        "#![deny(needless_return)]\npub fn main() { return 1; }",
+        &Options::default(),
+    );
+
+    assert!(error);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].level(), LintLevel::Deny);
+
+    assert_eq!(
+        reported(
+            "#![allow(needless_return, unused_functions)]\nfn a() { return 1; } pub fn main() {}"
+        ),
+        []
+    );
+}
+
+#[test]
+fn test_unknown_lint() {
+    assert_errors! {
+        "#[allow(not_a_lint)] pub fn main() {}",
+        span!(8, 18), UnknownLint { name } => {
+            assert_eq!(&*name, "not_a_lint");
+        }
+    };
+}
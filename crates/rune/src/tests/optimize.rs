@@ -0,0 +1,213 @@
+prelude!();
+
+use crate::runtime::{Inst, InstOp, UnitStorage};
+use crate::Unit;
+
+fn build(source: &str, level: u8) -> Result<Unit> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+
+    let mut options = crate::Options::default();
+    options.opt_level(level);
+
+    Ok(prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?)
+}
+
+/// Run the `main` function of the source at every optimization level and make
+/// sure that they all produce the same result.
+fn run_all(source: &str) -> Result<String> {
+    let context = Arc::new(Context::with_default_modules()?.runtime()?);
+    let mut results = Vec::new();
+
+    for level in 0..=2 {
+        let unit = build(source, level)?;
+        let mut vm = Vm::new(context.clone(), Arc::new(unit));
+
+        let result = match vm.call(["main"], ()) {
+            Ok(value) => format!("{value:?}"),
+            Err(error) => format!("error: {}", error.into_kind()),
+        };
+
+        results.push(result);
+    }
+
+    assert!(
+        results.iter().all(|r| *r == results[0]),
+        "results differ between optimization levels: {results:?}"
+    );
+
+    Ok(results.remove(0))
+}
+
+fn instructions(unit: &Unit) -> Vec<Inst> {
+    unit.instructions().iter().map(|(_, inst)| inst).collect()
+}
+
+#[test]
+fn test_same_results() -> Result<()> {
+    let result = run_all(
+        r#"
+        fn classify(n) {
+            match n {
+                0 => "zero",
+                1 => "one",
+                _ => "many",
+            }
+        }
+
+        pub fn main() {
+            let a = 1 + 2 * 3;
+            let total = 0;
+            let names = [];
+            let i = 0;
+
+            while i < 10 {
+                let name = classify(i % 3);
+
+                if name == "one" {
+                    total += a;
+                }
+
+                names.push(name);
+                i += 1;
+            }
+
+            (total, names, !true, -(4 - 6), 1 << 4 == 16)
+        }
+        "#,
+    )?;
+
+    assert!(result.starts_with("(21, ["), "{result}");
+
+    let result = run_all(
+        r#"
+        pub fn main() {
+            let out = 0;
+
+            for n in 0..20 {
+                if n % 2 == 0 {
+                    continue;
+                }
+
+                out = match n {
+                    1 => out + 1,
+                    3 => out + 10,
+                    5 => out + 10,
+                    n if n > 15 => break,
+                    _ => out,
+                };
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    assert!(result.contains("21"), "{result}");
+    Ok(())
+}
+
+#[test]
+fn test_runtime_errors_preserved() -> Result<()> {
+    let result = run_all(
+        r#"
+        pub fn main() {
+            let a = 9223372036854775807;
+            a + 1
+        }
+        "#,
+    )?;
+
+    assert!(result.starts_with("error:"), "{result}");
+
+    let result = run_all(
+        r#"
+        pub fn main() {
+            let a = 0;
+            10 / a
+        }
+        "#,
+    )?;
+
+    assert!(result.starts_with("error:"), "{result}");
+    Ok(())
+}
+
+#[test]
+fn test_optimized_instructions() -> Result<()> {
+    let source = r#"
+        pub fn main(n) {
+            let a = 1 + 2 * 3;
+
+            let b = match n {
+                0 => a,
+                1 => a * 2,
+                _ => 0,
+            };
+
+            if true {
+                b
+            } else {
+                a
+            }
+        }
+    "#;
+
+    let unoptimized = instructions(&build(source, 0)?);
+    let optimized = instructions(&build(source, 2)?);
+
+    assert!(optimized.len() < unoptimized.len());
+
+    // The constant expression and the constant condition have been folded.
+    let ops = |insts: &[Inst]| {
+        insts
+            .iter()
+            .filter(|inst| {
+                matches!(
+                    inst,
+                    Inst::Op {
+                        op: InstOp::Add | InstOp::Mul,
+                        ..
+                    }
+                )
+            })
+            .count()
+    };
+
+    let jumps = |insts: &[Inst]| {
+        insts
+            .iter()
+            .filter(|inst| matches!(inst, Inst::JumpIf { .. } | Inst::JumpIfNot { .. }))
+            .count()
+    };
+
+    assert!(ops(&unoptimized) > ops(&optimized));
+    assert!(jumps(&unoptimized) > jumps(&optimized));
+
+    // Integer patterns followed by a conditional jump are matched with a
+    // single instruction.
+    assert!(optimized
+        .iter()
+        .any(|inst| matches!(inst, Inst::JumpIfNotEqInteger { .. })));
+
+    // Level one only performs local optimizations.
+    let local = instructions(&build(source, 1)?);
+    assert!(!local
+        .iter()
+        .any(|inst| matches!(inst, Inst::JumpIfNotEqInteger { .. })));
+    Ok(())
+}
+
+#[test]
+fn test_parse_option() {
+    let mut options = crate::Options::default();
+    assert!(options.parse_option("opt-level=2").is_ok());
+    assert_eq!(options.opt_level, 2);
+    assert!(options.parse_option("opt-level").is_err());
+    assert!(options.parse_option("opt-level=fast").is_err());
+}
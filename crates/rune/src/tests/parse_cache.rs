@@ -0,0 +1,40 @@
+prelude!();
+
+use crate::compile::ParseCache;
+
+/// Build the given sources with the given cache and call `main`.
+fn run_cached(cache: &mut ParseCache, main: &str, util: &str) -> Result<i64> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", main)?)?;
+    sources.insert(Source::new("util", util)?)?;
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_parse_cache(cache)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    Ok(crate::from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn test_parse_cache() -> Result<()> {
+    let mut cache = ParseCache::new();
+
+    let value = run_cached(&mut cache, "pub fn main() { 1 }", "pub fn util() { 2 }")?;
+    assert_eq!(value, 1);
+    assert_eq!(cache.len(), 2);
+
+    // Changed sources are parsed again, and files which are no longer used
+    // are dropped.
+    let value = run_cached(&mut cache, "pub fn main() { 3 }", "pub fn util() { 2 }")?;
+    assert_eq!(value, 3);
+    assert_eq!(cache.len(), 2);
+
+    let value = run_cached(&mut cache, "pub fn main() { 3 }", "pub fn util() { 2 }")?;
+    assert_eq!(value, 3);
+    assert_eq!(cache.len(), 2);
+    Ok(())
+}
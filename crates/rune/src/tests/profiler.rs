@@ -0,0 +1,74 @@
+prelude!();
+
+use crate::runtime::{ProfileFrame, Profiler};
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    a + b
+}
+
+async fn later(x) {
+    add(x, 1)
+}
+
+pub async fn main() {
+    let v = [1, 2].iter().map(|x| add(x, 2)).collect::<Vec>();
+    later(v[0]).await
+}
+"#;
+
+#[test]
+fn test_profiler() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::new(unit);
+
+    let profiler = Profiler::new(unit.clone())?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), unit.clone());
+    vm.set_profiler(Some(profiler.clone()));
+
+    let output = block_on(vm.async_call(["main"], ()))?;
+    assert_eq!(from_value::<i64>(output)?, 4);
+
+    let debug = unit.debug_info().context("missing debug info")?;
+
+    let mut stacks = Vec::new();
+
+    for sample in profiler.samples()? {
+        let mut stack = Vec::new();
+
+        for frame in &sample.stack {
+            let name = match *frame {
+                ProfileFrame::Function(entry) => {
+                    let (_, signature) = debug.function_at(entry).context("missing function")?;
+                    signature.path.to_string()
+                }
+                ProfileFrame::Native(..) => String::from("native"),
+            };
+
+            stack.push(name);
+        }
+
+        assert!(sample.calls > 0);
+        stacks.push((stack.join(";"), sample.calls, sample.instructions));
+    }
+
+    let calls = |stack: &str| {
+        stacks
+            .iter()
+            .find(|(s, ..)| s == stack)
+            .map(|&(_, calls, instructions)| (calls, instructions > 0))
+    };
+
+    assert_eq!(calls("main"), Some((1, true)));
+    assert_eq!(calls("main;native;main::$0::$0"), Some((2, true)));
+    assert_eq!(calls("main;native;main::$0::$0;add"), Some((2, true)));
+    assert_eq!(calls("main;later"), Some((1, true)));
+    assert_eq!(calls("main;later;add"), Some((1, true)));
+    Ok(())
+}
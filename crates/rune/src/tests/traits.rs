@@ -0,0 +1,352 @@
+prelude!();
+
+use ErrorKind::*;
+use VmErrorKind::*;
+
+#[test]
+fn test_trait_default_functions() {
+    let out: (i64, i64) = rune! {
+        trait Shape {
+            fn area(self);
+
+            fn double_area(self) {
+                self.area() * 2
+            }
+        }
+
+        struct Square { side }
+
+        impl Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        struct Rect { w, h }
+
+        impl Shape for Rect {
+            fn area(self) {
+                self.w * self.h
+            }
+
+            fn double_area(self) {
+                0
+            }
+        }
+
+        pub fn main() {
+            (Square { side: 3 }.double_area(), Rect { w: 2, h: 3 }.double_area())
+        }
+    };
+    assert_eq!(out, (18, 0));
+}
+
+#[test]
+fn test_trait_is_impl() {
+    let out: (bool, bool, bool, bool) = rune! {
+        trait Named {
+            fn name(self);
+        }
+
+        struct Foo;
+        struct Bar;
+
+        impl Named for Foo {
+            fn name(self) {
+                "foo"
+            }
+        }
+
+        pub fn main() {
+            (Foo is impl Named, Bar is impl Named, Bar is not impl Named, 42 is impl Named)
+        }
+    };
+    assert_eq!(out, (true, false, true, false));
+}
+
+#[test]
+fn test_trait_path_call() {
+    let out: String = rune! {
+        mod shapes {
+            pub trait Describe {
+                fn describe(self, prefix);
+            }
+        }
+
+        struct Foo { name }
+
+        impl shapes::Describe for Foo {
+            fn describe(self, prefix) {
+                format!("{prefix}{}", self.name)
+            }
+        }
+
+        pub fn main() {
+            shapes::Describe::describe(Foo { name: "foo" }, "> ")
+        }
+    };
+    assert_eq!(out, "> foo");
+}
+
+#[test]
+fn test_trait_qualified_calls() {
+    let out: (String, String, String, String) = rune! {
+        trait First {
+            fn name(self);
+        }
+
+        trait Second {
+            fn name(self);
+        }
+
+        struct Foo;
+
+        impl Foo {
+            fn name(self) {
+                "inherent"
+            }
+        }
+
+        impl First for Foo {
+            fn name(self) {
+                "first"
+            }
+        }
+
+        impl Second for Foo {
+            fn name(self) {
+                "second"
+            }
+        }
+
+        struct Bar;
+
+        impl First for Bar {
+            fn name(self) {
+                "bar"
+            }
+        }
+
+        pub fn main() {
+            (First::name(Foo), Second::name(Foo), Foo.name(), Bar.name())
+        }
+    };
+    assert_eq!(
+        out,
+        (
+            String::from("first"),
+            String::from("second"),
+            String::from("inherent"),
+            String::from("bar")
+        )
+    );
+
+    // A function with the same name as a trait function isn't called
+    // through the trait unless the trait is implemented.
+    assert_vm_error!(
+        r#"
+        trait First {
+            fn name(self);
+        }
+
+        struct Foo;
+
+        impl Foo {
+            fn name(self) {
+                "inherent"
+            }
+        }
+
+        pub fn main() {
+            First::name(Foo)
+        }
+        "#,
+        MissingTraitImpl { .. } => {}
+    );
+
+    // Two traits providing a function with the same name are ambiguous
+    // when called as an instance function.
+    assert_vm_error!(
+        r#"
+        trait First {
+            fn name(self);
+        }
+
+        trait Second {
+            fn name(self);
+        }
+
+        struct Foo;
+
+        impl First for Foo {
+            fn name(self) {
+                "first"
+            }
+        }
+
+        impl Second for Foo {
+            fn name(self) {
+                "second"
+            }
+        }
+
+        pub fn main() {
+            Foo.name()
+        }
+        "#,
+        MissingInstanceFunction { .. } => {}
+    );
+}
+
+#[test]
+fn test_trait_associated_functions() {
+    let out: (i64, i64, i64) = rune! {
+        trait Make {
+            fn new(value);
+
+            fn zero() {
+                0
+            }
+        }
+
+        struct A { value }
+
+        impl Make for A {
+            fn new(value) {
+                A { value }
+            }
+        }
+
+        struct B { value }
+
+        impl B {
+            fn new(value) {
+                B { value: value + 1 }
+            }
+        }
+
+        impl Make for B {
+            fn new(value) {
+                B { value }
+            }
+        }
+
+        pub fn main() {
+            (A::new(42).value + A::zero(), B::new(1).value, B::zero())
+        }
+    };
+    assert_eq!(out, (42, 2, 0));
+
+    // Functions provided by more than one trait must be called through the
+    // trait.
+    assert_errors! {
+        r#"
+        trait First { fn new(); }
+        trait Second { fn new(); }
+        struct A;
+        impl First for A { fn new() { 1 } }
+        impl Second for A { fn new() { 2 } }
+        pub fn main() { A::new() }
+        "#,
+        span, MissingItem { .. } => {
+            assert_eq!(span, span!(201, 207));
+        }
+    };
+}
+
+#[test]
+fn test_module_trait() -> Result<()> {
+    let mut module = Module::with_crate("host")?;
+    module.define_trait("Plugin")?.function("run")?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let out: (i64, bool) = run(
+        &context,
+        r#"
+        struct Doubler;
+
+        impl host::Plugin for Doubler {
+            fn run(self, value) {
+                value * 2
+            }
+        }
+
+        pub fn main() {
+            let plugin = Doubler;
+            (host::Plugin::run(plugin, 21), plugin is impl host::Plugin)
+        }
+        "#,
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (42, true));
+
+    let mut module = Module::with_crate("host")?;
+    module.define_trait("Plugin")?.function("run")?;
+    assert!(module.define_trait("Plugin").is_err());
+    assert!(module.function("Plugin", || ()).build().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_trait_errors() {
+    assert_errors! {
+        r#"
+        trait Foo { fn a(self); }
+        struct Bar;
+        impl Foo for Bar {}
+        "#,
+        span, MissingTraitFunction { name, .. } => {
+            assert_eq!(&*name, "a");
+            assert_eq!(span, span!(68, 71));
+        }
+    };
+
+    assert_errors! {
+        r#"
+        trait Foo { fn a(self); }
+        struct Bar;
+        impl Foo for Bar { fn a(self) {} fn b(self) {} }
+        "#,
+        span, UnknownTraitFunction { name, .. } => {
+            assert_eq!(&*name, "b");
+            assert_eq!(span, span!(96, 106));
+        }
+    };
+
+    assert_errors! {
+        r#"
+        trait Foo {}
+        struct Bar;
+        impl Foo for Bar {}
+        impl Foo for Bar {}
+        "#,
+        span, ConflictingTraitImpl { .. } => {
+            assert_eq!(span, span!(83, 86));
+        }
+    };
+
+    assert_errors! {
+        r#"
+        struct Foo;
+        struct Bar;
+        impl Foo for Bar {}
+        "#,
+        span, ExpectedMeta { .. } => {
+            assert_eq!(span, span!(54, 57));
+        }
+    };
+
+    assert_errors! {
+        r#"
+        trait Foo {}
+        pub fn main() { 1 is Foo }
+        "#,
+        span, ExpectedMeta { .. } => {
+            assert_eq!(span, span!(51, 54));
+        }
+    };
+}
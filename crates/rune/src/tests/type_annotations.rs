@@ -0,0 +1,301 @@
+prelude!();
+
+use ErrorKind::*;
+use VmErrorKind::*;
+
+/// Compile the given source with the static type checker enabled, returning
+/// the compile errors which were reported.
+fn type_check(source: &str) -> Result<Vec<compile::ErrorKind>> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+
+    let mut options = crate::Options::default();
+    options.type_check(true);
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(&options)
+        .build();
+
+    let mut errors = Vec::new();
+
+    for diagnostic in diagnostics.into_diagnostics() {
+        let diagnostics::Diagnostic::Fatal(e) = diagnostic else {
+            continue;
+        };
+
+        if let diagnostics::FatalDiagnosticKind::CompileError(e) = e.into_kind() {
+            errors.push(e.into_kind());
+        }
+    }
+
+    Ok(errors)
+}
+
+/// Run the given source with runtime type checks enabled, returning the kind
+/// of the error raised by the virtual machine if any.
+fn run_checked(source: &str) -> Result<Option<VmErrorKind>> {
+    let mut options = crate::Options::default();
+    options.runtime_type_checks(true);
+    run_with(&options, source)
+}
+
+/// Run the given source with the given options, returning the kind of the
+/// error raised by the virtual machine if any.
+fn run_with(options: &crate::Options, source: &str) -> Result<Option<VmErrorKind>> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(options)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+
+    match vm.call(["main"], ()) {
+        Ok(..) => Ok(None),
+        Err(error) => Ok(Some(error.into_kind())),
+    }
+}
+
+#[test]
+fn test_annotated_functions() {
+    let out: i64 = rune! {
+        fn add(a: i64, b: i64) -> i64 {
+            a + b
+        }
+
+        fn sum((a, b): (i64, i64)) -> i64 {
+            a + b
+        }
+
+        pub fn main() {
+            let x: i64 = add(1, 2);
+            let y: i64 = sum((3, 4));
+            x + y
+        }
+    };
+    assert_eq!(out, 10);
+
+    let out: String = rune! {
+        struct Foo { name }
+
+        impl Foo {
+            fn new(name: String) -> Self {
+                Foo { name }
+            }
+
+            fn name(self) -> String {
+                return self.name;
+            }
+        }
+
+        pub fn main() {
+            let foo: Foo = Foo::new("bar");
+            foo.name()
+        }
+    };
+    assert_eq!(out, "bar");
+}
+
+#[test]
+fn test_annotation_runtime_checks() -> Result<()> {
+    let error = run_checked(
+        r#"
+        fn add(a: i64, b: i64) -> i64 { a + b }
+        pub fn main() { add(1.0, 2.0) }
+        "#,
+    )?;
+
+    assert!(matches!(
+        error,
+        Some(TypeAnnotationMismatch { expected, actual })
+            if expected.as_str() == "i64"
+                && actual == TypeInfo::StaticType(runtime::static_type::FLOAT_TYPE)
+    ));
+
+    let error = run_checked(
+        r#"
+        fn name() -> String { 42 }
+        pub fn main() { name() }
+        "#,
+    )?;
+
+    assert!(matches!(
+        error,
+        Some(TypeAnnotationMismatch { expected, .. }) if expected.as_str() == "String"
+    ));
+
+    let error = run_checked(
+        r#"
+        fn name(n) -> String { if n { return 42; } "foo" }
+        pub fn main() { name(true) }
+        "#,
+    )?;
+
+    assert!(matches!(
+        error,
+        Some(TypeAnnotationMismatch { expected, .. }) if expected.as_str() == "String"
+    ));
+
+    // Wider integer annotations accept both their own kind and integers.
+    let error = run_checked(
+        r#"
+        fn wide(a: u64, b: i128, c: u128) -> u64 { a }
+        pub fn main() {
+            let a: u64 = wide(1, 2, 3);
+            let b: u64 = wide(1u64, 2i128, 3u128);
+            a as u64 + b
+        }
+        "#,
+    )?;
+
+    assert!(error.is_none(), "{error:?}");
+
+    let error = run_checked(
+        r#"
+        fn wide(a: u64) -> u64 { a }
+        pub fn main() { wide(1.0) }
+        "#,
+    )?;
+
+    assert!(matches!(error, Some(TypeAnnotationMismatch { .. })));
+    Ok(())
+}
+
+#[test]
+fn test_annotation_runtime_checks_default() -> Result<()> {
+    let source = r#"
+        fn add(a: i64) -> String { a + "x" }
+        pub fn main() { add("x") }
+    "#;
+
+    // Annotations aren't checked at runtime by default, regardless of how the
+    // host is built.
+    let error = run_with(&crate::Options::default(), source)?;
+    assert!(error.is_none(), "{error:?}");
+
+    let mut options = crate::Options::default();
+    options.parse_option("runtime-type-checks=true")?;
+    let error = run_with(&options, source)?;
+
+    assert!(matches!(
+        error,
+        Some(TypeAnnotationMismatch { expected, .. }) if expected.as_str() == "i64"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_type_check() -> Result<()> {
+    let errors = type_check(
+        r#"
+        struct Foo { a }
+
+        fn add(a: i64, b: i64) -> i64 {
+            a + b
+        }
+
+        pub fn main() {
+            let foo = Foo { a: add(1, 2) };
+            let n: i64 = foo.a;
+            let s: String = n.to_string();
+            let m = std::u64::max(1, 2u64);
+            (foo, n, s, m)
+        }
+        "#,
+    )?;
+
+    assert!(errors.is_empty(), "{errors:?}");
+
+    let errors = type_check(
+        r#"
+        struct Foo { a }
+
+        fn add(a: i64, b: i64) -> i64 {
+            a + b
+        }
+
+        fn name() -> String {
+            1.0
+        }
+
+        pub fn main() {
+            let a: String = add(1, 2);
+            let b = add(1);
+            let c = add(1, "two");
+            let d = std::u64::max(1u64);
+            let e = std::u64::max(1u64, 'a');
+            let f = Foo { a: 1 };
+            let g = f.b;
+            let h = 1 + 2.0;
+        }
+        "#,
+    )?;
+
+    let mut it = errors.into_iter();
+
+    assert!(matches!(
+        it.next(),
+        Some(TypeMismatch { expected, actual }) if &*expected == "String" && &*actual == "i64"
+    ));
+    assert!(matches!(
+        it.next(),
+        Some(UnsupportedArgumentCount {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    assert!(matches!(
+        it.next(),
+        Some(TypeMismatch { expected, actual }) if &*expected == "i64" && &*actual == "String"
+    ));
+    assert!(matches!(
+        it.next(),
+        Some(UnsupportedArgumentCount {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    assert!(matches!(
+        it.next(),
+        Some(TypeMismatch { expected, actual }) if &*expected == "u64" && &*actual == "char"
+    ));
+    assert!(matches!(
+        it.next(),
+        Some(UnknownField { field, .. }) if &*field == "b"
+    ));
+    assert!(matches!(
+        it.next(),
+        Some(TypeMismatch { expected, actual }) if &*expected == "i64" && &*actual == "f64"
+    ));
+    assert!(matches!(
+        it.next(),
+        Some(TypeMismatch { expected, actual }) if &*expected == "String" && &*actual == "f64"
+    ));
+    assert!(it.next().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_unknown_type() {
+    assert_errors! {
+        r#"fn foo(a: Missing) {}"#,
+        span!(10, 17),
+        MissingItem { .. }
+    };
+
+    assert_errors! {
+        r#"const N = 1; fn foo() -> N {}"#,
+        span!(25, 26),
+        ExpectedMeta { expected: "a type", .. }
+    };
+}
@@ -0,0 +1,274 @@
+prelude!();
+
+use VmErrorKind::*;
+
+#[test]
+fn test_unsigned_fns() {
+    let n: u64 = rune! {
+        pub fn main() {
+            1u64.min(2u64)
+        }
+    };
+    assert_eq!(n, 1);
+
+    let n: u64 = rune! {
+        pub fn main() {
+            std::u64::max(1u64, 2u64)
+        }
+    };
+    assert_eq!(n, 2);
+
+    let n: u64 = rune! {
+        pub fn main() {
+            u64::MAX.wrapping_add(2u64)
+        }
+    };
+    assert_eq!(n, 1);
+
+    let n: Option<u64> = rune! {
+        pub fn main() {
+            0u64.checked_sub(1u64)
+        }
+    };
+    assert_eq!(n, None);
+
+    let n: u64 = rune! {
+        pub fn main() {
+            u64::parse("18446744073709551615")?
+        }
+    };
+    assert_eq!(n, u64::MAX);
+}
+
+#[test]
+fn test_unsigned_as() {
+    let n: u64 = rune! {
+        pub fn main() {
+            42 as u64
+        }
+    };
+    assert_eq!(n, 42);
+
+    let n: u64 = rune! {
+        pub fn main() {
+            -1 as u64
+        }
+    };
+    assert_eq!(n, u64::MAX);
+
+    let n: i64 = rune! {
+        pub fn main() {
+            u64::MAX as i64
+        }
+    };
+    assert_eq!(n, -1);
+
+    let n: f64 = rune! {
+        pub fn main() {
+            4u64 as f64
+        }
+    };
+    assert_eq!(n, 4.0);
+
+    let n: u8 = rune! {
+        pub fn main() {
+            257u64 as u8
+        }
+    };
+    assert_eq!(n, 1);
+}
+
+#[test]
+fn test_unsigned_values() {
+    let out: String = rune! {
+        pub fn main() {
+            let n = 18446744073709551615u64;
+            format!("{n} {n:x} {n:?} {}", 10u64 == 10u64)
+        }
+    };
+    assert_eq!(
+        out,
+        "18446744073709551615 ffffffffffffffff 18446744073709551615 true"
+    );
+
+    let out: bool = rune! {
+        pub fn main() {
+            match 2u64 {
+                1u64 => false,
+                2u64 => true,
+                _ => false,
+            }
+        }
+    };
+    assert!(out);
+
+    let out: u64 = rune! {
+        const N = 1u64 << 63;
+
+        pub fn main() {
+            N - 1u64 + N
+        }
+    };
+    assert_eq!(out, u64::MAX);
+
+    let out: u64 = rune! {
+        pub fn main() {
+            let sum = 0u64;
+
+            for n in 1u64..=4u64 {
+                sum += n;
+            }
+
+            sum
+        }
+    };
+    assert_eq!(out, 10);
+
+    let out: bool = rune! {
+        pub fn main() {
+            1u64 < 2u64 && 1u64 is u64 && !(1 is u64)
+        }
+    };
+    assert!(out);
+}
+
+#[test]
+fn test_unsigned_conversions() {
+    let value = rune::to_value(u64::MAX).unwrap();
+    assert_eq!(rune::from_value::<u64>(value.clone()).unwrap(), u64::MAX);
+    assert!(rune::from_value::<i64>(value.clone()).is_err());
+    assert!(rune::from_value::<u32>(value).is_err());
+
+    // Signed integers can still be passed where unsigned ones are expected.
+    let value = rune::to_value(42i64).unwrap();
+    assert_eq!(rune::from_value::<u64>(value).unwrap(), 42);
+
+    let value = rune::to_value(-1i64).unwrap();
+    assert!(rune::from_value::<u64>(value).is_err());
+}
+
+#[test]
+fn test_unsigned_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { -(1u64) }"#,
+        UnsupportedUnaryOperation { op: "-", .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { 1 - 2u64 }"#,
+        Underflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { -1 + 1u64 }"#,
+        IntegerWideningError { op: "add", .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { -4 / 2u64 }"#,
+        IntegerWideningError { op: "div", .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { -4 % 2u64 }"#,
+        IntegerWideningError { op: "rem", .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { 4 / 0u64 }"#,
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = -4; n %= 2u64; n }"#,
+        IntegerWideningError { op: "rem_assign", .. } => {}
+    );
+}
+
+#[test]
+fn test_unsigned_mixed() {
+    let out: (u64, u64, bool, bool) = rune! {
+        pub fn main() {
+            let n = 1u64;
+            n += 1;
+            (1u64 + 1, n, (2 * 1u64) is u64, 1u64 == 1 && -1 < 1u64)
+        }
+    };
+    assert_eq!(out, (2, 2, true, true));
+
+    let out: Option<String> = rune! {
+        pub fn main() {
+            let keys = std::collections::HashMap::new();
+            keys.insert(1, "one");
+            keys.get(1u64)
+        }
+    };
+    assert_eq!(out.as_deref(), Some("one"));
+}
+
+#[test]
+fn test_unsigned_mixed_div_rem() {
+    let out: (u64, u64, u64, u64) = rune! {
+        pub fn main() {
+            let n = 7;
+            n /= 2u64;
+            (9 / 2u64, 9u64 % 4, u64::MAX / 2, n)
+        }
+    };
+    assert_eq!(out, (4, 1, u64::MAX / 2, 3));
+}
+
+#[test]
+fn test_unsigned_host_boundary() -> Result<()> {
+    let mut module = Module::new();
+    module.function("below", || i64::MAX as u64).build()?;
+    module.function("above", || i64::MAX as u64 + 1).build()?;
+    module.function("id", |value: u64| value).build()?;
+    module.function("len", || 3usize).build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    // Native u64 values are always u64, on either side of 2^63, and integers
+    // mixed with them are widened into u64.
+    let out: (bool, bool, bool, u64) = run(
+        &context,
+        r#"
+        pub fn main() {
+            (
+                below() is u64 && above() is u64,
+                below() + 1 == above(),
+                above() == 9223372036854775808u64,
+                above() - 1,
+            )
+        }
+        "#,
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (true, true, true, i64::MAX as u64));
+
+    // Native functions taking a u64 accept non-negative integers, and mixing
+    // the result with integers widens them into u64.
+    let out: (u64, i64, u64, bool) = run(
+        &context,
+        "pub fn main() { (id(41), below() as i64, id(1) + 1, id(41) == 41) }",
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (41, i64::MAX, 2, true));
+
+    // Native usize values are plain integers, since they're used for sizes and
+    // indexes.
+    let out: (i64, bool) = run(
+        &context,
+        "pub fn main() { (len() + 1, len() is i64) }",
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (4, true));
+    Ok(())
+}
@@ -172,3 +172,44 @@ fn test_bitwise_not_u8() {
     );
     assert_eq!(out, !0b10100u8);
 }
+
+#[test]
+fn test_ops_u64() {
+    op_tests!(u64, 10u64 + 2u64 = 12);
+    op_tests!(u64, 10u64 - 2u64 = 8);
+    op_tests!(u64, 10u64 * 2u64 = 20);
+    op_tests!(u64, 10u64 / 2u64 = 5);
+    op_tests!(u64, 10u64 % 3u64 = 1);
+    op_tests!(u64, 18446744073709551614u64 + 1u64 = u64::MAX);
+    error_test!(18446744073709551615u64 + 1u64 = Overflow);
+    error_test!(0u64 - 1u64 = Underflow);
+    error_test!(18446744073709551615u64 * 2u64 = Overflow);
+    error_test!(10u64 / 0u64 = DivideByZero);
+    error_test!(10u64 % 0u64 = DivideByZero);
+
+    // Integers are widened into u64 when mixed with them.
+    op_tests!(u64, 10u64 + 1 = 11);
+    error_test!(1 - 2u64 = Underflow);
+}
+
+#[test]
+fn test_bit_ops_u64() {
+    op_tests!(u64, 0b1100u64 & 0b0110u64 = 0b1100u64 & 0b0110u64);
+    op_tests!(u64, 0b1100u64 ^ 0b0110u64 = 0b1100u64 ^ 0b0110u64);
+    op_tests!(u64, 0b1100u64 | 0b0110u64 = 0b1100u64 | 0b0110u64);
+    op_tests!(u64, 0b1100u64 << 2 = 0b1100u64 << 2);
+    op_tests!(u64, 0b1100u64 >> 2 = 0b1100u64 >> 2);
+    error_test!(0b1u64 << 64 = Overflow);
+    error_test!(0b1u64 >> 64 = Underflow);
+}
+
+#[test]
+fn test_bitwise_not_u64() {
+    let out: u64 = rune!(
+        pub fn main() {
+            let a = 0b10100u64;
+            !a
+        }
+    );
+    assert_eq!(out, !0b10100u64);
+}
@@ -1,5 +1,7 @@
 prelude!();
 
+use std::cell::Cell;
+
 macro_rules! test_op {
     ($ty:ty => $lhs:literal $op:tt $rhs:literal = $result:literal) => {{
         let program = format!(
@@ -270,3 +272,214 @@ fn test_const_block() {
 
     assert_eq!(result, "Hello World");
 }
+
+#[test]
+fn test_const_structs() {
+    let result: (i64, i64, i64, bool) = rune! {
+        struct Point { x, y }
+        struct Pair(a, b);
+        struct Unit;
+
+        const POINT = Point { x: 1, y: SCALE * 2 };
+        const PAIR = swap(Pair(3, POINT_X));
+        const POINT_X = next_x(origin());
+        const UNIT = Unit;
+        const SCALE = 2;
+
+        const fn origin() {
+            Point { x: 0, y: 0 }
+        }
+
+        const fn next_x(point) {
+            point.x + 1
+        }
+
+        const fn swap(pair) {
+            Pair(pair.1, pair.0)
+        }
+
+        pub fn main() {
+            (POINT.x + POINT.y, PAIR.0 + PAIR.1, POINT.y, UNIT is Unit)
+        }
+    };
+
+    assert_eq!(result, (5, 4, 4, true));
+}
+
+#[test]
+fn test_const_variants() {
+    let result: (i64, i64, i64) = rune! {
+        enum Shape {
+            Empty,
+            Circle(radius),
+            Rect { w, h },
+        }
+
+        const EMPTY = Shape::Empty;
+        const CIRCLE = Shape::Circle(2);
+        const RECT = Shape::Rect { w: 3, h: 4 };
+        const SHAPES = [EMPTY, CIRCLE, RECT];
+
+        fn area(shape) {
+            match shape {
+                Shape::Empty => 0,
+                Shape::Circle(r) => r * r * 3,
+                Shape::Rect { w, h } => w * h,
+            }
+        }
+
+        pub fn main() {
+            let total = 0;
+
+            for shape in SHAPES {
+                total += area(shape);
+            }
+
+            (total, area(CIRCLE), area(RECT))
+        }
+    };
+
+    assert_eq!(result, (24, 12, 12));
+}
+
+thread_local! {
+    /// Calls made to the native `add` function on the current thread.
+    static ADD_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A native type which can't be stored in a constant.
+#[derive(Any)]
+struct Timeout {
+    secs: i64,
+}
+
+fn native_context() -> Result<Context> {
+    #[rune::function(const)]
+    fn add(a: i64, b: i64) -> i64 {
+        ADD_CALLS.with(|calls| calls.set(calls.get() + 1));
+        a + b
+    }
+
+    #[rune::function]
+    fn sub(a: i64, b: i64) -> i64 {
+        a - b
+    }
+
+    #[rune::function(const)]
+    fn timeout(secs: i64) -> Timeout {
+        Timeout { secs }
+    }
+
+    #[rune::function(const)]
+    fn timeout_secs(timeout: &Timeout) -> i64 {
+        timeout.secs
+    }
+
+    let mut module = Module::new();
+    module.ty::<Timeout>()?;
+    module.function_meta(add)?;
+    module.function_meta(sub)?;
+    module.function_meta(timeout)?;
+    module.function_meta(timeout_secs)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    Ok(context)
+}
+
+/// Build the given source, returning the first fatal diagnostic.
+fn build_error(context: &Context, source: &str) -> String {
+    let mut sources = crate::tests::sources(source);
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_context(context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    diagnostics
+        .diagnostics()
+        .iter()
+        .find_map(|d| match d {
+            crate::diagnostics::Diagnostic::Fatal(e) => Some(e.to_string()),
+            _ => None,
+        })
+        .expect("expected a fatal diagnostic")
+}
+
+#[test]
+fn test_const_native_fn() -> Result<()> {
+    let context = native_context()?;
+    let calls = ADD_CALLS.with(Cell::get);
+
+    let mut sources =
+        crate::tests::sources("const VALUE = add(1, add(2, 3)); pub fn main() { VALUE + VALUE }");
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    assert_eq!(ADD_CALLS.with(Cell::get) - calls, 2);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    let result: i64 = crate::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(result, 12);
+
+    // The function is only called while compiling.
+    assert_eq!(ADD_CALLS.with(Cell::get) - calls, 2);
+    Ok(())
+}
+
+#[test]
+fn test_const_native_any() -> Result<()> {
+    let context = native_context()?;
+
+    // Native values can be passed between constant functions, as long as the
+    // final value of the constant can be stored.
+    let mut sources =
+        crate::tests::sources("const SECS = timeout_secs(timeout(30)); pub fn main() { SECS }");
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    let result: i64 = crate::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(result, 30);
+
+    let error = build_error(
+        &context,
+        "const TIMEOUT = timeout(30); pub fn main() { TIMEOUT }",
+    );
+
+    assert!(
+        error.contains("can't be converted to a constant value"),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_const_std_fns() {
+    let result: (i64, bool, bool, bool) = rune! {
+        const MAX = i64::max(i64::pow(2, 10), 100);
+        const DIV = i64::checked_div(MAX, 0);
+        const SQRT = f64::sqrt(f64::abs(-16.0));
+        const CHAR = char::from_i64(97);
+        const ALPHA = char::is_alphabetic('a');
+
+        pub fn main() {
+            (MAX, DIV.is_none(), SQRT == 4.0, CHAR == Some('a') && ALPHA)
+        }
+    };
+
+    assert_eq!(result, (1024, true, true, true));
+}
+
+#[test]
+fn test_const_native_fn_not_const() -> Result<()> {
+    let context = native_context()?;
+    let error = build_error(&context, "const VALUE = sub(1, 2); pub fn main() { VALUE }");
+
+    assert!(
+        error.contains("Call not supported in constant contexts"),
+        "{error}"
+    );
+    Ok(())
+}
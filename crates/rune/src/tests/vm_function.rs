@@ -71,3 +71,31 @@ fn test_function() {
     let value: Value = function.call(()).unwrap();
     assert!(matches!(value.take_kind().unwrap(), ValueKind::Integer(3)));
 }
+
+#[test]
+fn test_function_from_other_unit() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime()?);
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a) { |b| a + b }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(runtime.clone(), Arc::new(unit));
+    let function = vm.call(["main"], (1i64,))?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(f) { f(2) + f(3) }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(runtime, Arc::new(unit));
+    let output: i64 = from_value(vm.call(["main"], (function,))?)?;
+    assert_eq!(output, 7);
+    Ok(())
+}
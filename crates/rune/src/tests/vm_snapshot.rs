@@ -0,0 +1,204 @@
+#![cfg(feature = "byte-code")]
+
+prelude!();
+
+use crate::runtime::{Generator, GeneratorState, RuntimeContext, VmExecution, VmSnapshot};
+use crate::Unit;
+
+fn build(mut sources: Sources) -> Result<(Arc<RuntimeContext>, Arc<Unit>)> {
+    let context = Context::with_default_modules()?;
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok((Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+fn roundtrip(snapshot: VmSnapshot) -> Result<VmSnapshot> {
+    let bytes = snapshot.to_bytes()?;
+    Ok(VmSnapshot::from_bytes(&bytes)?)
+}
+
+#[test]
+fn test_generator_snapshot() -> Result<()> {
+    let (context, unit) = build(sources! {
+        entry => {
+            struct Counter { total, seen }
+
+            enum Step { Add(n), Skip }
+
+            fn count() {
+                let counter = Counter { total: 0, seen: [] };
+                let seen = counter.seen;
+
+                loop {
+                    let step = yield (counter.total, seen.len());
+
+                    if let Step::Add(n) = step {
+                        counter.total += n;
+                        counter.seen.push(n);
+                    }
+                }
+            }
+
+            pub fn main() {
+                count()
+            }
+
+            pub fn add(n) {
+                Step::Add(n)
+            }
+
+            pub fn skip() {
+                Step::Skip
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    let mut generator: Generator<Vm> = from_value(vm.call(["main"], ())?)?;
+
+    let a = vm.call(["add"], (1i64,))?;
+    let b = vm.call(["add"], (2i64,))?;
+
+    generator.resume(Value::empty()?).into_result()?;
+    generator.resume(a).into_result()?;
+    generator.resume(b).into_result()?;
+
+    let snapshot = roundtrip(generator.snapshot()?)?;
+    drop(generator);
+
+    let mut generator = Generator::restore(context.clone(), unit.clone(), &snapshot)?;
+
+    let c = vm.call(["add"], (3i64,))?;
+
+    let GeneratorState::Yielded(value) = generator.resume(c).into_result()? else {
+        panic!("expected generator to yield");
+    };
+
+    // The vector is shared between the struct and a local, which must survive
+    // the snapshot.
+    let (total, seen): (i64, usize) = from_value(value)?;
+    assert_eq!(total, 6);
+    assert_eq!(seen, 3);
+
+    // The same snapshot can be restored more than once.
+    let mut generator = Generator::restore(context, unit, &snapshot)?;
+    let skip = vm.call(["skip"], ())?;
+
+    let GeneratorState::Yielded(value) = generator.resume(skip).into_result()? else {
+        panic!("expected generator to yield");
+    };
+
+    let (total, seen): (i64, usize) = from_value(value)?;
+    assert_eq!(total, 3);
+    assert_eq!(seen, 2);
+    Ok(())
+}
+
+#[test]
+fn test_execution_snapshot() -> Result<()> {
+    let (context, unit) = build(sources! {
+        entry => {
+            pub fn main(a) {
+                let out = #{ a: a.clone(), b: Some(1..=2), c: Ok('c'), d: (b"bytes", 2u64, 1.5) };
+                let n = yield a;
+                out.b = n;
+                out
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.execute(["main"], (String::from("hello"),))?;
+
+    let GeneratorState::Yielded(value) = execution.resume().into_result()? else {
+        panic!("expected execution to yield");
+    };
+
+    // Taking the yielded value moves it out of the stack of the execution,
+    // which must not prevent a snapshot from being taken.
+    assert_eq!(from_value::<String>(value)?, "hello");
+
+    let snapshot = roundtrip(execution.snapshot()?)?;
+    drop(execution);
+
+    let mut execution = VmExecution::restore(context, unit, &snapshot)?;
+
+    let GeneratorState::Complete(value) = execution
+        .resume_with(rune::to_value(42i64)?)
+        .into_result()?
+    else {
+        panic!("expected execution to complete");
+    };
+
+    let object: Object = from_value(value)?;
+    assert_eq!(
+        from_value::<String>(object.get("a").unwrap().clone())?,
+        "hello"
+    );
+    assert_eq!(from_value::<i64>(object.get("b").unwrap().clone())?, 42);
+
+    let c: Result<char, char> = from_value(object.get("c").unwrap().clone())?;
+    assert_eq!(c, Ok('c'));
+
+    let (bytes, n, f): (Bytes, u64, f64) = from_value(object.get("d").unwrap().clone())?;
+    assert_eq!(bytes, b"bytes"[..]);
+    assert_eq!(n, 2);
+    assert_eq!(f, 1.5);
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_errors() -> Result<()> {
+    let (context, unit) = build(sources! {
+        entry => {
+            fn future() {
+                let f = async { 1 };
+                yield;
+                f
+            }
+
+            pub fn main() {
+                future()
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    let mut generator: Generator<Vm> = from_value(vm.call(["main"], ())?)?;
+    generator.next().into_result()?;
+
+    let error = generator.snapshot().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Value of type `Future` cannot be serialized"
+    );
+
+    let (_, other) = build(sources! {
+        entry => {
+            fn once() {
+                yield 1;
+            }
+
+            pub fn main() {
+                once()
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(context.clone(), other.clone());
+    let mut generator: Generator<Vm> = from_value(vm.call(["main"], ())?)?;
+    let snapshot = generator.snapshot()?;
+
+    let error = Generator::restore(context.clone(), unit, &snapshot).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("Snapshot was taken from unit"));
+
+    while generator.next().into_result()?.is_some() {}
+
+    let error = generator.snapshot().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Completed generator cannot be snapshotted"
+    );
+    Ok(())
+}
@@ -1,14 +1,15 @@
 //! Worker used by compiler.
 
 use crate::alloc::prelude::*;
-use crate::alloc::{self, Box, HashMap, Vec, VecDeque};
-use crate::ast::{self, Span};
-use crate::compile::{self, ModId};
+use crate::alloc::{self, Box, HashMap, HashSet, Vec, VecDeque};
+use crate::ast::{self, Span, Spanned};
+use crate::compile::{self, meta, ComponentRef, Location, ModId, ParseCache};
 use crate::indexing::index;
 use crate::indexing::items::Items;
 use crate::indexing::{IndexItem, Indexer, Scopes};
-use crate::query::{GenericsParameters, Query, Used};
-use crate::SourceId;
+use crate::parse::Resolve;
+use crate::query::{GenericsParameters, Query, QueryImplFn, QueryTraitDefaults, Used};
+use crate::{Hash, SourceId};
 
 mod import;
 mod task;
@@ -25,15 +26,18 @@ pub(crate) struct Worker<'a, 'arena> {
     pub(crate) loaded: HashMap<ModId, (SourceId, Span)>,
     /// Worker queue.
     pub(crate) queue: VecDeque<Task>,
+    /// Files parsed by earlier builds.
+    parse_cache: Option<&'a mut ParseCache>,
 }
 
 impl<'a, 'arena> Worker<'a, 'arena> {
     /// Construct a new worker.
-    pub(crate) fn new(q: Query<'a, 'arena>) -> Self {
+    pub(crate) fn new(q: Query<'a, 'arena>, parse_cache: Option<&'a mut ParseCache>) -> Self {
         Self {
             q,
             loaded: HashMap::new(),
             queue: VecDeque::new(),
+            parse_cache,
         }
     }
 
@@ -109,11 +113,14 @@ impl<'a, 'arena> Worker<'a, 'arena> {
 
                                 index::empty_block_fn(&mut idx, ast, &span)?;
                             } else {
-                                let mut ast = crate::parse::parse_all::<ast::File>(
-                                    source.as_str(),
-                                    source_id,
-                                    true,
-                                )?;
+                                let mut ast = match &mut self.parse_cache {
+                                    Some(cache) => cache.parse(source_id, source)?,
+                                    None => crate::parse::parse_all::<ast::File>(
+                                        source.as_str(),
+                                        source_id,
+                                        true,
+                                    )?,
+                                };
 
                                 let mut idx = indexer!();
 
@@ -200,8 +207,36 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                         .items
                         .try_insert(meta.item_meta.id, meta.item_meta)?;
 
-                    let item = self.q.pool.item(meta.item_meta.item);
-                    let items = Items::new(item, meta.item_meta.id, self.q.gen)?;
+                    let removed = self
+                        .q
+                        .inner
+                        .impl_functions
+                        .remove(&entry.id)
+                        .unwrap_or_default();
+
+                    let mut item = self.q.pool.item(meta.item_meta.item).try_to_owned()?;
+
+                    let (impl_trait, defaults) = match &entry.trait_ {
+                        Some(trait_) => {
+                            let trait_impl = self.impl_trait(
+                                &entry.location,
+                                &entry.path,
+                                trait_,
+                                &meta,
+                                &removed,
+                            )?;
+
+                            // Functions implementing a trait are indexed in
+                            // an item which is unique to the implementation,
+                            // so that traits with functions of the same name
+                            // don't conflict.
+                            item.push(ComponentRef::Id(trait_impl.index))?;
+                            (Some(trait_impl.hash), trait_impl.defaults)
+                        }
+                        None => (None, None),
+                    };
+
+                    let items = Items::new(&item, meta.item_meta.id, self.q.gen)?;
 
                     let mut idx = Indexer {
                         q: self.q.borrow(),
@@ -209,24 +244,49 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                         source_id: entry.location.source_id,
                         items,
                         scopes: Scopes::new()?,
-                        item: IndexItem::with_impl_item(named.module, meta.item_meta.id),
+                        item: IndexItem::with_impl_item(
+                            named.module,
+                            meta.item_meta.id,
+                            impl_trait,
+                        ),
                         nested_item: entry.nested_item,
                         macro_depth: entry.macro_depth,
                         loaded: Some(&mut self.loaded),
                         queue: Some(&mut self.queue),
                     };
 
-                    let removed = idx
-                        .q
-                        .inner
-                        .impl_functions
-                        .remove(&entry.id)
-                        .unwrap_or_default();
-
                     for f in removed {
                         index::item_fn_immediate(&mut idx, Box::into_inner(f.ast))?;
                     }
 
+                    // Default functions are indexed as if they were declared
+                    // in the implementation, but resolve names from where the
+                    // trait is declared.
+                    if let Some(defaults) = defaults {
+                        let items = Items::new(&item, meta.item_meta.id, self.q.gen)?;
+
+                        let mut idx = Indexer {
+                            q: self.q.borrow(),
+                            root: defaults.root,
+                            source_id: defaults.source_id,
+                            items,
+                            scopes: Scopes::new()?,
+                            item: IndexItem::with_impl_item(
+                                defaults.module,
+                                meta.item_meta.id,
+                                impl_trait,
+                            ),
+                            nested_item: entry.nested_item,
+                            macro_depth: entry.macro_depth,
+                            loaded: Some(&mut self.loaded),
+                            queue: Some(&mut self.queue),
+                        };
+
+                        for f in defaults.functions {
+                            index::item_fn_immediate(&mut idx, f)?;
+                        }
+                    }
+
                     Ok::<_, compile::Error>(())
                 };
 
@@ -236,10 +296,150 @@ impl<'a, 'arena> Worker<'a, 'arena> {
             }
         }
 
+        if let Some(cache) = &mut self.parse_cache {
+            cache.evict_unused();
+        }
+
         Ok(())
     }
 }
 
+impl Worker<'_, '_> {
+    /// Check an implementation of a trait for the type described by `meta`
+    /// and record it in the unit.
+    ///
+    /// Returns the default functions of the trait which are not provided by
+    /// the implementation as part of the [`TraitImpl`].
+    fn impl_trait(
+        &mut self,
+        location: &Location,
+        path: &ast::Path,
+        trait_: &ast::Path,
+        meta: &meta::Meta,
+        functions: &[QueryImplFn],
+    ) -> compile::Result<TraitImpl> {
+        let named = self
+            .q
+            .convert_path_with(trait_, true, Used::Used, Used::Unused)?;
+
+        if let Some((spanned, _)) = named.parameters.into_iter().flatten().next() {
+            return Err(compile::Error::new(
+                spanned.span(),
+                compile::ErrorKind::UnsupportedGenerics,
+            ));
+        }
+
+        let location = Location::new(location.source_id, trait_.span());
+
+        let trait_meta =
+            self.q
+                .lookup_meta(&location, named.item, GenericsParameters::default())?;
+
+        let meta::Kind::Trait {
+            functions: trait_functions,
+        } = &trait_meta.kind
+        else {
+            return Err(compile::Error::expected_meta(
+                trait_,
+                trait_meta.info(self.q.pool)?,
+                "a trait",
+            ));
+        };
+
+        let Some(type_hash) = meta.type_hash_of() else {
+            return Err(compile::Error::expected_meta(
+                path,
+                meta.info(self.q.pool)?,
+                "a type",
+            ));
+        };
+
+        let trait_item = || self.q.pool.item(trait_meta.item_meta.item).try_to_owned();
+
+        let mut provided = HashSet::new();
+
+        for f in functions {
+            let name = f.ast.name.resolve(resolve_context!(self.q))?;
+
+            if !trait_functions.iter().any(|f| f.name.as_ref() == name) {
+                return Err(compile::Error::new(
+                    f.ast.descriptive_span(),
+                    compile::ErrorKind::UnknownTraitFunction {
+                        trait_item: trait_item()?,
+                        name: name.try_into()?,
+                    },
+                ));
+            }
+
+            provided.try_insert(name)?;
+        }
+
+        for f in trait_functions {
+            if !f.has_default && !provided.contains(f.name.as_ref()) {
+                return Err(compile::Error::new(
+                    trait_,
+                    compile::ErrorKind::MissingTraitFunction {
+                        trait_item: trait_item()?,
+                        name: f.name.try_clone()?,
+                    },
+                ));
+            }
+        }
+
+        let Some(index) = self.q.unit.insert_trait_impl(type_hash, trait_meta.hash)? else {
+            return Err(compile::Error::new(
+                trait_,
+                compile::ErrorKind::ConflictingTraitImpl {
+                    item: self.q.pool.item(meta.item_meta.item).try_to_owned()?,
+                    trait_item: trait_item()?,
+                },
+            ));
+        };
+
+        let Some(defaults) = self.q.inner.trait_defaults.get(&trait_meta.item_meta.item) else {
+            return Ok(TraitImpl {
+                hash: trait_meta.hash,
+                index,
+                defaults: None,
+            });
+        };
+
+        let mut functions = Vec::new();
+
+        for f in &defaults.functions {
+            let name = f.name.resolve(resolve_context!(self.q))?;
+
+            if !provided.contains(name) {
+                functions.try_push(f.try_clone()?)?;
+            }
+        }
+
+        let defaults = QueryTraitDefaults {
+            module: defaults.module,
+            source_id: defaults.source_id,
+            root: defaults.root.try_clone()?,
+            functions,
+        };
+
+        Ok(TraitImpl {
+            hash: trait_meta.hash,
+            index,
+            defaults: Some(defaults),
+        })
+    }
+}
+
+/// A checked implementation of a trait.
+struct TraitImpl {
+    /// The hash of the trait being implemented.
+    hash: Hash,
+    /// The index of the implementation among the traits implemented by the
+    /// type.
+    index: usize,
+    /// Default functions which are not provided by the implementation.
+    defaults: Option<QueryTraitDefaults>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum ImportKind {
     /// The import is in-place.
@@ -15,6 +15,7 @@ use serde_hashkey as key;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, String, Vec};
 use crate::ast::{Span, Spanned};
+use crate::diagnostics::{Lint, LintLevel};
 use crate::workspace::spanned_value::{Array, SpannedValue, Table, Value};
 use crate::workspace::{
     glob, Diagnostics, SourceLoader, WorkspaceError, WorkspaceErrorKind, MANIFEST_FILE,
@@ -109,6 +110,9 @@ impl<T> Spanned for toml::Spanned<T> {
 pub struct Manifest {
     /// List of packages found.
     pub packages: Vec<Package>,
+    /// Formatting options from the `[format]` section of the root manifest.
+    #[cfg(feature = "fmt")]
+    pub format: Option<crate::fmt::FormatOptions>,
 }
 
 impl Manifest {
@@ -181,6 +185,9 @@ pub struct Package {
     pub auto_examples: bool,
     /// Automatically detect benches.
     pub auto_benches: bool,
+    /// Lint levels configured through the `[lints]` table of the manifest, or
+    /// inherited from the workspace the package is a member of.
+    pub lints: Vec<(Lint, LintLevel)>,
 }
 
 impl Package {
@@ -248,6 +255,9 @@ pub(crate) struct Loader<'a> {
     diagnostics: &'a mut Diagnostics,
     source_loader: &'a mut dyn SourceLoader,
     manifest: &'a mut Manifest,
+    /// Lints configured by the current manifest and the manifests it is a
+    /// member of.
+    lints: Vec<(Lint, LintLevel)>,
 }
 
 impl<'a> Loader<'a> {
@@ -264,6 +274,7 @@ impl<'a> Loader<'a> {
             diagnostics,
             source_loader,
             manifest,
+            lints: Vec::new(),
         }
     }
 
@@ -300,6 +311,25 @@ impl<'a> Loader<'a> {
             return Ok(());
         };
 
+        // Lints apply to the package in this manifest and to all members, so
+        // they need to be loaded first.
+        let inherited = self.lints.try_clone()?;
+
+        if let Some((lints, _)) = table
+            .remove("lints")
+            .map(|value| self.ensure_table(value))
+            .transpose()?
+            .flatten()
+        {
+            self.load_lints(lints)?;
+        }
+
+        // Formatting options only apply to the workspace as a whole, so
+        // options from the root manifest take precedence over members.
+        if let Some(format) = table.remove("format") {
+            self.load_format(format)?;
+        }
+
         // If manifest is a package, add it here.
         if let Some((package, span)) = table
             .remove("package")
@@ -338,10 +368,70 @@ impl<'a> Loader<'a> {
             self.ensure_empty(table)?;
         }
 
+        self.lints = inherited;
         self.ensure_empty(table)?;
         Ok(())
     }
 
+    /// Load the `[lints]` table, where each key is the name of a lint and each
+    /// value is the level to use for it.
+    fn load_lints(&mut self, table: Table) -> alloc::Result<()> {
+        for (key, value) in table {
+            let Some(lint) = Lint::from_name(key.get_ref().as_str()) else {
+                self.fatal(WorkspaceError::new(
+                    Spanned::span(&key),
+                    WorkspaceErrorKind::UnknownLint {
+                        name: key.get_ref().as_str().try_into()?,
+                    },
+                ))?;
+                continue;
+            };
+
+            let span = Spanned::span(&value);
+
+            let level = match deserialize::<std::string::String>(value) {
+                Ok(level) => level,
+                Err(error) => {
+                    self.fatal(error)?;
+                    continue;
+                }
+            };
+
+            let Some(level) = LintLevel::from_name(&level) else {
+                self.fatal(WorkspaceError::new(
+                    span,
+                    WorkspaceErrorKind::UnknownLintLevel {
+                        level: level.as_str().try_into()?,
+                    },
+                ))?;
+                continue;
+            };
+
+            self.lints.try_push((lint, level))?;
+        }
+
+        Ok(())
+    }
+
+    /// Load the `[format]` table, which is ignored if the formatter is not
+    /// enabled.
+    #[cfg_attr(not(feature = "fmt"), allow(unused_variables))]
+    fn load_format(&mut self, value: SpannedValue) -> alloc::Result<()> {
+        #[cfg(feature = "fmt")]
+        match deserialize(value) {
+            Ok(format) => {
+                if self.manifest.format.is_none() {
+                    self.manifest.format = Some(format);
+                }
+            }
+            Err(error) => {
+                self.fatal(error)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Load members from the given workspace configuration.
     fn load_members(
         &mut self,
@@ -467,6 +557,7 @@ impl<'a> Loader<'a> {
             auto_tests: true,
             auto_examples: true,
             auto_benches: true,
+            lints: self.lints.try_clone()?,
         }))
     }
 
@@ -28,6 +28,9 @@ export function createClient(
         documentSelector: [{ scheme: 'file', language: 'rune' }, { pattern: '**/Rune.toml', language: 'toml' }],
         traceOutputChannel: traceOutputChannel(),
         outputChannel: outputChannel(),
+        synchronize: {
+            fileEvents: workspace.createFileSystemWatcher('**/*.rn'),
+        },
     };
 
     const client = new lc.LanguageClient(
@@ -217,9 +217,15 @@ fn main() -> Result<()> {
                 ('}') => {
                     $$crate::ast::CloseBrace
                 };
+                (is not impl) => {
+                    $$crate::ast::IsNotImpl
+                };
                 (is not) => {
                     $$crate::ast::IsNot
                 };
+                (is impl) => {
+                    $$crate::ast::IsImpl
+                };
                 $(for k in &keywords join($['\r']) =>
                     ($(&k.keyword)) => {
                         $$crate::ast::$(&k.variant)